@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use anyhow::{Context, Error, Result};
 use url::Url;
 
+use distribution_filename::SourceDistExtension;
 use uv_git::{GitSha, GitUrl};
 
 #[derive(Debug)]
@@ -19,12 +20,30 @@ pub enum DirectUrl {
 ///
 /// Examples:
 /// * `file:///home/ferris/my_project`
+/// * `file:///home/ferris/my_project-0.1.0-py3-none-any.whl`
 #[derive(Debug)]
 pub struct LocalFileUrl {
     pub url: Url,
     pub editable: bool,
 }
 
+impl LocalFileUrl {
+    /// Returns `true` if the URL points to a wheel or source distribution archive, rather than a
+    /// directory.
+    pub fn is_archive(&self) -> bool {
+        if self.editable {
+            return false;
+        }
+        let Some(filename) = self.url.path_segments().and_then(Iterator::last) else {
+            return false;
+        };
+        std::path::Path::new(filename)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
+            || SourceDistExtension::from_filename(filename).is_some()
+    }
+}
+
 /// A git repository url
 ///
 /// Examples:
@@ -131,6 +150,17 @@ impl TryFrom<&LocalFileUrl> for pypi_types::DirectUrl {
     type Error = Error;
 
     fn try_from(value: &LocalFileUrl) -> Result<Self, Self::Error> {
+        // Per PEP 610, local archives are recorded with `archive_info`, not `dir_info`.
+        if value.is_archive() {
+            return Ok(Self::ArchiveUrl {
+                url: value.url.to_string(),
+                archive_info: pypi_types::ArchiveInfo {
+                    hash: None,
+                    hashes: None,
+                },
+                subdirectory: None,
+            });
+        }
         Ok(Self::LocalDirectory {
             url: value.url.to_string(),
             dir_info: pypi_types::DirInfo {
@@ -246,4 +276,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn direct_url_json_from_local_file() -> Result<()> {
+        let url = Url::parse("file:///path/to/directory")?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&url)?)?;
+        assert!(matches!(
+            direct_url,
+            pypi_types::DirectUrl::LocalDirectory { .. }
+        ));
+
+        let url = Url::parse("file:///path/to/foo-1.0.0-py3-none-any.whl")?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&url)?)?;
+        assert!(matches!(
+            direct_url,
+            pypi_types::DirectUrl::ArchiveUrl { .. }
+        ));
+
+        let url = Url::parse("file:///path/to/foo-1.0.0.tar.gz")?;
+        let direct_url = pypi_types::DirectUrl::try_from(&DirectUrl::try_from(&url)?)?;
+        assert!(matches!(
+            direct_url,
+            pypi_types::DirectUrl::ArchiveUrl { .. }
+        ));
+
+        Ok(())
+    }
 }
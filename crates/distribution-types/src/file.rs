@@ -28,6 +28,8 @@ pub struct File {
     pub dist_info_metadata: bool,
     pub filename: String,
     pub hashes: Vec<HashDigest>,
+    /// The absolute URL of the PEP 740 provenance object for the file, if the index provides one.
+    pub provenance: Option<String>,
     pub requires_python: Option<VersionSpecifiers>,
     pub size: Option<u64>,
     // N.B. We don't use a chrono DateTime<Utc> here because it's a little
@@ -44,11 +46,17 @@ impl File {
     pub fn try_from(file: pypi_types::File, base: &Url) -> Result<Self, FileConversionError> {
         Ok(Self {
             dist_info_metadata: file
-                .dist_info_metadata
+                .core_metadata
                 .as_ref()
+                .or(file.dist_info_metadata.as_ref())
                 .is_some_and(DistInfoMetadata::is_available),
             filename: file.filename,
             hashes: file.hashes.into_digests(),
+            provenance: file.provenance.and_then(|provenance| {
+                pypi_types::base_url_join_relative(base.as_str(), &provenance)
+                    .ok()
+                    .map(String::from)
+            }),
             requires_python: file
                 .requires_python
                 .transpose()
@@ -11,6 +11,7 @@ use url::Url;
 
 use pep508_rs::{expand_env_vars, split_scheme, strip_host, Scheme, VerbatimUrl};
 use uv_fs::normalize_url_path;
+use uv_normalize::{InvalidNameError, PackageName};
 
 use crate::Verbatim;
 
@@ -104,6 +105,40 @@ impl Deref for IndexUrl {
     }
 }
 
+/// A package that may only be fetched from a specific index, regardless of the other configured
+/// indexes and the index strategy.
+///
+/// Parsed from a `<package>=<url>` pair, as in `--index-pin torch=https://download.pytorch.org/whl/cpu`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct IndexPin {
+    pub package: PackageName,
+    pub index: IndexUrl,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IndexPinError {
+    #[error("Expected a `<package>=<url>` pair, but got: `{0}`")]
+    MissingSeparator(String),
+    #[error(transparent)]
+    InvalidName(#[from] InvalidNameError),
+    #[error(transparent)]
+    InvalidUrl(#[from] url::ParseError),
+}
+
+impl FromStr for IndexPin {
+    type Err = IndexPinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, index)) = s.split_once('=') else {
+            return Err(IndexPinError::MissingSeparator(s.to_string()));
+        };
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            index: IndexUrl::from_str(index.trim())?,
+        })
+    }
+}
+
 /// A directory with distributions or a URL to an HTML file with a flat listing of distributions.
 ///
 /// Also known as `--find-links`.
@@ -179,6 +214,7 @@ pub struct IndexLocations {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
     flat_index: Vec<FlatIndexLocation>,
+    pins: Vec<IndexPin>,
     no_index: bool,
 }
 
@@ -189,6 +225,7 @@ impl Default for IndexLocations {
             index: Some(DEFAULT_INDEX_URL.clone()),
             extra_index: Vec::new(),
             flat_index: Vec::new(),
+            pins: Vec::new(),
             no_index: false,
         }
     }
@@ -206,10 +243,18 @@ impl IndexLocations {
             index,
             extra_index,
             flat_index,
+            pins: Vec::new(),
             no_index,
         }
     }
 
+    /// Set the [`IndexPin`] entries, such that each of the given packages is only fetched from
+    /// its pinned index.
+    #[must_use]
+    pub fn with_pins(self, pins: Vec<IndexPin>) -> Self {
+        Self { pins, ..self }
+    }
+
     /// Combine a set of index locations.
     ///
     /// If either the current or the other index locations have `no_index` set, the result will
@@ -228,6 +273,7 @@ impl IndexLocations {
             index: self.index.or(index),
             extra_index: self.extra_index.into_iter().chain(extra_index).collect(),
             flat_index: self.flat_index.into_iter().chain(flat_index).collect(),
+            pins: self.pins,
             no_index: self.no_index || no_index,
         }
     }
@@ -274,6 +320,7 @@ impl<'a> IndexLocations {
         IndexUrls {
             index: self.index.clone(),
             extra_index: self.extra_index.clone(),
+            pins: self.pins.clone(),
             no_index: self.no_index,
         }
     }
@@ -282,6 +329,7 @@ impl<'a> IndexLocations {
     pub fn urls(&'a self) -> impl Iterator<Item = &'a Url> + 'a {
         self.indexes()
             .map(IndexUrl::url)
+            .chain(self.pins.iter().map(|pin| pin.index.url()))
             .chain(self.flat_index.iter().filter_map(|index| match index {
                 FlatIndexLocation::Path(_) => None,
                 FlatIndexLocation::Url(url) => Some(url),
@@ -296,6 +344,7 @@ impl<'a> IndexLocations {
 pub struct IndexUrls {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
+    pins: Vec<IndexPin>,
     no_index: bool,
 }
 
@@ -305,6 +354,7 @@ impl Default for IndexUrls {
         Self {
             index: Some(DEFAULT_INDEX_URL.clone()),
             extra_index: Vec::new(),
+            pins: Vec::new(),
             no_index: false,
         }
     }
@@ -345,6 +395,26 @@ impl<'a> IndexUrls {
     pub fn indexes(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
         self.extra_index().chain(self.index())
     }
+
+    /// Return an iterator over the [`IndexUrl`] entries to search for the given package, in
+    /// order.
+    ///
+    /// If the package is pinned to an index, only that index is returned. Otherwise, returns the
+    /// same entries as [`IndexUrls::indexes`].
+    pub fn indexes_for(
+        &'a self,
+        package_name: &PackageName,
+    ) -> impl Iterator<Item = &'a IndexUrl> + 'a {
+        let pin = self
+            .pins
+            .iter()
+            .find(|pin| pin.package == *package_name)
+            .filter(|_| !self.no_index);
+        match pin {
+            Some(pin) => Either::Left(std::iter::once(&pin.index)),
+            None => Either::Right(self.indexes()),
+        }
+    }
 }
 
 impl From<IndexLocations> for IndexUrls {
@@ -352,6 +422,7 @@ impl From<IndexLocations> for IndexUrls {
         Self {
             index: locations.index,
             extra_index: locations.extra_index,
+            pins: locations.pins,
             no_index: locations.no_index,
         }
     }
@@ -384,4 +455,57 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn parse_index_pin() {
+        let pin = IndexPin::from_str("torch=https://download.pytorch.org/whl/cpu").unwrap();
+        assert_eq!(pin.package, PackageName::from_str("torch").unwrap());
+        assert_eq!(
+            pin.index,
+            IndexUrl::from_str("https://download.pytorch.org/whl/cpu").unwrap()
+        );
+
+        assert!(matches!(
+            IndexPin::from_str("torch"),
+            Err(IndexPinError::MissingSeparator(_))
+        ));
+        assert!(matches!(
+            IndexPin::from_str("torch=not a url"),
+            Err(IndexPinError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn indexes_for_pinned_package() {
+        let mirror = IndexUrl::from_str("https://mirror.example.com/simple").unwrap();
+        let pytorch = IndexUrl::from_str("https://download.pytorch.org/whl/cpu").unwrap();
+        let locations = IndexLocations::new(None, vec![mirror.clone()], Vec::new(), false)
+            .with_pins(vec![IndexPin {
+                package: PackageName::from_str("torch").unwrap(),
+                index: pytorch.clone(),
+            }]);
+        let index_urls = locations.index_urls();
+
+        // A pinned package is only fetched from its pinned index...
+        let torch = PackageName::from_str("torch").unwrap();
+        assert_eq!(
+            index_urls.indexes_for(&torch).collect::<Vec<_>>(),
+            vec![&pytorch]
+        );
+
+        // ...while any other package is fetched from every index, in order.
+        let flask = PackageName::from_str("flask").unwrap();
+        assert_eq!(
+            index_urls.indexes_for(&flask).collect::<Vec<_>>(),
+            vec![&mirror, &*DEFAULT_INDEX_URL]
+        );
+
+        // The pins are ignored with `--no-index`.
+        let locations =
+            IndexLocations::new(None, Vec::new(), Vec::new(), true).with_pins(vec![IndexPin {
+                package: torch.clone(),
+                index: pytorch,
+            }]);
+        assert_eq!(locations.index_urls().indexes_for(&torch).count(), 0);
+    }
 }
@@ -19,6 +19,9 @@ pub enum InstalledDist {
     Registry(InstalledRegistryDist),
     /// The distribution was derived from an arbitrary URL.
     Url(InstalledDirectUrlDist),
+    /// The distribution was installed in editable mode by `setup.py develop`, i.e., as an
+    /// `.egg-link` file.
+    LegacyEditable(InstalledLegacyEditable),
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +40,20 @@ pub struct InstalledDirectUrlDist {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone)]
+pub struct InstalledLegacyEditable {
+    pub name: PackageName,
+    pub version: Version,
+    /// The `.egg-link` file in `site-packages`.
+    pub egg_link: PathBuf,
+    /// The project directory to which the `.egg-link` file points.
+    pub target: PathBuf,
+    /// The URL of the project directory.
+    pub target_url: Url,
+    /// The `.egg-info` directory within the project directory.
+    pub egg_info: PathBuf,
+}
+
 impl InstalledDist {
     /// Try to parse a distribution from a `.dist-info` directory name (like `django-5.0a1.dist-info`).
     ///
@@ -81,14 +98,76 @@ impl InstalledDist {
                 })))
             };
         }
+
+        // Parse legacy editables from an `.egg-link` file (like `django.egg-link`).
+        if path.extension().is_some_and(|ext| ext == "egg-link") {
+            return Self::try_from_egg_link(path);
+        }
+
         Ok(None)
     }
 
+    /// Try to parse a legacy editable from an `.egg-link` file, the first line of which is the path
+    /// to the project directory. The name and version are read from the `PKG-INFO` file in the
+    /// project's `.egg-info` directory.
+    fn try_from_egg_link(path: &Path) -> Result<Option<Self>> {
+        let contents = fs::read_to_string(path)?;
+        let Some(target) = contents
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+        else {
+            warn!("Ignoring empty `.egg-link` file: {}", path.user_display());
+            return Ok(None);
+        };
+        let target = path.parent().unwrap_or(path).join(target);
+
+        // Following `pip`, use the first `.egg-info` directory in the project directory.
+        let egg_info = match fs::read_dir(&target) {
+            Ok(entries) => entries
+                .filter_map(std::result::Result::ok)
+                .map(|entry| entry.path())
+                .find(|path| path.extension().is_some_and(|ext| ext == "egg-info")),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let Some(egg_info) = egg_info else {
+            warn!(
+                "Ignoring `.egg-link` file without a matching `.egg-info` directory: {}",
+                path.user_display()
+            );
+            return Ok(None);
+        };
+
+        let metadata = Self::read_pkg_info(&egg_info)?;
+        let target_url = Url::from_directory_path(&target)
+            .map_err(|()| anyhow!("Invalid project path: {}", target.user_display()))?;
+        Ok(Some(Self::LegacyEditable(InstalledLegacyEditable {
+            name: metadata.name,
+            version: metadata.version,
+            egg_link: path.to_path_buf(),
+            target,
+            target_url,
+            egg_info,
+        })))
+    }
+
+    /// Read the `PKG-INFO` file from an `.egg-info` directory.
+    fn read_pkg_info(egg_info: &Path) -> Result<pypi_types::Metadata23> {
+        let path = egg_info.join("PKG-INFO");
+        let contents = fs::read(&path)?;
+        pypi_types::Metadata23::parse_metadata(&contents)
+            .with_context(|| format!("Failed to parse PKG-INFO file at: {}", path.user_display()))
+    }
+
     /// Return the [`Path`] at which the distribution is stored on-disk.
+    ///
+    /// For legacy editables, this is the `.egg-info` directory within the project.
     pub fn path(&self) -> &Path {
         match self {
             Self::Registry(dist) => &dist.path,
             Self::Url(dist) => &dist.path,
+            Self::LegacyEditable(dist) => &dist.egg_info,
         }
     }
 
@@ -97,6 +176,7 @@ impl InstalledDist {
         match self {
             Self::Registry(dist) => &dist.version,
             Self::Url(dist) => &dist.version,
+            Self::LegacyEditable(dist) => &dist.version,
         }
     }
 
@@ -110,8 +190,12 @@ impl InstalledDist {
         Ok(Some(direct_url))
     }
 
-    /// Read the `METADATA` file from a `.dist-info` directory.
+    /// Read the `METADATA` file from a `.dist-info` directory (or the `PKG-INFO` file from an
+    /// `.egg-info` directory).
     pub fn metadata(&self) -> Result<pypi_types::Metadata23> {
+        if let Self::LegacyEditable(dist) = self {
+            return Self::read_pkg_info(&dist.egg_info);
+        }
         let path = self.path().join("METADATA");
         let contents = fs::read(&path)?;
         // TODO(zanieb): Update this to use thiserror so we can unpack parse errors downstream
@@ -119,8 +203,22 @@ impl InstalledDist {
             .with_context(|| format!("Failed to parse METADATA file at: {}", path.user_display()))
     }
 
+    /// Read the licensing information from the `METADATA` file in a `.dist-info` directory.
+    pub fn license(&self) -> Result<pypi_types::MetadataLicense> {
+        let path = match self {
+            Self::LegacyEditable(dist) => dist.egg_info.join("PKG-INFO"),
+            _ => self.path().join("METADATA"),
+        };
+        let contents = fs::read(&path)?;
+        pypi_types::MetadataLicense::parse_metadata(&contents)
+            .with_context(|| format!("Failed to parse METADATA file at: {}", path.user_display()))
+    }
+
     /// Return the `INSTALLER` of the distribution.
     pub fn installer(&self) -> Result<Option<String>> {
+        if let Self::LegacyEditable(_) = self {
+            return Ok(None);
+        }
         let path = self.path().join("INSTALLER");
         match fs::read_to_string(path) {
             Ok(installer) => Ok(Some(installer)),
@@ -134,6 +232,7 @@ impl InstalledDist {
         match self {
             Self::Registry(_) => false,
             Self::Url(dist) => dist.editable,
+            Self::LegacyEditable(_) => true,
         }
     }
 
@@ -142,6 +241,7 @@ impl InstalledDist {
         match self {
             Self::Registry(_) => None,
             Self::Url(dist) => dist.editable.then_some(&dist.url),
+            Self::LegacyEditable(dist) => Some(&dist.target_url),
         }
     }
 }
@@ -164,11 +264,18 @@ impl Name for InstalledDirectUrlDist {
     }
 }
 
+impl Name for InstalledLegacyEditable {
+    fn name(&self) -> &PackageName {
+        &self.name
+    }
+}
+
 impl Name for InstalledDist {
     fn name(&self) -> &PackageName {
         match self {
             Self::Registry(dist) => dist.name(),
             Self::Url(dist) => dist.name(),
+            Self::LegacyEditable(dist) => dist.name(),
         }
     }
 }
@@ -185,11 +292,18 @@ impl InstalledMetadata for InstalledDirectUrlDist {
     }
 }
 
+impl InstalledMetadata for InstalledLegacyEditable {
+    fn installed_version(&self) -> InstalledVersion {
+        InstalledVersion::Url(&self.target_url, &self.version)
+    }
+}
+
 impl InstalledMetadata for InstalledDist {
     fn installed_version(&self) -> InstalledVersion {
         match self {
             Self::Registry(dist) => dist.installed_version(),
             Self::Url(dist) => dist.installed_version(),
+            Self::LegacyEditable(dist) => dist.installed_version(),
         }
     }
 }
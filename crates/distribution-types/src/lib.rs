@@ -31,6 +31,9 @@
 //! * [`InstalledRegistryDist`]
 //! * [`InstalledDirectUrlDist`]
 //!
+//! Projects installed in editable mode by `setup.py develop` are tracked as an
+//! [`InstalledLegacyEditable`].
+//!
 //! Since we read this information from [`direct_url.json`](https://packaging.python.org/en/latest/specifications/direct-url-data-structure/), it doesn't match the information [`Dist`] exactly.
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
@@ -84,6 +84,12 @@ impl Display for IncompatibleDist {
                 IncompatibleWheel::RequiresPython(python) => {
                     write!(f, "it requires at python {python}")
                 }
+                IncompatibleWheel::Filtered(reason) => {
+                    write!(
+                        f,
+                        "it was rejected by a candidate filter (reason: {reason})"
+                    )
+                }
             },
             Self::Source(incompatibility) => match incompatibility {
                 IncompatibleSource::NoBuild => {
@@ -104,6 +110,12 @@ impl Display for IncompatibleDist {
                 IncompatibleSource::RequiresPython(python) => {
                     write!(f, "it requires python {python}")
                 }
+                IncompatibleSource::Filtered(reason) => {
+                    write!(
+                        f,
+                        "it was rejected by a candidate filter (reason: {reason})"
+                    )
+                }
             },
             Self::Unavailable => f.write_str("no distributions are available"),
         }
@@ -113,7 +125,7 @@ impl Display for IncompatibleDist {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WheelCompatibility {
     Incompatible(IncompatibleWheel),
-    Compatible(Hash, TagPriority),
+    Compatible(Hash, Rank, TagPriority),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -122,13 +134,15 @@ pub enum IncompatibleWheel {
     Tag(IncompatibleTag),
     RequiresPython(VersionSpecifiers),
     Yanked(Yanked),
+    /// The wheel was rejected by a candidate filter, with the given reason.
+    Filtered(String),
     NoBinary,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SourceDistCompatibility {
     Incompatible(IncompatibleSource),
-    Compatible(Hash),
+    Compatible(Hash, Rank),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -136,6 +150,8 @@ pub enum IncompatibleSource {
     ExcludeNewer(Option<i64>),
     RequiresPython(VersionSpecifiers),
     Yanked(Yanked),
+    /// The source distribution was rejected by a candidate filter, with the given reason.
+    Filtered(String),
     NoBuild,
 }
 
@@ -149,6 +165,12 @@ pub enum Hash {
     Matched,
 }
 
+/// The preference for a distribution relative to other compatible distributions of the same
+/// package version, as assigned by a candidate filter. Higher ranks are preferred; distributions
+/// that weren't re-ranked have the default rank of zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rank(pub i32);
+
 impl PrioritizedDist {
     /// Create a new [`PrioritizedDist`] from the given wheel distribution.
     pub fn from_built(
@@ -217,19 +239,19 @@ impl PrioritizedDist {
     /// Return the highest-priority distribution for the package version, if any.
     pub fn get(&self) -> Option<CompatibleDist> {
         match (&self.0.wheel, &self.0.source) {
-            // If both are compatible, break ties based on the hash.
+            // If both are compatible, break ties based on the hash, and then the rank.
             (
-                Some((wheel, WheelCompatibility::Compatible(wheel_hash, tag_priority))),
-                Some((source_dist, SourceDistCompatibility::Compatible(source_hash))),
+                Some((wheel, WheelCompatibility::Compatible(wheel_hash, wheel_rank, tag_priority))),
+                Some((source_dist, SourceDistCompatibility::Compatible(source_hash, source_rank))),
             ) => {
-                if source_hash > wheel_hash {
+                if (source_hash, source_rank) > (wheel_hash, wheel_rank) {
                     Some(CompatibleDist::SourceDist(source_dist))
                 } else {
                     Some(CompatibleDist::CompatibleWheel(wheel, *tag_priority))
                 }
             }
             // Prefer the highest-priority, platform-compatible wheel.
-            (Some((wheel, WheelCompatibility::Compatible(_, tag_priority))), _) => {
+            (Some((wheel, WheelCompatibility::Compatible(_, _, tag_priority))), _) => {
                 Some(CompatibleDist::CompatibleWheel(wheel, *tag_priority))
             }
             // If we have a compatible source distribution and an incompatible wheel, return the
@@ -238,10 +260,10 @@ impl PrioritizedDist {
             // using the wheel is faster.
             (
                 Some((wheel, WheelCompatibility::Incompatible(_))),
-                Some((source_dist, SourceDistCompatibility::Compatible(_))),
+                Some((source_dist, SourceDistCompatibility::Compatible(..))),
             ) => Some(CompatibleDist::IncompatibleWheel { source_dist, wheel }),
             // Otherwise, if we have a source distribution, return it.
-            (None, Some((source_dist, SourceDistCompatibility::Compatible(_)))) => {
+            (None, Some((source_dist, SourceDistCompatibility::Compatible(..)))) => {
                 Some(CompatibleDist::SourceDist(source_dist))
             }
             _ => None,
@@ -254,7 +276,7 @@ impl PrioritizedDist {
             .source
             .as_ref()
             .and_then(|(dist, compatibility)| match compatibility {
-                SourceDistCompatibility::Compatible(_) => None,
+                SourceDistCompatibility::Compatible(..) => None,
                 SourceDistCompatibility::Incompatible(incompatibility) => {
                     Some((dist, incompatibility))
                 }
@@ -267,7 +289,7 @@ impl PrioritizedDist {
             .wheel
             .as_ref()
             .and_then(|(dist, compatibility)| match compatibility {
-                WheelCompatibility::Compatible(_, _) => None,
+                WheelCompatibility::Compatible(..) => None,
                 WheelCompatibility::Incompatible(incompatibility) => Some((dist, incompatibility)),
             })
     }
@@ -326,21 +348,21 @@ impl<'a> CompatibleDist<'a> {
 
 impl WheelCompatibility {
     pub fn is_compatible(&self) -> bool {
-        matches!(self, Self::Compatible(_, _))
+        matches!(self, Self::Compatible(..))
     }
 
     /// Return `true` if the current compatibility is more compatible than another.
     ///
     /// Compatible wheels are always higher more compatible than incompatible wheels.
-    /// Compatible wheel ordering is determined by tag priority.
+    /// Compatible wheel ordering is determined by hash, then rank, then tag priority.
     pub fn is_more_compatible(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::Compatible(_, _), Self::Incompatible(_)) => true,
+            (Self::Compatible(..), Self::Incompatible(_)) => true,
             (
-                Self::Compatible(hash, tag_priority),
-                Self::Compatible(other_hash, other_tag_priority),
-            ) => (hash, tag_priority) > (other_hash, other_tag_priority),
-            (Self::Incompatible(_), Self::Compatible(_, _)) => false,
+                Self::Compatible(hash, rank, tag_priority),
+                Self::Compatible(other_hash, other_rank, other_tag_priority),
+            ) => (hash, rank, tag_priority) > (other_hash, other_rank, other_tag_priority),
+            (Self::Incompatible(_), Self::Compatible(..)) => false,
             (Self::Incompatible(incompatibility), Self::Incompatible(other_incompatibility)) => {
                 incompatibility.is_more_compatible(other_incompatibility)
             }
@@ -352,15 +374,15 @@ impl SourceDistCompatibility {
     /// Return the higher priority compatibility.
     ///
     /// Compatible source distributions are always higher priority than incompatible source distributions.
-    /// Compatible source distribution priority is arbitrary.
+    /// Compatible source distribution priority is determined by hash, then rank, and is otherwise arbitrary.
     /// Incompatible source distribution priority selects a source distribution that was "closest" to being usable.
     pub fn is_more_compatible(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::Compatible(_), Self::Incompatible(_)) => true,
-            (Self::Compatible(compatibility), Self::Compatible(other_compatibility)) => {
-                compatibility > other_compatibility
+            (Self::Compatible(..), Self::Incompatible(_)) => true,
+            (Self::Compatible(hash, rank), Self::Compatible(other_hash, other_rank)) => {
+                (hash, rank) > (other_hash, other_rank)
             }
-            (Self::Incompatible(_), Self::Compatible(_)) => false,
+            (Self::Incompatible(_), Self::Compatible(..)) => false,
             (Self::Incompatible(incompatibility), Self::Incompatible(other_incompatibility)) => {
                 incompatibility.is_more_compatible(other_incompatibility)
             }
@@ -374,18 +396,25 @@ impl IncompatibleSource {
             Self::ExcludeNewer(timestamp_self) => match other {
                 // Smaller timestamps are closer to the cut-off time
                 Self::ExcludeNewer(timestamp_other) => timestamp_other < timestamp_self,
-                Self::NoBuild | Self::RequiresPython(_) | Self::Yanked(_) => true,
+                Self::NoBuild | Self::RequiresPython(_) | Self::Yanked(_) | Self::Filtered(_) => {
+                    true
+                }
             },
             Self::RequiresPython(_) => match other {
                 Self::ExcludeNewer(_) => false,
                 // Version specifiers cannot be reasonably compared
                 Self::RequiresPython(_) => false,
-                Self::NoBuild | Self::Yanked(_) => true,
+                Self::NoBuild | Self::Yanked(_) | Self::Filtered(_) => true,
             },
             Self::Yanked(_) => match other {
                 Self::ExcludeNewer(_) | Self::RequiresPython(_) => false,
                 // Yanks with a reason are more helpful for errors
                 Self::Yanked(yanked_other) => matches!(yanked_other, Yanked::Reason(_)),
+                Self::NoBuild | Self::Filtered(_) => true,
+            },
+            Self::Filtered(_) => match other {
+                Self::ExcludeNewer(_) | Self::RequiresPython(_) | Self::Yanked(_) => false,
+                Self::Filtered(_) => false,
                 Self::NoBuild => true,
             },
             Self::NoBuild => false,
@@ -405,23 +434,37 @@ impl IncompatibleWheel {
                         timestamp_other < timestamp_self
                     }
                 },
-                Self::NoBinary | Self::RequiresPython(_) | Self::Tag(_) | Self::Yanked(_) => true,
+                Self::NoBinary
+                | Self::RequiresPython(_)
+                | Self::Tag(_)
+                | Self::Yanked(_)
+                | Self::Filtered(_) => true,
             },
             Self::Tag(tag_self) => match other {
                 Self::ExcludeNewer(_) => false,
                 Self::Tag(tag_other) => tag_other > tag_self,
-                Self::NoBinary | Self::RequiresPython(_) | Self::Yanked(_) => true,
+                Self::NoBinary | Self::RequiresPython(_) | Self::Yanked(_) | Self::Filtered(_) => {
+                    true
+                }
             },
             Self::RequiresPython(_) => match other {
                 Self::ExcludeNewer(_) | Self::Tag(_) => false,
                 // Version specifiers cannot be reasonably compared
                 Self::RequiresPython(_) => false,
-                Self::NoBinary | Self::Yanked(_) => true,
+                Self::NoBinary | Self::Yanked(_) | Self::Filtered(_) => true,
             },
             Self::Yanked(_) => match other {
                 Self::ExcludeNewer(_) | Self::Tag(_) | Self::RequiresPython(_) => false,
                 // Yanks with a reason are more helpful for errors
                 Self::Yanked(yanked_other) => matches!(yanked_other, Yanked::Reason(_)),
+                Self::NoBinary | Self::Filtered(_) => true,
+            },
+            Self::Filtered(_) => match other {
+                Self::ExcludeNewer(_)
+                | Self::Tag(_)
+                | Self::RequiresPython(_)
+                | Self::Yanked(_) => false,
+                Self::Filtered(_) => false,
                 Self::NoBinary => true,
             },
             Self::NoBinary => false,
@@ -0,0 +1,370 @@
+//! Pack a set of installed distributions into a self-contained, runnable bundle.
+//!
+//! A bundle contains the `site-packages` files of each distribution (as listed in its `RECORD`),
+//! along with a `__main__.py` that invokes the chosen entry point. It can be written either as a
+//! zipapp (a single archive, runnable with `python app.pyz`) or as a directory (runnable with
+//! `python app/`), which also supports distributions with native extension modules.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use fs_err as fs;
+use fs_err::File;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::script::scripts_from_ini;
+use crate::uninstall::{normalize_path, read_dist_info_record, site_packages};
+use crate::Error;
+
+/// The callable (or module) that a bundle runs on startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoint {
+    /// The module to import, e.g., `black`.
+    module: String,
+    /// The (possibly dotted) attribute of the module to call, e.g., `patched_main`. If absent,
+    /// the module itself is run as `__main__`.
+    function: Option<String>,
+}
+
+impl EntryPoint {
+    /// Find the console (or GUI) script with the given name among the entry points declared by
+    /// the distribution in the given `.dist-info` directory.
+    pub fn from_script(dist_info: &Path, name: &str) -> Result<Option<Self>, Error> {
+        let entry_points = match fs::read_to_string(dist_info.join("entry_points.txt")) {
+            Ok(entry_points) => entry_points,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        // The Python version is only used to generate versioned `pip` launchers.
+        let (console_scripts, gui_scripts) = scripts_from_ini(None, 0, entry_points)?;
+        Ok(console_scripts
+            .into_iter()
+            .chain(gui_scripts)
+            .find(|script| script.name == name)
+            .map(|script| Self {
+                module: script.module,
+                function: Some(script.function),
+            }))
+    }
+
+    /// Render the `__main__.py` that runs the entry point.
+    fn main_py(&self) -> String {
+        match &self.function {
+            Some(function) => {
+                let import_name = function
+                    .split_once('.')
+                    .map_or(function.as_str(), |(import_name, _)| import_name);
+                format!(
+                    r##"# -*- coding: utf-8 -*-
+import sys
+from {module} import {import_name}
+if __name__ == "__main__":
+    sys.exit({function}())
+"##,
+                    module = self.module
+                )
+            }
+            None => format!(
+                r##"# -*- coding: utf-8 -*-
+import runpy
+if __name__ == "__main__":
+    runpy.run_module("{module}", run_name="__main__", alter_sys=True)
+"##,
+                module = self.module
+            ),
+        }
+    }
+}
+
+impl FromStr for EntryPoint {
+    type Err = Error;
+
+    /// Parse an entry point like `foo.bar:main` or `foo.bar`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let is_identifier = |part: &str| {
+            !part.is_empty()
+                && part.split('.').all(|segment| {
+                    segment
+                        .chars()
+                        .next()
+                        .is_some_and(|c| c.is_alphabetic() || c == '_')
+                        && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+                })
+        };
+        let (module, function) = match value.split_once(':') {
+            Some((module, function)) => (module.trim(), Some(function.trim())),
+            None => (value.trim(), None),
+        };
+        if !is_identifier(module) || !function.map_or(true, is_identifier) {
+            return Err(Error::InvalidEntryPoint(value.to_string()));
+        }
+        Ok(Self {
+            module: module.to_string(),
+            function: function.map(ToString::to_string),
+        })
+    }
+}
+
+/// A set of files to pack into a runnable bundle, keyed by their path relative to the bundle
+/// root.
+#[derive(Debug)]
+pub struct Bundle {
+    entry_point: EntryPoint,
+    files: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl Bundle {
+    /// Create an empty [`Bundle`] that runs the given entry point.
+    pub fn new(entry_point: EntryPoint) -> Self {
+        Self {
+            entry_point,
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Add the files of the distribution in the given `.dist-info` directory, as listed in its
+    /// `RECORD`.
+    ///
+    /// Only files within `site-packages` are included: scripts, headers, and data files are
+    /// installed relative to the environment and can't be imported from the bundle. Compiled
+    /// bytecode is also omitted, since it's tied to the interpreter that produced it.
+    ///
+    /// Returns the number of files added.
+    pub fn add_dist(&mut self, dist_info: &Path) -> Result<usize, Error> {
+        let site_packages = site_packages(dist_info)?;
+        let record = read_dist_info_record(dist_info)?;
+
+        let mut count = 0;
+        for entry in &record {
+            let path = normalize_path(&site_packages.join(&entry.path));
+            let Ok(relative) = path.strip_prefix(site_packages) else {
+                continue;
+            };
+            if relative
+                .components()
+                .any(|component| component == Component::Normal("__pycache__".as_ref()))
+            {
+                continue;
+            }
+            // Some installers (incorrectly) record directories in the `RECORD`.
+            if !path.is_file() {
+                continue;
+            }
+            if relative == Path::new("__main__.py") {
+                return Err(Error::BundleConflict(relative.to_path_buf()));
+            }
+            if let Some(existing) = self.files.get(relative) {
+                if *existing != path {
+                    return Err(Error::BundleConflict(relative.to_path_buf()));
+                }
+                continue;
+            }
+            self.files.insert(relative.to_path_buf(), path);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns an iterator over the bundled files, relative to the bundle root.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Returns `true` if the bundle includes native extension modules, which can't be imported
+    /// from a zipapp.
+    pub fn has_extension_modules(&self) -> bool {
+        self.files().any(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "so" || extension == "pyd")
+        })
+    }
+
+    /// Write the bundle as a zipapp at the given path, with an optional shebang (e.g.,
+    /// `/usr/bin/env python3`).
+    ///
+    /// Entries are written in a stable order with fixed timestamps, such that the same set of
+    /// distributions always produces the same archive.
+    pub fn write_zipapp(&self, target: &Path, shebang: Option<&str>) -> Result<(), Error> {
+        let mut file = File::create(target)?;
+        if let Some(shebang) = shebang {
+            writeln!(file, "#!{shebang}")?;
+        }
+
+        let mut writer = ZipWriter::new(file);
+        let options = FileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+        let zip_err = |err| Error::BundleZip(target.to_path_buf(), err);
+
+        writer.start_file("__main__.py", options).map_err(zip_err)?;
+        writer.write_all(self.entry_point.main_py().as_bytes())?;
+
+        for (relative, path) in &self.files {
+            // Zip archives always use forward slashes.
+            let name = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            writer.start_file(name, options).map_err(zip_err)?;
+            io::copy(&mut File::open(path)?, &mut writer)?;
+        }
+        writer.finish().map_err(zip_err)?;
+
+        #[cfg(unix)]
+        if shebang.is_some() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(target, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the bundle as a relocatable directory at the given path.
+    pub fn write_directory(&self, target: &Path) -> Result<(), Error> {
+        fs::create_dir_all(target)?;
+        fs::write(target.join("__main__.py"), self.entry_point.main_py())?;
+        for (relative, path) in &self.files {
+            let destination = target.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(path, &destination)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use fs_err as fs;
+
+    use super::{Bundle, EntryPoint};
+    use crate::Error;
+
+    /// Create a minimal installed distribution in the given `site-packages` directory.
+    fn install(site_packages: &std::path::Path) -> Result<std::path::PathBuf, Error> {
+        let dist_info = site_packages.join("foo-1.0.0.dist-info");
+        fs::create_dir_all(&dist_info)?;
+        fs::create_dir_all(site_packages.join("foo").join("__pycache__"))?;
+        fs::write(site_packages.join("foo").join("__init__.py"), "")?;
+        fs::write(
+            site_packages.join("foo").join("cli.py"),
+            "def main(): pass\n",
+        )?;
+        fs::write(
+            site_packages
+                .join("foo")
+                .join("__pycache__")
+                .join("cli.cpython-312.pyc"),
+            "",
+        )?;
+        fs::write(
+            dist_info.join("entry_points.txt"),
+            "[console_scripts]\nfoo = foo.cli:main\n",
+        )?;
+        fs::write(
+            dist_info.join("RECORD"),
+            "foo/__init__.py,,\n\
+             foo/cli.py,,\n\
+             foo/__pycache__/cli.cpython-312.pyc,,\n\
+             foo-1.0.0.dist-info/entry_points.txt,,\n\
+             foo-1.0.0.dist-info/RECORD,,\n\
+             ../../../bin/foo,,\n",
+        )?;
+        Ok(dist_info)
+    }
+
+    #[test]
+    fn parse_entry_point() {
+        assert_eq!(
+            "foo.cli:main".parse::<EntryPoint>().unwrap(),
+            EntryPoint {
+                module: "foo.cli".to_string(),
+                function: Some("main".to_string()),
+            }
+        );
+        assert_eq!(
+            "foo".parse::<EntryPoint>().unwrap(),
+            EntryPoint {
+                module: "foo".to_string(),
+                function: None,
+            }
+        );
+        assert!("foo-cli:main".parse::<EntryPoint>().is_err());
+        assert!("foo:".parse::<EntryPoint>().is_err());
+    }
+
+    #[test]
+    fn script_entry_point() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let dist_info = install(temp_dir.path())?;
+        assert_eq!(
+            EntryPoint::from_script(&dist_info, "foo")?,
+            Some("foo.cli:main".parse()?)
+        );
+        assert_eq!(EntryPoint::from_script(&dist_info, "bar")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn zipapp() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        let dist_info = install(&site_packages)?;
+
+        let mut bundle = Bundle::new("foo.cli:main".parse()?);
+        assert_eq!(bundle.add_dist(&dist_info)?, 4);
+        assert!(!bundle.has_extension_modules());
+
+        let target = temp_dir.path().join("app.pyz");
+        bundle.write_zipapp(&target, Some("/usr/bin/env python3"))?;
+
+        let mut contents = Vec::new();
+        fs::File::open(&target)?.read_to_end(&mut contents)?;
+        assert!(contents.starts_with(b"#!/usr/bin/env python3\n"));
+
+        let archive = zip::ZipArchive::new(fs::File::open(&target)?)
+            .map_err(|err| Error::Zip(target.display().to_string(), err))?;
+        let mut names = archive.file_names().collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            vec![
+                "__main__.py",
+                "foo-1.0.0.dist-info/RECORD",
+                "foo-1.0.0.dist-info/entry_points.txt",
+                "foo/__init__.py",
+                "foo/cli.py",
+            ]
+        );
+
+        // The archive is reproducible.
+        let first = fs::read(&target)?;
+        bundle.write_zipapp(&target, Some("/usr/bin/env python3"))?;
+        assert_eq!(fs::read(&target)?, first);
+        Ok(())
+    }
+
+    #[test]
+    fn directory() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        let dist_info = install(&site_packages)?;
+
+        let mut bundle = Bundle::new("foo".parse()?);
+        bundle.add_dist(&dist_info)?;
+
+        let target = temp_dir.path().join("app");
+        bundle.write_directory(&target)?;
+        assert!(fs::read_to_string(target.join("__main__.py"))?.contains("run_module(\"foo\""));
+        assert!(target.join("foo").join("cli.py").is_file());
+        assert!(!target.join("foo").join("__pycache__").exists());
+        Ok(())
+    }
+}
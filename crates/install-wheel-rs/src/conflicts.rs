@@ -0,0 +1,170 @@
+use std::path::{Component, Path, PathBuf};
+
+use fs_err as fs;
+use rustc_hash::FxHashMap;
+
+use uv_normalize::PackageName;
+
+use crate::record::RecordEntry;
+use crate::uninstall::{normalize_path, read_dist_info_record};
+use crate::wheel::read_record_file;
+use crate::Error;
+
+/// The files claimed by the distributions in a `site-packages` directory, used to detect wheels
+/// that would overwrite files belonging to another distribution.
+///
+/// Only files within `site-packages` itself are tracked; `.dist-info` directories, bytecode, and
+/// the contents of a wheel's `.data` directory are ignored.
+#[derive(Debug, Default)]
+pub struct FileClaims {
+    /// The claimed files, relative to `site-packages`, mapped to the owning distribution and the
+    /// hash of the file, per the `RECORD`.
+    claims: FxHashMap<PathBuf, (PackageName, Option<String>)>,
+}
+
+impl FileClaims {
+    /// Register the files of the installed distribution represented by the given `dist_info`
+    /// directory.
+    ///
+    /// Existing conflicts between installed distributions are not reported.
+    pub fn add_installed(&mut self, name: &PackageName, dist_info: &Path) -> Result<(), Error> {
+        for entry in read_dist_info_record(dist_info)? {
+            if let Some(path) = claimed_path(&entry) {
+                self.claims
+                    .entry(path)
+                    .or_insert_with(|| (name.clone(), entry.hash));
+            }
+        }
+        Ok(())
+    }
+
+    /// Register the files that would be installed from the given unzipped wheel.
+    ///
+    /// Returns [`Error::FileConflict`] if the wheel would overwrite a file claimed by a different
+    /// distribution. Files with identical contents (e.g., the `__init__.py` of a legacy namespace
+    /// package) are not considered conflicts.
+    pub fn add_wheel(&mut self, name: &PackageName, wheel: &Path) -> Result<(), Error> {
+        for entry in read_wheel_record(wheel)? {
+            let Some(path) = claimed_path(&entry) else {
+                continue;
+            };
+            if let Some((existing, hash)) = self.claims.get(&path) {
+                if existing != name && (hash.is_none() || *hash != entry.hash) {
+                    return Err(Error::FileConflict {
+                        path,
+                        existing_package: existing.clone(),
+                    });
+                }
+                continue;
+            }
+            self.claims.insert(path, (name.clone(), entry.hash));
+        }
+        Ok(())
+    }
+}
+
+/// Read the `RECORD` file from the `.dist-info` directory of an unzipped wheel.
+fn read_wheel_record(wheel: &Path) -> Result<Vec<RecordEntry>, Error> {
+    let dist_info = fs::read_dir(wheel)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "dist-info")
+        })
+        .ok_or(Error::MissingDistInfo)?;
+    let mut record_file = fs::File::open(dist_info.join("RECORD"))?;
+    read_record_file(&mut record_file)
+}
+
+/// Return the path, relative to `site-packages`, of the file described by the given `RECORD`
+/// entry, if it should be tracked for conflicts.
+fn claimed_path(entry: &RecordEntry) -> Option<PathBuf> {
+    let path = normalize_path(Path::new(&entry.path));
+    let mut components = path.components();
+    let Some(Component::Normal(first)) = components.next() else {
+        // Skip files outside of `site-packages`, like scripts.
+        return None;
+    };
+    let first = first.to_string_lossy();
+    if first.ends_with(".dist-info") || first.ends_with(".data") {
+        return None;
+    }
+    if path
+        .components()
+        .any(|component| component.as_os_str() == "__pycache__")
+    {
+        return None;
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use fs_err as fs;
+
+    use uv_normalize::PackageName;
+
+    use crate::Error;
+
+    use super::FileClaims;
+
+    fn write_wheel(root: &Path, name: &str, record: &str) -> Result<(), Error> {
+        let dist_info = root.join(format!("{name}-1.0.0.dist-info"));
+        fs::create_dir_all(&dist_info)?;
+        fs::write(dist_info.join("RECORD"), record)?;
+        Ok(())
+    }
+
+    #[test]
+    fn conflicting_wheels() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let foo = PackageName::from_str("foo").unwrap();
+        let bar = PackageName::from_str("bar").unwrap();
+        let baz = PackageName::from_str("baz").unwrap();
+
+        write_wheel(
+            &temp_dir.path().join("foo"),
+            "foo",
+            "ns/__init__.py,sha256=abc,1\nshared/module.py,sha256=def,1\nfoo-1.0.0.dist-info/RECORD,,\n",
+        )?;
+        write_wheel(
+            &temp_dir.path().join("bar"),
+            "bar",
+            "ns/__init__.py,sha256=abc,1\nbar/__init__.py,sha256=ghi,1\nbar-1.0.0.dist-info/RECORD,,\n",
+        )?;
+        write_wheel(
+            &temp_dir.path().join("baz"),
+            "baz",
+            "shared/module.py,sha256=jkl,1\nbaz-1.0.0.dist-info/RECORD,,\n",
+        )?;
+
+        let mut claims = FileClaims::default();
+        claims.add_wheel(&foo, &temp_dir.path().join("foo"))?;
+
+        // Identical files are allowed.
+        claims.add_wheel(&bar, &temp_dir.path().join("bar"))?;
+
+        // Files with different contents are not.
+        let err = claims
+            .add_wheel(&baz, &temp_dir.path().join("baz"))
+            .unwrap_err();
+        match err {
+            Error::FileConflict {
+                path,
+                existing_package,
+            } => {
+                assert_eq!(path, Path::new("shared/module.py"));
+                assert_eq!(existing_package, foo);
+            }
+            err => panic!("Expected a file conflict, got: {err}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! A record of the files written or removed while modifying an environment, such that the
+//! modification can be undone if it fails part way.
+
+use std::cmp::Reverse;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use reflink_copy as reflink;
+use rustc_hash::FxHashSet;
+use tempfile::TempDir;
+use tracing::debug;
+
+use crate::script::Script;
+use crate::uninstall::normalize_path;
+use crate::Error;
+
+/// The files written or removed by an in-progress installation or upgrade, such that they can be
+/// restored if it fails.
+pub(crate) struct Journal {
+    /// The directory that holds the backups of the replaced files, which is removed when the
+    /// journal is dropped.
+    backups: TempDir,
+    /// The (normalized) paths that have been noted already.
+    seen: FxHashSet<PathBuf>,
+    /// The files that existed before the modification, and the path of their backup.
+    replaced: Vec<(PathBuf, PathBuf)>,
+    /// The files and directories that didn't exist before the modification.
+    created: Vec<PathBuf>,
+}
+
+impl Journal {
+    /// Create a journal, storing its backups within `site-packages`.
+    pub(crate) fn new(site_packages: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            backups: tempfile::Builder::new()
+                .prefix(".journal-")
+                .tempdir_in(site_packages)?,
+            seen: FxHashSet::default(),
+            replaced: Vec::new(),
+            created: Vec::new(),
+        })
+    }
+
+    /// Note that the file at `path` is about to be written or removed, backing it up if it
+    /// exists.
+    ///
+    /// Files may be written in place, so they're copied (or cloned) rather than hard-linked.
+    pub(crate) fn save(&mut self, path: &Path) -> Result<(), Error> {
+        let path = normalize_path(path);
+        if !self.seen.insert(path.clone()) {
+            return Ok(());
+        }
+        match path.symlink_metadata() {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => {
+                let backup = self.backups.path().join(self.replaced.len().to_string());
+                reflink::reflink_or_copy(&path, &backup)?;
+                self.replaced.push((path, backup));
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                // Note any missing parent directories too, such that they're removed again.
+                let mut parent = path.parent();
+                while let Some(directory) = parent {
+                    if directory.symlink_metadata().is_ok()
+                        || !self.seen.insert(directory.to_path_buf())
+                    {
+                        break;
+                    }
+                    self.created.push(directory.to_path_buf());
+                    parent = directory.parent();
+                }
+                self.created.push(path);
+            }
+            Err(err) => return Err(err.into()),
+        }
+        Ok(())
+    }
+
+    /// Note that the entrypoints for the given scripts are about to be written to `scripts`.
+    pub(crate) fn save_entrypoints<'a>(
+        &mut self,
+        scripts: &Path,
+        entrypoints: impl IntoIterator<Item = &'a Script>,
+    ) -> Result<(), Error> {
+        for script in entrypoints {
+            // Depending on the platform, an entrypoint may be written as a launcher binary or as
+            // a script, so back up every candidate.
+            let stem = script.name.strip_suffix(".py").unwrap_or(&script.name);
+            for script_name in [
+                script.name.clone(),
+                format!("{stem}.exe"),
+                format!("{stem}-script.py"),
+                format!("{stem}-script.pyw"),
+            ] {
+                self.save(&scripts.join(script_name))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the files created by the modification, and restore the backups of those it
+    /// replaced.
+    pub(crate) fn rollback(mut self) -> Result<(), Error> {
+        // Remove files before their parent directories.
+        self.created
+            .sort_by_key(|path| Reverse(path.components().count()));
+        for path in &self.created {
+            let result = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            match result {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        debug!("Restoring {} file(s) from backup", self.replaced.len());
+        for (path, backup) in &self.replaced {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // The backups may be on a different filesystem (e.g., from the scripts directory).
+            if fs::rename(backup, path).is_err() {
+                fs::copy(backup, path)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,31 +1,49 @@
 //! Takes a wheel and installs it into a venv.
 
 use std::io;
-
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use platform_info::PlatformInfoError;
 use thiserror::Error;
 use zip::result::ZipError;
 
+pub use bundle::{Bundle, EntryPoint};
+pub use conflicts::FileClaims;
 use pep440_rs::Version;
 use platform_tags::{Arch, Os};
 use pypi_types::Scheme;
-pub use uninstall::{uninstall_wheel, Uninstall};
+pub use uninstall::{
+    repair_uninstall_wheel, uninstall_legacy_editable, uninstall_plan, uninstall_wheel, Uninstall,
+    UninstallPlan,
+};
+pub use upgrade::{upgrade_wheel, UpgradeSummary};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
+pub use verify::{verify_wheel, Verification};
+pub use warnings::InstallWarning;
 
+mod bundle;
+mod conflicts;
+mod journal;
 pub mod linker;
 pub mod metadata;
+mod preflight;
 mod record;
 mod script;
+#[cfg(test)]
+mod test_utils;
 mod uninstall;
+mod upgrade;
+mod verify;
+mod warnings;
 mod wheel;
 
 /// The layout of the target environment into which a wheel can be installed.
 #[derive(Debug, Clone)]
 pub struct Layout {
-    /// The Python interpreter, as returned by `sys.executable`.
+    /// The Python interpreter that generated scripts should invoke. Typically `sys.executable`,
+    /// but for Conda environments on Windows, the `python.exe` at the root of the environment.
     pub sys_executable: PathBuf,
     /// The Python version, as returned by `sys.version_info`.
     pub python_version: (u8, u8),
@@ -33,6 +51,42 @@ pub struct Layout {
     pub os_name: String,
     /// The [`Scheme`] paths for the interpreter.
     pub scheme: Scheme,
+    /// How generated scripts should locate the interpreter.
+    pub shebang: Shebang,
+}
+
+/// How the scripts generated during installation (i.e., entrypoints, and scripts with a
+/// `#!python` shebang) should locate the Python interpreter.
+///
+/// Only applies on Unix; on Windows, scripts are wrapped in a launcher that always refers to the
+/// interpreter by its absolute path.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum Shebang {
+    /// Use the absolute path to the interpreter.
+    #[default]
+    Absolute,
+    /// Use `#!/usr/bin/env python`, to run the first `python` on the `PATH`.
+    Env,
+    /// Locate the interpreter relative to the script, such that the environment can be moved.
+    Relative,
+    /// Use the given interpreter path, e.g., the path at which the environment will be deployed.
+    Interpreter(PathBuf),
+}
+
+impl FromStr for Shebang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "absolute" => Ok(Self::Absolute),
+            "env" => Ok(Self::Env),
+            "relative" => Ok(Self::Relative),
+            path if Path::new(path).is_absolute() => Ok(Self::Interpreter(PathBuf::from(path))),
+            _ => Err(format!(
+                "`{s}` is not a valid shebang (expected `absolute`, `env`, `relative`, or an absolute interpreter path)"
+            )),
+        }
+    }
 }
 
 /// Note: The caller is responsible for adding the path of the wheel we're installing.
@@ -84,6 +138,18 @@ pub enum Error {
     MissingDistInfo,
     #[error("Cannot uninstall package; RECORD file not found at: {}", _0.user_display())]
     MissingRecord(PathBuf),
+    #[error("Cannot uninstall package; `.egg-link` file is empty: {}", _0.user_display())]
+    InvalidEggLink(PathBuf),
+    #[error("The wheel contains paths that differ only by case, which would overwrite each other on a case-insensitive filesystem: `{0}` and `{1}`")]
+    CaseCollision(String, String),
+    #[error("The wheel contains paths that exceed the Windows limit of 260 characters when installed under {}; enable long path support (see https://learn.microsoft.com/en-us/windows/win32/fileio/maximum-file-path-limitation), or install into an environment with a shorter, absolute path", _0.user_display())]
+    PathTooLong(PathBuf),
+    #[error("Invalid entry point `{0}` (expected `module` or `module:function`)")]
+    InvalidEntryPoint(String),
+    #[error("Multiple distributions provide `{}`, which can't be bundled", _0.user_display())]
+    BundleConflict(PathBuf),
+    #[error("Failed to write bundle to: {}", _0.user_display())]
+    BundleZip(PathBuf, #[source] ZipError),
     #[error("Multiple .dist-info directories found: {0}")]
     MultipleDistInfo(String),
     #[error(
@@ -106,4 +172,9 @@ pub enum Error {
     MismatchedName(PackageName, PackageName),
     #[error("Wheel version does not match filename: {0} != {1}")]
     MismatchedVersion(Version, Version),
+    #[error("The file `{}` would overwrite a file installed by `{existing_package}`", path.user_display())]
+    FileConflict {
+        path: PathBuf,
+        existing_package: PackageName,
+    },
 }
@@ -1,7 +1,7 @@
 //! Like `wheel.rs`, but for installing wheels that have already been unzipped, rather than
 //! reading from a zip file.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::SystemTime;
 
@@ -9,24 +9,31 @@ use fs_err as fs;
 use fs_err::{DirEntry, File};
 use reflink_copy as reflink;
 use tempfile::tempdir_in;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 use distribution_filename::WheelFilename;
 use pep440_rs::Version;
 use pypi_types::DirectUrl;
 use uv_normalize::PackageName;
 
+use crate::journal::Journal;
+use crate::preflight::{Filesystem, Preflight};
+use crate::record::RecordEntry;
 use crate::script::{scripts_from_ini, Script};
+use crate::warnings::{missing_hashes, outside_scheme};
 use crate::wheel::{
-    extra_dist_info, install_data, parse_metadata, parse_wheel_file, read_record_file,
-    write_script_entrypoints, LibKind,
+    extra_dist_info, install_data, parse_metadata, parse_wheel_file, read_record_file, set_mtimes,
+    source_date_epoch, write_script_entrypoints, LibKind,
 };
-use crate::{Error, Layout};
+use crate::{Error, InstallWarning, Layout};
 
 /// Install the given wheel to the given venv
 ///
 /// The caller must ensure that the wheel is compatible to the environment.
 ///
+/// If `requested` is set, the distribution is marked as having been requested directly (rather
+/// than installed as a dependency) by way of a `REQUESTED` file, per PEP 376.
+///
 /// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#installing-a-wheel-distribution-1-0-py32-none-any-whl>
 ///
 /// Wheel 1.0: <https://www.python.org/dev/peps/pep-0427/>
@@ -36,25 +43,14 @@ pub fn install_wheel(
     wheel: impl AsRef<Path>,
     filename: &WheelFilename,
     direct_url: Option<&DirectUrl>,
+    requested: bool,
     installer: Option<&str>,
     link_mode: LinkMode,
-) -> Result<(), Error> {
+) -> Result<InstallSummary, Error> {
     let dist_info_prefix = find_dist_info(&wheel)?;
     let metadata = dist_info_metadata(&dist_info_prefix, &wheel)?;
     let (name, version) = parse_metadata(&dist_info_prefix, &metadata)?;
-
-    // Validate the wheel name and version.
-    {
-        let name = PackageName::from_str(&name)?;
-        if name != filename.name {
-            return Err(Error::MismatchedName(name, filename.name.clone()));
-        }
-
-        let version = Version::from_str(&version)?;
-        if version != filename.version {
-            return Err(Error::MismatchedVersion(version, filename.version.clone()));
-        }
-    }
+    validate_name_and_version(&name, &version, filename)?;
 
     // We're going step by step though
     // https://packaging.python.org/en/latest/specifications/binary-distribution-format/#installing-a-wheel-distribution-1-0-py32-none-any-whl
@@ -73,20 +69,101 @@ pub fn install_wheel(
         LibKind::Pure => &layout.scheme.purelib,
         LibKind::Plat => &layout.scheme.platlib,
     };
-    let num_unpacked = link_mode.link_wheel_files(site_packages, &wheel)?;
+
+    // Before linking any files, check that the wheel's paths can be represented on the target
+    // filesystem.
+    let wheel_record = {
+        let mut record_file = File::open(
+            wheel
+                .as_ref()
+                .join(format!("{dist_info_prefix}.dist-info/RECORD")),
+        )?;
+        read_record_file(&mut record_file)?
+    };
+    let preflight = Preflight::check(site_packages, &wheel_record, Filesystem::current())?;
+
+    // Note every file that the installation writes, and back up those that exist already, such
+    // that a partial installation can be rolled back.
+    let mut journal = Journal::new(site_packages)?;
+    match install_wheel_files(
+        layout,
+        site_packages,
+        wheel.as_ref(),
+        &dist_info_prefix,
+        &name,
+        &preflight,
+        direct_url,
+        requested,
+        installer,
+        link_mode,
+        &mut journal,
+    ) {
+        Ok(summary) => Ok(summary),
+        Err(err) => {
+            debug!(name, "Rolling back partial installation");
+            if let Err(rollback_err) = journal.rollback() {
+                warn!("Failed to roll back partial installation of {name}: {rollback_err}");
+            }
+            Err(err)
+        }
+    }
+}
+
+/// A summary of the changes made by [`install_wheel`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InstallSummary {
+    /// The `.dist-info` directory of the installed distribution.
+    pub dist_info: PathBuf,
+    /// The number of files linked from the unzipped wheel into `site-packages`.
+    pub files_linked: usize,
+    /// The number of entrypoint scripts (console and GUI) that were written.
+    pub scripts_written: usize,
+    /// The non-fatal anomalies that were tolerated during installation.
+    pub warnings: Vec<InstallWarning>,
+}
+
+/// Link the files from an unzipped wheel into `site-packages`, and write the entrypoints, data,
+/// and metadata, noting every file in the [`Journal`] before it's written.
+#[allow(clippy::too_many_arguments)]
+fn install_wheel_files(
+    layout: &Layout,
+    site_packages: &Path,
+    wheel: &Path,
+    dist_info_prefix: &str,
+    name: &str,
+    preflight: &Preflight,
+    direct_url: Option<&DirectUrl>,
+    requested: bool,
+    installer: Option<&str>,
+    link_mode: LinkMode,
+    journal: &mut Journal,
+) -> Result<InstallSummary, Error> {
+    for relative in wheel_files(wheel)? {
+        journal.save(&site_packages.join(relative))?;
+    }
+
+    // If any of the wheel's files would exceed the Windows path limit, link them via
+    // extended-length paths.
+    let num_unpacked = link_mode.link_wheel_files(
+        preflight.link_path(site_packages)?,
+        preflight.link_path(wheel)?,
+    )?;
     debug!(name, "Extracted {num_unpacked} files");
 
     // Read the RECORD file.
-    let mut record_file = File::open(
-        wheel
-            .as_ref()
-            .join(format!("{dist_info_prefix}.dist-info/RECORD")),
-    )?;
+    let mut record_file = File::open(wheel.join(format!("{dist_info_prefix}.dist-info/RECORD")))?;
     let mut record = read_record_file(&mut record_file)?;
 
+    let mut warnings = Vec::new();
+    missing_hashes(&record, &mut warnings);
+
     debug!(name, "Writing entrypoints");
     let (console_scripts, gui_scripts) =
-        parse_scripts(&wheel, &dist_info_prefix, None, layout.python_version.1)?;
+        parse_scripts(wheel, dist_info_prefix, None, layout.python_version.1)?;
+    journal.save_entrypoints(
+        &layout.scheme.scripts,
+        console_scripts.iter().chain(&gui_scripts),
+    )?;
     write_script_entrypoints(layout, site_packages, &console_scripts, &mut record, false)?;
     write_script_entrypoints(layout, site_packages, &gui_scripts, &mut record, true)?;
 
@@ -95,14 +172,21 @@ pub fn install_wheel(
     let data_dir = site_packages.join(format!("{dist_info_prefix}.data"));
     if data_dir.is_dir() {
         debug!(name, "Installing data");
+        for entry in &record {
+            if let Some(destination) = data_destination(layout, dist_info_prefix, name, &entry.path)
+            {
+                journal.save(&destination)?;
+            }
+        }
         install_data(
             layout,
             site_packages,
             &data_dir,
-            &name,
+            name,
             &console_scripts,
             &gui_scripts,
             &mut record,
+            &mut warnings,
         )?;
         // 2.c If applicable, update scripts starting with #!python to point to the correct interpreter.
         // Script are unsupported through data
@@ -111,36 +195,121 @@ pub fn install_wheel(
     } else {
         debug!(name, "No data");
     }
+    outside_scheme(layout, site_packages, &record, &mut warnings);
 
     debug!(name, "Writing extra metadata");
+    let dist_info_dir = site_packages.join(format!("{dist_info_prefix}.dist-info"));
+    for file_name in ["REQUESTED", "direct_url.json", "INSTALLER", "RECORD"] {
+        journal.save(&dist_info_dir.join(file_name))?;
+    }
     extra_dist_info(
         site_packages,
-        &dist_info_prefix,
-        true,
+        dist_info_prefix,
+        requested,
         direct_url,
         installer,
         &mut record,
     )?;
 
     debug!(name, "Writing record");
+    write_record(site_packages, dist_info_prefix, &mut record)?;
+
+    if let Some(mtime) = source_date_epoch() {
+        debug!(name, "Setting mtimes from `SOURCE_DATE_EPOCH`");
+        set_mtimes(site_packages, &record, mtime)?;
+    }
+
+    Ok(InstallSummary {
+        dist_info: dist_info_dir,
+        files_linked: num_unpacked,
+        scripts_written: console_scripts.len() + gui_scripts.len(),
+        warnings,
+    })
+}
+
+/// Validate that the name and version in the wheel's metadata match its filename.
+pub(crate) fn validate_name_and_version(
+    name: &str,
+    version: &str,
+    filename: &WheelFilename,
+) -> Result<(), Error> {
+    let name = PackageName::from_str(name)?;
+    if name != filename.name {
+        return Err(Error::MismatchedName(name, filename.name.clone()));
+    }
+
+    let version = Version::from_str(version)?;
+    if version != filename.version {
+        return Err(Error::MismatchedVersion(version, filename.version.clone()));
+    }
+
+    Ok(())
+}
+
+/// Sort the given entries and write them to the `RECORD` file of the installed `.dist-info`
+/// directory.
+pub(crate) fn write_record(
+    site_packages: &Path,
+    dist_info_prefix: &str,
+    record: &mut [RecordEntry],
+) -> Result<(), Error> {
     let mut record_writer = csv::WriterBuilder::new()
         .has_headers(false)
         .escape(b'"')
+        // Use `\n` line endings on all platforms, for a reproducible `RECORD`.
+        .terminator(csv::Terminator::Any(b'\n'))
         .from_path(site_packages.join(format!("{dist_info_prefix}.dist-info/RECORD")))?;
     record.sort();
-    for entry in record {
+    for entry in record.iter() {
         record_writer.serialize(entry)?;
     }
-
+    record_writer.flush()?;
     Ok(())
 }
 
+/// Return the path that a file in the wheel's `.data` directory is moved to when the wheel is
+/// installed, given its path relative to the root of the wheel.
+///
+/// Returns `None` for files outside the `.data` directory, and for unknown data types.
+pub(crate) fn data_destination(
+    layout: &Layout,
+    dist_info_prefix: &str,
+    name: &str,
+    path: &str,
+) -> Option<PathBuf> {
+    let (key, path) = path
+        .strip_prefix(dist_info_prefix)?
+        .strip_prefix(".data/")?
+        .split_once('/')?;
+    match key {
+        "data" => Some(layout.scheme.data.join(path)),
+        "headers" => Some(layout.scheme.include.join(name).join(path)),
+        "purelib" => Some(layout.scheme.purelib.join(path)),
+        "platlib" => Some(layout.scheme.platlib.join(path)),
+        "scripts" => Some(layout.scheme.scripts.join(path)),
+        _ => None,
+    }
+}
+
+/// Return the paths of the files in the unzipped wheel, relative to its root, in a stable order.
+pub(crate) fn wheel_files(wheel: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(wheel).sort_by_file_name() {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        files.push(entry.path().strip_prefix(wheel).unwrap().to_path_buf());
+    }
+    Ok(files)
+}
+
 /// Find the `dist-info` directory in an unzipped wheel.
 ///
 /// See: <https://github.com/PyO3/python-pkginfo-rs>
 ///
 /// See: <https://github.com/pypa/pip/blob/36823099a9cdd83261fdbc8c1d2a24fa2eea72ca/src/pip/_internal/utils/wheel.py#L38>
-fn find_dist_info(path: impl AsRef<Path>) -> Result<String, Error> {
+pub(crate) fn find_dist_info(path: impl AsRef<Path>) -> Result<String, Error> {
     // Iterate over `path` to find the `.dist-info` directory. It should be at the top-level.
     let Some(dist_info) = fs::read_dir(path.as_ref())?.find_map(|entry| {
         let entry = entry.ok()?;
@@ -171,7 +340,10 @@ fn find_dist_info(path: impl AsRef<Path>) -> Result<String, Error> {
 }
 
 /// Read the `dist-info` metadata from a directory.
-fn dist_info_metadata(dist_info_prefix: &str, wheel: impl AsRef<Path>) -> Result<Vec<u8>, Error> {
+pub(crate) fn dist_info_metadata(
+    dist_info_prefix: &str,
+    wheel: impl AsRef<Path>,
+) -> Result<Vec<u8>, Error> {
     let metadata_file = wheel
         .as_ref()
         .join(format!("{dist_info_prefix}.dist-info/METADATA"));
@@ -183,7 +355,7 @@ fn dist_info_metadata(dist_info_prefix: &str, wheel: impl AsRef<Path>) -> Result
 /// Returns (`script_name`, module, function)
 ///
 /// Extras are supposed to be ignored, which happens if you pass None for extras.
-fn parse_scripts(
+pub(crate) fn parse_scripts(
     wheel: impl AsRef<Path>,
     dist_info_prefix: &str,
     extras: Option<&[String]>,
@@ -236,6 +408,47 @@ impl LinkMode {
             Self::Hardlink => hardlink_wheel_files(site_packages, wheel),
         }
     }
+
+    /// Link a single file from an unzipped wheel to the given path, creating its parent
+    /// directories and atomically replacing any existing file.
+    ///
+    /// Like [`LinkMode::link_wheel_files`], falls back to copying if the file can't be cloned or
+    /// hard-linked.
+    pub(crate) fn link_file(self, from: &Path, to: &Path) -> Result<(), Error> {
+        let (Some(parent), Some(file_name)) = (to.parent(), to.file_name()) else {
+            return Err(Error::InvalidWheel(format!(
+                "Invalid file path: {}",
+                to.display()
+            )));
+        };
+        fs::create_dir_all(parent)?;
+
+        // Link to a temporary sibling of the target first, such that the existing file is never
+        // missing.
+        let tempfile = tempfile::Builder::new()
+            .prefix(&format!(".{}.", file_name.to_string_lossy()))
+            .suffix(".tmp")
+            .make_in(parent, |tempfile| match self {
+                Self::Clone => reflink::reflink_or_copy(from, tempfile).map(|_| ()),
+                Self::Copy => fs::copy(from, tempfile).map(|_| ()),
+                Self::Hardlink => match fs::hard_link(from, tempfile) {
+                    Ok(()) => Ok(()),
+                    // Let the builder retry with a different name.
+                    Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Err(err),
+                    Err(err) => {
+                        debug!(
+                            "Failed to hardlink `{}` to `{}`, copying instead: {err}",
+                            from.display(),
+                            to.display()
+                        );
+                        fs::copy(from, tempfile).map(|_| ())
+                    }
+                },
+            })?
+            .into_temp_path();
+        tempfile.persist(to).map_err(|err| err.error)?;
+        Ok(())
+    }
 }
 
 /// Extract a wheel by cloning all of its files into site packages. The files will be cloned
@@ -513,3 +726,225 @@ fn hardlink_wheel_files(
 
     Ok(count)
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use fs_err as fs;
+
+    use distribution_filename::WheelFilename;
+
+    use crate::test_utils::layout;
+    use crate::Error;
+
+    use super::{install_wheel, LinkMode};
+
+    #[test]
+    fn rollback() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let layout = layout(temp_dir.path());
+        let site_packages = layout.scheme.purelib.clone();
+        let scripts = layout.scheme.scripts.clone();
+
+        // A wheel with an unknown data directory, which fails after its files and entrypoints are
+        // written.
+        let wheel = temp_dir.path().join("foo-1.0.0");
+        for (path, contents) in [
+            ("foo/__init__.py", "new"),
+            ("foo/new.py", ""),
+            ("foo-1.0.0.data/unknown/file.txt", ""),
+            (
+                "foo-1.0.0.dist-info/METADATA",
+                "Metadata-Version: 2.1\nName: foo\nVersion: 1.0.0\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/WHEEL",
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/entry_points.txt",
+                "[console_scripts]\nfoo-existing = foo:main\nfoo-new = foo:main\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/RECORD",
+                "foo/__init__.py,,\nfoo/new.py,,\nfoo-1.0.0.data/unknown/file.txt,,\n\
+                 foo-1.0.0.dist-info/METADATA,,\nfoo-1.0.0.dist-info/WHEEL,,\n\
+                 foo-1.0.0.dist-info/entry_points.txt,,\nfoo-1.0.0.dist-info/RECORD,,\n",
+            ),
+        ] {
+            let path = wheel.join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, contents)?;
+        }
+
+        // Files that the wheel overwrites, which belong to something else.
+        fs::create_dir_all(site_packages.join("foo"))?;
+        fs::write(site_packages.join("foo/__init__.py"), "old")?;
+        fs::create_dir_all(&scripts)?;
+        fs::write(scripts.join("foo-existing"), "old")?;
+
+        let result = install_wheel(
+            &layout,
+            &wheel,
+            &WheelFilename::from_str("foo-1.0.0-py3-none-any.whl")?,
+            None,
+            true,
+            Some("uv"),
+            LinkMode::Copy,
+        );
+        assert!(matches!(result, Err(Error::InvalidWheel(_))), "{result:?}");
+
+        // The files created by the installation are removed...
+        assert!(!site_packages.join("foo/new.py").exists());
+        assert!(!site_packages.join("foo-1.0.0.dist-info").exists());
+        assert!(!site_packages.join("foo-1.0.0.data").exists());
+        assert!(!scripts.join("foo-new").exists());
+
+        // ...and the files that existed beforehand are restored.
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo/__init__.py"))?,
+            "old"
+        );
+        assert_eq!(fs::read_to_string(scripts.join("foo-existing"))?, "old");
+
+        Ok(())
+    }
+
+    #[test]
+    fn summary() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let layout = layout(temp_dir.path());
+        let site_packages = layout.scheme.purelib.clone();
+
+        // A wheel with two modules, and a console and a GUI entrypoint.
+        let wheel = temp_dir.path().join("foo-1.0.0");
+        for (path, contents) in [
+            ("foo/__init__.py", ""),
+            ("foo/cli.py", ""),
+            (
+                "foo-1.0.0.dist-info/METADATA",
+                "Metadata-Version: 2.1\nName: foo\nVersion: 1.0.0\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/WHEEL",
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/entry_points.txt",
+                "[console_scripts]\nfoo = foo.cli:main\n\n[gui_scripts]\nfoo-gui = foo.cli:gui\n",
+            ),
+            (
+                "foo-1.0.0.dist-info/RECORD",
+                "foo/__init__.py,,\nfoo/cli.py,,\nfoo-1.0.0.dist-info/METADATA,,\n\
+                 foo-1.0.0.dist-info/WHEEL,,\nfoo-1.0.0.dist-info/entry_points.txt,,\n\
+                 foo-1.0.0.dist-info/RECORD,,\n",
+            ),
+        ] {
+            let path = wheel.join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, contents)?;
+        }
+
+        let summary = install_wheel(
+            &layout,
+            &wheel,
+            &WheelFilename::from_str("foo-1.0.0-py3-none-any.whl")?,
+            None,
+            true,
+            Some("uv"),
+            LinkMode::Copy,
+        )?;
+
+        // Every file in the unpacked wheel is linked, including the `.dist-info` files.
+        assert_eq!(summary.files_linked, 6);
+        assert_eq!(summary.scripts_written, 2);
+        assert_eq!(summary.dist_info, site_packages.join("foo-1.0.0.dist-info"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn script_permissions() -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        use crate::InstallWarning;
+
+        let temp_dir = tempfile::tempdir()?;
+        let layout = layout(temp_dir.path());
+        let scripts = layout.scheme.scripts.clone();
+
+        // A wheel with executable and non-executable scripts, with and without a `#!python`
+        // shebang to rewrite.
+        let wheel = temp_dir.path().join("foo-1.0.0");
+        for (path, contents, mode) in [
+            ("foo/__init__.py", "", 0o644),
+            ("foo-1.0.0.data/scripts/rewritten", "#!python\n", 0o755),
+            (
+                "foo-1.0.0.data/scripts/rewritten-plain",
+                "#!python\n",
+                0o644,
+            ),
+            ("foo-1.0.0.data/scripts/moved", "#!/bin/sh\n", 0o755),
+            ("foo-1.0.0.data/scripts/moved-plain", "#!/bin/sh\n", 0o644),
+            (
+                "foo-1.0.0.dist-info/METADATA",
+                "Metadata-Version: 2.1\nName: foo\nVersion: 1.0.0\n",
+                0o644,
+            ),
+            (
+                "foo-1.0.0.dist-info/WHEEL",
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                0o644,
+            ),
+            (
+                "foo-1.0.0.dist-info/RECORD",
+                "foo/__init__.py,,\nfoo-1.0.0.data/scripts/rewritten,,\n\
+                 foo-1.0.0.data/scripts/rewritten-plain,,\nfoo-1.0.0.data/scripts/moved,,\n\
+                 foo-1.0.0.data/scripts/moved-plain,,\nfoo-1.0.0.dist-info/METADATA,,\n\
+                 foo-1.0.0.dist-info/WHEEL,,\nfoo-1.0.0.dist-info/RECORD,,\n",
+                0o644,
+            ),
+        ] {
+            let path = wheel.join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, contents)?;
+            fs::set_permissions(&path, std::fs::Permissions::from_mode(mode))?;
+        }
+        fs::create_dir_all(&scripts)?;
+
+        let summary = install_wheel(
+            &layout,
+            &wheel,
+            &WheelFilename::from_str("foo-1.0.0-py3-none-any.whl")?,
+            None,
+            true,
+            Some("uv"),
+            LinkMode::Copy,
+        )?;
+
+        // Only the scripts that were shipped without an executable bit are reported...
+        let mut not_executable = summary
+            .warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                InstallWarning::NotExecutable(path) => Some(path.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        not_executable.sort();
+        assert_eq!(
+            not_executable,
+            vec![scripts.join("moved-plain"), scripts.join("rewritten-plain")]
+        );
+
+        // ...but every script is installed as executable.
+        for script in ["rewritten", "rewritten-plain", "moved", "moved-plain"] {
+            let mode = fs::metadata(scripts.join(script))?.permissions().mode();
+            assert_eq!(mode & 0o777, 0o755, "{script}");
+        }
+
+        Ok(())
+    }
+}
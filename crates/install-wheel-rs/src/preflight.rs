@@ -0,0 +1,221 @@
+//! Checks run against a wheel's `RECORD` before any of its files are linked into the environment,
+//! such that paths the target filesystem can't represent fail the installation up front, rather
+//! than leaving a partial or silently corrupted install behind.
+
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashMap;
+
+use crate::record::RecordEntry;
+use crate::uninstall::normalize_path;
+use crate::Error;
+
+/// The maximum length of a path on Windows, including the terminating null character, unless the
+/// path is in the extended-length (`\\?\`) form.
+///
+/// See: <https://learn.microsoft.com/en-us/windows/win32/fileio/maximum-file-path-limitation>
+const MAX_PATH: usize = 260;
+
+/// The filesystem properties that the preflight checks account for.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Filesystem {
+    /// Whether paths are limited to [`MAX_PATH`] characters (i.e., Windows).
+    pub(crate) max_path: bool,
+    /// Whether filenames that differ only by case refer to the same file (i.e., Windows and,
+    /// by default, macOS).
+    pub(crate) case_insensitive: bool,
+}
+
+impl Filesystem {
+    /// The properties of the filesystem on the current platform.
+    pub(crate) fn current() -> Self {
+        Self {
+            max_path: cfg!(windows),
+            case_insensitive: cfg!(any(windows, target_os = "macos")),
+        }
+    }
+}
+
+/// The outcome of the preflight checks for a wheel.
+#[derive(Debug, Default)]
+pub(crate) struct Preflight {
+    /// Whether any file would exceed [`MAX_PATH`] once installed, such that the wheel must be
+    /// linked using extended-length paths.
+    long_paths: bool,
+}
+
+impl Preflight {
+    /// Check the files listed in a wheel's `RECORD` against the given filesystem, as they would
+    /// be installed into `site_packages`.
+    ///
+    /// Returns an error if two files differ only by case on a case-insensitive filesystem.
+    pub(crate) fn check(
+        site_packages: &Path,
+        record: &[RecordEntry],
+        filesystem: Filesystem,
+    ) -> Result<Self, Error> {
+        let mut preflight = Self::default();
+        // A map from the case-folded path of every file and directory to its original spelling.
+        let mut seen: FxHashMap<String, &str> = FxHashMap::default();
+
+        for entry in record {
+            if filesystem.case_insensitive {
+                let prefixes = entry
+                    .path
+                    .match_indices('/')
+                    .map(|(index, _)| &entry.path[..index])
+                    .chain(std::iter::once(entry.path.as_str()));
+                for prefix in prefixes {
+                    match seen.entry(prefix.to_lowercase()) {
+                        Entry::Occupied(existing) => {
+                            if *existing.get() != prefix {
+                                return Err(Error::CaseCollision(
+                                    (*existing.get()).to_string(),
+                                    prefix.to_string(),
+                                ));
+                            }
+                        }
+                        Entry::Vacant(vacant) => {
+                            vacant.insert(prefix);
+                        }
+                    }
+                }
+            }
+
+            if filesystem.max_path && !preflight.long_paths {
+                let path = normalize_path(&site_packages.join(&entry.path));
+                if path.as_os_str().len() >= MAX_PATH {
+                    preflight.long_paths = true;
+                }
+            }
+        }
+
+        Ok(preflight)
+    }
+
+    /// Return the form of the given path to use when linking the wheel's files: the
+    /// extended-length (`\\?\`) form if any path would exceed [`MAX_PATH`], and the path itself
+    /// otherwise.
+    pub(crate) fn link_path<'a>(&self, path: &'a Path) -> Result<Cow<'a, Path>, Error> {
+        if !self.long_paths {
+            return Ok(Cow::Borrowed(path));
+        }
+        extended_length_path(path)
+            .map(Cow::Owned)
+            .ok_or_else(|| Error::PathTooLong(path.to_path_buf()))
+    }
+}
+
+/// Convert an absolute Windows path into its extended-length (`\\?\`) form, which isn't subject
+/// to [`MAX_PATH`].
+///
+/// Extended-length paths are passed to the filesystem verbatim, so `.` and `..` components are
+/// resolved, and forward slashes replaced, beforehand. Returns `None` if the path is relative.
+fn extended_length_path(path: &Path) -> Option<PathBuf> {
+    let path = normalize_path(path);
+    let path = path.to_string_lossy().replace('/', "\\");
+    if path.starts_with(r"\\?\") {
+        Some(PathBuf::from(path))
+    } else if let Some(unc) = path.strip_prefix(r"\\") {
+        Some(PathBuf::from(format!(r"\\?\UNC\{unc}")))
+    } else if path.as_bytes().get(1) == Some(&b':')
+        && path.as_bytes().get(2) == Some(&b'\\')
+        && path.as_bytes()[0].is_ascii_alphabetic()
+    {
+        Some(PathBuf::from(format!(r"\\?\{path}")))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use crate::record::RecordEntry;
+    use crate::Error;
+
+    use super::{extended_length_path, Filesystem, Preflight};
+
+    fn entries(paths: &[&str]) -> Vec<RecordEntry> {
+        paths
+            .iter()
+            .map(|path| RecordEntry {
+                path: (*path).to_string(),
+                hash: None,
+                size: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn case_collision() {
+        let filesystem = Filesystem {
+            max_path: false,
+            case_insensitive: true,
+        };
+
+        let record = entries(&["foo/__init__.py", "foo/Bar.py", "foo-1.0.dist-info/RECORD"]);
+        assert!(Preflight::check(Path::new("site-packages"), &record, filesystem).is_ok());
+
+        let record = entries(&["foo/bar.py", "foo/Bar.py"]);
+        assert!(matches!(
+            Preflight::check(Path::new("site-packages"), &record, filesystem),
+            Err(Error::CaseCollision(..))
+        ));
+
+        // Directories that differ only by case would be merged.
+        let record = entries(&["Foo/__init__.py", "foo/__init__.py"]);
+        assert!(matches!(
+            Preflight::check(Path::new("site-packages"), &record, filesystem),
+            Err(Error::CaseCollision(..))
+        ));
+
+        // On a case-sensitive filesystem, both files can be installed.
+        let filesystem = Filesystem {
+            max_path: false,
+            case_insensitive: false,
+        };
+        assert!(Preflight::check(Path::new("site-packages"), &record, filesystem).is_ok());
+    }
+
+    #[test]
+    fn long_paths() -> Result<(), Error> {
+        let filesystem = Filesystem {
+            max_path: true,
+            case_insensitive: false,
+        };
+        let site_packages = Path::new("C:/env/Lib/site-packages");
+
+        let preflight =
+            Preflight::check(site_packages, &entries(&["foo/__init__.py"]), filesystem)?;
+        assert_eq!(preflight.link_path(site_packages)?, site_packages);
+
+        let long = format!("foo/{}.py", "a".repeat(250));
+        let preflight = Preflight::check(site_packages, &entries(&[&long]), filesystem)?;
+        assert_eq!(
+            preflight.link_path(site_packages)?,
+            PathBuf::from(r"\\?\C:\env\Lib\site-packages")
+        );
+        assert!(matches!(
+            preflight.link_path(Path::new("site-packages")),
+            Err(Error::PathTooLong(..))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn extended_length() {
+        assert_eq!(
+            extended_length_path(Path::new("C:/env/Lib/../Lib/site-packages")),
+            Some(PathBuf::from(r"\\?\C:\env\Lib\site-packages"))
+        );
+        assert_eq!(
+            extended_length_path(Path::new(r"\\server\share\env")),
+            Some(PathBuf::from(r"\\?\UNC\server\share\env"))
+        );
+        assert_eq!(extended_length_path(Path::new("env/Lib")), None);
+    }
+}
@@ -0,0 +1,40 @@
+//! Helpers shared between the unit tests of this crate.
+
+use std::path::Path;
+
+use fs_err as fs;
+use sha2::Sha256;
+
+use pypi_types::Scheme;
+
+use crate::uninstall::hash_reader;
+use crate::{Error, Layout, Shebang};
+
+/// Create a POSIX [`Layout`] for Python 3.12, rooted at `root`, with its `site-packages` directory
+/// at `lib/python3.12/site-packages`.
+pub(crate) fn layout(root: &Path) -> Layout {
+    let site_packages = root.join("lib").join("python3.12").join("site-packages");
+    Layout {
+        sys_executable: root.join("bin").join("python"),
+        python_version: (3, 12),
+        os_name: "posix".to_string(),
+        scheme: Scheme {
+            purelib: site_packages.clone(),
+            platlib: site_packages,
+            scripts: root.join("bin"),
+            data: root.to_path_buf(),
+            include: root.join("include"),
+        },
+        shebang: Shebang::default(),
+    }
+}
+
+/// Write `contents` to the file at `path` (relative to `root`), returning the corresponding
+/// `RECORD` line.
+pub(crate) fn record_line(root: &Path, path: &str, contents: &str) -> Result<String, Error> {
+    let hash = hash_reader::<Sha256>(&mut contents.as_bytes())?;
+    let target = root.join(path);
+    fs::create_dir_all(target.parent().unwrap())?;
+    fs::write(target, contents)?;
+    Ok(format!("{path},sha256={hash},{}\n", contents.len()))
+}
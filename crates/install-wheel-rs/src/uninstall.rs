@@ -1,39 +1,146 @@
 use std::collections::BTreeSet;
+use std::io;
 use std::path::{Component, Path, PathBuf};
 
+use data_encoding::BASE64URL_NOPAD;
 use fs_err as fs;
+use sha2::{Digest, Sha256, Sha384, Sha512};
 use tracing::debug;
 
+use crate::record::RecordEntry;
+use crate::warnings::missing_hashes;
 use crate::wheel::read_record_file;
-use crate::Error;
+use crate::{Error, InstallWarning};
 
 /// Uninstall the wheel represented by the given `dist_info` directory.
 pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
-    let Some(site_packages) = dist_info.parent() else {
-        return Err(Error::BrokenVenv(
-            "dist-info directory is not in a site-packages directory".to_string(),
-        ));
+    let site_packages = site_packages(dist_info)?;
+    let record = read_dist_info_record(dist_info)?;
+    let mut uninstall = remove_record_entries(site_packages, &record)?;
+    uninstall.file_count += clean_pth_files(site_packages, &record)?;
+    missing_hashes(&record, &mut uninstall.warnings);
+    Ok(uninstall)
+}
+
+/// Uninstall the wheel represented by the given `dist_info` directory, even if its `RECORD` file
+/// is missing or damaged.
+///
+/// If the `RECORD` can be read, this is equivalent to [`uninstall_wheel`]. Otherwise, the files to
+/// remove are inferred from the top-level modules listed in `top_level.txt` (or, absent that, the
+/// module named after the distribution), along with the `.dist-info` directory itself. Files
+/// installed outside of `site-packages` (like scripts) can't be inferred, and are left in place.
+pub fn repair_uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
+    match uninstall_wheel(dist_info) {
+        Err(Error::MissingRecord(_) | Error::RecordCsv(_)) => {}
+        result => return result,
+    }
+
+    debug!(
+        "Inferring installed files for {} from its top-level modules",
+        dist_info.display()
+    );
+    let site_packages = site_packages(dist_info)?;
+    let record = infer_record_entries(site_packages, dist_info)?;
+    let mut uninstall = remove_record_entries(site_packages, &record)?;
+    uninstall.file_count += clean_pth_files(site_packages, &record)?;
+    Ok(uninstall)
+}
+
+/// Infer the `RECORD` entries for a distribution whose `RECORD` file can't be read, based on the
+/// names of the files in `site-packages`.
+fn infer_record_entries(site_packages: &Path, dist_info: &Path) -> Result<Vec<RecordEntry>, Error> {
+    let dist_info_name = dist_info
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or(Error::MissingDistInfo)?;
+
+    // Read the top-level modules from `top_level.txt`, which is written by `setuptools`. Otherwise,
+    // assume that the distribution provides a single module named after itself (e.g.,
+    // `typing_extensions` for `typing_extensions-4.10.0.dist-info`).
+    let modules = match fs::read_to_string(dist_info.join("top_level.txt")) {
+        Ok(contents) => contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            // Nested entries (e.g., `google/protobuf`) are only removed as part of their parent.
+            .filter_map(|line| line.split('/').next())
+            .map(ToString::to_string)
+            .collect::<BTreeSet<_>>(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let name = dist_info_name
+                .split_once('-')
+                .map_or(dist_info_name, |(name, _)| name);
+            BTreeSet::from([name.replace(['-', '.'], "_")])
+        }
+        Err(err) => return Err(err.into()),
     };
 
-    // Read the RECORD file.
-    let record = {
-        let record_path = dist_info.join("RECORD");
-        let mut record_file = match fs::File::open(&record_path) {
-            Ok(record_file) => record_file,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                return Err(Error::MissingRecord(record_path));
-            }
-            Err(err) => return Err(err.into()),
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(site_packages)? {
+        let entry = entry?;
+        let Some(file_name) = entry.file_name().to_str().map(ToString::to_string) else {
+            continue;
         };
-        read_record_file(&mut record_file)?
+        if !modules
+            .iter()
+            .any(|module| is_module_file(&file_name, module))
+        {
+            continue;
+        }
+
+        // A directory without an `__init__.py` may be a namespace package shared with other
+        // distributions, so it can't be removed wholesale.
+        if entry.file_type()?.is_dir() && !entry.path().join("__init__.py").is_file() {
+            debug!(
+                "Skipping possible namespace package: {}",
+                entry.path().display()
+            );
+            continue;
+        }
+
+        entries.push(file_name);
+    }
+    entries.push(dist_info_name.to_string());
+
+    Ok(entries
+        .into_iter()
+        .map(|path| RecordEntry {
+            path,
+            hash: None,
+            size: None,
+        })
+        .collect())
+}
+
+/// Returns `true` if the file or directory named `file_name` in `site-packages` provides the
+/// top-level module `module`: as a package directory, a `.py` file, or an extension module (e.g.,
+/// `foo.cpython-312-x86_64-linux-gnu.so`).
+fn is_module_file(file_name: &str, module: &str) -> bool {
+    let Some(rest) = file_name
+        .get(..module.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(module))
+        .map(|_| &file_name[module.len()..])
+    else {
+        return false;
     };
+    rest.is_empty()
+        || rest == ".py"
+        || (rest.starts_with('.') && (rest.ends_with(".so") || rest.ends_with(".pyd")))
+}
 
+/// Remove the files listed in the given RECORD entries (relative to `site-packages`), along with
+/// any directories that are left empty.
+pub(crate) fn remove_record_entries(
+    site_packages: &Path,
+    record: &[RecordEntry],
+) -> Result<Uninstall, Error> {
     let mut file_count = 0usize;
     let mut dir_count = 0usize;
+    let mut warnings = Vec::new();
 
     // Uninstall the files, keeping track of any directories that are left empty.
     let mut visited = BTreeSet::new();
-    for entry in &record {
+    for entry in record {
         let path = site_packages.join(&entry.path);
         match fs::remove_file(&path) {
             Ok(()) => {
@@ -43,13 +150,15 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
                     visited.insert(normalize_path(parent));
                 }
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                warnings.push(InstallWarning::MissingFile(normalize_path(&path)));
+            }
             Err(err) => match fs::remove_dir_all(&path) {
                 Ok(()) => {
                     debug!("Removed directory: {}", path.display());
                     dir_count += 1;
                 }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
                 Err(_) => return Err(err.into()),
             },
         }
@@ -82,7 +191,7 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
                     debug!("Removed directory: {}", pycache.display());
                     dir_count += 1;
                 }
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
                 Err(err) => return Err(err.into()),
             }
 
@@ -90,7 +199,7 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
             // previous iteration.
             let mut read_dir = match fs::read_dir(path) {
                 Ok(read_dir) => read_dir,
-                Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => break,
                 Err(err) => return Err(err.into()),
             };
 
@@ -115,21 +224,310 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
     Ok(Uninstall {
         file_count,
         dir_count,
+        warnings,
+    })
+}
+
+/// Return the `.pth` files in `site-packages` that may refer to the paths removed along with the
+/// given RECORD entries: `easy-install.pth`, and any `.pth` files listed in the RECORD itself.
+///
+/// Other `.pth` files belong to other distributions, and so are never modified (which would
+/// invalidate the hashes in their RECORD).
+pub(crate) fn pth_files(site_packages: &Path, record: &[RecordEntry]) -> Vec<PathBuf> {
+    let mut pth_files = vec![site_packages.join("easy-install.pth")];
+    pth_files.extend(
+        record
+            .iter()
+            .map(|entry| Path::new(&entry.path))
+            .filter(|path| {
+                path.extension().is_some_and(|extension| extension == "pth")
+                    && path.parent() == Some(Path::new(""))
+                    && path != Path::new("easy-install.pth")
+            })
+            .map(|path| site_packages.join(path)),
+    );
+    pth_files
+}
+
+/// Remove any entries in the [`pth_files`] for the given RECORD entries that point to paths
+/// removed along with them, deleting any `.pth` files that are left without entries.
+///
+/// An entry is considered stale if it no longer exists, and contained one of the removed files.
+/// Returns the number of `.pth` files that were removed.
+pub(crate) fn clean_pth_files(
+    site_packages: &Path,
+    record: &[RecordEntry],
+) -> Result<usize, Error> {
+    let removed = record
+        .iter()
+        .map(|entry| normalize_path(&site_packages.join(&entry.path)))
+        .collect::<Vec<_>>();
+
+    let is_stale = |line: &str| {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("import ")
+            || line.starts_with("import\t")
+        {
+            return false;
+        }
+        let path = normalize_path(&site_packages.join(line));
+        !path.exists() && removed.iter().any(|removed| removed.starts_with(&path))
+    };
+
+    let mut file_count = 0usize;
+    for path in pth_files(site_packages, record) {
+        if !path.is_file() {
+            continue;
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        let new_contents = contents
+            .split_inclusive('\n')
+            .filter(|line| !is_stale(line))
+            .collect::<String>();
+        if new_contents == contents {
+            continue;
+        }
+
+        // If only comments and blank lines remain, remove the file entirely.
+        if new_contents
+            .lines()
+            .map(str::trim)
+            .all(|line| line.is_empty() || line.starts_with('#'))
+        {
+            fs::remove_file(&path)?;
+            debug!("Removed file: {}", path.display());
+            file_count += 1;
+        } else {
+            fs::write(&path, new_contents)?;
+            debug!("Removed stale entries from: {}", path.display());
+        }
+    }
+
+    Ok(file_count)
+}
+
+/// Uninstall the legacy editable (i.e., `setup.py develop`) represented by the given `.egg-link`
+/// file.
+///
+/// Removes the `.egg-link` file itself, along with the corresponding entry in the adjacent
+/// `easy-install.pth` file. The project directory is left untouched.
+///
+/// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L534-L552>
+pub fn uninstall_legacy_editable(egg_link: &Path) -> Result<Uninstall, Error> {
+    let mut file_count = 0usize;
+
+    // The first non-empty line of the `.egg-link` file is the path to the project.
+    let contents = fs::read_to_string(egg_link)?;
+    let target = contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .ok_or_else(|| Error::InvalidEggLink(egg_link.to_path_buf()))?;
+
+    match fs::remove_file(egg_link) {
+        Ok(()) => {
+            debug!("Removed file: {}", egg_link.display());
+            file_count += 1;
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let site_packages = egg_link.parent().ok_or_else(|| {
+        Error::BrokenVenv("`.egg-link` file is not in a site-packages directory".to_string())
+    })?;
+    let pth_path = site_packages.join("easy-install.pth");
+    let pth_contents = match fs::read_to_string(&pth_path) {
+        Ok(pth_contents) => pth_contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(Uninstall {
+                file_count,
+                dir_count: 0,
+                warnings: Vec::new(),
+            });
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Remove the matching entry, preserving everything else (including line endings).
+    let new_contents = pth_contents
+        .split_inclusive('\n')
+        .filter(|line| line.trim() != target)
+        .collect::<String>();
+    if new_contents != pth_contents {
+        fs::write(&pth_path, new_contents)?;
+        debug!("Removed entry from: {}", pth_path.display());
+    }
+
+    Ok(Uninstall {
+        file_count,
+        dir_count: 0,
+        warnings: Vec::new(),
     })
 }
 
+/// Compute the [`UninstallPlan`] for the wheel represented by the given `dist_info` directory,
+/// without modifying the filesystem.
+///
+/// Each entry in the `RECORD` file is classified based on its location, and checked against the
+/// filesystem to determine whether it's missing or has been modified since installation.
+pub fn uninstall_plan(dist_info: &Path) -> Result<UninstallPlan, Error> {
+    let site_packages = site_packages(dist_info)?;
+    let record = read_dist_info_record(dist_info)?;
+
+    let mut plan = UninstallPlan {
+        dist_info: dist_info.to_path_buf(),
+        ..UninstallPlan::default()
+    };
+
+    for entry in &record {
+        let path = normalize_path(&site_packages.join(&entry.path));
+
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                plan.missing.push(path);
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // Some installers (incorrectly) record directories in the `RECORD`; like `uninstall_wheel`,
+        // we remove them wholesale, so there's nothing to verify.
+        if metadata.is_file() {
+            if let Some(hash) = entry.hash.as_deref() {
+                if is_modified(&path, hash)? {
+                    plan.modified.push(path.clone());
+                }
+            }
+        }
+
+        if path.starts_with(site_packages) {
+            plan.package_files.push(path);
+        } else if is_script(&path) {
+            plan.scripts.push(path);
+        } else {
+            plan.data_files.push(path);
+        }
+    }
+
+    Ok(plan)
+}
+
+/// The set of changes that uninstalling a wheel would make to the environment.
+///
+/// Returned by [`uninstall_plan`]; no files are removed until [`uninstall_wheel`] is called.
+#[derive(Debug, Default, Clone)]
+pub struct UninstallPlan {
+    /// The `.dist-info` directory of the distribution.
+    pub dist_info: PathBuf,
+    /// The existing files within `site-packages` that would be removed, including the
+    /// `.dist-info` directory's contents.
+    pub package_files: Vec<PathBuf>,
+    /// The existing entrypoints and scripts (e.g., in `bin` or `Scripts`) that would be removed.
+    pub scripts: Vec<PathBuf>,
+    /// The existing files outside of `site-packages` (e.g., headers and data files) that would be
+    /// removed.
+    pub data_files: Vec<PathBuf>,
+    /// The files listed in the `RECORD` that no longer exist.
+    pub missing: Vec<PathBuf>,
+    /// The files whose contents no longer match the hash in the `RECORD`. These files are also
+    /// included in their respective category above.
+    pub modified: Vec<PathBuf>,
+}
+
+impl UninstallPlan {
+    /// Returns an iterator over all existing files that would be removed.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.package_files
+            .iter()
+            .chain(&self.scripts)
+            .chain(&self.data_files)
+            .map(PathBuf::as_path)
+    }
+
+    /// Returns `true` if the plan would not remove any files.
+    pub fn is_empty(&self) -> bool {
+        self.files().next().is_none()
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Uninstall {
     /// The number of files that were removed during the uninstallation.
     pub file_count: usize,
     /// The number of directories that were removed during the uninstallation.
     pub dir_count: usize,
+    /// The non-fatal anomalies that were tolerated during the uninstallation.
+    pub warnings: Vec<InstallWarning>,
+}
+
+/// Return the `site-packages` directory containing the given `dist_info` directory.
+pub(crate) fn site_packages(dist_info: &Path) -> Result<&Path, Error> {
+    dist_info.parent().ok_or_else(|| {
+        Error::BrokenVenv("dist-info directory is not in a site-packages directory".to_string())
+    })
+}
+
+/// Read the `RECORD` file from the given `dist_info` directory.
+pub(crate) fn read_dist_info_record(dist_info: &Path) -> Result<Vec<RecordEntry>, Error> {
+    let record_path = dist_info.join("RECORD");
+    let mut record_file = match fs::File::open(&record_path) {
+        Ok(record_file) => record_file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    read_record_file(&mut record_file)
+}
+
+/// Returns `true` if the path is located in a scripts directory (i.e., `bin` on Unix, or
+/// `Scripts` on Windows).
+fn is_script(path: &Path) -> bool {
+    path.parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "bin" || name == "Scripts")
+}
+
+/// Returns `true` if the file at the given path doesn't match the `RECORD` hash (e.g.,
+/// `sha256=x_c8nmc4Huc-lKEsAXj78ZiyqSJ9hJ71j7vltY67icw`).
+///
+/// Hashes with an unsupported or malformed algorithm are assumed to match.
+pub(crate) fn is_modified(path: &Path, hash: &str) -> Result<bool, Error> {
+    let Some((algorithm, expected)) = hash.split_once('=') else {
+        return Ok(false);
+    };
+    let mut file = fs::File::open(path)?;
+    let actual = match algorithm {
+        "sha256" => hash_reader::<Sha256>(&mut file)?,
+        "sha384" => hash_reader::<Sha384>(&mut file)?,
+        "sha512" => hash_reader::<Sha512>(&mut file)?,
+        _ => {
+            debug!(
+                "Unsupported RECORD hash algorithm `{algorithm}` for: {}",
+                path.display()
+            );
+            return Ok(false);
+        }
+    };
+    Ok(actual != expected)
+}
+
+/// Compute the URL-safe, unpadded base64 digest of the given reader, as used in `RECORD` files.
+pub(crate) fn hash_reader<D: Digest + io::Write>(reader: &mut impl io::Read) -> io::Result<String> {
+    let mut hasher = D::new();
+    io::copy(reader, &mut hasher)?;
+    Ok(BASE64URL_NOPAD.encode(&hasher.finalize()))
 }
 
 /// Normalize a path, removing things like `.` and `..`.
 ///
 /// Source: <https://github.com/rust-lang/cargo/blob/b48c41aedbd69ee3990d62a0e2006edbb506a480/crates/cargo-util/src/paths.rs#L76C1-L109C2>
-fn normalize_path(path: &Path) -> PathBuf {
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
     let mut components = path.components().peekable();
     let mut ret = if let Some(c @ Component::Prefix(..)) = components.peek().copied() {
         components.next();
@@ -155,3 +553,260 @@ fn normalize_path(path: &Path) -> PathBuf {
     }
     ret
 }
+
+#[cfg(test)]
+mod test {
+    use fs_err as fs;
+
+    use crate::record::RecordEntry;
+    use crate::test_utils::record_line;
+    use crate::{Error, InstallWarning};
+
+    use super::{
+        is_module_file, remove_record_entries, repair_uninstall_wheel, uninstall_legacy_editable,
+        uninstall_plan, uninstall_wheel,
+    };
+
+    #[test]
+    fn plan_classifies_entries() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        let dist_info = site_packages.join("foo-1.0.0.dist-info");
+        fs::create_dir_all(&dist_info)?;
+
+        let mut record = String::new();
+        record.push_str(&record_line(&site_packages, "foo/__init__.py", "")?);
+        record.push_str(&record_line(&site_packages, "foo/bar.py", "x = 1")?);
+        record.push_str(&record_line(&site_packages, "../../bin/foo", "#!python")?);
+        record.push_str(&record_line(&site_packages, "../../share/foo.txt", "data")?);
+        record.push_str(&record_line(&site_packages, "foo/missing.py", "")?);
+        record.push_str("foo-1.0.0.dist-info/RECORD,,\n");
+        fs::write(dist_info.join("RECORD"), record)?;
+
+        // Modify one file, and remove another.
+        fs::write(site_packages.join("foo/bar.py"), "x = 2")?;
+        fs::remove_file(site_packages.join("foo/missing.py"))?;
+
+        let plan = uninstall_plan(&dist_info)?;
+        assert_eq!(
+            plan.package_files,
+            vec![
+                site_packages.join("foo/__init__.py"),
+                site_packages.join("foo/bar.py"),
+                dist_info.join("RECORD"),
+            ]
+        );
+        assert_eq!(plan.scripts, vec![temp_dir.path().join("bin/foo")]);
+        assert_eq!(plan.data_files, vec![temp_dir.path().join("share/foo.txt")]);
+        assert_eq!(plan.missing, vec![site_packages.join("foo/missing.py")]);
+        assert_eq!(plan.modified, vec![site_packages.join("foo/bar.py")]);
+
+        // The plan shouldn't touch the filesystem.
+        assert!(site_packages.join("foo/__init__.py").exists());
+        assert!(dist_info.join("RECORD").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn remove_partial_install() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        record_line(&site_packages, "foo/__init__.py", "")?;
+        record_line(&site_packages, "foo/sub/bar.py", "x = 1")?;
+        record_line(&site_packages, "foo-1.0.0.dist-info/METADATA", "")?;
+        record_line(&site_packages, "other/__init__.py", "")?;
+
+        let record = ["foo/__init__.py", "foo/sub/bar.py", "foo-1.0.0.dist-info"]
+            .into_iter()
+            .map(|path| RecordEntry {
+                path: path.to_string(),
+                hash: None,
+                size: None,
+            })
+            .collect::<Vec<_>>();
+        let uninstall = remove_record_entries(&site_packages, &record)?;
+        assert_eq!(uninstall.file_count, 2);
+
+        // Empty directories are pruned, but unrelated packages are left alone.
+        assert!(!site_packages.join("foo").exists());
+        assert!(!site_packages.join("foo-1.0.0.dist-info").exists());
+        assert!(site_packages.join("other/__init__.py").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn remove_stale_pth_entries() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        let dist_info = site_packages.join("foo-1.0.0.dist-info");
+        fs::create_dir_all(&dist_info)?;
+
+        let mut record = String::new();
+        record.push_str(&record_line(&site_packages, "foo_src/foo/__init__.py", "")?);
+        record.push_str(&record_line(&site_packages, "foo/__init__.py", "")?);
+        record.push_str(&record_line(&site_packages, "foo.pth", "./foo_src\n")?);
+        record.push_str("foo-1.0.0.dist-info/RECORD,,\n");
+        fs::write(dist_info.join("RECORD"), record)?;
+
+        // `easy-install.pth`, which is shared between distributions, and a `.pth` file that
+        // belongs to another distribution.
+        fs::create_dir_all(site_packages.join("bar_src"))?;
+        fs::write(
+            site_packages.join("easy-install.pth"),
+            "# Comment\nfoo_src\nbar_src\nimport bar\n",
+        )?;
+        fs::write(site_packages.join("bar.pth"), "foo_src\nbar_src\n")?;
+
+        let uninstall = uninstall_wheel(&dist_info)?;
+        assert_eq!(uninstall.file_count, 4);
+
+        // Stale entries are removed from `easy-install.pth`...
+        assert_eq!(
+            fs::read_to_string(site_packages.join("easy-install.pth"))?,
+            "# Comment\nbar_src\nimport bar\n"
+        );
+        assert!(!site_packages.join("foo.pth").exists());
+        assert!(!site_packages.join("foo_src").exists());
+
+        // ...but other distributions' `.pth` files are left untouched.
+        assert_eq!(
+            fs::read_to_string(site_packages.join("bar.pth"))?,
+            "foo_src\nbar_src\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn uninstall_warnings() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        let dist_info = site_packages.join("foo-1.0.0.dist-info");
+        fs::create_dir_all(&dist_info)?;
+
+        let mut record = String::new();
+        record.push_str(&record_line(&site_packages, "foo/__init__.py", "")?);
+        record.push_str(&record_line(&site_packages, "foo/missing.py", "")?);
+        record_line(&site_packages, "foo/unhashed.py", "")?;
+        record.push_str("foo/unhashed.py,,\n");
+        record.push_str("foo-1.0.0.dist-info/RECORD,,\n");
+        fs::write(dist_info.join("RECORD"), record)?;
+        fs::remove_file(site_packages.join("foo/missing.py"))?;
+
+        let uninstall = uninstall_wheel(&dist_info)?;
+        assert_eq!(uninstall.file_count, 3);
+        assert_eq!(
+            uninstall.warnings,
+            vec![
+                InstallWarning::MissingFile(site_packages.join("foo").join("missing.py")),
+                InstallWarning::MissingHash("foo/unhashed.py".to_string()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn legacy_editable() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path();
+        let egg_link = site_packages.join("foo.egg-link");
+        fs::write(&egg_link, "/home/ferris/foo\n.\n")?;
+        fs::write(
+            site_packages.join("easy-install.pth"),
+            "/home/ferris/bar\n/home/ferris/foo\r\n/home/ferris/baz\n",
+        )?;
+
+        let uninstall = uninstall_legacy_editable(&egg_link)?;
+        assert_eq!(uninstall.file_count, 1);
+        assert!(!egg_link.exists());
+        assert_eq!(
+            fs::read_to_string(site_packages.join("easy-install.pth"))?,
+            "/home/ferris/bar\n/home/ferris/baz\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn module_files() {
+        assert!(is_module_file("foo", "foo"));
+        assert!(is_module_file("foo.py", "foo"));
+        assert!(is_module_file("Foo", "foo"));
+        assert!(is_module_file("foo.cpython-312-x86_64-linux-gnu.so", "foo"));
+        assert!(is_module_file("foo.cp312-win_amd64.pyd", "foo"));
+        assert!(!is_module_file("foobar", "foo"));
+        assert!(!is_module_file("foo-1.0.0.dist-info", "foo"));
+        assert!(!is_module_file("foo.pth", "foo"));
+    }
+
+    #[test]
+    fn repair_without_record() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        let dist_info = site_packages.join("foo-1.0.0.dist-info");
+        record_line(&site_packages, "foo/__init__.py", "")?;
+        record_line(&site_packages, "foo/sub/bar.py", "x = 1")?;
+        record_line(
+            &site_packages,
+            "_foo_speedups.cpython-312-x86_64-linux-gnu.so",
+            "",
+        )?;
+        record_line(&site_packages, "foo-1.0.0.dist-info/METADATA", "")?;
+        record_line(
+            &site_packages,
+            "foo-1.0.0.dist-info/top_level.txt",
+            "_foo_speedups\nfoo\n",
+        )?;
+        record_line(&site_packages, "foobar/__init__.py", "")?;
+
+        // Without a RECORD, a regular uninstall fails.
+        assert!(matches!(
+            uninstall_wheel(&dist_info),
+            Err(Error::MissingRecord(_))
+        ));
+
+        repair_uninstall_wheel(&dist_info)?;
+        assert!(!site_packages.join("foo").exists());
+        assert!(!site_packages
+            .join("_foo_speedups.cpython-312-x86_64-linux-gnu.so")
+            .exists());
+        assert!(!dist_info.exists());
+        assert!(site_packages.join("foobar/__init__.py").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_damaged_record() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        let dist_info = site_packages.join("typing_extensions-4.10.0.dist-info");
+        record_line(&site_packages, "typing_extensions.py", "")?;
+        record_line(
+            &site_packages,
+            "typing_extensions-4.10.0.dist-info/METADATA",
+            "",
+        )?;
+        record_line(
+            &site_packages,
+            "typing_extensions-4.10.0.dist-info/RECORD",
+            "typing_extensions.py,sha256=abc,not-a-size\n",
+        )?;
+        record_line(&site_packages, "other.py", "")?;
+
+        assert!(matches!(
+            uninstall_wheel(&dist_info),
+            Err(Error::RecordCsv(_))
+        ));
+
+        repair_uninstall_wheel(&dist_info)?;
+        assert!(!site_packages.join("typing_extensions.py").exists());
+        assert!(!dist_info.exists());
+        assert!(site_packages.join("other.py").exists());
+
+        Ok(())
+    }
+}
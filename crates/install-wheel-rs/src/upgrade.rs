@@ -0,0 +1,534 @@
+//! Upgrade an installed distribution in place, touching only the files that differ between the
+//! installed `RECORD` and the new wheel.
+
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use fs_err::File;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::{debug, instrument, warn};
+
+use distribution_filename::WheelFilename;
+use pypi_types::DirectUrl;
+
+use crate::journal::Journal;
+use crate::linker::{
+    data_destination, dist_info_metadata, find_dist_info, parse_scripts, validate_name_and_version,
+    wheel_files, write_record, LinkMode,
+};
+use crate::preflight::{Filesystem, Preflight};
+use crate::uninstall::{
+    clean_pth_files, is_modified, normalize_path, pth_files, read_dist_info_record,
+    remove_record_entries, site_packages,
+};
+use crate::warnings::{missing_hashes, outside_scheme};
+use crate::wheel::{
+    extra_dist_info, install_data, parse_metadata, parse_wheel_file, read_record_file, set_mtimes,
+    source_date_epoch, write_script_entrypoints, LibKind,
+};
+use crate::{Error, InstallWarning, Layout};
+
+/// A summary of the changes made by [`upgrade_wheel`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UpgradeSummary {
+    /// The number of files linked from the unzipped wheel, because they were added or changed.
+    pub files_linked: usize,
+    /// The number of installed files that were left in place, because they're identical in the
+    /// new wheel.
+    pub files_unchanged: usize,
+    /// The number of installed files that were removed, because they're absent from the new
+    /// wheel.
+    pub files_removed: usize,
+    /// The number of entrypoint scripts (console and GUI) that were written.
+    pub scripts_written: usize,
+    /// The non-fatal anomalies that were tolerated during the upgrade.
+    pub warnings: Vec<InstallWarning>,
+}
+
+/// Replace the distribution installed at the given `.dist-info` directory with the given
+/// (unzipped) wheel, as an alternative to [`uninstall_wheel`](crate::uninstall_wheel) followed by
+/// [`install_wheel`](crate::linker::install_wheel).
+///
+/// Rather than removing and re-linking every file, the installed `RECORD` is compared against the
+/// wheel's `RECORD`: files with the same path and hash (that haven't been modified since they
+/// were installed) are left in place, new and changed files are linked (replacing the installed
+/// file atomically), and files that are absent from the new wheel are removed. Entrypoints and
+/// `.data` files are always rewritten.
+///
+/// The `.dist-info` directory is swapped last, such that the installed distribution remains
+/// discoverable until every other file is in place. Every file is backed up before it's replaced
+/// or removed, such that if the upgrade fails part way, the installed distribution is restored.
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, fields(wheel = % wheel.as_ref().display()))]
+pub fn upgrade_wheel(
+    layout: &Layout,
+    installed: &Path,
+    wheel: impl AsRef<Path>,
+    filename: &WheelFilename,
+    direct_url: Option<&DirectUrl>,
+    requested: bool,
+    installer: Option<&str>,
+    link_mode: LinkMode,
+) -> Result<UpgradeSummary, Error> {
+    let wheel = wheel.as_ref();
+    let dist_info_prefix = find_dist_info(wheel)?;
+    let metadata = dist_info_metadata(&dist_info_prefix, wheel)?;
+    let (name, version) = parse_metadata(&dist_info_prefix, &metadata)?;
+    validate_name_and_version(&name, &version, filename)?;
+    let name = name.as_str();
+
+    let wheel_text = fs::read_to_string(wheel.join(format!("{dist_info_prefix}.dist-info/WHEEL")))?;
+    let site_packages_dir = match parse_wheel_file(&wheel_text)? {
+        LibKind::Pure => &layout.scheme.purelib,
+        LibKind::Plat => &layout.scheme.platlib,
+    };
+
+    let mut journal = Journal::new(site_packages_dir)?;
+    match upgrade_files(
+        layout,
+        installed,
+        wheel,
+        site_packages_dir,
+        &dist_info_prefix,
+        name,
+        direct_url,
+        requested,
+        installer,
+        link_mode,
+        &mut journal,
+    ) {
+        Ok(summary) => Ok(summary),
+        Err(err) => {
+            debug!(name, "Rolling back partial upgrade");
+            if let Err(rollback_err) = journal.rollback() {
+                warn!("Failed to roll back partial upgrade of {name}: {rollback_err}");
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Upgrade the installed distribution's files in place, noting every file in the [`Journal`]
+/// before it's written or removed.
+#[allow(clippy::too_many_arguments)]
+fn upgrade_files(
+    layout: &Layout,
+    installed: &Path,
+    wheel: &Path,
+    site_packages_dir: &Path,
+    dist_info_prefix: &str,
+    name: &str,
+    direct_url: Option<&DirectUrl>,
+    requested: bool,
+    installer: Option<&str>,
+    link_mode: LinkMode,
+    journal: &mut Journal,
+) -> Result<UpgradeSummary, Error> {
+    let mut record = {
+        let mut record_file =
+            File::open(wheel.join(format!("{dist_info_prefix}.dist-info/RECORD")))?;
+        read_record_file(&mut record_file)?
+    };
+    let preflight = Preflight::check(site_packages_dir, &record, Filesystem::current())?;
+
+    // Index the installed files by their absolute path, since the installed distribution may live
+    // in a different `site-packages` directory (e.g., `purelib` rather than `platlib`).
+    let installed_site_packages = site_packages(installed)?;
+    let installed_record = read_dist_info_record(installed)?;
+    let installed_hashes = installed_record
+        .iter()
+        .map(|entry| {
+            (
+                normalize_path(&installed_site_packages.join(&entry.path)),
+                entry.hash.clone(),
+            )
+        })
+        .collect::<FxHashMap<_, _>>();
+    let wheel_hashes = record
+        .iter()
+        .map(|entry| {
+            (
+                normalize_path(&site_packages_dir.join(&entry.path)),
+                entry.hash.clone(),
+            )
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    let mut summary = UpgradeSummary::default();
+    missing_hashes(&record, &mut summary.warnings);
+    let link = |relative: &Path,
+                summary: &mut UpgradeSummary,
+                journal: &mut Journal|
+     -> Result<(), Error> {
+        let target = site_packages_dir.join(relative);
+        let key = normalize_path(&target);
+        if let Some(Some(hash)) = wheel_hashes.get(&key) {
+            if installed_hashes
+                .get(&key)
+                .is_some_and(|installed| installed.as_ref() == Some(hash))
+                && target.is_file()
+                && !is_modified(&target, hash)?
+            {
+                summary.files_unchanged += 1;
+                return Ok(());
+            }
+        }
+        journal.save(&target)?;
+        link_mode.link_file(
+            &preflight.link_path(&wheel.join(relative))?,
+            &preflight.link_path(&target)?,
+        )?;
+        summary.files_linked += 1;
+        Ok(())
+    };
+
+    // Link the new and changed files, leaving the `.dist-info` directory for last.
+    let dist_info_dir = PathBuf::from(format!("{dist_info_prefix}.dist-info"));
+    for relative in wheel_files(wheel)? {
+        if !relative.starts_with(&dist_info_dir) {
+            link(&relative, &mut summary, journal)?;
+        }
+    }
+    debug!(
+        name,
+        "Linked {} file(s), {} unchanged", summary.files_linked, summary.files_unchanged
+    );
+
+    debug!(name, "Writing entrypoints");
+    let (console_scripts, gui_scripts) =
+        parse_scripts(wheel, dist_info_prefix, None, layout.python_version.1)?;
+    journal.save_entrypoints(
+        &layout.scheme.scripts,
+        console_scripts.iter().chain(&gui_scripts),
+    )?;
+    write_script_entrypoints(
+        layout,
+        site_packages_dir,
+        &console_scripts,
+        &mut record,
+        false,
+    )?;
+    write_script_entrypoints(layout, site_packages_dir, &gui_scripts, &mut record, true)?;
+    summary.scripts_written = console_scripts.len() + gui_scripts.len();
+
+    let data_dir = site_packages_dir.join(format!("{dist_info_prefix}.data"));
+    if data_dir.is_dir() {
+        debug!(name, "Installing data");
+        for entry in &record {
+            if let Some(destination) = data_destination(layout, dist_info_prefix, name, &entry.path)
+            {
+                journal.save(&destination)?;
+            }
+        }
+        install_data(
+            layout,
+            site_packages_dir,
+            &data_dir,
+            name,
+            &console_scripts,
+            &gui_scripts,
+            &mut record,
+            &mut summary.warnings,
+        )?;
+        fs::remove_dir_all(data_dir)?;
+    }
+    outside_scheme(layout, site_packages_dir, &record, &mut summary.warnings);
+
+    // Determine the installed files that are absent from the new wheel, separating out those in
+    // the installed `.dist-info` directory.
+    let installed_dist_info = normalize_path(installed);
+    let (stale_metadata, stale_files): (Vec<_>, Vec<_>) = {
+        let new_paths = record
+            .iter()
+            .map(|entry| normalize_path(&site_packages_dir.join(&entry.path)))
+            .collect::<FxHashSet<_>>();
+        installed_record
+            .into_iter()
+            .filter(|entry| {
+                !new_paths.contains(&normalize_path(&installed_site_packages.join(&entry.path)))
+            })
+            .partition(|entry| {
+                normalize_path(&installed_site_packages.join(&entry.path))
+                    .starts_with(&installed_dist_info)
+            })
+    };
+
+    debug!(name, "Removing {} stale file(s)", stale_files.len());
+    for entry in &stale_files {
+        journal.save(&installed_site_packages.join(&entry.path))?;
+    }
+    let uninstall = remove_record_entries(installed_site_packages, &stale_files)?;
+    summary.files_removed += uninstall.file_count;
+    summary.warnings.extend(uninstall.warnings);
+    for path in pth_files(installed_site_packages, &stale_files) {
+        journal.save(&path)?;
+    }
+    summary.files_removed += clean_pth_files(installed_site_packages, &stale_files)?;
+
+    // Swap in the new `.dist-info` directory.
+    debug!(name, "Writing metadata");
+    for relative in wheel_files(wheel)? {
+        // The `RECORD` is rewritten below, so it must not be linked to the wheel.
+        if relative.starts_with(&dist_info_dir) && relative != dist_info_dir.join("RECORD") {
+            link(&relative, &mut summary, journal)?;
+        }
+    }
+    for file_name in ["REQUESTED", "direct_url.json", "INSTALLER", "RECORD"] {
+        journal.save(&site_packages_dir.join(&dist_info_dir).join(file_name))?;
+    }
+    extra_dist_info(
+        site_packages_dir,
+        dist_info_prefix,
+        requested,
+        direct_url,
+        installer,
+        &mut record,
+    )?;
+    write_record(site_packages_dir, dist_info_prefix, &mut record)?;
+    for entry in &stale_metadata {
+        journal.save(&installed_site_packages.join(&entry.path))?;
+    }
+    let uninstall = remove_record_entries(installed_site_packages, &stale_metadata)?;
+    summary.files_removed += uninstall.file_count;
+    summary.warnings.extend(uninstall.warnings);
+
+    if let Some(mtime) = source_date_epoch() {
+        debug!(name, "Setting mtimes from `SOURCE_DATE_EPOCH`");
+        set_mtimes(site_packages_dir, &record, mtime)?;
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+
+    use fs_err as fs;
+
+    use distribution_filename::WheelFilename;
+
+    use crate::linker::LinkMode;
+    use crate::test_utils::{layout, record_line};
+    use crate::{Error, Layout};
+
+    use super::upgrade_wheel;
+
+    /// Write the given files to `root`, returning the corresponding `RECORD` lines.
+    fn write_files(root: &Path, files: &[(&str, &str)]) -> Result<String, Error> {
+        let mut record = String::new();
+        for (path, contents) in files {
+            record.push_str(&record_line(root, path, contents)?);
+        }
+        Ok(record)
+    }
+
+    /// Create an unzipped wheel for `foo` with the given version and module files.
+    fn wheel(root: &Path, version: &str, files: &[(&str, &str)]) -> Result<PathBuf, Error> {
+        let wheel = root.join(format!("foo-{version}"));
+        let dist_info = format!("foo-{version}.dist-info");
+        let mut record = write_files(&wheel, files)?;
+        record.push_str(&write_files(
+            &wheel,
+            &[
+                (
+                    &format!("{dist_info}/METADATA"),
+                    &format!("Metadata-Version: 2.1\nName: foo\nVersion: {version}\n"),
+                ),
+                (
+                    &format!("{dist_info}/WHEEL"),
+                    "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        )?);
+        record.push_str(&format!("{dist_info}/RECORD,,\n"));
+        fs::write(wheel.join(format!("{dist_info}/RECORD")), record)?;
+        Ok(wheel)
+    }
+
+    /// Install the given (unzipped) wheel for `foo`.
+    fn install(layout: &Layout, wheel: &Path, version: &str) -> Result<(), Error> {
+        crate::linker::install_wheel(
+            layout,
+            wheel,
+            &WheelFilename::from_str(&format!("foo-{version}-py3-none-any.whl"))?,
+            None,
+            true,
+            Some("uv"),
+            LinkMode::Copy,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let layout = layout(temp_dir.path());
+        fs::create_dir_all(&layout.scheme.purelib)?;
+        let site_packages = layout.scheme.purelib.clone();
+
+        // Install the old version.
+        let old = wheel(
+            temp_dir.path(),
+            "1.0.0",
+            &[
+                ("foo/__init__.py", ""),
+                ("foo/changed.py", "x = 1"),
+                ("foo/removed.py", ""),
+                ("foo/stale/__init__.py", ""),
+            ],
+        )?;
+        install(&layout, &old, "1.0.0")?;
+
+        let new = wheel(
+            temp_dir.path(),
+            "2.0.0",
+            &[
+                ("foo/__init__.py", ""),
+                ("foo/changed.py", "x = 2"),
+                ("foo/added.py", ""),
+            ],
+        )?;
+        let summary = upgrade_wheel(
+            &layout,
+            &site_packages.join("foo-1.0.0.dist-info"),
+            &new,
+            &WheelFilename::from_str("foo-2.0.0-py3-none-any.whl")?,
+            None,
+            false,
+            Some("uv"),
+            LinkMode::Copy,
+        )?;
+
+        // `__init__.py` is unchanged; `METADATA` and `WHEEL` are linked into the new `.dist-info`.
+        assert_eq!(summary.files_unchanged, 1);
+        assert_eq!(summary.files_linked, 4);
+        // `removed.py`, `stale/__init__.py`, and the old `METADATA`, `WHEEL`, `RECORD`,
+        // `REQUESTED`, and `INSTALLER`.
+        assert_eq!(summary.files_removed, 7);
+        assert_eq!(summary.warnings, vec![]);
+
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo/changed.py"))?,
+            "x = 2"
+        );
+        assert!(site_packages.join("foo/added.py").is_file());
+        assert!(!site_packages.join("foo/removed.py").exists());
+        assert!(!site_packages.join("foo/stale").exists());
+        assert!(!site_packages.join("foo-1.0.0.dist-info").exists());
+        assert!(!site_packages.join("foo-2.0.0.dist-info/REQUESTED").exists());
+        assert!(site_packages
+            .join("foo-2.0.0.dist-info/INSTALLER")
+            .is_file());
+
+        // The new `RECORD` covers every installed file, such that the upgrade can be undone.
+        let uninstall = crate::uninstall_wheel(&site_packages.join("foo-2.0.0.dist-info"))?;
+        assert_eq!(uninstall.file_count, 7);
+        assert!(!site_packages.join("foo").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_modified() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let layout = layout(temp_dir.path());
+        fs::create_dir_all(&layout.scheme.purelib)?;
+        let site_packages = layout.scheme.purelib.clone();
+
+        let old = wheel(temp_dir.path(), "1.0.0", &[("foo/__init__.py", "x = 1")])?;
+        install(&layout, &old, "1.0.0")?;
+
+        // Modify the installed file, which has the same hash in the new wheel.
+        fs::write(site_packages.join("foo/__init__.py"), "x = 'modified'")?;
+
+        let new = wheel(temp_dir.path(), "2.0.0", &[("foo/__init__.py", "x = 1")])?;
+        let summary = upgrade_wheel(
+            &layout,
+            &site_packages.join("foo-1.0.0.dist-info"),
+            &new,
+            &WheelFilename::from_str("foo-2.0.0-py3-none-any.whl")?,
+            None,
+            false,
+            Some("uv"),
+            LinkMode::Copy,
+        )?;
+
+        // The modified file is replaced, rather than left in place.
+        assert_eq!(summary.files_unchanged, 0);
+        assert_eq!(summary.files_linked, 3);
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo/__init__.py"))?,
+            "x = 1"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn upgrade_failure() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let layout = layout(temp_dir.path());
+        fs::create_dir_all(&layout.scheme.purelib)?;
+        let site_packages = layout.scheme.purelib.clone();
+
+        let old = wheel(
+            temp_dir.path(),
+            "1.0.0",
+            &[
+                ("foo/__init__.py", ""),
+                ("foo/changed.py", "x = 1"),
+                ("foo/removed.py", ""),
+            ],
+        )?;
+        install(&layout, &old, "1.0.0")?;
+
+        // The unknown data directory fails the upgrade after the new and changed files are linked.
+        let new = wheel(
+            temp_dir.path(),
+            "2.0.0",
+            &[
+                ("foo/__init__.py", ""),
+                ("foo/changed.py", "x = 2"),
+                ("foo/added/__init__.py", ""),
+                ("foo-2.0.0.data/unknown/file.txt", ""),
+            ],
+        )?;
+        let result = upgrade_wheel(
+            &layout,
+            &site_packages.join("foo-1.0.0.dist-info"),
+            &new,
+            &WheelFilename::from_str("foo-2.0.0-py3-none-any.whl")?,
+            None,
+            false,
+            Some("uv"),
+            LinkMode::Copy,
+        );
+        assert!(matches!(result, Err(Error::InvalidWheel(_))), "{result:?}");
+
+        // The old version is restored...
+        assert_eq!(
+            fs::read_to_string(site_packages.join("foo/changed.py"))?,
+            "x = 1"
+        );
+        assert!(site_packages.join("foo/removed.py").is_file());
+        assert!(!site_packages.join("foo/added").exists());
+        assert!(!site_packages.join("foo-2.0.0.data").exists());
+        assert!(!site_packages.join("foo-2.0.0.dist-info").exists());
+
+        // ...without leaving any backups behind.
+        let mut entries = fs::read_dir(&site_packages)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().to_string()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        entries.sort();
+        assert_eq!(entries, vec!["foo", "foo-1.0.0.dist-info"]);
+
+        // The old version can still be uninstalled cleanly.
+        let uninstall = crate::uninstall_wheel(&site_packages.join("foo-1.0.0.dist-info"))?;
+        assert_eq!(uninstall.file_count, 8);
+        assert!(!site_packages.join("foo").exists());
+
+        Ok(())
+    }
+}
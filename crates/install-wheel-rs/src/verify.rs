@@ -0,0 +1,176 @@
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use fs_err as fs;
+use rustc_hash::FxHashSet;
+use walkdir::WalkDir;
+
+use crate::uninstall::{is_modified, normalize_path, read_dist_info_record, site_packages};
+use crate::Error;
+
+/// Verify the installed files of the wheel represented by the given `dist_info` directory against
+/// its `RECORD`.
+///
+/// Every file listed in the `RECORD` is re-hashed to detect files that are missing or have been
+/// modified since installation. In addition, the directories owned by the distribution (its
+/// `.dist-info` directory, and any top-level package whose `__init__.py` it records) are scanned
+/// for orphaned files, i.e., files that aren't listed in the `RECORD`. Compiled bytecode is
+/// ignored, as it's generated at runtime.
+///
+/// Top-level directories without a recorded `__init__.py` (like PEP 420 namespace packages) may be
+/// shared with other distributions, and so aren't scanned.
+pub fn verify_wheel(dist_info: &Path) -> Result<Verification, Error> {
+    let site_packages = normalize_path(site_packages(dist_info)?);
+    let record = read_dist_info_record(dist_info)?;
+
+    let mut verification = Verification {
+        dist_info: dist_info.to_path_buf(),
+        ..Verification::default()
+    };
+
+    let mut recorded = FxHashSet::default();
+    let mut owned = vec![normalize_path(dist_info)];
+    for entry in &record {
+        let path = normalize_path(&site_packages.join(&entry.path));
+        recorded.insert(path.clone());
+
+        // A top-level package is owned by the distribution if the distribution provides its
+        // `__init__.py`.
+        if let Ok(relative) = path.strip_prefix(&site_packages) {
+            let mut components = relative.components();
+            if let (Some(Component::Normal(package)), Some(Component::Normal(init)), None) =
+                (components.next(), components.next(), components.next())
+            {
+                if init == "__init__.py" {
+                    owned.push(site_packages.join(package));
+                }
+            }
+        }
+
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                verification.missing.push(path);
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if metadata.is_file() {
+            if let Some(hash) = entry.hash.as_deref() {
+                if is_modified(&path, hash)? {
+                    verification.modified.push(path);
+                }
+            }
+        }
+    }
+
+    for directory in owned {
+        // If the package directory is gone entirely, its files are already reported as missing.
+        if !directory.is_dir() {
+            continue;
+        }
+        for entry in WalkDir::new(&directory)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != "__pycache__")
+        {
+            let entry = entry?;
+            if entry.file_type().is_dir() {
+                continue;
+            }
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "pyc" || extension == "pyo")
+            {
+                continue;
+            }
+            if !recorded.contains(path) {
+                verification.orphaned.push(path.to_path_buf());
+            }
+        }
+    }
+
+    Ok(verification)
+}
+
+/// The result of verifying an installed wheel against its `RECORD`.
+///
+/// Returned by [`verify_wheel`].
+#[derive(Debug, Default, Clone)]
+pub struct Verification {
+    /// The `.dist-info` directory of the distribution.
+    pub dist_info: PathBuf,
+    /// The files listed in the `RECORD` that no longer exist.
+    pub missing: Vec<PathBuf>,
+    /// The files whose contents no longer match the hash in the `RECORD`.
+    pub modified: Vec<PathBuf>,
+    /// The files in directories owned by the distribution that aren't listed in the `RECORD`.
+    pub orphaned: Vec<PathBuf>,
+}
+
+impl Verification {
+    /// Returns `true` if the installed files match the `RECORD` exactly.
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty() && self.orphaned.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fs_err as fs;
+
+    use crate::test_utils::record_line;
+    use crate::Error;
+
+    use super::verify_wheel;
+
+    #[test]
+    fn verify_entries() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("lib").join("site-packages");
+        let dist_info = site_packages.join("foo-1.0.0.dist-info");
+        fs::create_dir_all(&dist_info)?;
+
+        let mut record = String::new();
+        record.push_str(&record_line(&site_packages, "foo/__init__.py", "")?);
+        record.push_str(&record_line(&site_packages, "foo/bar.py", "x = 1")?);
+        record.push_str(&record_line(&site_packages, "foo/missing.py", "")?);
+        record.push_str(&record_line(&site_packages, "namespace/baz.py", "")?);
+        record.push_str("foo-1.0.0.dist-info/RECORD,,\n");
+        fs::write(dist_info.join("RECORD"), record)?;
+
+        let verification = verify_wheel(&dist_info)?;
+        assert!(verification.is_valid());
+
+        // Modify one file, remove another, and add some unrecorded files.
+        fs::write(site_packages.join("foo/bar.py"), "x = 2")?;
+        fs::remove_file(site_packages.join("foo/missing.py"))?;
+        fs::write(site_packages.join("foo/extra.py"), "")?;
+        fs::create_dir_all(site_packages.join("foo/__pycache__"))?;
+        fs::write(
+            site_packages.join("foo/__pycache__/bar.cpython-312.pyc"),
+            "",
+        )?;
+        fs::write(site_packages.join("namespace/other.py"), "")?;
+
+        let verification = verify_wheel(&dist_info)?;
+        assert_eq!(
+            verification.missing,
+            vec![site_packages.join("foo/missing.py")]
+        );
+        assert_eq!(
+            verification.modified,
+            vec![site_packages.join("foo/bar.py")]
+        );
+
+        // Bytecode, and files in namespace packages, aren't considered orphaned.
+        assert_eq!(
+            verification.orphaned,
+            vec![site_packages.join("foo/extra.py")]
+        );
+
+        Ok(())
+    }
+}
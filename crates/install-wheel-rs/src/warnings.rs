@@ -0,0 +1,152 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use uv_fs::Simplified;
+
+use crate::record::RecordEntry;
+use crate::uninstall::normalize_path;
+use crate::Layout;
+
+/// A non-fatal anomaly that was tolerated while installing or uninstalling a distribution.
+///
+/// Warnings are collected in the [`InstallSummary`](crate::linker::InstallSummary) and
+/// [`Uninstall`](crate::Uninstall) results, such that callers can log them, or fail on them, as
+/// appropriate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallWarning {
+    /// A `RECORD` entry has no hash, so the file can't be verified (or, when uninstalling,
+    /// checked for modifications).
+    MissingHash(String),
+    /// A `RECORD` entry refers to a file that doesn't exist.
+    MissingFile(PathBuf),
+    /// A script in the wheel's `.data/scripts` directory wasn't executable, and was made
+    /// executable when it was installed.
+    NotExecutable(PathBuf),
+    /// A `RECORD` entry refers to a file outside of the installation scheme's directories.
+    OutsideScheme(PathBuf),
+}
+
+impl Display for InstallWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHash(path) => write!(f, "RECORD entry has no hash: `{path}`"),
+            Self::MissingFile(path) => {
+                write!(
+                    f,
+                    "RECORD entry refers to a missing file: `{}`",
+                    path.user_display()
+                )
+            }
+            Self::NotExecutable(path) => {
+                write!(
+                    f,
+                    "Script was not executable, and has been made executable: `{}`",
+                    path.user_display()
+                )
+            }
+            Self::OutsideScheme(path) => {
+                write!(
+                    f,
+                    "RECORD entry is outside of the installation scheme: `{}`",
+                    path.user_display()
+                )
+            }
+        }
+    }
+}
+
+/// Report every `RECORD` entry that lacks a hash, other than those that are exempt: the `RECORD`
+/// itself, its signatures, and compiled bytecode.
+///
+/// See: <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#the-record-file>
+pub(crate) fn missing_hashes(record: &[RecordEntry], warnings: &mut Vec<InstallWarning>) {
+    for entry in record {
+        if entry.hash.is_some() {
+            continue;
+        }
+        let file_name = entry.path.rsplit('/').next().unwrap_or(&entry.path);
+        if matches!(file_name, "RECORD" | "RECORD.jws" | "RECORD.p7s")
+            || file_name.ends_with(".pyc")
+        {
+            continue;
+        }
+        warnings.push(InstallWarning::MissingHash(entry.path.clone()));
+    }
+}
+
+/// Report every `RECORD` entry (relative to `site_packages`) that resolves to a path outside of
+/// the directories of the [`Layout`]'s scheme.
+pub(crate) fn outside_scheme(
+    layout: &Layout,
+    site_packages: &Path,
+    record: &[RecordEntry],
+    warnings: &mut Vec<InstallWarning>,
+) {
+    let roots = [
+        &layout.scheme.purelib,
+        &layout.scheme.platlib,
+        &layout.scheme.scripts,
+        &layout.scheme.data,
+        &layout.scheme.include,
+    ]
+    .map(|root| normalize_path(root));
+    for entry in record {
+        let path = normalize_path(&site_packages.join(&entry.path));
+        if !roots.iter().any(|root| path.starts_with(root)) {
+            warnings.push(InstallWarning::OutsideScheme(path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::record::RecordEntry;
+    use crate::test_utils::layout;
+
+    use super::{missing_hashes, outside_scheme, InstallWarning};
+
+    fn entry(path: &str, hash: Option<&str>) -> RecordEntry {
+        RecordEntry {
+            path: path.to_string(),
+            hash: hash.map(ToString::to_string),
+            size: None,
+        }
+    }
+
+    #[test]
+    fn warnings() {
+        let root = PathBuf::from("/venv");
+        let layout = layout(&root);
+        let site_packages = layout.scheme.purelib.clone();
+        let record = [
+            entry(
+                "foo/__init__.py",
+                Some("sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU"),
+            ),
+            entry("foo/bar.py", None),
+            entry("foo/__pycache__/bar.cpython-312.pyc", None),
+            entry("foo-1.0.0.dist-info/RECORD", None),
+            entry(
+                "../../../bin/foo",
+                Some("sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU"),
+            ),
+            entry(
+                "../../../../etc/foo.conf",
+                Some("sha256=47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU"),
+            ),
+        ];
+
+        let mut warnings = Vec::new();
+        missing_hashes(&record, &mut warnings);
+        outside_scheme(&layout, &site_packages, &record, &mut warnings);
+        assert_eq!(
+            warnings,
+            vec![
+                InstallWarning::MissingHash("foo/bar.py".to_string()),
+                InstallWarning::OutsideScheme(PathBuf::from("/etc/foo.conf")),
+            ]
+        );
+    }
+}
@@ -4,12 +4,13 @@ use std::path::{Path, PathBuf};
 use std::{env, io, iter};
 
 use data_encoding::BASE64URL_NOPAD;
+use filetime::FileTime;
 use fs_err as fs;
 use fs_err::{DirEntry, File};
 use mailparse::MailHeaderMap;
 use rustc_hash::FxHashMap;
 use sha2::{Digest, Sha256};
-use tracing::{instrument, warn};
+use tracing::{debug, instrument, warn};
 use walkdir::WalkDir;
 use zip::write::FileOptions;
 use zip::ZipWriter;
@@ -19,7 +20,8 @@ use uv_fs::Simplified;
 
 use crate::record::RecordEntry;
 use crate::script::Script;
-use crate::{Error, Layout};
+use crate::uninstall::normalize_path;
+use crate::{Error, InstallWarning, Layout, Shebang};
 
 const LAUNCHER_MAGIC_NUMBER: [u8; 4] = [b'U', b'V', b'U', b'V'];
 
@@ -143,6 +145,49 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str) -> String {
     format!("#!{executable}")
 }
 
+/// Format the shebang for the scripts installed into the given [`Layout`], per its [`Shebang`]
+/// strategy.
+fn layout_shebang(layout: &Layout) -> String {
+    script_shebang(
+        &layout.shebang,
+        &layout.sys_executable,
+        &layout.scheme.scripts,
+        &layout.os_name,
+    )
+}
+
+/// Format the shebang for a script in the `scripts` directory, using the given [`Shebang`]
+/// strategy to locate the `executable`.
+fn script_shebang(shebang: &Shebang, executable: &Path, scripts: &Path, os_name: &str) -> String {
+    // On Windows, the launcher always invokes the interpreter by its absolute path.
+    if os_name != "posix" {
+        return format_shebang(executable, os_name);
+    }
+
+    match shebang {
+        Shebang::Absolute => format_shebang(executable, os_name),
+        Shebang::Interpreter(interpreter) => format_shebang(interpreter, os_name),
+        Shebang::Env => "#!/usr/bin/env python".to_string(),
+        Shebang::Relative => {
+            let Some(relative) = pathdiff::diff_paths(executable, scripts) else {
+                return format_shebang(executable, os_name);
+            };
+            // A shebang can't contain a relative path, so resolve the interpreter relative to the
+            // (symlink-resolved) script via `/bin/sh`.
+            let relative = format!(
+                "'{}'",
+                relative
+                    .simplified_display()
+                    .to_string()
+                    .replace('\'', r#"'"'"'"#)
+            );
+            format!(
+                "#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/{relative} \"$0\" \"$@\"\n' '''"
+            )
+        }
+    }
+}
+
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
 /// stored zip file. The launcher will look for `python[w].exe` adjacent to it in the same directory
 /// to start the embedded script.
@@ -218,6 +263,11 @@ pub(crate) fn windows_script_launcher(
 }
 
 /// Create the wrapper scripts in the bin folder of the venv for launching console scripts.
+///
+/// On Windows, each script is wrapped in a launcher binary (a windowed launcher for GUI scripts,
+/// such that launching them doesn't open a console). If no launcher is available for the current
+/// architecture, we fall back to writing a `-script.py` (or, for GUI scripts, `-script.pyw`) file,
+/// like setuptools, which can be run via the Python launcher's file associations.
 pub(crate) fn write_script_entrypoints(
     layout: &Layout,
     site_packages: &Path,
@@ -253,19 +303,43 @@ pub(crate) fn write_script_entrypoints(
             })?;
 
         // Generate the launcher script.
-        let launcher_python_script = get_script_launcher(
-            entrypoint,
-            &format_shebang(&layout.sys_executable, &layout.os_name),
-        );
+        let launcher_python_script = get_script_launcher(entrypoint, &layout_shebang(layout));
 
         // If necessary, wrap the launcher script in a Windows launcher binary.
         if cfg!(windows) {
-            write_file_recorded(
-                site_packages,
-                &entrypoint_relative,
-                &windows_script_launcher(&launcher_python_script, is_gui, &layout.sys_executable)?,
-                record,
-            )?;
+            match windows_script_launcher(&launcher_python_script, is_gui, &layout.sys_executable) {
+                Ok(launcher) => {
+                    write_file_recorded(site_packages, &entrypoint_relative, &launcher, record)?;
+                }
+                Err(Error::UnsupportedWindowsArch(arch)) => {
+                    let script_name = format!(
+                        "{}-script.{}",
+                        entrypoint
+                            .name
+                            .strip_suffix(".py")
+                            .unwrap_or(&entrypoint.name),
+                        if is_gui { "pyw" } else { "py" }
+                    );
+                    warn!(
+                        "No script launcher is available for {arch}; installing `{}` as `{script_name}`",
+                        entrypoint.name
+                    );
+                    // GUI scripts should be run with `pythonw.exe`, to avoid opening a console.
+                    let pythonw = layout.sys_executable.with_file_name("pythonw.exe");
+                    let launcher_python_script = if is_gui && pythonw.is_file() {
+                        get_script_launcher(entrypoint, &format_shebang(&pythonw, &layout.os_name))
+                    } else {
+                        launcher_python_script
+                    };
+                    write_file_recorded(
+                        site_packages,
+                        &entrypoint_relative.with_file_name(script_name),
+                        &launcher_python_script,
+                        record,
+                    )?;
+                }
+                Err(err) => return Err(err),
+            }
         } else {
             write_file_recorded(
                 site_packages,
@@ -432,6 +506,7 @@ fn install_script(
     site_packages: &Path,
     record: &mut [RecordEntry],
     file: &DirEntry,
+    #[cfg_attr(not(unix), allow(unused_variables))] warnings: &mut Vec<InstallWarning>,
 ) -> Result<(), Error> {
     if !file.file_type()?.is_file() {
         return Err(Error::InvalidWheel(format!(
@@ -453,6 +528,15 @@ fn install_script(
         })?;
 
     let path = file.path();
+
+    // Check the mode of the script as shipped, before it's rewritten or moved.
+    #[cfg(unix)]
+    let executable = {
+        use std::os::unix::fs::PermissionsExt;
+
+        file.metadata()?.permissions().mode() & 0o111 != 0
+    };
+
     let mut script = File::open(&path)?;
 
     // https://sphinx-locales.github.io/peps/pep-0427/#recommended-installer-features
@@ -468,9 +552,7 @@ fn install_script(
     let mut start = vec![0; placeholder_python.len()];
     script.read_exact(&mut start)?;
     let size_and_encoded_hash = if start == placeholder_python {
-        let start = format_shebang(&layout.sys_executable, &layout.os_name)
-            .as_bytes()
-            .to_vec();
+        let start = layout_shebang(layout).as_bytes().to_vec();
         let mut target = File::create(&script_absolute)?;
         let size_and_encoded_hash = copy_and_hash(&mut start.chain(script), &mut target)?;
         fs::remove_file(&path)?;
@@ -486,6 +568,9 @@ fn install_script(
         use std::fs::Permissions;
         use std::os::unix::fs::PermissionsExt;
 
+        if !executable {
+            warnings.push(InstallWarning::NotExecutable(script_absolute.clone()));
+        }
         fs::set_permissions(&script_absolute, Permissions::from_mode(0o755))?;
     }
 
@@ -525,6 +610,7 @@ pub(crate) fn install_data(
     console_scripts: &[Script],
     gui_scripts: &[Script],
     record: &mut [RecordEntry],
+    warnings: &mut Vec<InstallWarning>,
 ) -> Result<(), Error> {
     for entry in fs::read_dir(data_dir)? {
         let entry = entry?;
@@ -555,7 +641,7 @@ pub(crate) fn install_data(
                         continue;
                     }
 
-                    install_script(layout, site_packages, record, &file)?;
+                    install_script(layout, site_packages, record, &file, warnings)?;
                 }
             }
             Some("headers") => {
@@ -606,6 +692,103 @@ pub(crate) fn write_file_recorded(
     Ok(())
 }
 
+/// Return the timestamp given by the `SOURCE_DATE_EPOCH` environment variable, if set.
+///
+/// See: <https://reproducible-builds.org/specs/source-date-epoch/>
+pub(crate) fn source_date_epoch() -> Option<FileTime> {
+    let value = env::var("SOURCE_DATE_EPOCH").ok()?;
+    let mtime = parse_source_date_epoch(&value);
+    if mtime.is_none() {
+        warn!("Ignoring invalid `SOURCE_DATE_EPOCH` (expected a non-negative integer): `{value}`");
+    }
+    mtime
+}
+
+/// Parse a `SOURCE_DATE_EPOCH` value, i.e., a number of seconds since the Unix epoch.
+fn parse_source_date_epoch(value: &str) -> Option<FileTime> {
+    let seconds = value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|seconds| *seconds >= 0)?;
+    Some(FileTime::from_unix_time(seconds, 0))
+}
+
+/// Set the modification time of every file in the `RECORD` (relative to `site_packages`), along
+/// with the directories that contain them, such that the installed tree is reproducible.
+///
+/// `site-packages` itself is left untouched, since CPython relies on its mtime to discover newly
+/// installed packages.
+///
+/// Files that share their contents with another path (i.e., that were symlinked or hard-linked
+/// from the cache) are copied first, such that the mtime of the cached file (and of any other
+/// environment that links to it) is left untouched.
+pub(crate) fn set_mtimes(
+    site_packages: &Path,
+    record: &[RecordEntry],
+    mtime: FileTime,
+) -> Result<(), Error> {
+    let mut directories = Vec::new();
+    for entry in record {
+        let path = normalize_path(&site_packages.join(&entry.path));
+        if !path.is_file() {
+            continue;
+        }
+        if is_shared(&path.symlink_metadata()?) {
+            unshare(&path)?;
+        }
+        filetime::set_file_mtime(&path, mtime)?;
+        // Only the directories within `site-packages` are owned by the distribution.
+        if path.starts_with(site_packages) {
+            directories.extend(
+                path.ancestors()
+                    .skip(1)
+                    .take_while(|ancestor| *ancestor != site_packages)
+                    .map(Path::to_path_buf),
+            );
+        }
+    }
+
+    // Directories are updated last, since writing to a directory changes its mtime.
+    directories.sort_unstable();
+    directories.dedup();
+    for directory in &directories {
+        if let Err(err) = filetime::set_file_mtime(directory, mtime) {
+            debug!("Failed to set mtime for {}: {err}", directory.display());
+        }
+    }
+    Ok(())
+}
+
+/// Return `true` if the file with the given metadata may share its contents with another path,
+/// i.e., if it's a symlink or has multiple hard links.
+///
+/// The number of hard links isn't available on all platforms, in which case every file is
+/// assumed to be shared.
+fn is_shared(metadata: &std::fs::Metadata) -> bool {
+    if metadata.file_type().is_symlink() {
+        return true;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.nlink() > 1
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+/// Replace the file (or symlink) at `path` with an independent copy of its contents.
+fn unshare(path: &Path) -> Result<(), Error> {
+    let parent = path.parent().expect("installed files to have a parent");
+    let temp_file = tempfile::NamedTempFile::new_in(parent)?;
+    fs::copy(path, temp_file.path())?;
+    temp_file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
 /// Adds `INSTALLER`, `REQUESTED` and `direct_url.json` to the .dist-info dir
 pub(crate) fn extra_dist_info(
     site_packages: &Path,
@@ -729,14 +912,17 @@ pub(crate) fn parse_metadata(
 #[cfg(test)]
 mod test {
     use std::io::Cursor;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
 
-    use crate::Error;
+    use crate::{Error, Shebang};
     use indoc::{formatdoc, indoc};
 
-    use crate::wheel::format_shebang;
+    use crate::wheel::{format_shebang, script_shebang};
 
-    use super::{parse_key_value_file, parse_wheel_file, read_record_file, relative_to, Script};
+    use super::{
+        parse_key_value_file, parse_source_date_epoch, parse_wheel_file, read_record_file,
+        relative_to, set_mtimes, Script,
+    };
 
     #[test]
     fn test_parse_key_value_file() {
@@ -897,6 +1083,41 @@ mod test {
         assert_eq!(format_shebang(executable, os_name), "#!/bin/sh\n'''exec' '/usr/bin/path/to/a/very/long/executable/executable/executable/executable/executable/executable/executable/executable/name/python3' \"$0\" \"$@\"\n' '''");
     }
 
+    #[test]
+    fn test_shebang_strategy() {
+        let executable = Path::new("/venv/bin/python3");
+        let scripts = Path::new("/venv/bin");
+        let os_name = "posix";
+
+        assert_eq!(
+            script_shebang(&Shebang::Absolute, executable, scripts, os_name),
+            "#!/venv/bin/python3"
+        );
+        assert_eq!(
+            script_shebang(&Shebang::Env, executable, scripts, os_name),
+            "#!/usr/bin/env python"
+        );
+        assert_eq!(
+            script_shebang(
+                &Shebang::Interpreter(PathBuf::from("/opt/app/bin/python3")),
+                executable,
+                scripts,
+                os_name
+            ),
+            "#!/opt/app/bin/python3"
+        );
+        assert_eq!(
+            script_shebang(&Shebang::Relative, executable, scripts, os_name),
+            "#!/bin/sh\n'''exec' \"$(dirname -- \"$(realpath -- \"$0\")\")\"/'python3' \"$0\" \"$@\"\n' '''"
+        );
+
+        // On Windows, the absolute path is always used.
+        assert_eq!(
+            script_shebang(&Shebang::Env, executable, scripts, "nt"),
+            "#!/venv/bin/python3"
+        );
+    }
+
     #[test]
     fn test_empty_value() -> Result<(), Error> {
         let wheel = indoc! {r"
@@ -959,4 +1180,90 @@ mod test {
             super::LAUNCHER_AArch64_CONSOLE.len()
         );
     }
+
+    #[test]
+    fn source_date_epoch() {
+        assert_eq!(
+            parse_source_date_epoch("1700000000"),
+            Some(filetime::FileTime::from_unix_time(1_700_000_000, 0))
+        );
+        assert_eq!(parse_source_date_epoch("-1"), None);
+        assert_eq!(parse_source_date_epoch("yesterday"), None);
+    }
+
+    #[test]
+    fn reproducible_mtimes() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        fs_err::create_dir_all(site_packages.join("foo"))?;
+        fs_err::create_dir_all(temp_dir.path().join("bin"))?;
+        fs_err::write(site_packages.join("foo").join("__init__.py"), "")?;
+        fs_err::write(temp_dir.path().join("bin").join("foo"), "")?;
+
+        let record = read_record_file(&mut "foo/__init__.py,,\n../bin/foo,,\n".as_bytes())?;
+        let mtime = filetime::FileTime::from_unix_time(1_700_000_000, 0);
+        set_mtimes(&site_packages, &record, mtime)?;
+
+        let mtime_of = |path: &Path| {
+            filetime::FileTime::from_last_modification_time(&path.metadata().unwrap())
+        };
+        assert_eq!(
+            mtime_of(&site_packages.join("foo").join("__init__.py")),
+            mtime
+        );
+        assert_eq!(mtime_of(&site_packages.join("foo")), mtime);
+        assert_eq!(mtime_of(&temp_dir.path().join("bin").join("foo")), mtime);
+        assert_ne!(mtime_of(&site_packages), mtime);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn reproducible_mtimes_linked() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let cache = temp_dir.path().join("cache");
+        let site_packages = temp_dir.path().join("site-packages");
+        fs_err::create_dir_all(&cache)?;
+        fs_err::create_dir_all(site_packages.join("foo"))?;
+        fs_err::write(cache.join("hardlink.py"), "hardlink")?;
+        fs_err::write(cache.join("symlink.py"), "symlink")?;
+        fs_err::hard_link(
+            cache.join("hardlink.py"),
+            site_packages.join("foo").join("hardlink.py"),
+        )?;
+        std::os::unix::fs::symlink(
+            cache.join("symlink.py"),
+            site_packages.join("foo").join("symlink.py"),
+        )?;
+
+        let mtime_of = |path: &Path| {
+            filetime::FileTime::from_last_modification_time(&path.metadata().unwrap())
+        };
+        let cached_mtimes = [
+            mtime_of(&cache.join("hardlink.py")),
+            mtime_of(&cache.join("symlink.py")),
+        ];
+
+        let record = read_record_file(&mut "foo/hardlink.py,,\nfoo/symlink.py,,\n".as_bytes())?;
+        let mtime = filetime::FileTime::from_unix_time(1_700_000_000, 0);
+        set_mtimes(&site_packages, &record, mtime)?;
+
+        // The installed files are copied before their mtime is set...
+        for (name, contents) in [("hardlink.py", "hardlink"), ("symlink.py", "symlink")] {
+            let path = site_packages.join("foo").join(name);
+            assert!(!path.symlink_metadata()?.file_type().is_symlink());
+            assert_eq!(fs_err::read_to_string(&path)?, contents);
+            assert_eq!(mtime_of(&path), mtime);
+        }
+
+        // ...such that the cached files are left untouched.
+        assert_eq!(
+            [
+                mtime_of(&cache.join("hardlink.py")),
+                mtime_of(&cache.join("symlink.py")),
+            ],
+            cached_mtimes
+        );
+        Ok(())
+    }
 }
@@ -217,6 +217,21 @@ impl Tags {
         false
     }
 
+    /// Returns the most-preferred tag, as a `(python, abi, platform)` triple.
+    pub fn best(&self) -> Option<(&str, &str, &str)> {
+        self.map
+            .iter()
+            .flat_map(|(py, abis)| {
+                abis.iter().flat_map(move |(abi, platforms)| {
+                    platforms
+                        .iter()
+                        .map(move |(platform, priority)| (*priority, py, abi, platform))
+                })
+            })
+            .max_by_key(|(priority, ..)| *priority)
+            .map(|(_, py, abi, platform)| (py.as_str(), abi.as_str(), platform.as_str()))
+    }
+
     /// Returns the [`TagCompatibility`] of the given tags.
     ///
     /// If compatible, includes the score of the most-compatible platform tag.
@@ -2022,4 +2037,24 @@ mod tests {
         "###
         );
     }
+
+    #[test]
+    fn test_best_tag() {
+        let tags = Tags::from_env(
+            &Platform::new(
+                Os::Macos {
+                    major: 14,
+                    minor: 0,
+                },
+                Arch::Aarch64,
+            ),
+            (3, 9),
+            "cpython",
+            (3, 9),
+            false,
+        )
+        .unwrap();
+        assert_eq!(tags.best(), Some(("cp39", "cp39", "macosx_14_0_arm64")));
+        assert_eq!(Tags::new(vec![]).best(), None);
+    }
 }
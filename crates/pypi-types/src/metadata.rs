@@ -19,7 +19,7 @@ use crate::LenientVersionSpecifiers;
 /// <https://packaging.python.org/specifications/core-metadata/>.
 ///
 /// This is a subset of the full metadata specification, and only includes the
-/// fields that are relevant to dependency resolution.
+/// fields that are relevant to dependency resolution (including license policy enforcement).
 ///
 /// At present, we support up to version 2.3 of the metadata specification.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -32,6 +32,8 @@ pub struct Metadata23 {
     pub requires_dist: Vec<Requirement>,
     pub requires_python: Option<VersionSpecifiers>,
     pub provides_extras: Vec<ExtraName>,
+    #[serde(default)]
+    pub license: MetadataLicense,
 }
 
 /// <https://github.com/PyO3/python-pkginfo-rs/blob/d719988323a0cfea86d4737116d7917f30e819e2/src/error.rs>
@@ -100,6 +102,7 @@ impl Metadata23 {
                 }
             })
             .collect::<Vec<_>>();
+        let license = MetadataLicense::from_headers(&headers);
 
         Ok(Self {
             name,
@@ -107,6 +110,7 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license,
         })
     }
 
@@ -175,6 +179,7 @@ impl Metadata23 {
                 }
             })
             .collect::<Vec<_>>();
+        let license = MetadataLicense::from_headers(&headers);
 
         Ok(Self {
             name,
@@ -182,6 +187,7 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license,
         })
     }
 
@@ -234,12 +240,16 @@ impl Metadata23 {
             provides_extras.push(extra);
         }
 
+        // Extract the license, as declared inline or through the trove classifiers.
+        let license = MetadataLicense::from_project(project.license, project.classifiers);
+
         Ok(Self {
             name,
             version,
             requires_dist,
             requires_python,
             provides_extras,
+            license,
         })
     }
 }
@@ -271,11 +281,27 @@ pub(crate) struct Project {
     pub(crate) dependencies: Option<Vec<LenientRequirement>>,
     /// Optional dependencies
     pub(crate) optional_dependencies: Option<IndexMap<ExtraName, Vec<LenientRequirement>>>,
+    /// The license of the project
+    pub(crate) license: Option<ProjectLicense>,
+    /// The trove classifiers of the project
+    pub(crate) classifiers: Option<Vec<String>>,
     /// Specifies which fields listed by PEP 621 were intentionally unspecified
     /// so another tool can/will provide such metadata dynamically.
     pub(crate) dynamic: Option<Vec<String>>,
 }
 
+/// The `license` field of a `pyproject.toml`, as either an SPDX expression (PEP 639) or a table
+/// (PEP 621).
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub(crate) enum ProjectLicense {
+    /// An SPDX license expression, e.g., `license = "MIT OR Apache-2.0"`.
+    Expression(String),
+    /// The license text, e.g., `license = { text = "MIT" }`, or a path to the license file, e.g.,
+    /// `license = { file = "LICENSE" }`.
+    Table { text: Option<String> },
+}
+
 /// Python Package Metadata 1.0 and later as specified in
 /// <https://peps.python.org/pep-0241/>.
 ///
@@ -300,6 +326,81 @@ impl Metadata10 {
     }
 }
 
+/// The licensing information declared in a distribution's core metadata.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct MetadataLicense {
+    /// The SPDX license expression, from the `License-Expression` field (PEP 639).
+    pub expression: Option<String>,
+    /// The free-text `License` field.
+    pub text: Option<String>,
+    /// The `License ::` trove classifiers, e.g., `License :: OSI Approved :: MIT License`.
+    pub classifiers: Vec<String>,
+}
+
+impl MetadataLicense {
+    /// Parse the [`MetadataLicense`] from a `METADATA` file, as included in a built distribution
+    /// (wheel).
+    pub fn parse_metadata(content: &[u8]) -> Result<Self, MetadataError> {
+        let headers = Headers::parse(content)?;
+        Ok(Self::from_headers(&headers))
+    }
+
+    /// Extract the [`MetadataLicense`] from the parsed headers of a metadata file.
+    fn from_headers(headers: &Headers) -> Self {
+        let expression = headers
+            .get_first_value("License-Expression")
+            .map(|expression| expression.trim().to_string())
+            .filter(|expression| !expression.is_empty());
+        let text = headers
+            .get_first_value("License")
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        let classifiers = headers
+            .get_all_values("Classifier")
+            .filter(|classifier| classifier.starts_with("License ::"))
+            .collect();
+        Self {
+            expression,
+            text,
+            classifiers,
+        }
+    }
+
+    /// Extract the [`MetadataLicense`] from the `license` and `classifiers` fields of a
+    /// `pyproject.toml`.
+    ///
+    /// A license declared by file is omitted, as its contents aren't available.
+    fn from_project(license: Option<ProjectLicense>, classifiers: Option<Vec<String>>) -> Self {
+        let (expression, text) = match license {
+            Some(ProjectLicense::Expression(expression)) => (Some(expression), None),
+            Some(ProjectLicense::Table { text }) => (None, text),
+            None => (None, None),
+        };
+        let expression = expression
+            .map(|expression| expression.trim().to_string())
+            .filter(|expression| !expression.is_empty());
+        let text = text
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        let classifiers = classifiers
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|classifier| classifier.starts_with("License ::"))
+            .collect();
+        Self {
+            expression,
+            text,
+            classifiers,
+        }
+    }
+
+    /// Returns `true` if the metadata doesn't declare a license.
+    pub fn is_empty(&self) -> bool {
+        self.expression.is_none() && self.text.is_none() && self.classifiers.is_empty()
+    }
+}
+
 /// Parse a `Metadata-Version` field into a (major, minor) tuple.
 fn parse_version(metadata_version: &str) -> Result<(u8, u8), MetadataError> {
     let (major, minor) =
@@ -358,7 +459,7 @@ mod tests {
 
     use crate::MetadataError;
 
-    use super::Metadata23;
+    use super::{Metadata23, MetadataLicense};
 
     #[test]
     fn test_parse_metadata() {
@@ -502,4 +603,64 @@ mod tests {
         );
         assert_eq!(meta.provides_extras, vec!["dotenv".parse().unwrap()]);
     }
+
+    #[test]
+    fn test_parse_license() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0";
+        let license = MetadataLicense::parse_metadata(s.as_bytes()).unwrap();
+        assert!(license.is_empty());
+
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nLicense: UNKNOWN";
+        let license = MetadataLicense::parse_metadata(s.as_bytes()).unwrap();
+        assert!(license.is_empty());
+
+        let s = "Metadata-Version: 2.4\nName: asdf\nVersion: 1.0\nLicense-Expression: MIT OR Apache-2.0\nClassifier: Programming Language :: Python\nClassifier: License :: OSI Approved :: MIT License";
+        let license = MetadataLicense::parse_metadata(s.as_bytes()).unwrap();
+        assert_eq!(license.expression.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(license.text, None);
+        assert_eq!(
+            license.classifiers,
+            vec!["License :: OSI Approved :: MIT License".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_pyproject_toml_license() {
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+            license = "MIT OR Apache-2.0"
+            classifiers = ["Programming Language :: Python", "License :: OSI Approved :: MIT License"]
+        "#;
+        let meta = Metadata23::parse_pyproject_toml(s).unwrap();
+        assert_eq!(
+            meta.license.expression.as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(meta.license.text, None);
+        assert_eq!(
+            meta.license.classifiers,
+            vec!["License :: OSI Approved :: MIT License".to_string()]
+        );
+
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+            license = { text = "BSD" }
+        "#;
+        let meta = Metadata23::parse_pyproject_toml(s).unwrap();
+        assert_eq!(meta.license.expression, None);
+        assert_eq!(meta.license.text.as_deref(), Some("BSD"));
+
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+            license = { file = "LICENSE" }
+        "#;
+        let meta = Metadata23::parse_pyproject_toml(s).unwrap();
+        assert!(meta.license.is_empty());
+    }
 }
@@ -37,11 +37,18 @@ fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct File {
+    /// The PEP 714 name for the PEP 658 metadata field, which takes precedence over
+    /// `dist-info-metadata` when both are present.
+    #[serde(alias = "data-core-metadata")]
+    pub core_metadata: Option<DistInfoMetadata>,
     // Non-PEP 691-compliant alias used by PyPI.
     #[serde(alias = "data-dist-info-metadata")]
     pub dist_info_metadata: Option<DistInfoMetadata>,
     pub filename: String,
     pub hashes: Hashes,
+    /// The URL of the PEP 740 provenance object for the file, which contains its attestations.
+    #[serde(default, alias = "data-provenance")]
+    pub provenance: Option<String>,
     /// There are a number of invalid specifiers on pypi, so we first try to parse it into a [`VersionSpecifiers`]
     /// according to spec (PEP 440), then a [`LenientVersionSpecifiers`] with fixup for some common problems and if this
     /// still fails, we skip the file when creating a version map.
@@ -87,6 +87,10 @@ enum RequirementsTxtStatement {
     NoBinary(NoBinary),
     /// `only-binary`
     OnlyBinary(NoBuild),
+    /// `--pre`
+    Pre,
+    /// A `pip` option that has no effect in uv (e.g., `--trusted-host`)
+    IgnoredOption(&'static str),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -332,6 +336,8 @@ pub struct RequirementsTxt {
     pub no_binary: NoBinary,
     /// Whether to allow only wheels, specified with `--only-binary`.
     pub only_binary: NoBuild,
+    /// Whether to allow pre-release versions, specified with `--pre`.
+    pub pre: bool,
 }
 
 impl RequirementsTxt {
@@ -528,6 +534,12 @@ impl RequirementsTxt {
                 RequirementsTxtStatement::OnlyBinary(only_binary) => {
                     data.only_binary.extend(only_binary);
                 }
+                RequirementsTxtStatement::Pre => {
+                    data.pre = true;
+                }
+                RequirementsTxtStatement::IgnoredOption(option) => {
+                    warn_user!("Ignoring unsupported option in requirements file: `{option}`");
+                }
             }
         }
         Ok(data)
@@ -545,6 +557,7 @@ impl RequirementsTxt {
             no_index,
             no_binary,
             only_binary,
+            pre,
         } = other;
         self.requirements.extend(requirements);
         self.constraints.extend(constraints);
@@ -557,6 +570,7 @@ impl RequirementsTxt {
         self.no_index = self.no_index || no_index;
         self.no_binary.extend(no_binary);
         self.only_binary.extend(only_binary);
+        self.pre = self.pre || pre;
     }
 }
 
@@ -658,6 +672,16 @@ fn parse_entry(
             }
         })?;
         RequirementsTxtStatement::OnlyBinary(NoBuild::from_arg(specifier))
+    } else if s.eat_if("--trusted-host") {
+        parse_value(content, s, |c: char| !['\n', '\r', '#'].contains(&c))?;
+        RequirementsTxtStatement::IgnoredOption("--trusted-host")
+    } else if s.eat_if("--use-feature") {
+        parse_value(content, s, |c: char| !['\n', '\r', '#'].contains(&c))?;
+        RequirementsTxtStatement::IgnoredOption("--use-feature")
+    } else if s.eat_if("--prefer-binary") {
+        RequirementsTxtStatement::IgnoredOption("--prefer-binary")
+    } else if s.eat_if("--pre") {
+        RequirementsTxtStatement::Pre
     } else if s.at(char::is_ascii_alphanumeric) || s.at(|char| matches!(char, '.' | '/' | '$')) {
         let (requirement, hashes) = parse_requirement_and_hashes(s, content, working_dir)?;
         RequirementsTxtStatement::RequirementEntry(RequirementEntry {
@@ -1743,6 +1767,7 @@ mod test {
             no_index: false,
             no_binary: None,
             only_binary: None,
+            pre: false,
         }
         "###);
 
@@ -1803,6 +1828,7 @@ mod test {
                 ],
             ),
             only_binary: None,
+            pre: false,
         }
         "###);
 
@@ -1870,6 +1896,7 @@ mod test {
             no_index: true,
             no_binary: None,
             only_binary: None,
+            pre: false,
         }
         "###);
 
@@ -2100,6 +2127,7 @@ mod test {
             no_index: false,
             no_binary: All,
             only_binary: None,
+            pre: false,
         }
         "###);
 
@@ -2126,6 +2154,34 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn ignored_options() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            --trusted-host example.com
+            --use-feature=truststore
+            --prefer-binary
+            --pre
+            numpy
+        "})?;
+
+        let requirements = RequirementsTxt::parse(
+            requirements_txt.path(),
+            temp_dir.path(),
+            &BaseClientBuilder::new(),
+        )
+        .await?;
+        assert_eq!(requirements.requirements.len(), 1);
+        assert_eq!(
+            requirements.requirements[0].requirement.to_string(),
+            "numpy"
+        );
+        assert!(requirements.pre);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parser_error_line_and_column() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
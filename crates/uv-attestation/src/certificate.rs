@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use rustls_pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+use webpki::{EndEntityCert, KeyUsage};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::der_parser::der::parse_der_utf8string;
+use x509_parser::prelude::FromDer;
+
+use crate::AttestationError;
+
+/// `id-kp-codeSigning` (1.3.6.1.5.5.7.3.3).
+const EKU_CODE_SIGNING: &[u8] = &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03];
+
+/// Fulcio's "Source Repository URI" extension.
+///
+/// See: <https://github.com/sigstore/fulcio/blob/main/docs/oid-info.md>
+const OID_FULCIO_SOURCE_REPOSITORY_URI: &str = "1.3.6.1.4.1.57264.1.12";
+
+/// The algorithms with which certificates in the chain may be signed.
+static CHAIN_ALGORITHMS: &[&dyn SignatureVerificationAlgorithm] = &[
+    webpki::ring::ECDSA_P256_SHA256,
+    webpki::ring::ECDSA_P256_SHA384,
+    webpki::ring::ECDSA_P384_SHA256,
+    webpki::ring::ECDSA_P384_SHA384,
+];
+
+/// The algorithms with which attestations may be signed.
+///
+/// Per Sigstore's conventions, the digest is determined by the curve: SHA-256 for P-256 keys,
+/// and SHA-384 for P-384 keys.
+static SIGNING_ALGORITHMS: &[&dyn SignatureVerificationAlgorithm] = &[
+    webpki::ring::ECDSA_P256_SHA256,
+    webpki::ring::ECDSA_P384_SHA384,
+];
+
+/// A signing certificate issued by Sigstore's Fulcio certificate authority.
+#[derive(Debug, Clone)]
+pub(crate) struct Certificate {
+    der: CertificateDer<'static>,
+    /// The source repository URI recorded by Fulcio.
+    source_repository: Option<String>,
+}
+
+impl Certificate {
+    /// Parse a DER-encoded certificate.
+    pub(crate) fn from_der(der: Vec<u8>) -> Result<Self, AttestationError> {
+        let (_, certificate) =
+            X509Certificate::from_der(&der).map_err(|_| AttestationError::MalformedCertificate)?;
+        let source_repository = certificate
+            .extensions()
+            .iter()
+            .find(|extension| extension.oid.to_id_string() == OID_FULCIO_SOURCE_REPOSITORY_URI)
+            .and_then(|extension| parse_der_utf8string(extension.value).ok())
+            .and_then(|(_, uri)| uri.as_str().ok().map(ToString::to_string));
+        Ok(Self {
+            der: CertificateDer::from(der),
+            source_repository,
+        })
+    }
+
+    /// Return the DER encoding of the certificate.
+    pub(crate) fn der(&self) -> &[u8] {
+        self.der.as_ref()
+    }
+
+    /// Verify that the certificate chains to one of the trust anchors, through the given
+    /// intermediates, with every certificate valid at the given time (in seconds since the Unix
+    /// epoch), and that it permits code signing.
+    pub(crate) fn verify_chain(
+        &self,
+        anchors: &[TrustAnchor<'_>],
+        intermediates: &[CertificateDer<'_>],
+        time: i64,
+    ) -> Result<(), AttestationError> {
+        let time = u64::try_from(time).map_err(|_| AttestationError::ExpiredCertificate)?;
+        let certificate = EndEntityCert::try_from(&self.der)
+            .map_err(|_| AttestationError::MalformedCertificate)?;
+        certificate
+            .verify_for_usage(
+                CHAIN_ALGORITHMS,
+                anchors,
+                intermediates,
+                UnixTime::since_unix_epoch(Duration::from_secs(time)),
+                KeyUsage::required(EKU_CODE_SIGNING),
+                None,
+            )
+            .map_err(|err| match err {
+                webpki::Error::CertExpired | webpki::Error::CertNotValidYet => {
+                    AttestationError::ExpiredCertificate
+                }
+                webpki::Error::RequiredEkuNotFound => AttestationError::NotCodeSigning,
+                webpki::Error::UnsupportedCriticalExtension => {
+                    AttestationError::UnsupportedCriticalExtension
+                }
+                _ => AttestationError::UntrustedCertificate,
+            })
+    }
+
+    /// Verify an ASN.1-encoded ECDSA signature over the message with the certificate's key.
+    pub(crate) fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), AttestationError> {
+        let certificate = EndEntityCert::try_from(&self.der)
+            .map_err(|_| AttestationError::MalformedCertificate)?;
+        for algorithm in SIGNING_ALGORITHMS {
+            match certificate.verify_signature(*algorithm, message, signature) {
+                Ok(()) => return Ok(()),
+                // The algorithm doesn't apply to the certificate's key; try the next one.
+                Err(webpki::Error::UnsupportedSignatureAlgorithmForPublicKey) => {}
+                Err(_) => return Err(AttestationError::InvalidSignature),
+            }
+        }
+        Err(AttestationError::UnsupportedAlgorithm)
+    }
+
+    /// Return the source repository URI recorded by Fulcio (e.g., `https://github.com/org/repo`),
+    /// which identifies the repository whose workflow signed the attestation.
+    pub(crate) fn source_repository(&self) -> Option<&str> {
+        self.source_repository.as_deref()
+    }
+}
+
+/// Returns `true` if the DER-encoded certificate is self-issued, i.e., a root.
+pub(crate) fn is_self_issued(der: &[u8]) -> Result<bool, AttestationError> {
+    let (_, certificate) =
+        X509Certificate::from_der(der).map_err(|_| AttestationError::MalformedCertificate)?;
+    Ok(certificate.subject() == certificate.issuer())
+}
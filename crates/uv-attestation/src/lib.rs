@@ -0,0 +1,1138 @@
+//! Verification of PEP 740 attestations for distributions served by package indexes.
+//!
+//! An attestation is an in-toto statement about a distribution file, signed with a short-lived
+//! certificate issued by Sigstore's Fulcio certificate authority to the publisher's CI workflow.
+//! Verification checks that:
+//!
+//! 1. Each transparency log entry carries a signed entry timestamp and an inclusion proof from a
+//!    trusted log, and records the attestation's signature and certificate.
+//! 2. The signing certificate chains, through intermediate certificates, to a root in the
+//!    configured trust root, with every certificate valid at the entry's (signed) integrated time,
+//!    and permits code signing.
+//! 3. The envelope's signature is valid for the certificate's key.
+//! 4. The statement's subject matches the distribution's filename and SHA-256 digest.
+//! 5. The certificate's source repository matches the identity pinned for the package.
+//!
+//! See: <https://peps.python.org/pep-0740/>
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use rustc_hash::FxHashMap;
+use rustls_pemfile::Item;
+use rustls_pki_types::{CertificateDer, TrustAnchor};
+use thiserror::Error;
+
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+
+pub use crate::provenance::{
+    Attestation, AttestationBundle, Checkpoint, Envelope, InclusionPromise, InclusionProof, LogId,
+    Provenance, Publisher, TransparencyEntry, VerificationMaterial,
+};
+
+use crate::certificate::{self, Certificate};
+use crate::provenance::Statement;
+use crate::transparency::LogKey;
+use crate::trusted_root::TrustedRoot;
+
+mod certificate;
+mod provenance;
+mod transparency;
+mod trusted_root;
+
+/// The media type of the provenance objects served by package indexes.
+pub const PROVENANCE_MEDIA_TYPE: &str = "application/vnd.pypi.integrity.v1+json";
+
+/// The payload type of the DSSE envelopes that contain attestations.
+const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// The type of an in-toto v1 statement.
+const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+
+#[derive(Debug, Error)]
+pub enum AttestationError {
+    #[error("Failed to read attestation trust root at: {}", _0.user_display())]
+    TrustRootRead(PathBuf, #[source] std::io::Error),
+    #[error("Attestation trust root isn't a valid Sigstore trusted root")]
+    TrustRootJson(#[source] serde_json::Error),
+    #[error("Attestation trust root doesn't contain any root certificates")]
+    EmptyTrustRoot,
+    #[error(
+        "Attestation trust root doesn't contain any transparency log keys (`PUBLIC KEY` blocks)"
+    )]
+    NoTransparencyLogKey,
+    #[error("Attestation verification requires a trust root; provide one with `--attestation-trust-root`")]
+    NoTrustRoot,
+    #[error("Attestation contains a malformed certificate")]
+    MalformedCertificate,
+    #[error("Attestation contains invalid base64")]
+    Base64(#[from] base64::DecodeError),
+    #[error("Attestation contains an invalid statement")]
+    Statement(#[source] serde_json::Error),
+    #[error("Attestation statement is invalid: {0}")]
+    InvalidStatement(String),
+    #[error("Unsupported attestation version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Attestation certificate uses an unsupported key or signature algorithm")]
+    UnsupportedAlgorithm,
+    #[error("Provenance doesn't contain any attestations")]
+    NoAttestations,
+    #[error("Attestation doesn't contain a transparency log entry")]
+    MissingTransparencyEntry,
+    #[error("Attestation was recorded by an untrusted transparency log")]
+    UntrustedTransparencyLog,
+    #[error("Attestation transparency log entry is invalid: {0}")]
+    InvalidTransparencyEntry(String),
+    #[error("Attestation certificate wasn't issued by the trust root")]
+    UntrustedCertificate,
+    #[error("Attestation certificate wasn't valid at the time of signing")]
+    ExpiredCertificate,
+    #[error("Attestation certificate isn't valid for code signing")]
+    NotCodeSigning,
+    #[error("Attestation certificate contains an unsupported critical extension")]
+    UnsupportedCriticalExtension,
+    #[error("Attestation signature is invalid")]
+    InvalidSignature,
+    #[error("Attestation is for `{actual}`, but expected `{expected}`")]
+    SubjectMismatch { expected: String, actual: String },
+    #[error("Attestation digest doesn't match for `{filename}`: expected sha256 `{expected}`, but the attestation contains `{actual}`")]
+    DigestMismatch {
+        filename: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Attestation certificate doesn't include a source repository")]
+    MissingIdentity,
+    #[error("Attestation was signed by `{actual}`, but expected `{expected}`")]
+    IdentityMismatch { expected: String, actual: String },
+    #[error("No attestation identity is pinned for `{0}`; provide the expected source repository with `--attestation-identity {0}=<URI>`")]
+    UnpinnedIdentity(PackageName),
+}
+
+/// Whether to verify the attestations of distributions before installing them.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum AttestationMode {
+    /// Don't verify attestations.
+    #[default]
+    Ignore,
+    /// Verify attestations for any distributions that have them.
+    Verify,
+    /// Verify attestations, and fail if any registry distribution doesn't have them.
+    Require,
+}
+
+/// The certificates that attestation signing certificates must chain to, i.e., the Fulcio root
+/// and intermediate certificates, along with the keys of the trusted transparency logs.
+#[derive(Debug, Clone)]
+pub struct TrustRoot {
+    /// The self-issued root certificates.
+    anchors: Vec<TrustAnchor<'static>>,
+    /// The intermediate certificates, through which signing certificates chain to a root.
+    intermediates: Vec<CertificateDer<'static>>,
+    log_keys: Vec<LogKey>,
+}
+
+impl TrustRoot {
+    /// Read the trust root from a PEM bundle of certificates (`CERTIFICATE` blocks) and
+    /// transparency log keys (`PUBLIC KEY` blocks).
+    pub fn from_pem(pem: &str) -> Result<Self, AttestationError> {
+        let mut certificates = Vec::new();
+        let mut log_keys = Vec::new();
+        for item in rustls_pemfile::read_all(&mut pem.as_bytes()) {
+            match item.map_err(|_| AttestationError::MalformedCertificate)? {
+                Item::X509Certificate(der) => certificates.push(der),
+                Item::SubjectPublicKeyInfo(der) => log_keys.push(LogKey::from_der(der.as_ref())?),
+                _ => {}
+            }
+        }
+        Self::new(certificates, log_keys)
+    }
+
+    /// Read the trust root from Sigstore's trusted root format (i.e., `trusted_root.json`).
+    pub fn from_json(json: &str) -> Result<Self, AttestationError> {
+        let trusted_root: TrustedRoot =
+            serde_json::from_str(json).map_err(AttestationError::TrustRootJson)?;
+        let certificates = trusted_root
+            .certificate_authorities
+            .iter()
+            .flat_map(|authority| &authority.cert_chain.certificates)
+            .map(|certificate| {
+                Ok(CertificateDer::from(
+                    BASE64_STANDARD.decode(&certificate.raw_bytes)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, AttestationError>>()?;
+        // Skip any logs with unsupported keys (e.g., Ed25519), since their entries can't be
+        // verified regardless.
+        let log_keys = trusted_root
+            .tlogs
+            .iter()
+            .filter_map(|log| {
+                let der = match BASE64_STANDARD.decode(&log.public_key.raw_bytes) {
+                    Ok(der) => der,
+                    Err(err) => return Some(Err(err.into())),
+                };
+                match LogKey::from_der(&der) {
+                    Err(AttestationError::UnsupportedAlgorithm) => None,
+                    result => Some(result),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(certificates, log_keys)
+    }
+
+    /// Read the trust root from a PEM file, or from a Sigstore trusted root (JSON) file.
+    pub fn from_file(path: &Path) -> Result<Self, AttestationError> {
+        let contents = fs_err::read_to_string(path)
+            .map_err(|err| AttestationError::TrustRootRead(path.to_path_buf(), err))?;
+        if contents.trim_start().starts_with('{') {
+            Self::from_json(&contents)
+        } else {
+            Self::from_pem(&contents)
+        }
+    }
+
+    /// Create a trust root from the given certificates, of which the self-issued certificates
+    /// are trusted as roots, and transparency log keys.
+    fn new(
+        certificates: Vec<CertificateDer<'static>>,
+        log_keys: Vec<LogKey>,
+    ) -> Result<Self, AttestationError> {
+        let mut anchors = Vec::new();
+        let mut intermediates = Vec::new();
+        for der in certificates {
+            if certificate::is_self_issued(der.as_ref())? {
+                let anchor = webpki::anchor_from_trusted_cert(&der)
+                    .map_err(|_| AttestationError::MalformedCertificate)?;
+                anchors.push(anchor.to_owned());
+            } else {
+                intermediates.push(der);
+            }
+        }
+        if anchors.is_empty() {
+            return Err(AttestationError::EmptyTrustRoot);
+        }
+        if log_keys.is_empty() {
+            return Err(AttestationError::NoTransparencyLogKey);
+        }
+        Ok(Self {
+            anchors,
+            intermediates,
+            log_keys,
+        })
+    }
+}
+
+/// The expected signing identity for a package, as provided on the command line in the form
+/// `<package>=<source repository URI>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageIdentity {
+    pub package: PackageName,
+    pub identity: String,
+}
+
+impl FromStr for PackageIdentity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (package, identity) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected `<package>=<identity>`, found: `{s}`"))?;
+        let package = PackageName::from_str(package.trim()).map_err(|err| err.to_string())?;
+        let identity = identity.trim();
+        if identity.is_empty() {
+            return Err(format!("Missing identity for `{package}`"));
+        }
+        Ok(Self {
+            package,
+            identity: identity.to_string(),
+        })
+    }
+}
+
+/// A policy for verifying the attestations of distributions before installing them.
+#[derive(Debug, Default, Clone)]
+pub struct AttestationPolicy {
+    mode: AttestationMode,
+    trust_root: Option<TrustRoot>,
+    identities: FxHashMap<PackageName, String>,
+}
+
+impl AttestationPolicy {
+    /// Determine the attestation policy to use for the given arguments.
+    pub fn from_args(
+        mode: AttestationMode,
+        trust_root: Option<&Path>,
+        identities: Vec<PackageIdentity>,
+    ) -> Result<Self, AttestationError> {
+        if mode == AttestationMode::Ignore {
+            return Ok(Self::default());
+        }
+        let Some(trust_root) = trust_root else {
+            return Err(AttestationError::NoTrustRoot);
+        };
+        Ok(Self {
+            mode,
+            trust_root: Some(TrustRoot::from_file(trust_root)?),
+            identities: identities
+                .into_iter()
+                .map(|PackageIdentity { package, identity }| (package, identity))
+                .collect(),
+        })
+    }
+
+    /// Return the verification mode.
+    pub fn mode(&self) -> AttestationMode {
+        self.mode
+    }
+
+    /// Verify the provenance of a distribution file with the given filename and SHA-256 digest,
+    /// returning the identity that signed it.
+    ///
+    /// Every attestation in the provenance must be valid and signed by the identity pinned for
+    /// the package. The publisher listed in the provenance is _not_ trusted, since it's served by
+    /// the same index as the distribution.
+    pub fn verify(
+        &self,
+        package: &PackageName,
+        provenance: &Provenance,
+        filename: &str,
+        sha256: &str,
+    ) -> Result<String, AttestationError> {
+        let trust_root = self
+            .trust_root
+            .as_ref()
+            .ok_or(AttestationError::NoTrustRoot)?;
+
+        let mut attestations = provenance
+            .attestation_bundles
+            .iter()
+            .flat_map(|bundle| &bundle.attestations)
+            .peekable();
+        if attestations.peek().is_none() {
+            return Err(AttestationError::NoAttestations);
+        }
+
+        let expected = self
+            .identities
+            .get(package)
+            .ok_or_else(|| AttestationError::UnpinnedIdentity(package.clone()))?;
+        for attestation in attestations {
+            verify_attestation(attestation, trust_root, filename, sha256, expected)?;
+        }
+        Ok(expected.clone())
+    }
+}
+
+/// Verify a single attestation against the trust root, distribution, and expected identity.
+fn verify_attestation(
+    attestation: &Attestation,
+    trust_root: &TrustRoot,
+    filename: &str,
+    sha256: &str,
+    expected: &str,
+) -> Result<(), AttestationError> {
+    if attestation.version != 1 {
+        return Err(AttestationError::UnsupportedVersion(attestation.version));
+    }
+
+    let material = &attestation.verification_material;
+    let certificate = Certificate::from_der(BASE64_STANDARD.decode(&material.certificate)?)?;
+    let statement = BASE64_STANDARD.decode(&attestation.envelope.statement)?;
+    let signature = BASE64_STANDARD.decode(&attestation.envelope.signature)?;
+
+    // Verify that the signature was recorded in a trusted transparency log, and that the
+    // certificate chain was valid at the (authenticated) time that it was recorded.
+    if material.transparency_entries.is_empty() {
+        return Err(AttestationError::MissingTransparencyEntry);
+    }
+    for entry in &material.transparency_entries {
+        transparency::verify_entry(
+            entry,
+            &trust_root.log_keys,
+            &certificate,
+            &statement,
+            &signature,
+        )?;
+        certificate.verify_chain(
+            &trust_root.anchors,
+            &trust_root.intermediates,
+            entry.integrated_time,
+        )?;
+    }
+
+    // Verify the signature over the statement.
+    certificate.verify(&pre_authentication_encoding(&statement), &signature)?;
+
+    // Verify that the statement is about this distribution.
+    let statement: Statement =
+        serde_json::from_slice(&statement).map_err(AttestationError::Statement)?;
+    if statement.type_ != STATEMENT_TYPE {
+        return Err(AttestationError::InvalidStatement(format!(
+            "unsupported statement type `{}`",
+            statement.type_
+        )));
+    }
+    let [subject] = statement.subject.as_slice() else {
+        return Err(AttestationError::InvalidStatement(
+            "expected exactly one subject".to_string(),
+        ));
+    };
+    if subject.name != filename {
+        return Err(AttestationError::SubjectMismatch {
+            expected: filename.to_string(),
+            actual: subject.name.clone(),
+        });
+    }
+    let actual = subject.digest.sha256.as_deref().unwrap_or_default();
+    if !actual.eq_ignore_ascii_case(sha256) {
+        return Err(AttestationError::DigestMismatch {
+            filename: filename.to_string(),
+            expected: sha256.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+
+    // Verify the signing identity.
+    let actual = certificate
+        .source_repository()
+        .ok_or(AttestationError::MissingIdentity)?;
+    if !is_same_identity(actual, expected) {
+        return Err(AttestationError::IdentityMismatch {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Return the DSSE pre-authentication encoding of the payload, i.e., the signed message.
+///
+/// See: <https://github.com/secure-systems-lab/dsse/blob/master/protocol.md>
+fn pre_authentication_encoding(payload: &[u8]) -> Vec<u8> {
+    let mut message = format!(
+        "DSSEv1 {} {PAYLOAD_TYPE} {} ",
+        PAYLOAD_TYPE.len(),
+        payload.len()
+    )
+    .into_bytes();
+    message.extend_from_slice(payload);
+    message
+}
+
+/// Returns `true` if two source repository URIs identify the same repository.
+///
+/// Repository hosts treat owner and repository names case-insensitively, and trailing slashes
+/// are ignored.
+fn is_same_identity(actual: &str, expected: &str) -> bool {
+    actual
+        .trim_end_matches('/')
+        .eq_ignore_ascii_case(expected.trim_end_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    use uv_normalize::PackageName;
+
+    use super::*;
+
+    #[test]
+    fn pae() {
+        assert_eq!(
+            pre_authentication_encoding(b"{}"),
+            b"DSSEv1 28 application/vnd.in-toto+json 2 {}".to_vec()
+        );
+    }
+
+    #[test]
+    fn identity() {
+        assert!(is_same_identity(
+            "https://github.com/PyPA/sampleproject",
+            "https://github.com/pypa/sampleproject/"
+        ));
+        assert!(!is_same_identity(
+            "https://github.com/pypa/sampleproject",
+            "https://github.com/pypa/sampleproject-fork"
+        ));
+    }
+
+    #[test]
+    fn package_identity() {
+        assert_eq!(
+            PackageIdentity::from_str("Sample_Project=https://github.com/pypa/sampleproject")
+                .unwrap(),
+            PackageIdentity {
+                package: PackageName::from_str("sample-project").unwrap(),
+                identity: "https://github.com/pypa/sampleproject".to_string(),
+            }
+        );
+        assert!(PackageIdentity::from_str("sampleproject").is_err());
+        assert!(PackageIdentity::from_str("sampleproject=").is_err());
+    }
+
+    #[test]
+    fn deserialize_provenance() {
+        let provenance: Provenance = serde_json::from_str(
+            r#"{
+              "version": 1,
+              "attestation_bundles": [
+                {
+                  "publisher": {
+                    "kind": "GitHub",
+                    "repository": "pypa/sampleproject",
+                    "workflow": "release.yml",
+                    "environment": null
+                  },
+                  "attestations": [
+                    {
+                      "version": 1,
+                      "verification_material": {
+                        "certificate": "",
+                        "transparency_entries": [
+                          {
+                            "logIndex": "123",
+                            "logId": { "keyId": "" },
+                            "integratedTime": "1718368215",
+                            "inclusionPromise": { "signedEntryTimestamp": "" },
+                            "canonicalizedBody": ""
+                          }
+                        ]
+                      },
+                      "envelope": { "statement": "", "signature": "" }
+                    }
+                  ]
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+
+        let bundle = &provenance.attestation_bundles[0];
+        assert_eq!(
+            bundle.publisher.identity().as_deref(),
+            Some("https://github.com/pypa/sampleproject")
+        );
+        assert_eq!(
+            bundle.attestations[0]
+                .verification_material
+                .transparency_entries[0]
+                .integrated_time,
+            1_718_368_215
+        );
+    }
+
+    #[test]
+    fn verify_without_attestations() {
+        let policy = AttestationPolicy {
+            mode: AttestationMode::Verify,
+            trust_root: Some(TrustRoot {
+                anchors: Vec::new(),
+                intermediates: Vec::new(),
+                log_keys: Vec::new(),
+            }),
+            identities: FxHashMap::default(),
+        };
+        let provenance = Provenance {
+            version: 1,
+            attestation_bundles: Vec::new(),
+        };
+        assert!(matches!(
+            policy.verify(
+                &PackageName::from_str("sampleproject").unwrap(),
+                &provenance,
+                "sampleproject-4.0.0-py3-none-any.whl",
+                "0000"
+            ),
+            Err(AttestationError::NoAttestations)
+        ));
+    }
+
+    const TAG_BOOLEAN: u8 = 0x01;
+    const TAG_INTEGER: u8 = 0x02;
+    const TAG_BIT_STRING: u8 = 0x03;
+    const TAG_OCTET_STRING: u8 = 0x04;
+    const TAG_OID: u8 = 0x06;
+    const TAG_UTF8_STRING: u8 = 0x0C;
+    const TAG_UTC_TIME: u8 = 0x17;
+    const TAG_SEQUENCE: u8 = 0x30;
+
+    /// Encode a DER element.
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag];
+        match u8::try_from(contents.len()) {
+            Ok(length) if length < 0x80 => encoded.push(length),
+            _ => {
+                encoded.push(0x82);
+                encoded.extend(u16::try_from(contents.len()).unwrap().to_be_bytes());
+            }
+        }
+        encoded.extend_from_slice(contents);
+        encoded
+    }
+
+    fn sequence(elements: &[Vec<u8>]) -> Vec<u8> {
+        tlv(TAG_SEQUENCE, &elements.concat())
+    }
+
+    fn pem(label: &str, der: &[u8]) -> String {
+        format!(
+            "-----BEGIN {label}-----\n{}\n-----END {label}-----\n",
+            BASE64_STANDARD.encode(der)
+        )
+    }
+
+    fn sha256(parts: &[&[u8]]) -> Vec<u8> {
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        for part in parts {
+            context.update(part);
+        }
+        context.finish().as_ref().to_vec()
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// A P-256 signing key.
+    struct Key(EcdsaKeyPair);
+
+    impl Key {
+        fn generate() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+            Self(
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap(),
+            )
+        }
+
+        fn sign(&self, message: &[u8]) -> Vec<u8> {
+            self.0
+                .sign(&SystemRandom::new(), message)
+                .unwrap()
+                .as_ref()
+                .to_vec()
+        }
+
+        /// Return the DER-encoded `SubjectPublicKeyInfo`.
+        fn public_key_info(&self) -> Vec<u8> {
+            let mut point = vec![0];
+            point.extend_from_slice(self.0.public_key().as_ref());
+            sequence(&[
+                sequence(&[
+                    tlv(TAG_OID, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01]),
+                    tlv(TAG_OID, &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07]),
+                ]),
+                tlv(TAG_BIT_STRING, &point),
+            ])
+        }
+    }
+
+    /// An X.509 extension, as its OID, criticality, and value.
+    type Extension = (&'static [u8], bool, Vec<u8>);
+
+    /// `basicConstraints`, with `cA` set to `TRUE`.
+    fn basic_constraints_ca() -> Extension {
+        (
+            &[0x55, 0x1D, 0x13],
+            true,
+            sequence(&[tlv(TAG_BOOLEAN, &[0xFF])]),
+        )
+    }
+
+    /// `extKeyUsage`, permitting code signing.
+    fn code_signing() -> Extension {
+        (
+            &[0x55, 0x1D, 0x25],
+            false,
+            sequence(&[tlv(
+                TAG_OID,
+                &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03],
+            )]),
+        )
+    }
+
+    /// Issue a DER-encoded certificate for the subject's key, signed by the issuer's key.
+    fn certificate(
+        subject: (&str, &Key),
+        issuer: (&str, &Key),
+        validity: (&str, &str),
+        extensions: &[Extension],
+    ) -> Vec<u8> {
+        let name = |common_name: &str| {
+            sequence(&[tlv(
+                0x31,
+                &sequence(&[
+                    tlv(TAG_OID, &[0x55, 0x04, 0x03]),
+                    tlv(TAG_UTF8_STRING, common_name.as_bytes()),
+                ]),
+            )])
+        };
+        let algorithm = sequence(&[tlv(
+            TAG_OID,
+            &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02],
+        )]);
+        let extensions = extensions
+            .iter()
+            .map(|(oid, critical, value)| {
+                let mut extension = vec![tlv(TAG_OID, oid)];
+                if *critical {
+                    extension.push(tlv(TAG_BOOLEAN, &[0xFF]));
+                }
+                extension.push(tlv(TAG_OCTET_STRING, value));
+                sequence(&extension)
+            })
+            .collect::<Vec<_>>();
+        let mut fields = vec![
+            tlv(0xA0, &tlv(TAG_INTEGER, &[2])),
+            tlv(TAG_INTEGER, &[1]),
+            algorithm.clone(),
+            name(issuer.0),
+            sequence(&[
+                tlv(TAG_UTC_TIME, validity.0.as_bytes()),
+                tlv(TAG_UTC_TIME, validity.1.as_bytes()),
+            ]),
+            name(subject.0),
+            subject.1.public_key_info(),
+        ];
+        if !extensions.is_empty() {
+            fields.push(tlv(0xA3, &sequence(&extensions)));
+        }
+        let tbs = sequence(&fields);
+        let mut signature = vec![0];
+        signature.extend(issuer.1.sign(&tbs));
+        sequence(&[tbs, algorithm, tlv(TAG_BIT_STRING, &signature)])
+    }
+
+    const FILENAME: &str = "sampleproject-4.0.0-py3-none-any.whl";
+    const SHA256: &str = "0ace7a2c6b1bbb3b0c8b0a1d6d4a1b6bb5a46ba7c94e8e51c8a3d6dfe3b2b4b5";
+    const REPOSITORY: &str = "https://github.com/pypa/sampleproject";
+    /// The time at which the attestation was recorded in the transparency log.
+    const INTEGRATED_TIME: i64 = 1_718_368_215;
+
+    /// A trust root and provenance for [`FILENAME`], signed in [`REPOSITORY`].
+    struct Fixture {
+        /// The DER-encoded root certificate.
+        root: Vec<u8>,
+        /// The DER-encoded `SubjectPublicKeyInfo` of the transparency log.
+        log_key: Vec<u8>,
+        trust_root: TrustRoot,
+        provenance: Provenance,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self::with_extensions(&[])
+        }
+
+        /// Create a fixture whose signing certificate has the given additional extensions.
+        fn with_extensions(extensions: &[Extension]) -> Self {
+            let root_key = Key::generate();
+            let leaf_key = Key::generate();
+            let log_key = Key::generate();
+
+            // A self-signed CA, and a short-lived code signing certificate issued by it.
+            let root = certificate(
+                ("sigstore", &root_key),
+                ("sigstore", &root_key),
+                ("240101000000Z", "340101000000Z"),
+                &[basic_constraints_ca()],
+            );
+            let mut leaf_extensions = vec![
+                code_signing(),
+                // Fulcio's source repository URI.
+                (
+                    &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x83, 0xBF, 0x30, 0x01, 0x0C][..],
+                    false,
+                    tlv(TAG_UTF8_STRING, REPOSITORY.as_bytes()),
+                ),
+            ];
+            leaf_extensions.extend_from_slice(extensions);
+            let leaf = certificate(
+                ("", &leaf_key),
+                ("sigstore", &root_key),
+                ("240614122500Z", "240614123500Z"),
+                &leaf_extensions,
+            );
+
+            // The signed statement.
+            let statement = serde_json::json!({
+                "_type": STATEMENT_TYPE,
+                "subject": [{ "name": FILENAME, "digest": { "sha256": SHA256 } }],
+                "predicateType": "https://docs.pypi.org/attestations/publish/v1",
+                "predicate": null,
+            })
+            .to_string();
+            let signature = leaf_key.sign(&pre_authentication_encoding(statement.as_bytes()));
+
+            // The transparency log entry, and the log's signed entry timestamp.
+            let log_id = sha256(&[&log_key.public_key_info()]);
+            let body = serde_json::json!({
+                "apiVersion": "0.0.1",
+                "kind": "dsse",
+                "spec": {
+                    "payloadHash": { "algorithm": "sha256", "value": hex(&sha256(&[statement.as_bytes()])) },
+                    "signatures": [{
+                        "signature": BASE64_STANDARD.encode(&signature),
+                        "verifier": BASE64_STANDARD.encode(pem("CERTIFICATE", &leaf)),
+                    }],
+                },
+            })
+            .to_string();
+            let encoded_body = BASE64_STANDARD.encode(&body);
+            let timestamp = log_key.sign(
+                format!(
+                    r#"{{"body":"{encoded_body}","integratedTime":{INTEGRATED_TIME},"logID":"{}","logIndex":123}}"#,
+                    hex(&log_id)
+                )
+                .as_bytes(),
+            );
+
+            // A tree of two entries, the first of which is this entry, and the log's signed
+            // checkpoint of the tree.
+            let leaf_hash = sha256(&[&[0x00], body.as_bytes()]);
+            let sibling_hash = sha256(&[&[0x00], b"{}"]);
+            let root_hash = sha256(&[&[0x01], &leaf_hash, &sibling_hash]);
+            let note = format!(
+                "rekor.sigstore.dev - 1\n2\n{}\n",
+                BASE64_STANDARD.encode(&root_hash)
+            );
+            let mut note_signature = log_id[..4].to_vec();
+            note_signature.extend(log_key.sign(note.as_bytes()));
+            let checkpoint = format!(
+                "{note}\n\u{2014} rekor.sigstore.dev {}\n",
+                BASE64_STANDARD.encode(note_signature)
+            );
+
+            let trust_root = TrustRoot::from_pem(&format!(
+                "{}{}",
+                pem("CERTIFICATE", &root),
+                pem("PUBLIC KEY", &log_key.public_key_info())
+            ))
+            .unwrap();
+            let provenance = Provenance {
+                version: 1,
+                attestation_bundles: vec![AttestationBundle {
+                    publisher: Publisher {
+                        kind: "GitHub".to_string(),
+                        repository: Some("pypa/sampleproject".to_string()),
+                    },
+                    attestations: vec![Attestation {
+                        version: 1,
+                        verification_material: VerificationMaterial {
+                            certificate: BASE64_STANDARD.encode(&leaf),
+                            transparency_entries: vec![TransparencyEntry {
+                                log_index: 123,
+                                log_id: LogId {
+                                    key_id: BASE64_STANDARD.encode(&log_id),
+                                },
+                                integrated_time: INTEGRATED_TIME,
+                                inclusion_promise: Some(InclusionPromise {
+                                    signed_entry_timestamp: BASE64_STANDARD.encode(timestamp),
+                                }),
+                                inclusion_proof: Some(InclusionProof {
+                                    log_index: 0,
+                                    root_hash: BASE64_STANDARD.encode(&root_hash),
+                                    tree_size: 2,
+                                    hashes: vec![BASE64_STANDARD.encode(&sibling_hash)],
+                                    checkpoint: Checkpoint {
+                                        envelope: checkpoint,
+                                    },
+                                }),
+                                canonicalized_body: encoded_body,
+                            }],
+                        },
+                        envelope: Envelope {
+                            statement: BASE64_STANDARD.encode(&statement),
+                            signature: BASE64_STANDARD.encode(&signature),
+                        },
+                    }],
+                }],
+            };
+
+            Self {
+                root,
+                log_key: log_key.public_key_info(),
+                trust_root,
+                provenance,
+            }
+        }
+
+        fn policy(&self, identities: &[(&str, &str)]) -> AttestationPolicy {
+            AttestationPolicy {
+                mode: AttestationMode::Verify,
+                trust_root: Some(self.trust_root.clone()),
+                identities: identities
+                    .iter()
+                    .map(|(package, identity)| {
+                        (
+                            PackageName::from_str(package).unwrap(),
+                            (*identity).to_string(),
+                        )
+                    })
+                    .collect(),
+            }
+        }
+
+        fn entry(&mut self) -> &mut TransparencyEntry {
+            &mut self.provenance.attestation_bundles[0].attestations[0]
+                .verification_material
+                .transparency_entries[0]
+        }
+
+        /// Verify the fixture's provenance, pinning [`REPOSITORY`] as the identity.
+        fn verify(&self) -> Result<String, AttestationError> {
+            self.policy(&[("sampleproject", REPOSITORY)]).verify(
+                &PackageName::from_str("sampleproject").unwrap(),
+                &self.provenance,
+                FILENAME,
+                SHA256,
+            )
+        }
+    }
+
+    #[test]
+    fn verify() {
+        let fixture = Fixture::new();
+        let policy = fixture.policy(&[("sampleproject", "https://github.com/PyPA/sampleproject/")]);
+        let identity = policy
+            .verify(
+                &PackageName::from_str("sampleproject").unwrap(),
+                &fixture.provenance,
+                FILENAME,
+                SHA256,
+            )
+            .unwrap();
+        assert_eq!(identity, "https://github.com/PyPA/sampleproject/");
+    }
+
+    #[test]
+    fn verify_identity_mismatch() {
+        let fixture = Fixture::new();
+        let policy =
+            fixture.policy(&[("sampleproject", "https://github.com/attacker/sampleproject")]);
+        let result = policy.verify(
+            &PackageName::from_str("sampleproject").unwrap(),
+            &fixture.provenance,
+            FILENAME,
+            SHA256,
+        );
+        assert!(matches!(
+            result,
+            Err(AttestationError::IdentityMismatch { expected, actual })
+                if expected == "https://github.com/attacker/sampleproject" && actual == REPOSITORY
+        ));
+    }
+
+    #[test]
+    fn verify_unpinned_identity() {
+        // The publisher listed by the index must not be trusted in lieu of a pinned identity.
+        let fixture = Fixture::new();
+        let policy = fixture.policy(&[]);
+        let result = policy.verify(
+            &PackageName::from_str("sampleproject").unwrap(),
+            &fixture.provenance,
+            FILENAME,
+            SHA256,
+        );
+        assert!(matches!(result, Err(AttestationError::UnpinnedIdentity(_))));
+    }
+
+    #[test]
+    fn verify_digest_mismatch() {
+        let fixture = Fixture::new();
+        let policy = fixture.policy(&[("sampleproject", REPOSITORY)]);
+        let result = policy.verify(
+            &PackageName::from_str("sampleproject").unwrap(),
+            &fixture.provenance,
+            FILENAME,
+            &"0".repeat(64),
+        );
+        assert!(matches!(
+            result,
+            Err(AttestationError::DigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_unsigned_integrated_time() {
+        // The integrated time is covered by the log's signed entry timestamp, so tampering with
+        // it invalidates the entry.
+        let mut fixture = Fixture::new();
+        fixture.entry().integrated_time += 60;
+        assert!(matches!(
+            fixture.verify(),
+            Err(AttestationError::InvalidTransparencyEntry(_))
+        ));
+    }
+
+    #[test]
+    fn verify_untrusted_log() {
+        let mut fixture = Fixture::new();
+        fixture.entry().log_id.key_id = BASE64_STANDARD.encode([0; 32]);
+        assert!(matches!(
+            fixture.verify(),
+            Err(AttestationError::UntrustedTransparencyLog)
+        ));
+    }
+
+    #[test]
+    fn verify_missing_inclusion_proof() {
+        let mut fixture = Fixture::new();
+        fixture.entry().inclusion_proof = None;
+        assert!(matches!(
+            fixture.verify(),
+            Err(AttestationError::InvalidTransparencyEntry(reason)) if reason == "missing inclusion proof"
+        ));
+    }
+
+    #[test]
+    fn verify_invalid_inclusion_proof() {
+        // The audit path must lead from the entry to the root hash.
+        let mut fixture = Fixture::new();
+        fixture.entry().inclusion_proof.as_mut().unwrap().hashes =
+            vec![BASE64_STANDARD.encode([0; 32])];
+        assert!(matches!(
+            fixture.verify(),
+            Err(AttestationError::InvalidTransparencyEntry(reason)) if reason == "inclusion proof doesn't match the root hash"
+        ));
+    }
+
+    #[test]
+    fn verify_unsigned_root_hash() {
+        // The root hash must be covered by the log's signed checkpoint.
+        let mut fixture = Fixture::new();
+        let proof = fixture.entry().inclusion_proof.as_mut().unwrap();
+        let note = format!(
+            "rekor.sigstore.dev - 1\n2\n{}\n",
+            BASE64_STANDARD.encode([0; 32])
+        );
+        proof.checkpoint.envelope = proof.checkpoint.envelope.replacen(
+            &format!("rekor.sigstore.dev - 1\n2\n{}\n", proof.root_hash),
+            &note,
+            1,
+        );
+        assert!(matches!(
+            fixture.verify(),
+            Err(AttestationError::InvalidTransparencyEntry(reason)) if reason == "invalid checkpoint signature"
+        ));
+    }
+
+    #[test]
+    fn verify_unsupported_critical_extension() {
+        // An unrecognized, critical extension restricts the certificate in some unknown way, so
+        // it must be rejected.
+        let fixture = Fixture::with_extensions(&[(
+            &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x83, 0xBF, 0x30, 0x01, 0x7F],
+            true,
+            tlv(TAG_UTF8_STRING, b"unknown"),
+        )]);
+        assert!(matches!(
+            fixture.verify(),
+            Err(AttestationError::UnsupportedCriticalExtension)
+        ));
+    }
+
+    #[test]
+    fn trust_root_from_json() {
+        let mut fixture = Fixture::new();
+        let json = serde_json::json!({
+            "mediaType": "application/vnd.dev.sigstore.trustedroot+json;version=0.1",
+            "tlogs": [{
+                "baseUrl": "https://rekor.sigstore.dev",
+                "hashAlgorithm": "SHA2_256",
+                "publicKey": {
+                    "rawBytes": BASE64_STANDARD.encode(&fixture.log_key),
+                    "keyDetails": "PKIX_ECDSA_P256_SHA_256",
+                },
+                "logId": { "keyId": fixture.entry().log_id.key_id.clone() },
+            }],
+            "certificateAuthorities": [{
+                "subject": { "organization": "sigstore.dev", "commonName": "sigstore" },
+                "uri": "https://fulcio.sigstore.dev",
+                "certChain": {
+                    "certificates": [{ "rawBytes": BASE64_STANDARD.encode(&fixture.root) }],
+                },
+            }],
+            "ctlogs": [],
+        });
+        fixture.trust_root = TrustRoot::from_json(&json.to_string()).unwrap();
+        assert_eq!(fixture.verify().unwrap(), REPOSITORY);
+    }
+
+    #[test]
+    fn trust_root_requires_log_key() {
+        let key = Key::generate();
+        let root = certificate(
+            ("sigstore", &key),
+            ("sigstore", &key),
+            ("240101000000Z", "340101000000Z"),
+            &[basic_constraints_ca()],
+        );
+        assert!(matches!(
+            TrustRoot::from_pem(&pem("CERTIFICATE", &root)),
+            Err(AttestationError::NoTransparencyLogKey)
+        ));
+    }
+
+    #[test]
+    fn non_ca_intermediate() {
+        // A certificate issued by an intermediate that isn't marked as a CA isn't trusted.
+        let root_key = Key::generate();
+        let intermediate_key = Key::generate();
+        let leaf_key = Key::generate();
+        let root = certificate(
+            ("sigstore", &root_key),
+            ("sigstore", &root_key),
+            ("240101000000Z", "340101000000Z"),
+            &[basic_constraints_ca()],
+        );
+        let intermediate = certificate(
+            ("sigstore-intermediate", &intermediate_key),
+            ("sigstore", &root_key),
+            ("240101000000Z", "340101000000Z"),
+            &[],
+        );
+        let leaf = Certificate::from_der(certificate(
+            ("", &leaf_key),
+            ("sigstore-intermediate", &intermediate_key),
+            ("240614122500Z", "240614123500Z"),
+            &[code_signing()],
+        ))
+        .unwrap();
+        let trust_root = TrustRoot::from_pem(&format!(
+            "{}{}{}",
+            pem("CERTIFICATE", &root),
+            pem("CERTIFICATE", &intermediate),
+            pem("PUBLIC KEY", &Key::generate().public_key_info())
+        ))
+        .unwrap();
+        assert!(matches!(
+            leaf.verify_chain(
+                &trust_root.anchors,
+                &trust_root.intermediates,
+                INTEGRATED_TIME
+            ),
+            Err(AttestationError::UntrustedCertificate)
+        ));
+    }
+
+    #[test]
+    fn empty_trust_root() {
+        assert!(matches!(
+            TrustRoot::from_pem("not a certificate"),
+            Err(AttestationError::EmptyTrustRoot)
+        ));
+    }
+}
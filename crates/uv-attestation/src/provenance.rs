@@ -0,0 +1,168 @@
+//! Types for the PEP 740 provenance objects served by package indexes.
+//!
+//! See: <https://peps.python.org/pep-0740/#provenance-objects>
+
+use serde::{Deserialize, Deserializer};
+
+/// The provenance of a distribution: the attestations for the file, grouped by the publisher
+/// that produced them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Provenance {
+    pub version: u8,
+    pub attestation_bundles: Vec<AttestationBundle>,
+}
+
+/// A set of attestations produced by a single (Trusted) Publisher.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttestationBundle {
+    pub publisher: Publisher,
+    pub attestations: Vec<Attestation>,
+}
+
+/// The Trusted Publisher that produced a set of attestations, e.g., a GitHub Actions workflow.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Publisher {
+    /// The kind of publisher, e.g., `GitHub` or `GitLab`.
+    pub kind: String,
+    /// The repository that published the distribution, e.g., `pypa/sampleproject`.
+    pub repository: Option<String>,
+}
+
+impl Publisher {
+    /// Return the source repository URI that attestations from this publisher are expected to
+    /// be signed by, if the publisher is of a known kind.
+    pub fn identity(&self) -> Option<String> {
+        let repository = self.repository.as_deref()?;
+        match self.kind.as_str() {
+            "GitHub" => Some(format!("https://github.com/{repository}")),
+            "GitLab" => Some(format!("https://gitlab.com/{repository}")),
+            _ => None,
+        }
+    }
+}
+
+/// A single attestation: a signed in-toto statement about a distribution.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Attestation {
+    pub version: u8,
+    pub verification_material: VerificationMaterial,
+    pub envelope: Envelope,
+}
+
+/// The material needed to verify an attestation's signature.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VerificationMaterial {
+    /// The base64-encoded, DER-encoded signing certificate.
+    pub certificate: String,
+    /// The transparency log entries that record the signature.
+    pub transparency_entries: Vec<TransparencyEntry>,
+}
+
+/// A transparency log entry, in the JSON encoding of Sigstore's `TransparencyLogEntry`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransparencyEntry {
+    /// The index of the entry in the log.
+    #[serde(deserialize_with = "deserialize_int64")]
+    pub log_index: i64,
+    /// The identifier of the log that contains the entry.
+    pub log_id: LogId,
+    /// The time at which the entry was integrated into the log, in seconds since the Unix epoch.
+    #[serde(deserialize_with = "deserialize_int64")]
+    pub integrated_time: i64,
+    /// The log's signed promise to include the entry.
+    pub inclusion_promise: Option<InclusionPromise>,
+    /// The log's proof that it includes the entry.
+    pub inclusion_proof: Option<InclusionProof>,
+    /// The base64-encoded, canonicalized body of the entry.
+    pub canonicalized_body: String,
+}
+
+/// The identifier of a transparency log.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogId {
+    /// The base64-encoded SHA-256 digest of the log's DER-encoded public key.
+    pub key_id: String,
+}
+
+/// A transparency log's signed promise to include an entry, as of the entry's integrated time.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionPromise {
+    /// The base64-encoded signature over the entry's canonicalized body, integrated time, log
+    /// ID, and log index.
+    pub signed_entry_timestamp: String,
+}
+
+/// A transparency log's proof that an entry is included in its Merkle tree.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionProof {
+    /// The index of the entry in the tree, which may differ from its index in the log.
+    #[serde(deserialize_with = "deserialize_int64")]
+    pub log_index: i64,
+    /// The base64-encoded root hash of the tree.
+    pub root_hash: String,
+    /// The number of entries in the tree.
+    #[serde(deserialize_with = "deserialize_int64")]
+    pub tree_size: i64,
+    /// The base64-encoded hashes of the nodes on the path from the entry to the root.
+    pub hashes: Vec<String>,
+    /// The log's signed commitment to the tree's size and root hash.
+    pub checkpoint: Checkpoint,
+}
+
+/// A transparency log checkpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Checkpoint {
+    /// The checkpoint, as a signed note.
+    pub envelope: String,
+}
+
+/// A DSSE envelope containing an in-toto statement.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Envelope {
+    /// The base64-encoded statement.
+    pub statement: String,
+    /// The base64-encoded signature over the statement.
+    pub signature: String,
+}
+
+/// An in-toto statement, as contained in an attestation's envelope.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Statement {
+    #[serde(rename = "_type")]
+    pub(crate) type_: String,
+    pub(crate) subject: Vec<Subject>,
+}
+
+/// The subject of an in-toto statement, i.e., the attested file.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Subject {
+    pub(crate) name: String,
+    pub(crate) digest: Digest,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Digest {
+    pub(crate) sha256: Option<String>,
+}
+
+/// Deserialize a 64-bit integer, which the protobuf JSON mapping encodes as a string.
+fn deserialize_int64<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Int64 {
+        Number(i64),
+        String(String),
+    }
+
+    match Int64::deserialize(deserializer)? {
+        Int64::Number(value) => Ok(value),
+        Int64::String(value) => value.parse().map_err(serde::de::Error::custom),
+    }
+}
@@ -0,0 +1,383 @@
+//! Verification of entries in Sigstore's transparency log (Rekor).
+//!
+//! An entry is trusted if:
+//!
+//! 1. Its signed entry timestamp (the log's promise to include the entry) was signed by a trusted
+//!    log key, which authenticates the entry's integrated time.
+//! 2. Its inclusion proof shows that the entry is included in the log's Merkle tree, as of a
+//!    checkpoint signed by the same log key.
+//! 3. Its body records the attestation's signature and certificate.
+//!
+//! See: <https://docs.sigstore.dev/logging/overview/>
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use ring::digest::{digest, Context, SHA256};
+use ring::signature::{
+    UnparsedPublicKey, VerificationAlgorithm, ECDSA_P256_SHA256_ASN1, ECDSA_P384_SHA384_ASN1,
+};
+use serde::Deserialize;
+use x509_parser::prelude::FromDer;
+use x509_parser::public_key::PublicKey;
+use x509_parser::x509::SubjectPublicKeyInfo;
+
+use crate::certificate::Certificate;
+use crate::provenance::{InclusionProof, TransparencyEntry};
+use crate::AttestationError;
+
+/// The public key of a trusted transparency log.
+#[derive(Debug, Clone)]
+pub(crate) struct LogKey {
+    /// The log's ID, i.e., the SHA-256 digest of the DER-encoded `SubjectPublicKeyInfo`.
+    id: Vec<u8>,
+    algorithm: &'static dyn VerificationAlgorithm,
+    /// The uncompressed elliptic curve point.
+    public_key: Vec<u8>,
+}
+
+impl LogKey {
+    /// Parse a DER-encoded `SubjectPublicKeyInfo`.
+    ///
+    /// Only ECDSA keys on the P-256 and P-384 curves are supported, as used by Rekor.
+    pub(crate) fn from_der(der: &[u8]) -> Result<Self, AttestationError> {
+        let (_, key_info) = SubjectPublicKeyInfo::from_der(der)
+            .map_err(|_| AttestationError::MalformedCertificate)?;
+        let Ok(PublicKey::EC(point)) = key_info.parsed() else {
+            return Err(AttestationError::UnsupportedAlgorithm);
+        };
+        // The curve is determined by the size of the uncompressed point.
+        let algorithm: &'static dyn VerificationAlgorithm = match point.data().len() {
+            65 => &ECDSA_P256_SHA256_ASN1,
+            97 => &ECDSA_P384_SHA384_ASN1,
+            _ => return Err(AttestationError::UnsupportedAlgorithm),
+        };
+        Ok(Self {
+            id: digest(&SHA256, der).as_ref().to_vec(),
+            algorithm,
+            public_key: point.data().to_vec(),
+        })
+    }
+
+    /// Verify an ASN.1-encoded ECDSA signature over the message with the log's key.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        UnparsedPublicKey::new(self.algorithm, &self.public_key)
+            .verify(message, signature)
+            .is_ok()
+    }
+}
+
+/// The body of a Rekor entry.
+#[derive(Debug, Deserialize)]
+struct Body {
+    kind: String,
+    spec: serde_json::Value,
+}
+
+/// The `spec` of a Rekor `dsse` entry.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DsseSpec {
+    payload_hash: HashValue,
+    signatures: Vec<DsseSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HashValue {
+    algorithm: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DsseSignature {
+    /// The base64-encoded signature.
+    signature: String,
+    /// The base64-encoded, PEM-encoded signing certificate.
+    verifier: String,
+}
+
+/// Verify that the entry was promised by one of the trusted logs, and that it records the given
+/// statement, signed with the given certificate and signature.
+///
+/// On success, the entry's integrated time can be relied upon.
+pub(crate) fn verify_entry(
+    entry: &TransparencyEntry,
+    log_keys: &[LogKey],
+    certificate: &Certificate,
+    statement: &[u8],
+    signature: &[u8],
+) -> Result<(), AttestationError> {
+    let log_id = BASE64_STANDARD.decode(&entry.log_id.key_id)?;
+    let log_key = log_keys
+        .iter()
+        .find(|log_key| log_key.id == log_id)
+        .ok_or(AttestationError::UntrustedTransparencyLog)?;
+
+    // Verify the signed entry timestamp, which is computed over the RFC 8785 canonical JSON
+    // encoding of the entry. Decoding the body first ensures that it can be embedded verbatim.
+    let body = BASE64_STANDARD.decode(&entry.canonicalized_body)?;
+    let promise = entry.inclusion_promise.as_ref().ok_or_else(|| {
+        AttestationError::InvalidTransparencyEntry("missing inclusion promise".to_string())
+    })?;
+    let timestamp = BASE64_STANDARD.decode(&promise.signed_entry_timestamp)?;
+    let payload = format!(
+        r#"{{"body":"{}","integratedTime":{},"logID":"{}","logIndex":{}}}"#,
+        entry.canonicalized_body,
+        entry.integrated_time,
+        hex(&log_id),
+        entry.log_index
+    );
+    let invalid = |reason: &str| AttestationError::InvalidTransparencyEntry(reason.to_string());
+    if !log_key.verify(payload.as_bytes(), &timestamp) {
+        return Err(invalid("invalid signed entry timestamp"));
+    }
+
+    // Verify that the entry is included in the log.
+    let proof = entry
+        .inclusion_proof
+        .as_ref()
+        .ok_or_else(|| invalid("missing inclusion proof"))?;
+    verify_inclusion_proof(proof, log_key, &body)?;
+
+    // Verify that the entry records this attestation.
+    let body: Body = serde_json::from_slice(&body).map_err(|_| invalid("malformed body"))?;
+    if body.kind != "dsse" {
+        return Err(AttestationError::InvalidTransparencyEntry(format!(
+            "unsupported entry kind `{}`",
+            body.kind
+        )));
+    }
+    let spec: DsseSpec =
+        serde_json::from_value(body.spec).map_err(|_| invalid("malformed `dsse` entry"))?;
+    if spec.payload_hash.algorithm != "sha256"
+        || !spec
+            .payload_hash
+            .value
+            .eq_ignore_ascii_case(&hex(digest(&SHA256, statement).as_ref()))
+    {
+        return Err(invalid("entry is for a different statement"));
+    }
+    let recorded = spec.signatures.iter().any(|recorded| {
+        BASE64_STANDARD
+            .decode(&recorded.signature)
+            .is_ok_and(|recorded| recorded == signature)
+            && is_same_certificate(&recorded.verifier, certificate)
+    });
+    if !recorded {
+        return Err(invalid("entry is for a different signature"));
+    }
+
+    Ok(())
+}
+
+/// Verify that the inclusion proof shows that the entry with the given body is included in the
+/// log's Merkle tree, and that the tree's root hash was signed by the log.
+///
+/// See: <https://www.rfc-editor.org/rfc/rfc9162#section-2.1.3.2>
+fn verify_inclusion_proof(
+    proof: &InclusionProof,
+    log_key: &LogKey,
+    body: &[u8],
+) -> Result<(), AttestationError> {
+    let invalid = |reason: &str| AttestationError::InvalidTransparencyEntry(reason.to_string());
+    let (Ok(index), Ok(size)) = (
+        u64::try_from(proof.log_index),
+        u64::try_from(proof.tree_size),
+    ) else {
+        return Err(invalid("malformed inclusion proof"));
+    };
+    let hashes = proof
+        .hashes
+        .iter()
+        .map(|hash| BASE64_STANDARD.decode(hash))
+        .collect::<Result<Vec<_>, _>>()?;
+    let root_hash = BASE64_STANDARD.decode(&proof.root_hash)?;
+
+    let leaf_hash = hash(&[&[0x00], body]);
+    if root_from_inclusion_proof(index, size, leaf_hash, &hashes).as_ref() != Some(&root_hash) {
+        return Err(invalid("inclusion proof doesn't match the root hash"));
+    }
+
+    verify_checkpoint(&proof.checkpoint.envelope, log_key, size, &root_hash)
+}
+
+/// Compute the root hash of a tree with the given size from the hash of the leaf at the given
+/// index and the hashes along its audit path, returning `None` if the path has the wrong length.
+fn root_from_inclusion_proof(
+    index: u64,
+    size: u64,
+    leaf_hash: Vec<u8>,
+    hashes: &[Vec<u8>],
+) -> Option<Vec<u8>> {
+    if index >= size {
+        return None;
+    }
+    let mut node = index;
+    let mut last = size - 1;
+    let mut root = leaf_hash;
+    for sibling in hashes {
+        if last == 0 {
+            return None;
+        }
+        if node & 1 == 1 || node == last {
+            root = hash(&[&[0x01], sibling, &root]);
+            while node & 1 == 0 && node != 0 {
+                node >>= 1;
+                last >>= 1;
+            }
+        } else {
+            root = hash(&[&[0x01], &root, sibling]);
+        }
+        node >>= 1;
+        last >>= 1;
+    }
+    (last == 0).then_some(root)
+}
+
+/// Verify that the checkpoint is a note signed by the log, which commits to a tree of the given
+/// size and root hash.
+///
+/// See: <https://github.com/transparency-dev/formats/blob/main/log/README.md>
+fn verify_checkpoint(
+    envelope: &str,
+    log_key: &LogKey,
+    size: u64,
+    root_hash: &[u8],
+) -> Result<(), AttestationError> {
+    let invalid = |reason: &str| AttestationError::InvalidTransparencyEntry(reason.to_string());
+
+    // The note's text is separated from its signatures by a blank line.
+    let (text, signatures) = envelope
+        .split_once("\n\n")
+        .ok_or_else(|| invalid("malformed checkpoint"))?;
+    let text = format!("{text}\n");
+
+    // Each signature is prefixed with the first four bytes of the key's ID.
+    let signed = signatures
+        .lines()
+        .filter_map(|line| line.strip_prefix("\u{2014} "))
+        .filter_map(|line| line.rsplit_once(' '))
+        .filter_map(|(_, signature)| BASE64_STANDARD.decode(signature).ok())
+        .any(|signature| {
+            signature.len() > 4
+                && signature[..4] == log_key.id[..4]
+                && log_key.verify(text.as_bytes(), &signature[4..])
+        });
+    if !signed {
+        return Err(invalid("invalid checkpoint signature"));
+    }
+
+    // The text consists of the log's origin, the tree size, and the root hash.
+    let mut lines = text.lines().skip(1);
+    if lines.next() != Some(size.to_string().as_str()) {
+        return Err(invalid("checkpoint is for a different tree size"));
+    }
+    if lines
+        .next()
+        .and_then(|hash| BASE64_STANDARD.decode(hash).ok())
+        .as_deref()
+        != Some(root_hash)
+    {
+        return Err(invalid("checkpoint is for a different root hash"));
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the base64-encoded PEM certificate is the given certificate.
+fn is_same_certificate(verifier: &str, certificate: &Certificate) -> bool {
+    let Ok(pem) = BASE64_STANDARD.decode(verifier) else {
+        return false;
+    };
+    let Ok(certificates) =
+        rustls_pemfile::certs(&mut pem.as_slice()).collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+    matches!(certificates.as_slice(), [der] if der.as_ref() == certificate.der())
+}
+
+/// Compute the SHA-256 digest of the concatenated parts.
+fn hash(parts: &[&[u8]]) -> Vec<u8> {
+    let mut context = Context::new(&SHA256);
+    for part in parts {
+        context.update(part);
+    }
+    context.finish().as_ref().to_vec()
+}
+
+/// Encode the bytes as lowercase hexadecimal.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compute the Merkle tree hash of the leaves.
+    fn tree_hash(leaves: &[Vec<u8>]) -> Vec<u8> {
+        match leaves {
+            [] => hash(&[]),
+            [leaf] => hash(&[&[0x00], leaf]),
+            _ => {
+                let split = split(leaves.len());
+                hash(&[
+                    &[0x01],
+                    &tree_hash(&leaves[..split]),
+                    &tree_hash(&leaves[split..]),
+                ])
+            }
+        }
+    }
+
+    /// Return the largest power of two smaller than `size`.
+    fn split(size: usize) -> usize {
+        let mut split = 1;
+        while split << 1 < size {
+            split <<= 1;
+        }
+        split
+    }
+
+    /// Compute the audit path from the leaf at the given index to the root.
+    fn audit_path(index: usize, leaves: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        if leaves.len() <= 1 {
+            return Vec::new();
+        }
+        let split = split(leaves.len());
+        if index < split {
+            let mut path = audit_path(index, &leaves[..split]);
+            path.push(tree_hash(&leaves[split..]));
+            path
+        } else {
+            let mut path = audit_path(index - split, &leaves[split..]);
+            path.push(tree_hash(&leaves[..split]));
+            path
+        }
+    }
+
+    #[test]
+    fn inclusion_proof() {
+        for size in 1..=9 {
+            let leaves = (0..size)
+                .map(|leaf| leaf.to_string().into_bytes())
+                .collect::<Vec<_>>();
+            let root = tree_hash(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                let path = audit_path(index, &leaves);
+                let leaf_hash = hash(&[&[0x00], leaf]);
+                let index = u64::try_from(index).unwrap();
+                let size = u64::try_from(size).unwrap();
+                assert_eq!(
+                    root_from_inclusion_proof(index, size, leaf_hash.clone(), &path).as_ref(),
+                    Some(&root)
+                );
+                // The path doesn't lead to the root of a larger tree.
+                assert_ne!(
+                    root_from_inclusion_proof(index, size + 1, leaf_hash, &path).as_ref(),
+                    Some(&root)
+                );
+            }
+        }
+    }
+}
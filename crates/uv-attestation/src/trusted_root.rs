@@ -0,0 +1,40 @@
+//! Types for Sigstore's trusted root, as distributed by its TUF repository (e.g., as
+//! `trusted_root.json`).
+//!
+//! See: <https://github.com/sigstore/protobuf-specs/blob/main/protos/sigstore_trustroot.proto>
+
+use serde::Deserialize;
+
+/// The certificate authorities and transparency logs trusted by Sigstore clients.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TrustedRoot {
+    #[serde(default)]
+    pub(crate) tlogs: Vec<TransparencyLog>,
+    #[serde(default)]
+    pub(crate) certificate_authorities: Vec<CertificateAuthority>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TransparencyLog {
+    pub(crate) public_key: RawBytes,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CertificateAuthority {
+    pub(crate) cert_chain: CertificateChain,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CertificateChain {
+    pub(crate) certificates: Vec<RawBytes>,
+}
+
+/// A base64-encoded, DER-encoded certificate or `SubjectPublicKeyInfo`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawBytes {
+    pub(crate) raw_bytes: String,
+}
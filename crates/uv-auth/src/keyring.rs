@@ -4,6 +4,7 @@ use thiserror::Error;
 use tracing::debug;
 use url::Url;
 
+use crate::redact_url;
 use crate::store::{BasicAuthData, Credential};
 
 /// Keyring provider to use for authentication
@@ -56,7 +57,7 @@ pub fn get_keyring_subprocess_auth(url: &Url) -> Result<Option<Credential>, Erro
     };
     debug!(
         "Running `keyring get` for `{}` with username `{}`",
-        url.to_string(),
+        redact_url(url),
         username
     );
     let output = match Command::new("keyring")
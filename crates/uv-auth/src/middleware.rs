@@ -1,38 +1,45 @@
 use http::Extensions;
 use std::path::Path;
+use std::sync::Arc;
 
-use netrc::Netrc;
 use reqwest::{header::HeaderValue, Request, Response};
 use reqwest_middleware::{Middleware, Next};
-use tracing::{debug, warn};
+use tracing::debug;
 
 use crate::{
-    keyring::{get_keyring_subprocess_auth, KeyringProvider},
-    store::Credential,
-    GLOBAL_AUTH_STORE,
+    keyring::KeyringProvider,
+    provider::{
+        default_providers, find_credentials, CredentialProvider, EnvironmentProvider,
+        KeyringSubprocessProvider, NetrcProvider,
+    },
+    redact_url, GLOBAL_AUTH_STORE,
 };
 
-/// A middleware that adds basic authentication to requests based on the netrc file and the keyring.
-///
-/// Netrc support Based on: <https://github.com/gribouille/netrc>.
+/// A middleware that adds basic authentication to requests based on a chain of
+/// [`CredentialProvider`]s (by default: environment variables, the netrc file, and the keyring).
 pub struct AuthMiddleware {
-    nrc: Option<Netrc>,
-    keyring_provider: KeyringProvider,
+    providers: Vec<Arc<dyn CredentialProvider>>,
 }
 
 impl AuthMiddleware {
     pub fn new(keyring_provider: KeyringProvider) -> Self {
-        Self {
-            nrc: Netrc::new().ok(),
-            keyring_provider,
-        }
+        Self::with_providers(default_providers(keyring_provider))
     }
 
     pub fn from_netrc_file(file: &Path, keyring_provider: KeyringProvider) -> Self {
-        Self {
-            nrc: Netrc::from_file(file).ok(),
-            keyring_provider,
+        let mut providers: Vec<Arc<dyn CredentialProvider>> = vec![
+            Arc::new(EnvironmentProvider),
+            Arc::new(NetrcProvider::from_file(file)),
+        ];
+        if matches!(keyring_provider, KeyringProvider::Subprocess) {
+            providers.push(Arc::new(KeyringSubprocessProvider));
         }
+        Self::with_providers(providers)
+    }
+
+    /// Create a middleware that consults the given providers, in order.
+    pub fn with_providers(providers: Vec<Arc<dyn CredentialProvider>>) -> Self {
+        Self { providers }
     }
 }
 
@@ -45,59 +52,37 @@ impl Middleware for AuthMiddleware {
         next: Next<'_>,
     ) -> reqwest_middleware::Result<Response> {
         let url = req.url().clone();
+        let redacted_url = redact_url(&url);
 
         // If the request already has an authorization header, we don't need to do anything.
-        // This gives in-URL credentials precedence over the netrc file.
+        // This gives in-URL credentials precedence over the credential providers.
         if req.headers().contains_key(reqwest::header::AUTHORIZATION) {
-            debug!("Request already has an authorization header: {url}");
+            debug!("Request already has an authorization header: {redacted_url}");
             return next.run(req, _extensions).await;
         }
 
-        // Try auth strategies in order of precedence:
         if let Some(stored_auth) = GLOBAL_AUTH_STORE.get(&url) {
             // If we've already seen this URL, we can use the stored credentials
             if let Some(auth) = stored_auth {
-                debug!("Adding authentication to already-seen URL: {url}");
+                debug!("Adding authentication to already-seen URL: {redacted_url}");
                 req.headers_mut().insert(
                     reqwest::header::AUTHORIZATION,
                     basic_auth(auth.username(), auth.password()),
                 );
             } else {
-                debug!("No credentials found for already-seen URL: {url}");
+                debug!("No credentials found for already-seen URL: {redacted_url}");
             }
-        } else if let Some(auth) = self.nrc.as_ref().and_then(|nrc| {
-            // If we find a matching entry in the netrc file, we can use it
-            url.host_str()
-                .and_then(|host| nrc.hosts.get(host).or_else(|| nrc.hosts.get("default")))
-        }) {
-            let auth = Credential::from(auth.to_owned());
+        } else if let Some(auth) = find_credentials(&self.providers, &url).await {
             req.headers_mut().insert(
                 reqwest::header::AUTHORIZATION,
                 basic_auth(auth.username(), auth.password()),
             );
             GLOBAL_AUTH_STORE.set(&url, Some(auth));
-        } else if matches!(self.keyring_provider, KeyringProvider::Subprocess) {
-            // If we have keyring support enabled, we check there as well
-            match get_keyring_subprocess_auth(&url) {
-                Ok(Some(auth)) => {
-                    req.headers_mut().insert(
-                        reqwest::header::AUTHORIZATION,
-                        basic_auth(auth.username(), auth.password()),
-                    );
-                    GLOBAL_AUTH_STORE.set(&url, Some(auth));
-                }
-                Ok(None) => {
-                    debug!("No keyring credentials found for {url}");
-                }
-                Err(e) => {
-                    warn!("Failed to get keyring credentials for {url}: {e}");
-                }
-            }
         }
 
-        // If we still don't have any credentials, we save the URL so we don't have to check netrc or keyring again
+        // If we still don't have any credentials, we save the URL so we don't have to check the providers again
         if !req.headers().contains_key(reqwest::header::AUTHORIZATION) {
-            debug!("No credentials found for: {url}");
+            debug!("No credentials found for: {redacted_url}");
             GLOBAL_AUTH_STORE.set(&url, None);
         }
 
@@ -0,0 +1,283 @@
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+use netrc::Netrc;
+use thiserror::Error;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::keyring::{get_keyring_subprocess_auth, KeyringProvider};
+use crate::redact_url;
+use crate::store::{BasicAuthData, Credential};
+
+#[derive(Debug, Error)]
+pub enum CredentialError {
+    #[error(transparent)]
+    Keyring(#[from] crate::keyring::Error),
+    #[error(transparent)]
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// A source of credentials for index and upload URLs.
+///
+/// Providers are consulted in order for requests that don't already carry credentials; the first
+/// provider to return credentials for a URL wins.
+#[async_trait::async_trait]
+pub trait CredentialProvider: Debug + Send + Sync {
+    /// A short, human-readable name for the provider (e.g., `netrc`), for use in logs.
+    fn name(&self) -> &'static str;
+
+    /// Return the credentials for the given URL, if the provider has any.
+    async fn credentials(&self, url: &Url) -> Result<Option<Credential>, CredentialError>;
+}
+
+/// Return the default credential providers, in order of precedence: environment variables, the
+/// netrc file, and, if enabled, the `keyring` CLI.
+pub fn default_providers(keyring_provider: KeyringProvider) -> Vec<Arc<dyn CredentialProvider>> {
+    let mut providers: Vec<Arc<dyn CredentialProvider>> = vec![
+        Arc::new(EnvironmentProvider),
+        Arc::new(NetrcProvider::new()),
+    ];
+    if matches!(keyring_provider, KeyringProvider::Subprocess) {
+        providers.push(Arc::new(KeyringSubprocessProvider));
+    }
+    providers
+}
+
+/// Query the providers in order, returning the first credentials found for the URL.
+///
+/// Provider failures are logged and skipped, such that a misconfigured provider doesn't prevent
+/// the remaining providers from being consulted.
+pub async fn find_credentials(
+    providers: &[Arc<dyn CredentialProvider>],
+    url: &Url,
+) -> Option<Credential> {
+    for provider in providers {
+        match provider.credentials(url).await {
+            Ok(Some(credential)) => {
+                debug!(
+                    "Found credentials for {} via {}",
+                    redact_url(url),
+                    provider.name()
+                );
+                return Some(credential);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                warn!(
+                    "Failed to get {} credentials for {}: {err}",
+                    provider.name(),
+                    redact_url(url)
+                );
+            }
+        }
+    }
+    None
+}
+
+/// Read credentials from environment variables named after the URL's host (and port, if any).
+///
+/// For `https://pypi.example.com:8443/simple/`, the password (or token) is read from
+/// `UV_AUTH_PYPI_EXAMPLE_COM_8443_PASSWORD`, and the username from
+/// `UV_AUTH_PYPI_EXAMPLE_COM_8443_USERNAME`, defaulting to `__token__` if unset.
+///
+/// As the variables are read on each lookup, short-lived tokens can be injected by the
+/// surrounding environment (e.g., a CI job) without being written to disk.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnvironmentProvider;
+
+impl EnvironmentProvider {
+    /// Return the prefix of the environment variables for the given URL, e.g.,
+    /// `UV_AUTH_PYPI_EXAMPLE_COM_`.
+    pub fn variable_prefix(url: &Url) -> Option<String> {
+        let host = url.host_str()?;
+        let netloc = match url.port() {
+            Some(port) => format!("{host}_{port}"),
+            None => host.to_string(),
+        };
+        let netloc = netloc
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        Some(format!("UV_AUTH_{netloc}_"))
+    }
+}
+
+#[async_trait::async_trait]
+impl CredentialProvider for EnvironmentProvider {
+    fn name(&self) -> &'static str {
+        "environment variables"
+    }
+
+    async fn credentials(&self, url: &Url) -> Result<Option<Credential>, CredentialError> {
+        let Some(prefix) = Self::variable_prefix(url) else {
+            return Ok(None);
+        };
+        let Some(password) = std::env::var(format!("{prefix}PASSWORD"))
+            .ok()
+            .filter(|password| !password.is_empty())
+        else {
+            return Ok(None);
+        };
+        let username = std::env::var(format!("{prefix}USERNAME"))
+            .ok()
+            .filter(|username| !username.is_empty())
+            .unwrap_or_else(|| "__token__".to_string());
+        Ok(Some(Credential::Basic(BasicAuthData {
+            username,
+            password: Some(password),
+        })))
+    }
+}
+
+/// Read credentials from a netrc file, matching on the URL's host (or the `default` entry).
+///
+/// Netrc support based on: <https://github.com/gribouille/netrc>.
+pub struct NetrcProvider {
+    netrc: Option<Netrc>,
+}
+
+impl NetrcProvider {
+    /// Read the netrc file from the `NETRC` environment variable or the user's home directory.
+    pub fn new() -> Self {
+        Self {
+            netrc: Netrc::new().ok(),
+        }
+    }
+
+    /// Read the netrc file at the given path.
+    pub fn from_file(path: &Path) -> Self {
+        Self {
+            netrc: Netrc::from_file(path).ok(),
+        }
+    }
+}
+
+impl Default for NetrcProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for NetrcProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetrcProvider")
+            .field("netrc", &self.netrc.as_ref().map(|_| "****"))
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl CredentialProvider for NetrcProvider {
+    fn name(&self) -> &'static str {
+        "netrc"
+    }
+
+    async fn credentials(&self, url: &Url) -> Result<Option<Credential>, CredentialError> {
+        Ok(self.netrc.as_ref().and_then(|netrc| {
+            url.host_str()
+                .and_then(|host| netrc.hosts.get(host).or_else(|| netrc.hosts.get("default")))
+                .map(|auth| Credential::from(auth.to_owned()))
+        }))
+    }
+}
+
+/// Read credentials by invoking the `keyring` CLI, as with pip's `--keyring-provider subprocess`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KeyringSubprocessProvider;
+
+#[async_trait::async_trait]
+impl CredentialProvider for KeyringSubprocessProvider {
+    fn name(&self) -> &'static str {
+        "keyring"
+    }
+
+    async fn credentials(&self, url: &Url) -> Result<Option<Credential>, CredentialError> {
+        Ok(get_keyring_subprocess_auth(url)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+    use url::Url;
+
+    use super::*;
+
+    #[test]
+    fn variable_prefix() {
+        let url = Url::parse("https://pypi.example.com/simple/").unwrap();
+        assert_eq!(
+            EnvironmentProvider::variable_prefix(&url).as_deref(),
+            Some("UV_AUTH_PYPI_EXAMPLE_COM_")
+        );
+
+        let url = Url::parse("https://user@pypi-mirror.example.com:8443/simple/").unwrap();
+        assert_eq!(
+            EnvironmentProvider::variable_prefix(&url).as_deref(),
+            Some("UV_AUTH_PYPI_MIRROR_EXAMPLE_COM_8443_")
+        );
+
+        let url = Url::parse("file:///home/ferris/wheels/").unwrap();
+        assert_eq!(EnvironmentProvider::variable_prefix(&url), None);
+    }
+
+    #[tokio::test]
+    async fn environment_credentials() {
+        let url = Url::parse("https://env-provider.example.com/simple/").unwrap();
+        assert_eq!(EnvironmentProvider.credentials(&url).await.unwrap(), None);
+
+        std::env::set_var("UV_AUTH_ENV_PROVIDER_EXAMPLE_COM_PASSWORD", "secret");
+        assert_eq!(
+            EnvironmentProvider.credentials(&url).await.unwrap(),
+            Some(Credential::Basic(BasicAuthData {
+                username: "__token__".to_string(),
+                password: Some("secret".to_string()),
+            }))
+        );
+
+        std::env::set_var("UV_AUTH_ENV_PROVIDER_EXAMPLE_COM_USERNAME", "ferris");
+        assert_eq!(
+            EnvironmentProvider.credentials(&url).await.unwrap(),
+            Some(Credential::Basic(BasicAuthData {
+                username: "ferris".to_string(),
+                password: Some("secret".to_string()),
+            }))
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_precedence() {
+        let mut netrc_file = NamedTempFile::new().unwrap();
+        writeln!(
+            netrc_file,
+            "machine precedence.example.com login netrc password from-netrc"
+        )
+        .unwrap();
+
+        let providers: Vec<Arc<dyn CredentialProvider>> = vec![
+            Arc::new(EnvironmentProvider),
+            Arc::new(NetrcProvider::from_file(netrc_file.path())),
+        ];
+
+        let url = Url::parse("https://precedence.example.com/simple/").unwrap();
+        let credential = find_credentials(&providers, &url).await.unwrap();
+        assert_eq!(credential.password(), Some("from-netrc"));
+
+        std::env::set_var("UV_AUTH_PRECEDENCE_EXAMPLE_COM_PASSWORD", "from-env");
+        let credential = find_credentials(&providers, &url).await.unwrap();
+        assert_eq!(credential.password(), Some("from-env"));
+
+        let url = Url::parse("https://other.example.com/simple/").unwrap();
+        assert!(find_credentials(&providers, &url).await.is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use std::fmt::{Debug, Formatter};
 use std::{collections::HashMap, sync::Mutex};
 
 use netrc::Authenticator;
@@ -38,12 +39,21 @@ impl From<Authenticator> for Credential {
 
 // Used for URL encoded auth in User info
 // <https://datatracker.ietf.org/doc/html/rfc3986#section-3.2.1>
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct UrlAuthData {
     pub username: String,
     pub password: Option<String>,
 }
 
+impl Debug for UrlAuthData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UrlAuthData")
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "****"))
+            .finish()
+    }
+}
+
 impl UrlAuthData {
     pub fn apply_to_url(&self, mut url: Url) -> Url {
         url.set_username(&self.username)
@@ -56,12 +66,21 @@ impl UrlAuthData {
 
 // HttpBasicAuth - Used for netrc and keyring auth
 // <https://datatracker.ietf.org/doc/html/rfc7617>
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct BasicAuthData {
     pub username: String,
     pub password: Option<String>,
 }
 
+impl Debug for BasicAuthData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasicAuthData")
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "****"))
+            .finish()
+    }
+}
+
 pub struct AuthenticationStore {
     credentials: Mutex<HashMap<NetLoc, Option<Credential>>>,
 }
@@ -22,10 +22,9 @@ use serde::{de, Deserialize, Deserializer};
 use tempfile::{tempdir_in, TempDir};
 use thiserror::Error;
 use tokio::process::Command;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell};
 use tracing::{debug, info_span, instrument, Instrument};
 
-use distribution_types::Resolution;
 use pep440_rs::Version;
 use pep508_rs::{PackageName, Requirement};
 use uv_configuration::{BuildKind, ConfigSettings, SetupPyStrategy};
@@ -332,10 +331,19 @@ impl Pep517Backend {
 /// Uses an [`Arc`] internally, clone freely.
 #[derive(Debug, Default, Clone)]
 pub struct SourceBuildContext {
-    /// An in-memory resolution of the default backend's requirements for PEP 517 builds.
-    default_resolution: Arc<Mutex<Option<Resolution>>>,
-    /// An in-memory resolution of the build requirements for `--legacy-setup-py` builds.
-    setup_py_resolution: Arc<Mutex<Option<Resolution>>>,
+    /// The isolated build environments, keyed by their build requirements (e.g., the
+    /// `build-system.requires` of a PEP 517 backend), such that builds with the same
+    /// requirements share an environment.
+    environments: Arc<Mutex<FxHashMap<Vec<Requirement>, Arc<OnceCell<Arc<BuildEnvironment>>>>>>,
+}
+
+/// An isolated environment with a set of build requirements installed, which is removed once
+/// it's no longer in use.
+#[derive(Debug)]
+struct BuildEnvironment {
+    venv: PythonEnvironment,
+    /// The directory that contains the environment.
+    _temp_dir: TempDir,
 }
 
 /// Holds the state through a series of PEP 517 frontend to backend calls or a single setup.py
@@ -353,7 +361,11 @@ pub struct SourceBuild {
     project: Option<Project>,
     /// The virtual environment in which to build the source distribution.
     venv: PythonEnvironment,
-    /// Populated if `prepare_metadata_for_build_wheel` was called.
+    /// The shared, isolated build environment that contains `venv`, if any, which must outlive
+    /// the build.
+    _environment: Option<Arc<BuildEnvironment>>,
+    /// Populated if `prepare_metadata_for_build_wheel` (or `prepare_metadata_for_build_editable`)
+    /// was called.
     ///
     /// > If the build frontend has previously called prepare_metadata_for_build_wheel and depends
     /// > on the wheel resulting from this call to have metadata matching this earlier call, then
@@ -407,36 +419,23 @@ impl SourceBuild {
             Self::extract_pep517_backend(&source_tree, setup_py, &default_backend)
                 .map_err(|err| *err)?;
 
-        // Create a virtual environment, or install into the shared environment if requested.
-        let venv = match build_isolation {
-            BuildIsolation::Isolated => uv_virtualenv::create_venv(
-                &temp_dir.path().join(".venv"),
-                interpreter.clone(),
-                uv_virtualenv::Prompt::None,
-                false,
-                Vec::new(),
-            )?,
-            BuildIsolation::Shared(venv) => venv.clone(),
-        };
-
-        // Setup the build environment. If build isolation is disabled, we assume the build
+        // Use an isolated environment with the build requirements installed, or the shared
+        // environment if requested. If build isolation is disabled, we assume the build
         // environment is already setup.
-        if build_isolation.is_isolated() {
-            let resolved_requirements = Self::get_resolved_requirements(
-                build_context,
-                source_build_context,
-                &default_backend,
-                pep517_backend.as_ref(),
-            )
-            .await?;
-
-            build_context
-                .install(&resolved_requirements, &venv)
-                .await
-                .map_err(|err| {
-                    Error::RequirementsInstall("build-system.requires (install)", err)
-                })?;
-        }
+        let (mut venv, mut environment) = match build_isolation {
+            BuildIsolation::Isolated => {
+                let environment = Self::get_build_environment(
+                    interpreter,
+                    build_context,
+                    &source_build_context,
+                    &default_backend,
+                    pep517_backend.as_ref(),
+                )
+                .await?;
+                (environment.venv.clone(), Some(environment))
+            }
+            BuildIsolation::Shared(venv) => (venv.clone(), None),
+        };
 
         // Figure out what the modified path should be
         // Remove the PATH variable from the environment variables if it's there
@@ -460,19 +459,13 @@ impl SourceBuild {
             os_path
         };
 
-        // Prepend the venv bin directory to the modified path
-        let modified_path = if let Some(path) = modified_path {
-            let venv_path = iter::once(venv.scripts().to_path_buf()).chain(env::split_paths(&path));
-            env::join_paths(venv_path).map_err(Error::BuildScriptPath)?
-        } else {
-            OsString::from(venv.scripts())
-        };
+        let mut venv_path = prepend_scripts(&venv, modified_path.as_ref())?;
 
         // Create the PEP 517 build environment. If build isolation is disabled, we assume the build
         // environment is already setup.
         if build_isolation.is_isolated() {
             if let Some(pep517_backend) = &pep517_backend {
-                create_pep517_build_environment(
+                if let Some(private) = create_pep517_build_environment(
                     &source_tree,
                     &venv,
                     pep517_backend,
@@ -481,12 +474,18 @@ impl SourceBuild {
                     build_kind,
                     &config_settings,
                     &environment_variables,
-                    &modified_path,
+                    &venv_path,
                     &temp_dir,
                 )
-                .await?;
+                .await?
+                {
+                    venv_path = prepend_scripts(&private, modified_path.as_ref())?;
+                    venv = private;
+                    environment = None;
+                }
             }
         }
+        let modified_path = venv_path;
 
         Ok(Self {
             temp_dir,
@@ -494,6 +493,7 @@ impl SourceBuild {
             pep517_backend,
             project,
             venv,
+            _environment: environment,
             build_kind,
             config_settings,
             metadata_directory: None,
@@ -503,49 +503,75 @@ impl SourceBuild {
         })
     }
 
-    async fn get_resolved_requirements(
+    /// Return an isolated environment with the build requirements installed, reusing the
+    /// environment of any previous build with the same requirements.
+    async fn get_build_environment(
+        interpreter: &Interpreter,
         build_context: &impl BuildContext,
-        source_build_context: SourceBuildContext,
+        source_build_context: &SourceBuildContext,
         default_backend: &Pep517Backend,
         pep517_backend: Option<&Pep517Backend>,
-    ) -> Result<Resolution, Error> {
-        Ok(if let Some(pep517_backend) = pep517_backend {
-            if pep517_backend.requirements == default_backend.requirements {
-                let mut resolution = source_build_context.default_resolution.lock().await;
-                if let Some(resolved_requirements) = &*resolution {
-                    resolved_requirements.clone()
-                } else {
-                    let resolved_requirements = build_context
-                        .resolve(&default_backend.requirements)
-                        .await
-                        .map_err(|err| {
+    ) -> Result<Arc<BuildEnvironment>, Error> {
+        // Install default requirements for `setup.py`-based builds.
+        let requirements = pep517_backend.map_or(&SETUP_PY_REQUIREMENTS[..], |pep517_backend| {
+            &pep517_backend.requirements
+        });
+
+        // Builds with the same requirements wait for the environment to be created once, while
+        // unrelated builds proceed concurrently.
+        let cell = source_build_context
+            .environments
+            .lock()
+            .await
+            .entry(requirements.to_vec())
+            .or_default()
+            .clone();
+        let mut created = false;
+        let environment = cell
+            .get_or_try_init(|| async {
+                created = true;
+                debug!(
+                    "Creating build environment for: {}",
+                    requirements.iter().join(", ")
+                );
+                let temp_dir = tempdir_in(build_context.cache().root())?;
+                let venv = uv_virtualenv::create_venv(
+                    &temp_dir.path().join(".venv"),
+                    interpreter.clone(),
+                    uv_virtualenv::Prompt::None,
+                    false,
+                    false,
+                    Vec::new(),
+                )?;
+                let resolved_requirements =
+                    build_context.resolve(requirements).await.map_err(|err| {
+                        if pep517_backend.is_some_and(|pep517_backend| {
+                            pep517_backend.requirements != default_backend.requirements
+                        }) {
+                            Error::RequirementsInstall("build-system.requires (resolve)", err)
+                        } else {
                             Error::RequirementsInstall("setup.py build (resolve)", err)
-                        })?;
-                    *resolution = Some(resolved_requirements.clone());
-                    resolved_requirements
-                }
-            } else {
+                        }
+                    })?;
                 build_context
-                    .resolve(&pep517_backend.requirements)
+                    .install(&resolved_requirements, &venv)
                     .await
                     .map_err(|err| {
-                        Error::RequirementsInstall("build-system.requires (resolve)", err)
-                    })?
-            }
-        } else {
-            // Install default requirements for `setup.py`-based builds.
-            let mut resolution = source_build_context.setup_py_resolution.lock().await;
-            if let Some(resolved_requirements) = &*resolution {
-                resolved_requirements.clone()
-            } else {
-                let resolved_requirements = build_context
-                    .resolve(&*SETUP_PY_REQUIREMENTS)
-                    .await
-                    .map_err(|err| Error::RequirementsInstall("setup.py build (resolve)", err))?;
-                *resolution = Some(resolved_requirements.clone());
-                resolved_requirements
-            }
-        })
+                        Error::RequirementsInstall("build-system.requires (install)", err)
+                    })?;
+                Ok::<_, Error>(Arc::new(BuildEnvironment {
+                    venv,
+                    _temp_dir: temp_dir,
+                }))
+            })
+            .await?;
+        if !created {
+            debug!(
+                "Reusing build environment for: {}",
+                requirements.iter().join(", ")
+            );
+        }
+        Ok(environment.clone())
     }
 
     /// Extract the PEP 517 backend from the `pyproject.toml` or `setup.py` file.
@@ -603,8 +629,9 @@ impl SourceBuild {
         }
     }
 
-    /// Try calling `prepare_metadata_for_build_wheel` to get the metadata without executing the
-    /// actual build.
+    /// Try calling `prepare_metadata_for_build_wheel` (or, for PEP 660 editable builds,
+    /// `prepare_metadata_for_build_editable`) to get the metadata without executing the actual
+    /// build.
     pub async fn get_metadata_without_build(&mut self) -> Result<Option<PathBuf>, Error> {
         let Some(pep517_backend) = &self.pep517_backend else {
             return Ok(None);
@@ -649,23 +676,23 @@ impl SourceBuild {
         fs::create_dir(&metadata_directory)?;
 
         // Write the hook output to a file so that we can read it back reliably.
-        let outfile = self
-            .temp_dir
-            .path()
-            .join("prepare_metadata_for_build_wheel.txt");
+        let outfile = self.temp_dir.path().join(format!(
+            "prepare_metadata_for_build_{}.txt",
+            self.build_kind
+        ));
 
         debug!(
-            "Calling `{}.prepare_metadata_for_build_wheel()`",
-            pep517_backend.backend
+            "Calling `{}.prepare_metadata_for_build_{}()`",
+            pep517_backend.backend, self.build_kind,
         );
         let script = formatdoc! {
             r#"
             {}
             import json
 
-            prepare_metadata_for_build_wheel = getattr(backend, "prepare_metadata_for_build_wheel", None)
-            if prepare_metadata_for_build_wheel:
-                dirname = prepare_metadata_for_build_wheel("{}", config_settings={})
+            prepare_metadata_for_build = getattr(backend, "prepare_metadata_for_build_{}", None)
+            if prepare_metadata_for_build:
+                dirname = prepare_metadata_for_build("{}", config_settings={})
             else:
                 dirname = None
 
@@ -673,13 +700,14 @@ impl SourceBuild {
                 fp.write(dirname or "")
             "#,
             pep517_backend.backend_import(),
+            self.build_kind,
             escape_path_for_python(&metadata_directory),
             self.config_settings.escape_for_python(),
             outfile.escape_for_python(),
         };
         let span = info_span!(
             "run_python_script",
-            script="prepare_metadata_for_build_wheel",
+            script=format!("prepare_metadata_for_build_{}", self.build_kind),
             python_version = %self.venv.interpreter().python_version()
         );
         let output = run_python_script(
@@ -693,7 +721,10 @@ impl SourceBuild {
         .await?;
         if !output.status.success() {
             return Err(Error::from_command_output(
-                "Build backend failed to determine metadata through `prepare_metadata_for_build_wheel`".to_string(),
+                format!(
+                    "Build backend failed to determine metadata through `prepare_metadata_for_build_{}`",
+                    self.build_kind
+                ),
                 &output,
                 &self.version_id,
             ));
@@ -867,6 +898,10 @@ fn escape_path_for_python(path: &Path) -> String {
 }
 
 /// Not a method because we call it before the builder is completely initialized
+///
+/// If the backend requires additional packages, they're installed into a private environment
+/// (along with the backend's requirements), since `venv` may be shared with other builds; the
+/// private environment is returned.
 #[allow(clippy::too_many_arguments)]
 async fn create_pep517_build_environment(
     source_tree: &Path,
@@ -879,7 +914,7 @@ async fn create_pep517_build_environment(
     environment_variables: &FxHashMap<OsString, OsString>,
     modified_path: &OsString,
     temp_dir: &TempDir,
-) -> Result<(), Error> {
+) -> Result<Option<PythonEnvironment>, Error> {
     // Write the hook output to a file so that we can read it back reliably.
     let outfile = temp_dir
         .path()
@@ -973,13 +1008,32 @@ async fn create_pep517_build_environment(
             .await
             .map_err(|err| Error::RequirementsInstall("build-system.requires (resolve)", err))?;
 
+        let private = uv_virtualenv::create_venv(
+            &temp_dir.path().join(".venv"),
+            venv.interpreter().clone(),
+            uv_virtualenv::Prompt::None,
+            false,
+            false,
+            Vec::new(),
+        )?;
         build_context
-            .install(&resolution, venv)
+            .install(&resolution, &private)
             .await
             .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+        return Ok(Some(private));
     }
 
-    Ok(())
+    Ok(None)
+}
+
+/// Prepend the scripts directory of the environment to the given `PATH`.
+fn prepend_scripts(venv: &PythonEnvironment, path: Option<&OsString>) -> Result<OsString, Error> {
+    if let Some(path) = path {
+        let venv_path = iter::once(venv.scripts().to_path_buf()).chain(env::split_paths(path));
+        env::join_paths(venv_path).map_err(Error::BuildScriptPath)
+    } else {
+        Ok(OsString::from(venv.scripts()))
+    }
 }
 
 /// It is the caller's responsibility to create an informative span.
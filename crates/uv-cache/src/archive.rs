@@ -15,6 +15,12 @@ impl ArchiveId {
     pub fn new() -> Self {
         Self(nanoid::nanoid!())
     }
+
+    /// Create a content-addressed identifier for an archive from the SHA-256 digest of the
+    /// original (zipped) wheel, such that identical wheels share a single archive.
+    pub fn from_sha256(digest: &str) -> Self {
+        Self(digest.to_string())
+    }
 }
 
 impl AsRef<Path> for ArchiveId {
@@ -5,6 +5,7 @@ use std::io::Write;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use fs_err as fs;
 use rustc_hash::FxHashSet;
@@ -13,7 +14,7 @@ use tracing::debug;
 
 use distribution_types::InstalledDist;
 use pypi_types::Metadata23;
-use uv_fs::directories;
+use uv_fs::{directories, LockedFile, Simplified};
 use uv_normalize::PackageName;
 
 pub use crate::by_timestamp::CachedByTimestamp;
@@ -31,6 +32,7 @@ mod by_timestamp;
 mod cli;
 mod removal;
 mod timestamp;
+mod usage;
 mod wheel;
 
 /// A [`CacheEntry`] which may or may not exist yet.
@@ -228,7 +230,6 @@ impl Cache {
         path: impl AsRef<Path>,
     ) -> io::Result<ArchiveId> {
         // Create a unique ID for the artifact.
-        // TODO(charlie): Support content-addressed persistence via SHAs.
         let id = ArchiveId::new();
 
         // Move the temporary directory into the directory store.
@@ -243,6 +244,46 @@ impl Cache {
         Ok(id)
     }
 
+    /// Persist a temporary directory to the artifact store under a content-addressed ID (see
+    /// [`ArchiveId::from_sha256`]).
+    ///
+    /// If the artifact store already contains an archive with the same ID (e.g., because the same
+    /// wheel was previously fetched from a different index or URL), the temporary directory is
+    /// discarded in favor of the existing archive.
+    pub async fn persist_content_addressed(
+        &self,
+        temp_dir: impl AsRef<Path>,
+        path: impl AsRef<Path>,
+        id: ArchiveId,
+    ) -> io::Result<ArchiveId> {
+        let archive_entry = self.entry(CacheBucket::Archive, "", &id);
+        if archive_entry.path().is_dir() {
+            debug!(
+                "Reusing existing archive: {}",
+                archive_entry.path().display()
+            );
+            fs_err::remove_dir_all(temp_dir.as_ref())?;
+        } else {
+            // Move the temporary directory into the directory store.
+            fs_err::create_dir_all(archive_entry.dir())?;
+            if let Err(err) =
+                uv_fs::rename_with_retry(temp_dir.as_ref(), archive_entry.path()).await
+            {
+                // If another process persisted the same archive concurrently, use theirs.
+                if !archive_entry.path().is_dir() {
+                    return Err(err);
+                }
+                fs_err::remove_dir_all(temp_dir.as_ref())?;
+            }
+        }
+
+        // Create a symlink to the directory store.
+        fs_err::create_dir_all(path.as_ref().parent().expect("Cache entry to have parent"))?;
+        uv_fs::replace_symlink(archive_entry.path(), path.as_ref())?;
+
+        Ok(id)
+    }
+
     /// Initialize a directory for use as a cache.
     fn init(root: impl Into<PathBuf>) -> Result<PathBuf, io::Error> {
         let root = root.into();
@@ -292,9 +333,34 @@ impl Cache {
         fs::canonicalize(root)
     }
 
+    /// Acquire a shared lock on the cache, to be held for the duration of any operation that
+    /// reads from or writes to the cache.
+    ///
+    /// Any number of processes may hold a shared lock at once, such that concurrent installs
+    /// don't block one another, but none can while the cache is being cleaned or pruned (see
+    /// [`Cache::lock_exclusive`]).
+    pub fn lock_shared(&self) -> Result<LockedFile, io::Error> {
+        LockedFile::acquire_shared(self.root.join(".lock"), self.root.simplified_display())
+    }
+
+    /// Acquire an exclusive lock on the cache, to be held while removing entries from the cache.
+    pub fn lock_exclusive(&self) -> Result<LockedFile, io::Error> {
+        LockedFile::acquire(self.root.join(".lock"), self.root.simplified_display())
+    }
+
     /// Clear the cache, removing all entries.
+    ///
+    /// The lock file is retained, such that the cache can be cleared while locked.
     pub fn clear(&self) -> Result<Removal, io::Error> {
-        rm_rf(&self.root)
+        let mut summary = Removal::default();
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if entry.file_name() == ".lock" {
+                continue;
+            }
+            summary += rm_rf(entry.path())?;
+        }
+        Ok(summary)
     }
 
     /// Remove a package from the cache.
@@ -308,6 +374,28 @@ impl Cache {
         Ok(summary)
     }
 
+    /// Return the disk usage of each bucket in the cache, in bytes.
+    ///
+    /// Symlinks aren't followed, so unzipped wheels are attributed to the
+    /// [`CacheBucket::Archive`] bucket, rather than the buckets that refer to them.
+    pub fn bucket_sizes(&self) -> Result<Vec<(CacheBucket, u64)>, io::Error> {
+        CacheBucket::iter()
+            .map(|bucket| {
+                let path = self.bucket(bucket);
+                let mut size = 0;
+                if path.is_dir() {
+                    for entry in walkdir::WalkDir::new(path) {
+                        let entry = entry?;
+                        if entry.file_type().is_file() {
+                            size += entry.metadata()?.len();
+                        }
+                    }
+                }
+                Ok((bucket, size))
+            })
+            .collect()
+    }
+
     /// Run the garbage collector on the cache, removing any dangling entries.
     pub fn prune(&self) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
@@ -321,6 +409,7 @@ impl Cache {
             if entry.file_name() == "CACHEDIR.TAG"
                 || entry.file_name() == ".gitignore"
                 || entry.file_name() == ".git"
+                || entry.file_name() == ".lock"
             {
                 continue;
             }
@@ -351,7 +440,10 @@ impl Cache {
                 for entry in walkdir::WalkDir::new(bucket) {
                     let entry = entry?;
                     if entry.file_type().is_symlink() {
-                        references.insert(entry.path().canonicalize()?);
+                        // Environments may contain symlinks to interpreters that no longer exist.
+                        if let Ok(target) = entry.path().canonicalize() {
+                            references.insert(target);
+                        }
                     }
                 }
             }
@@ -368,6 +460,47 @@ impl Cache {
 
         Ok(summary)
     }
+
+    /// Remove any cache entries that haven't been used since the given time.
+    ///
+    /// An unzipped wheel is removed together with the symlinks that refer to it, and cached
+    /// environments and Git repositories are removed as a whole. The last use of an entry is
+    /// approximated by the access and modification times of its files.
+    pub fn prune_unused_since(&self, cutoff: SystemTime) -> Result<Removal, io::Error> {
+        let mut summary = Removal::default();
+        for entry in usage::entries(self)? {
+            if entry.last_used >= cutoff {
+                break;
+            }
+            for path in entry.paths {
+                debug!("Removing unused cache entry: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Remove the least recently used cache entries until the cache fits within the given size,
+    /// in bytes.
+    ///
+    /// Entries are delimited as in [`Cache::prune_unused_since`]. The size of the cache is
+    /// the sum of the sizes of its files, and so over-counts hard-linked files.
+    pub fn evict_to_size(&self, max_size: u64) -> Result<Removal, io::Error> {
+        let mut summary = Removal::default();
+        let entries = usage::entries(self)?;
+        let mut size = entries.iter().map(|entry| entry.size).sum::<u64>();
+        for entry in entries {
+            if size <= max_size {
+                break;
+            }
+            size -= entry.size;
+            for path in entry.paths {
+                debug!("Evicting cache entry: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+        Ok(summary)
+    }
 }
 
 /// The different kinds of data in the cache are stored in different bucket, which in our case
@@ -596,6 +729,14 @@ pub enum CacheBucket {
     ///
     /// The response is parsed into `uv_client::SimpleMetadata` before storage.
     Simple,
+    /// Ephemeral virtual environments for running scripts with inline metadata (PEP 723).
+    ///
+    /// Each environment is keyed by the interpreter and the script's requirements, such that
+    /// scripts with the same requirements share an environment. An environment is only reused
+    /// once it contains a `.complete` marker, which is written after its packages are installed.
+    ///
+    /// Cache structure: `environments-v0/<digest(interpreter, requirements)>/{bin, lib, pyvenv.cfg, .complete}`
+    Environments,
     /// A cache of unzipped wheels, stored as directories. This is used internally within the cache.
     /// When other buckets need to store directories, they should persist them to
     /// [`CacheBucket::Archive`], and then symlink them into the appropriate bucket. This ensures
@@ -607,13 +748,14 @@ pub enum CacheBucket {
 impl CacheBucket {
     fn to_str(self) -> &'static str {
         match self {
-            Self::BuiltWheels => "built-wheels-v3",
+            Self::BuiltWheels => "built-wheels-v4",
             Self::FlatIndex => "flat-index-v0",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v1",
-            Self::Simple => "simple-v7",
-            Self::Wheels => "wheels-v1",
+            Self::Interpreter => "interpreter-v2",
+            Self::Simple => "simple-v8",
+            Self::Wheels => "wheels-v2",
             Self::Archive => "archive-v0",
+            Self::Environments => "environments-v0",
         }
     }
 
@@ -724,6 +866,9 @@ impl CacheBucket {
             Self::Archive => {
                 // Nothing to do.
             }
+            Self::Environments => {
+                // Nothing to do.
+            }
         }
         Ok(summary)
     }
@@ -738,6 +883,7 @@ impl CacheBucket {
             CacheBucket::Interpreter,
             CacheBucket::Simple,
             CacheBucket::Archive,
+            CacheBucket::Environments,
         ]
         .iter()
         .copied()
@@ -0,0 +1,146 @@
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use fs_err as fs;
+use rustc_hash::FxHashMap;
+
+use crate::{Cache, CacheBucket};
+
+/// A unit of eviction in the cache, along with its size and the last time it was used.
+#[derive(Debug)]
+pub(crate) struct Usage {
+    /// The paths that make up the entry, to be removed together.
+    pub(crate) paths: Vec<PathBuf>,
+    /// The size of the entry, in bytes.
+    pub(crate) size: u64,
+    /// The last time the entry was used.
+    ///
+    /// Computed as the latest access or modification time of any of the entry's paths, so it's
+    /// only an approximation: many file systems update access times lazily (e.g., `relatime`), or
+    /// not at all (e.g., `noatime`).
+    pub(crate) last_used: SystemTime,
+}
+
+impl Usage {
+    fn new(path: PathBuf, size: u64, metadata: &std::fs::Metadata) -> Self {
+        Self {
+            paths: vec![path],
+            size,
+            last_used: last_used(metadata),
+        }
+    }
+}
+
+/// Collect the evictable entries in the cache, sorted from least to most recently used.
+///
+/// The granularity of an entry depends on the bucket:
+///
+/// - Unzipped wheels in the [`CacheBucket::Archive`] bucket form one entry each, together with the
+///   symlinks that refer to them from the other buckets.
+/// - Environments in the [`CacheBucket::Environments`] bucket and repositories in the
+///   [`CacheBucket::Git`] bucket form one entry per directory.
+/// - Every other file (e.g., a cached HTTP response or a wheel's metadata) forms its own entry.
+///
+/// Dangling symlinks aren't included, as they're removed by [`Cache::prune`].
+pub(crate) fn entries(cache: &Cache) -> io::Result<Vec<Usage>> {
+    let mut entries = Vec::new();
+
+    // Collect the archives, which are referenced by symlinks from the other buckets.
+    let mut archives = FxHashMap::default();
+    let archive = cache.bucket(CacheBucket::Archive);
+    if archive.is_dir() {
+        for entry in fs::read_dir(&archive)? {
+            let entry = entry?;
+            archives.insert(entry.path().canonicalize()?, entries.len());
+            entries.push(directory_usage(entry.path())?);
+        }
+    }
+
+    for bucket in CacheBucket::iter() {
+        let root = cache.bucket(bucket);
+        if !root.is_dir() {
+            continue;
+        }
+        match bucket {
+            CacheBucket::Archive => {}
+            CacheBucket::Environments => {
+                for entry in fs::read_dir(&root)? {
+                    let entry = entry?;
+                    entries.push(directory_usage(entry.path())?);
+                }
+            }
+            CacheBucket::Git => {
+                // Git repositories are stored as `db/<digest>` and `checkouts/<digest>`.
+                for kind in fs::read_dir(&root)? {
+                    let kind = kind?;
+                    if !kind.file_type()?.is_dir() {
+                        continue;
+                    }
+                    for entry in fs::read_dir(kind.path())? {
+                        let entry = entry?;
+                        entries.push(directory_usage(entry.path())?);
+                    }
+                }
+            }
+            _ => {
+                for entry in walkdir::WalkDir::new(&root) {
+                    let entry = entry?;
+                    if entry.file_type().is_symlink() {
+                        // Attribute the symlink to the archive that it refers to.
+                        let Ok(target) = entry.path().canonicalize() else {
+                            continue;
+                        };
+                        if let Some(&index) = archives.get(&target) {
+                            let usage = &mut entries[index];
+                            usage.last_used = usage
+                                .last_used
+                                .max(last_used(&fs::symlink_metadata(entry.path())?));
+                            usage.paths.push(entry.into_path());
+                        }
+                    } else if entry.file_type().is_file() {
+                        // Skip the marker files (see `Cache::init`).
+                        if entry.depth() == 1
+                            && (entry.file_name() == ".gitignore" || entry.file_name() == ".git")
+                        {
+                            continue;
+                        }
+                        let metadata = entry.metadata()?;
+                        entries.push(Usage::new(entry.into_path(), metadata.len(), &metadata));
+                    }
+                }
+            }
+        }
+    }
+
+    entries.sort_by_key(|usage| usage.last_used);
+    Ok(entries)
+}
+
+/// Return the usage of a directory that forms a single entry.
+///
+/// The last use is that of the most recently used file within the directory.
+fn directory_usage(path: PathBuf) -> io::Result<Usage> {
+    let mut size = 0;
+    let mut latest = last_used(&fs::symlink_metadata(&path)?);
+    for entry in walkdir::WalkDir::new(&path) {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if entry.file_type().is_file() {
+            size += metadata.len();
+        }
+        latest = latest.max(last_used(&metadata));
+    }
+    Ok(Usage {
+        paths: vec![path],
+        size,
+        last_used: latest,
+    })
+}
+
+/// Return the latest of the access and modification times in the given metadata.
+fn last_used(metadata: &std::fs::Metadata) -> SystemTime {
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let accessed = metadata.accessed().unwrap_or(SystemTime::UNIX_EPOCH);
+    modified.max(accessed)
+}
@@ -2,29 +2,33 @@ use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
 use reqwest::{Client, ClientBuilder};
 use reqwest_middleware::ClientWithMiddleware;
-use reqwest_retry::policies::ExponentialBackoff;
-use reqwest_retry::RetryTransientMiddleware;
 use std::env;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
 use tracing::debug;
 use uv_auth::{AuthMiddleware, KeyringProvider};
+use uv_configuration::Concurrency;
 use uv_fs::Simplified;
 use uv_version::version;
 use uv_warnings::warn_user_once;
 
 use crate::linehaul::LineHaul;
-use crate::middleware::OfflineMiddleware;
-use crate::Connectivity;
+use crate::metrics::ClientMetrics;
+use crate::middleware::{MetricsMiddleware, OfflineMiddleware, ThrottleMiddleware};
+use crate::retry::RetryMiddleware;
+use crate::{Connectivity, RetryPolicy};
 
 /// A builder for an [`BaseClient`].
 #[derive(Debug, Clone)]
 pub struct BaseClientBuilder<'a> {
     keyring_provider: KeyringProvider,
     native_tls: bool,
-    retries: u32,
+    retry_policy: RetryPolicy,
+    timeout: Option<u64>,
     connectivity: Connectivity,
+    concurrency: Concurrency,
     client: Option<Client>,
     markers: Option<&'a MarkerEnvironment>,
     platform: Option<&'a Platform>,
@@ -42,7 +46,9 @@ impl BaseClientBuilder<'_> {
             keyring_provider: KeyringProvider::default(),
             native_tls: false,
             connectivity: Connectivity::Online,
-            retries: 3,
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+            concurrency: Concurrency::default(),
             client: None,
             markers: None,
             platform: None,
@@ -63,9 +69,35 @@ impl<'a> BaseClientBuilder<'a> {
         self
     }
 
+    /// Set the maximum number of times to retry a request that failed with a transient error.
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
-        self.retries = retries;
+        self.retry_policy.retries = retries;
+        self
+    }
+
+    /// Set the [`RetryPolicy`], including the backoff between retries and the status codes that
+    /// are retried.
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the request timeout, in seconds.
+    ///
+    /// If unset, the timeout is read from the `UV_HTTP_TIMEOUT` environment variable (or its
+    /// aliases), defaulting to five minutes.
+    #[must_use]
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the limits on concurrent requests and bandwidth.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = concurrency;
         self
     }
 
@@ -112,18 +144,20 @@ impl<'a> BaseClientBuilder<'a> {
         // Timeout options, matching https://doc.rust-lang.org/nightly/cargo/reference/config.html#httptimeout
         // `UV_REQUEST_TIMEOUT` is provided for backwards compatibility with v0.1.6
         let default_timeout = 5 * 60;
-        let timeout = env::var("UV_HTTP_TIMEOUT")
-            .or_else(|_| env::var("UV_REQUEST_TIMEOUT"))
-            .or_else(|_| env::var("HTTP_TIMEOUT"))
-            .and_then(|value| {
-                value.parse::<u64>()
-                    .or_else(|_| {
-                        // On parse error, warn and use the default timeout
-                        warn_user_once!("Ignoring invalid value from environment for UV_HTTP_TIMEOUT. Expected integer number of seconds, got \"{value}\".");
-                        Ok(default_timeout)
-                    })
-            })
-            .unwrap_or(default_timeout);
+        let timeout = self.timeout.unwrap_or_else(|| {
+            env::var("UV_HTTP_TIMEOUT")
+                .or_else(|_| env::var("UV_REQUEST_TIMEOUT"))
+                .or_else(|_| env::var("HTTP_TIMEOUT"))
+                .and_then(|value| {
+                    value.parse::<u64>()
+                        .or_else(|_| {
+                            // On parse error, warn and use the default timeout
+                            warn_user_once!("Ignoring invalid value from environment for UV_HTTP_TIMEOUT. Expected integer number of seconds, got \"{value}\".");
+                            Ok(default_timeout)
+                        })
+                })
+                .unwrap_or(default_timeout)
+        });
         debug!("Using registry request timeout of {}s", timeout);
 
         // Initialize the base client.
@@ -158,19 +192,38 @@ impl<'a> BaseClientBuilder<'a> {
         });
 
         // Wrap in any relevant middleware.
+        let metrics = Arc::<ClientMetrics>::default();
         let client = match self.connectivity {
             Connectivity::Online => {
                 let client = reqwest_middleware::ClientBuilder::new(client.clone());
 
                 // Initialize the retry strategy.
-                let retry_policy =
-                    ExponentialBackoff::builder().build_with_max_retries(self.retries);
-                let retry_strategy = RetryTransientMiddleware::new_with_policy(retry_policy);
-                let client = client.with(retry_strategy);
+                let client = if self.retry_policy.retries > 0 {
+                    client.with(RetryMiddleware::new(self.retry_policy.clone()))
+                } else {
+                    client
+                };
 
                 // Initialize the authentication middleware to set headers.
                 let client = client.with(AuthMiddleware::new(self.keyring_provider));
 
+                // Limit the requests made to each host, and the bandwidth they consume. Applied
+                // after the retry strategy, such that each retry is limited too.
+                let client = if self.concurrency.per_host.is_some()
+                    || self.concurrency.bandwidth.is_some()
+                {
+                    client.with(ThrottleMiddleware::new(
+                        self.concurrency.per_host,
+                        self.concurrency.bandwidth,
+                    ))
+                } else {
+                    client
+                };
+
+                // Count every request that's sent, including retries. Applied innermost, such that
+                // the size of the response is known.
+                let client = client.with(MetricsMiddleware::new(metrics.clone()));
+
                 client.build()
             }
             Connectivity::Offline => reqwest_middleware::ClientBuilder::new(client.clone())
@@ -182,6 +235,8 @@ impl<'a> BaseClientBuilder<'a> {
             connectivity: self.connectivity,
             client,
             timeout,
+            concurrency: self.concurrency,
+            metrics,
         }
     }
 }
@@ -195,6 +250,10 @@ pub struct BaseClient {
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// The limits on concurrent requests and bandwidth.
+    concurrency: Concurrency,
+    /// The counters for the requests made by this client, shared between its clones.
+    metrics: Arc<ClientMetrics>,
 }
 
 impl BaseClient {
@@ -212,6 +271,16 @@ impl BaseClient {
     pub fn connectivity(&self) -> Connectivity {
         self.connectivity
     }
+
+    /// The configured limits on concurrent requests and bandwidth.
+    pub fn concurrency(&self) -> Concurrency {
+        self.concurrency
+    }
+
+    /// The counters for the requests made by this client (and its clones) so far.
+    pub(crate) fn metrics(&self) -> &ClientMetrics {
+        &self.metrics
+    }
 }
 
 // To avoid excessively verbose call chains, as the [`BaseClient`] is often nested within other client types.
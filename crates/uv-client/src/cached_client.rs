@@ -10,6 +10,7 @@ use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use uv_cache::{CacheEntry, Freshness};
 use uv_fs::write_atomic;
 
+use crate::metrics::ClientMetricsSnapshot;
 use crate::BaseClient;
 use crate::{
     httpcache::{AfterResponse, BeforeRequest, CachePolicy, CachePolicyBuilder},
@@ -158,16 +159,24 @@ impl From<Freshness> for CacheControl {
 /// Again unlike `http-cache`, the caller gets full control over the cache key with the assumption
 /// that it's a file.
 #[derive(Debug, Clone)]
-pub struct CachedClient(BaseClient);
+pub struct CachedClient {
+    client: BaseClient,
+}
 
 impl CachedClient {
     pub fn new(client: BaseClient) -> Self {
-        Self(client)
+        Self { client }
     }
 
     /// The base client
     pub fn uncached(&self) -> BaseClient {
-        self.0.clone()
+        self.client.clone()
+    }
+
+    /// Return a snapshot of the requests made through this client (and its clones) so far,
+    /// including those made through the uncached client.
+    pub fn metrics(&self) -> ClientMetricsSnapshot {
+        self.client.metrics().snapshot()
     }
 
     /// Make a cached request with a custom response transformation
@@ -239,6 +248,12 @@ impl CachedClient {
                 }
             }
         };
+        match &cached_response {
+            CachedResponse::FreshCache(_) | CachedResponse::NotModified { .. } => {
+                self.client.metrics().record_cache_hit();
+            }
+            CachedResponse::ModifiedOrNew { .. } => self.client.metrics().record_cache_miss(),
+        }
         match cached_response {
             CachedResponse::FreshCache(cached) => match Payload::from_aligned_bytes(cached.data) {
                 Ok(payload) => Ok(payload),
@@ -316,6 +331,7 @@ impl CachedClient {
         CallbackReturn: Future<Output = Result<Payload, CallBackError>> + Send,
     {
         let (response, cache_policy) = self.fresh_request(req).await?;
+        self.client.metrics().record_cache_miss();
 
         let payload = self
             .run_response_callback(cache_entry, cache_policy, response, move |resp| async {
@@ -458,7 +474,7 @@ impl CachedClient {
         let url = req.url().clone();
         debug!("Sending revalidation request for: {url}");
         let response = self
-            .0
+            .client
             .execute(req)
             .instrument(info_span!("revalidation_request", url = url.as_str()))
             .await
@@ -497,7 +513,7 @@ impl CachedClient {
         trace!("Sending fresh {} request for {}", req.method(), req.url());
         let cache_policy_builder = CachePolicyBuilder::new(&req);
         let response = self
-            .0
+            .client
             .execute(req)
             .await
             .map_err(ErrorKind::from)?
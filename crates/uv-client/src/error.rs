@@ -10,6 +10,7 @@ use uv_normalize::PackageName;
 
 use crate::html;
 use crate::middleware::OfflineError;
+use crate::retry::RetriesExhaustedError;
 
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
@@ -30,11 +31,13 @@ impl Error {
 
     /// Create a new error from a JSON parsing error.
     pub(crate) fn from_json_err(err: serde_json::Error, url: Url) -> Self {
+        let url = uv_auth::redact_url(&url);
         ErrorKind::BadJson { source: err, url }.into()
     }
 
     /// Create a new error from an HTML parsing error.
     pub(crate) fn from_html_err(err: html::Error, url: Url) -> Self {
+        let url = uv_auth::redact_url(&url);
         ErrorKind::BadHtml { source: err, url }.into()
     }
 
@@ -43,6 +46,18 @@ impl Error {
         matches!(&*self.kind, ErrorKind::Offline(_))
     }
 
+    /// Returns the HTTP status of the response that caused this error, if any.
+    ///
+    /// If the request was retried until the retries were exhausted, returns the status of the
+    /// last attempt.
+    pub(crate) fn status(&self) -> Option<reqwest::StatusCode> {
+        match &*self.kind {
+            ErrorKind::ReqwestError(err) => err.status(),
+            ErrorKind::RetriesExhausted { source, .. } => source.status(),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if this error corresponds to an I/O "not found" error.
     pub(crate) fn is_file_not_exists(&self) -> bool {
         let ErrorKind::Io(ref err) = &*self.kind else {
@@ -80,6 +95,11 @@ impl Error {
                 }
             }
 
+            // The `HEAD` request was retried, so check the error of the last attempt.
+            ErrorKind::RetriesExhausted { source, .. } => {
+                return source.is_http_range_requests_unsupported();
+            }
+
             // The server doesn't support range requests, but we only discovered this while
             // unzipping due to erroneous server behavior.
             ErrorKind::Zip(_, ZipError::UpstreamReadError(err)) => {
@@ -227,6 +247,16 @@ pub enum ErrorKind {
 
     #[error("Network connectivity is disabled, but the requested data wasn't found in the cache for: `{0}`")]
     Offline(String),
+
+    /// A request failed with a transient error (e.g., a connection error or a `503 Service
+    /// Unavailable` response), and every retry failed too.
+    #[error("Request to {url} failed after {} attempts", retries + 1)]
+    RetriesExhausted {
+        url: Url,
+        retries: u32,
+        #[source]
+        source: Error,
+    },
 }
 
 impl From<reqwest::Error> for ErrorKind {
@@ -244,7 +274,16 @@ impl From<reqwest_middleware::Error> for ErrorKind {
         }
 
         match error {
-            reqwest_middleware::Error::Middleware(err) => Self::ReqwestMiddlewareError(err),
+            reqwest_middleware::Error::Middleware(err) => {
+                match err.downcast::<RetriesExhaustedError>() {
+                    Ok(err) => Self::RetriesExhausted {
+                        url: uv_auth::redact_url(err.url()),
+                        retries: err.retries(),
+                        source: Self::from(err.into_source()).into(),
+                    },
+                    Err(err) => Self::ReqwestMiddlewareError(err),
+                }
+            }
             reqwest_middleware::Error::Reqwest(err) => Self::from(err),
         }
     }
@@ -270,7 +309,11 @@ impl BetterReqwestError {
 }
 
 impl From<reqwest::Error> for BetterReqwestError {
-    fn from(error: reqwest::Error) -> Self {
+    fn from(mut error: reqwest::Error) -> Self {
+        // Avoid leaking credentials in error messages.
+        if let Some(url) = error.url_mut() {
+            *url = uv_auth::redact_url(url);
+        }
         Self(error)
     }
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use futures::{FutureExt, StreamExt};
 use reqwest::Response;
@@ -19,6 +19,9 @@ pub enum FlatIndexError {
     #[error("Failed to read `--find-links` directory: {0}")]
     FindLinksDirectory(PathBuf, #[source] std::io::Error),
 
+    #[error("Failed to read `--find-links` file: {0}")]
+    FindLinksFile(PathBuf, #[source] Error),
+
     #[error("Failed to read `--find-links` URL: {0}")]
     FindLinksUrl(Url, #[source] Error),
 }
@@ -66,8 +69,8 @@ impl FlatIndexEntries {
     }
 }
 
-/// A client for reading distributions from `--find-links` entries (either local directories or
-/// remote HTML indexes).
+/// A client for reading distributions from `--find-links` entries (either local directories, local
+/// HTML files, or remote HTML indexes).
 #[derive(Debug, Clone)]
 pub struct FlatIndexClient<'a> {
     client: &'a RegistryClient,
@@ -89,6 +92,10 @@ impl<'a> FlatIndexClient<'a> {
         let mut fetches = futures::stream::iter(indexes)
             .map(|index| async move {
                 let entries = match index {
+                    FlatIndexLocation::Path(path) if path.is_file() => {
+                        Self::read_from_html_file(path)
+                            .map_err(|err| FlatIndexError::FindLinksFile(path.clone(), err))?
+                    }
                     FlatIndexLocation::Path(path) => Self::read_from_directory(path)
                         .map_err(|err| FlatIndexError::FindLinksDirectory(path.clone(), err))?,
                     FlatIndexLocation::Url(url) => self
@@ -201,6 +208,57 @@ impl<'a> FlatIndexClient<'a> {
         }
     }
 
+    /// Read a flat index from a local `--find-links` HTML file (e.g., a page of links to wheels
+    /// and source distributions).
+    fn read_from_html_file(path: &Path) -> Result<FlatIndexEntries, Error> {
+        // Absolute paths are required for the URL conversion.
+        let path = fs_err::canonicalize(path).map_err(ErrorKind::Io)?;
+        let index_url = IndexUrl::Path(VerbatimUrl::from_path(&path));
+        let url = index_url.url().clone();
+
+        let text = fs_err::read_to_string(&path).map_err(ErrorKind::Io)?;
+        let SimpleHtml { base, files } =
+            SimpleHtml::parse(&text, &url).map_err(|err| Error::from_html_err(err, url.clone()))?;
+
+        let mut dists = Vec::new();
+        for file in files {
+            let mut file = match File::try_from(file, base.as_url()) {
+                Ok(file) => file,
+                Err(err) => {
+                    // Ignore files with unparsable version specifiers.
+                    warn!("Skipping file in {}: {err}", path.display());
+                    continue;
+                }
+            };
+
+            // Links to local files are read directly from disk, like those in a `--find-links`
+            // directory.
+            let file_url = match &file.url {
+                FileLocation::RelativeUrl(base, url) => {
+                    pypi_types::base_url_join_relative(base, url).ok()
+                }
+                FileLocation::AbsoluteUrl(url) => Url::parse(url).ok(),
+                FileLocation::Path(_) => None,
+            };
+            if let Some(file_path) = file_url
+                .filter(|url| url.scheme() == "file")
+                .and_then(|url| url.to_file_path().ok())
+            {
+                file.url = FileLocation::Path(file_path);
+            }
+
+            let Some(filename) = DistFilename::try_from_normalized_filename(&file.filename) else {
+                debug!(
+                    "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
+                    file.filename
+                );
+                continue;
+            };
+            dists.push((filename, file, index_url.clone()));
+        }
+        Ok(FlatIndexEntries::from_entries(dists))
+    }
+
     /// Read a flat remote index from a `--find-links` directory.
     fn read_from_directory(path: &PathBuf) -> Result<FlatIndexEntries, std::io::Error> {
         // Absolute paths are required for the URL conversion.
@@ -227,6 +285,7 @@ impl<'a> FlatIndexClient<'a> {
                 dist_info_metadata: false,
                 filename: filename.to_string(),
                 hashes: Vec::new(),
+                provenance: None,
                 requires_python: None,
                 size: None,
                 upload_time_utc_ms: None,
@@ -246,3 +305,66 @@ impl<'a> FlatIndexClient<'a> {
         Ok(FlatIndexEntries::from_entries(dists))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use distribution_filename::DistFilename;
+    use distribution_types::FileLocation;
+
+    use super::FlatIndexClient;
+
+    #[test]
+    fn read_from_html_file() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let index = temp_dir.path().join("index.html");
+        fs_err::write(
+            &index,
+            r#"
+            <html>
+              <body>
+                <a href="wheels/example-1.0.0-py3-none-any.whl">example-1.0.0-py3-none-any.whl</a>
+                <a href="https://example.com/files/example-1.0.0.tar.gz">example-1.0.0.tar.gz</a>
+                <a href="README.txt">README.txt</a>
+              </body>
+            </html>
+            "#,
+        )?;
+
+        let entries = FlatIndexClient::read_from_html_file(&index)?;
+        assert!(!entries.offline);
+
+        // The `README.txt` isn't a distribution, and is ignored.
+        let [(wheel_filename, wheel, _), (sdist_filename, sdist, _)] = entries.entries.as_slice()
+        else {
+            panic!("expected two entries, got: {:?}", entries.entries);
+        };
+
+        // Links to local files are resolved relative to the HTML file.
+        assert!(matches!(wheel_filename, DistFilename::WheelFilename(_)));
+        let expected = fs_err::canonicalize(temp_dir.path())?
+            .join("wheels")
+            .join("example-1.0.0-py3-none-any.whl");
+        assert!(
+            matches!(&wheel.url, FileLocation::Path(path) if *path == expected),
+            "{:?}",
+            wheel.url
+        );
+
+        // Remote links are left as-is.
+        assert!(matches!(
+            sdist_filename,
+            DistFilename::SourceDistFilename(_)
+        ));
+        assert!(
+            matches!(
+                &sdist.url,
+                FileLocation::AbsoluteUrl(url) if url == "https://example.com/files/example-1.0.0.tar.gz"
+            ),
+            "{:?}",
+            sdist.url
+        );
+
+        Ok(())
+    }
+}
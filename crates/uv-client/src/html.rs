@@ -69,6 +69,24 @@ impl SimpleHtml {
         Ok(Some(url))
     }
 
+    /// Parse a PEP 658 metadata attribute (e.g., `data-dist-info-metadata`) from a link, which is
+    /// either `true`, `false`, or a hash of the metadata file.
+    fn parse_metadata_attribute(
+        link: &HTMLTag,
+        attribute: &str,
+    ) -> Result<Option<DistInfoMetadata>, Error> {
+        let Some(metadata) = link.attributes().get(attribute).flatten() else {
+            return Ok(None);
+        };
+        let metadata = std::str::from_utf8(metadata.as_bytes())?;
+        let metadata = html_escape::decode_html_entities(metadata);
+        match metadata.as_ref() {
+            "true" => Ok(Some(DistInfoMetadata::Bool(true))),
+            "false" => Ok(Some(DistInfoMetadata::Bool(false))),
+            fragment => Ok(Some(DistInfoMetadata::Hashes(Self::parse_hash(fragment)?))),
+        }
+    }
+
     /// Parse the hash from a fragment, as in: `sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61`
     fn parse_hash(fragment: &str) -> Result<Hashes, Error> {
         let mut parts = fragment.split('=');
@@ -183,21 +201,20 @@ impl SimpleHtml {
             None
         };
 
-        // Extract the `data-dist-info-metadata` field, which should be set on
-        // the `data-dist-info-metadata` attribute.
-        let dist_info_metadata = if let Some(dist_info_metadata) =
-            link.attributes().get("data-dist-info-metadata").flatten()
-        {
-            let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
-            let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
-            match dist_info_metadata.as_ref() {
-                "true" => Some(DistInfoMetadata::Bool(true)),
-                "false" => Some(DistInfoMetadata::Bool(false)),
-                fragment => Some(DistInfoMetadata::Hashes(Self::parse_hash(fragment)?)),
-            }
-        } else {
-            None
-        };
+        // Extract the `data-core-metadata` field (per PEP 714), or its predecessor, the
+        // `data-dist-info-metadata` field.
+        let core_metadata = Self::parse_metadata_attribute(link, "data-core-metadata")?;
+        let dist_info_metadata = Self::parse_metadata_attribute(link, "data-dist-info-metadata")?;
+
+        // Extract the `data-provenance` field (per PEP 740), which links to the attestations for
+        // the file.
+        let provenance =
+            if let Some(provenance) = link.attributes().get("data-provenance").flatten() {
+                let provenance = std::str::from_utf8(provenance.as_bytes())?;
+                Some(html_escape::decode_html_entities(provenance).to_string())
+            } else {
+                None
+            };
 
         // Extract the `yanked` field, which should be set on the `data-yanked`
         // attribute.
@@ -210,10 +227,12 @@ impl SimpleHtml {
         };
 
         Ok(File {
+            core_metadata,
             dist_info_metadata,
             yanked,
             requires_python,
             hashes,
+            provenance,
             filename: filename.to_string(),
             url: decoded.to_string(),
             size: None,
@@ -296,6 +315,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -306,6 +326,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -352,6 +373,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -362,6 +384,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -411,6 +434,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -421,6 +445,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -467,6 +492,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2+233fca715f49-py3-none-any.whl",
                     hashes: Hashes {
@@ -477,6 +503,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -523,6 +550,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "torchtext-0.17.0+cpu-cp39-cp39-win_amd64.whl",
                     hashes: Hashes {
@@ -531,6 +559,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -577,6 +606,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -585,6 +615,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -596,6 +627,30 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn parse_core_metadata() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl" data-core-metadata="true" data-dist-info-metadata="false">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base).unwrap();
+        let file = &result.files[0];
+        assert!(matches!(
+            file.core_metadata,
+            Some(DistInfoMetadata::Bool(true))
+        ));
+        assert!(matches!(
+            file.dist_info_metadata,
+            Some(DistInfoMetadata::Bool(false))
+        ));
+    }
+
     #[test]
     fn parse_missing_href() {
         let text = r"
@@ -665,6 +720,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -673,6 +729,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -719,6 +776,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -727,6 +785,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -808,6 +867,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "jaxlib-0.1.52+cuda100-cp36-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
@@ -816,6 +876,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -823,6 +884,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "jaxlib-0.1.52+cuda100-cp37-none-manylinux2010_x86_64.whl",
                     hashes: Hashes {
@@ -831,6 +893,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -887,6 +950,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Flask-0.1.tar.gz",
                     hashes: Hashes {
@@ -897,6 +961,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -904,6 +969,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Flask-0.10.1.tar.gz",
                     hashes: Hashes {
@@ -914,6 +980,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: None,
                     size: None,
                     upload_time: None,
@@ -921,6 +988,7 @@ mod tests {
                     yanked: None,
                 },
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "flask-3.0.1.tar.gz",
                     hashes: Hashes {
@@ -931,6 +999,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: Some(
                         Ok(
                             VersionSpecifiers(
@@ -987,6 +1056,7 @@ mod tests {
             ),
             files: [
                 File {
+                    core_metadata: None,
                     dist_info_metadata: None,
                     filename: "Jinja2-3.1.2-py3-none-any.whl",
                     hashes: Hashes {
@@ -997,6 +1067,7 @@ mod tests {
                         sha384: None,
                         sha512: None,
                     },
+                    provenance: None,
                     requires_python: Some(
                         Ok(
                             VersionSpecifiers(
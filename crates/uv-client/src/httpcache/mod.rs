@@ -465,14 +465,14 @@ impl ArchivedCachePolicy {
                 }
             }
         }
-        // As per [RFC 9111 S4.3.4], if we have no validators anywhere, then
-        // we can just rely on the HTTP 304 status code and reuse the cached
-        // response.
+        // As per [RFC 9111 S4.3.4], if the new response has no validators,
+        // then we can just rely on the HTTP 304 status code and reuse the
+        // cached response. (Servers are not required to repeat the validators
+        // in a 304 response, and since a 304 has no body, treating it as
+        // modified would leave us with nothing to cache.)
         //
         // [RFC 9111 S4.3.4]: https://www.rfc-editor.org/rfc/rfc9111.html#section-4.3.4
-        if self.response.headers.etag.is_none()
-            && new_policy.response.headers.etag.is_none()
-            && self.response.headers.last_modified_unix_timestamp.is_none()
+        if new_policy.response.headers.etag.is_none()
             && new_policy
                 .response
                 .headers
@@ -480,8 +480,8 @@ impl ArchivedCachePolicy {
                 .is_none()
         {
             tracing::trace!(
-                "not modified because there are no etags or last modified \
-                 timestamps, so we assume the 304 status is correct",
+                "not modified because the 304 response has no etag or last \
+                 modified timestamp, so we assume the 304 status is correct",
             );
             return false;
         }
@@ -1371,3 +1371,82 @@ fn parse_seconds(value: &[u8]) -> Option<u64> {
     }
     std::str::from_utf8(value).ok()?.parse().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> reqwest::Request {
+        reqwest::Request::new(
+            reqwest::Method::GET,
+            "https://pypi.org/simple/flask/".parse().unwrap(),
+        )
+    }
+
+    fn response(status: u16, headers: &[(&'static str, &'static str)]) -> reqwest::Response {
+        let mut builder = http::Response::builder().status(status);
+        for &(name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        reqwest::Response::from(builder.body("").unwrap())
+    }
+
+    /// Cache a response with the given headers, then revalidate it against the given response.
+    fn revalidate(
+        cached: &[(&'static str, &'static str)],
+        revalidation: &reqwest::Response,
+    ) -> (reqwest::Request, AfterResponse) {
+        let policy = CachePolicyBuilder::new(&request())
+            .build(&response(200, cached))
+            .to_archived();
+        let mut request = request();
+        let BeforeRequest::Stale(builder) = policy.before_request(&mut request) else {
+            panic!("expected a stale cached response");
+        };
+        let after = policy.after_response(builder, revalidation);
+        (request, after)
+    }
+
+    #[test]
+    fn revalidate_etag() {
+        let cached = [("cache-control", "no-cache"), ("etag", "\"abc\"")];
+
+        // The revalidation request should include the cached validator.
+        let (request, after) = revalidate(&cached, &response(304, &[("etag", "\"abc\"")]));
+        assert_eq!(request.headers()["if-none-match"], "\"abc\"");
+        assert!(matches!(after, AfterResponse::NotModified(_)));
+
+        // A 304 is allowed to omit the validators.
+        let (_, after) = revalidate(&cached, &response(304, &[]));
+        assert!(matches!(after, AfterResponse::NotModified(_)));
+
+        // A 304 with a mismatched validator doesn't match the cached response.
+        let (_, after) = revalidate(&cached, &response(304, &[("etag", "\"def\"")]));
+        assert!(matches!(after, AfterResponse::Modified(_)));
+
+        // Any other status indicates that the resource has changed.
+        let (_, after) = revalidate(&cached, &response(200, &[("etag", "\"def\"")]));
+        assert!(matches!(after, AfterResponse::Modified(_)));
+    }
+
+    #[test]
+    fn revalidate_last_modified() {
+        let cached = [
+            ("cache-control", "no-cache"),
+            ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ];
+
+        let (request, after) = revalidate(
+            &cached,
+            &response(304, &[("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")]),
+        );
+        assert!(request.headers().contains_key("if-modified-since"));
+        assert!(matches!(after, AfterResponse::NotModified(_)));
+
+        let (_, after) = revalidate(
+            &cached,
+            &response(304, &[("last-modified", "Thu, 22 Oct 2015 07:28:00 GMT")]),
+        );
+        assert!(matches!(after, AfterResponse::Modified(_)));
+    }
+}
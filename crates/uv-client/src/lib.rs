@@ -3,10 +3,12 @@ pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithC
 pub use error::{BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatIndexClient, FlatIndexEntries, FlatIndexError};
 pub use linehaul::LineHaul;
+pub use metrics::ClientMetricsSnapshot;
 pub use registry_client::{
     Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
 };
+pub use retry::RetryPolicy;
 pub use rkyvutil::OwnedArchive;
 
 mod base_client;
@@ -16,7 +18,9 @@ mod flat_index;
 mod html;
 mod httpcache;
 mod linehaul;
+mod metrics;
 mod middleware;
 mod registry_client;
 mod remote_metadata;
+mod retry;
 mod rkyvutil;
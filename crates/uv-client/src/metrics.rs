@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+
+/// Counters for the requests made through a [`crate::BaseClient`], whether directly (e.g., for
+/// range requests and wheel downloads) or through a [`crate::CachedClient`].
+///
+/// The counters are shared between all clones of the client, so a snapshot taken after a
+/// resolution covers every request made on its behalf.
+#[derive(Debug, Default)]
+pub(crate) struct ClientMetrics {
+    requests: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    bytes_downloaded: AtomicU64,
+}
+
+impl ClientMetrics {
+    /// Record an HTTP request, along with the size of its response body, if known.
+    pub(crate) fn record_request(&self, content_length: Option<u64>) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if let Some(content_length) = content_length {
+            self.bytes_downloaded
+                .fetch_add(content_length, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a response that was served from the cache, with or without revalidation.
+    pub(crate) fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a response that had to be fetched from the network.
+    pub(crate) fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Return a point-in-time copy of the counters.
+    pub(crate) fn snapshot(&self) -> ClientMetricsSnapshot {
+        ClientMetricsSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            bytes_downloaded: self.bytes_downloaded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The requests made by a client, as of the time the snapshot was taken.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ClientMetricsSnapshot {
+    /// The number of HTTP requests sent, including revalidation requests and retries.
+    pub requests: u64,
+    /// The number of cacheable responses served from the cache, including those that were
+    /// revalidated with the server.
+    pub cache_hits: u64,
+    /// The number of cacheable responses that had to be fetched from the network.
+    pub cache_misses: u64,
+    /// The number of response bytes received, as reported by the `Content-Length` header.
+    ///
+    /// Responses without a `Content-Length` (e.g., compressed or chunked responses) aren't
+    /// counted.
+    pub bytes_downloaded: u64,
+}
+
+impl ClientMetricsSnapshot {
+    /// The fraction of cacheable responses that were served from the cache, or `None` if no
+    /// cacheable requests were made.
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        #[allow(clippy::cast_precision_loss)]
+        (total > 0).then(|| self.cache_hits as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClientMetrics;
+
+    #[test]
+    fn snapshot() {
+        let metrics = ClientMetrics::default();
+        assert_eq!(metrics.snapshot().cache_hit_rate(), None);
+
+        metrics.record_request(Some(1024));
+        metrics.record_cache_miss();
+        metrics.record_request(None);
+        metrics.record_cache_hit();
+        metrics.record_cache_hit();
+        metrics.record_cache_hit();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.requests, 2);
+        assert_eq!(snapshot.bytes_downloaded, 1024);
+        assert_eq!(snapshot.cache_hits, 3);
+        assert_eq!(snapshot.cache_misses, 1);
+        assert_eq!(snapshot.cache_hit_rate(), Some(0.75));
+    }
+}
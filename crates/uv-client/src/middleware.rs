@@ -1,10 +1,19 @@
 use http::Extensions;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use reqwest::{Request, Response};
+use futures::StreamExt;
+use reqwest::{Request, Response, ResponseBuilderExt};
 use reqwest_middleware::{Middleware, Next};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::trace;
 use url::Url;
 
+use crate::metrics::ClientMetrics;
+
 /// A custom error type for the offline middleware.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct OfflineError {
@@ -45,3 +54,300 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// A middleware that counts the requests sent by a client, and the bytes received in response.
+///
+/// Applied innermost, such that every attempt (including retries) is counted, and such that the
+/// response body hasn't yet been wrapped by the [`ThrottleMiddleware`].
+pub(crate) struct MetricsMiddleware {
+    metrics: Arc<ClientMetrics>,
+}
+
+impl MetricsMiddleware {
+    pub(crate) fn new(metrics: Arc<ClientMetrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for MetricsMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let response = next.run(req, extensions).await?;
+        self.metrics.record_request(content_length(&response));
+        Ok(response)
+    }
+}
+
+/// Return the size of the response body, if known.
+///
+/// Falls back to the `Content-Length` header for bodies that don't report their size.
+fn content_length(response: &Response) -> Option<u64> {
+    response.content_length().or_else(|| {
+        response
+            .headers()
+            .get(http::header::CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    })
+}
+
+/// A middleware that limits the number of concurrent requests to each host, and the average
+/// bandwidth across all requests.
+///
+/// A per-host slot is held until the response body has been read (or dropped), such that
+/// long-running downloads count against the limit. The bandwidth limit is enforced by pacing the
+/// chunks of each response body, and by delaying new requests until the bytes received so far
+/// would have been transferred at the configured rate.
+pub(crate) struct ThrottleMiddleware {
+    /// The maximum number of concurrent requests to any single host.
+    per_host: Option<NonZeroUsize>,
+    /// The semaphore for each host that has been requested so far.
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+    /// The limit on the average bandwidth across all requests.
+    bandwidth: Option<Arc<BandwidthLimit>>,
+}
+
+impl ThrottleMiddleware {
+    pub(crate) fn new(per_host: Option<NonZeroUsize>, bandwidth: Option<u64>) -> Self {
+        Self {
+            per_host,
+            hosts: Mutex::default(),
+            bandwidth: bandwidth.map(|bandwidth| Arc::new(BandwidthLimit::new(bandwidth))),
+        }
+    }
+
+    /// Return the semaphore that limits concurrent requests to the host of the given URL.
+    fn semaphore(&self, url: &Url) -> Option<Arc<Semaphore>> {
+        let per_host = self.per_host?;
+        let host = url.host_str()?;
+        let mut hosts = self.hosts.lock().unwrap();
+        let semaphore = hosts
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(per_host.get())));
+        Some(semaphore.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for ThrottleMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        // Wait for the bandwidth budget to catch up with the bytes received so far.
+        if let Some(bandwidth) = self.bandwidth.as_ref() {
+            let available_at = bandwidth.available_at();
+            if available_at > Instant::now() {
+                trace!(
+                    "Delaying request to {} to respect the bandwidth limit",
+                    req.url()
+                );
+                tokio::time::sleep_until(available_at.into()).await;
+            }
+        }
+
+        // Wait for a free slot for the host.
+        let permit = match self.semaphore(req.url()) {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        let response = next.run(req, extensions).await?;
+        if permit.is_none() && self.bandwidth.is_none() {
+            return Ok(response);
+        }
+        Ok(throttle_body(response, permit, self.bandwidth.clone()))
+    }
+}
+
+/// Wrap the body of a [`Response`], such that it's paced to the bandwidth limit, and the per-host
+/// permit is held until the body is read to completion or dropped.
+fn throttle_body(
+    response: Response,
+    permit: Option<OwnedSemaphorePermit>,
+    bandwidth: Option<Arc<BandwidthLimit>>,
+) -> Response {
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+
+    let stream = response
+        .bytes_stream()
+        .then(move |chunk| {
+            let bandwidth = bandwidth.clone();
+            async move {
+                if let (Ok(bytes), Some(bandwidth)) = (&chunk, bandwidth) {
+                    let available_at = bandwidth.consume(bytes.len() as u64);
+                    tokio::time::sleep_until(available_at.into()).await;
+                }
+                chunk
+            }
+        })
+        .map(move |chunk| {
+            // Release the permit only once the stream itself is dropped.
+            let _permit = &permit;
+            chunk
+        });
+
+    Response::from(
+        builder
+            .body(reqwest::Body::wrap_stream(stream))
+            .expect("response parts are valid"),
+    )
+}
+
+/// A limit on the average bandwidth shared by a set of requests.
+#[derive(Debug)]
+struct BandwidthLimit {
+    /// The maximum average bandwidth, in bytes per second.
+    bytes_per_second: u64,
+    /// The instant at which the bytes received so far would have been transferred at the
+    /// configured bandwidth.
+    available_at: Mutex<Instant>,
+}
+
+impl BandwidthLimit {
+    fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second,
+            available_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Return the instant at which the bytes received so far would have been transferred.
+    fn available_at(&self) -> Instant {
+        *self.available_at.lock().unwrap()
+    }
+
+    /// Account for the given number of received bytes, returning the instant at which they (and
+    /// all bytes received before them) would have been transferred.
+    fn consume(&self, bytes: u64) -> Instant {
+        #[allow(clippy::cast_precision_loss)]
+        let duration = Duration::from_secs_f64(bytes as f64 / self.bytes_per_second as f64);
+        let mut available_at = self.available_at.lock().unwrap();
+        *available_at = (*available_at).max(Instant::now()) + duration;
+        *available_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use anyhow::Result;
+    use http_body_util::Full;
+    use hyper::body::Bytes;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpListener;
+    use url::Url;
+
+    use crate::metrics::ClientMetrics;
+
+    use super::{MetricsMiddleware, ThrottleMiddleware};
+
+    /// Serve the given body in response to every request, returning the URL of the server.
+    async fn serve(body: &'static [u8]) -> Result<Url> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let svc = service_fn(move |_| async move {
+                        Ok::<_, hyper::Error>(hyper::Response::new(Full::new(Bytes::from(body))))
+                    });
+                    http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), svc)
+                        .await
+                        .unwrap();
+                });
+            }
+        });
+        Ok(Url::parse(&format!("http://{addr}/"))?)
+    }
+
+    #[tokio::test]
+    async fn per_host_permit_held_until_body_is_read() -> Result<()> {
+        let url = serve(b"hello").await?;
+        let throttle = Arc::new(ThrottleMiddleware::new(NonZeroUsize::new(1), None));
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with_arc(throttle.clone())
+            .build();
+        let semaphore = throttle.semaphore(&url).unwrap();
+
+        // The permit outlives the response headers...
+        let response = client.get(url.clone()).send().await?;
+        assert_eq!(semaphore.available_permits(), 0);
+
+        // ...and is released once the body has been read.
+        assert_eq!(response.bytes().await?.as_ref(), b"hello");
+        assert_eq!(semaphore.available_permits(), 1);
+
+        // Dropping an unread response releases the permit too.
+        let response = client.get(url).send().await?;
+        assert_eq!(semaphore.available_permits(), 0);
+        drop(response);
+        assert_eq!(semaphore.available_permits(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bandwidth_paces_body() -> Result<()> {
+        let url = serve(&[0; 2000]).await?;
+        let throttle = ThrottleMiddleware::new(None, Some(10_000));
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(throttle)
+            .build();
+
+        // 2000 bytes at 10,000 bytes per second should take at least 200ms.
+        let start = Instant::now();
+        let response = client.get(url).send().await?;
+        assert_eq!(response.url().path(), "/");
+        assert_eq!(response.bytes().await?.len(), 2000);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn metrics_count_requests_and_bytes() -> Result<()> {
+        let url = serve(b"hello").await?;
+        let metrics = Arc::new(ClientMetrics::default());
+        let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(MetricsMiddleware::new(metrics.clone()))
+            .build();
+
+        client.get(url.clone()).send().await?.bytes().await?;
+        client.get(url).send().await?.bytes().await?;
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.requests, 2);
+        assert_eq!(snapshot.bytes_downloaded, 10);
+        assert_eq!(snapshot.cache_hit_rate(), None);
+
+        Ok(())
+    }
+}
@@ -22,7 +22,7 @@ use platform_tags::Platform;
 use pypi_types::{Metadata23, SimpleJson};
 use uv_auth::KeyringProvider;
 use uv_cache::{Cache, CacheBucket, WheelCache};
-use uv_configuration::IndexStrategy;
+use uv_configuration::{Concurrency, IndexStrategy};
 use uv_normalize::PackageName;
 
 use crate::base_client::{BaseClient, BaseClientBuilder};
@@ -30,7 +30,9 @@ use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
 use crate::rkyvutil::OwnedArchive;
-use crate::{CachedClient, CachedClientError, Error, ErrorKind};
+use crate::{
+    CachedClient, CachedClientError, ClientMetricsSnapshot, Error, ErrorKind, RetryPolicy,
+};
 
 /// A builder for an [`RegistryClient`].
 #[derive(Debug, Clone)]
@@ -39,7 +41,9 @@ pub struct RegistryClientBuilder<'a> {
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
     native_tls: bool,
-    retries: u32,
+    retry_policy: RetryPolicy,
+    timeout: Option<u64>,
+    concurrency: Option<Concurrency>,
     connectivity: Connectivity,
     cache: Cache,
     client: Option<Client>,
@@ -56,7 +60,9 @@ impl RegistryClientBuilder<'_> {
             native_tls: false,
             cache,
             connectivity: Connectivity::Online,
-            retries: 3,
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+            concurrency: None,
             client: None,
             markers: None,
             platform: None,
@@ -89,9 +95,32 @@ impl<'a> RegistryClientBuilder<'a> {
         self
     }
 
+    /// Set the maximum number of times to retry a request that failed with a transient error.
     #[must_use]
     pub fn retries(mut self, retries: u32) -> Self {
-        self.retries = retries;
+        self.retry_policy.retries = retries;
+        self
+    }
+
+    /// Set the [`RetryPolicy`], including the backoff between retries and the status codes that
+    /// are retried.
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the request timeout, in seconds.
+    #[must_use]
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the limits on concurrent requests and bandwidth.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = Some(concurrency);
         self
     }
 
@@ -141,8 +170,16 @@ impl<'a> RegistryClientBuilder<'a> {
             builder = builder.platform(platform)
         }
 
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout)
+        }
+
+        if let Some(concurrency) = self.concurrency {
+            builder = builder.concurrency(concurrency)
+        }
+
         let client = builder
-            .retries(self.retries)
+            .retry_policy(self.retry_policy)
             .connectivity(self.connectivity)
             .native_tls(self.native_tls)
             .keyring_provider(self.keyring_provider)
@@ -150,6 +187,7 @@ impl<'a> RegistryClientBuilder<'a> {
 
         let timeout = client.timeout();
         let connectivity = client.connectivity();
+        let concurrency = client.concurrency();
 
         // Wrap in the cache middleware.
         let client = CachedClient::new(client);
@@ -161,10 +199,21 @@ impl<'a> RegistryClientBuilder<'a> {
             connectivity,
             client,
             timeout,
+            concurrency,
         }
     }
 }
 
+/// Return the directory within the [`CacheBucket::Simple`] bucket that holds the cached "Simple
+/// API" responses for the given index.
+fn simple_cache_dir(index: &IndexUrl) -> String {
+    match index {
+        IndexUrl::Pypi(_) => "pypi".to_string(),
+        IndexUrl::Url(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
+        IndexUrl::Path(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
+    }
+}
+
 /// A client for fetching packages from a `PyPI`-compatible index.
 #[derive(Debug, Clone)]
 pub struct RegistryClient {
@@ -180,6 +229,8 @@ pub struct RegistryClient {
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// The limits on concurrent requests and bandwidth.
+    concurrency: Concurrency,
 }
 
 impl RegistryClient {
@@ -193,11 +244,21 @@ impl RegistryClient {
         self.client.uncached()
     }
 
+    /// Return a snapshot of the requests made by this client so far, across all of its clones.
+    pub fn metrics(&self) -> ClientMetricsSnapshot {
+        self.client.metrics()
+    }
+
     /// Return the [`Connectivity`] mode used by this client.
     pub fn connectivity(&self) -> Connectivity {
         self.connectivity
     }
 
+    /// Return the limits on concurrent requests and bandwidth this client is configured with.
+    pub fn concurrency(&self) -> Concurrency {
+        self.concurrency
+    }
+
     /// Return the timeout this client is configured with, in seconds.
     pub fn timeout(&self) -> u64 {
         self.timeout
@@ -214,12 +275,14 @@ impl RegistryClient {
     /// "simple" here refers to [PEP 503 – Simple Repository API](https://peps.python.org/pep-0503/)
     /// and [PEP 691 – JSON-based Simple API for Python Package Indexes](https://peps.python.org/pep-0691/),
     /// which the pypi json api approximately implements.
+    ///
+    /// If the package is pinned to an index, only that index is searched.
     #[instrument("simple_api", skip_all, fields(package = % package_name))]
     pub async fn simple(
         &self,
         package_name: &PackageName,
     ) -> Result<Vec<(IndexUrl, OwnedArchive<SimpleMetadata>)>, Error> {
-        let mut it = self.index_urls.indexes().peekable();
+        let mut it = self.index_urls.indexes_for(package_name).peekable();
         if it.peek().is_none() {
             return Err(ErrorKind::NoIndex(package_name.as_ref().to_string()).into());
         }
@@ -235,18 +298,18 @@ impl RegistryClient {
                         break;
                     }
                 }
-                Err(CachedClientError::Client(err)) => match err.into_kind() {
-                    ErrorKind::Offline(_) => continue,
-                    ErrorKind::ReqwestError(err) => {
-                        if err.status() == Some(StatusCode::NOT_FOUND)
-                            || err.status() == Some(StatusCode::FORBIDDEN)
-                        {
-                            continue;
-                        }
-                        return Err(ErrorKind::from(err).into());
+                Err(CachedClientError::Client(err)) => {
+                    // Skip indexes that don't have the package, even if the request was retried.
+                    if err.is_offline()
+                        || matches!(
+                            err.status(),
+                            Some(StatusCode::NOT_FOUND | StatusCode::FORBIDDEN)
+                        )
+                    {
+                        continue;
                     }
-                    other => return Err(other.into()),
-                },
+                    return Err(err);
+                }
                 Err(CachedClientError::Callback(err)) => return Err(err),
             };
         }
@@ -263,6 +326,38 @@ impl RegistryClient {
         Ok(results)
     }
 
+    /// Return the names of the packages for which a "Simple API" response is cached, across all
+    /// of the configured indexes.
+    ///
+    /// Used to suggest alternatives for a package that could not be found (e.g., `requests` for
+    /// `reqests`), without making any additional requests.
+    pub fn cached_package_names(&self) -> Vec<PackageName> {
+        let mut names = self
+            .index_urls
+            .indexes()
+            .filter_map(|index| {
+                fs_err::read_dir(
+                    self.cache
+                        .shard(CacheBucket::Simple, simple_cache_dir(index))
+                        .as_ref(),
+                )
+                .ok()
+            })
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension().is_some_and(|ext| ext == "rkyv") {
+                    PackageName::from_str(path.file_stem()?.to_str()?).ok()
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
     async fn simple_single_index(
         &self,
         package_name: &PackageName,
@@ -282,11 +377,7 @@ impl RegistryClient {
 
         let cache_entry = self.cache.entry(
             CacheBucket::Simple,
-            Path::new(&match index {
-                IndexUrl::Pypi(_) => "pypi".to_string(),
-                IndexUrl::Url(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
-                IndexUrl::Path(url) => cache_key::digest(&cache_key::CanonicalUrl::new(url)),
-            }),
+            Path::new(&simple_cache_dir(index)),
             format!("{package_name}.rkyv"),
         );
         let cache_control = match self.connectivity {
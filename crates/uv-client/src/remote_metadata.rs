@@ -1,5 +1,9 @@
+use std::io::SeekFrom;
+use std::ops::Range;
+
 use async_http_range_reader::AsyncHttpRangeReader;
 use futures::io::BufReader;
+use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
 use distribution_filename::WheelFilename;
@@ -56,9 +60,17 @@ pub(crate) async fn wheel_metadata_from_remote_zip(
     // Best guess for the central directory size inside the zip
     const CENTRAL_DIRECTORY_SIZE: u64 = 16384;
     // Because the zip index is at the back
-    reader
-        .prefetch(reader.len().saturating_sub(CENTRAL_DIRECTORY_SIZE)..reader.len())
-        .await;
+    let tail = reader.len().saturating_sub(CENTRAL_DIRECTORY_SIZE);
+    reader.prefetch(tail..reader.len()).await;
+
+    // If the central directory is larger than our guess (e.g., for wheels with many files), fetch
+    // the remainder in a single request, rather than reading it piecemeal.
+    let len = reader.len();
+    if let Some(central_directory) = central_directory_range(reader.compat(), len).await {
+        if central_directory.start < tail {
+            reader.prefetch(central_directory.start..tail).await;
+        }
+    }
 
     // Construct a zip reader to uses the stream.
     let buf = BufReader::new(reader.compat());
@@ -107,3 +119,92 @@ pub(crate) async fn wheel_metadata_from_remote_zip(
 
     Ok(contents)
 }
+
+/// The size of the end of central directory record, excluding the trailing comment.
+const END_OF_CENTRAL_DIRECTORY_SIZE: u64 = 22;
+
+/// Return the byte range of the central directory, as advertised by the end of central directory
+/// record of an archive of the given length.
+///
+/// Returns `None` if the record can't be found at the very end of the archive (i.e., if the archive
+/// has a trailing comment), or if the archive uses ZIP64 sizes and offsets.
+async fn central_directory_range(
+    mut reader: impl AsyncRead + AsyncSeek + Unpin,
+    len: u64,
+) -> Option<Range<u64>> {
+    let start = len.checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE)?;
+
+    let mut record = [0u8; 22];
+    reader.seek(SeekFrom::Start(start)).await.ok()?;
+    reader.read_exact(&mut record).await.ok()?;
+
+    if record[0..4] != [0x50, 0x4b, 0x05, 0x06] {
+        return None;
+    }
+    let size = u32::from_le_bytes(record[12..16].try_into().ok()?);
+    let offset = u32::from_le_bytes(record[16..20].try_into().ok()?);
+    if size == u32::MAX || offset == u32::MAX {
+        return None;
+    }
+
+    let offset = u64::from(offset);
+    let end = offset.checked_add(u64::from(size))?;
+    (end <= start).then_some(offset..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    use super::central_directory_range;
+
+    /// Write a small archive, with an optional trailing comment.
+    fn archive(comment: Option<&str>) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for name in ["foo-1.0.dist-info/METADATA", "foo-1.0.dist-info/WHEEL"] {
+            writer.start_file(name, FileOptions::default()).unwrap();
+            writer.write_all(b"Metadata-Version: 2.1\n").unwrap();
+        }
+        if let Some(comment) = comment {
+            writer.set_comment(comment);
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[tokio::test]
+    async fn central_directory() {
+        let bytes = archive(None);
+        let len = bytes.len() as u64;
+        let range = central_directory_range(futures::io::Cursor::new(&bytes), len)
+            .await
+            .unwrap();
+
+        // The central directory ends where the end of central directory record begins, and starts
+        // with the signature of its first file header.
+        assert_eq!(range.end, len - 22);
+        let start = usize::try_from(range.start).unwrap();
+        assert_eq!(bytes[start..start + 4], [0x50, 0x4b, 0x01, 0x02]);
+    }
+
+    #[tokio::test]
+    async fn central_directory_trailing_comment() {
+        let bytes = archive(Some("A trailing comment"));
+        let len = bytes.len() as u64;
+        assert_eq!(
+            central_directory_range(futures::io::Cursor::new(&bytes), len).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn central_directory_truncated() {
+        let bytes = archive(None);
+        assert_eq!(
+            central_directory_range(futures::io::Cursor::new(&bytes[..10]), 10).await,
+            None
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::Extensions;
+use reqwest::{Request, Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use reqwest_retry::{default_on_request_failure, Retryable};
+use tracing::debug;
+use url::Url;
+
+/// The policy for retrying requests that fail with a transient error.
+///
+/// Connection errors and timeouts are always considered transient; responses are considered
+/// transient if their status code is one of the [`RetryPolicy::statuses`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times to retry a request. If zero, requests are never retried.
+    pub retries: u32,
+    /// The delay before the first retry. The delay is doubled for each subsequent retry.
+    pub min_backoff: Duration,
+    /// The maximum delay between two attempts, including delays requested by the server via a
+    /// `Retry-After` header.
+    pub max_backoff: Duration,
+    /// The response status codes that should be retried.
+    pub statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            statuses: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Return the delay before the given retry (starting from zero).
+    ///
+    /// If the server requested a delay via a `Retry-After` header, it takes precedence over the
+    /// exponential backoff. Either way, the delay is capped at the [`RetryPolicy::max_backoff`].
+    fn backoff(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| self.min_backoff.saturating_mul(2u32.saturating_pow(retry)))
+            .min(self.max_backoff)
+    }
+}
+
+/// An error indicating that a request failed with a transient error, and every retry failed too.
+#[derive(Debug, thiserror::Error)]
+#[error("Request to {url} failed after {} attempts", retries + 1)]
+pub(crate) struct RetriesExhaustedError {
+    url: Url,
+    retries: u32,
+    #[source]
+    source: reqwest_middleware::Error,
+}
+
+impl RetriesExhaustedError {
+    /// Returns the URL of the failed request.
+    pub(crate) fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the number of retries that were attempted.
+    pub(crate) fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns the error of the last attempt.
+    pub(crate) fn into_source(self) -> reqwest_middleware::Error {
+        self.source
+    }
+}
+
+/// A middleware that retries requests according to a [`RetryPolicy`].
+///
+/// Unlike [`reqwest_retry::RetryTransientMiddleware`], the middleware honors the `Retry-After`
+/// header, and fails with a [`RetriesExhaustedError`] once the retries are exhausted, rather than
+/// returning the last response.
+pub(crate) struct RetryMiddleware {
+    policy: RetryPolicy,
+}
+
+impl RetryMiddleware {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut retries = 0;
+        loop {
+            // Requests with a streaming body can't be cloned, and so can't be retried.
+            let Some(attempt) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+
+            let result = next.clone().run(attempt, extensions).await;
+            let retry_after = match &result {
+                Ok(response) if self.policy.statuses.contains(&response.status()) => {
+                    retry_after(response)
+                }
+                Err(err)
+                    if matches!(default_on_request_failure(err), Some(Retryable::Transient)) =>
+                {
+                    None
+                }
+                _ => return result,
+            };
+
+            if retries >= self.policy.retries {
+                let source = match result {
+                    Ok(response) => match response.error_for_status() {
+                        Ok(response) => return Ok(response),
+                        Err(err) => reqwest_middleware::Error::Reqwest(err),
+                    },
+                    Err(err) => err,
+                };
+                return Err(reqwest_middleware::Error::Middleware(
+                    RetriesExhaustedError {
+                        url: req.url().clone(),
+                        retries,
+                        source,
+                    }
+                    .into(),
+                ));
+            }
+
+            let delay = self.policy.backoff(retries, retry_after);
+            retries += 1;
+            debug!(
+                "Transient failure for {}; retrying in {:.1}s ({retries}/{})",
+                req.url(),
+                delay.as_secs_f32(),
+                self.policy.retries
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Parse the delay requested by the `Retry-After` header of a response, if any.
+///
+/// The header holds either a number of seconds or an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response
+        .headers()
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use anyhow::Result;
+    use http_body_util::Full;
+    use hyper::body::Bytes;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper::StatusCode;
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpListener;
+    use url::Url;
+
+    use crate::ErrorKind;
+
+    use super::{RetriesExhaustedError, RetryMiddleware, RetryPolicy};
+
+    /// Serve the given responses in order, repeating the last one, returning the URL of the
+    /// server and the number of requests received.
+    async fn serve(
+        responses: &'static [(StatusCode, Option<&'static str>)],
+    ) -> Result<(Url, Arc<AtomicUsize>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let svc = service_fn(move |_| {
+                        let index = counter.fetch_add(1, Ordering::SeqCst);
+                        let (status, retry_after) = responses[index.min(responses.len() - 1)];
+                        async move {
+                            let mut builder = hyper::Response::builder().status(status);
+                            if let Some(retry_after) = retry_after {
+                                builder = builder.header("Retry-After", retry_after);
+                            }
+                            Ok::<_, hyper::Error>(builder.body(Full::new(Bytes::new())).unwrap())
+                        }
+                    });
+                    http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), svc)
+                        .await
+                        .unwrap();
+                });
+            }
+        });
+        Ok((Url::parse(&format!("http://{addr}/"))?, requests))
+    }
+
+    fn client(policy: RetryPolicy) -> reqwest_middleware::ClientWithMiddleware {
+        reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(RetryMiddleware::new(policy))
+            .build()
+    }
+
+    fn policy(retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            retries,
+            min_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = policy(10);
+        assert_eq!(policy.backoff(0, None), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2, None), Duration::from_millis(40));
+        assert_eq!(policy.backoff(9, None), Duration::from_secs(1));
+        assert_eq!(
+            policy.backoff(0, Some(Duration::from_millis(500))),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            policy.backoff(0, Some(Duration::from_secs(120))),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() -> Result<()> {
+        let (url, requests) = serve(&[
+            (StatusCode::SERVICE_UNAVAILABLE, None),
+            (StatusCode::TOO_MANY_REQUESTS, None),
+            (StatusCode::OK, None),
+        ])
+        .await?;
+
+        let response = client(policy(3)).get(url).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn honors_retry_after() -> Result<()> {
+        let (url, requests) = serve(&[
+            (StatusCode::SERVICE_UNAVAILABLE, Some("1")),
+            (StatusCode::OK, None),
+        ])
+        .await?;
+
+        let start = Instant::now();
+        let response = client(policy(3)).get(url).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn configurable_statuses() -> Result<()> {
+        // Statuses outside the policy are returned as-is...
+        let (url, requests) = serve(&[(StatusCode::SERVICE_UNAVAILABLE, None)]).await?;
+        let policy = RetryPolicy {
+            statuses: vec![StatusCode::BAD_GATEWAY],
+            ..policy(3)
+        };
+        let response = client(policy.clone()).get(url).send().await?;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // ...while those within it are retried.
+        let (url, requests) =
+            serve(&[(StatusCode::BAD_GATEWAY, None), (StatusCode::OK, None)]).await?;
+        let response = client(policy).get(url).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retries_exhausted() -> Result<()> {
+        let (url, requests) = serve(&[(StatusCode::SERVICE_UNAVAILABLE, None)]).await?;
+
+        let err = client(policy(2)).get(url.clone()).send().await.unwrap_err();
+        let reqwest_middleware::Error::Middleware(err) = err else {
+            panic!("Expected a middleware error, got: {err}");
+        };
+        let err = err.downcast::<RetriesExhaustedError>()?;
+        assert_eq!(err.url(), &url);
+        assert_eq!(err.retries(), 2);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        let reqwest_middleware::Error::Reqwest(source) = err.into_source() else {
+            panic!("Expected a status error");
+        };
+        assert_eq!(source.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+
+        // Once converted to a client error, the status of the last attempt is retained.
+        let err = crate::Error::from(ErrorKind::from(
+            client(policy(0)).get(url).send().await.unwrap_err(),
+        ));
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::RetriesExhausted { retries: 0, .. }
+        ));
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use std::num::NonZeroUsize;
+
+/// Limits on the number of concurrent network operations, and on the bandwidth they consume.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of requests (e.g., for package versions, metadata, or distributions)
+    /// to have in flight at once.
+    pub downloads: NonZeroUsize,
+    /// The maximum number of concurrent requests to any single host, if limited.
+    pub per_host: Option<NonZeroUsize>,
+    /// The maximum average download bandwidth across all requests, in bytes per second, if
+    /// limited.
+    pub bandwidth: Option<u64>,
+}
+
+impl Concurrency {
+    /// The default number of requests to have in flight at once.
+    pub const DEFAULT_DOWNLOADS: NonZeroUsize = match NonZeroUsize::new(50) {
+        Some(downloads) => downloads,
+        None => unreachable!(),
+    };
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            per_host: None,
+            bandwidth: None,
+        }
+    }
+}
+
+/// Parse a bandwidth, in bytes per second, with an optional (binary) unit suffix: `K`, `M`, or
+/// `G` (e.g., `500K` or `10M`).
+pub fn parse_bandwidth(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let (digits, multiplier) = match input.char_indices().last() {
+        Some((index, 'k' | 'K')) => (&input[..index], 1024),
+        Some((index, 'm' | 'M')) => (&input[..index], 1024 * 1024),
+        Some((index, 'g' | 'G')) => (&input[..index], 1024 * 1024 * 1024),
+        _ => (input, 1),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|value| value.checked_mul(multiplier))
+        .filter(|value| *value > 0)
+        .ok_or_else(|| {
+            format!("`{input}` is not a valid bandwidth (expected, e.g., `500K` or `10M`)")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_bandwidth;
+
+    #[test]
+    fn bandwidth() {
+        assert_eq!(parse_bandwidth("1000"), Ok(1000));
+        assert_eq!(parse_bandwidth("500K"), Ok(500 * 1024));
+        assert_eq!(parse_bandwidth("10m"), Ok(10 * 1024 * 1024));
+        assert_eq!(parse_bandwidth("2G"), Ok(2 * 1024 * 1024 * 1024));
+        assert!(parse_bandwidth("0").is_err());
+        assert!(parse_bandwidth("M").is_err());
+        assert!(parse_bandwidth("ten").is_err());
+    }
+}
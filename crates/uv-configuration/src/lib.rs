@@ -1,13 +1,19 @@
 pub use build_options::*;
+pub use concurrency::*;
 pub use config_settings::*;
 pub use constraints::*;
 pub use name_specifiers::*;
 pub use overrides::*;
 pub use package_options::*;
+pub use tag_policy::*;
+pub use target_triple::*;
 
 mod build_options;
+mod concurrency;
 mod config_settings;
 mod constraints;
 mod name_specifiers;
 mod overrides;
 mod package_options;
+mod tag_policy;
+mod target_triple;
@@ -0,0 +1,166 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use platform_tags::{Os, Platform};
+
+/// A `manylinux` glibc version, e.g., `manylinux_2_17` (or its legacy alias, `manylinux2014`).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ManylinuxVersion {
+    major: u16,
+    minor: u16,
+}
+
+impl ManylinuxVersion {
+    /// Create a new [`ManylinuxVersion`] from the given glibc version.
+    pub fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl FromStr for ManylinuxVersion {
+    type Err = String;
+
+    /// Parse a [`ManylinuxVersion`] from a string.
+    ///
+    /// Accepts PEP 600 tags (e.g., `manylinux_2_17`) and the legacy `manylinux1`,
+    /// `manylinux2010`, and `manylinux2014` aliases.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "manylinux1" => return Ok(Self::new(2, 5)),
+            "manylinux2010" => return Ok(Self::new(2, 12)),
+            "manylinux2014" => return Ok(Self::new(2, 17)),
+            _ => {}
+        }
+        input
+            .strip_prefix("manylinux_")
+            .and_then(|version| version.split_once('_'))
+            .and_then(|(major, minor)| Some(Self::new(major.parse().ok()?, minor.parse().ok()?)))
+            .ok_or_else(|| {
+                format!(
+                    "`{input}` is not a valid `manylinux` version (expected, e.g., `manylinux_2_17` or `manylinux2014`)"
+                )
+            })
+    }
+}
+
+impl Display for ManylinuxVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "manylinux_{}_{}", self.major, self.minor)
+    }
+}
+
+/// A policy that restricts the wheels considered compatible with the target platform, beyond
+/// what's inferred from the interpreter.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct TagPolicy {
+    /// The most recent `manylinux` version to accept, if capped.
+    max_manylinux: Option<ManylinuxVersion>,
+    /// Whether to reject platform-specific wheels entirely.
+    no_platform_wheels: bool,
+}
+
+impl TagPolicy {
+    /// Determine the tag policy to use for the given arguments.
+    pub fn from_args(max_manylinux: Option<ManylinuxVersion>, no_platform_wheels: bool) -> Self {
+        Self {
+            max_manylinux,
+            no_platform_wheels,
+        }
+    }
+
+    /// Returns `true` if the policy doesn't restrict the inferred tags.
+    pub fn is_none(&self) -> bool {
+        self.max_manylinux.is_none() && !self.no_platform_wheels
+    }
+
+    /// Apply the policy to a [`Platform`], capping its `manylinux` version if necessary.
+    ///
+    /// Non-`manylinux` platforms are returned unchanged.
+    pub fn platform(&self, platform: &Platform) -> Platform {
+        match (platform.os(), self.max_manylinux) {
+            (Os::Manylinux { major, minor }, Some(max))
+                if ManylinuxVersion::new(*major, *minor) > max =>
+            {
+                Platform::new(
+                    Os::Manylinux {
+                        major: max.major,
+                        minor: max.minor,
+                    },
+                    platform.arch(),
+                )
+            }
+            _ => platform.clone(),
+        }
+    }
+
+    /// Returns `true` if platform-specific wheels should be rejected.
+    ///
+    /// Unlike the `manylinux` cap, this isn't reflected in the target tags: wheels built from
+    /// source distributions are platform-specific, and must remain compatible (e.g., when looked up
+    /// in the cache). Instead, the policy is applied when selecting among the files listed by an
+    /// index.
+    pub fn no_platform_wheels(&self) -> bool {
+        self.no_platform_wheels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use platform_tags::Arch;
+
+    use super::*;
+
+    #[test]
+    fn parse_manylinux_version() {
+        assert_eq!(
+            ManylinuxVersion::from_str("manylinux_2_28"),
+            Ok(ManylinuxVersion::new(2, 28))
+        );
+        assert_eq!(
+            ManylinuxVersion::from_str("manylinux2014"),
+            Ok(ManylinuxVersion::new(2, 17))
+        );
+        assert_eq!(
+            ManylinuxVersion::from_str("manylinux1"),
+            Ok(ManylinuxVersion::new(2, 5))
+        );
+        assert!(ManylinuxVersion::from_str("manylinux_2").is_err());
+        assert!(ManylinuxVersion::from_str("musllinux_1_2").is_err());
+    }
+
+    #[test]
+    fn cap_manylinux() {
+        let policy = TagPolicy::from_args(Some(ManylinuxVersion::new(2, 17)), false);
+
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 35,
+            },
+            Arch::X86_64,
+        );
+        assert_eq!(
+            policy.platform(&platform),
+            Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            )
+        );
+
+        // Older and non-`manylinux` platforms are unaffected.
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 12,
+            },
+            Arch::X86_64,
+        );
+        assert_eq!(policy.platform(&platform), platform);
+
+        let platform = Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::X86_64);
+        assert_eq!(policy.platform(&platform), platform);
+    }
+}
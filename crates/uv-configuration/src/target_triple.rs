@@ -0,0 +1,328 @@
+use pep508_rs::MarkerEnvironment;
+use platform_tags::{Arch, Os, Platform};
+
+/// The supported target triples. Each triple consists of an architecture, vendor, and operating
+/// system.
+///
+/// See: <https://doc.rust-lang.org/nightly/rustc/platform-support.html>
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TargetTriple {
+    /// An alias for `x86_64-pc-windows-msvc`, the default target for Windows.
+    Windows,
+
+    /// An alias for `x86_64-unknown-linux-gnu`, the default target for Linux.
+    Linux,
+
+    /// An alias for `aarch64-apple-darwin`, the default target for macOS.
+    Macos,
+
+    /// An x86 Windows target.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-pc-windows-msvc"))]
+    X8664PcWindowsMsvc,
+
+    /// An x86 Linux target, equivalent to `x86_64-manylinux_2_17`.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-unknown-linux-gnu"))]
+    X8664UnknownLinuxGnu,
+
+    /// An ARM-based macOS target, as seen on Apple Silicon devices.
+    ///
+    /// By default, assumes the least-recent, non-EOL macOS version (12.0), but respects
+    /// the `MACOSX_DEPLOYMENT_TARGET` environment variable if set.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-apple-darwin"))]
+    Aarch64AppleDarwin,
+
+    /// An x86 macOS target.
+    ///
+    /// By default, assumes the least-recent, non-EOL macOS version (12.0), but respects
+    /// the `MACOSX_DEPLOYMENT_TARGET` environment variable if set.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-apple-darwin"))]
+    X8664AppleDarwin,
+
+    /// An ARM64 Linux target, equivalent to `aarch64-manylinux_2_17`.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-unknown-linux-gnu"))]
+    Aarch64UnknownLinuxGnu,
+
+    /// An `x86_64` target for the `manylinux_2_28` platform.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-manylinux_2_28"))]
+    X8664Manylinux228,
+
+    /// An ARM64 target for the `manylinux_2_28` platform.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-manylinux_2_28"))]
+    Aarch64Manylinux228,
+
+    /// An `x86_64` Linux target using musl (e.g., Alpine), equivalent to `x86_64-musllinux_1_2`.
+    #[cfg_attr(feature = "clap", value(name = "x86_64-unknown-linux-musl"))]
+    X8664UnknownLinuxMusl,
+
+    /// An ARM64 Linux target using musl (e.g., Alpine), equivalent to `aarch64-musllinux_1_2`.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-unknown-linux-musl"))]
+    Aarch64UnknownLinuxMusl,
+}
+
+impl TargetTriple {
+    /// Return the [`Platform`] for the target.
+    pub fn platform(self) -> Platform {
+        match self {
+            Self::Windows | Self::X8664PcWindowsMsvc => Platform::new(Os::Windows, Arch::X86_64),
+            Self::Linux | Self::X8664UnknownLinuxGnu => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::X86_64,
+            ),
+            Self::Macos | Self::Aarch64AppleDarwin => {
+                let (major, minor) = macos_deployment_target().unwrap_or((12, 0));
+                Platform::new(Os::Macos { major, minor }, Arch::Aarch64)
+            }
+            Self::X8664AppleDarwin => {
+                let (major, minor) = macos_deployment_target().unwrap_or((12, 0));
+                Platform::new(Os::Macos { major, minor }, Arch::X86_64)
+            }
+            Self::Aarch64UnknownLinuxGnu => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17,
+                },
+                Arch::Aarch64,
+            ),
+            Self::X8664Manylinux228 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::X86_64,
+            ),
+            Self::Aarch64Manylinux228 => Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::Aarch64,
+            ),
+            Self::X8664UnknownLinuxMusl => {
+                Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::X86_64)
+            }
+            Self::Aarch64UnknownLinuxMusl => {
+                Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::Aarch64)
+            }
+        }
+    }
+
+    /// Return the `platform_machine` value for the target.
+    fn platform_machine(self) -> &'static str {
+        match self {
+            Self::Windows | Self::X8664PcWindowsMsvc => "AMD64",
+            Self::Macos | Self::Aarch64AppleDarwin => "arm64",
+            Self::Linux
+            | Self::X8664UnknownLinuxGnu
+            | Self::X8664AppleDarwin
+            | Self::X8664Manylinux228
+            | Self::X8664UnknownLinuxMusl => "x86_64",
+            Self::Aarch64UnknownLinuxGnu
+            | Self::Aarch64Manylinux228
+            | Self::Aarch64UnknownLinuxMusl => "aarch64",
+        }
+    }
+
+    /// Return the `os_name`, `platform_system`, and `sys_platform` values for the target.
+    fn os_markers(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Self::Windows | Self::X8664PcWindowsMsvc => ("nt", "Windows", "win32"),
+            Self::Macos | Self::Aarch64AppleDarwin | Self::X8664AppleDarwin => {
+                ("posix", "Darwin", "darwin")
+            }
+            Self::Linux
+            | Self::X8664UnknownLinuxGnu
+            | Self::Aarch64UnknownLinuxGnu
+            | Self::X8664Manylinux228
+            | Self::Aarch64Manylinux228
+            | Self::X8664UnknownLinuxMusl
+            | Self::Aarch64UnknownLinuxMusl => ("posix", "Linux", "linux"),
+        }
+    }
+
+    /// Return a [`MarkerEnvironment`] compatible with the given [`TargetTriple`], based on
+    /// a base [`MarkerEnvironment`].
+    ///
+    /// The interpreter-specific markers (e.g., `python_version`) are preserved from the base
+    /// environment, while the platform-specific markers are replaced with those of the target.
+    /// Markers that can't be inferred from the target (like `platform_release` and
+    /// `platform_version`) are cleared.
+    pub fn markers(self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let (os_name, platform_system, sys_platform) = self.os_markers();
+        MarkerEnvironment {
+            os_name: os_name.to_string(),
+            platform_machine: self.platform_machine().to_string(),
+            platform_release: String::new(),
+            platform_system: platform_system.to_string(),
+            platform_version: String::new(),
+            sys_platform: sys_platform.to_string(),
+            ..base.clone()
+        }
+    }
+}
+
+/// Return the macOS deployment target as parsed from the `MACOSX_DEPLOYMENT_TARGET` environment
+/// variable, if set.
+fn macos_deployment_target() -> Option<(u16, u16)> {
+    let version = std::env::var("MACOSX_DEPLOYMENT_TARGET").ok()?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse::<u16>().ok()?;
+    let minor = parts
+        .next()
+        .map_or(Some(0), |minor| minor.parse::<u16>().ok())?;
+    Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::{MarkerEnvironment, StringVersion};
+    use platform_tags::{Arch, Os, Platform, Tags};
+
+    use super::TargetTriple;
+
+    /// A CPython 3.12 environment on an x86 Linux machine.
+    fn base() -> MarkerEnvironment {
+        let version = StringVersion::from_str("3.12.1").unwrap();
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: version.clone(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: "6.5.0".to_string(),
+            platform_system: "Linux".to_string(),
+            platform_version: "#1 SMP".to_string(),
+            python_full_version: version,
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: "linux".to_string(),
+        }
+    }
+
+    /// Return whether a CPython 3.12 wheel with the given platform tag is compatible with the
+    /// target.
+    fn is_compatible(target: TargetTriple, platform_tag: &str) -> bool {
+        let tags = Tags::from_env(&target.platform(), (3, 12), "cpython", (3, 12), false).unwrap();
+        tags.is_compatible(
+            &["cp312".to_string()],
+            &["cp312".to_string()],
+            &[platform_tag.to_string()],
+        )
+    }
+
+    #[test]
+    fn markers() {
+        let base = base();
+
+        let windows = TargetTriple::Windows.markers(&base);
+        assert_eq!(windows.os_name, "nt");
+        assert_eq!(windows.platform_machine, "AMD64");
+        assert_eq!(windows.platform_system, "Windows");
+        assert_eq!(windows.sys_platform, "win32");
+        assert_eq!(windows, TargetTriple::X8664PcWindowsMsvc.markers(&base));
+
+        let macos = TargetTriple::Macos.markers(&base);
+        assert_eq!(macos.os_name, "posix");
+        assert_eq!(macos.platform_machine, "arm64");
+        assert_eq!(macos.platform_system, "Darwin");
+        assert_eq!(macos.sys_platform, "darwin");
+        assert_eq!(
+            TargetTriple::X8664AppleDarwin
+                .markers(&base)
+                .platform_machine,
+            "x86_64"
+        );
+
+        let musl = TargetTriple::Aarch64UnknownLinuxMusl.markers(&base);
+        assert_eq!(musl.os_name, "posix");
+        assert_eq!(musl.platform_machine, "aarch64");
+        assert_eq!(musl.platform_system, "Linux");
+        assert_eq!(musl.sys_platform, "linux");
+
+        // The interpreter markers are preserved, while those that can't be inferred are cleared.
+        assert_eq!(windows.implementation_name, base.implementation_name);
+        assert_eq!(windows.python_full_version, base.python_full_version);
+        assert_eq!(windows.python_version, base.python_version);
+        assert_eq!(windows.platform_release, "");
+        assert_eq!(windows.platform_version, "");
+    }
+
+    #[test]
+    fn platform() {
+        assert_eq!(
+            TargetTriple::Linux.platform(),
+            Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 17
+                },
+                Arch::X86_64
+            )
+        );
+        assert_eq!(
+            TargetTriple::Aarch64Manylinux228.platform(),
+            Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28
+                },
+                Arch::Aarch64
+            )
+        );
+        assert_eq!(
+            TargetTriple::X8664UnknownLinuxMusl.platform(),
+            Platform::new(Os::Musllinux { major: 1, minor: 2 }, Arch::X86_64)
+        );
+        assert_eq!(
+            TargetTriple::Windows.platform(),
+            Platform::new(Os::Windows, Arch::X86_64)
+        );
+        assert_eq!(TargetTriple::Macos.platform().arch(), Arch::Aarch64);
+        assert_eq!(
+            TargetTriple::X8664AppleDarwin.platform().arch(),
+            Arch::X86_64
+        );
+    }
+
+    #[test]
+    fn tags() {
+        assert!(is_compatible(TargetTriple::Windows, "win_amd64"));
+        assert!(!is_compatible(
+            TargetTriple::Windows,
+            "manylinux_2_17_x86_64"
+        ));
+
+        assert!(is_compatible(TargetTriple::Linux, "manylinux_2_17_x86_64"));
+        assert!(is_compatible(TargetTriple::Linux, "manylinux2014_x86_64"));
+        assert!(!is_compatible(TargetTriple::Linux, "manylinux_2_28_x86_64"));
+        assert!(!is_compatible(
+            TargetTriple::Linux,
+            "manylinux_2_17_aarch64"
+        ));
+
+        assert!(is_compatible(
+            TargetTriple::X8664Manylinux228,
+            "manylinux_2_28_x86_64"
+        ));
+        assert!(is_compatible(
+            TargetTriple::Aarch64UnknownLinuxGnu,
+            "manylinux_2_17_aarch64"
+        ));
+
+        assert!(is_compatible(
+            TargetTriple::Aarch64UnknownLinuxMusl,
+            "musllinux_1_2_aarch64"
+        ));
+        assert!(!is_compatible(
+            TargetTriple::Aarch64UnknownLinuxMusl,
+            "manylinux_2_17_aarch64"
+        ));
+
+        assert!(is_compatible(TargetTriple::Macos, "macosx_11_0_arm64"));
+        assert!(!is_compatible(TargetTriple::Macos, "macosx_11_0_x86_64"));
+    }
+}
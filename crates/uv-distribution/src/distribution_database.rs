@@ -1,5 +1,5 @@
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use futures::{FutureExt, TryStreamExt};
@@ -15,7 +15,7 @@ use distribution_types::{
     LocalEditable, Name, SourceDist,
 };
 use platform_tags::Tags;
-use pypi_types::{HashDigest, Metadata23};
+use pypi_types::{HashAlgorithm, HashDigest, Metadata23};
 use uv_cache::{ArchiveId, ArchiveTimestamp, CacheBucket, CacheEntry, Timestamp, WheelCache};
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
@@ -204,7 +204,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         hashes: archive.hashes,
                         filename: wheel.filename.clone(),
                     }),
-                    Err(Error::Extract(err)) if err.is_http_streaming_unsupported() => {
+                    Err(err) if err.is_http_streaming_unsupported() => {
                         warn!(
                             "Streaming unsupported for {dist}; downloading wheel to disk ({err})"
                         );
@@ -250,7 +250,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                         hashes: archive.hashes,
                         filename: wheel.filename.clone(),
                     }),
-                    Err(Error::Client(err)) if err.is_http_streaming_unsupported() => {
+                    Err(err) if err.is_http_streaming_unsupported() => {
                         warn!(
                             "Streaming unsupported for {dist}; downloading wheel to disk ({err})"
                         );
@@ -428,8 +428,12 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     .map_err(|err| self.handle_response_errors(err))
                     .into_async_read();
 
-                // Create a hasher for each hash algorithm.
-                let algorithms = hashes.algorithms();
+                // Create a hasher for each hash algorithm. Always compute the SHA-256 digest, such
+                // that the unzipped wheel can be content-addressed in the cache.
+                let mut algorithms = hashes.algorithms();
+                if !algorithms.contains(&HashAlgorithm::Sha256) {
+                    algorithms.push(HashAlgorithm::Sha256);
+                }
                 let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
                 let mut hasher = uv_extract::hash::HashReader::new(reader.compat(), &mut hashers);
 
@@ -438,23 +442,21 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     .map_err(Error::CacheWrite)?;
                 uv_extract::stream::unzip(&mut hasher, temp_dir.path()).await?;
 
-                // If necessary, exhaust the reader to compute the hash.
-                if !hashes.is_none() {
-                    hasher.finish().await.map_err(Error::HashExhaustion)?;
-                }
+                // Exhaust the reader to compute the hash.
+                hasher.finish().await.map_err(Error::HashExhaustion)?;
+
+                let hashes = hashers
+                    .into_iter()
+                    .map(HashDigest::from)
+                    .collect::<Vec<_>>();
 
                 // Persist the temporary directory to the directory store.
                 let id = self
-                    .build_context
-                    .cache()
-                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .persist_archive(temp_dir.into_path(), wheel_entry.path(), &hashes)
                     .await
                     .map_err(Error::CacheRead)?;
 
-                Ok(Archive::new(
-                    id,
-                    hashers.into_iter().map(HashDigest::from).collect(),
-                ))
+                Ok(Archive::new(id, hashes))
             }
             .instrument(info_span!("wheel", wheel = %dist))
         };
@@ -561,9 +563,7 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                 // Persist the temporary directory to the directory store.
                 let id = self
-                    .build_context
-                    .cache()
-                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .persist_archive(temp_dir.into_path(), wheel_entry.path(), &hashes)
                     .await
                     .map_err(Error::CacheRead)?;
 
@@ -675,13 +675,14 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
             // Exhaust the reader to compute the hash.
             hasher.finish().await.map_err(Error::HashExhaustion)?;
 
-            let hashes = hashers.into_iter().map(HashDigest::from).collect();
+            let hashes = hashers
+                .into_iter()
+                .map(HashDigest::from)
+                .collect::<Vec<_>>();
 
             // Persist the temporary directory to the directory store.
             let id = self
-                .build_context
-                .cache()
-                .persist(temp_dir.into_path(), wheel_entry.path())
+                .persist_archive(temp_dir.into_path(), wheel_entry.path(), &hashes)
                 .await
                 .map_err(Error::CacheWrite)?;
 
@@ -704,6 +705,29 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
+    /// Persist an unzipped wheel to the cache, returning its [`ArchiveId`].
+    ///
+    /// If the SHA-256 digest of the wheel is known, the archive is content-addressed, such that
+    /// identical wheels (e.g., fetched from different indexes or URLs) share a single archive.
+    async fn persist_archive(
+        &self,
+        temp_dir: PathBuf,
+        target: &Path,
+        hashes: &[HashDigest],
+    ) -> io::Result<ArchiveId> {
+        let cache = self.build_context.cache();
+        if let Some(digest) = hashes
+            .iter()
+            .find(|digest| digest.algorithm == HashAlgorithm::Sha256)
+        {
+            cache
+                .persist_content_addressed(temp_dir, target, ArchiveId::from_sha256(&digest.digest))
+                .await
+        } else {
+            cache.persist(temp_dir, target).await
+        }
+    }
+
     /// Unzip a wheel into the cache, returning the path to the unzipped directory.
     async fn unzip_wheel(&self, path: &Path, target: &Path) -> Result<ArchiveId, Error> {
         let temp_dir = tokio::task::spawn_blocking({
@@ -136,6 +136,17 @@ impl From<reqwest_middleware::Error> for Error {
 }
 
 impl Error {
+    /// Returns `true` if the error is due to the server not supporting HTTP streaming, whether
+    /// it was encountered while reading the wheel's metadata (a client error) or while unzipping
+    /// the wheel as it's downloaded (an extraction error).
+    pub(crate) fn is_http_streaming_unsupported(&self) -> bool {
+        match self {
+            Self::Client(err) => err.is_http_streaming_unsupported(),
+            Self::Extract(err) => err.is_http_streaming_unsupported(),
+            _ => false,
+        }
+    }
+
     /// Construct a hash mismatch error.
     pub fn hash_mismatch(
         distribution: String,
@@ -190,3 +201,67 @@ impl Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::Error;
+
+    /// Write an archive containing a single empty file, with its sizes and checksum recorded in
+    /// a data descriptor that follows the (empty) file data, rather than in its local header.
+    fn data_descriptor_archive(name: &str) -> Vec<u8> {
+        let name_len = u16::try_from(name.len()).unwrap();
+        let mut archive = Vec::new();
+
+        // The local file header, with the data descriptor flag set, followed by the data
+        // descriptor.
+        archive.extend(0x0403_4b50u32.to_le_bytes());
+        archive.extend([20, 0, 0x08, 0, 0, 0, 0, 0, 0, 0]);
+        archive.extend([0; 12]);
+        archive.extend(name_len.to_le_bytes());
+        archive.extend([0, 0]);
+        archive.extend(name.as_bytes());
+        archive.extend(0x0807_4b50u32.to_le_bytes());
+        archive.extend([0; 12]);
+
+        // The central directory, with a single entry.
+        let offset = u32::try_from(archive.len()).unwrap();
+        archive.extend(0x0201_4b50u32.to_le_bytes());
+        archive.extend([20, 0, 20, 0, 0x08, 0, 0, 0, 0, 0, 0, 0]);
+        archive.extend([0; 12]);
+        archive.extend(name_len.to_le_bytes());
+        archive.extend([0; 16]);
+        archive.extend(name.as_bytes());
+        let size = u32::try_from(archive.len()).unwrap() - offset;
+
+        // The end of central directory record.
+        archive.extend(0x0605_4b50u32.to_le_bytes());
+        archive.extend([0, 0, 0, 0, 1, 0, 1, 0]);
+        archive.extend(size.to_le_bytes());
+        archive.extend(offset.to_le_bytes());
+        archive.extend([0, 0]);
+
+        archive
+    }
+
+    /// An archive with data descriptors can't be unzipped as it's streamed, which surfaces as an
+    /// extraction error that triggers the fallback to downloading the wheel to disk, where it
+    /// can be unzipped.
+    #[tokio::test]
+    async fn streaming_unsupported() {
+        let archive = data_descriptor_archive("foo/__init__.py");
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let err = uv_extract::stream::unzip(archive.as_slice(), temp_dir.path().join("stream"))
+            .await
+            .map_err(Error::from)
+            .unwrap_err();
+        assert!(matches!(err, Error::Extract(_)), "{err:?}");
+        assert!(err.is_http_streaming_unsupported(), "{err:?}");
+
+        let target = temp_dir.path().join("download");
+        uv_extract::unzip(Cursor::new(&archive), &target).unwrap();
+        assert!(target.join("foo").join("__init__.py").is_file());
+    }
+}
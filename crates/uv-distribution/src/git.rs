@@ -11,6 +11,7 @@ use url::Url;
 use cache_key::{CanonicalUrl, RepositoryUrl};
 use distribution_types::DirectGitUrl;
 use uv_cache::{Cache, CacheBucket};
+use uv_client::Connectivity;
 use uv_fs::LockedFile;
 use uv_git::{Fetch, GitReference, GitSha, GitSource, GitUrl};
 
@@ -50,6 +51,7 @@ impl RepositoryReference {
 pub(crate) async fn fetch_git_archive(
     url: &Url,
     cache: &Cache,
+    connectivity: Connectivity,
     reporter: Option<&Arc<dyn Reporter>>,
 ) -> Result<(Fetch, Option<PathBuf>), Error> {
     debug!("Fetching source distribution from Git: {url}");
@@ -70,10 +72,12 @@ pub(crate) async fn fetch_git_archive(
     let DirectGitUrl { url, subdirectory } = DirectGitUrl::try_from(url).map_err(Error::Git)?;
 
     // Fetch the Git repository.
+    let source = GitSource::new(url.clone(), git_dir)
+        .with_offline(matches!(connectivity, Connectivity::Offline));
     let source = if let Some(reporter) = reporter {
-        GitSource::new(url.clone(), git_dir).with_reporter(Facade::from(reporter.clone()))
+        source.with_reporter(Facade::from(reporter.clone()))
     } else {
-        GitSource::new(url.clone(), git_dir)
+        source
     };
     let fetch = tokio::task::spawn_blocking(move || source.fetch())
         .await?
@@ -93,6 +97,7 @@ pub(crate) async fn fetch_git_archive(
 pub(crate) async fn resolve_precise(
     url: &Url,
     cache: &Cache,
+    connectivity: Connectivity,
     reporter: Option<&Arc<dyn Reporter>>,
 ) -> Result<Option<Url>, Error> {
     let DirectGitUrl { url, subdirectory } = DirectGitUrl::try_from(url).map_err(Error::Git)?;
@@ -118,10 +123,12 @@ pub(crate) async fn resolve_precise(
 
     // Fetch the precise SHA of the Git reference (which could be a branch, a tag, a partial
     // commit, etc.).
+    let source = GitSource::new(url.clone(), git_dir)
+        .with_offline(matches!(connectivity, Connectivity::Offline));
     let source = if let Some(reporter) = reporter {
-        GitSource::new(url.clone(), git_dir).with_reporter(Facade::from(reporter.clone()))
+        source.with_reporter(Facade::from(reporter.clone()))
     } else {
-        GitSource::new(url.clone(), git_dir)
+        source
     };
     let fetch = tokio::task::spawn_blocking(move || source.fetch())
         .await?
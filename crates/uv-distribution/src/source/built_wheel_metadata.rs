@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use distribution_filename::WheelFilename;
 use distribution_types::Hashed;
-use platform_tags::Tags;
+use platform_tags::{TagCompatibility, Tags};
 use pypi_types::HashDigest;
 use uv_cache::CacheShard;
 use uv_fs::files;
@@ -22,17 +22,28 @@ pub(crate) struct BuiltWheelMetadata {
 }
 
 impl BuiltWheelMetadata {
-    /// Find a compatible wheel in the cache.
+    /// Find the most compatible wheel in the cache.
+    ///
+    /// The cache shard may contain wheels built for several interpreters or platforms from the
+    /// same source distribution; prefer the one with the highest tag priority.
     pub(crate) fn find_in_cache(tags: &Tags, cache_shard: &CacheShard) -> Option<Self> {
+        let mut candidate: Option<(Self, TagCompatibility)> = None;
         for directory in files(cache_shard) {
             if let Some(metadata) = Self::from_path(directory, cache_shard) {
                 // Validate that the wheel is compatible with the target platform.
-                if metadata.filename.is_compatible(tags) {
-                    return Some(metadata);
+                let compatibility = metadata.filename.compatibility(tags);
+                if !compatibility.is_compatible() {
+                    continue;
+                }
+                if candidate
+                    .as_ref()
+                    .map_or(true, |(_, existing)| compatibility > *existing)
+                {
+                    candidate = Some((metadata, compatibility));
                 }
             }
         }
-        None
+        candidate.map(|(metadata, _)| metadata)
     }
 
     /// Try to parse a distribution from a cached directory name (like `typing-extensions-4.8.0-py3-none-any.whl`).
@@ -60,3 +71,53 @@ impl Hashed for BuiltWheelMetadata {
         &self.hashes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use platform_tags::{Arch, Os, Platform, Tags};
+    use uv_cache::{Cache, CacheBucket};
+
+    use super::BuiltWheelMetadata;
+
+    /// If wheels were built from the same source distribution for several platforms, the most
+    /// specific compatible wheel should be used.
+    #[test]
+    fn find_most_compatible() -> Result<()> {
+        let cache = Cache::temp()?;
+        let shard = cache.shard(CacheBucket::BuiltWheels, "example");
+        fs_err::create_dir_all(&shard)?;
+        for filename in [
+            "example-1.0.0-py3-none-any.whl",
+            "example-1.0.0-cp312-cp312-manylinux_2_17_x86_64.whl",
+            "example-1.0.0-cp312-cp312-macosx_11_0_arm64.whl",
+            "example-1.0.0-cp311-cp311-manylinux_2_17_x86_64.whl",
+        ] {
+            fs_err::write(shard.join(filename), "")?;
+        }
+
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 28,
+            },
+            Arch::X86_64,
+        );
+        let tags = Tags::from_env(&platform, (3, 12), "cpython", (3, 12), false)?;
+        let metadata = BuiltWheelMetadata::find_in_cache(&tags, &shard).unwrap();
+        assert_eq!(
+            metadata.filename.to_string(),
+            "example-1.0.0-cp312-cp312-manylinux_2_17_x86_64.whl"
+        );
+
+        // On another interpreter, the universal wheel is the only compatible one.
+        let tags = Tags::from_env(&platform, (3, 10), "cpython", (3, 10), false)?;
+        let metadata = BuiltWheelMetadata::find_in_cache(&tags, &shard).unwrap();
+        assert_eq!(
+            metadata.filename.to_string(),
+            "example-1.0.0-py3-none-any.whl"
+        );
+
+        Ok(())
+    }
+}
@@ -998,6 +998,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         let url = if let Some(url) = resolve_precise(
             resource.url,
             self.build_context.cache(),
+            self.client.connectivity(),
             self.reporter.as_ref(),
         )
         .await?
@@ -1008,8 +1009,13 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         };
 
         // Fetch the Git repository.
-        let (fetch, subdirectory) =
-            fetch_git_archive(&url, self.build_context.cache(), self.reporter.as_ref()).await?;
+        let (fetch, subdirectory) = fetch_git_archive(
+            &url,
+            self.build_context.cache(),
+            self.client.connectivity(),
+            self.reporter.as_ref(),
+        )
+        .await?;
 
         let git_sha = fetch.git().precise().expect("Exact commit after checkout");
         let cache_shard = self.build_context.cache().shard(
@@ -1070,6 +1076,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         let url = if let Some(url) = resolve_precise(
             resource.url,
             self.build_context.cache(),
+            self.client.connectivity(),
             self.reporter.as_ref(),
         )
         .await?
@@ -1080,8 +1087,13 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         };
 
         // Fetch the Git repository.
-        let (fetch, subdirectory) =
-            fetch_git_archive(&url, self.build_context.cache(), self.reporter.as_ref()).await?;
+        let (fetch, subdirectory) = fetch_git_archive(
+            &url,
+            self.build_context.cache(),
+            self.client.connectivity(),
+            self.reporter.as_ref(),
+        )
+        .await?;
 
         let git_sha = fetch.git().precise().expect("Exact commit after checkout");
         let cache_shard = self.build_context.cache().shard(
@@ -1392,8 +1404,8 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             return Err(Error::NotFound(editable.path.clone()));
         }
 
-        // Build the wheel.
-        let disk_filename = self
+        // Setup the builder.
+        let mut builder = self
             .build_context
             .setup_build(
                 &editable.path,
@@ -1403,7 +1415,17 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 BuildKind::Editable,
             )
             .await
-            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?
+            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
+
+        // Prepare the metadata, if the backend supports it, such that the editable wheel is built
+        // from the prepared metadata (as with `pip install -e`).
+        builder
+            .metadata()
+            .await
+            .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
+
+        // Build the wheel.
+        let disk_filename = builder
             .wheel(editable_wheel_dir)
             .await
             .map_err(|err| Error::BuildEditable(editable.to_string(), err))?;
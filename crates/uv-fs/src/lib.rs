@@ -304,6 +304,39 @@ impl LockedFile {
             }
         }
     }
+
+    /// Like [`LockedFile::acquire`], but acquires a shared lock, which may be held by any number
+    /// of processes at once, but excludes the holder of an exclusive lock.
+    pub fn acquire_shared(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+    ) -> Result<Self, std::io::Error> {
+        let file = fs_err::File::create(path.as_ref())?;
+        debug!(
+            "Trying to lock if free (shared): {}",
+            path.as_ref().user_display()
+        );
+        match file.file().try_lock_shared() {
+            Ok(()) => Ok(Self(file)),
+            Err(err) => {
+                // Log error code and enum kind to help debugging more exotic failures
+                debug!("Try lock error, waiting for shared lock: {:?}", err);
+                warn_user!(
+                    "Waiting to acquire lock for {} (lockfile: {})",
+                    resource,
+                    path.user_display(),
+                );
+                file.file().lock_shared().map_err(|err| {
+                    // Not an fs_err method, we need to build our own path context
+                    std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!("Could not lock {}: {}", path.as_ref().user_display(), err),
+                    )
+                })?;
+                Ok(Self(file))
+            }
+        }
+    }
 }
 
 impl Drop for LockedFile {
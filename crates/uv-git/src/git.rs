@@ -255,6 +255,12 @@ impl GitDatabase {
     pub(crate) fn contains(&self, oid: git2::Oid) -> bool {
         self.repo.revparse_single(&oid.to_string()).is_ok()
     }
+
+    /// Resolves the [`GitReference`] to an object ID using only the objects already present in
+    /// this database (i.e., without fetching).
+    pub(crate) fn resolve(&self, reference: &GitReference) -> Result<git2::Oid> {
+        reference.resolve(&self.repo)
+    }
 }
 
 impl GitReference {
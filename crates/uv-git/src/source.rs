@@ -3,7 +3,7 @@
 //! Source: <https://github.com/rust-lang/cargo/blob/23eb492cf920ce051abfc56bbaf838514dc8365c/src/cargo/sources/git/source.rs>
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use reqwest::Client;
 use tracing::{debug, instrument};
 use url::Url;
@@ -25,6 +25,8 @@ pub struct GitSource {
     cache: PathBuf,
     /// The reporter to use for this source.
     reporter: Option<Box<dyn Reporter>>,
+    /// Whether to avoid network access, relying solely on the Git database in the cache.
+    offline: bool,
 }
 
 impl GitSource {
@@ -36,6 +38,7 @@ impl GitSource {
             strategy: FetchStrategy::Cli,
             cache: cache.into(),
             reporter: None,
+            offline: false,
         }
     }
 
@@ -48,6 +51,15 @@ impl GitSource {
         }
     }
 
+    /// Set whether to avoid network access for this `Git` source.
+    ///
+    /// When offline, references are resolved against the cached Git database, and an error is
+    /// returned if the requested revision isn't available locally.
+    #[must_use]
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+
     /// Fetch the underlying Git repository at the given revision.
     #[instrument(skip(self), fields(repository = %self.git.repository, rev = ?self.git.precise))]
     pub fn fetch(self) -> Result<Fetch> {
@@ -61,10 +73,30 @@ impl GitSource {
             // which has that revision, then no update needs to happen.
             (Some(rev), Some(db)) if db.contains(rev.into()) => (db, rev, None),
 
-            // ... otherwise we use this state to update the git database. Note
-            // that we still check for being offline here, for example in the
-            // situation that we have a locked revision but the database
-            // doesn't have it.
+            // If we're offline and have a preexisting database, resolve the
+            // reference against whatever the database already contains.
+            (None, Some(db)) if self.offline => {
+                let rev = db.resolve(&self.git.reference).map_err(|err| {
+                    anyhow!(
+                        "Unable to resolve `{}` from the cached Git repository `{}` while offline: {err}",
+                        self.git.reference.as_rev(),
+                        self.git.repository,
+                    )
+                })?;
+                (db, GitSha::from(rev), None)
+            }
+
+            // Otherwise, we'd need to update the database, which isn't
+            // possible while offline (e.g., we have a locked revision but the
+            // database doesn't have it).
+            _ if self.offline => {
+                return Err(anyhow!(
+                    "Unable to fetch Git repository `{}` while offline; the requested revision is not in the cache",
+                    self.git.repository,
+                ));
+            }
+
+            // ... otherwise we use this state to update the git database.
             (locked_rev, db) => {
                 debug!("Updating git source `{:?}`", self.git.repository);
 
@@ -151,3 +183,124 @@ pub trait Reporter: Send + Sync {
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::Result;
+    use url::Url;
+
+    use crate::{GitSha, GitUrl};
+
+    use super::GitSource;
+
+    /// Commit a file to the `main` branch of the repository at `path`, creating the repository if
+    /// necessary.
+    fn commit(path: &Path, contents: &str) -> Result<git2::Oid> {
+        let repo = git2::Repository::init(path)?;
+        fs_err::write(path.join("README.md"), contents)?;
+        let mut index = repo.index()?;
+        index.add_path(Path::new("README.md"))?;
+        index.write()?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let signature = git2::Signature::now("uv", "uv@example.com")?;
+        let parent = repo
+            .find_branch("main", git2::BranchType::Local)
+            .ok()
+            .and_then(|branch| branch.get().peel_to_commit().ok());
+        let oid = repo.commit(
+            None,
+            &signature,
+            &signature,
+            contents,
+            &tree,
+            parent.as_ref().into_iter().collect::<Vec<_>>().as_slice(),
+        )?;
+        repo.branch("main", &repo.find_commit(oid)?, true)?;
+        repo.set_head("refs/heads/main")?;
+        Ok(oid)
+    }
+
+    fn git_url(repository: &Path, reference: &str) -> Result<GitUrl> {
+        let url = Url::from_file_path(repository).unwrap();
+        GitUrl::try_from(Url::parse(&format!("{url}@{reference}"))?)
+    }
+
+    /// While offline, a reference should be resolved against the cached Git database, even if the
+    /// remote has moved on.
+    #[test]
+    fn offline_cached() -> Result<()> {
+        let remote = tempfile::tempdir()?;
+        let cache = tempfile::tempdir()?;
+        let first = commit(remote.path(), "first")?;
+
+        // Populate the cache.
+        let fetch = GitSource::new(git_url(remote.path(), "main")?, cache.path()).fetch()?;
+        assert_eq!(fetch.git().precise(), Some(GitSha::from(first)));
+
+        // Move the branch in the remote; the cached revision should still be used.
+        commit(remote.path(), "second")?;
+        let fetch = GitSource::new(git_url(remote.path(), "main")?, cache.path())
+            .with_offline(true)
+            .fetch()?;
+        assert_eq!(fetch.git().precise(), Some(GitSha::from(first)));
+        assert_eq!(
+            fs_err::read_to_string(fetch.path().join("README.md"))?,
+            "first"
+        );
+
+        // A locked revision that's in the cache can be checked out, too.
+        let fetch = GitSource::new(git_url(remote.path(), &first.to_string())?, cache.path())
+            .with_offline(true)
+            .fetch()?;
+        assert_eq!(fetch.git().precise(), Some(GitSha::from(first)));
+
+        Ok(())
+    }
+
+    /// While offline, a repository or revision that isn't in the cache should be an error.
+    #[test]
+    fn offline_uncached() -> Result<()> {
+        let remote = tempfile::tempdir()?;
+        let cache = tempfile::tempdir()?;
+        commit(remote.path(), "first")?;
+
+        // The repository isn't in the cache.
+        let err = GitSource::new(git_url(remote.path(), "main")?, cache.path())
+            .with_offline(true)
+            .fetch()
+            .err()
+            .expect("the repository isn't in the cache");
+        assert!(
+            err.to_string().contains("while offline"),
+            "unexpected error: {err}"
+        );
+
+        // The repository is in the cache, but the locked revision isn't.
+        GitSource::new(git_url(remote.path(), "main")?, cache.path()).fetch()?;
+        let second = commit(remote.path(), "second")?;
+        let err = GitSource::new(git_url(remote.path(), &second.to_string())?, cache.path())
+            .with_offline(true)
+            .fetch()
+            .err()
+            .expect("the revision isn't in the cache");
+        assert!(
+            err.to_string().contains("while offline"),
+            "unexpected error: {err}"
+        );
+
+        // A reference that doesn't exist in the cached database can't be resolved.
+        let err = GitSource::new(git_url(remote.path(), "missing")?, cache.path())
+            .with_offline(true)
+            .fetch()
+            .err()
+            .expect("the reference isn't in the cache");
+        assert!(
+            err.to_string().contains("while offline"),
+            "unexpected error: {err}"
+        );
+
+        Ok(())
+    }
+}
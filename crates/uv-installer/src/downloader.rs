@@ -1,4 +1,5 @@
 use std::cmp::Reverse;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -43,6 +44,8 @@ pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     hashes: &'a HashStrategy,
     database: DistributionDatabase<'a, Context>,
     reporter: Option<Arc<dyn Reporter>>,
+    /// The maximum number of distributions to fetch at once.
+    concurrency: NonZeroUsize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -59,6 +62,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             hashes,
             database: DistributionDatabase::new(client, build_context),
             reporter: None,
+            concurrency: client.concurrency().downloads,
         }
     }
 
@@ -72,6 +76,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             hashes: self.hashes,
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             reporter: Some(reporter.clone()),
+            concurrency: self.concurrency,
         }
     }
 
@@ -91,7 +96,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             })
             // TODO(charlie): The number of concurrent fetches, such that we limit the number of
             // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            .buffer_unordered(self.concurrency.get())
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
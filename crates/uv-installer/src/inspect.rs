@@ -0,0 +1,260 @@
+//! Enumerate the distributions installed in an environment, without invoking its interpreter.
+
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use fs_err as fs;
+use url::Url;
+
+use distribution_types::InstalledDist;
+use install_wheel_rs::Layout;
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+use crate::site_packages::is_egg_link;
+
+/// A distribution installed in an environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPackage {
+    /// The name of the distribution.
+    pub name: PackageName,
+    /// The installed version of the distribution.
+    pub version: Version,
+    /// The path to the distribution's `.dist-info` directory (or, for legacy editables, its
+    /// `.egg-link` file).
+    pub location: PathBuf,
+    /// Whether the distribution was installed in editable mode.
+    pub editable: bool,
+    /// The URL from which the distribution was installed, as recorded in its `direct_url.json`,
+    /// if it wasn't installed from a registry.
+    pub url: Option<Url>,
+}
+
+impl From<InstalledDist> for InstalledPackage {
+    fn from(dist: InstalledDist) -> Self {
+        match dist {
+            InstalledDist::Registry(dist) => Self {
+                name: dist.name,
+                version: dist.version,
+                location: dist.path,
+                editable: false,
+                url: None,
+            },
+            InstalledDist::Url(dist) => Self {
+                name: dist.name,
+                version: dist.version,
+                location: dist.path,
+                editable: dist.editable,
+                url: Some(dist.url),
+            },
+            InstalledDist::LegacyEditable(dist) => Self {
+                name: dist.name,
+                version: dist.version,
+                location: dist.egg_link,
+                editable: true,
+                url: Some(dist.target_url),
+            },
+        }
+    }
+}
+
+/// Format the distribution as a `pip freeze`-style requirement: `-e <url>` for editable installs,
+/// `<name> @ <url>` for direct URL installs, and `<name>==<version>` otherwise.
+impl Display for InstalledPackage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.url {
+            Some(url) if self.editable => write!(f, "-e {url}"),
+            Some(url) => write!(f, "{} @ {url}", self.name),
+            None => write!(f, "{}=={}", self.name, self.version),
+        }
+    }
+}
+
+/// Enumerate the distributions installed in the `purelib` and `platlib` directories of the given
+/// [`Layout`], sorted by name and version.
+pub fn installed_packages(layout: &Layout) -> Result<Vec<InstalledPackage>> {
+    let purelib = layout.scheme.purelib.as_path();
+    let platlib = layout.scheme.platlib.as_path();
+
+    let mut packages = Vec::new();
+    read_site_packages(purelib, &mut packages)?;
+    if !is_same_dir(purelib, platlib) {
+        read_site_packages(platlib, &mut packages)?;
+    }
+
+    packages.sort_unstable_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+    Ok(packages)
+}
+
+/// Return the `pip freeze`-style requirement for every distribution installed in the given
+/// [`Layout`], optionally omitting editable installs.
+pub fn freeze_requirements(layout: &Layout, exclude_editable: bool) -> Result<Vec<String>> {
+    Ok(installed_packages(layout)?
+        .into_iter()
+        .filter(|package| !(exclude_editable && package.editable))
+        .map(|package| package.to_string())
+        .collect())
+}
+
+/// Read the distributions from a `site-packages` directory, which may not exist.
+fn read_site_packages(site_packages: &Path, packages: &mut Vec<InstalledPackage>) -> Result<()> {
+    let entries = match fs::read_dir(site_packages) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err).context("Failed to read site-packages directory"),
+    };
+
+    // Collect sorted directory paths (along with any `.egg-link` files, for legacy editables);
+    // `read_dir` is not stable across platforms.
+    let directories = entries
+        .filter_map(|entry| match entry {
+            Ok(entry) => match entry.file_type() {
+                Ok(file_type) => {
+                    (file_type.is_dir() || is_egg_link(&entry.path())).then_some(Ok(entry.path()))
+                }
+                Err(err) => Some(Err(err)),
+            },
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<Result<BTreeSet<_>, std::io::Error>>()?;
+
+    for path in directories {
+        if let Some(dist) = InstalledDist::try_from_path(&path)
+            .with_context(|| format!("Failed to read metadata from: {}", path.display()))?
+        {
+            packages.push(InstalledPackage::from(dist));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if both paths refer to the same directory.
+fn is_same_dir(a: &Path, b: &Path) -> bool {
+    a == b
+        || matches!(
+            (fs::canonicalize(a), fs::canonicalize(b)),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use anyhow::Result;
+    use fs_err as fs;
+
+    use install_wheel_rs::{Layout, Shebang};
+    use pypi_types::Scheme;
+
+    use super::{freeze_requirements, installed_packages};
+
+    fn layout(purelib: &Path, platlib: &Path) -> Layout {
+        Layout {
+            sys_executable: PathBuf::from("python"),
+            python_version: (3, 12),
+            os_name: "posix".to_string(),
+            scheme: Scheme {
+                purelib: purelib.to_path_buf(),
+                platlib: platlib.to_path_buf(),
+                scripts: PathBuf::from("bin"),
+                data: PathBuf::new(),
+                include: PathBuf::from("include"),
+            },
+            shebang: Shebang::default(),
+        }
+    }
+
+    /// Create a `.dist-info` directory, with the given `direct_url.json` contents, if any.
+    fn dist_info(site_packages: &Path, name: &str, direct_url: Option<&str>) -> Result<()> {
+        let path = site_packages.join(format!("{name}.dist-info"));
+        fs::create_dir_all(&path)?;
+        if let Some(direct_url) = direct_url {
+            fs::write(path.join("direct_url.json"), direct_url)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn freeze() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let purelib = temp_dir.path().join("purelib");
+        let platlib = temp_dir.path().join("platlib");
+
+        // A registry install, a direct URL install, and an editable install.
+        dist_info(&purelib, "requests-2.31.0", None)?;
+        dist_info(
+            &purelib,
+            "iniconfig-2.0.0",
+            Some(
+                r#"{"url": "https://example.com/iniconfig-2.0.0-py3-none-any.whl", "archive_info": {}}"#,
+            ),
+        )?;
+        dist_info(
+            &platlib,
+            "editable-0.1.0",
+            Some(r#"{"url": "file:///projects/editable", "dir_info": {"editable": true}}"#),
+        )?;
+
+        // Directories that aren't distributions are ignored.
+        fs::create_dir_all(purelib.join("requests"))?;
+
+        let packages = installed_packages(&layout(&purelib, &platlib))?;
+        assert_eq!(
+            packages
+                .iter()
+                .map(|package| package.name.to_string())
+                .collect::<Vec<_>>(),
+            vec!["editable", "iniconfig", "requests"]
+        );
+        assert!(packages[0].editable);
+        assert_eq!(
+            packages[0].location,
+            platlib.join("editable-0.1.0.dist-info")
+        );
+
+        assert_eq!(
+            freeze_requirements(&layout(&purelib, &platlib), false)?,
+            vec![
+                "-e file:///projects/editable",
+                "iniconfig @ https://example.com/iniconfig-2.0.0-py3-none-any.whl",
+                "requests==2.31.0",
+            ]
+        );
+        assert_eq!(
+            freeze_requirements(&layout(&purelib, &platlib), true)?,
+            vec![
+                "iniconfig @ https://example.com/iniconfig-2.0.0-py3-none-any.whl",
+                "requests==2.31.0",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn same_purelib_and_platlib() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        dist_info(&site_packages, "requests-2.31.0", None)?;
+
+        // Distributions aren't listed twice if `purelib` and `platlib` are the same directory.
+        assert_eq!(
+            freeze_requirements(&layout(&site_packages, &site_packages), false)?,
+            vec!["requests==2.31.0"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_site_packages() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let site_packages = temp_dir.path().join("site-packages");
+        assert!(installed_packages(&layout(&site_packages, &site_packages))?.is_empty());
+        Ok(())
+    }
+}
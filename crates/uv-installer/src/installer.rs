@@ -1,15 +1,32 @@
-use anyhow::{Context, Error, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{anyhow, Context, Error, Result};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use tracing::instrument;
+use rustc_hash::FxHashSet;
+use tracing::{debug, instrument, warn};
 
-use distribution_types::CachedDist;
+use distribution_types::{CachedDist, Name};
+use install_wheel_rs::linker::InstallSummary;
+use install_wheel_rs::{FileClaims, Layout, Shebang};
+use pypi_types::DirectUrl;
 use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user;
+
+use crate::{SitePackages, Snapshot};
 
 pub struct Installer<'a> {
     venv: &'a PythonEnvironment,
     link_mode: install_wheel_rs::linker::LinkMode,
     reporter: Option<Box<dyn Reporter>>,
     installer_name: Option<String>,
+    requested: Option<FxHashSet<PackageName>>,
+    shebang: Shebang,
+    site_packages: Option<&'a SitePackages<'a>>,
+    strict: bool,
+    snapshot: Option<Snapshot>,
 }
 
 impl<'a> Installer<'a> {
@@ -20,6 +37,11 @@ impl<'a> Installer<'a> {
             link_mode: install_wheel_rs::linker::LinkMode::default(),
             reporter: None,
             installer_name: Some("uv".to_string()),
+            requested: None,
+            shebang: Shebang::default(),
+            site_packages: None,
+            strict: false,
+            snapshot: None,
         }
     }
 
@@ -47,41 +69,375 @@ impl<'a> Installer<'a> {
         }
     }
 
+    /// Set the packages that were requested directly, as opposed to being installed as
+    /// dependencies.
+    ///
+    /// Only the requested packages are marked with a `REQUESTED` file. If unset, every installed
+    /// package is treated as requested.
+    #[must_use]
+    pub fn with_requested(self, requested: FxHashSet<PackageName>) -> Self {
+        Self {
+            requested: Some(requested),
+            ..self
+        }
+    }
+
+    /// Set the [`Shebang`] strategy used by the scripts generated during installation.
+    #[must_use]
+    pub fn with_shebang(self, shebang: Shebang) -> Self {
+        Self { shebang, ..self }
+    }
+
+    /// Refuse to install wheels that would overwrite a file belonging to one of the given
+    /// installed distributions, or to another wheel in the same set (unless the files are
+    /// identical).
+    ///
+    /// Installed distributions that share a name with one of the wheels, or that have since been
+    /// uninstalled, are about to be replaced, and so are ignored.
+    #[must_use]
+    pub fn with_conflict_check(self, site_packages: Option<&'a SitePackages<'a>>) -> Self {
+        Self {
+            site_packages,
+            ..self
+        }
+    }
+
+    /// Fail installation if a wheel has any non-fatal anomalies (e.g., `RECORD` entries without
+    /// hashes), rather than warning about them.
+    ///
+    /// See: [`InstallWarning`](install_wheel_rs::InstallWarning)
+    #[must_use]
+    pub fn with_strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Set the [`Snapshot`] of the distributions that were removed to make way for the wheels
+    /// (e.g., reinstalled or extraneous distributions), to restore if installation fails.
+    #[must_use]
+    pub fn with_snapshot(self, snapshot: Option<Snapshot>) -> Self {
+        Self { snapshot, ..self }
+    }
+
     /// Install a set of wheels into a Python virtual environment.
+    ///
+    /// Installation stops at the first wheel that fails to install. The wheels that were
+    /// installed as part of this operation are then uninstalled again, and the distributions in
+    /// the [`Snapshot`] (if any) are restored, such that the environment isn't left with a
+    /// partially applied plan. The [`Snapshot`] is also restored if the wheels conflict with the
+    /// environment, before any of them is installed.
     #[instrument(skip_all, fields(num_wheels = %wheels.len()))]
     pub fn install(self, wheels: &[CachedDist]) -> Result<()> {
-        let layout = self.venv.interpreter().layout();
-        tokio::task::block_in_place(|| {
-            wheels.par_iter().try_for_each(|wheel| {
-                install_wheel_rs::linker::install_wheel(
-                    &layout,
-                    wheel.path(),
-                    wheel.filename(),
-                    wheel
-                        .direct_url()?
-                        .as_ref()
-                        .map(pypi_types::DirectUrl::try_from)
-                        .transpose()?
-                        .as_ref(),
-                    self.installer_name.as_deref(),
-                    self.link_mode,
-                )
-                .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
-
-                if let Some(reporter) = self.reporter.as_ref() {
-                    reporter.on_install_progress(wheel);
+        if let Some(site_packages) = self.site_packages {
+            if let Err(err) = check_conflicts(site_packages, wheels) {
+                if let Err(rollback) = Self::rollback(&[], self.snapshot) {
+                    warn!("Failed to roll back installation: {rollback}");
                 }
+                return Err(err);
+            }
+        }
+
+        let layout = Layout {
+            shebang: self.shebang.clone(),
+            ..self.venv.interpreter().layout()
+        };
+        let failed = AtomicBool::new(false);
+        let results = tokio::task::block_in_place(|| {
+            wheels
+                .par_iter()
+                .map(|wheel| {
+                    // Don't start any further installations once one has failed.
+                    if failed.load(Ordering::Relaxed) {
+                        return Ok(None);
+                    }
+
+                    if let Some(reporter) = self.reporter.as_ref() {
+                        reporter.on_install_start(wheel);
+                    }
+
+                    let result = install_wheel_rs::linker::install_wheel(
+                        &layout,
+                        wheel.path(),
+                        wheel.filename(),
+                        direct_url(wheel)?.as_ref(),
+                        self.requested
+                            .as_ref()
+                            .map_or(true, |requested| requested.contains(wheel.name())),
+                        self.installer_name.as_deref(),
+                        self.link_mode,
+                    )
+                    .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()));
+                    let summary = result.inspect_err(|_| failed.store(true, Ordering::Relaxed))?;
+
+                    debug!(
+                        "Installed {wheel}: linked {} file(s), wrote {} script(s)",
+                        summary.files_linked, summary.scripts_written
+                    );
+                    if self.strict && !summary.warnings.is_empty() {
+                        failed.store(true, Ordering::Relaxed);
+                        install_wheel_rs::uninstall_wheel(&summary.dist_info).with_context(
+                            || format!("Failed to roll back installation of: {wheel}"),
+                        )?;
+                        let warnings = summary
+                            .warnings
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        return Err(anyhow!(warnings)).with_context(|| {
+                            format!("Failed to install: {} ({wheel})", wheel.filename())
+                        });
+                    }
+                    for warning in &summary.warnings {
+                        warn_user!("{wheel}: {warning}");
+                    }
+                    if let Some(reporter) = self.reporter.as_ref() {
+                        reporter.on_install_progress(wheel, &summary);
+                    }
 
-                Ok::<(), Error>(())
-            })
-        })
+                    Ok::<Option<PathBuf>, Error>(Some(summary.dist_info))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let (installed, errors): (Vec<_>, Vec<_>) = results.into_iter().partition(Result::is_ok);
+        let Some(err) = errors.into_iter().find_map(Result::err) else {
+            return Ok(());
+        };
+
+        // Roll back any wheels that were installed successfully, and restore the distributions
+        // that they replaced.
+        let installed = installed
+            .into_iter()
+            .filter_map(|result| result.ok().flatten())
+            .collect::<Vec<_>>();
+        if let Err(rollback) = Self::rollback(&installed, self.snapshot) {
+            warn!("Failed to roll back installation: {rollback}");
+        }
+
+        Err(err)
+    }
+
+    /// Uninstall the wheels with the given `.dist-info` directories, which were installed by a
+    /// failed [`Installer::install`], then restore the [`Snapshot`] of the distributions that
+    /// they replaced.
+    fn rollback(installed: &[PathBuf], snapshot: Option<Snapshot>) -> Result<()> {
+        for dist_info in installed {
+            debug!("Rolling back installation of: {}", dist_info.display());
+            install_wheel_rs::uninstall_wheel(dist_info)?;
+        }
+        if let Some(snapshot) = snapshot {
+            snapshot.restore()?;
+        }
+        Ok(())
     }
 }
 
+/// Verify that none of the wheels would overwrite a file that belongs to another distribution,
+/// whether already installed or in the set of wheels being installed.
+fn check_conflicts(site_packages: &SitePackages, wheels: &[CachedDist]) -> Result<()> {
+    let names = wheels.iter().map(Name::name).collect::<FxHashSet<_>>();
+
+    let mut claims = FileClaims::default();
+    for dist in site_packages.iter() {
+        // Distributions with the same name are about to be replaced, and those that no longer
+        // exist were uninstalled (e.g., as extraneous).
+        if names.contains(dist.name()) || !dist.path().exists() {
+            continue;
+        }
+        if let Err(err) = claims.add_installed(dist.name(), dist.path()) {
+            debug!("Failed to read installed files for {dist}: {err}");
+        }
+    }
+
+    for wheel in wheels {
+        claims
+            .add_wheel(wheel.name(), wheel.path())
+            .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
+    }
+
+    Ok(())
+}
+
+/// Return the PEP 610 `direct_url.json` metadata for a wheel, if it was installed from a URL.
+///
+/// For archives, the hashes that were computed when the archive was downloaded are recorded in
+/// the `archive_info`.
+fn direct_url(wheel: &CachedDist) -> Result<Option<DirectUrl>> {
+    let Some(direct_url) = wheel.direct_url()? else {
+        return Ok(None);
+    };
+    let mut direct_url = DirectUrl::try_from(&direct_url)?;
+    if let (DirectUrl::ArchiveUrl { archive_info, .. }, CachedDist::Url(dist)) =
+        (&mut direct_url, wheel)
+    {
+        let hashes = &dist.hashes;
+        if !hashes.is_empty() {
+            // Prefer SHA-256 for the legacy `hash` field, as recommended by PEP 610.
+            let preferred = hashes
+                .iter()
+                .find(|digest| digest.algorithm == pypi_types::HashAlgorithm::Sha256)
+                .unwrap_or(&hashes[0]);
+            archive_info.hash = Some(format!("{}={}", preferred.algorithm, preferred.digest));
+            archive_info.hashes = Some(
+                hashes
+                    .iter()
+                    .map(|digest| (digest.algorithm.to_string(), digest.digest.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            );
+        }
+    }
+    Ok(Some(direct_url))
+}
+
 pub trait Reporter: Send + Sync {
-    /// Callback to invoke when a dependency is resolved.
-    fn on_install_progress(&self, wheel: &CachedDist);
+    /// Callback to invoke when a wheel starts installing.
+    fn on_install_start(&self, wheel: &CachedDist);
 
-    /// Callback to invoke when the resolution is complete.
+    /// Callback to invoke when a wheel is installed, with a summary of the files linked and
+    /// scripts written.
+    fn on_install_progress(&self, wheel: &CachedDist, summary: &InstallSummary);
+
+    /// Callback to invoke when all wheels are installed.
     fn on_install_complete(&self);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::Result;
+    use fs_err as fs;
+
+    use distribution_filename::WheelFilename;
+    use distribution_types::{CachedDist, CachedRegistryDist};
+    use install_wheel_rs::linker::{InstallSummary, LinkMode};
+    use pep508_rs::{MarkerEnvironment, StringVersion};
+    use platform_tags::{Arch, Os, Platform};
+    use uv_interpreter::{Interpreter, PythonEnvironment, Target};
+
+    use super::{Installer, Reporter};
+
+    /// A [`Reporter`] that records the events it receives.
+    #[derive(Default, Clone)]
+    struct RecordingReporter(Arc<Mutex<Vec<String>>>);
+
+    impl Reporter for RecordingReporter {
+        fn on_install_start(&self, wheel: &CachedDist) {
+            self.0.lock().unwrap().push(format!("start {wheel}"));
+        }
+
+        fn on_install_progress(&self, wheel: &CachedDist, summary: &InstallSummary) {
+            self.0.lock().unwrap().push(format!(
+                "progress {wheel} ({} files, {} scripts)",
+                summary.files_linked, summary.scripts_written
+            ));
+        }
+
+        fn on_install_complete(&self) {
+            self.0.lock().unwrap().push("complete".to_string());
+        }
+    }
+
+    /// Write an unpacked wheel with a single module and the given console scripts.
+    fn wheel(root: &Path, name: &str, scripts: &[&str]) -> Result<CachedDist> {
+        let path = root.join(format!("{name}-1.0.0"));
+        let entry_points = scripts
+            .iter()
+            .map(|script| format!("{script} = {name}:main\n"))
+            .collect::<String>();
+        for (file, contents) in [
+            (format!("{name}/__init__.py"), String::new()),
+            (
+                format!("{name}-1.0.0.dist-info/METADATA"),
+                format!("Metadata-Version: 2.1\nName: {name}\nVersion: 1.0.0\n"),
+            ),
+            (
+                format!("{name}-1.0.0.dist-info/WHEEL"),
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n".to_string(),
+            ),
+            (
+                format!("{name}-1.0.0.dist-info/entry_points.txt"),
+                format!("[console_scripts]\n{entry_points}"),
+            ),
+            (
+                format!("{name}-1.0.0.dist-info/RECORD"),
+                format!(
+                    "{name}/__init__.py,,\n{name}-1.0.0.dist-info/METADATA,,\n\
+                     {name}-1.0.0.dist-info/WHEEL,,\n{name}-1.0.0.dist-info/entry_points.txt,,\n\
+                     {name}-1.0.0.dist-info/RECORD,,\n"
+                ),
+            ),
+        ] {
+            let file = path.join(file);
+            fs::create_dir_all(file.parent().unwrap())?;
+            fs::write(file, contents)?;
+        }
+        Ok(CachedDist::Registry(CachedRegistryDist {
+            filename: WheelFilename::from_str(&format!("{name}-1.0.0-py3-none-any.whl"))?,
+            path,
+            hashes: vec![],
+        }))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reporter() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let markers = MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: StringVersion::from_str("3.12.1")?,
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: "6.5.0".to_string(),
+            platform_system: "Linux".to_string(),
+            platform_version: "Linux".to_string(),
+            python_full_version: StringVersion::from_str("3.12.1")?,
+            python_version: StringVersion::from_str("3.12")?,
+            sys_platform: "linux".to_string(),
+        };
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 17,
+            },
+            Arch::X86_64,
+        );
+        let venv = PythonEnvironment::from_interpreter(Interpreter::artificial(platform, markers))
+            .with_target(Target::from(temp_dir.path().join("target")));
+
+        let wheels = [
+            wheel(&temp_dir.path().join("wheels"), "foo", &["foo"])?,
+            wheel(&temp_dir.path().join("wheels"), "bar", &["bar", "bar-cli"])?,
+        ];
+
+        let reporter = RecordingReporter::default();
+        Installer::new(&venv)
+            .with_link_mode(LinkMode::Copy)
+            .with_reporter(reporter.clone())
+            .install(&wheels)?;
+
+        // Each wheel is reported as started before it's reported as installed, along with the
+        // files linked and scripts written.
+        let events = reporter.0.lock().unwrap().clone();
+        assert_eq!(events.len(), 4, "{events:?}");
+        for (wheel, summary) in [
+            ("foo==1.0.0", "5 files, 1 scripts"),
+            ("bar==1.0.0", "5 files, 2 scripts"),
+        ] {
+            let start = events
+                .iter()
+                .position(|event| *event == format!("start {wheel}"));
+            let progress = events
+                .iter()
+                .position(|event| *event == format!("progress {wheel} ({summary})"));
+            assert!(
+                matches!((start, progress), (Some(start), Some(progress)) if start < progress),
+                "{events:?}"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,15 +1,19 @@
 pub use compile::{compile_tree, CompileError};
 pub use downloader::{Downloader, Reporter as DownloadReporter};
 pub use editable::{is_dynamic, BuiltEditable, ResolvedEditable};
+pub use inspect::{freeze_requirements, installed_packages, InstalledPackage};
 pub use installer::{Installer, Reporter as InstallReporter};
 pub use plan::{Plan, Planner};
 pub use site_packages::{Diagnostic, SitePackages};
-pub use uninstall::{uninstall, UninstallError};
+pub use snapshot::Snapshot;
+pub use uninstall::{repair_uninstall, uninstall, UninstallError};
 
 mod compile;
 mod downloader;
 mod editable;
+mod inspect;
 mod installer;
 mod plan;
 mod site_packages;
+mod snapshot;
 mod uninstall;
@@ -9,6 +9,7 @@ which contains some vendored Python 2 code which fails to compile.
 """
 
 import compileall
+import importlib.util
 import os
 import py_compile
 import sys
@@ -42,6 +43,13 @@ with warnings.catch_warnings():
         except AttributeError:
             invalidation_mode = None  # guard against implementation details
 
+    # If `SOURCE_DATE_EPOCH` is set, the bytecode is hash-based by default (see above); we also
+    # set the mtimes of the compiled files to match, such that installs are reproducible.
+    try:
+        source_date_epoch = int(os.environ["SOURCE_DATE_EPOCH"])
+    except (KeyError, ValueError):
+        source_date_epoch = None
+
     # Unlike pip, we will usually set force=False. It's unclear why pip sets force=True, but it
     # doesn't matter much for them, as pip only compiles newly installed files.
     force = False
@@ -63,5 +71,13 @@ with warnings.catch_warnings():
         success = compileall.compile_file(
             path, invalidation_mode=invalidation_mode, force=force, quiet=2
         )
+        if success and source_date_epoch is not None:
+            try:
+                pyc = importlib.util.cache_from_source(path)
+                # Update the `__pycache__` directory, and its parent, after the file itself.
+                for target in (pyc, os.path.dirname(pyc), os.path.dirname(path)):
+                    os.utime(target, (source_date_epoch, source_date_epoch))
+            except (NotImplementedError, OSError):
+                pass
         # We're ready for the next file.
         print(path)
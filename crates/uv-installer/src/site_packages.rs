@@ -1,5 +1,5 @@
 use std::iter::Flatten;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{collections::BTreeSet, hash::BuildHasherDefault};
 
 use anyhow::{Context, Result};
@@ -13,7 +13,7 @@ use pep508_rs::{Requirement, RequirementsTxtRequirement, VerbatimUrl};
 use requirements_txt::{EditableRequirement, RequirementEntry};
 use uv_cache::{ArchiveTarget, ArchiveTimestamp};
 use uv_interpreter::PythonEnvironment;
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 use uv_types::InstalledPackagesProvider;
 
 use crate::is_dynamic;
@@ -21,7 +21,7 @@ use crate::is_dynamic;
 /// An index over the packages installed in an environment.
 ///
 /// Packages are indexed by both name and (for editable installs) URL.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SitePackages<'a> {
     venv: &'a PythonEnvironment,
     /// The vector of all installed distributions. The `by_name` and `by_url` indices index into
@@ -47,11 +47,13 @@ impl<'a> SitePackages<'a> {
             // Read the site-packages directory.
             let site_packages = match fs::read_dir(site_packages) {
                 Ok(site_packages) => {
-                    // Collect sorted directory paths; `read_dir` is not stable across platforms
+                    // Collect sorted directory paths (along with any `.egg-link` files, for legacy
+                    // editables); `read_dir` is not stable across platforms
                     let directories: BTreeSet<_> = site_packages
                         .filter_map(|read_dir| match read_dir {
                             Ok(entry) => match entry.file_type() {
-                                Ok(file_type) => file_type.is_dir().then_some(Ok(entry.path())),
+                                Ok(file_type) => (file_type.is_dir() || is_egg_link(&entry.path()))
+                                    .then_some(Ok(entry.path())),
                                 Err(err) => Some(Err(err)),
                             },
                             Err(err) => Some(Err(err)),
@@ -73,7 +75,7 @@ impl<'a> SitePackages<'a> {
             // Index all installed packages by name.
             for path in site_packages {
                 let Some(dist_info) = InstalledDist::try_from_path(&path)
-                    .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
+                    .with_context(|| format!("Failed to read metadata from: {}", path.display()))?
                 else {
                     continue;
                 };
@@ -87,11 +89,20 @@ impl<'a> SitePackages<'a> {
                     .push(idx);
 
                 // Index the distribution by URL.
-                if let InstalledDist::Url(dist) = &dist_info {
-                    by_url
-                        .entry(dist.url.clone())
-                        .or_insert_with(Vec::new)
-                        .push(idx);
+                match &dist_info {
+                    InstalledDist::Url(dist) => {
+                        by_url
+                            .entry(dist.url.clone())
+                            .or_insert_with(Vec::new)
+                            .push(idx);
+                    }
+                    InstalledDist::LegacyEditable(dist) => {
+                        by_url
+                            .entry(dist.target_url.clone())
+                            .or_insert_with(Vec::new)
+                            .push(idx);
+                    }
+                    InstalledDist::Registry(_) => {}
                 }
 
                 // Add the distribution to the database.
@@ -203,6 +214,10 @@ impl<'a> SitePackages<'a> {
     pub fn diagnostics(&self) -> Result<Vec<Diagnostic>> {
         let mut diagnostics = Vec::new();
 
+        // Determine the extras that the installed packages request of one another, such that
+        // dependencies gated behind those extras are validated too.
+        let requested_extras = self.requested_extras();
+
         for (package, indexes) in &self.by_name {
             let mut distributions = indexes.iter().flat_map(|index| &self.distributions[*index]);
 
@@ -249,8 +264,12 @@ impl<'a> SitePackages<'a> {
                 }
 
                 // Verify that the dependencies are installed.
+                let extras = requested_extras
+                    .get(package)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
                 for dependency in &metadata.requires_dist {
-                    if !dependency.evaluate_markers(self.venv.interpreter().markers(), &[]) {
+                    if !dependency.evaluate_markers(self.venv.interpreter().markers(), extras) {
                         continue;
                     }
 
@@ -293,6 +312,46 @@ impl<'a> SitePackages<'a> {
         Ok(diagnostics)
     }
 
+    /// Returns the extras that are requested of each installed package by the dependencies of the
+    /// other installed packages.
+    ///
+    /// Extras are activated transitively: if `a` requires `b[x]`, and `b[x]` requires `c[y]`,
+    /// then `y` is requested of `c`.
+    fn requested_extras(&self) -> FxHashMap<PackageName, Vec<ExtraName>> {
+        let metadata = self
+            .iter()
+            .filter_map(|distribution| {
+                let metadata = distribution.metadata().ok()?;
+                Some((distribution.name().clone(), metadata.requires_dist))
+            })
+            .collect::<Vec<_>>();
+
+        let mut requested_extras: FxHashMap<PackageName, Vec<ExtraName>> = FxHashMap::default();
+        loop {
+            let mut changed = false;
+            for (package, requires_dist) in &metadata {
+                let extras = requested_extras.get(package).cloned().unwrap_or_default();
+                for dependency in requires_dist {
+                    if dependency.extras.is_empty()
+                        || !dependency.evaluate_markers(self.venv.interpreter().markers(), &extras)
+                    {
+                        continue;
+                    }
+                    let entry = requested_extras.entry(dependency.name.clone()).or_default();
+                    for extra in &dependency.extras {
+                        if !entry.contains(extra) {
+                            entry.push(extra.clone());
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            if !changed {
+                return requested_extras;
+            }
+        }
+    }
+
     /// Returns `true` if the installed packages satisfy the given requirements.
     pub fn satisfies(
         &self,
@@ -602,3 +661,8 @@ impl InstalledPackagesProvider for SitePackages<'_> {
         self.get_packages(name)
     }
 }
+
+/// Returns `true` if the path is an `.egg-link` file, as written for legacy editable installs.
+pub(crate) fn is_egg_link(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "egg-link")
+}
@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tempfile::TempDir;
+use tracing::debug;
+use walkdir::WalkDir;
+
+use distribution_types::InstalledDist;
+use uv_fs::Simplified;
+use uv_interpreter::PythonEnvironment;
+
+/// A copy of the files of a set of installed distributions, taken before they're uninstalled or
+/// replaced, such that they can be restored if installing their replacements fails.
+///
+/// Files are hard-linked into the snapshot where possible, so taking a snapshot is cheap.
+#[derive(Debug)]
+pub struct Snapshot {
+    /// The directory that contains the copies, which is removed when the snapshot is dropped.
+    dir: TempDir,
+    /// The original path of each file, and the path of its copy.
+    files: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Snapshot {
+    /// Take a snapshot of the files that uninstalling the given distributions would remove.
+    ///
+    /// The snapshot is stored within the environment, such that its files can be hard-linked.
+    pub fn take<'a>(
+        dists: impl IntoIterator<Item = &'a InstalledDist>,
+        venv: &PythonEnvironment,
+    ) -> Result<Self> {
+        let dir = tempfile::tempdir_in(venv.root())?;
+        let mut snapshot = Self {
+            dir,
+            files: Vec::new(),
+        };
+
+        for dist in dists {
+            // Uninstalling a legacy editable only touches its `.egg-link` file and the adjacent
+            // `easy-install.pth` file.
+            if let InstalledDist::LegacyEditable(dist) = dist {
+                snapshot.add(&dist.egg_link)?;
+                let pth = dist.egg_link.with_file_name("easy-install.pth");
+                if pth.is_file() {
+                    snapshot.add(&pth)?;
+                }
+                continue;
+            }
+
+            // If the `RECORD` can't be read, at least preserve the metadata directory, which is
+            // what identifies the distribution as installed.
+            let paths = match install_wheel_rs::uninstall_plan(dist.path()) {
+                Ok(plan) => plan.files().map(Path::to_path_buf).collect::<Vec<_>>(),
+                Err(err) => {
+                    debug!("Failed to determine installed files for {dist}: {err}");
+                    vec![dist.path().to_path_buf()]
+                }
+            };
+            for path in paths {
+                for entry in WalkDir::new(&path) {
+                    let entry = entry?;
+                    if !entry.file_type().is_dir() {
+                        snapshot.add(entry.path())?;
+                    }
+                }
+            }
+        }
+
+        debug!(
+            "Took a snapshot of {} file(s) at: {}",
+            snapshot.files.len(),
+            snapshot.dir.path().user_display()
+        );
+        Ok(snapshot)
+    }
+
+    /// Copy a file into the snapshot.
+    fn add(&mut self, path: &Path) -> Result<()> {
+        let copy = self.dir.path().join(self.files.len().to_string());
+        link_or_copy(path, &copy)
+            .with_context(|| format!("Failed to snapshot: {}", path.user_display()))?;
+        self.files.push((path.to_path_buf(), copy));
+        Ok(())
+    }
+
+    /// Restore every file in the snapshot to its original location, replacing any file that has
+    /// since been written there.
+    pub fn restore(self) -> Result<()> {
+        debug!("Restoring {} file(s) from snapshot", self.files.len());
+        for (path, copy) in &self.files {
+            if let Some(parent) = path.parent() {
+                fs_err::create_dir_all(parent)?;
+            }
+            match fs_err::remove_file(path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            link_or_copy(copy, path)
+                .with_context(|| format!("Failed to restore: {}", path.user_display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Hard-link a file, falling back to copying it, e.g., across filesystems.
+fn link_or_copy(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs_err::hard_link(from, to).is_ok() {
+        return Ok(());
+    }
+    fs_err::copy(from, to)?;
+    Ok(())
+}
@@ -7,8 +7,37 @@ pub async fn uninstall(
     dist: &InstalledDist,
 ) -> Result<install_wheel_rs::Uninstall, UninstallError> {
     let uninstall = tokio::task::spawn_blocking({
-        let path = dist.path().to_owned();
-        move || install_wheel_rs::uninstall_wheel(&path)
+        let dist = dist.clone();
+        move || match dist {
+            InstalledDist::Registry(_) | InstalledDist::Url(_) => {
+                install_wheel_rs::uninstall_wheel(dist.path())
+            }
+            InstalledDist::LegacyEditable(dist) => {
+                install_wheel_rs::uninstall_legacy_editable(&dist.egg_link)
+            }
+        }
+    })
+    .await??;
+
+    Ok(uninstall)
+}
+
+/// Uninstall a package from the specified Python environment, inferring the files to remove if
+/// its `RECORD` file is missing or damaged.
+pub async fn repair_uninstall(
+    dist: &InstalledDist,
+) -> Result<install_wheel_rs::Uninstall, UninstallError> {
+    let uninstall = tokio::task::spawn_blocking({
+        let dist = dist.clone();
+        move || match dist {
+            InstalledDist::Registry(_) | InstalledDist::Url(_) => {
+                install_wheel_rs::repair_uninstall_wheel(dist.path())
+            }
+            // Legacy editables are tracked by their `.egg-link` file, rather than a `RECORD`.
+            InstalledDist::LegacyEditable(dist) => {
+                install_wheel_rs::uninstall_legacy_editable(&dist.egg_link)
+            }
+        }
     })
     .await??;
 
@@ -407,6 +407,32 @@ def get_scheme():
         return get_distutils_scheme()
 
 
+def get_user_scheme():
+    """Return the Scheme for installing packages into the user site-packages directory, as with
+    `pip install --user`.
+
+    This is based on pip's `--user` scheme discovery logic:
+        https://github.com/pypa/pip/blob/ae5fff36b0aad6e5e0037884927eaa29163c0611/src/pip/_internal/locations/_sysconfig.py#L108
+    """
+    # `get_preferred_scheme` was added in Python 3.10; before that, the user scheme is
+    # determined by the platform.
+    if hasattr(sysconfig, "get_preferred_scheme"):
+        scheme_name = sysconfig.get_preferred_scheme("user")
+    elif sys.platform == "darwin" and getattr(sys, "_framework", None):
+        scheme_name = "osx_framework_user"
+    else:
+        scheme_name = f"{os.name}_user"
+
+    paths = sysconfig.get_paths(scheme=scheme_name)
+    return {
+        "platlib": paths["platlib"],
+        "purelib": paths["purelib"],
+        "include": paths["include"],
+        "scripts": paths["scripts"],
+        "data": paths["data"],
+    }
+
+
 def get_operating_system_and_architecture():
     """Determine the Python interpreter architecture and operating system.
 
@@ -533,6 +559,7 @@ def main() -> None:
         "stdlib": sysconfig.get_path("stdlib"),
         "scheme": get_scheme(),
         "virtualenv": get_virtualenv(),
+        "user_scheme": get_user_scheme(),
         "platform": get_operating_system_and_architecture(),
         # The `t` abiflag for freethreading python
         # https://peps.python.org/pep-0703/#build-configuration-changes
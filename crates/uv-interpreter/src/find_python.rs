@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use std::env;
 use std::ffi::{OsStr, OsString};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tracing::{debug, instrument};
 
@@ -87,6 +87,8 @@ pub(crate) fn try_find_default_python(cache: &Cache) -> Result<Option<Interprete
 ///   * Major: `pythonx`, `python`
 ///   * Default: `python3`, `python`
 ///   * (windows): For each of the above, test for the existence of `python.bat` shim (pyenv-windows) last.
+/// * (unix): Discover installations managed by pyenv in `$PYENV_ROOT/versions` (or
+///   `~/.pyenv/versions`), even if they aren't activated via a shim in `PATH`.
 /// * (windows): Discover installations using `py --list-paths` (PEP514). Continue if `py` is not installed.
 ///
 /// (Windows): Filter out the Windows store shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
@@ -170,6 +172,31 @@ fn find_python(
         }
     }
 
+    if cfg!(unix) && !use_override {
+        // Search the pyenv installations, from the most recent version to the oldest.
+        if let Some(root) = pyenv_root() {
+            for executable in pyenv_executables(&root) {
+                let interpreter = match Interpreter::query(&executable, cache) {
+                    Ok(interpreter) => interpreter,
+                    Err(error) => {
+                        // Don't fail on a broken or unsupported pyenv installation.
+                        debug!(
+                            "Failed to query pyenv installation at {}: {error}",
+                            executable.display()
+                        );
+                        continue;
+                    }
+                };
+
+                if let Some(interpreter) =
+                    PythonInstallation::Interpreter(interpreter).select(selector, cache)?
+                {
+                    return Ok(Some(interpreter));
+                }
+            }
+        }
+    }
+
     if cfg!(windows) && !use_override {
         // Use `py` to find the python installation on the system.
         match windows::py_list_paths() {
@@ -193,6 +220,55 @@ fn find_python(
     Ok(None)
 }
 
+/// Return the pyenv root directory, i.e., `PYENV_ROOT` or `~/.pyenv`.
+fn pyenv_root() -> Option<PathBuf> {
+    env::var_os("PYENV_ROOT")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".pyenv")))
+}
+
+/// Return the Python executables installed under the given pyenv root, ordered from the most
+/// recent version to the oldest.
+///
+/// Entries that don't start with a version number (e.g., `pypy3.10-7.3.15` or virtualenvs created
+/// by `pyenv-virtualenv`) are included after all versioned entries, in lexicographic order.
+fn pyenv_executables(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs_err::read_dir(root.join("versions")) else {
+        return Vec::new();
+    };
+
+    let mut versions = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let executable = entry.path().join("bin").join("python3");
+            executable.is_file().then_some((name, executable))
+        })
+        .collect::<Vec<_>>();
+
+    // Sort by the leading numeric release segments, in descending order.
+    let release = |name: &str| -> Vec<u64> {
+        name.split(['.', '-'])
+            .map_while(|segment| segment.parse::<u64>().ok())
+            .collect()
+    };
+    versions.sort_by(|(a, _), (b, _)| {
+        let (a_release, b_release) = (release(a), release(b));
+        match (a_release.is_empty(), b_release.is_empty()) {
+            (false, true) => std::cmp::Ordering::Less,
+            (true, false) => std::cmp::Ordering::Greater,
+            (true, true) => a.cmp(b),
+            (false, false) => b_release.cmp(&a_release),
+        }
+    });
+
+    versions
+        .into_iter()
+        .map(|(_, executable)| executable)
+        .collect()
+}
+
 /// Find the Python interpreter in `PATH` matching the given name (e.g., `python3`, respecting
 /// `UV_PYTHON_PATH`.
 ///
@@ -447,7 +523,7 @@ pub fn find_best_python(
 /// - `UV_DEFAULT_PYTHON`, which is set to the python interpreter when using `python -m uv`.
 /// - `VIRTUAL_ENV` and `CONDA_PREFIX`
 /// - A `.venv` folder
-/// - If a python version is given: Search `PATH` and `py --list-paths`, see `find_python`
+/// - If a python version is given: Search `PATH`, pyenv, and `py --list-paths`, see `find_python`
 /// - `python3` (unix) or `python.exe` (windows)
 ///
 /// If `UV_TEST_PYTHON_PATH` is set, we will not check for Python versions in the
@@ -737,7 +813,7 @@ mod tests {
 
     use uv_cache::Cache;
 
-    use crate::find_python::find_requested_python;
+    use crate::find_python::{find_requested_python, pyenv_executables};
     use crate::Error;
 
     fn format_err<T: std::fmt::Debug>(err: Result<T, Error>) -> String {
@@ -746,6 +822,52 @@ mod tests {
             .join("\n  Caused by: ")
     }
 
+    #[test]
+    fn pyenv_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        for version in [
+            "3.8.18",
+            "3.12.2",
+            "3.12.10",
+            "pypy3.10-7.3.15",
+            "3.11-dev",
+            "3.9.1",
+        ] {
+            let bin = root.join("versions").join(version).join("bin");
+            fs_err::create_dir_all(&bin).unwrap();
+            fs_err::write(bin.join("python3"), "").unwrap();
+        }
+        // A version without an interpreter is skipped.
+        fs_err::create_dir_all(root.join("versions").join("3.13.0")).unwrap();
+
+        let versions = pyenv_executables(root)
+            .into_iter()
+            .map(|executable| {
+                executable
+                    .strip_prefix(root.join("versions"))
+                    .unwrap()
+                    .components()
+                    .next()
+                    .unwrap()
+                    .as_os_str()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            [
+                "3.12.10",
+                "3.12.2",
+                "3.11-dev",
+                "3.9.1",
+                "3.8.18",
+                "pypy3.10-7.3.15"
+            ]
+        );
+    }
+
     #[test]
     #[cfg_attr(not(unix), ignore)]
     fn no_such_python_version() {
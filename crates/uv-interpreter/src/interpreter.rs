@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
 use cache_key::digest;
-use install_wheel_rs::Layout;
+use install_wheel_rs::{Layout, Shebang};
 use pep440_rs::Version;
 use pep508_rs::{MarkerEnvironment, StringVersion};
 use platform_tags::Platform;
@@ -18,8 +18,7 @@ use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
 use uv_fs::{write_atomic_sync, PythonExt, Simplified};
 use uv_toolchain::PythonVersion;
 
-use crate::Error;
-use crate::Virtualenv;
+use crate::{Error, Target, Virtualenv};
 
 /// A Python executable and its associated platform markers.
 #[derive(Debug, Clone)]
@@ -28,6 +27,7 @@ pub struct Interpreter {
     markers: Box<MarkerEnvironment>,
     scheme: Scheme,
     virtualenv: Scheme,
+    user_scheme: Scheme,
     prefix: PathBuf,
     base_exec_prefix: PathBuf,
     base_prefix: PathBuf,
@@ -35,6 +35,8 @@ pub struct Interpreter {
     sys_executable: PathBuf,
     stdlib: PathBuf,
     tags: OnceCell<Tags>,
+    target: Option<Target>,
+    user_site: bool,
     gil_disabled: bool,
 }
 
@@ -54,6 +56,7 @@ impl Interpreter {
             markers: Box::new(info.markers),
             scheme: info.scheme,
             virtualenv: info.virtualenv,
+            user_scheme: info.user_scheme,
             prefix: info.prefix,
             base_exec_prefix: info.base_exec_prefix,
             gil_disabled: info.gil_disabled,
@@ -62,6 +65,8 @@ impl Interpreter {
             sys_executable: info.sys_executable,
             stdlib: info.stdlib,
             tags: OnceCell::new(),
+            target: None,
+            user_site: false,
         })
     }
 
@@ -84,6 +89,13 @@ impl Interpreter {
                 scripts: PathBuf::from("/dev/null"),
                 data: PathBuf::from("/dev/null"),
             },
+            user_scheme: Scheme {
+                purelib: PathBuf::from("/dev/null"),
+                platlib: PathBuf::from("/dev/null"),
+                include: PathBuf::from("/dev/null"),
+                scripts: PathBuf::from("/dev/null"),
+                data: PathBuf::from("/dev/null"),
+            },
             prefix: PathBuf::from("/dev/null"),
             base_exec_prefix: PathBuf::from("/dev/null"),
             base_prefix: PathBuf::from("/dev/null"),
@@ -91,6 +103,8 @@ impl Interpreter {
             sys_executable: PathBuf::from("/dev/null"),
             stdlib: PathBuf::from("/dev/null"),
             tags: OnceCell::new(),
+            target: None,
+            user_site: false,
             gil_disabled: false,
         }
     }
@@ -102,10 +116,45 @@ impl Interpreter {
             scheme: virtualenv.scheme,
             sys_executable: virtualenv.executable,
             prefix: virtualenv.root,
+            target: None,
+            user_site: false,
+            ..self
+        }
+    }
+
+    /// Return a new [`Interpreter`] that installs packages into the given `--target` directory.
+    #[must_use]
+    pub fn with_target(self, target: Target) -> Self {
+        Self {
+            scheme: target.scheme(),
+            target: Some(target),
+            user_site: false,
+            ..self
+        }
+    }
+
+    /// Return a new [`Interpreter`] that installs packages into the user site-packages directory,
+    /// as with `pip install --user`.
+    #[must_use]
+    pub fn with_user_site(self) -> Self {
+        Self {
+            scheme: self.user_scheme.clone(),
+            target: None,
+            user_site: true,
             ..self
         }
     }
 
+    /// Returns `true` if this interpreter installs packages into the user site-packages directory.
+    pub fn is_user_site(&self) -> bool {
+        self.user_site
+    }
+
+    /// Returns the `--target` directory for this interpreter, if any.
+    pub fn target(&self) -> Option<&Target> {
+        self.target.as_ref()
+    }
+
     /// Returns the path to the Python virtual environment.
     #[inline]
     pub fn platform(&self) -> &Platform {
@@ -138,6 +187,12 @@ impl Interpreter {
         self.prefix != self.base_prefix
     }
 
+    /// Returns `true` if the environment is a Conda environment, as identified by the presence of
+    /// a `conda-meta` directory in its prefix.
+    pub fn is_conda(&self) -> bool {
+        self.prefix.join("conda-meta").is_dir()
+    }
+
     /// Returns `Some` if the environment is externally managed, optionally including an error
     /// message from the `EXTERNALLY-MANAGED` file.
     ///
@@ -307,7 +362,7 @@ impl Interpreter {
     pub fn layout(&self) -> Layout {
         Layout {
             python_version: self.python_tuple(),
-            sys_executable: self.sys_executable().to_path_buf(),
+            sys_executable: self.layout_executable(),
             os_name: self.markers.os_name.clone(),
             scheme: Scheme {
                 purelib: self.purelib().to_path_buf(),
@@ -326,9 +381,27 @@ impl Interpreter {
                     self.include().to_path_buf()
                 },
             },
+            shebang: Shebang::default(),
         }
     }
 
+    /// Return the interpreter that installed scripts should invoke.
+    ///
+    /// On Windows, Conda environments place the interpreter at the root of the prefix, rather
+    /// than in `Scripts` as in a virtual environment. When Python is run through a shim or
+    /// wrapper (e.g., via `conda run`), `sys.executable` may not point to the environment's own
+    /// `python.exe`, in which case script launchers would fail to start (or start the wrong
+    /// interpreter) once the wrapper is gone.
+    fn layout_executable(&self) -> PathBuf {
+        if self.markers.os_name == "nt" && self.is_conda() {
+            let executable = self.prefix.join("python.exe");
+            if executable.is_file() {
+                return executable;
+            }
+        }
+        self.sys_executable().to_path_buf()
+    }
+
     /// Check if the interpreter matches the given Python version.
     ///
     /// If a patch version is present, we will require an exact match.
@@ -381,6 +454,7 @@ struct InterpreterInfo {
     markers: MarkerEnvironment,
     scheme: Scheme,
     virtualenv: Scheme,
+    user_scheme: Scheme,
     prefix: PathBuf,
     base_exec_prefix: PathBuf,
     base_prefix: PathBuf,
@@ -616,6 +690,13 @@ mod tests {
                     "purelib": "lib/python3.12/site-packages",
                     "scripts": "bin"
                 },
+                "user_scheme": {
+                    "data": "/home/ferris/.local",
+                    "include": "/home/ferris/.local/include/python3.12",
+                    "platlib": "/home/ferris/.local/lib/python3.12/site-packages",
+                    "purelib": "/home/ferris/.local/lib/python3.12/site-packages",
+                    "scripts": "/home/ferris/.local/bin"
+                },
                 "gil_disabled": true
             }
         "##};
@@ -654,4 +735,99 @@ mod tests {
             Version::from_str("3.13").unwrap()
         );
     }
+
+    #[test]
+    fn conda_layout() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python");
+        let prefix = mock_dir.path().join("envs").join("app");
+        fs::create_dir_all(prefix.join("conda-meta")).unwrap();
+        fs::create_dir_all(prefix.join("Scripts")).unwrap();
+        fs::write(prefix.join("python.exe"), "").unwrap();
+
+        let prefix = prefix.display().to_string();
+        let json = formatdoc! {r##"
+            {{
+                "result": "success",
+                "platform": {{
+                    "os": {{
+                        "name": "manylinux",
+                        "major": 2,
+                        "minor": 38
+                    }},
+                    "arch": "x86_64"
+                }},
+                "markers": {{
+                    "implementation_name": "cpython",
+                    "implementation_version": "3.12.0",
+                    "os_name": "nt",
+                    "platform_machine": "AMD64",
+                    "platform_python_implementation": "CPython",
+                    "platform_release": "10",
+                    "platform_system": "Windows",
+                    "platform_version": "10.0.22631",
+                    "python_full_version": "3.12.0",
+                    "python_version": "3.12",
+                    "sys_platform": "win32"
+                }},
+                "base_exec_prefix": "{prefix}",
+                "base_prefix": "{prefix}",
+                "prefix": "{prefix}",
+                "sys_executable": "{prefix}/Scripts/python.exe",
+                "stdlib": "{prefix}/Lib",
+                "scheme": {{
+                    "data": "{prefix}",
+                    "include": "{prefix}/Include",
+                    "platlib": "{prefix}/Lib/site-packages",
+                    "purelib": "{prefix}/Lib/site-packages",
+                    "scripts": "{prefix}/Scripts"
+                }},
+                "virtualenv": {{
+                    "data": "",
+                    "include": "Include",
+                    "platlib": "Lib/site-packages",
+                    "purelib": "Lib/site-packages",
+                    "scripts": "Scripts"
+                }},
+                "user_scheme": {{
+                    "data": "{prefix}/AppData/Roaming/Python",
+                    "include": "{prefix}/AppData/Roaming/Python/Python312/Include",
+                    "platlib": "{prefix}/AppData/Roaming/Python/Python312/site-packages",
+                    "purelib": "{prefix}/AppData/Roaming/Python/Python312/site-packages",
+                    "scripts": "{prefix}/AppData/Roaming/Python/Python312/Scripts"
+                }},
+                "gil_disabled": false
+            }}
+        "##};
+
+        let cache = Cache::temp().unwrap();
+
+        fs::write(
+            &mocked_interpreter,
+            formatdoc! {r##"
+            #!/bin/bash
+            echo '{json}'
+            "##},
+        )
+        .unwrap();
+        fs::set_permissions(
+            &mocked_interpreter,
+            std::os::unix::fs::PermissionsExt::from_mode(0o770),
+        )
+        .unwrap();
+        let interpreter = Interpreter::query(&mocked_interpreter, &cache).unwrap();
+        assert!(interpreter.is_conda());
+        assert!(!interpreter.is_virtualenv());
+
+        // Scripts should invoke the environment's own interpreter, at the root of the prefix.
+        let layout = interpreter.layout();
+        assert_eq!(
+            layout.sys_executable,
+            std::path::Path::new(&prefix).join("python.exe")
+        );
+        assert_eq!(
+            layout.scheme.scripts,
+            std::path::Path::new(&prefix).join("Scripts")
+        );
+    }
 }
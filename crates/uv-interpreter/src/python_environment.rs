@@ -8,7 +8,7 @@ use uv_cache::Cache;
 use uv_fs::{LockedFile, Simplified};
 
 use crate::cfg::PyVenvConfiguration;
-use crate::{find_default_python, find_requested_python, Error, Interpreter};
+use crate::{find_default_python, find_requested_python, Error, Interpreter, Target};
 
 /// A Python environment, consisting of a Python [`Interpreter`] and its associated paths.
 #[derive(Debug, Clone)]
@@ -40,6 +40,17 @@ impl PythonEnvironment {
         })
     }
 
+    /// Create a [`PythonEnvironment`] for the virtual environment at the given root directory.
+    pub fn from_root(root: impl AsRef<Path>, cache: &Cache) -> Result<Self, Error> {
+        let venv = fs_err::canonicalize(root.as_ref())?;
+        let executable = detect_python_executable(&venv);
+        let interpreter = Interpreter::query(&executable, cache)?;
+        Ok(Self {
+            root: venv,
+            interpreter,
+        })
+    }
+
     /// Create a [`PythonEnvironment`] for a Python interpreter specifier (e.g., a path or a binary name).
     pub fn from_requested_python(python: &str, cache: &Cache) -> Result<Self, Error> {
         let Some(interpreter) = find_requested_python(python, cache)? else {
@@ -68,6 +79,24 @@ impl PythonEnvironment {
         }
     }
 
+    /// Create a [`PythonEnvironment`] that installs packages into the given `--target` directory.
+    #[must_use]
+    pub fn with_target(self, target: Target) -> Self {
+        Self {
+            interpreter: self.interpreter.with_target(target),
+            ..self
+        }
+    }
+
+    /// Create a [`PythonEnvironment`] that installs packages into the user site-packages directory.
+    #[must_use]
+    pub fn with_user_site(self) -> Self {
+        Self {
+            interpreter: self.interpreter.with_user_site(),
+            ..self
+        }
+    }
+
     /// Returns the location of the Python interpreter.
     pub fn root(&self) -> &Path {
         &self.root
@@ -115,7 +144,23 @@ impl PythonEnvironment {
 
     /// Grab a file lock for the virtual environment to prevent concurrent writes across processes.
     pub fn lock(&self) -> Result<LockedFile, std::io::Error> {
-        if self.interpreter.is_virtualenv() {
+        if let Some(target) = self.interpreter.target() {
+            // If we're installing into a `--target`, use a target-specific lock file.
+            LockedFile::acquire(
+                target.root().join(".lock"),
+                target.root().simplified_display(),
+            )
+        } else if self.interpreter.is_user_site() {
+            // If we're installing into the user site-packages, use a lock file specific to the
+            // user's base directory.
+            LockedFile::acquire(
+                env::temp_dir().join(format!(
+                    "uv-{}.lock",
+                    cache_key::digest(&self.interpreter.data())
+                )),
+                self.interpreter.purelib().simplified_display(),
+            )
+        } else if self.interpreter.is_virtualenv() {
             // If the environment a virtualenv, use a virtualenv-specific lock file.
             LockedFile::acquire(self.root.join(".lock"), self.root.simplified_display())
         } else {
@@ -139,7 +184,7 @@ pub(crate) fn detect_virtual_env() -> Result<Option<PathBuf>, Error> {
     }
     if let Some(dir) = env::var_os("CONDA_PREFIX").filter(|value| !value.is_empty()) {
         info!(
-            "Found a virtualenv through CONDA_PREFIX at: {}",
+            "Found a Conda environment through CONDA_PREFIX at: {}",
             Path::new(&dir).display()
         );
         return Ok(Some(PathBuf::from(dir)));
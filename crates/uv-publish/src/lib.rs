@@ -0,0 +1,528 @@
+//! Upload wheels and source distributions to a PyPI-compatible index, using the legacy upload API.
+//!
+//! See: <https://warehouse.pypa.io/api-reference/legacy.html#upload-api>
+
+use std::fmt::Write as _;
+use std::io::{Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use mailparse::MailHeaderMap;
+use md5::Md5;
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use reqwest::{Response, StatusCode};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+use tracing::debug;
+use url::Url;
+
+use distribution_filename::{DistFilename, SourceDistExtension, WheelFilename};
+use uv_auth::{find_credentials, CredentialProvider};
+use uv_client::BaseClient;
+use uv_fs::Simplified;
+use uv_warnings::warn_user;
+
+pub use trusted_publishing::{
+    get_trusted_publishing_token, MintTokenErrors, TrustedPublishing, TrustedPublishingError,
+};
+
+mod trusted_publishing;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PublishError {
+    #[error("Invalid publish path: `{0}`")]
+    Pattern(String, #[source] glob::PatternError),
+    #[error(transparent)]
+    Glob(#[from] glob::GlobError),
+    #[error("Path patterns didn't match any wheels or source distributions")]
+    NoFiles,
+    #[error("Failed to publish: `{}`", _0.user_display())]
+    Prepare(PathBuf, #[source] Box<PublishPrepareError>),
+    #[error("Failed to publish `{}` to `{}`", _0.user_display(), _1)]
+    Send(PathBuf, Url, #[source] Box<PublishSendError>),
+    #[error("Failed to obtain an upload token via trusted publishing")]
+    TrustedPublishing(#[from] TrustedPublishingError),
+}
+
+/// Failure to read the metadata of a distribution to upload.
+#[derive(Debug, thiserror::Error)]
+pub enum PublishPrepareError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Failed to read wheel metadata")]
+    Wheel(#[from] install_wheel_rs::Error),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[error("Source distribution does not contain a `PKG-INFO` file in its top-level directory")]
+    MissingPkgInfo,
+    #[error("Invalid distribution metadata")]
+    Metadata(#[from] mailparse::MailParseError),
+    #[error("Distribution metadata is missing the required `{0}` field")]
+    MissingField(&'static str),
+}
+
+/// Failure to send a distribution to the index.
+#[derive(Debug, thiserror::Error)]
+pub enum PublishSendError {
+    #[error(transparent)]
+    ReqwestMiddleware(#[from] reqwest_middleware::Error),
+    #[error("Upload failed with status {0}")]
+    StatusNoBody(StatusCode, #[source] reqwest::Error),
+    #[error("Upload failed with status code {0}: {1}")]
+    Status(StatusCode, String),
+}
+
+/// The outcome of uploading a single distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadOutcome {
+    /// The distribution was uploaded.
+    Uploaded,
+    /// The index already contains the distribution, and `--skip-existing` was set.
+    AlreadyExists,
+}
+
+/// Credentials to send with an upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// Use HTTP basic authentication with the given username and password.
+    Basic { username: String, password: String },
+    /// Use an API token, sent as the password for the `__token__` user.
+    Token(String),
+    /// Don't send credentials explicitly, deferring to any credentials embedded in the URL.
+    None,
+}
+
+impl Credentials {
+    /// If no credentials were given explicitly, look them up for the upload URL using the given
+    /// providers (e.g., environment variables, netrc, or the keyring).
+    ///
+    /// Credentials embedded in the URL take precedence over the providers.
+    pub async fn or_from_providers(
+        self,
+        url: &Url,
+        providers: &[Arc<dyn CredentialProvider>],
+    ) -> Self {
+        if !matches!(self, Self::None) || !url.username().is_empty() {
+            return self;
+        }
+        match find_credentials(providers, url).await {
+            Some(credential) => Self::Basic {
+                username: credential.username().to_string(),
+                password: credential.password().unwrap_or_default().to_string(),
+            },
+            None => Self::None,
+        }
+    }
+}
+
+/// Expand the given glob patterns into the wheels and source distributions to upload.
+///
+/// Files that aren't wheels or source distributions (e.g., `.gitignore` files that build backends
+/// add to `dist/`) are skipped.
+pub fn files_for_publishing(
+    paths: Vec<String>,
+) -> Result<Vec<(PathBuf, DistFilename)>, PublishError> {
+    let mut seen = Vec::new();
+    let mut files = Vec::new();
+    for path in paths {
+        for entry in glob::glob(&path).map_err(|err| PublishError::Pattern(path.clone(), err))? {
+            let entry = entry?;
+            if !entry.is_file() || seen.contains(&entry) {
+                continue;
+            }
+            seen.push(entry.clone());
+            let Some(filename) = entry
+                .file_name()
+                .and_then(|filename| filename.to_str())
+                .and_then(DistFilename::try_from_normalized_filename)
+            else {
+                debug!("Skipping non-distribution file: `{}`", entry.user_display());
+                continue;
+            };
+            files.push((entry, filename));
+        }
+    }
+    if files.is_empty() {
+        return Err(PublishError::NoFiles);
+    }
+    Ok(files)
+}
+
+/// Upload a distribution to the index at the given URL.
+///
+/// Transient failures (connection errors, timeouts, and server errors) are retried up to `retries`
+/// times with exponential backoff. The request body is buffered in memory, so each attempt sends
+/// the full distribution.
+///
+/// If `skip_existing` is set and the index reports that the file already exists, returns
+/// [`UploadOutcome::AlreadyExists`] rather than an error.
+pub async fn upload(
+    file: &Path,
+    filename: &DistFilename,
+    registry: &Url,
+    client: &BaseClient,
+    retries: u32,
+    credentials: &Credentials,
+    skip_existing: bool,
+) -> Result<UploadOutcome, PublishError> {
+    let form = build_form(file, filename)
+        .await
+        .map_err(|err| PublishError::Prepare(file.to_path_buf(), Box::new(err)))?;
+
+    let mut attempt = 0;
+    loop {
+        let mut request = client
+            .post(registry.clone())
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_str(&form.content_type())
+                    .expect("Multipart content type is a valid header value"),
+            )
+            .body(form.body.clone());
+        request = match credentials {
+            Credentials::Basic { username, password } => {
+                request.basic_auth(username, Some(password))
+            }
+            Credentials::Token(token) => request.basic_auth("__token__", Some(token)),
+            Credentials::None => request,
+        };
+
+        let result = request.send().await;
+        let retryable = match &result {
+            Ok(response) => {
+                response.status().is_server_error()
+                    || response.status() == StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(reqwest_middleware::Error::Reqwest(err)) => {
+                err.is_connect() || err.is_timeout() || err.is_request()
+            }
+            Err(reqwest_middleware::Error::Middleware(_)) => false,
+        };
+        if retryable && attempt < retries {
+            attempt += 1;
+            let reason = match &result {
+                Ok(response) => format!("status {}", response.status()),
+                Err(err) => err.to_string(),
+            };
+            warn_user!("Upload of `{filename}` failed ({reason}), retrying ({attempt}/{retries})");
+            tokio::time::sleep(Duration::from_millis(500 * 2u64.pow(attempt - 1))).await;
+            continue;
+        }
+
+        let response = result.map_err(|err| {
+            PublishError::Send(
+                file.to_path_buf(),
+                registry.clone(),
+                Box::new(PublishSendError::from(err)),
+            )
+        })?;
+        return handle_response(response, skip_existing)
+            .await
+            .map_err(|err| {
+                PublishError::Send(file.to_path_buf(), registry.clone(), Box::new(err))
+            });
+    }
+}
+
+/// Interpret the index's response to an upload.
+async fn handle_response(
+    response: Response,
+    skip_existing: bool,
+) -> Result<UploadOutcome, PublishSendError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(UploadOutcome::Uploaded);
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|err| PublishSendError::StatusNoBody(status, err))?;
+
+    if skip_existing && is_already_exists(status, &body) {
+        return Ok(UploadOutcome::AlreadyExists);
+    }
+
+    Err(PublishSendError::Status(status, body.trim().to_string()))
+}
+
+/// Returns `true` if the index rejected the upload because the file already exists.
+///
+/// There's no standard response for this case, so we match the responses of known indexes, as
+/// twine does.
+fn is_already_exists(status: StatusCode, body: &str) -> bool {
+    let body = body.to_lowercase();
+    match status {
+        // pypiserver and Gitea.
+        StatusCode::CONFLICT => true,
+        // PyPI, TestPyPI, GCP Artifact Registry, and Nexus.
+        StatusCode::BAD_REQUEST => {
+            body.contains("already exist") || body.contains("updating asset")
+        }
+        // Artifactory.
+        StatusCode::FORBIDDEN => body.contains("overwrite artifact"),
+        _ => false,
+    }
+}
+
+/// A `multipart/form-data` request body for the upload API.
+///
+/// The body is encoded up front, rather than streamed, so that it can be re-sent on retry.
+#[derive(Debug)]
+struct Form {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Form {
+    fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+}
+
+/// Build the upload form for a distribution: its core metadata, its hashes, and the file itself.
+async fn build_form(file: &Path, filename: &DistFilename) -> Result<Form, PublishPrepareError> {
+    let content = fs_err::tokio::read(file).await?;
+
+    let metadata = match filename {
+        DistFilename::WheelFilename(wheel) => wheel_metadata(wheel, &content)?,
+        DistFilename::SourceDistFilename(sdist) => match sdist.extension {
+            SourceDistExtension::TarGz => tar_gz_pkg_info(&content).await?,
+            SourceDistExtension::Zip => zip_pkg_info(&content)?,
+        },
+    };
+    let mut fields = metadata_fields(&metadata)?;
+
+    let (filetype, pyversion) = match filename {
+        DistFilename::WheelFilename(wheel) => ("bdist_wheel", wheel.python_tag.join(".")),
+        DistFilename::SourceDistFilename(_) => ("sdist", "source".to_string()),
+    };
+    let sha256_digest = format!("{:x}", Sha256::digest(&content));
+    let md5_digest = format!("{:x}", Md5::digest(&content));
+    fields.extend([
+        (":action".to_string(), "file_upload".to_string()),
+        ("protocol_version".to_string(), "1".to_string()),
+        ("filetype".to_string(), filetype.to_string()),
+        ("pyversion".to_string(), pyversion),
+        ("md5_digest".to_string(), md5_digest),
+        ("sha256_digest".to_string(), sha256_digest.clone()),
+    ]);
+
+    // The boundary must not occur in the body. Deriving it from the file's hash makes a collision
+    // with the file contents vanishingly unlikely, and keeps the request body deterministic.
+    let boundary = format!("----uv-publish-{}", &sha256_digest[..32]);
+    let mut body = Vec::with_capacity(content.len() + 4096);
+    for (name, value) in &fields {
+        let mut part = String::new();
+        write!(
+            part,
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{value}\r\n",
+            escape_quoted(name)
+        )
+        .expect("Writing to a string is infallible");
+        body.extend_from_slice(part.as_bytes());
+    }
+    let header = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"content\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        escape_quoted(&file.file_name().unwrap_or_default().to_string_lossy())
+    );
+    body.extend_from_slice(header.as_bytes());
+    body.extend_from_slice(&content);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    Ok(Form { boundary, body })
+}
+
+/// Escape a value for use in a quoted `Content-Disposition` parameter.
+fn escape_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Read the `METADATA` file from a wheel.
+fn wheel_metadata(
+    filename: &WheelFilename,
+    content: &[u8],
+) -> Result<Vec<u8>, PublishPrepareError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(content))?;
+    Ok(install_wheel_rs::metadata::read_archive_metadata(
+        filename,
+        &mut archive,
+    )?)
+}
+
+/// Returns `true` if the path within a source distribution archive is its top-level `PKG-INFO`,
+/// i.e., `{name}-{version}/PKG-INFO`.
+fn is_top_level_pkg_info(path: &Path) -> bool {
+    let components = path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect::<Vec<_>>();
+    matches!(
+        components.as_slice(),
+        [Component::Normal(_), Component::Normal(name)] if *name == "PKG-INFO"
+    )
+}
+
+/// Read the `PKG-INFO` file from a `.tar.gz` source distribution.
+async fn tar_gz_pkg_info(content: &[u8]) -> Result<Vec<u8>, PublishPrepareError> {
+    let decoder = async_compression::tokio::bufread::GzipDecoder::new(content);
+    let mut archive = tokio_tar::Archive::new(decoder);
+    let mut entries = archive.entries()?;
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        if is_top_level_pkg_info(&entry.path()?) {
+            let mut buffer = Vec::new();
+            entry.read_to_end(&mut buffer).await?;
+            return Ok(buffer);
+        }
+    }
+    Err(PublishPrepareError::MissingPkgInfo)
+}
+
+/// Read the `PKG-INFO` file from a `.zip` source distribution.
+fn zip_pkg_info(content: &[u8]) -> Result<Vec<u8>, PublishPrepareError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(content))?;
+    let Some(name) = archive
+        .file_names()
+        .find(|name| is_top_level_pkg_info(Path::new(name)))
+        .map(ToString::to_string)
+    else {
+        return Err(PublishPrepareError::MissingPkgInfo);
+    };
+    let mut buffer = Vec::new();
+    archive.by_name(&name)?.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Convert core metadata into upload form fields.
+///
+/// Each header becomes a field named after the lowercased header, with dashes replaced by
+/// underscores (e.g., `Requires-Dist` becomes `requires_dist`); multiple-use headers produce one
+/// field per value. If the description is given as the message body, it's sent as `description`.
+fn metadata_fields(metadata: &[u8]) -> Result<Vec<(String, String)>, PublishPrepareError> {
+    let (headers, body_start) = mailparse::parse_headers(metadata)?;
+
+    for required in ["Metadata-Version", "Name", "Version"] {
+        if headers.get_first_header(required).is_none() {
+            return Err(PublishPrepareError::MissingField(required));
+        }
+    }
+
+    let mut fields = headers
+        .iter()
+        .map(|header| (form_field_name(&header.get_key()), header.get_value()))
+        .collect::<Vec<_>>();
+
+    if headers.get_first_header("Description").is_none() {
+        let description = String::from_utf8_lossy(&metadata[body_start..]);
+        if !description.trim().is_empty() {
+            fields.push(("description".to_string(), description.to_string()));
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Map a core metadata header to the name of the corresponding upload form field.
+fn form_field_name(header: &str) -> String {
+    let header = header.to_ascii_lowercase();
+    match header.as_str() {
+        "classifier" => "classifiers".to_string(),
+        "project-url" => "project_urls".to_string(),
+        "license-file" => "license_files".to_string(),
+        "home-page" => "home_page".to_string(),
+        _ => header.replace('-', "_"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use reqwest::StatusCode;
+
+    use super::{form_field_name, is_already_exists, is_top_level_pkg_info, metadata_fields};
+
+    #[test]
+    fn fields_from_metadata() {
+        let metadata = [
+            "Metadata-Version: 2.1",
+            "Name: example",
+            "Version: 1.0.0",
+            "Classifier: Programming Language :: Python",
+            "Requires-Dist: anyio>=4",
+            "Requires-Dist: idna",
+            "Description-Content-Type: text/markdown",
+            "",
+            "# Example",
+            "",
+        ]
+        .join("\n");
+        let fields = metadata_fields(metadata.as_bytes()).unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                ("metadata_version".to_string(), "2.1".to_string()),
+                ("name".to_string(), "example".to_string()),
+                ("version".to_string(), "1.0.0".to_string()),
+                (
+                    "classifiers".to_string(),
+                    "Programming Language :: Python".to_string()
+                ),
+                ("requires_dist".to_string(), "anyio>=4".to_string()),
+                ("requires_dist".to_string(), "idna".to_string()),
+                (
+                    "description_content_type".to_string(),
+                    "text/markdown".to_string()
+                ),
+                ("description".to_string(), "# Example\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_version() {
+        let metadata = "Metadata-Version: 2.1\nName: example\n";
+        let err = metadata_fields(metadata.as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Distribution metadata is missing the required `Version` field"
+        );
+    }
+
+    #[test]
+    fn field_names() {
+        assert_eq!(form_field_name("Project-URL"), "project_urls");
+        assert_eq!(form_field_name("Home-page"), "home_page");
+        assert_eq!(form_field_name("Requires-Python"), "requires_python");
+    }
+
+    #[test]
+    fn already_exists() {
+        assert!(is_already_exists(
+            StatusCode::BAD_REQUEST,
+            "400 File already exists ('example-1.0.0.tar.gz', with blake2_256 hash '...')."
+        ));
+        assert!(is_already_exists(StatusCode::CONFLICT, ""));
+        assert!(!is_already_exists(
+            StatusCode::BAD_REQUEST,
+            "Invalid value for classifiers."
+        ));
+        assert!(!is_already_exists(
+            StatusCode::FORBIDDEN,
+            "Invalid API token"
+        ));
+    }
+
+    #[test]
+    fn top_level_pkg_info() {
+        assert!(is_top_level_pkg_info(Path::new("example-1.0.0/PKG-INFO")));
+        assert!(is_top_level_pkg_info(Path::new("./example-1.0.0/PKG-INFO")));
+        assert!(!is_top_level_pkg_info(Path::new(
+            "example-1.0.0/src/example.egg-info/PKG-INFO"
+        )));
+        assert!(!is_top_level_pkg_info(Path::new("PKG-INFO")));
+    }
+}
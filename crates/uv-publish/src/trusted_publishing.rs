@@ -0,0 +1,342 @@
+//! Exchange an OpenID Connect (OIDC) token from a CI provider for a short-lived upload token, so
+//! that releases can be published from CI without storing a long-lived API token.
+//!
+//! See: <https://docs.pypi.org/trusted-publishers/using-a-publisher/>
+
+use std::env;
+use std::fmt::{Display, Formatter};
+
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+use uv_client::BaseClient;
+
+/// Whether to use trusted publishing to authenticate an upload.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TrustedPublishing {
+    /// Use trusted publishing when running in a supported CI provider (GitHub Actions or GitLab
+    /// CI) and no other credentials were given, falling back to uploading without credentials if
+    /// no OIDC token is available.
+    #[default]
+    Automatic,
+    /// Always use trusted publishing, failing if no OIDC token is available.
+    Always,
+    /// Never use trusted publishing.
+    Never,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TrustedPublishingError {
+    #[error("Trusted publishing is only supported in GitHub Actions and GitLab CI")]
+    UnsupportedEnvironment,
+    #[error("Environment variable `{0}` is not set; is the `id-token: write` permission set for the job?")]
+    MissingGitHubVariable(&'static str),
+    #[error("Environment variable `{0}` is not set; define an `id_tokens` entry with `aud: {1}` for the job")]
+    MissingGitLabVariable(String, String),
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+    #[error("Failed to fetch `{0}`")]
+    Request(Url, #[source] reqwest_middleware::Error),
+    #[error("Failed to read the response from `{0}`")]
+    Response(Url, #[source] reqwest::Error),
+    #[error("Request to `{0}` failed with status code {1}")]
+    Status(Url, StatusCode),
+    #[error("Invalid response from `{0}`")]
+    Json(Url, #[source] serde_json::Error),
+    #[error("Failed to exchange the OIDC token for an upload token with status code {0}: {1}")]
+    MintToken(StatusCode, MintTokenErrors),
+}
+
+/// The errors reported by the index when rejecting an OIDC token, e.g., because no trusted
+/// publisher is configured for the repository and workflow.
+#[derive(Debug, Deserialize)]
+pub struct MintTokenErrors {
+    message: String,
+    #[serde(default)]
+    errors: Vec<MintTokenError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MintTokenError {
+    code: String,
+    description: String,
+}
+
+impl Display for MintTokenErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        for error in &self.errors {
+            write!(f, "\n  * `{}`: {}", error.code, error.description)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Audience {
+    audience: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubToken {
+    value: String,
+}
+
+#[derive(Debug, Serialize)]
+struct MintTokenRequest<'a> {
+    token: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct MintTokenResponse {
+    token: String,
+}
+
+/// The CI provider we're running in, if it supports trusted publishing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CiProvider {
+    GitHubActions,
+    GitLab,
+}
+
+impl CiProvider {
+    fn detect() -> Option<Self> {
+        if env::var_os("GITHUB_ACTIONS").is_some_and(|value| value == "true") {
+            Some(Self::GitHubActions)
+        } else if env::var_os("GITLAB_CI").is_some_and(|value| value == "true") {
+            Some(Self::GitLab)
+        } else {
+            None
+        }
+    }
+}
+
+/// Obtain a short-lived upload token for the given upload URL.
+///
+/// Returns `Ok(None)` in [`TrustedPublishing::Automatic`] mode if we're not running in a supported
+/// CI provider, if the index doesn't support trusted publishing, or if the CI job wasn't granted
+/// an OIDC token.
+pub async fn get_trusted_publishing_token(
+    publish_url: &Url,
+    mode: TrustedPublishing,
+    client: &BaseClient,
+) -> Result<Option<String>, TrustedPublishingError> {
+    let provider = match (mode, CiProvider::detect()) {
+        (TrustedPublishing::Never, _) | (TrustedPublishing::Automatic, None) => return Ok(None),
+        (TrustedPublishing::Always, None) => {
+            return Err(TrustedPublishingError::UnsupportedEnvironment)
+        }
+        (_, Some(provider)) => provider,
+    };
+
+    let index_url = index_url(publish_url)?;
+    let audience = match get_audience(&index_url, client).await {
+        Ok(audience) => audience,
+        // The index may not support trusted publishing at all.
+        Err(err) if mode == TrustedPublishing::Automatic => {
+            debug!("Skipping trusted publishing: {err}");
+            return Ok(None);
+        }
+        Err(err) => return Err(err),
+    };
+    debug!("Using trusted publishing audience `{audience}` for `{index_url}`");
+
+    let oidc_token = match get_oidc_token(provider, &audience, client).await {
+        Ok(oidc_token) => oidc_token,
+        Err(
+            err @ (TrustedPublishingError::MissingGitHubVariable(_)
+            | TrustedPublishingError::MissingGitLabVariable(..)),
+        ) if mode == TrustedPublishing::Automatic => {
+            debug!("Skipping trusted publishing: {err}");
+            return Ok(None);
+        }
+        Err(err) => return Err(err),
+    };
+
+    let token = mint_token(&index_url, &oidc_token, client).await?;
+    Ok(Some(token))
+}
+
+/// Return the base URL of the index that hosts the trusted publishing endpoints for an upload URL.
+///
+/// PyPI serves uploads from `upload.pypi.org`, but the OIDC endpoints from `pypi.org`; other
+/// indexes (e.g., TestPyPI) serve both from the same host.
+fn index_url(publish_url: &Url) -> Result<Url, url::ParseError> {
+    let mut url = publish_url.join("/")?;
+    if url.host_str() == Some("upload.pypi.org") {
+        url.set_host(Some("pypi.org"))?;
+    }
+    Ok(url)
+}
+
+/// Request the audience that the index expects in OIDC tokens.
+async fn get_audience(
+    index_url: &Url,
+    client: &BaseClient,
+) -> Result<String, TrustedPublishingError> {
+    let url = index_url.join("_/oidc/audience")?;
+    let body = get_json_body(client.get(url.clone()), &url).await?;
+    let audience: Audience =
+        serde_json::from_str(&body).map_err(|err| TrustedPublishingError::Json(url, err))?;
+    Ok(audience.audience)
+}
+
+/// Request an OIDC token for the given audience from the CI provider.
+async fn get_oidc_token(
+    provider: CiProvider,
+    audience: &str,
+    client: &BaseClient,
+) -> Result<String, TrustedPublishingError> {
+    match provider {
+        CiProvider::GitHubActions => {
+            let Ok(request_token) = env::var("ACTIONS_ID_TOKEN_REQUEST_TOKEN") else {
+                return Err(TrustedPublishingError::MissingGitHubVariable(
+                    "ACTIONS_ID_TOKEN_REQUEST_TOKEN",
+                ));
+            };
+            let Ok(request_url) = env::var("ACTIONS_ID_TOKEN_REQUEST_URL") else {
+                return Err(TrustedPublishingError::MissingGitHubVariable(
+                    "ACTIONS_ID_TOKEN_REQUEST_URL",
+                ));
+            };
+            let mut url = Url::parse(&request_url)?;
+            url.query_pairs_mut().append_pair("audience", audience);
+            let body =
+                get_json_body(client.get(url.clone()).bearer_auth(request_token), &url).await?;
+            let token: GitHubToken = serde_json::from_str(&body)
+                .map_err(|err| TrustedPublishingError::Json(url, err))?;
+            Ok(token.value)
+        }
+        CiProvider::GitLab => {
+            // GitLab exposes ID tokens through variables named in the job's `id_tokens` section;
+            // by convention, `PYPI_ID_TOKEN` for PyPI and `TESTPYPI_ID_TOKEN` for TestPyPI.
+            let variable = gitlab_variable(audience);
+            env::var(&variable).map_err(|_| {
+                TrustedPublishingError::MissingGitLabVariable(variable, audience.to_string())
+            })
+        }
+    }
+}
+
+/// The name of the GitLab CI variable holding the ID token for the given audience.
+fn gitlab_variable(audience: &str) -> String {
+    let audience = audience
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{audience}_ID_TOKEN")
+}
+
+/// Exchange the OIDC token for a short-lived upload token.
+async fn mint_token(
+    index_url: &Url,
+    oidc_token: &str,
+    client: &BaseClient,
+) -> Result<String, TrustedPublishingError> {
+    let url = index_url.join("_/oidc/mint-token")?;
+    let request = serde_json::to_string(&MintTokenRequest { token: oidc_token })
+        .expect("Token request is valid JSON");
+    let response = client
+        .post(url.clone())
+        .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+        .body(request)
+        .send()
+        .await
+        .map_err(|err| TrustedPublishingError::Request(url.clone(), err))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| TrustedPublishingError::Response(url.clone(), err))?;
+
+    if !status.is_success() {
+        return Err(match serde_json::from_str::<MintTokenErrors>(&body) {
+            Ok(errors) => TrustedPublishingError::MintToken(status, errors),
+            Err(_) => TrustedPublishingError::Status(url, status),
+        });
+    }
+
+    let token: MintTokenResponse =
+        serde_json::from_str(&body).map_err(|err| TrustedPublishingError::Json(url, err))?;
+    debug!("Received a short-lived upload token via trusted publishing");
+    Ok(token.token)
+}
+
+/// Send a request and return the body of a successful response.
+async fn get_json_body(
+    request: reqwest_middleware::RequestBuilder,
+    url: &Url,
+) -> Result<String, TrustedPublishingError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|err| TrustedPublishingError::Request(url.clone(), err))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(TrustedPublishingError::Status(url.clone(), status));
+    }
+    response
+        .text()
+        .await
+        .map_err(|err| TrustedPublishingError::Response(url.clone(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{gitlab_variable, index_url, MintTokenErrors};
+
+    #[test]
+    fn index_urls() {
+        let url = |publish_url: &str| {
+            index_url(&Url::parse(publish_url).unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(url("https://upload.pypi.org/legacy/"), "https://pypi.org/");
+        assert_eq!(
+            url("https://test.pypi.org/legacy/"),
+            "https://test.pypi.org/"
+        );
+        assert_eq!(
+            url("http://localhost:8080/legacy/"),
+            "http://localhost:8080/"
+        );
+    }
+
+    #[test]
+    fn gitlab_variables() {
+        assert_eq!(gitlab_variable("pypi"), "PYPI_ID_TOKEN");
+        assert_eq!(gitlab_variable("testpypi"), "TESTPYPI_ID_TOKEN");
+        assert_eq!(gitlab_variable("my-index"), "MY_INDEX_ID_TOKEN");
+    }
+
+    #[test]
+    fn mint_token_errors() {
+        let body = r#"{
+            "message": "Token request failed",
+            "errors": [
+                {
+                    "code": "invalid-publisher",
+                    "description": "valid token, but no corresponding publisher"
+                }
+            ]
+        }"#;
+        let errors: MintTokenErrors = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            errors.to_string(),
+            "Token request failed\n  * `invalid-publisher`: valid token, but no corresponding publisher"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub use crate::lookahead::*;
+pub use crate::script::*;
 pub use crate::source_tree::*;
 pub use crate::sources::*;
 pub use crate::specification::*;
@@ -7,8 +8,10 @@ pub use crate::unnamed::*;
 mod confirm;
 mod lookahead;
 mod pyproject;
+mod script;
 mod source_tree;
 mod sources;
 mod specification;
 mod unnamed;
 pub mod upgrade;
+mod workspace;
@@ -3,9 +3,10 @@ use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+use pep440_rs::{VersionSpecifiers, VersionSpecifiersParseError};
 use pep508_rs::Requirement;
-use pypi_types::LenientRequirement;
-use uv_normalize::{ExtraName, PackageName};
+use pypi_types::{LenientRequirement, LenientVersionSpecifiers};
+use uv_normalize::{ExtraName, GroupName, PackageName};
 
 use crate::ExtrasSpecification;
 
@@ -15,6 +16,106 @@ use crate::ExtrasSpecification;
 pub(crate) struct PyProjectToml {
     /// Project metadata
     pub(crate) project: Option<Project>,
+    /// PEP 735 dependency groups
+    pub(crate) dependency_groups: Option<IndexMap<GroupName, Vec<DependencyGroupSpecifier>>>,
+    /// Tool-specific metadata
+    pub(crate) tool: Option<Tool>,
+}
+
+impl PyProjectToml {
+    /// Collect the requirements for the given dependency groups, expanding any `include-group`
+    /// entries.
+    ///
+    /// Returns an error if a group is not defined, if groups include each other cyclically, or if
+    /// any of the requirements are not valid PEP 508 requirements.
+    pub(crate) fn dependency_group_requirements(
+        &self,
+        groups: &[GroupName],
+    ) -> Result<Vec<Requirement>, DependencyGroupError> {
+        fn inner(
+            group: &GroupName,
+            dependency_groups: &IndexMap<GroupName, Vec<DependencyGroupSpecifier>>,
+            parents: &mut Vec<GroupName>,
+            requirements: &mut Vec<Requirement>,
+        ) -> Result<(), DependencyGroupError> {
+            if parents.contains(group) {
+                return Err(DependencyGroupError::Cycle(group.clone()));
+            }
+            let Some(specifiers) = dependency_groups.get(group) else {
+                return Err(DependencyGroupError::NotFound(group.clone()));
+            };
+            parents.push(group.clone());
+            for specifier in specifiers {
+                match specifier {
+                    DependencyGroupSpecifier::Requirement(requirement) => {
+                        requirements.push(Requirement::from(LenientRequirement::from_str(
+                            requirement,
+                        )?));
+                    }
+                    DependencyGroupSpecifier::IncludeGroup { include_group } => {
+                        inner(include_group, dependency_groups, parents, requirements)?;
+                    }
+                }
+            }
+            parents.pop();
+            Ok(())
+        }
+
+        let mut requirements = Vec::new();
+        if groups.is_empty() {
+            return Ok(requirements);
+        }
+        let empty = IndexMap::new();
+        let dependency_groups = self.dependency_groups.as_ref().unwrap_or(&empty);
+        for group in groups {
+            inner(group, dependency_groups, &mut Vec::new(), &mut requirements)?;
+        }
+        Ok(requirements)
+    }
+}
+
+/// The `[tool]` table of a `pyproject.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tool {
+    pub(crate) uv: Option<ToolUv>,
+}
+
+/// The `[tool.uv]` table of a `pyproject.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ToolUv {
+    pub(crate) workspace: Option<ToolUvWorkspace>,
+}
+
+/// The `[tool.uv.workspace]` table of a `pyproject.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ToolUvWorkspace {
+    /// Glob patterns for the member project directories, relative to the workspace root.
+    pub(crate) members: Option<Vec<String>>,
+    /// Glob patterns for directories to exclude from the members.
+    pub(crate) exclude: Option<Vec<String>>,
+}
+
+/// An entry in a PEP 735 dependency group: either a requirement, or a reference to another group.
+///
+/// See <https://peps.python.org/pep-0735/>.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub(crate) enum DependencyGroupSpecifier {
+    /// A PEP 508 requirement, e.g., `pytest>=8`.
+    Requirement(String),
+    /// A reference to another dependency group, e.g., `{ include-group = "test" }`.
+    #[serde(rename_all = "kebab-case")]
+    IncludeGroup { include_group: GroupName },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum DependencyGroupError {
+    #[error("Dependency group `{0}` is not defined in `pyproject.toml`")]
+    NotFound(GroupName),
+    #[error("Dependency group `{0}` includes itself")]
+    Cycle(GroupName),
+    #[error(transparent)]
+    Pep508(#[from] pep508_rs::Pep508Error),
 }
 
 /// PEP 621 project metadata.
@@ -28,6 +129,8 @@ pub(crate) struct PyProjectToml {
 pub(crate) struct Project {
     /// The name of the project
     pub(crate) name: PackageName,
+    /// The Python versions supported by the project
+    pub(crate) requires_python: Option<String>,
     /// Project dependencies
     pub(crate) dependencies: Option<Vec<String>>,
     /// Optional dependencies
@@ -42,6 +145,8 @@ pub(crate) struct Project {
 pub(crate) struct Pep621Metadata {
     /// The name of the project.
     pub(crate) name: PackageName,
+    /// The Python versions supported by the project, if specified statically.
+    pub(crate) requires_python: Option<VersionSpecifiers>,
     /// The requirements extracted from the project.
     pub(crate) requirements: Vec<Requirement>,
     /// The extras used to collect requirements.
@@ -52,6 +157,8 @@ pub(crate) struct Pep621Metadata {
 pub(crate) enum Pep621Error {
     #[error(transparent)]
     Pep508(#[from] pep508_rs::Pep508Error),
+    #[error(transparent)]
+    VersionSpecifiers(#[from] VersionSpecifiersParseError),
 }
 
 impl Pep621Metadata {
@@ -80,6 +187,13 @@ impl Pep621Metadata {
 
         let name = project.name;
 
+        // Parse out the supported Python versions.
+        let requires_python = project
+            .requires_python
+            .as_deref()
+            .map(|s| LenientVersionSpecifiers::from_str(s).map(VersionSpecifiers::from))
+            .transpose()?;
+
         // Parse out the project requirements.
         let mut requirements = project
             .dependencies
@@ -122,6 +236,7 @@ impl Pep621Metadata {
 
         Ok(Some(Self {
             name,
+            requires_python,
             requirements,
             used_extras,
         }))
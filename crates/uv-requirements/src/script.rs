@@ -0,0 +1,284 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use pep440_rs::{VersionSpecifiers, VersionSpecifiersParseError};
+use pep508_rs::{Pep508Error, Requirement};
+
+/// The inline metadata embedded in a single-file script, as specified in PEP 723.
+///
+/// See: <https://peps.python.org/pep-0723/>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pep723Metadata {
+    /// The requirements of the script.
+    pub dependencies: Vec<Requirement>,
+    /// The Python versions supported by the script.
+    pub requires_python: Option<VersionSpecifiers>,
+}
+
+/// The raw TOML content of a `script` metadata block.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ScriptToml {
+    #[serde(default)]
+    dependencies: Vec<String>,
+    requires_python: Option<String>,
+}
+
+impl Pep723Metadata {
+    /// Read the PEP 723 `script` metadata from the Python script at the given path.
+    ///
+    /// Returns `None` if the script doesn't contain a `script` metadata block.
+    pub fn read(path: impl AsRef<Path>) -> Result<Option<Self>, Pep723Error> {
+        let contents = fs_err::read_to_string(path.as_ref())?;
+        Self::parse(&contents)
+    }
+
+    /// Parse the PEP 723 `script` metadata from the contents of a Python script.
+    ///
+    /// A metadata block starts with a `# /// <type>` line and ends with the last `# ///` line
+    /// before the next line that isn't a comment. Blocks that are never closed are ignored, as are
+    /// blocks of any type other than `script`.
+    pub fn parse(contents: &str) -> Result<Option<Self>, Pep723Error> {
+        let lines = contents.lines().collect::<Vec<_>>();
+
+        let mut toml = None;
+        let mut index = 0;
+        while index < lines.len() {
+            let Some(kind) = lines[index].strip_prefix("# /// ").filter(|kind| {
+                !kind.is_empty() && kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            }) else {
+                index += 1;
+                continue;
+            };
+
+            // Find the closing line, i.e., the last `# ///` in the run of comment lines.
+            let mut end = None;
+            for (offset, line) in lines[index + 1..].iter().enumerate() {
+                if *line != "#" && !line.starts_with("# ") {
+                    break;
+                }
+                if *line == "# ///" {
+                    end = Some(index + 1 + offset);
+                }
+            }
+            let Some(end) = end else {
+                index += 1;
+                continue;
+            };
+
+            if kind == "script" {
+                if toml.is_some() {
+                    return Err(Pep723Error::MultipleBlocks);
+                }
+                toml = Some(
+                    lines[index + 1..end]
+                        .iter()
+                        .map(|line| line.strip_prefix("# ").unwrap_or_default())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+            }
+
+            index = end + 1;
+        }
+
+        let Some(toml) = toml else {
+            return Ok(None);
+        };
+        let ScriptToml {
+            dependencies,
+            requires_python,
+        } = toml::from_str(&toml)?;
+
+        let dependencies = dependencies
+            .iter()
+            .map(|dependency| Requirement::from_str(dependency))
+            .collect::<Result<Vec<_>, _>>()?;
+        let requires_python = requires_python
+            .as_deref()
+            .map(VersionSpecifiers::from_str)
+            .transpose()?;
+
+        Ok(Some(Self {
+            dependencies,
+            requires_python,
+        }))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Pep723Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Script contains multiple `script` metadata blocks")]
+    MultipleBlocks,
+
+    #[error("Failed to parse `script` metadata block")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Invalid requirement in `dependencies`")]
+    Requirement(#[from] Pep508Error),
+
+    #[error("Invalid `requires-python` specifier")]
+    RequiresPython(#[from] VersionSpecifiersParseError),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep440_rs::VersionSpecifiers;
+    use pep508_rs::Requirement;
+
+    use super::{Pep723Error, Pep723Metadata};
+
+    #[test]
+    fn parse() {
+        let contents = "\
+# /// script
+# requires-python = \">=3.11\"
+# dependencies = [
+#   \"requests<3\",
+#   \"rich\",
+# ]
+# ///
+
+import requests
+";
+        let metadata = Pep723Metadata::parse(contents).unwrap().unwrap();
+        assert_eq!(
+            metadata.dependencies,
+            vec![
+                Requirement::from_str("requests<3").unwrap(),
+                Requirement::from_str("rich").unwrap(),
+            ]
+        );
+        assert_eq!(
+            metadata.requires_python,
+            Some(VersionSpecifiers::from_str(">=3.11").unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_without_block() {
+        assert_eq!(Pep723Metadata::parse("import sys\n").unwrap(), None);
+        assert_eq!(Pep723Metadata::parse("").unwrap(), None);
+    }
+
+    #[test]
+    fn parse_empty_lines() {
+        // A bare `#` is an empty line within the block.
+        let contents = "\
+# /// script
+# dependencies = [\"rich\"]
+#
+# requires-python = \">=3.8\"
+# ///
+";
+        let metadata = Pep723Metadata::parse(contents).unwrap().unwrap();
+        assert_eq!(
+            metadata.dependencies,
+            vec![Requirement::from_str("rich").unwrap()]
+        );
+        assert!(metadata.requires_python.is_some());
+    }
+
+    #[test]
+    fn parse_last_closing_line() {
+        // The block ends at the last `# ///` before the next non-comment line, so a `# ///`
+        // within the block (here, in a multi-line string) doesn't close it early.
+        let contents = "\
+# /// script
+# dependencies = [\"rich\"]
+# description = \"\"\"
+# ///
+# \"\"\"
+# ///
+import rich
+";
+        let metadata = Pep723Metadata::parse(contents).unwrap().unwrap();
+        assert_eq!(
+            metadata.dependencies,
+            vec![Requirement::from_str("rich").unwrap()]
+        );
+    }
+
+    #[test]
+    fn parse_unclosed_block() {
+        let contents = "\
+# /// script
+# dependencies = [\"rich\"]
+
+import rich
+";
+        assert_eq!(Pep723Metadata::parse(contents).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_other_block_types() {
+        // Blocks of other types are ignored.
+        let contents = "\
+# /// pyproject
+# [project]
+# name = \"example\"
+# ///
+";
+        assert_eq!(Pep723Metadata::parse(contents).unwrap(), None);
+
+        // Including alongside a `script` block.
+        let contents = "\
+# /// pyproject
+# [project]
+# ///
+
+# /// script
+# dependencies = [\"rich\"]
+# ///
+";
+        let metadata = Pep723Metadata::parse(contents).unwrap().unwrap();
+        assert_eq!(metadata.dependencies.len(), 1);
+    }
+
+    #[test]
+    fn parse_multiple_blocks() {
+        let contents = "\
+# /// script
+# dependencies = [\"rich\"]
+# ///
+
+# /// script
+# dependencies = [\"requests\"]
+# ///
+";
+        assert!(matches!(
+            Pep723Metadata::parse(contents),
+            Err(Pep723Error::MultipleBlocks)
+        ));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let contents = "\
+# /// script
+# dependencies = [\"rich >\"]
+# ///
+";
+        assert!(matches!(
+            Pep723Metadata::parse(contents),
+            Err(Pep723Error::Requirement(_))
+        ));
+
+        let contents = "\
+# /// script
+# requires-python = \"3.8\"
+# ///
+";
+        assert!(matches!(
+            Pep723Metadata::parse(contents),
+            Err(Pep723Error::RequiresPython(_))
+        ));
+    }
+}
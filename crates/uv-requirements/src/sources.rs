@@ -22,6 +22,9 @@ pub enum RequirementsSource {
     SetupPy(PathBuf),
     /// Dependencies were provided via a `setup.cfg` file (e.g., `pip-compile setup.cfg`).
     SetupCfg(PathBuf),
+    /// Pinned distributions were provided via a lockfile, as written by
+    /// `uv pip compile --format lock` (e.g., `uv pip sync requirements.lock`).
+    Lockfile(PathBuf),
 }
 
 impl RequirementsSource {
@@ -34,6 +37,11 @@ impl RequirementsSource {
             Self::SetupPy(path)
         } else if path.ends_with("setup.cfg") {
             Self::SetupCfg(path)
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "lock")
+        {
+            Self::Lockfile(path)
         } else {
             Self::RequirementsTxt(path)
         }
@@ -129,6 +137,11 @@ impl RequirementsSource {
             Self::PyprojectToml(_) | Self::SetupPy(_) | Self::SetupCfg(_)
         )
     }
+
+    /// Returns `true` if the source allows dependency groups to be specified.
+    pub fn allows_groups(&self) -> bool {
+        matches!(self, Self::PyprojectToml(_))
+    }
 }
 
 impl std::fmt::Display for RequirementsSource {
@@ -139,7 +152,8 @@ impl std::fmt::Display for RequirementsSource {
             Self::RequirementsTxt(path)
             | Self::PyprojectToml(path)
             | Self::SetupPy(path)
-            | Self::SetupCfg(path) => {
+            | Self::SetupCfg(path)
+            | Self::Lockfile(path) => {
                 write!(f, "{}", path.simplified_display())
             }
         }
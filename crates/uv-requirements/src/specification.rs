@@ -2,24 +2,28 @@ use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use rustc_hash::FxHashSet;
-use tracing::{instrument, Level};
+use tracing::{debug, instrument, Level};
 
 use cache_key::CanonicalUrl;
 use distribution_types::{FlatIndexLocation, IndexUrl};
+use pep440_rs::VersionSpecifiers;
 use pep508_rs::{Requirement, RequirementsTxtRequirement};
 use requirements_txt::{EditableRequirement, FindLink, RequirementEntry, RequirementsTxt};
 use uv_client::BaseClientBuilder;
 use uv_configuration::{NoBinary, NoBuild};
 use uv_fs::Simplified;
-use uv_normalize::{ExtraName, PackageName};
+use uv_normalize::{ExtraName, GroupName, PackageName};
 
 use crate::pyproject::{Pep621Metadata, PyProjectToml};
+use crate::workspace::Workspace;
 use crate::{ExtrasSpecification, RequirementsSource};
 
 #[derive(Debug, Default)]
 pub struct RequirementsSpecification {
     /// The name of the project specifying requirements.
     pub project: Option<PackageName>,
+    /// The Python versions supported by the project, if specified statically.
+    pub requires_python: Option<VersionSpecifiers>,
     /// The requirements for the project.
     pub requirements: Vec<RequirementEntry>,
     /// The constraints for the project.
@@ -44,6 +48,8 @@ pub struct RequirementsSpecification {
     pub no_binary: NoBinary,
     /// The `--no-build` flags to enforce when selecting distributions.
     pub no_build: NoBuild,
+    /// Whether to allow pre-release versions (`--pre`).
+    pub pre: bool,
 }
 
 impl RequirementsSpecification {
@@ -52,6 +58,7 @@ impl RequirementsSpecification {
     pub async fn from_source(
         source: &RequirementsSource,
         extras: &ExtrasSpecification<'_>,
+        groups: &[GroupName],
         client_builder: &BaseClientBuilder<'_>,
     ) -> Result<Self> {
         Ok(match source {
@@ -60,6 +67,7 @@ impl RequirementsSpecification {
                     .with_context(|| format!("Failed to parse `{name}`"))?;
                 Self {
                     project: None,
+                    requires_python: None,
                     requirements: vec![RequirementEntry {
                         requirement,
                         hashes: vec![],
@@ -75,6 +83,7 @@ impl RequirementsSpecification {
                     find_links: vec![],
                     no_binary: NoBinary::default(),
                     no_build: NoBuild::default(),
+                    pre: false,
                 }
             }
             RequirementsSource::Editable(name) => {
@@ -82,6 +91,7 @@ impl RequirementsSpecification {
                     .with_context(|| format!("Failed to parse `{name}`"))?;
                 Self {
                     project: None,
+                    requires_python: None,
                     requirements: vec![],
                     constraints: vec![],
                     overrides: vec![],
@@ -94,6 +104,7 @@ impl RequirementsSpecification {
                     find_links: vec![],
                     no_binary: NoBinary::default(),
                     no_build: NoBuild::default(),
+                    pre: false,
                 }
             }
             RequirementsSource::RequirementsTxt(path) => {
@@ -101,6 +112,7 @@ impl RequirementsSpecification {
                     RequirementsTxt::parse(path, std::env::current_dir()?, client_builder).await?;
                 Self {
                     project: None,
+                    requires_python: None,
                     requirements: requirements_txt.requirements,
                     constraints: requirements_txt.constraints,
                     overrides: vec![],
@@ -124,6 +136,7 @@ impl RequirementsSpecification {
                         .collect(),
                     no_binary: requirements_txt.no_binary,
                     no_build: requirements_txt.only_binary,
+                    pre: requirements_txt.pre,
                 }
             }
             RequirementsSource::PyprojectToml(path) => {
@@ -131,6 +144,77 @@ impl RequirementsSpecification {
                 let pyproject = toml::from_str::<PyProjectToml>(&contents)
                     .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
 
+                // Collect the requirements from any requested dependency groups. Unlike the
+                // project's dependencies, these are always static.
+                let group_requirements = pyproject
+                    .dependency_group_requirements(groups)
+                    .with_context(|| {
+                        format!(
+                            "Failed to read dependency groups from `{}`",
+                            path.user_display()
+                        )
+                    })?
+                    .into_iter()
+                    .map(|requirement| RequirementEntry {
+                        requirement: RequirementsTxtRequirement::Pep508(requirement),
+                        hashes: vec![],
+                    });
+
+                // If the `pyproject.toml` is a workspace root, discover its members, which are
+                // resolved alongside the root as editables. If it's a workspace member, resolve
+                // the entire workspace instead, such that all members share a single resolution
+                // (and lock).
+                let workspace = if let Some(workspace) = pyproject
+                    .tool
+                    .as_ref()
+                    .and_then(|tool| tool.uv.as_ref())
+                    .and_then(|uv| uv.workspace.as_ref())
+                {
+                    let path = fs_err::canonicalize(path)?;
+                    let root = path.parent().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "The file `{}` appears to be a `pyproject.toml` file, which must be in a directory",
+                            path.user_display()
+                        )
+                    })?;
+                    Some(Workspace::discover(root, workspace).with_context(|| {
+                        format!(
+                            "Failed to discover workspace members from `{}`",
+                            path.user_display()
+                        )
+                    })?)
+                } else {
+                    let path = fs_err::canonicalize(path)?;
+                    if let Some(project) = path.parent() {
+                        if let Some(root) = Workspace::find_root(project).with_context(|| {
+                            format!(
+                                "Failed to discover the workspace containing `{}`",
+                                path.user_display()
+                            )
+                        })? {
+                            debug!(
+                                "Resolving `{}` as a member of the workspace at: {}",
+                                path.user_display(),
+                                root.user_display()
+                            );
+                            return Box::pin(Self::from_source(
+                                &RequirementsSource::PyprojectToml(root),
+                                extras,
+                                groups,
+                                client_builder,
+                            ))
+                            .await;
+                        }
+                    }
+                    None
+                };
+
+                // A `pyproject.toml` without a `[project]` table may only define dependency
+                // groups or workspace members, in which case it needn't be a buildable source
+                // tree.
+                let is_virtual =
+                    pyproject.project.is_none() && (!groups.is_empty() || workspace.is_some());
+
                 // Attempt to read metadata from the `pyproject.toml` directly.
                 //
                 // If we fail to extract the PEP 621 metadata, fall back to treating it as a source
@@ -141,12 +225,13 @@ impl RequirementsSpecification {
                 // For example, Hatch's "Context formatting" API is not compliant with PEP 621, as
                 // it expects dynamic processing by the build backend for the static metadata
                 // fields. See: https://hatch.pypa.io/latest/config/context/
-                if let Some(project) = pyproject
+                let mut spec = if let Some(project) = pyproject
                     .project
                     .and_then(|project| Pep621Metadata::try_from(project, extras).ok().flatten())
                 {
                     Self {
                         project: Some(project.name),
+                        requires_python: project.requires_python,
                         requirements: project
                             .requirements
                             .into_iter()
@@ -166,7 +251,10 @@ impl RequirementsSpecification {
                         find_links: vec![],
                         no_binary: NoBinary::default(),
                         no_build: NoBuild::default(),
+                        pre: false,
                     }
+                } else if is_virtual {
+                    Self::default()
                 } else {
                     let path = fs_err::canonicalize(path)?;
                     let source_tree = path.parent().ok_or_else(|| {
@@ -177,6 +265,7 @@ impl RequirementsSpecification {
                     })?;
                     Self {
                         project: None,
+                        requires_python: None,
                         requirements: vec![],
                         constraints: vec![],
                         overrides: vec![],
@@ -189,8 +278,20 @@ impl RequirementsSpecification {
                         find_links: vec![],
                         no_binary: NoBinary::default(),
                         no_build: NoBuild::default(),
+                        pre: false,
                     }
+                };
+                spec.requirements.extend(group_requirements);
+                if let Some(workspace) = workspace {
+                    spec.editables.extend(workspace.editables());
                 }
+                spec
+            }
+            RequirementsSource::Lockfile(path) => {
+                return Err(anyhow::anyhow!(
+                    "The file `{}` appears to be a lockfile, which can only be installed with `uv pip sync`",
+                    path.user_display()
+                ));
             }
             RequirementsSource::SetupPy(path) | RequirementsSource::SetupCfg(path) => {
                 let path = fs_err::canonicalize(path)?;
@@ -202,6 +303,7 @@ impl RequirementsSpecification {
                 })?;
                 Self {
                     project: None,
+                    requires_python: None,
                     requirements: vec![],
                     constraints: vec![],
                     overrides: vec![],
@@ -214,6 +316,7 @@ impl RequirementsSpecification {
                     find_links: vec![],
                     no_binary: NoBinary::default(),
                     no_build: NoBuild::default(),
+                    pre: false,
                 }
             }
         })
@@ -225,6 +328,7 @@ impl RequirementsSpecification {
         constraints: &[RequirementsSource],
         overrides: &[RequirementsSource],
         extras: &ExtrasSpecification<'_>,
+        groups: &[GroupName],
         client_builder: &BaseClientBuilder<'_>,
     ) -> Result<Self> {
         let mut spec = Self::default();
@@ -233,7 +337,7 @@ impl RequirementsSpecification {
         // A `requirements.txt` can contain a `-c constraints.txt` directive within it, so reading
         // a requirements file can also add constraints.
         for source in requirements {
-            let source = Self::from_source(source, extras, client_builder).await?;
+            let source = Self::from_source(source, extras, groups, client_builder).await?;
             spec.requirements.extend(source.requirements);
             spec.constraints.extend(source.constraints);
             spec.overrides.extend(source.overrides);
@@ -241,9 +345,10 @@ impl RequirementsSpecification {
             spec.editables.extend(source.editables);
             spec.source_trees.extend(source.source_trees);
 
-            // Use the first project name discovered.
+            // Use the first project name (and supported Python versions) discovered.
             if spec.project.is_none() {
                 spec.project = source.project;
+                spec.requires_python = source.requires_python;
             }
 
             if let Some(index_url) = source.index_url {
@@ -261,12 +366,13 @@ impl RequirementsSpecification {
             spec.find_links.extend(source.find_links);
             spec.no_binary.extend(source.no_binary);
             spec.no_build.extend(source.no_build);
+            spec.pre |= source.pre;
         }
 
         // Read all constraints, treating both requirements _and_ constraints as constraints.
         // Overrides are ignored, as are the hashes, as they are not relevant for constraints.
         for source in constraints {
-            let source = Self::from_source(source, extras, client_builder).await?;
+            let source = Self::from_source(source, extras, &[], client_builder).await?;
             for entry in source.requirements {
                 match entry.requirement {
                     RequirementsTxtRequirement::Pep508(requirement) => {
@@ -296,12 +402,13 @@ impl RequirementsSpecification {
             spec.find_links.extend(source.find_links);
             spec.no_binary.extend(source.no_binary);
             spec.no_build.extend(source.no_build);
+            spec.pre |= source.pre;
         }
 
         // Read all overrides, treating both requirements _and_ overrides as overrides.
         // Constraints are ignored.
         for source in overrides {
-            let source = Self::from_source(source, extras, client_builder).await?;
+            let source = Self::from_source(source, extras, &[], client_builder).await?;
             spec.overrides.extend(source.requirements);
             spec.overrides.extend(source.overrides);
 
@@ -320,6 +427,7 @@ impl RequirementsSpecification {
             spec.find_links.extend(source.find_links);
             spec.no_binary.extend(source.no_binary);
             spec.no_build.extend(source.no_build);
+            spec.pre |= source.pre;
         }
 
         Ok(spec)
@@ -335,6 +443,7 @@ impl RequirementsSpecification {
             &[],
             &[],
             &ExtrasSpecification::None,
+            &[],
             client_builder,
         )
         .await
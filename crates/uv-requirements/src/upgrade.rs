@@ -1,16 +1,18 @@
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
+use pep508_rs::MarkerEnvironment;
 use requirements_txt::RequirementsTxt;
 use uv_client::{BaseClientBuilder, Connectivity};
 use uv_configuration::Upgrade;
-use uv_resolver::{Preference, PreferenceError};
+use uv_fs::Simplified;
+use uv_resolver::{Lock, Preference, PreferenceError};
 
 /// Load the preferred requirements from an existing lockfile, applying the upgrade strategy.
 pub async fn read_lockfile(
     output_file: Option<&Path>,
-    upgrade: Upgrade,
+    upgrade: &Upgrade,
 ) -> Result<Vec<Preference>> {
     // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
     let Some(output_file) = output_file
@@ -33,8 +35,44 @@ pub async fn read_lockfile(
         .map(Preference::from_entry)
         .collect::<Result<Vec<_>, PreferenceError>>()?;
 
-    // Apply the upgrade strategy to the requirements.
-    Ok(match upgrade {
+    Ok(apply_upgrade(preferences, upgrade))
+}
+
+/// Load the preferred requirements from a lockfile produced by another tool (a `poetry.lock` or
+/// `Pipfile.lock` file), applying the upgrade strategy.
+///
+/// Only the pins of registry distributions are preserved; see [`Lock::preferences`].
+pub fn read_imported_lockfile(
+    path: &Path,
+    markers: &MarkerEnvironment,
+    upgrade: &Upgrade,
+) -> Result<Vec<Preference>> {
+    if upgrade.is_all() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs_err::read_to_string(path)?;
+
+    // Relative paths within the lockfile are resolved against its directory.
+    let absolute = std::env::current_dir()?.join(path);
+    let lockfile_dir = absolute.parent().unwrap_or(&absolute);
+
+    let lock = match path.file_name().and_then(|name| name.to_str()) {
+        Some("poetry.lock") => Lock::from_poetry_lock(&contents, lockfile_dir, markers),
+        Some("Pipfile.lock") => Lock::from_pipfile_lock(&contents, lockfile_dir, markers, true),
+        _ => bail!(
+            "Unsupported lockfile `{}`: expected a `poetry.lock` or `Pipfile.lock` file",
+            path.user_display()
+        ),
+    }
+    .with_context(|| format!("Failed to import `{}`", path.user_display()))?;
+
+    Ok(apply_upgrade(lock.preferences()?, upgrade))
+}
+
+/// Apply the upgrade strategy to the preferred requirements.
+fn apply_upgrade(preferences: Vec<Preference>, upgrade: &Upgrade) -> Vec<Preference> {
+    match upgrade {
         // Respect all pinned versions from the existing lockfile.
         Upgrade::None => preferences,
         // Ignore all pinned versions from the existing lockfile.
@@ -44,5 +82,5 @@ pub async fn read_lockfile(
             .into_iter()
             .filter(|preference| !packages.contains(preference.name()))
             .collect(),
-    })
+    }
 }
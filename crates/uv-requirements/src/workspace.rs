@@ -0,0 +1,144 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use glob::{glob, GlobError, Pattern, PatternError};
+use rustc_hash::FxHashSet;
+use tracing::debug;
+
+use pep508_rs::VerbatimUrl;
+use requirements_txt::EditableRequirement;
+use uv_fs::Simplified;
+
+use crate::pyproject::{PyProjectToml, ToolUvWorkspace};
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum WorkspaceError {
+    #[error("Invalid workspace member pattern: `{0}`")]
+    Pattern(String, #[source] PatternError),
+    #[error(transparent)]
+    Glob(#[from] GlobError),
+    #[error("Workspace member is missing a `pyproject.toml`: `{}`", _0.user_display())]
+    MissingPyprojectToml(PathBuf),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// A workspace: a root `pyproject.toml` that declares a set of member projects via
+/// `[tool.uv.workspace]`, such that the members can be resolved together.
+///
+/// For example:
+/// ```toml
+/// [tool.uv.workspace]
+/// members = ["packages/*"]
+/// exclude = ["packages/legacy"]
+/// ```
+#[derive(Debug)]
+pub(crate) struct Workspace {
+    /// The directories of the workspace members, in sorted order.
+    members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Discover the members of the workspace rooted at the given directory.
+    ///
+    /// Member patterns are globs relative to the workspace root; every matching directory must
+    /// contain a `pyproject.toml`. Matches for any of the `exclude` patterns are skipped.
+    pub(crate) fn discover(
+        root: &Path,
+        workspace: &ToolUvWorkspace,
+    ) -> Result<Self, WorkspaceError> {
+        // Escape the root, such that any glob metacharacters in its path are matched literally.
+        let root = PathBuf::from(Pattern::escape(&root.to_string_lossy()));
+
+        let exclude = workspace
+            .exclude
+            .iter()
+            .flatten()
+            .map(|pattern| {
+                Pattern::new(&root.join(pattern).to_string_lossy())
+                    .map_err(|err| WorkspaceError::Pattern(pattern.clone(), err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut seen = FxHashSet::default();
+        let mut members = Vec::new();
+        for pattern in workspace.members.iter().flatten() {
+            let absolute = root.join(pattern);
+            let paths = glob(&absolute.to_string_lossy())
+                .map_err(|err| WorkspaceError::Pattern(pattern.clone(), err))?;
+            for path in paths {
+                let path = path?;
+                if !path.is_dir() {
+                    continue;
+                }
+                if exclude.iter().any(|exclude| exclude.matches_path(&path)) {
+                    continue;
+                }
+                if !path.join("pyproject.toml").is_file() {
+                    return Err(WorkspaceError::MissingPyprojectToml(path));
+                }
+                let path = fs_err::canonicalize(&path)?;
+                if seen.insert(path.clone()) {
+                    members.push(path);
+                }
+            }
+        }
+        members.sort();
+
+        Ok(Self { members })
+    }
+
+    /// Find the workspace that the project in the given (canonical) directory is a member of,
+    /// such that the project is resolved, and locked, together with the rest of the workspace.
+    ///
+    /// Returns the path to the `pyproject.toml` at the root of the workspace, i.e., the nearest
+    /// ancestor with a `[tool.uv.workspace]` table whose members include the project.
+    pub(crate) fn find_root(project: &Path) -> Result<Option<PathBuf>, WorkspaceError> {
+        for root in project.ancestors().skip(1) {
+            let pyproject_toml = root.join("pyproject.toml");
+            let contents = match fs_err::read_to_string(&pyproject_toml) {
+                Ok(contents) => contents,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            let pyproject = match toml::from_str::<PyProjectToml>(&contents) {
+                Ok(pyproject) => pyproject,
+                Err(err) => {
+                    debug!(
+                        "Ignoring invalid `pyproject.toml` while searching for a workspace root at `{}`: {err}",
+                        pyproject_toml.user_display()
+                    );
+                    continue;
+                }
+            };
+            let Some(workspace) = pyproject
+                .tool
+                .and_then(|tool| tool.uv)
+                .and_then(|uv| uv.workspace)
+            else {
+                continue;
+            };
+            if Self::discover(root, &workspace)?
+                .members
+                .iter()
+                .any(|member| member == project)
+            {
+                return Ok(Some(pyproject_toml));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return the workspace members as editable requirements, such that any dependencies between
+    /// members are satisfied by the local source trees.
+    pub(crate) fn editables(&self) -> Vec<EditableRequirement> {
+        self.members
+            .iter()
+            .map(|path| EditableRequirement {
+                url: VerbatimUrl::from_path(path).with_given(path.user_display().to_string()),
+                extras: vec![],
+                path: path.clone(),
+            })
+            .collect()
+    }
+}
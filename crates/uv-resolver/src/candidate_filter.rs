@@ -0,0 +1,220 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use distribution_filename::DistFilename;
+use distribution_types::{File, IndexUrl, Rank};
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+
+/// A file that's being considered as a candidate for a package version, as presented to a
+/// [`CandidateFilter`].
+#[derive(Debug, Clone, Copy)]
+pub struct CandidateFile<'a> {
+    /// The name of the package.
+    pub package_name: &'a PackageName,
+    /// The version of the package that the file provides.
+    pub version: &'a Version,
+    /// The parsed filename of the wheel or source distribution.
+    pub filename: &'a DistFilename,
+    /// The file as listed by the index, including its URL, hashes, size, and upload time.
+    pub file: &'a File,
+    /// The index (or `--find-links` location) from which the file was listed.
+    pub index: &'a IndexUrl,
+}
+
+impl CandidateFile<'_> {
+    /// The upload time of the file, in milliseconds since the Unix epoch, if known.
+    pub fn upload_time_utc_ms(&self) -> Option<i64> {
+        self.file.upload_time_utc_ms
+    }
+}
+
+/// The decision of a [`CandidateFilter`] for a single [`CandidateFile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandidateVerdict {
+    /// Leave the file's eligibility and preference unchanged.
+    Accept,
+    /// Accept the file, preferring it over compatible files of the same version with a lower
+    /// rank (and vice versa). Files that aren't re-ranked have a rank of zero.
+    ///
+    /// Ranks only break ties between files that are otherwise equally suitable (e.g., with
+    /// respect to `--require-hashes`); they're considered before wheel tag priority.
+    Rank(i32),
+    /// Reject the file, with the given reason. Rejected files are never selected, and the reason
+    /// is reported if no other file is available for the version.
+    Reject(String),
+}
+
+/// A hook to veto or re-rank the files available for each package version during resolution,
+/// e.g., to enforce organizational rules about which artifacts may be installed.
+///
+/// The filter is consulted for every wheel and source distribution that's listed for a package,
+/// from both registries and `--find-links` locations, before compatibility with the target
+/// environment is assessed. It may be called concurrently, and more than once for the same file.
+///
+/// For decisions that depend on data beyond what the index provides (e.g., provenance metadata
+/// maintained by an internal mirror), implementations can key their own lookups by the file's URL
+/// or hashes.
+pub trait CandidateFilter: Debug + Send + Sync {
+    /// Decide whether (and how strongly) the given file should be considered.
+    fn evaluate(&self, candidate: &CandidateFile) -> CandidateVerdict;
+}
+
+/// The set of [`CandidateFilter`]s to apply during a resolution, in order.
+///
+/// A file is rejected if any filter rejects it; otherwise, its rank is the sum of the ranks
+/// assigned by each filter.
+#[derive(Debug, Default, Clone)]
+pub struct CandidateFilters(Vec<Arc<dyn CandidateFilter>>);
+
+impl CandidateFilters {
+    /// Add a filter to the set.
+    #[must_use]
+    pub fn with(mut self, filter: Arc<dyn CandidateFilter>) -> Self {
+        self.0.push(filter);
+        self
+    }
+
+    /// Returns `true` if no filters are registered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Evaluate the given file against every filter, returning either its combined [`Rank`] or
+    /// the reason for which it was rejected.
+    pub(crate) fn evaluate(&self, candidate: &CandidateFile) -> Result<Rank, String> {
+        let mut rank = 0i32;
+        for filter in &self.0 {
+            match filter.evaluate(candidate) {
+                CandidateVerdict::Accept => {}
+                CandidateVerdict::Rank(value) => rank = rank.saturating_add(value),
+                CandidateVerdict::Reject(reason) => return Err(reason),
+            }
+        }
+        Ok(Rank(rank))
+    }
+}
+
+/// A [`CandidateFilter`] that rejects platform-specific wheels (i.e., those without the `any`
+/// platform tag), such that only pure-Python wheels and source distributions are selected.
+///
+/// Unlike restricting the target [`Tags`](platform_tags::Tags), the policy only applies to the
+/// wheels listed by an index, so wheels built from source distributions remain usable.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoPlatformWheels;
+
+impl CandidateFilter for NoPlatformWheels {
+    fn evaluate(&self, candidate: &CandidateFile) -> CandidateVerdict {
+        match candidate.filename {
+            DistFilename::WheelFilename(filename)
+                if !filename.platform_tag.iter().any(|tag| tag == "any") =>
+            {
+                CandidateVerdict::Reject(
+                    "platform-specific wheels are disabled by `--no-platform-wheels`".to_string(),
+                )
+            }
+            _ => CandidateVerdict::Accept,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use distribution_filename::DistFilename;
+    use distribution_types::{File, FileLocation, IndexUrl, Rank};
+    use pep440_rs::Version;
+    use uv_normalize::PackageName;
+
+    use super::{
+        CandidateFile, CandidateFilter, CandidateFilters, CandidateVerdict, NoPlatformWheels,
+    };
+
+    /// Reject `manylinux1` wheels, and prefer anything uploaded after a cutoff.
+    #[derive(Debug)]
+    struct Policy;
+
+    impl CandidateFilter for Policy {
+        fn evaluate(&self, candidate: &CandidateFile) -> CandidateVerdict {
+            if candidate.file.filename.contains("manylinux1") {
+                CandidateVerdict::Reject("manylinux1 wheels are not allowed".to_string())
+            } else if candidate
+                .upload_time_utc_ms()
+                .is_some_and(|time| time > 1000)
+            {
+                CandidateVerdict::Rank(1)
+            } else {
+                CandidateVerdict::Accept
+            }
+        }
+    }
+
+    fn file(filename: &str, upload_time_utc_ms: Option<i64>) -> File {
+        File {
+            dist_info_metadata: false,
+            filename: filename.to_string(),
+            hashes: vec![],
+            provenance: None,
+            requires_python: None,
+            size: None,
+            upload_time_utc_ms,
+            url: FileLocation::AbsoluteUrl(format!("https://example.com/{filename}")),
+            yanked: None,
+        }
+    }
+
+    fn evaluate(filters: &CandidateFilters, file: &File) -> Result<Rank, String> {
+        let package_name = PackageName::from_str("example").unwrap();
+        let version = Version::from_str("1.0.0").unwrap();
+        let filename = DistFilename::try_from_normalized_filename(&file.filename).unwrap();
+        let index = IndexUrl::from_str("https://example.com/simple").unwrap();
+        filters.evaluate(&CandidateFile {
+            package_name: &package_name,
+            version: &version,
+            filename: &filename,
+            file,
+            index: &index,
+        })
+    }
+
+    #[test]
+    fn filters() {
+        let none = CandidateFilters::default();
+        let policy = CandidateFilters::default().with(Arc::new(Policy));
+        let twice = policy.clone().with(Arc::new(Policy));
+
+        let old = file("example-1.0.0-py3-none-any.whl", Some(10));
+        let new = file("example-1.0.0-py3-none-any.whl", Some(2000));
+        let manylinux1 = file(
+            "example-1.0.0-cp312-cp312-manylinux1_x86_64.whl",
+            Some(2000),
+        );
+
+        assert_eq!(evaluate(&none, &manylinux1), Ok(Rank(0)));
+        assert_eq!(evaluate(&policy, &old), Ok(Rank(0)));
+        assert_eq!(evaluate(&policy, &new), Ok(Rank(1)));
+        assert_eq!(evaluate(&twice, &new), Ok(Rank(2)));
+        assert_eq!(
+            evaluate(&policy, &manylinux1),
+            Err("manylinux1 wheels are not allowed".to_string())
+        );
+    }
+
+    #[test]
+    fn no_platform_wheels() {
+        let filters = CandidateFilters::default().with(Arc::new(NoPlatformWheels));
+
+        let pure = file("example-1.0.0-py3-none-any.whl", None);
+        let sdist = file("example-1.0.0.tar.gz", None);
+        let platform = file("example-1.0.0-cp312-cp312-manylinux_2_17_x86_64.whl", None);
+
+        assert_eq!(evaluate(&filters, &pure), Ok(Rank(0)));
+        assert_eq!(evaluate(&filters, &sdist), Ok(Rank(0)));
+        assert_eq!(
+            evaluate(&filters, &platform),
+            Err("platform-specific wheels are disabled by `--no-platform-wheels`".to_string())
+        );
+    }
+}
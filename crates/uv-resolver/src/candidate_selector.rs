@@ -5,6 +5,7 @@ use distribution_types::{DistributionMetadata, IncompatibleWheel, Name, Prioriti
 use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use tracing::debug;
+use uv_configuration::IndexStrategy;
 use uv_normalize::PackageName;
 use uv_types::InstalledPackagesProvider;
 
@@ -18,6 +19,7 @@ use crate::{Exclusions, Manifest, Options};
 pub(crate) struct CandidateSelector {
     resolution_strategy: ResolutionStrategy,
     prerelease_strategy: PreReleaseStrategy,
+    index_strategy: IndexStrategy,
 }
 
 impl CandidateSelector {
@@ -38,11 +40,11 @@ impl CandidateSelector {
                 manifest,
                 markers,
             ),
+            index_strategy: options.index_strategy,
         }
     }
 
     #[inline]
-    #[allow(dead_code)]
     pub(crate) fn resolution_strategy(&self) -> &ResolutionStrategy {
         &self.resolution_strategy
     }
@@ -66,7 +68,9 @@ impl CandidateSelector {
     ///
     /// Unless present in the provided [`Exclusions`], local distributions from the
     /// [`InstalledPackagesProvider`] are preferred over remote distributions in
-    /// the [`VersionMap`].
+    /// the [`VersionMap`]. When resolving for the lowest compatible version, an installed
+    /// distribution is only preferred if it matches an explicit preference, since any other
+    /// installed version may not be the lowest.
     pub(crate) fn select<'a, InstalledPackages: InstalledPackagesProvider>(
         &'a self,
         package_name: &'a PackageName,
@@ -83,6 +87,7 @@ impl CandidateSelector {
             preferences,
             installed_packages,
             exclusions,
+            self.use_highest_version(package_name),
         ) {
             return Some(preferred);
         }
@@ -92,6 +97,9 @@ impl CandidateSelector {
 
     /// Get a preferred version if one exists. This is the preference from a lockfile or a locally
     /// installed version.
+    ///
+    /// If `prefer_installed` is `false`, locally installed versions are only considered when they
+    /// match a preference.
     fn get_preferred<'a, InstalledPackages: InstalledPackagesProvider>(
         package_name: &'a PackageName,
         range: &Range<Version>,
@@ -99,6 +107,7 @@ impl CandidateSelector {
         preferences: &'a Preferences,
         installed_packages: &'a InstalledPackages,
         exclusions: &'a Exclusions,
+        prefer_installed: bool,
     ) -> Option<Candidate<'a>> {
         // If the package has a preference (e.g., an existing version from an existing lockfile),
         // and the preference satisfies the current range, use that.
@@ -141,7 +150,7 @@ impl CandidateSelector {
         }
 
         // Check for a locally installed distribution that satisfies the range
-        if !exclusions.contains(package_name) {
+        if prefer_installed && !exclusions.contains(package_name) {
             let installed_dists = installed_packages.get_packages(package_name);
             match installed_dists.as_slice() {
                 [] => {}
@@ -208,32 +217,42 @@ impl CandidateSelector {
         let highest = self.use_highest_version(package_name);
         let allow_prerelease = self.allow_prereleases(package_name);
 
-        if highest {
-            version_maps.iter().find_map(|version_map| {
+        let mut candidates = version_maps.iter().filter_map(|version_map| {
+            if highest {
                 Self::select_candidate(
                     version_map.iter().rev(),
                     package_name,
                     range,
                     allow_prerelease,
                 )
-            })
-        } else {
-            version_maps.iter().find_map(|version_map| {
+            } else {
                 Self::select_candidate(version_map.iter(), package_name, range, allow_prerelease)
+            }
+        });
+
+        if self.index_strategy == IndexStrategy::UnsafeBestMatch {
+            // Consider the best candidate from every index, preferring earlier indexes on ties.
+            candidates.reduce(|best, candidate| {
+                let better = if highest {
+                    candidate.version() > best.version()
+                } else {
+                    candidate.version() < best.version()
+                };
+                if better {
+                    candidate
+                } else {
+                    best
+                }
             })
+        } else {
+            candidates.next()
         }
     }
 
     /// By default, we select the latest version, but we also allow using the lowest version instead
     /// to check the lower bounds.
     pub(crate) fn use_highest_version(&self, package_name: &PackageName) -> bool {
-        match &self.resolution_strategy {
-            ResolutionStrategy::Highest => true,
-            ResolutionStrategy::Lowest => false,
-            ResolutionStrategy::LowestDirect(direct_dependencies) => {
-                !direct_dependencies.contains(package_name)
-            }
-        }
+        self.resolution_strategy.prefers_highest(package_name)
     }
 
     /// Select the first-matching [`Candidate`] from a set of candidate versions and files,
@@ -432,3 +451,304 @@ impl DistributionMetadata for Candidate<'_> {
         distribution_types::VersionOrUrl::Version(self.version)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use pubgrub::range::Range;
+
+    use distribution_filename::DistFilename;
+    use distribution_types::{
+        CompatibleDist, Dist, File, FileLocation, Hash, IndexUrl, InstalledDist,
+        InstalledRegistryDist, Name, PrioritizedDist, Rank, WheelCompatibility,
+    };
+    use pep440_rs::Version;
+    use pep508_rs::{MarkerEnvironment, Requirement, StringVersion};
+    use platform_tags::TagPriority;
+    use uv_configuration::IndexStrategy;
+    use uv_normalize::PackageName;
+    use uv_types::InstalledPackagesProvider;
+
+    use crate::preferences::Preferences;
+    use crate::prerelease_mode::PreReleaseStrategy;
+    use crate::resolution_mode::ResolutionStrategy;
+    use crate::version_map::VersionMap;
+    use crate::{Exclusions, Preference};
+
+    use super::{Candidate, CandidateSelector};
+
+    const MIRROR: &str = "https://mirror.example.com/simple";
+    const PYPI: &str = "https://pypi.org/simple";
+
+    fn candidate_selector(
+        resolution_strategy: ResolutionStrategy,
+        index_strategy: IndexStrategy,
+    ) -> CandidateSelector {
+        CandidateSelector {
+            resolution_strategy,
+            prerelease_strategy: PreReleaseStrategy::Disallow,
+            index_strategy,
+        }
+    }
+
+    /// Return a [`VersionMap`] with a pure-Python wheel for each of the given versions of
+    /// `example`, as served by the given index.
+    fn version_map(index: &str, versions: &[&str]) -> VersionMap {
+        let index = IndexUrl::from_str(index).unwrap();
+        versions
+            .iter()
+            .map(|version| {
+                let filename = format!("example-{version}-py3-none-any.whl");
+                let file = File {
+                    dist_info_metadata: false,
+                    filename: filename.clone(),
+                    hashes: vec![],
+                    provenance: None,
+                    requires_python: None,
+                    size: None,
+                    upload_time_utc_ms: None,
+                    url: FileLocation::AbsoluteUrl(format!("{index}/example/{filename}")),
+                    yanked: None,
+                };
+                let dist = Dist::from_registry(
+                    DistFilename::try_from_normalized_filename(&filename).unwrap(),
+                    file,
+                    index.clone(),
+                );
+                let compatibility = WheelCompatibility::Compatible(
+                    Hash::Matched,
+                    Rank(0),
+                    TagPriority::try_from(1).unwrap(),
+                );
+                (
+                    Version::from_str(version).unwrap(),
+                    PrioritizedDist::from_built(dist, vec![], compatibility),
+                )
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into()
+    }
+
+    /// Return the version of the selected candidate, and the index that it was found on.
+    fn selected(candidate: Option<Candidate>) -> Option<(String, String)> {
+        let candidate = candidate?;
+        let Some(CompatibleDist::CompatibleWheel(dist, _)) = candidate.compatible() else {
+            return None;
+        };
+        Some((candidate.version().to_string(), dist.index()?.to_string()))
+    }
+
+    fn example() -> PackageName {
+        PackageName::from_str("example").unwrap()
+    }
+
+    /// A set of installed packages.
+    struct Installed(Vec<InstalledDist>);
+
+    impl Installed {
+        /// Return the packages with `example` installed at the given version.
+        fn example(version: &str) -> Self {
+            Self(vec![InstalledDist::Registry(InstalledRegistryDist {
+                name: example(),
+                version: Version::from_str(version).unwrap(),
+                path: PathBuf::from(format!("example-{version}.dist-info")),
+            })])
+        }
+    }
+
+    impl InstalledPackagesProvider for Installed {
+        fn iter(&self) -> impl Iterator<Item = &InstalledDist> {
+            self.0.iter()
+        }
+
+        fn get_packages(&self, name: &PackageName) -> Vec<&InstalledDist> {
+            self.0.iter().filter(|dist| dist.name() == name).collect()
+        }
+    }
+
+    fn markers() -> MarkerEnvironment {
+        let version = StringVersion::from_str("3.12.1").unwrap();
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: version.clone(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: String::new(),
+            platform_system: "Linux".to_string(),
+            platform_version: String::new(),
+            python_full_version: version,
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: "linux".to_string(),
+        }
+    }
+
+    /// Return the [`Preferences`] for the given pinned requirements.
+    fn preferences_for(pins: &[&str], resolution_strategy: &ResolutionStrategy) -> Preferences {
+        Preferences::from_iter(
+            pins.iter()
+                .map(|pin| Preference::from_requirement(Requirement::from_str(pin).unwrap())),
+            &markers(),
+            resolution_strategy,
+        )
+    }
+
+    /// Return the version of the selected candidate, and whether it's already installed.
+    fn selected_installed(candidate: Option<Candidate>) -> Option<(String, bool)> {
+        let candidate = candidate?;
+        let installed = matches!(candidate.compatible()?, CompatibleDist::InstalledDist(_));
+        Some((candidate.version().to_string(), installed))
+    }
+
+    #[test]
+    fn first_match() {
+        let version_maps = [
+            version_map(MIRROR, &["1.0.0"]),
+            version_map(PYPI, &["1.0.0", "2.0.0"]),
+        ];
+        let selector = candidate_selector(ResolutionStrategy::Highest, IndexStrategy::FirstMatch);
+        let name = example();
+
+        // The first index with a compatible version wins, even if a later index has a newer one.
+        assert_eq!(
+            selected(selector.select_no_preference(&name, &Range::full(), &version_maps)),
+            Some(("1.0.0".to_string(), MIRROR.to_string()))
+        );
+    }
+
+    #[test]
+    fn best_match() {
+        let version_maps = [
+            version_map(MIRROR, &["1.0.0"]),
+            version_map(PYPI, &["1.0.0", "2.0.0"]),
+        ];
+        let selector =
+            candidate_selector(ResolutionStrategy::Highest, IndexStrategy::UnsafeBestMatch);
+        let name = example();
+
+        // The highest version across all indexes wins, and records the index it came from.
+        assert_eq!(
+            selected(selector.select_no_preference(&name, &Range::full(), &version_maps)),
+            Some(("2.0.0".to_string(), PYPI.to_string()))
+        );
+
+        // Within the range, both indexes offer the same best version, so the first index wins.
+        let range = Range::strictly_lower_than(Version::from_str("2.0.0").unwrap());
+        assert_eq!(
+            selected(selector.select_no_preference(&name, &range, &version_maps)),
+            Some(("1.0.0".to_string(), MIRROR.to_string()))
+        );
+    }
+
+    #[test]
+    fn best_match_lowest() {
+        let version_maps = [
+            version_map(MIRROR, &["2.0.0"]),
+            version_map(PYPI, &["1.0.0", "2.0.0"]),
+        ];
+        let selector =
+            candidate_selector(ResolutionStrategy::Lowest, IndexStrategy::UnsafeBestMatch);
+        let name = example();
+
+        // When resolving for the lowest version, the lowest version across all indexes wins.
+        assert_eq!(
+            selected(selector.select_no_preference(&name, &Range::full(), &version_maps)),
+            Some(("1.0.0".to_string(), PYPI.to_string()))
+        );
+
+        // Whereas the first index wins with `unsafe-any-match`.
+        let selector =
+            candidate_selector(ResolutionStrategy::Lowest, IndexStrategy::UnsafeAnyMatch);
+        assert_eq!(
+            selected(selector.select_no_preference(&name, &Range::full(), &version_maps)),
+            Some(("2.0.0".to_string(), MIRROR.to_string()))
+        );
+    }
+
+    #[test]
+    fn prefer_installed() {
+        let version_maps = [version_map(PYPI, &["1.0.0", "2.0.0"])];
+        let selector = candidate_selector(ResolutionStrategy::Highest, IndexStrategy::FirstMatch);
+        let preferences = preferences_for(&[], selector.resolution_strategy());
+        let name = example();
+
+        // An installed version that satisfies the range is kept, even if a newer one exists...
+        assert_eq!(
+            selected_installed(selector.select(
+                &name,
+                &Range::full(),
+                &version_maps,
+                &preferences,
+                &Installed::example("1.0.0"),
+                &Exclusions::default(),
+            )),
+            Some(("1.0.0".to_string(), true))
+        );
+
+        // ...but not if it falls outside of the range...
+        let range = Range::higher_than(Version::from_str("2.0.0").unwrap());
+        assert_eq!(
+            selected_installed(selector.select(
+                &name,
+                &range,
+                &version_maps,
+                &preferences,
+                &Installed::example("1.0.0"),
+                &Exclusions::default(),
+            )),
+            Some(("2.0.0".to_string(), false))
+        );
+
+        // ...or if it's excluded (e.g., by `--upgrade`).
+        assert_eq!(
+            selected_installed(selector.select(
+                &name,
+                &Range::full(),
+                &version_maps,
+                &preferences,
+                &Installed::example("1.0.0"),
+                &Exclusions::All,
+            )),
+            Some(("2.0.0".to_string(), false))
+        );
+    }
+
+    #[test]
+    fn prefer_installed_lowest() {
+        let version_maps = [version_map(PYPI, &["1.0.0", "2.0.0"])];
+        let selector = candidate_selector(ResolutionStrategy::Lowest, IndexStrategy::FirstMatch);
+        let name = example();
+
+        // When resolving for the lowest version, an installed version may not be the lowest, so
+        // it's not preferred on its own...
+        let preferences = preferences_for(&[], selector.resolution_strategy());
+        assert_eq!(
+            selected_installed(selector.select(
+                &name,
+                &Range::full(),
+                &version_maps,
+                &preferences,
+                &Installed::example("2.0.0"),
+                &Exclusions::default(),
+            )),
+            Some(("1.0.0".to_string(), false))
+        );
+
+        // ...but it's still used if it matches a preference.
+        let preferences = preferences_for(&["example==2.0.0"], selector.resolution_strategy());
+        assert_eq!(
+            selected_installed(selector.select(
+                &name,
+                &Range::full(),
+                &version_maps,
+                &preferences,
+                &Installed::example("2.0.0"),
+                &Exclusions::default(),
+            )),
+            Some(("2.0.0".to_string(), true))
+        );
+    }
+}
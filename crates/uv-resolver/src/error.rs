@@ -129,6 +129,7 @@ impl From<pubgrub::error::PubGrubError<UvDependencyProvider>> for ResolveError {
                     index_locations: None,
                     unavailable_packages: FxHashMap::default(),
                     incomplete_packages: FxHashMap::default(),
+                    newest_compatible_versions: FxHashMap::default(),
                 })
             }
             pubgrub::error::PubGrubError::SelfDependency { package, version } => {
@@ -151,6 +152,7 @@ pub struct NoSolutionError {
     index_locations: Option<IndexLocations>,
     unavailable_packages: FxHashMap<PackageName, UnavailablePackage>,
     incomplete_packages: FxHashMap<PackageName, BTreeMap<Version, IncompletePackage>>,
+    newest_compatible_versions: FxHashMap<PackageName, Version>,
 }
 
 impl std::error::Error for NoSolutionError {}
@@ -173,6 +175,7 @@ impl std::fmt::Display for NoSolutionError {
             &self.index_locations,
             &self.unavailable_packages,
             &self.incomplete_packages,
+            &self.newest_compatible_versions,
         ) {
             write!(f, "\n\n{hint}")?;
         }
@@ -182,7 +185,8 @@ impl std::fmt::Display for NoSolutionError {
 }
 
 impl NoSolutionError {
-    /// Update the available versions attached to the error using the given package version index.
+    /// Update the available versions attached to the error using the given package version index,
+    /// along with the newest version of each package that has a compatible distribution.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
     #[must_use]
@@ -193,6 +197,7 @@ impl NoSolutionError {
         package_versions: &OnceMap<PackageName, VersionsResponse>,
     ) -> Self {
         let mut available_versions = IndexMap::default();
+        let mut newest_compatible_versions = FxHashMap::default();
         for package in self.derivation_tree.packages() {
             match package {
                 PubGrubPackage::Root(_) => {}
@@ -223,6 +228,26 @@ impl NoSolutionError {
                                         .extend(
                                             version_map.iter().map(|(version, _)| version.clone()),
                                         );
+
+                                    // Track the newest version with a compatible distribution
+                                    // (e.g., one that satisfies the `requires-python` of the
+                                    // target), to suggest it in hints.
+                                    if let Some((version, _)) =
+                                        version_map.iter().rev().find(|(_, handle)| {
+                                            handle
+                                                .prioritized_dist()
+                                                .is_some_and(|dist| dist.get().is_some())
+                                        })
+                                    {
+                                        newest_compatible_versions
+                                            .entry(name.clone())
+                                            .and_modify(|newest: &mut Version| {
+                                                if *version > *newest {
+                                                    *newest = version.clone();
+                                                }
+                                            })
+                                            .or_insert_with(|| version.clone());
+                                    }
                                 }
                             }
                         }
@@ -231,6 +256,7 @@ impl NoSolutionError {
             }
         }
         self.available_versions = available_versions;
+        self.newest_compatible_versions = newest_compatible_versions;
         self
     }
 
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 /// A timestamp that excludes files newer than it.
 #[derive(Debug, Copy, Clone)]
@@ -24,8 +24,9 @@ impl FromStr for ExcludeNewer {
 
     /// Parse an [`ExcludeNewer`] from a string.
     ///
-    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
-    /// format (e.g., `2006-12-02`).
+    /// Accepts RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`), timestamps without a timezone
+    /// offset, which are interpreted as UTC (e.g., `2006-12-02T02:07:43`), and UTC dates in the
+    /// same format (e.g., `2006-12-02`).
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let date_err = match NaiveDate::from_str(input) {
             Ok(date) => {
@@ -40,6 +41,9 @@ impl FromStr for ExcludeNewer {
             Ok(datetime) => return Ok(Self(datetime.with_timezone(&Utc))),
             Err(err) => err,
         };
+        if let Ok(datetime) = NaiveDateTime::from_str(input) {
+            return Ok(Self(datetime.and_utc()));
+        }
         Err(format!(
             "`{input}` is neither a valid date ({date_err}) nor a valid datetime ({datetime_err})"
         ))
@@ -51,3 +55,24 @@ impl std::fmt::Display for ExcludeNewer {
         self.0.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::ExcludeNewer;
+
+    #[test]
+    fn parse() {
+        let from_date = ExcludeNewer::from_str("2006-12-02").unwrap();
+        assert_eq!(from_date.to_string(), "2006-12-03 00:00:00 UTC");
+
+        let from_rfc3339 = ExcludeNewer::from_str("2006-12-02T02:07:43+01:00").unwrap();
+        assert_eq!(from_rfc3339.to_string(), "2006-12-02 01:07:43 UTC");
+
+        let from_naive = ExcludeNewer::from_str("2006-12-02T02:07:43").unwrap();
+        assert_eq!(from_naive.to_string(), "2006-12-02 02:07:43 UTC");
+
+        assert!(ExcludeNewer::from_str("yesterday").is_err());
+    }
+}
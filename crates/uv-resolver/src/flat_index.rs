@@ -7,8 +7,8 @@ use tracing::instrument;
 use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
 use distribution_types::{
     BuiltDist, Dist, File, Hash, HashPolicy, IncompatibleSource, IncompatibleWheel, IndexUrl,
-    PrioritizedDist, RegistryBuiltDist, RegistrySourceDist, SourceDist, SourceDistCompatibility,
-    WheelCompatibility,
+    PrioritizedDist, Rank, RegistryBuiltDist, RegistrySourceDist, SourceDist,
+    SourceDistCompatibility, WheelCompatibility,
 };
 use pep440_rs::Version;
 use platform_tags::{TagCompatibility, Tags};
@@ -18,12 +18,17 @@ use uv_configuration::{NoBinary, NoBuild};
 use uv_normalize::PackageName;
 use uv_types::HashStrategy;
 
+use crate::candidate_filter::{CandidateFile, CandidateFilters};
+
 /// A set of [`PrioritizedDist`] from a `--find-links` entry, indexed by [`PackageName`]
 /// and [`Version`].
 #[derive(Debug, Clone, Default)]
 pub struct FlatIndex {
     /// The list of [`FlatDistributions`] from the `--find-links` entries, indexed by package name.
     index: FxHashMap<PackageName, FlatDistributions>,
+    /// The files listed by the `--find-links` entries, retained such that the index can be
+    /// rebuilt with [`CandidateFilters`].
+    files: Vec<(DistFilename, File, IndexUrl)>,
     /// Whether any `--find-links` entries could not be resolved due to a lack of network
     /// connectivity.
     offline: bool,
@@ -39,26 +44,67 @@ impl FlatIndex {
         no_build: &NoBuild,
         no_binary: &NoBinary,
     ) -> Self {
-        // Collect compatible distributions.
+        let index = Self::index(
+            &entries.entries,
+            tags,
+            hasher,
+            no_build,
+            no_binary,
+            &CandidateFilters::default(),
+        );
+        Self {
+            index,
+            files: entries.entries,
+            offline: entries.offline,
+        }
+    }
+
+    /// Rebuild the [`FlatIndex`], consulting the given [`CandidateFilters`] for each file.
+    #[must_use]
+    pub fn with_candidate_filters(
+        self,
+        tags: &Tags,
+        hasher: &HashStrategy,
+        no_build: &NoBuild,
+        no_binary: &NoBinary,
+        candidate_filters: &CandidateFilters,
+    ) -> Self {
+        let index = Self::index(
+            &self.files,
+            tags,
+            hasher,
+            no_build,
+            no_binary,
+            candidate_filters,
+        );
+        Self { index, ..self }
+    }
+
+    /// Collect the compatible distributions from the given files.
+    fn index(
+        files: &[(DistFilename, File, IndexUrl)],
+        tags: &Tags,
+        hasher: &HashStrategy,
+        no_build: &NoBuild,
+        no_binary: &NoBinary,
+        candidate_filters: &CandidateFilters,
+    ) -> FxHashMap<PackageName, FlatDistributions> {
         let mut index = FxHashMap::default();
-        for (filename, file, url) in entries.entries {
+        for (filename, file, url) in files {
             let distributions = index.entry(filename.name().clone()).or_default();
             Self::add_file(
                 distributions,
-                file,
-                filename,
+                file.clone(),
+                filename.clone(),
                 tags,
                 hasher,
                 no_build,
                 no_binary,
-                url,
+                candidate_filters,
+                url.clone(),
             );
         }
-
-        // Collect offline entries.
-        let offline = entries.offline;
-
-        Self { index, offline }
+        index
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -70,16 +116,31 @@ impl FlatIndex {
         hasher: &HashStrategy,
         no_build: &NoBuild,
         no_binary: &NoBinary,
+        candidate_filters: &CandidateFilters,
         index: IndexUrl,
     ) {
+        let filtered = candidate_filters.evaluate(&CandidateFile {
+            package_name: filename.name(),
+            version: filename.version(),
+            filename: &filename,
+            file: &file,
+            index: &index,
+        });
+
         // No `requires-python` here: for source distributions, we don't have that information;
         // for wheels, we read it lazily only when selected.
         match filename {
             DistFilename::WheelFilename(filename) => {
                 let version = filename.version.clone();
 
-                let compatibility =
-                    Self::wheel_compatibility(&filename, &file.hashes, tags, hasher, no_binary);
+                let compatibility = Self::wheel_compatibility(
+                    &filename,
+                    &file.hashes,
+                    tags,
+                    hasher,
+                    no_binary,
+                    filtered,
+                );
                 let dist = Dist::Built(BuiltDist::Registry(RegistryBuiltDist {
                     filename,
                     file: Box::new(file),
@@ -95,8 +156,13 @@ impl FlatIndex {
                 }
             }
             DistFilename::SourceDistFilename(filename) => {
-                let compatibility =
-                    Self::source_dist_compatibility(&filename, &file.hashes, hasher, no_build);
+                let compatibility = Self::source_dist_compatibility(
+                    &filename,
+                    &file.hashes,
+                    hasher,
+                    no_build,
+                    filtered,
+                );
                 let dist = Dist::Source(SourceDist::Registry(RegistrySourceDist {
                     filename: filename.clone(),
                     file: Box::new(file),
@@ -119,6 +185,7 @@ impl FlatIndex {
         hashes: &[HashDigest],
         hasher: &HashStrategy,
         no_build: &NoBuild,
+        filtered: Result<Rank, String>,
     ) -> SourceDistCompatibility {
         // Check if source distributions are allowed for this package.
         let no_build = match no_build {
@@ -131,6 +198,14 @@ impl FlatIndex {
             return SourceDistCompatibility::Incompatible(IncompatibleSource::NoBuild);
         }
 
+        // Check if rejected by a candidate filter.
+        let rank = match filtered {
+            Ok(rank) => rank,
+            Err(reason) => {
+                return SourceDistCompatibility::Incompatible(IncompatibleSource::Filtered(reason))
+            }
+        };
+
         // Check if hashes line up
         let hash = if let HashPolicy::Validate(required) = hasher.get_package(&filename.name) {
             if hashes.is_empty() {
@@ -144,7 +219,7 @@ impl FlatIndex {
             Hash::Matched
         };
 
-        SourceDistCompatibility::Compatible(hash)
+        SourceDistCompatibility::Compatible(hash, rank)
     }
 
     fn wheel_compatibility(
@@ -153,6 +228,7 @@ impl FlatIndex {
         tags: &Tags,
         hasher: &HashStrategy,
         no_binary: &NoBinary,
+        filtered: Result<Rank, String>,
     ) -> WheelCompatibility {
         // Check if binaries are allowed for this package.
         let no_binary = match no_binary {
@@ -165,6 +241,14 @@ impl FlatIndex {
             return WheelCompatibility::Incompatible(IncompatibleWheel::NoBinary);
         }
 
+        // Check if rejected by a candidate filter.
+        let rank = match filtered {
+            Ok(rank) => rank,
+            Err(reason) => {
+                return WheelCompatibility::Incompatible(IncompatibleWheel::Filtered(reason))
+            }
+        };
+
         // Determine a compatibility for the wheel based on tags.
         let priority = match filename.compatibility(tags) {
             TagCompatibility::Incompatible(tag) => {
@@ -186,7 +270,7 @@ impl FlatIndex {
             Hash::Matched
         };
 
-        WheelCompatibility::Compatible(hash, priority)
+        WheelCompatibility::Compatible(hash, rank, priority)
     }
 
     /// Get the [`FlatDistributions`] for the given package name.
@@ -194,6 +278,11 @@ impl FlatIndex {
         self.index.get(package_name)
     }
 
+    /// Returns the names of the packages in the index.
+    pub fn package_names(&self) -> impl Iterator<Item = &PackageName> {
+        self.index.keys()
+    }
+
     /// Returns `true` if there are any offline `--find-links` entries.
     pub fn offline(&self) -> bool {
         self.offline
@@ -229,3 +318,174 @@ impl From<FlatDistributions> for BTreeMap<Version, PrioritizedDist> {
         distributions.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use distribution_filename::DistFilename;
+    use distribution_types::{
+        CompatibleDist, File, FileLocation, IncompatibleWheel, IndexUrl, PrioritizedDist,
+    };
+    use platform_tags::Tags;
+    use uv_client::FlatIndexEntries;
+    use uv_configuration::{NoBinary, NoBuild};
+    use uv_normalize::PackageName;
+    use uv_types::HashStrategy;
+
+    use crate::candidate_filter::{
+        CandidateFile, CandidateFilter, CandidateFilters, CandidateVerdict,
+    };
+
+    use super::FlatIndex;
+
+    /// Reject `manylinux1` wheels, and prefer source distributions.
+    #[derive(Debug)]
+    struct Policy;
+
+    impl CandidateFilter for Policy {
+        fn evaluate(&self, candidate: &CandidateFile) -> CandidateVerdict {
+            match candidate.filename {
+                DistFilename::WheelFilename(filename)
+                    if filename
+                        .platform_tag
+                        .iter()
+                        .any(|tag| tag.starts_with("manylinux1")) =>
+                {
+                    CandidateVerdict::Reject("manylinux1 wheels are not allowed".to_string())
+                }
+                DistFilename::WheelFilename(_) => CandidateVerdict::Accept,
+                DistFilename::SourceDistFilename(_) => CandidateVerdict::Rank(1),
+            }
+        }
+    }
+
+    /// Build a [`FlatIndex`] from the given files, then apply the given filters.
+    fn build(filenames: &[&str], candidate_filters: &CandidateFilters) -> FlatIndex {
+        let index = IndexUrl::from_str("https://example.com/simple").unwrap();
+        let entries = filenames
+            .iter()
+            .map(|filename| {
+                let file = File {
+                    dist_info_metadata: false,
+                    filename: (*filename).to_string(),
+                    hashes: vec![],
+                    provenance: None,
+                    requires_python: None,
+                    size: None,
+                    upload_time_utc_ms: None,
+                    url: FileLocation::AbsoluteUrl(format!("https://example.com/{filename}")),
+                    yanked: None,
+                };
+                let filename = DistFilename::try_from_normalized_filename(filename).unwrap();
+                (filename, file, index.clone())
+            })
+            .collect();
+        let tags = Tags::new(vec![
+            (
+                "cp312".to_string(),
+                "cp312".to_string(),
+                "manylinux1_x86_64".to_string(),
+            ),
+            ("py3".to_string(), "none".to_string(), "any".to_string()),
+        ]);
+        FlatIndex::from_entries(
+            FlatIndexEntries {
+                entries,
+                offline: false,
+            },
+            &tags,
+            &HashStrategy::None,
+            &NoBuild::None,
+            &NoBinary::None,
+        )
+        .with_candidate_filters(
+            &tags,
+            &HashStrategy::None,
+            &NoBuild::None,
+            &NoBinary::None,
+            candidate_filters,
+        )
+    }
+
+    /// Return the distributions for the single version of `example` in the index.
+    fn example(flat_index: &FlatIndex) -> &PrioritizedDist {
+        let (_, dist) = flat_index
+            .get(&PackageName::from_str("example").unwrap())
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap();
+        dist
+    }
+
+    /// Return the filename of the distribution that would be selected, if any.
+    fn selected(dist: &PrioritizedDist) -> Option<&str> {
+        match dist.get()? {
+            CompatibleDist::CompatibleWheel(dist, _) | CompatibleDist::SourceDist(dist) => {
+                Some(dist.file().unwrap().filename.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn rejected() {
+        let filters = CandidateFilters::default().with(Arc::new(Policy));
+        let flat_index = build(
+            &["example-1.0.0-cp312-cp312-manylinux1_x86_64.whl"],
+            &filters,
+        );
+        let dist = example(&flat_index);
+
+        assert_eq!(selected(dist), None);
+        assert_eq!(
+            dist.incompatible_wheel()
+                .map(|(_, incompatibility)| incompatibility),
+            Some(&IncompatibleWheel::Filtered(
+                "manylinux1 wheels are not allowed".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejected_fallback() {
+        let files = [
+            "example-1.0.0-cp312-cp312-manylinux1_x86_64.whl",
+            "example-1.0.0-py3-none-any.whl",
+        ];
+
+        // Without the filter, the platform-specific wheel is preferred, by tag priority.
+        let flat_index = build(&files, &CandidateFilters::default());
+        assert_eq!(
+            selected(example(&flat_index)),
+            Some("example-1.0.0-cp312-cp312-manylinux1_x86_64.whl")
+        );
+
+        // With the filter, it's rejected in favor of the pure-Python wheel.
+        let filters = CandidateFilters::default().with(Arc::new(Policy));
+        let flat_index = build(&files, &filters);
+        assert_eq!(
+            selected(example(&flat_index)),
+            Some("example-1.0.0-py3-none-any.whl")
+        );
+    }
+
+    #[test]
+    fn ranked() {
+        let files = ["example-1.0.0-py3-none-any.whl", "example-1.0.0.tar.gz"];
+
+        // Without the filter, the wheel is preferred over the source distribution.
+        let flat_index = build(&files, &CandidateFilters::default());
+        assert_eq!(
+            selected(example(&flat_index)),
+            Some("example-1.0.0-py3-none-any.whl")
+        );
+
+        // With the filter, the source distribution is ranked higher.
+        let filters = CandidateFilters::default().with(Arc::new(Policy));
+        let flat_index = build(&files, &filters);
+        assert_eq!(selected(example(&flat_index)), Some("example-1.0.0.tar.gz"));
+    }
+}
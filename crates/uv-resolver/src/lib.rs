@@ -1,13 +1,22 @@
+pub use candidate_filter::{
+    CandidateFile, CandidateFilter, CandidateFilters, CandidateVerdict, NoPlatformWheels,
+};
 pub use dependency_mode::DependencyMode;
 pub use error::ResolveError;
 pub use exclude_newer::ExcludeNewer;
 pub use exclusions::Exclusions;
 pub use flat_index::FlatIndex;
+pub use license_policy::{
+    LicensePolicy, LicensePolicyMode, LicenseViolation, LicenseViolationReason,
+};
+pub use lock::{Lock, LockError, LockedDist, LockedFile, LockedSource, RequirementsTxtExport};
 pub use manifest::Manifest;
+pub use metrics::{PackageMetrics, ResolverMetrics};
 pub use options::{Options, OptionsBuilder};
 pub use preferences::{Preference, PreferenceError};
 pub use prerelease_mode::PreReleaseMode;
 pub use python_requirement::PythonRequirement;
+pub use report::{ReportedPackage, RequestedBy, ResolutionReport};
 pub use resolution::{AnnotationStyle, Diagnostic, DisplayResolutionGraph, ResolutionGraph};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
@@ -15,10 +24,12 @@ pub use resolver::{
     Reporter as ResolverReporter, Resolver, ResolverProvider, VersionsResponse,
     WheelMetadataResult,
 };
+pub use universal::{ResolutionFork, UniversalError, UniversalPin, UniversalResolution};
 pub use version_map::VersionMap;
-pub use yanks::AllowedYanks;
+pub use yanks::{AllowedYanks, YankedMode};
 
 mod bare;
+mod candidate_filter;
 mod candidate_selector;
 
 mod dependency_mode;
@@ -28,7 +39,10 @@ mod error;
 mod exclude_newer;
 mod exclusions;
 mod flat_index;
+mod license_policy;
+mod lock;
 mod manifest;
+mod metrics;
 mod options;
 mod pins;
 mod preferences;
@@ -36,8 +50,10 @@ mod prerelease_mode;
 mod pubgrub;
 mod python_requirement;
 mod redirect;
+mod report;
 mod resolution;
 mod resolution_mode;
 mod resolver;
+mod universal;
 mod version_map;
 mod yanks;
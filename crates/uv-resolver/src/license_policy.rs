@@ -0,0 +1,519 @@
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+
+use tracing::debug;
+
+use pep440_rs::Version;
+use pypi_types::MetadataLicense;
+use uv_normalize::PackageName;
+
+/// How to respond to packages that violate a [`LicensePolicy`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum LicensePolicyMode {
+    /// Fail if any package violates the policy.
+    #[default]
+    Error,
+    /// Warn about packages that violate the policy, but continue.
+    Warn,
+}
+
+/// A policy that restricts the licenses of the packages in a resolution.
+///
+/// Licenses are read from the `License-Expression`, `License`, and `License ::` classifier fields
+/// of each package's metadata, and compared case-insensitively against the allowed and denied
+/// licenses. SPDX expressions are evaluated in full: a package is compliant if, for some choice
+/// among the alternatives of each `OR`, every license required by an `AND` is allowed and not
+/// denied. A license with an exception (e.g., `GPL-2.0-only WITH Classpath-exception-2.0`) matches
+/// policy entries that name either the license alone or the license together with the exception.
+#[derive(Debug, Default, Clone)]
+pub struct LicensePolicy {
+    /// The licenses that are allowed. If empty, any license that isn't denied is allowed.
+    allow: Vec<String>,
+    /// The licenses that are denied.
+    deny: Vec<String>,
+    /// How to respond to violations.
+    mode: LicensePolicyMode,
+}
+
+impl LicensePolicy {
+    /// Determine the license policy to use for the given arguments.
+    pub fn from_args(allow: Vec<String>, deny: Vec<String>, mode: LicensePolicyMode) -> Self {
+        Self { allow, deny, mode }
+    }
+
+    /// Returns `true` if the policy doesn't restrict any licenses.
+    pub fn is_none(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Return how to respond to violations of the policy.
+    pub fn mode(&self) -> LicensePolicyMode {
+        self.mode
+    }
+
+    /// Evaluate the policy against a package's declared license, returning the reason it's
+    /// violated, if any.
+    pub fn evaluate(&self, license: &MetadataLicense) -> Option<LicenseViolationReason> {
+        let Some(expression) = LicenseExpression::from_metadata(license) else {
+            return if self.allow.is_empty() {
+                None
+            } else {
+                Some(LicenseViolationReason::Unknown)
+            };
+        };
+
+        let offending = self.offending(&expression);
+        if offending.is_empty() {
+            return None;
+        }
+
+        if offending.iter().all(|license| self.is_denied(license)) {
+            Some(LicenseViolationReason::Denied(
+                offending.iter().map(ToString::to_string).collect(),
+            ))
+        } else {
+            Some(LicenseViolationReason::NotAllowed(
+                offending.iter().map(ToString::to_string).collect(),
+            ))
+        }
+    }
+
+    /// Return the licenses that prevent the expression from satisfying the policy, or an empty
+    /// list if it's satisfied.
+    fn offending<'a>(&self, expression: &'a LicenseExpression) -> Vec<&'a License> {
+        match expression {
+            LicenseExpression::License(license) => {
+                if self.is_denied(license) || !self.is_allowed(license) {
+                    vec![license]
+                } else {
+                    vec![]
+                }
+            }
+            LicenseExpression::And(operands) => operands
+                .iter()
+                .flat_map(|operand| self.offending(operand))
+                .collect(),
+            LicenseExpression::Or(operands) => {
+                let mut offending = Vec::new();
+                for operand in operands {
+                    let operand = self.offending(operand);
+                    if operand.is_empty() {
+                        return vec![];
+                    }
+                    offending.extend(operand);
+                }
+                offending
+            }
+        }
+    }
+
+    /// Returns `true` if the license is denied by the policy.
+    fn is_denied(&self, license: &License) -> bool {
+        self.deny.iter().any(|denied| license.matches(denied))
+    }
+
+    /// Returns `true` if the license is allowed by the policy.
+    fn is_allowed(&self, license: &License) -> bool {
+        self.allow.is_empty() || self.allow.iter().any(|allowed| license.matches(allowed))
+    }
+}
+
+/// A single license in a [`LicenseExpression`], e.g., `MIT` or
+/// `GPL-2.0-only WITH Classpath-exception-2.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct License {
+    /// The license identifier (or, for non-SPDX sources, the license name).
+    id: String,
+    /// The license exception, from a `WITH` clause.
+    exception: Option<String>,
+}
+
+impl License {
+    fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            exception: None,
+        }
+    }
+
+    /// Returns `true` if the policy entry names this license, with or without its exception.
+    fn matches(&self, entry: &str) -> bool {
+        if entry.eq_ignore_ascii_case(&self.id) {
+            return true;
+        }
+        let Some(exception) = self.exception.as_deref() else {
+            return false;
+        };
+        let mut words = entry.split_whitespace();
+        matches!(
+            (words.next(), words.next(), words.next(), words.next()),
+            (Some(id), Some(with), Some(entry_exception), None)
+                if id.eq_ignore_ascii_case(&self.id)
+                    && with.eq_ignore_ascii_case("WITH")
+                    && entry_exception.eq_ignore_ascii_case(exception)
+        )
+    }
+}
+
+impl Display for License {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.exception {
+            Some(exception) => write!(f, "{} WITH {exception}", self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// A license expression, as declared by a package.
+///
+/// See: <https://spdx.github.io/spdx-spec/v2.3/SPDX-license-expressions/>
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LicenseExpression {
+    /// A single license.
+    License(License),
+    /// Licenses that must all be satisfied.
+    And(Vec<LicenseExpression>),
+    /// Licenses of which any one must be satisfied.
+    Or(Vec<LicenseExpression>),
+}
+
+impl LicenseExpression {
+    /// Determine the license expression declared in the metadata, if any.
+    ///
+    /// An SPDX expression takes precedence over the free-text `License` field, which takes
+    /// precedence over the `License ::` classifiers (of which any one may be satisfied).
+    fn from_metadata(license: &MetadataLicense) -> Option<Self> {
+        if let Some(expression) = license.expression.as_deref() {
+            return Some(Self::parse(expression).unwrap_or_else(|| {
+                debug!("Treating invalid SPDX license expression as opaque: `{expression}`");
+                Self::License(License::new(expression.trim()))
+            }));
+        }
+        if let Some(text) = license.text.as_deref().and_then(|text| text.lines().next()) {
+            return Some(Self::License(License::new(text.trim())));
+        }
+        let mut classifiers = license
+            .classifiers
+            .iter()
+            .map(|classifier| {
+                let name = classifier.rsplit("::").next().unwrap_or(classifier);
+                Self::License(License::new(name.trim()))
+            })
+            .collect::<Vec<_>>();
+        match classifiers.len() {
+            0 => None,
+            1 => classifiers.pop(),
+            _ => Some(Self::Or(classifiers)),
+        }
+    }
+
+    /// Parse an SPDX license expression, in which `WITH` binds more tightly than `AND`, which
+    /// binds more tightly than `OR`. Returns `None` if the expression is malformed.
+    fn parse(expression: &str) -> Option<Self> {
+        let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+        let mut tokens = spaced.split_whitespace().peekable();
+        let parsed = Self::parse_or(&mut tokens)?;
+        if tokens.next().is_some() {
+            return None;
+        }
+        Some(parsed)
+    }
+
+    fn parse_or<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let mut operands = vec![Self::parse_and(tokens)?];
+        while tokens
+            .next_if(|token| token.eq_ignore_ascii_case("OR"))
+            .is_some()
+        {
+            operands.push(Self::parse_and(tokens)?);
+        }
+        Some(Self::flatten(operands, Self::Or))
+    }
+
+    fn parse_and<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let mut operands = vec![Self::parse_primary(tokens)?];
+        while tokens
+            .next_if(|token| token.eq_ignore_ascii_case("AND"))
+            .is_some()
+        {
+            operands.push(Self::parse_primary(tokens)?);
+        }
+        Some(Self::flatten(operands, Self::And))
+    }
+
+    fn parse_primary<'a>(tokens: &mut Peekable<impl Iterator<Item = &'a str>>) -> Option<Self> {
+        let token = tokens.next()?;
+        if token == "(" {
+            let expression = Self::parse_or(tokens)?;
+            return (tokens.next()? == ")").then_some(expression);
+        }
+        if is_keyword(token) {
+            return None;
+        }
+        let mut license = License::new(token);
+        if tokens
+            .next_if(|token| token.eq_ignore_ascii_case("WITH"))
+            .is_some()
+        {
+            let exception = tokens.next().filter(|token| !is_keyword(token))?;
+            license.exception = Some(exception.to_string());
+        }
+        Some(Self::License(license))
+    }
+
+    /// Combine the operands of an operator, omitting the operator if there's only one operand.
+    fn flatten(mut operands: Vec<Self>, operator: fn(Vec<Self>) -> Self) -> Self {
+        if operands.len() == 1 {
+            operands.pop().unwrap()
+        } else {
+            operator(operands)
+        }
+    }
+}
+
+/// Returns `true` if the token is an SPDX operator or parenthesis, rather than a license.
+fn is_keyword(token: &str) -> bool {
+    matches!(token, "(" | ")")
+        || ["AND", "OR", "WITH"]
+            .iter()
+            .any(|keyword| token.eq_ignore_ascii_case(keyword))
+}
+
+/// A package in a resolution that violates a [`LicensePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseViolation {
+    pub(crate) name: PackageName,
+    pub(crate) version: Version,
+    pub(crate) reason: LicenseViolationReason,
+}
+
+impl LicenseViolation {
+    /// Return the name of the offending package.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Return a human-readable description of the violation.
+    pub fn message(&self) -> String {
+        format!("{}=={}: {}", self.name, self.version, self.reason)
+    }
+}
+
+/// The reason a package violates a [`LicensePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseViolationReason {
+    /// Every declared license is denied.
+    Denied(Vec<String>),
+    /// None of the declared licenses are allowed.
+    NotAllowed(Vec<String>),
+    /// The package doesn't declare a license, but only specific licenses are allowed.
+    Unknown,
+}
+
+impl Display for LicenseViolationReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Denied(licenses) => {
+                write!(f, "{} {} denied", display(licenses), verb(licenses))
+            }
+            Self::NotAllowed(licenses) => {
+                write!(f, "{} {} not allowed", display(licenses), verb(licenses))
+            }
+            Self::Unknown => write!(f, "no license is declared"),
+        }
+    }
+}
+
+/// Format a list of licenses for display, e.g., `` `MIT` and `Apache-2.0` ``.
+fn display(licenses: &[String]) -> String {
+    licenses
+        .iter()
+        .map(|license| format!("`{license}`"))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Return the verb to use when describing the given licenses.
+fn verb(licenses: &[String]) -> &'static str {
+    if licenses.len() == 1 {
+        "is"
+    } else {
+        "are"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pypi_types::MetadataLicense;
+
+    use super::{
+        License, LicenseExpression, LicensePolicy, LicensePolicyMode, LicenseViolationReason,
+    };
+
+    fn expression(expression: &str) -> MetadataLicense {
+        MetadataLicense {
+            expression: Some(expression.to_string()),
+            ..MetadataLicense::default()
+        }
+    }
+
+    #[test]
+    fn deny() {
+        let policy = LicensePolicy::from_args(
+            vec![],
+            vec!["GPL-3.0-only".to_string()],
+            LicensePolicyMode::Error,
+        );
+        assert_eq!(policy.evaluate(&expression("MIT")), None);
+        assert_eq!(
+            policy.evaluate(&expression("GPL-3.0-only")),
+            Some(LicenseViolationReason::Denied(vec![
+                "GPL-3.0-only".to_string()
+            ]))
+        );
+        // Dual-licensed packages are compliant if any alternative is acceptable.
+        assert_eq!(policy.evaluate(&expression("GPL-3.0-only OR MIT")), None);
+        // Packages without a license are only rejected by an allow list.
+        assert_eq!(policy.evaluate(&MetadataLicense::default()), None);
+    }
+
+    #[test]
+    fn allow() {
+        let policy = LicensePolicy::from_args(
+            vec!["MIT".to_string(), "BSD License".to_string()],
+            vec![],
+            LicensePolicyMode::Error,
+        );
+        assert_eq!(policy.evaluate(&expression("mit")), None);
+        assert_eq!(
+            policy.evaluate(&MetadataLicense {
+                classifiers: vec!["License :: OSI Approved :: BSD License".to_string()],
+                ..MetadataLicense::default()
+            }),
+            None
+        );
+        assert_eq!(
+            policy.evaluate(&MetadataLicense {
+                text: Some("Apache 2.0\n\nFull license text...".to_string()),
+                ..MetadataLicense::default()
+            }),
+            Some(LicenseViolationReason::NotAllowed(vec![
+                "Apache 2.0".to_string()
+            ]))
+        );
+        assert_eq!(
+            policy.evaluate(&MetadataLicense::default()),
+            Some(LicenseViolationReason::Unknown)
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let license = |id: &str| LicenseExpression::License(License::new(id));
+
+        assert_eq!(LicenseExpression::parse("MIT"), Some(license("MIT")));
+        assert_eq!(
+            LicenseExpression::parse("(MIT OR Apache-2.0) AND BSD-3-Clause"),
+            Some(LicenseExpression::And(vec![
+                LicenseExpression::Or(vec![license("MIT"), license("Apache-2.0")]),
+                license("BSD-3-Clause"),
+            ]))
+        );
+        // `AND` binds more tightly than `OR`.
+        assert_eq!(
+            LicenseExpression::parse("MIT OR Apache-2.0 AND BSD-3-Clause"),
+            Some(LicenseExpression::Or(vec![
+                license("MIT"),
+                LicenseExpression::And(vec![license("Apache-2.0"), license("BSD-3-Clause")]),
+            ]))
+        );
+        assert_eq!(
+            LicenseExpression::parse("GPL-2.0-only WITH Classpath-exception-2.0 OR MIT"),
+            Some(LicenseExpression::Or(vec![
+                LicenseExpression::License(License {
+                    id: "GPL-2.0-only".to_string(),
+                    exception: Some("Classpath-exception-2.0".to_string()),
+                }),
+                license("MIT"),
+            ]))
+        );
+
+        assert_eq!(LicenseExpression::parse(""), None);
+        assert_eq!(LicenseExpression::parse("(MIT OR Apache-2.0"), None);
+        assert_eq!(LicenseExpression::parse("MIT AND"), None);
+        assert_eq!(LicenseExpression::parse("MIT Apache-2.0"), None);
+        assert_eq!(LicenseExpression::parse("GPL-2.0-only WITH"), None);
+    }
+
+    #[test]
+    fn conjunction() {
+        let policy = LicensePolicy::from_args(
+            vec!["MIT".to_string(), "Apache-2.0".to_string()],
+            vec!["GPL-3.0-only".to_string()],
+            LicensePolicyMode::Error,
+        );
+        assert_eq!(policy.evaluate(&expression("MIT AND Apache-2.0")), None);
+        // Every license required by an `AND` must be acceptable.
+        assert_eq!(
+            policy.evaluate(&expression("MIT AND BSD-3-Clause")),
+            Some(LicenseViolationReason::NotAllowed(vec![
+                "BSD-3-Clause".to_string()
+            ]))
+        );
+        assert_eq!(
+            policy.evaluate(&expression("(MIT OR BSD-3-Clause) AND GPL-3.0-only")),
+            Some(LicenseViolationReason::Denied(vec![
+                "GPL-3.0-only".to_string()
+            ]))
+        );
+        assert_eq!(
+            policy.evaluate(&expression("(BSD-3-Clause OR Apache-2.0) AND MIT")),
+            None
+        );
+        // Malformed expressions are matched verbatim.
+        assert_eq!(
+            policy.evaluate(&expression("MIT AND")),
+            Some(LicenseViolationReason::NotAllowed(vec![
+                "MIT AND".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn exception() {
+        let with = expression("GPL-2.0-only WITH Classpath-exception-2.0");
+
+        let policy = LicensePolicy::from_args(
+            vec!["GPL-2.0-only".to_string()],
+            vec![],
+            LicensePolicyMode::Error,
+        );
+        assert_eq!(policy.evaluate(&with), None);
+
+        let policy = LicensePolicy::from_args(
+            vec!["gpl-2.0-only with classpath-exception-2.0".to_string()],
+            vec![],
+            LicensePolicyMode::Error,
+        );
+        assert_eq!(policy.evaluate(&with), None);
+        assert_eq!(
+            policy.evaluate(&expression("GPL-2.0-only")),
+            Some(LicenseViolationReason::NotAllowed(vec![
+                "GPL-2.0-only".to_string()
+            ]))
+        );
+
+        let policy = LicensePolicy::from_args(
+            vec![],
+            vec!["GPL-2.0-only".to_string()],
+            LicensePolicyMode::Error,
+        );
+        assert_eq!(
+            policy.evaluate(&with),
+            Some(LicenseViolationReason::Denied(vec![
+                "GPL-2.0-only WITH Classpath-exception-2.0".to_string()
+            ]))
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Export a [`Lock`] to the `requirements.txt` format that pip understands.
+
+use std::fmt::{Display, Formatter};
+
+use url::Url;
+
+use super::{Lock, LockError, LockedDist, LockedSource};
+
+/// The URL of the default index, which pip uses unless told otherwise.
+const PYPI_URL: &str = "https://pypi.org/simple";
+
+/// A [`Lock`], formatted as a pip-compatible `requirements.txt` file.
+///
+/// Every distribution is written on its own line, with its marker (if any) and hashes (as
+/// `--hash` options). Registry distributions are pinned with `==`, and other distributions are
+/// written as direct URL requirements (or `-e` for editables). If any distribution comes from an
+/// index other than PyPI, the indexes are written as `--index-url` and `--extra-index-url`
+/// options.
+///
+/// Note that, when any hash is present, pip requires hashes for every requirement and rejects
+/// editables, so a [`Lock`] that contains either can only be exported without hashes.
+#[derive(Debug)]
+pub struct RequirementsTxtExport<'lock> {
+    lock: &'lock Lock,
+    include_hashes: bool,
+}
+
+impl Lock {
+    /// Format the [`Lock`] as a pip-compatible `requirements.txt` file, optionally including the
+    /// `--hash` options for each distribution.
+    ///
+    /// Returns an error if hashes are requested, but a distribution is editable or has no hashes,
+    /// since pip would reject the resulting file.
+    pub fn to_requirements_txt(
+        &self,
+        include_hashes: bool,
+    ) -> Result<RequirementsTxtExport<'_>, LockError> {
+        if include_hashes {
+            for dist in self.distributions() {
+                if dist.editable {
+                    return Err(LockError::EditableWithHashes(dist.name().clone()));
+                }
+                if dist.hashes.is_empty() {
+                    return Err(LockError::MissingHashes(dist.name().clone()));
+                }
+            }
+        }
+        Ok(RequirementsTxtExport {
+            lock: self,
+            include_hashes,
+        })
+    }
+}
+
+impl Display for RequirementsTxtExport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Collect the indexes, with PyPI (if used) first.
+        let mut indexes: Vec<&Url> = Vec::new();
+        for dist in self.lock.distributions() {
+            if let LockedSource::Registry(index) = dist.source() {
+                if !indexes.contains(&index) {
+                    indexes.push(index);
+                }
+            }
+        }
+        indexes.sort_by_key(|index| index.as_str().trim_end_matches('/') != PYPI_URL);
+        if indexes
+            .iter()
+            .any(|index| index.as_str().trim_end_matches('/') != PYPI_URL)
+        {
+            for (position, index) in indexes.iter().enumerate() {
+                if position == 0 {
+                    writeln!(f, "--index-url {index}")?;
+                } else {
+                    writeln!(f, "--extra-index-url {index}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        // Write the distributions, with editables first.
+        let mut distributions = self.lock.distributions().iter().collect::<Vec<_>>();
+        distributions.sort_by_key(|dist| !dist.editable);
+        for dist in distributions {
+            write_requirement(f, dist, self.include_hashes)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the `requirements.txt` line (or lines, with hashes) for a single distribution.
+fn write_requirement(
+    f: &mut Formatter<'_>,
+    dist: &LockedDist,
+    include_hashes: bool,
+) -> std::fmt::Result {
+    let name = dist.name();
+    match dist.source() {
+        LockedSource::Path(url) if dist.editable => write!(f, "-e {url}")?,
+        LockedSource::Registry(_) => write!(f, "{name}=={}", dist.version())?,
+        LockedSource::DirectUrl(url) | LockedSource::Path(url) | LockedSource::Git(url) => {
+            write!(f, "{name} @ {url}")?;
+        }
+    }
+
+    // Editables can't be combined with markers (or hashes, which are rejected up front).
+    if dist.editable {
+        return writeln!(f);
+    }
+
+    if let Some(marker) = &dist.marker {
+        write!(f, " ; {marker}")?;
+    }
+
+    if include_hashes {
+        for hash in &dist.hashes {
+            write!(f, " \\\n    --hash={hash}")?;
+        }
+    }
+
+    writeln!(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lock::{Lock, LockError};
+
+    const MARKERS: &str = r#"version = 1
+
+[markers]
+implementation_name = "cpython"
+implementation_version = "3.12.1"
+os_name = "posix"
+platform_machine = "x86_64"
+platform_python_implementation = "CPython"
+platform_release = "6.5.0"
+platform_system = "Linux"
+platform_version = "Linux"
+python_full_version = "3.12.1"
+python_version = "3.12"
+sys_platform = "linux"
+"#;
+
+    const HASHED: &str = r#"
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+hashes = ["sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8", "sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6"]
+dependencies = ["idna"]
+
+[distribution.source]
+registry = "https://pypi.org/simple"
+
+[distribution.file]
+filename = "anyio-4.3.0-py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl"
+
+[[distribution]]
+name = "colorama"
+version = "0.4.6"
+marker = "sys_platform == 'win32'"
+hashes = ["sha256:08695f5cb7ed6e0531a20572697297273c47b8cae5a63ffc6d6ed5c201be6e44"]
+
+[distribution.source]
+registry = "https://example.com/simple"
+
+[[distribution]]
+name = "idna"
+version = "3.6"
+hashes = ["sha256:c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f"]
+
+[distribution.source]
+direct-url = "https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+"#;
+
+    const EDITABLE: &str = r#"
+[[distribution]]
+name = "example"
+version = "0.1.0"
+editable = true
+
+[distribution.source]
+path = "file:///work/example"
+"#;
+
+    const UNHASHED: &str = r#"
+[[distribution]]
+name = "sniffio"
+version = "1.3.1"
+
+[distribution.source]
+direct-url = "https://files.pythonhosted.org/packages/sniffio-1.3.1-py3-none-any.whl"
+"#;
+
+    fn lock(distributions: &[&str]) -> Lock {
+        Lock::from_toml(&format!("{MARKERS}{}", distributions.concat())).unwrap()
+    }
+
+    #[test]
+    fn requirements_txt() {
+        let lock = lock(&[HASHED]);
+        insta::assert_snapshot!(lock.to_requirements_txt(true).unwrap().to_string(), @r###"
+        --index-url https://pypi.org/simple
+        --extra-index-url https://example.com/simple
+
+        anyio==4.3.0 \
+            --hash=sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8 \
+            --hash=sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6
+        colorama==0.4.6 ; sys_platform == 'win32' \
+            --hash=sha256:08695f5cb7ed6e0531a20572697297273c47b8cae5a63ffc6d6ed5c201be6e44
+        idna @ https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl \
+            --hash=sha256:c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f
+        "###);
+    }
+
+    #[test]
+    fn requirements_txt_without_hashes() {
+        let lock = lock(&[HASHED, EDITABLE, UNHASHED]);
+        insta::assert_snapshot!(lock.to_requirements_txt(false).unwrap().to_string(), @r###"
+        --index-url https://pypi.org/simple
+        --extra-index-url https://example.com/simple
+
+        -e file:///work/example
+        anyio==4.3.0
+        colorama==0.4.6 ; sys_platform == 'win32'
+        idna @ https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl
+        sniffio @ https://files.pythonhosted.org/packages/sniffio-1.3.1-py3-none-any.whl
+        "###);
+    }
+
+    #[test]
+    fn requirements_txt_editable_with_hashes() {
+        let lock = lock(&[HASHED, EDITABLE]);
+        let err = lock.to_requirements_txt(true).unwrap_err();
+        assert!(
+            matches!(&err, LockError::EditableWithHashes(name) if name.as_ref() == "example"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn requirements_txt_missing_hashes() {
+        let lock = lock(&[HASHED, UNHASHED]);
+        let err = lock.to_requirements_txt(true).unwrap_err();
+        assert!(
+            matches!(&err, LockError::MissingHashes(name) if name.as_ref() == "sniffio"),
+            "{err:?}"
+        );
+    }
+}
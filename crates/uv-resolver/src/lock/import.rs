@@ -0,0 +1,688 @@
+//! Import lockfiles produced by other tools (Poetry and Pipenv) into a [`Lock`].
+//!
+//! Neither format records the URL of the artifact selected for a registry distribution, so
+//! imported registry entries carry only a version, hashes, and index. Use [`Lock::preferences`] to
+//! resolve against the imported pins.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use serde::Deserialize;
+use url::Url;
+
+use pep440_rs::Version;
+use pep508_rs::MarkerEnvironment;
+use pypi_types::HashDigest;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user;
+
+use super::{Lock, LockError, LockedDist, LockedSource, LOCK_VERSION};
+
+/// The index assumed for distributions that don't specify one.
+const PYPI_URL: &str = "https://pypi.org/simple";
+
+/// A `poetry.lock` file.
+///
+/// See: <https://python-poetry.org/docs/basic-usage/#committing-your-poetrylock-file-to-version-control>
+#[derive(Debug, Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryPackage>,
+    #[serde(default)]
+    metadata: PoetryMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PoetryMetadata {
+    /// The files for each package, in lockfiles prior to version 2.0. Newer lockfiles list the
+    /// files on each package instead.
+    #[serde(default)]
+    files: BTreeMap<String, Vec<PoetryFile>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PoetryPackage {
+    name: String,
+    version: String,
+    /// Whether a `directory` source is installed in editable mode.
+    #[serde(default)]
+    develop: bool,
+    #[serde(default)]
+    files: Vec<PoetryFile>,
+    /// The package's dependencies, by name. The values (version constraints, markers, etc.) are
+    /// unused, as the locked versions are already known.
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    source: Option<PoetrySource>,
+    /// The markers under which the package is required, if it isn't required everywhere.
+    #[serde(default)]
+    markers: Option<PoetryMarkers>,
+}
+
+/// The markers recorded for a package in a `poetry.lock` file: a single expression or, in
+/// lockfiles that track dependency groups, an expression for each group that requires the
+/// package.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PoetryMarkers {
+    Marker(String),
+    Groups(BTreeMap<String, String>),
+}
+
+impl PoetryMarkers {
+    /// Return the marker expression under which the package is required by any group.
+    fn marker(&self) -> Option<String> {
+        match self {
+            Self::Marker(marker) => Some(marker.clone()),
+            Self::Groups(groups) => {
+                let markers = groups.values().unique().collect::<Vec<_>>();
+                match markers.as_slice() {
+                    [] => None,
+                    [marker] => Some((*marker).clone()),
+                    markers => Some(
+                        markers
+                            .iter()
+                            .map(|marker| format!("({marker})"))
+                            .join(" or "),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PoetryFile {
+    hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct PoetrySource {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default)]
+    resolved_reference: Option<String>,
+    #[serde(default)]
+    subdirectory: Option<String>,
+}
+
+/// A `Pipfile.lock` file.
+///
+/// See: <https://pipenv.pypa.io/en/latest/pipfile.html#pipfile-lock>
+#[derive(Debug, Deserialize)]
+struct PipfileLock {
+    #[serde(rename = "_meta", default)]
+    meta: PipfileMeta,
+    #[serde(default)]
+    default: BTreeMap<String, PipfileEntry>,
+    #[serde(default)]
+    develop: BTreeMap<String, PipfileEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PipfileMeta {
+    #[serde(default)]
+    sources: Vec<PipfileSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfileSource {
+    name: String,
+    url: Url,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipfileEntry {
+    /// The pinned version, as a specifier (e.g., `==1.2.3`).
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    hashes: Vec<String>,
+    #[serde(default)]
+    markers: Option<String>,
+    /// The name of the source (from `_meta.sources`) from which the package was locked.
+    #[serde(default)]
+    index: Option<String>,
+    #[serde(default)]
+    git: Option<String>,
+    #[serde(default, rename = "ref")]
+    reference: Option<String>,
+    #[serde(default)]
+    subdirectory: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    editable: bool,
+}
+
+impl Lock {
+    /// Import a `poetry.lock` file, as resolved against the given markers.
+    ///
+    /// Relative paths (for `file` and `directory` sources) are resolved against `lockfile_dir`,
+    /// which should be absolute.
+    ///
+    /// Each package is guarded by the `markers` that newer versions of Poetry record for it. Poetry
+    /// may also lock multiple versions of a package (e.g., for different Python versions); such
+    /// lockfiles are only supported if every version is guarded by a marker.
+    pub fn from_poetry_lock(
+        contents: &str,
+        lockfile_dir: &Path,
+        markers: &MarkerEnvironment,
+    ) -> Result<Self, LockError> {
+        let poetry: PoetryLock = toml::from_str(contents)?;
+
+        let names = poetry
+            .package
+            .iter()
+            .map(|package| parse_name(&package.name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let markers = poetry
+            .package
+            .iter()
+            .map(|package| package.markers.as_ref().and_then(PoetryMarkers::marker))
+            .collect::<Vec<_>>();
+        if let Some(duplicate) = names
+            .iter()
+            .zip(markers.iter())
+            .filter(|(_, marker)| marker.is_none())
+            .map(|(name, _)| name)
+            .find(|name| names.iter().filter(|other| other == name).count() > 1)
+        {
+            return Err(LockError::DuplicateDistribution(duplicate.clone()));
+        }
+
+        let mut distributions = Vec::with_capacity(poetry.package.len());
+        for ((package, name), marker) in poetry.package.iter().zip(names.iter()).zip(markers) {
+            let version = parse_version(name, &package.version)?;
+
+            let source = match &package.source {
+                None => LockedSource::Registry(Url::parse(PYPI_URL).expect("PyPI URL is valid")),
+                Some(source) => poetry_source(name, source, lockfile_dir)?,
+            };
+
+            let files = if package.files.is_empty() {
+                poetry
+                    .metadata
+                    .files
+                    .get(&package.name)
+                    .map_or(&[][..], Vec::as_slice)
+            } else {
+                package.files.as_slice()
+            };
+            let hashes = parse_hashes(name, files.iter().map(|file| file.hash.as_str()))?;
+
+            // Only retain the dependencies that are present in the lockfile (e.g., omitting
+            // optional dependencies for extras that weren't requested).
+            let dependencies = package
+                .dependencies
+                .keys()
+                .map(|dependency| parse_name(dependency))
+                .filter_ok(|dependency| names.contains(dependency))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .sorted()
+                .dedup()
+                .collect();
+
+            distributions.push(LockedDist {
+                name: name.clone(),
+                version,
+                editable: package.develop && matches!(source, LockedSource::Path(_)),
+                source,
+                marker,
+                file: None,
+                hashes,
+                dependencies,
+                extras: vec![],
+                optional_dependencies: BTreeMap::new(),
+            });
+        }
+
+        Self {
+            version: LOCK_VERSION,
+            tag: None,
+            markers: markers.clone(),
+            distributions,
+        }
+        .validated()
+    }
+
+    /// Import a `Pipfile.lock` file, as resolved against the given markers.
+    ///
+    /// The `develop` packages are included if `include_develop` is set. Relative paths are
+    /// resolved against `lockfile_dir`, which should be absolute.
+    ///
+    /// `Pipfile.lock` doesn't record dependency edges, nor the versions of packages installed
+    /// from Git repositories or local paths; such packages are skipped, with a warning, and should
+    /// be added as requirements instead.
+    pub fn from_pipfile_lock(
+        contents: &str,
+        lockfile_dir: &Path,
+        markers: &MarkerEnvironment,
+        include_develop: bool,
+    ) -> Result<Self, LockError> {
+        let pipfile: PipfileLock = serde_json::from_str(contents)?;
+
+        let entries = pipfile
+            .default
+            .iter()
+            .chain(pipfile.develop.iter().filter(|_| include_develop));
+
+        let mut distributions: Vec<LockedDist> = Vec::new();
+        for (name, entry) in entries {
+            let name = parse_name(name)?;
+
+            let Some(version) = entry.version.as_deref() else {
+                if entry.git.is_some() || entry.path.is_some() || entry.file.is_some() {
+                    warn_user!("Skipping `{name}` from `Pipfile.lock`, as its version isn't recorded; add it as a requirement instead");
+                    continue;
+                }
+                return Err(LockError::MissingVersion(name));
+            };
+            let version = parse_version(
+                &name,
+                version.trim_start_matches("===").trim_start_matches("=="),
+            )?;
+
+            let source = pipfile_source(&name, entry, &pipfile.meta.sources, lockfile_dir)?;
+            let hashes = parse_hashes(&name, entry.hashes.iter().map(String::as_str))?;
+
+            // A package may be locked in both `default` and `develop`; retain the first entry, but
+            // reject conflicting pins.
+            if let Some(existing) = distributions.iter().find(|dist| dist.name == name) {
+                if existing.version != version {
+                    return Err(LockError::DuplicateDistribution(name));
+                }
+                continue;
+            }
+
+            distributions.push(LockedDist {
+                name,
+                version,
+                editable: entry.editable,
+                source,
+                marker: entry.markers.clone(),
+                file: None,
+                hashes,
+                dependencies: vec![],
+                extras: vec![],
+                optional_dependencies: BTreeMap::new(),
+            });
+        }
+
+        Self {
+            version: LOCK_VERSION,
+            tag: None,
+            markers: markers.clone(),
+            distributions,
+        }
+        .validated()
+    }
+}
+
+fn parse_name(name: &str) -> Result<PackageName, LockError> {
+    PackageName::from_str(name).map_err(|err| LockError::Name(name.to_string(), err))
+}
+
+fn parse_version(name: &PackageName, version: &str) -> Result<Version, LockError> {
+    Version::from_str(version).map_err(|_| LockError::Version(name.clone(), version.to_string()))
+}
+
+/// Parse and normalize the hashes of a distribution, formatted as `{algorithm}:{digest}`.
+fn parse_hashes<'a>(
+    name: &PackageName,
+    hashes: impl Iterator<Item = &'a str>,
+) -> Result<Vec<String>, LockError> {
+    let mut hashes = hashes
+        .map(|hash| {
+            HashDigest::from_str(hash)
+                .map(|hash| hash.to_string())
+                .map_err(|err| LockError::Hash(name.clone(), err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    hashes.sort_unstable();
+    hashes.dedup();
+    Ok(hashes)
+}
+
+/// Convert a path or URL from a foreign lockfile to a URL, resolving relative paths against the
+/// lockfile's directory.
+fn path_or_url(name: &PackageName, path: &str, lockfile_dir: &Path) -> Result<Url, LockError> {
+    if let Ok(url) = Url::parse(path) {
+        // Exclude Windows drive letters, which parse as URL schemes.
+        if url.scheme().len() > 1 {
+            return Ok(url);
+        }
+    }
+    Url::from_file_path(lockfile_dir.join(path))
+        .map_err(|()| LockError::Url(name.clone(), path.to_string()))
+}
+
+/// Convert a Git repository URL and revision to a `git+` URL.
+fn git_url(
+    name: &PackageName,
+    repository: &str,
+    reference: Option<&str>,
+    subdirectory: Option<&str>,
+) -> Result<Url, LockError> {
+    let repository = repository.strip_prefix("git+").unwrap_or(repository);
+    let mut url = format!("git+{repository}");
+    if let Some(reference) = reference {
+        url.push('@');
+        url.push_str(reference);
+    }
+    if let Some(subdirectory) = subdirectory {
+        url.push_str("#subdirectory=");
+        url.push_str(subdirectory);
+    }
+    Url::parse(&url).map_err(|_| LockError::Url(name.clone(), url))
+}
+
+fn poetry_source(
+    name: &PackageName,
+    source: &PoetrySource,
+    lockfile_dir: &Path,
+) -> Result<LockedSource, LockError> {
+    match source.kind.as_str() {
+        "legacy" => Ok(LockedSource::Registry(
+            Url::parse(&source.url)
+                .map_err(|_| LockError::Url(name.clone(), source.url.clone()))?,
+        )),
+        "git" => Ok(LockedSource::Git(git_url(
+            name,
+            &source.url,
+            source.resolved_reference.as_deref(),
+            source.subdirectory.as_deref(),
+        )?)),
+        "url" => Ok(LockedSource::DirectUrl(
+            Url::parse(&source.url)
+                .map_err(|_| LockError::Url(name.clone(), source.url.clone()))?,
+        )),
+        "file" | "directory" => Ok(LockedSource::Path(path_or_url(
+            name,
+            &source.url,
+            lockfile_dir,
+        )?)),
+        kind => Err(LockError::UnsupportedSource(name.clone(), kind.to_string())),
+    }
+}
+
+fn pipfile_source(
+    name: &PackageName,
+    entry: &PipfileEntry,
+    sources: &[PipfileSource],
+    lockfile_dir: &Path,
+) -> Result<LockedSource, LockError> {
+    if let Some(repository) = &entry.git {
+        return Ok(LockedSource::Git(git_url(
+            name,
+            repository,
+            entry.reference.as_deref(),
+            entry.subdirectory.as_deref(),
+        )?));
+    }
+    if let Some(path) = &entry.path {
+        return Ok(LockedSource::Path(path_or_url(name, path, lockfile_dir)?));
+    }
+    if let Some(file) = &entry.file {
+        let url = path_or_url(name, file, lockfile_dir)?;
+        return Ok(if url.scheme() == "file" {
+            LockedSource::Path(url)
+        } else {
+            LockedSource::DirectUrl(url)
+        });
+    }
+
+    // Use the named source, or the first source if none is named.
+    let source = match &entry.index {
+        Some(index) => Some(
+            sources
+                .iter()
+                .find(|source| source.name == *index)
+                .ok_or_else(|| LockError::UnsupportedSource(name.clone(), index.clone()))?,
+        ),
+        None => sources.first(),
+    };
+    Ok(LockedSource::Registry(source.map_or_else(
+        || Url::parse(PYPI_URL).expect("PyPI URL is valid"),
+        |source| source.url.clone(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use itertools::Itertools;
+    use pep508_rs::MarkerEnvironment;
+
+    use crate::lock::{Lock, LockError, LockedSource};
+
+    const POETRY_LOCK: &str = r#"
+[[package]]
+name = "anyio"
+version = "4.3.0"
+description = "High level compatibility layer for multiple asynchronous event loop implementations"
+optional = false
+python-versions = ">=3.8"
+files = [
+    {file = "anyio-4.3.0-py3-none-any.whl", hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"},
+]
+
+[package.dependencies]
+idna = ">=2.8"
+trio = {version = ">=0.23", optional = true}
+
+[package.extras]
+trio = ["trio (>=0.23)"]
+
+[[package]]
+name = "idna"
+version = "3.6"
+description = "Internationalized Domain Names in Applications (IDNA)"
+optional = false
+python-versions = ">=3.5"
+files = []
+
+[package.source]
+type = "legacy"
+url = "https://example.com/simple"
+reference = "internal"
+
+[[package]]
+name = "Example_Project"
+version = "0.1.0"
+description = ""
+optional = false
+python-versions = "*"
+develop = true
+files = []
+
+[package.source]
+type = "directory"
+url = "project"
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.8"
+content-hash = "0000"
+"#;
+
+    const PIPFILE_LOCK: &str = r#"{
+    "_meta": {
+        "pipfile-spec": 6,
+        "sources": [
+            {"name": "pypi", "url": "https://pypi.org/simple", "verify_ssl": true},
+            {"name": "internal", "url": "https://example.com/simple", "verify_ssl": true}
+        ]
+    },
+    "default": {
+        "Requests": {
+            "hashes": ["sha256:58cd2187c01e70e6e26505bca751777aa9f2ee0b7f4300988b709f44e013003f"],
+            "markers": "python_version >= '3.7'",
+            "version": "==2.31.0",
+            "index": "internal"
+        },
+        "example": {"editable": true, "path": "."}
+    },
+    "develop": {
+        "pytest": {"hashes": [], "version": "==8.0.0"}
+    }
+}"#;
+
+    fn markers() -> MarkerEnvironment {
+        toml::from_str(
+            r#"
+implementation_name = "cpython"
+implementation_version = "3.12.1"
+os_name = "posix"
+platform_machine = "x86_64"
+platform_python_implementation = "CPython"
+platform_release = "6.5.0"
+platform_system = "Linux"
+platform_version = "Linux"
+python_full_version = "3.12.1"
+python_version = "3.12"
+sys_platform = "linux"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn poetry() {
+        let lock = Lock::from_poetry_lock(POETRY_LOCK, Path::new("/work"), &markers()).unwrap();
+        let names = lock
+            .distributions()
+            .iter()
+            .map(|dist| format!("{}=={}", dist.name(), dist.version()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["anyio==4.3.0", "example-project==0.1.0", "idna==3.6",]
+        );
+
+        let anyio = &lock.distributions()[0];
+        assert_eq!(anyio.dependencies().len(), 1);
+        assert_eq!(anyio.hashes().unwrap().len(), 1);
+        assert!(
+            matches!(anyio.source(), LockedSource::Registry(url) if url.as_str() == "https://pypi.org/simple")
+        );
+
+        let project = &lock.distributions()[1];
+        assert!(project.editable);
+        assert!(matches!(project.source(), LockedSource::Path(_)));
+
+        let idna = &lock.distributions()[2];
+        assert!(
+            matches!(idna.source(), LockedSource::Registry(url) if url.as_str() == "https://example.com/simple")
+        );
+
+        // The registry pins are available as preferences.
+        assert_eq!(lock.preferences().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn poetry_markers() {
+        let contents = r#"
+[[package]]
+name = "colorama"
+version = "0.4.6"
+python-versions = "*"
+groups = ["main", "dev"]
+markers = {main = "sys_platform == 'win32'", dev = "platform_system == 'Windows'"}
+files = []
+
+[[package]]
+name = "tomli"
+version = "2.0.1"
+python-versions = ">=3.7"
+groups = ["dev"]
+markers = "python_version < '3.11'"
+files = []
+
+[[package]]
+name = "typing-extensions"
+version = "4.10.0"
+python-versions = ">=3.8"
+groups = ["main", "dev"]
+markers = {main = "python_version < '3.11'", dev = "python_version < '3.11'"}
+files = []
+"#;
+        let lock = Lock::from_poetry_lock(contents, Path::new("/work"), &markers()).unwrap();
+        let expressions = lock
+            .distributions()
+            .iter()
+            .map(|dist| dist.marker.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            expressions,
+            vec![
+                Some("(platform_system == 'Windows') or (sys_platform == 'win32')"),
+                Some("python_version < '3.11'"),
+                Some("python_version < '3.11'"),
+            ]
+        );
+
+        // None of the packages are required on Linux with Python 3.12.
+        assert!(lock.applicable(&markers()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn poetry_duplicate() {
+        let contents = r#"
+[[package]]
+name = "numpy"
+version = "1.24.4"
+python-versions = ">=3.8"
+markers = "python_version < '3.9'"
+files = []
+
+[[package]]
+name = "numpy"
+version = "1.26.4"
+python-versions = ">=3.9"
+markers = "python_version >= '3.9'"
+files = []
+"#;
+        // The versions are distinguished by their markers.
+        let lock = Lock::from_poetry_lock(contents, Path::new("/work"), &markers()).unwrap();
+        let applicable = lock.applicable(&markers()).unwrap();
+        assert_eq!(applicable.len(), 1);
+        assert_eq!(applicable[0].version().to_string(), "1.26.4");
+
+        // Without the markers, they can't be distinguished.
+        let contents = contents
+            .lines()
+            .filter(|line| !line.starts_with("markers"))
+            .join("\n");
+        let err = Lock::from_poetry_lock(&contents, Path::new("/work"), &markers()).unwrap_err();
+        assert!(matches!(err, LockError::DuplicateDistribution(name) if name.as_ref() == "numpy"));
+    }
+
+    #[test]
+    fn pipfile() {
+        let lock =
+            Lock::from_pipfile_lock(PIPFILE_LOCK, Path::new("/work"), &markers(), false).unwrap();
+        assert_eq!(lock.distributions().len(), 1);
+        let requests = &lock.distributions()[0];
+        assert_eq!(requests.name().as_ref(), "requests");
+        assert_eq!(requests.version().to_string(), "2.31.0");
+        assert!(
+            matches!(requests.source(), LockedSource::Registry(url) if url.as_str() == "https://example.com/simple")
+        );
+        assert!(requests.applies_to(&markers()).unwrap());
+
+        let lock =
+            Lock::from_pipfile_lock(PIPFILE_LOCK, Path::new("/work"), &markers(), true).unwrap();
+        assert_eq!(lock.distributions().len(), 2);
+    }
+}
@@ -0,0 +1,899 @@
+//! A serializable lockfile for a completed resolution.
+//!
+//! A [`Lock`] captures everything needed to reproduce a [`ResolutionGraph`] without re-running
+//! the resolver: the pinned version and source of every distribution, the specific artifact that
+//! was selected (along with its hashes), the dependency edges between distributions, and the
+//! marker environment that the resolution was performed against.
+//!
+//! A lockfile may also cover multiple platforms, in which case individual distributions carry a
+//! marker expression. When installing, only the entries whose markers apply to the target
+//! environment are selected.
+//!
+//! Lockfiles produced by other tools (`poetry.lock` and `Pipfile.lock`) can be imported, such
+//! that their pins can be preserved when migrating; see [`Lock::from_poetry_lock`] and
+//! [`Lock::from_pipfile_lock`]. Conversely, a lockfile can be exported to a hashed
+//! `requirements.txt` for consumption by pip; see [`Lock::to_requirements_txt`].
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use distribution_filename::{DistFilename, WheelFilename};
+use distribution_types::{
+    BuiltDist, Dist, File, FileLocation, IndexUrl, Name, Resolution, ResolvedDist, SourceDist,
+};
+use pep440_rs::{Version, VersionSpecifier};
+use pep508_rs::{
+    MarkerEnvironment, MarkerTree, Pep508Error, Requirement, VerbatimUrl, VersionOrUrl,
+};
+use platform_tags::Tags;
+use pypi_types::{HashDigest, HashError};
+use uv_normalize::{ExtraName, InvalidNameError, PackageName};
+
+use crate::{Preference, ResolutionGraph, UniversalResolution};
+
+pub use export::RequirementsTxtExport;
+
+mod export;
+mod import;
+
+/// The current version of the lockfile format.
+const LOCK_VERSION: u32 = 1;
+
+#[derive(thiserror::Error, Debug)]
+pub enum LockError {
+    #[error("Failed to serialize lockfile")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("Failed to parse lockfile")]
+    Deserialize(#[from] toml::de::Error),
+
+    #[error("Failed to parse lockfile")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unsupported lockfile version: {0} (expected {LOCK_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("Distribution `{0}` is already installed and can't be recorded in a lockfile")]
+    Installed(PackageName),
+
+    #[error("Distribution `{0}` has no resolved version")]
+    MissingVersion(PackageName),
+
+    #[error("Invalid package name: `{0}`")]
+    Name(String, #[source] InvalidNameError),
+
+    #[error("Invalid version for `{0}`: `{1}`")]
+    Version(PackageName, String),
+
+    #[error("Unsupported source for `{0}`: `{1}`")]
+    UnsupportedSource(PackageName, String),
+
+    #[error("Multiple versions of `{0}` are locked, but they can't be distinguished by a marker")]
+    DuplicateDistribution(PackageName),
+
+    #[error("Failed to resolve the URL for `{0}`")]
+    JoinRelativeUrl(PackageName, #[source] pypi_types::JoinRelativeError),
+
+    #[error("Invalid URL for `{0}`: {1}")]
+    Url(PackageName, String),
+
+    #[error("Invalid filename for `{0}`: {1}")]
+    Filename(PackageName, String),
+
+    #[error("Invalid hash for `{0}`")]
+    Hash(PackageName, #[source] HashError),
+
+    #[error("Failed to reconstruct distribution `{0}`")]
+    Dist(PackageName, #[source] distribution_types::Error),
+
+    #[error("Invalid marker for `{0}`")]
+    Marker(PackageName, #[source] Box<Pep508Error>),
+
+    #[error("Multiple locked entries for `{0}` apply to the target environment")]
+    AmbiguousDistribution(PackageName),
+
+    #[error("Dependency `{1}` of `{0}` is not present in the lockfile")]
+    MissingDependency(PackageName, PackageName),
+
+    #[error("The locked wheel `{0}` is not compatible with the current platform")]
+    IncompatibleWheel(WheelFilename),
+
+    #[error("Invalid tag: `{0}` (expected `{{python}}-{{abi}}-{{platform}}`)")]
+    Tag(String),
+
+    #[error(
+        "The lockfile was generated for `{0}`, which is not supported by the current platform"
+    )]
+    IncompatibleTag(String),
+
+    #[error("Distribution `{0}` is editable, and can't be exported with hashes")]
+    EditableWithHashes(PackageName),
+
+    #[error("Distribution `{0}` has no hashes, and can't be exported with hashes")]
+    MissingHashes(PackageName),
+}
+
+/// A lockfile, recording a fully-pinned resolution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Lock {
+    /// The version of the lockfile format.
+    version: u32,
+    /// The most-preferred wheel tag of the environment against which the resolution was
+    /// performed (e.g., `cp312-cp312-manylinux_2_17_x86_64`), if it was performed for a single
+    /// environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    /// The marker environment against which the resolution was performed.
+    markers: MarkerEnvironment,
+    /// The pinned distributions, sorted by name. A name may appear more than once, provided
+    /// that each entry is guarded by a marker.
+    #[serde(rename = "distribution", default)]
+    distributions: Vec<LockedDist>,
+}
+
+/// A single pinned distribution within a [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedDist {
+    /// The name of the distribution.
+    name: PackageName,
+    /// The resolved version of the distribution.
+    version: Version,
+    /// Where the distribution is sourced from.
+    source: LockedSource,
+    /// The marker expression that determines the environments to which the entry applies. If
+    /// absent, the entry applies to all environments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
+    /// For registry distributions, the specific artifact that was selected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<LockedFile>,
+    /// Whether the distribution is installed in editable mode.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    editable: bool,
+    /// The known hashes of the distribution, formatted as `{algorithm}:{digest}`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<String>,
+    /// The names of the distributions that this distribution depends on, including those
+    /// introduced by its enabled extras.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<PackageName>,
+    /// The extras that were enabled for the distribution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extras: Vec<ExtraName>,
+    /// The names of the distributions introduced by each enabled extra.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    optional_dependencies: BTreeMap<ExtraName, Vec<PackageName>>,
+}
+
+/// The source of a [`LockedDist`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockedSource {
+    /// A distribution from a package index, identified by the index URL.
+    Registry(Url),
+    /// A distribution at a direct URL (e.g., an archive on a remote server).
+    DirectUrl(Url),
+    /// A distribution at a local path.
+    Path(Url),
+    /// A distribution in a Git repository, pinned to a precise commit.
+    Git(Url),
+}
+
+/// The artifact selected for a registry distribution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedFile {
+    /// The filename of the artifact.
+    filename: String,
+    /// The absolute URL of the artifact.
+    url: Url,
+}
+
+impl Lock {
+    /// Create a [`Lock`] from a [`ResolutionGraph`], resolved against the given markers.
+    ///
+    /// The resolution is only known to be valid for the given markers, so its entries aren't
+    /// guarded by markers of their own; see [`Lock::from_universal_resolution`] to lock for
+    /// multiple environments.
+    pub fn from_resolution_graph(
+        graph: &ResolutionGraph,
+        markers: &MarkerEnvironment,
+    ) -> Result<Self, LockError> {
+        let petgraph = graph.petgraph();
+
+        let mut distributions = Vec::with_capacity(petgraph.node_count());
+        for index in petgraph.node_indices() {
+            let ResolvedDist::Installable(dist) = &petgraph[index] else {
+                return Err(LockError::Installed(petgraph[index].name().clone()));
+            };
+            let name = dist.name().clone();
+
+            let version = graph
+                .version(&name)
+                .cloned()
+                .ok_or_else(|| LockError::MissingVersion(name.clone()))?;
+
+            let (source, file) = match dist {
+                Dist::Built(BuiltDist::Registry(wheel)) => (
+                    LockedSource::Registry(wheel.index.url().clone()),
+                    Some(LockedFile::from_file(&name, &wheel.file)?),
+                ),
+                Dist::Source(SourceDist::Registry(sdist)) => (
+                    LockedSource::Registry(sdist.index.url().clone()),
+                    Some(LockedFile::from_file(&name, &sdist.file)?),
+                ),
+                Dist::Built(BuiltDist::DirectUrl(wheel)) => {
+                    (LockedSource::DirectUrl(wheel.url.to_url()), None)
+                }
+                Dist::Source(SourceDist::DirectUrl(sdist)) => {
+                    (LockedSource::DirectUrl(sdist.url.to_url()), None)
+                }
+                Dist::Built(BuiltDist::Path(wheel)) => {
+                    (LockedSource::Path(wheel.url.to_url()), None)
+                }
+                Dist::Source(SourceDist::Path(sdist)) => {
+                    (LockedSource::Path(sdist.url.to_url()), None)
+                }
+                Dist::Source(SourceDist::Git(sdist)) => {
+                    (LockedSource::Git(sdist.url.to_url()), None)
+                }
+            };
+
+            // Prefer the hashes recorded on the resolution (which include any hashes preserved
+            // from an existing lockfile), falling back to those on the selected file.
+            let hashes = if graph.hashes_for(&name).is_empty() {
+                dist.file().map_or(&[][..], |file| file.hashes.as_slice())
+            } else {
+                graph.hashes_for(&name)
+            };
+            let mut hashes = hashes.iter().map(ToString::to_string).collect::<Vec<_>>();
+            hashes.sort_unstable();
+            hashes.dedup();
+
+            let mut dependencies = petgraph
+                .neighbors_directed(index, Direction::Outgoing)
+                .map(|dependency| petgraph[dependency].name().clone())
+                .collect::<Vec<_>>();
+            dependencies.sort_unstable();
+            dependencies.dedup();
+
+            let extras = graph.extras_for(&name).to_vec();
+            let optional_dependencies = graph
+                .extra_dependencies_for(&name)
+                .cloned()
+                .unwrap_or_default();
+
+            distributions.push(LockedDist {
+                name,
+                version,
+                source,
+                marker: None,
+                file,
+                editable: dist.is_editable(),
+                hashes,
+                dependencies,
+                extras,
+                optional_dependencies,
+            });
+        }
+        distributions.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            version: LOCK_VERSION,
+            tag: None,
+            markers: markers.clone(),
+            distributions,
+        })
+    }
+
+    /// Record the tags against which the resolution was performed, such that the [`Lock`] is
+    /// rejected when installing into an environment that doesn't support them.
+    #[must_use]
+    pub fn with_tags(self, tags: &Tags) -> Self {
+        Self {
+            tag: tags
+                .best()
+                .map(|(python, abi, platform)| format!("{python}-{abi}-{platform}")),
+            ..self
+        }
+    }
+
+    /// Create a [`Lock`] from a [`UniversalResolution`], spanning each of its target environments.
+    ///
+    /// Entries that are locked identically in every environment apply unconditionally, while the
+    /// others are guarded by a marker that selects the environments in which they apply. The
+    /// marker environment of the first fork is recorded as that of the lock.
+    pub fn from_universal_resolution(resolution: &UniversalResolution) -> Result<Self, LockError> {
+        let forks = resolution.forks();
+
+        // Map each distinct entry to the forks in which it appears.
+        let mut entries: Vec<(LockedDist, Vec<usize>)> = Vec::new();
+        for (index, fork) in forks.iter().enumerate() {
+            let lock = Self::from_resolution_graph(fork.graph(), fork.markers())?;
+            for dist in lock.distributions {
+                if let Some((_, indices)) = entries.iter_mut().find(|(entry, _)| *entry == dist) {
+                    indices.push(index);
+                } else {
+                    entries.push((dist, vec![index]));
+                }
+            }
+        }
+
+        let distributions = entries
+            .into_iter()
+            .map(|(dist, indices)| LockedDist {
+                marker: resolution
+                    .marker_for(&indices)
+                    .map(|marker| marker.to_string()),
+                ..dist
+            })
+            .collect();
+
+        Self {
+            version: LOCK_VERSION,
+            tag: None,
+            markers: forks
+                .first()
+                .expect("a universal resolution has at least one fork")
+                .markers()
+                .clone(),
+            distributions,
+        }
+        .validated()
+    }
+
+    /// Parse a [`Lock`] from its TOML representation.
+    pub fn from_toml(contents: &str) -> Result<Self, LockError> {
+        let lock: Self = toml::from_str(contents)?;
+        if lock.version != LOCK_VERSION {
+            return Err(LockError::UnsupportedVersion(lock.version));
+        }
+        lock.validated()
+    }
+
+    /// Sort the distributions by name, and validate their markers and dependency edges.
+    fn validated(mut self) -> Result<Self, LockError> {
+        let lock = &mut self;
+
+        // Use a stable sort, such that entries that share a name retain their relative order.
+        lock.distributions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        if let Some(tag) = &lock.tag {
+            split_tag(tag)?;
+        }
+
+        for dist in &lock.distributions {
+            dist.marker()?;
+        }
+
+        // Every dependency edge must point to a distribution in the lockfile.
+        for dist in &lock.distributions {
+            for dependency in dist
+                .dependencies
+                .iter()
+                .chain(dist.optional_dependencies.values().flatten())
+            {
+                if lock.find(dependency).is_none() {
+                    return Err(LockError::MissingDependency(
+                        dist.name.clone(),
+                        dependency.clone(),
+                    ));
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Serialize the [`Lock`] to TOML.
+    pub fn to_toml(&self) -> Result<String, LockError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Return the marker environment against which the resolution was performed.
+    pub fn markers(&self) -> &MarkerEnvironment {
+        &self.markers
+    }
+
+    /// Return the pinned distributions in the lockfile.
+    pub fn distributions(&self) -> &[LockedDist] {
+        &self.distributions
+    }
+
+    /// Return the first pinned distribution with the given name, if any.
+    ///
+    /// If the lockfile contains multiple marker-annotated entries for the name, use
+    /// [`Lock::applicable`] to select the entry for a given environment.
+    pub fn find(&self, name: &PackageName) -> Option<&LockedDist> {
+        let index = self.distributions.partition_point(|dist| dist.name < *name);
+        self.distributions
+            .get(index)
+            .filter(|dist| dist.name == *name)
+    }
+
+    /// Return the pinned distributions that apply to the given marker environment.
+    ///
+    /// Returns an error if more than one entry for the same distribution applies.
+    pub fn applicable(&self, markers: &MarkerEnvironment) -> Result<Vec<&LockedDist>, LockError> {
+        let mut applicable: Vec<&LockedDist> = Vec::with_capacity(self.distributions.len());
+        for dist in &self.distributions {
+            if !dist.applies_to(markers)? {
+                continue;
+            }
+            // The distributions are sorted by name, so any conflicting entry is the last one.
+            if applicable.last().is_some_and(|last| last.name == dist.name) {
+                return Err(LockError::AmbiguousDistribution(dist.name.clone()));
+            }
+            applicable.push(dist);
+        }
+        Ok(applicable)
+    }
+
+    /// Return the pins for the registry distributions in the lockfile, as [`Preference`]s to
+    /// provide to the resolver (e.g., via [`crate::Manifest`]), along with their hashes.
+    ///
+    /// Lockfiles imported from other tools don't record the artifact that was selected for each
+    /// registry distribution, so they can't be installed directly; resolving with their pins as
+    /// preferences retains the locked versions wherever they remain compatible.
+    pub fn preferences(&self) -> Result<Vec<Preference>, LockError> {
+        self.distributions
+            .iter()
+            .filter(|dist| matches!(dist.source, LockedSource::Registry(_)))
+            .map(|dist| {
+                let requirement = Requirement {
+                    name: dist.name.clone(),
+                    extras: vec![],
+                    version_or_url: Some(VersionOrUrl::VersionSpecifier(
+                        [VersionSpecifier::equals_version(dist.version.clone())]
+                            .into_iter()
+                            .collect(),
+                    )),
+                    marker: dist.marker()?,
+                };
+                Ok(Preference::from_pin(requirement, dist.hashes()?))
+            })
+            .collect()
+    }
+
+    /// Convert the [`Lock`] back into a [`Resolution`] for the given environment, such that it
+    /// can be installed without re-running the resolver.
+    ///
+    /// Only the entries whose markers apply to the given marker environment are included.
+    /// Returns an error if the lockfile was generated for tags that aren't among the given tags,
+    /// or if any selected wheel is incompatible with them.
+    pub fn to_resolution(
+        &self,
+        markers: &MarkerEnvironment,
+        tags: &Tags,
+    ) -> Result<Resolution, LockError> {
+        if let Some(tag) = &self.tag {
+            let (python, abi, platform) = split_tag(tag)?;
+            if !tags.is_compatible(&[python], &[abi], &[platform]) {
+                return Err(LockError::IncompatibleTag(tag.clone()));
+            }
+        }
+
+        let mut packages = FxHashMap::default();
+        for dist in self.applicable(markers)? {
+            let resolved = dist.to_dist()?;
+            if let Dist::Built(built) = &resolved {
+                let filename = match built {
+                    BuiltDist::Registry(wheel) => &wheel.filename,
+                    BuiltDist::DirectUrl(wheel) => &wheel.filename,
+                    BuiltDist::Path(wheel) => &wheel.filename,
+                };
+                if !filename.is_compatible(tags) {
+                    return Err(LockError::IncompatibleWheel(filename.clone()));
+                }
+            }
+            packages.insert(dist.name.clone(), ResolvedDist::Installable(resolved));
+        }
+        Ok(Resolution::new(packages))
+    }
+}
+
+/// Split a `{python}-{abi}-{platform}` tag into its components.
+fn split_tag(tag: &str) -> Result<(String, String, String), LockError> {
+    let mut parts = tag.splitn(3, '-');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(python), Some(abi), Some(platform))
+            if !python.is_empty() && !abi.is_empty() && !platform.is_empty() =>
+        {
+            Ok((python.to_string(), abi.to_string(), platform.to_string()))
+        }
+        _ => Err(LockError::Tag(tag.to_string())),
+    }
+}
+
+impl LockedDist {
+    /// Return the name of the distribution.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Return the pinned version of the distribution.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Return the source of the distribution.
+    pub fn source(&self) -> &LockedSource {
+        &self.source
+    }
+
+    /// Return the artifact that was selected for a registry distribution.
+    pub fn file(&self) -> Option<&LockedFile> {
+        self.file.as_ref()
+    }
+
+    /// Return the parsed marker expression that guards the entry, if any.
+    pub fn marker(&self) -> Result<Option<MarkerTree>, LockError> {
+        self.marker
+            .as_deref()
+            .map(|marker| {
+                MarkerTree::from_str(marker)
+                    .map_err(|err| LockError::Marker(self.name.clone(), Box::new(err)))
+            })
+            .transpose()
+    }
+
+    /// Returns `true` if the entry applies to the given marker environment.
+    pub fn applies_to(&self, markers: &MarkerEnvironment) -> Result<bool, LockError> {
+        Ok(self
+            .marker()?
+            .map_or(true, |marker| marker.evaluate(markers, &[])))
+    }
+
+    /// Return the names of the distributions that this distribution depends on.
+    pub fn dependencies(&self) -> &[PackageName] {
+        &self.dependencies
+    }
+
+    /// Return the extras that were enabled for the distribution.
+    pub fn extras(&self) -> &[ExtraName] {
+        &self.extras
+    }
+
+    /// Return the names of the distributions introduced by each enabled extra.
+    pub fn optional_dependencies(&self) -> &BTreeMap<ExtraName, Vec<PackageName>> {
+        &self.optional_dependencies
+    }
+
+    /// Return the parsed hashes of the distribution.
+    pub fn hashes(&self) -> Result<Vec<HashDigest>, LockError> {
+        self.hashes
+            .iter()
+            .map(|hash| {
+                HashDigest::from_str(hash).map_err(|err| LockError::Hash(self.name.clone(), err))
+            })
+            .collect()
+    }
+
+    /// Reconstruct the installable [`Dist`] for this locked distribution.
+    fn to_dist(&self) -> Result<Dist, LockError> {
+        match &self.source {
+            LockedSource::Registry(index) => {
+                let Some(file) = &self.file else {
+                    return Err(LockError::Filename(
+                        self.name.clone(),
+                        "missing file for registry distribution".to_string(),
+                    ));
+                };
+                let filename = DistFilename::try_from_filename(&file.filename, &self.name)
+                    .ok_or_else(|| LockError::Filename(self.name.clone(), file.filename.clone()))?;
+                let location = if file.url.scheme() == "file" {
+                    let path = file
+                        .url
+                        .to_file_path()
+                        .map_err(|()| LockError::Url(self.name.clone(), file.url.to_string()))?;
+                    FileLocation::Path(path)
+                } else {
+                    FileLocation::AbsoluteUrl(file.url.to_string())
+                };
+                let file = File {
+                    dist_info_metadata: false,
+                    filename: file.filename.clone(),
+                    hashes: self.hashes()?,
+                    provenance: None,
+                    requires_python: None,
+                    size: None,
+                    upload_time_utc_ms: None,
+                    url: location,
+                    yanked: None,
+                };
+                let index = IndexUrl::from(VerbatimUrl::from_url(index.clone()));
+                Ok(Dist::from_registry(filename, file, index))
+            }
+            LockedSource::DirectUrl(url) | LockedSource::Path(url) | LockedSource::Git(url) => {
+                let mut dist =
+                    Dist::from_url(self.name.clone(), VerbatimUrl::from_url(url.clone()))
+                        .map_err(|err| LockError::Dist(self.name.clone(), err))?;
+                if let Dist::Source(SourceDist::Path(sdist)) = &mut dist {
+                    sdist.editable = self.editable;
+                }
+                Ok(dist)
+            }
+        }
+    }
+}
+
+impl LockedFile {
+    fn from_file(name: &PackageName, file: &File) -> Result<Self, LockError> {
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => {
+                pypi_types::base_url_join_relative(base, url)
+                    .map_err(|err| LockError::JoinRelativeUrl(name.clone(), err))?
+            }
+            FileLocation::AbsoluteUrl(url) => {
+                Url::parse(url).map_err(|_| LockError::Url(name.clone(), url.clone()))?
+            }
+            FileLocation::Path(path) => Url::from_file_path(path)
+                .map_err(|()| LockError::Url(name.clone(), path.display().to_string()))?,
+        };
+        Ok(Self {
+            filename: file.filename.clone(),
+            url,
+        })
+    }
+
+    /// Return the filename of the artifact.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Return the absolute URL of the artifact.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use platform_tags::Tags;
+
+    use super::{Lock, LockError};
+
+    const LOCK: &str = r#"version = 1
+
+[markers]
+implementation_name = "cpython"
+implementation_version = "3.12.1"
+os_name = "posix"
+platform_machine = "x86_64"
+platform_python_implementation = "CPython"
+platform_release = "6.5.0"
+platform_system = "Linux"
+platform_version = "Linux"
+python_full_version = "3.12.1"
+python_version = "3.12"
+sys_platform = "linux"
+
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+hashes = ["sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"]
+dependencies = ["idna"]
+extras = ["trio"]
+
+[distribution.optional-dependencies]
+trio = ["idna"]
+
+[distribution.source]
+registry = "https://pypi.org/simple"
+
+[distribution.file]
+filename = "anyio-4.3.0-py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl"
+
+[[distribution]]
+name = "idna"
+version = "3.6"
+
+[distribution.source]
+direct-url = "https://files.pythonhosted.org/packages/idna-3.6-py3-none-any.whl"
+"#;
+
+    #[test]
+    fn round_trip() -> Result<(), LockError> {
+        let lock = Lock::from_toml(LOCK)?;
+        assert_eq!(lock.distributions().len(), 2);
+        assert_eq!(Lock::from_toml(&lock.to_toml()?)?, lock);
+        Ok(())
+    }
+
+    #[test]
+    fn extras() -> Result<(), LockError> {
+        let lock = Lock::from_toml(LOCK)?;
+        let anyio = &lock.distributions()[0];
+        assert_eq!(anyio.extras().len(), 1);
+        assert_eq!(anyio.optional_dependencies().len(), 1);
+
+        let idna = &lock.distributions()[1];
+        assert!(idna.extras().is_empty());
+        assert!(idna.optional_dependencies().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn to_resolution() -> Result<(), LockError> {
+        let lock = Lock::from_toml(LOCK)?;
+
+        let tags = Tags::new(vec![(
+            "py3".to_string(),
+            "none".to_string(),
+            "any".to_string(),
+        )]);
+        let resolution = lock.to_resolution(lock.markers(), &tags)?;
+        assert_eq!(resolution.len(), 2);
+
+        let tags = Tags::new(vec![(
+            "cp312".to_string(),
+            "cp312".to_string(),
+            "win_amd64".to_string(),
+        )]);
+        assert!(matches!(
+            lock.to_resolution(lock.markers(), &tags),
+            Err(LockError::IncompatibleWheel(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn tags() -> Result<(), LockError> {
+        let tags = Tags::new(vec![
+            (
+                "cp312".to_string(),
+                "cp312".to_string(),
+                "manylinux_2_17_x86_64".to_string(),
+            ),
+            ("py3".to_string(), "none".to_string(), "any".to_string()),
+        ]);
+        let lock = Lock::from_toml(LOCK)?.with_tags(&tags);
+        let toml = lock.to_toml()?;
+        assert!(
+            toml.starts_with("version = 1\ntag = \"cp312-cp312-manylinux_2_17_x86_64\"\n"),
+            "{toml}"
+        );
+        let lock = Lock::from_toml(&toml)?;
+        assert_eq!(lock.to_resolution(lock.markers(), &tags)?.len(), 2);
+
+        // The locked wheels are universal, but the lockfile was generated for another platform.
+        let tags = Tags::new(vec![
+            (
+                "cp312".to_string(),
+                "cp312".to_string(),
+                "win_amd64".to_string(),
+            ),
+            ("py3".to_string(), "none".to_string(), "any".to_string()),
+        ]);
+        assert!(matches!(
+            lock.to_resolution(lock.markers(), &tags),
+            Err(LockError::IncompatibleTag(tag)) if tag == "cp312-cp312-manylinux_2_17_x86_64"
+        ));
+
+        let toml = toml.replace("cp312-cp312-manylinux_2_17_x86_64", "cp312");
+        assert!(matches!(
+            Lock::from_toml(&toml),
+            Err(LockError::Tag(tag)) if tag == "cp312"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn marker_filtering() -> Result<(), LockError> {
+        let lock = format!(
+            r#"{LOCK}
+[[distribution]]
+name = "colorama"
+version = "0.4.6"
+marker = "sys_platform == 'win32'"
+
+[distribution.source]
+direct-url = "https://files.pythonhosted.org/packages/colorama-0.4.6-py2.py3-none-any.whl"
+
+[[distribution]]
+name = "idna"
+version = "3.7"
+marker = "sys_platform == 'win32'"
+
+[distribution.source]
+direct-url = "https://files.pythonhosted.org/packages/idna-3.7-py3-none-any.whl"
+"#
+        )
+        .replace(
+            "version = \"3.6\"\n",
+            "version = \"3.6\"\nmarker = \"sys_platform == 'linux'\"\n",
+        );
+        let lock = Lock::from_toml(&lock)?;
+        assert_eq!(Lock::from_toml(&lock.to_toml()?)?, lock);
+
+        let tags = Tags::new(vec![(
+            "py3".to_string(),
+            "none".to_string(),
+            "any".to_string(),
+        )]);
+
+        let linux = lock.markers().clone();
+        let applicable = lock.applicable(&linux)?;
+        assert_eq!(
+            applicable
+                .iter()
+                .map(|dist| format!("{}=={}", dist.name(), dist.version()))
+                .collect::<Vec<_>>(),
+            vec!["anyio==4.3.0", "idna==3.6"]
+        );
+        assert_eq!(lock.to_resolution(&linux, &tags)?.len(), 2);
+
+        let mut windows = linux.clone();
+        windows.sys_platform = "win32".to_string();
+        let applicable = lock.applicable(&windows)?;
+        assert_eq!(
+            applicable
+                .iter()
+                .map(|dist| format!("{}=={}", dist.name(), dist.version()))
+                .collect::<Vec<_>>(),
+            vec!["anyio==4.3.0", "colorama==0.4.6", "idna==3.7"]
+        );
+        assert_eq!(lock.to_resolution(&windows, &tags)?.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ambiguous_distribution() -> Result<(), LockError> {
+        let lock = format!(
+            r#"{LOCK}
+[[distribution]]
+name = "idna"
+version = "3.7"
+marker = "python_version >= '3.8'"
+
+[distribution.source]
+direct-url = "https://files.pythonhosted.org/packages/idna-3.7-py3-none-any.whl"
+"#
+        );
+        let lock = Lock::from_toml(&lock)?;
+        assert!(matches!(
+            lock.applicable(lock.markers()),
+            Err(LockError::AmbiguousDistribution(..))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_marker() {
+        let lock = LOCK.replace(
+            "version = \"3.6\"\n",
+            "version = \"3.6\"\nmarker = \"sys_platform ==\"\n",
+        );
+        assert!(matches!(Lock::from_toml(&lock), Err(LockError::Marker(..))));
+    }
+
+    #[test]
+    fn missing_optional_dependency() {
+        let lock = LOCK.replace(r#"trio = ["idna"]"#, r#"trio = ["sniffio"]"#);
+        assert!(matches!(
+            Lock::from_toml(&lock),
+            Err(LockError::MissingDependency(..))
+        ));
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let lock = LOCK.replace(
+            r#"dependencies = ["idna"]"#,
+            r#"dependencies = ["sniffio"]"#,
+        );
+        assert!(matches!(
+            Lock::from_toml(&lock),
+            Err(LockError::MissingDependency(..))
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use dashmap::DashMap;
+
+use uv_normalize::PackageName;
+
+/// Timing and request counts collected over the course of a resolution.
+///
+/// Retrieved from the [`crate::ResolutionGraph`] via [`crate::ResolutionGraph::metrics`]. For the
+/// underlying HTTP traffic (requests, cache hits, bytes downloaded), see
+/// `uv_client::RegistryClient::metrics`.
+#[derive(Debug, Default, Clone)]
+pub struct ResolverMetrics {
+    /// The total wall time of the resolution.
+    pub wall_time: Duration,
+    /// The number of packages for which the available versions were requested from the index.
+    pub index_requests: usize,
+    /// The number of distributions for which metadata was fetched or built.
+    pub metadata_requests: usize,
+    /// The per-package timings, for every package that was visited during the resolution.
+    pub packages: BTreeMap<PackageName, PackageMetrics>,
+}
+
+/// Timing collected for a single package over the course of a resolution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PackageMetrics {
+    /// The time spent fetching the available versions of the package.
+    pub versions_latency: Duration,
+    /// The cumulative time spent fetching or building metadata for the package's distributions.
+    pub metadata_latency: Duration,
+    /// The number of distributions of the package for which metadata was requested.
+    pub metadata_requests: usize,
+}
+
+impl PackageMetrics {
+    /// The total time spent waiting on the package, across all requests.
+    pub fn latency(&self) -> Duration {
+        self.versions_latency + self.metadata_latency
+    }
+}
+
+/// Collects [`ResolverMetrics`] from concurrent requests during a resolution.
+#[derive(Debug, Default)]
+pub(crate) struct MetricsRecorder {
+    index_requests: AtomicUsize,
+    metadata_requests: AtomicUsize,
+    packages: DashMap<PackageName, PackageMetrics>,
+}
+
+impl MetricsRecorder {
+    /// Record a request for the available versions of a package.
+    pub(crate) fn record_versions(&self, package_name: &PackageName, elapsed: Duration) {
+        self.index_requests.fetch_add(1, Ordering::Relaxed);
+        self.packages
+            .entry(package_name.clone())
+            .or_default()
+            .versions_latency += elapsed;
+    }
+
+    /// Record a request for the metadata of one of a package's distributions.
+    pub(crate) fn record_metadata(&self, package_name: &PackageName, elapsed: Duration) {
+        self.metadata_requests.fetch_add(1, Ordering::Relaxed);
+        let mut package = self.packages.entry(package_name.clone()).or_default();
+        package.metadata_latency += elapsed;
+        package.metadata_requests += 1;
+    }
+
+    /// Return the metrics collected so far, for a resolution that took `wall_time`.
+    pub(crate) fn finish(&self, wall_time: Duration) -> ResolverMetrics {
+        ResolverMetrics {
+            wall_time,
+            index_requests: self.index_requests.load(Ordering::Relaxed),
+            metadata_requests: self.metadata_requests.load(Ordering::Relaxed),
+            packages: self
+                .packages
+                .iter()
+                .map(|entry| (entry.key().clone(), *entry.value()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use uv_normalize::PackageName;
+
+    use super::MetricsRecorder;
+
+    #[test]
+    fn record() {
+        let flask = PackageName::from_str("flask").unwrap();
+        let jinja2 = PackageName::from_str("jinja2").unwrap();
+
+        let recorder = MetricsRecorder::default();
+        recorder.record_versions(&flask, Duration::from_millis(20));
+        recorder.record_metadata(&flask, Duration::from_millis(5));
+        recorder.record_metadata(&flask, Duration::from_millis(10));
+        recorder.record_versions(&jinja2, Duration::from_millis(30));
+
+        let metrics = recorder.finish(Duration::from_secs(1));
+        assert_eq!(metrics.wall_time, Duration::from_secs(1));
+        assert_eq!(metrics.index_requests, 2);
+        assert_eq!(metrics.metadata_requests, 2);
+        assert_eq!(
+            metrics.packages.keys().collect::<Vec<_>>(),
+            vec![&flask, &jinja2]
+        );
+
+        let flask = metrics.packages[&flask];
+        assert_eq!(flask.versions_latency, Duration::from_millis(20));
+        assert_eq!(flask.metadata_latency, Duration::from_millis(15));
+        assert_eq!(flask.metadata_requests, 2);
+        assert_eq!(flask.latency(), Duration::from_millis(35));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_configuration::IndexStrategy;
+
+use crate::{DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode, YankedMode};
 
 /// Options for resolving a manifest.
 #[derive(Debug, Default, Copy, Clone)]
@@ -6,6 +8,8 @@ pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
     pub dependency_mode: DependencyMode,
+    pub yanked_mode: YankedMode,
+    pub index_strategy: IndexStrategy,
     pub exclude_newer: Option<ExcludeNewer>,
 }
 
@@ -15,6 +19,8 @@ pub struct OptionsBuilder {
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
+    yanked_mode: YankedMode,
+    index_strategy: IndexStrategy,
     exclude_newer: Option<ExcludeNewer>,
 }
 
@@ -45,6 +51,20 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the [`YankedMode`].
+    #[must_use]
+    pub fn yanked_mode(mut self, yanked_mode: YankedMode) -> Self {
+        self.yanked_mode = yanked_mode;
+        self
+    }
+
+    /// Sets the [`IndexStrategy`].
+    #[must_use]
+    pub fn index_strategy(mut self, index_strategy: IndexStrategy) -> Self {
+        self.index_strategy = index_strategy;
+        self
+    }
+
     /// Sets the exclusion date.
     #[must_use]
     pub fn exclude_newer(mut self, exclude_newer: Option<ExcludeNewer>) -> Self {
@@ -58,6 +78,8 @@ impl OptionsBuilder {
             resolution_mode: self.resolution_mode,
             prerelease_mode: self.prerelease_mode,
             dependency_mode: self.dependency_mode,
+            yanked_mode: self.yanked_mode,
+            index_strategy: self.index_strategy,
             exclude_newer: self.exclude_newer,
         }
     }
@@ -1,3 +1,4 @@
+use std::collections::hash_map::Entry;
 use std::str::FromStr;
 
 use rustc_hash::FxHashMap;
@@ -11,6 +12,8 @@ use requirements_txt::RequirementEntry;
 use tracing::trace;
 use uv_normalize::PackageName;
 
+use crate::resolution_mode::ResolutionStrategy;
+
 #[derive(thiserror::Error, Debug)]
 pub enum PreferenceError {
     #[error("direct URL requirements without package names are not supported: {0}")]
@@ -53,6 +56,14 @@ impl Preference {
         }
     }
 
+    /// Create a [`Preference`] from a pinned [`Requirement`] and its known hashes.
+    pub(crate) fn from_pin(requirement: Requirement, hashes: Vec<HashDigest>) -> Self {
+        Self {
+            requirement,
+            hashes,
+        }
+    }
+
     /// Return the name of the package for this preference.
     pub fn name(&self) -> &PackageName {
         &self.requirement.name
@@ -74,66 +85,68 @@ impl Preferences {
     ///
     /// The provided [`MarkerEnvironment`] will be used to filter  the preferences
     /// to an applicable subset.
+    ///
+    /// If a package is pinned multiple times (e.g., when multiple versions of a package are
+    /// installed), the highest pinned version is preferred, unless the [`ResolutionStrategy`]
+    /// selects the lowest compatible version for that package.
     pub(crate) fn from_iter<PreferenceIterator: IntoIterator<Item = Preference>>(
         preferences: PreferenceIterator,
         markers: &MarkerEnvironment,
+        resolution_strategy: &ResolutionStrategy,
     ) -> Self {
-        Self(
-            // TODO(zanieb): We should explicitly ensure that when a package name is seen multiple times
-            // that the newest or oldest version is preferred dependning on the resolution strategy;
-            // right now, the order is dependent on the given iterator.
-            preferences
-                .into_iter()
-                .filter_map(|preference| {
-                    let Preference {
-                        requirement,
-                        hashes,
-                    } = preference;
+        let mut pins: FxHashMap<PackageName, Pin> = FxHashMap::default();
+        for preference in preferences {
+            let Preference {
+                requirement,
+                hashes,
+            } = preference;
 
-                    // Search for, e.g., `flask==1.2.3` entries that match the current environment.
-                    if !requirement.evaluate_markers(markers, &[]) {
+            // Search for, e.g., `flask==1.2.3` entries that match the current environment.
+            if !requirement.evaluate_markers(markers, &[]) {
+                trace!("Excluding {requirement} from preferences due to unmatched markers.");
+                continue;
+            }
+            match requirement.version_or_url.as_ref() {
+                Some(VersionOrUrl::VersionSpecifier(version_specifiers)) => {
+                    let [version_specifier] = version_specifiers.as_ref() else {
+                        trace!(
+                            "Excluding {requirement} from preferences due to multiple version specifiers."
+                        );
+                        continue;
+                    };
+                    if *version_specifier.operator() != Operator::Equal {
                         trace!(
-                            "Excluding {requirement} from preferences due to unmatched markers."
+                            "Excluding {requirement} from preferences due to inexact version specifier."
                         );
-                        return None;
+                        continue;
                     }
-                    match requirement.version_or_url.as_ref() {
-                        Some(VersionOrUrl::VersionSpecifier(version_specifiers)) =>
-                         {
-                            let [version_specifier] = version_specifiers.as_ref() else {
-                                trace!(
-                                    "Excluding {requirement} from preferences due to multiple version specifiers."
-                                );
-                                return None;
+                    let pin = Pin {
+                        version: version_specifier.version().clone(),
+                        hashes,
+                    };
+                    match pins.entry(requirement.name) {
+                        Entry::Occupied(mut entry) => {
+                            let replace = if resolution_strategy.prefers_highest(entry.key()) {
+                                pin.version > entry.get().version
+                            } else {
+                                pin.version < entry.get().version
                             };
-                            if *version_specifier.operator() != Operator::Equal {
-                                trace!(
-                                    "Excluding {requirement} from preferences due to inexact version specifier."
-                                );
-                                return None;
+                            if replace {
+                                entry.insert(pin);
                             }
-                            Some((
-                                requirement.name,
-                                Pin {
-                                    version: version_specifier.version().clone(),
-                                    hashes,
-                                },
-                            ))
                         }
-                        Some(VersionOrUrl::Url(_)) => {
-                            trace!(
-                                "Excluding {requirement} from preferences due to URL dependency."
-                            );
-                            None
+                        Entry::Vacant(entry) => {
+                            entry.insert(pin);
                         }
-                        _ => {
-                        None
-                    }
                     }
-
-                })
-                .collect(),
-        )
+                }
+                Some(VersionOrUrl::Url(_)) => {
+                    trace!("Excluding {requirement} from preferences due to URL dependency.");
+                }
+                None => {}
+            }
+        }
+        Self(pins)
     }
 
     /// Return the pinned version for a package, if any.
@@ -172,3 +185,126 @@ impl Pin {
         &self.hashes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rustc_hash::FxHashSet;
+
+    use pep440_rs::Version;
+    use pep508_rs::{MarkerEnvironment, Requirement, StringVersion};
+    use pypi_types::HashDigest;
+    use uv_normalize::PackageName;
+
+    use crate::resolution_mode::ResolutionStrategy;
+
+    use super::{Preference, Preferences};
+
+    fn markers() -> MarkerEnvironment {
+        let version = StringVersion::from_str("3.12.1").unwrap();
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: version.clone(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: String::new(),
+            platform_system: "Linux".to_string(),
+            platform_version: String::new(),
+            python_full_version: version,
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: "linux".to_string(),
+        }
+    }
+
+    fn preferences_for(pins: &[&str], resolution_strategy: &ResolutionStrategy) -> Preferences {
+        Preferences::from_iter(
+            pins.iter()
+                .map(|pin| Preference::from_requirement(Requirement::from_str(pin).unwrap())),
+            &markers(),
+            resolution_strategy,
+        )
+    }
+
+    fn name(name: &str) -> PackageName {
+        PackageName::from_str(name).unwrap()
+    }
+
+    fn version(version: &str) -> Version {
+        Version::from_str(version).unwrap()
+    }
+
+    #[test]
+    fn exact_pins_only() {
+        let preferences = preferences_for(
+            &[
+                "anyio==4.3.0",
+                "idna>=3.6",
+                "sniffio==1.3.1,<2",
+                "certifi==2024.2.2 ; sys_platform == 'win32'",
+                "iniconfig @ https://files.pythonhosted.org/packages/iniconfig-2.0.0-py3-none-any.whl",
+                "flask",
+            ],
+            &ResolutionStrategy::Highest,
+        );
+
+        assert_eq!(preferences.version(&name("anyio")), Some(&version("4.3.0")));
+
+        // Inexact, compound, and URL requirements aren't preferences, nor are pins that don't
+        // apply to the current environment.
+        assert_eq!(preferences.version(&name("idna")), None);
+        assert_eq!(preferences.version(&name("sniffio")), None);
+        assert_eq!(preferences.version(&name("certifi")), None);
+        assert_eq!(preferences.version(&name("iniconfig")), None);
+        assert_eq!(preferences.version(&name("flask")), None);
+    }
+
+    #[test]
+    fn duplicates() {
+        let pins = ["anyio==4.2.0", "anyio==4.3.0", "idna==3.5", "idna==3.6"];
+
+        // By default, the highest pinned version is preferred...
+        let preferences = preferences_for(&pins, &ResolutionStrategy::Highest);
+        assert_eq!(preferences.version(&name("anyio")), Some(&version("4.3.0")));
+        assert_eq!(preferences.version(&name("idna")), Some(&version("3.6")));
+
+        // ...unless resolving for the lowest version...
+        let preferences = preferences_for(&pins, &ResolutionStrategy::Lowest);
+        assert_eq!(preferences.version(&name("anyio")), Some(&version("4.2.0")));
+        assert_eq!(preferences.version(&name("idna")), Some(&version("3.5")));
+
+        // ...of that package.
+        let strategy = ResolutionStrategy::LowestDirect(FxHashSet::from_iter([name("anyio")]));
+        let preferences = preferences_for(&pins, &strategy);
+        assert_eq!(preferences.version(&name("anyio")), Some(&version("4.2.0")));
+        assert_eq!(preferences.version(&name("idna")), Some(&version("3.6")));
+    }
+
+    #[test]
+    fn match_hashes() {
+        let older = HashDigest::from_str("sha256:0000").unwrap();
+        let newer = HashDigest::from_str("sha256:1111").unwrap();
+        let preferences = Preferences::from_iter(
+            [
+                Preference::from_pin(Requirement::from_str("anyio==4.2.0").unwrap(), vec![older]),
+                Preference::from_pin(
+                    Requirement::from_str("anyio==4.3.0").unwrap(),
+                    vec![newer.clone()],
+                ),
+            ],
+            &markers(),
+            &ResolutionStrategy::Highest,
+        );
+
+        // The hashes are those of the selected pin, and only match its version.
+        assert_eq!(
+            preferences.match_hashes(&name("anyio"), &version("4.3.0")),
+            Some([newer].as_slice())
+        );
+        assert_eq!(
+            preferences.match_hashes(&name("anyio"), &version("4.2.0")),
+            None
+        );
+    }
+}
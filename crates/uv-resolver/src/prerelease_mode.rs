@@ -5,6 +5,10 @@ use uv_normalize::PackageName;
 
 use crate::Manifest;
 
+/// The strategy to use when considering pre-release versions (e.g., `1.0.0rc1`) as candidates.
+///
+/// Regardless of the mode, a pre-release version is only selected if it satisfies the version
+/// specifiers for the package, as in `foo>=0.9` matching `foo==1.0.0rc1`.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum PreReleaseMode {
@@ -59,46 +63,10 @@ impl PreReleaseStrategy {
             PreReleaseMode::Disallow => Self::Disallow,
             PreReleaseMode::Allow => Self::Allow,
             PreReleaseMode::IfNecessary => Self::IfNecessary,
-            PreReleaseMode::Explicit => Self::Explicit(
-                manifest
-                    .requirements(markers)
-                    .filter(|requirement| {
-                        let Some(version_or_url) = &requirement.version_or_url else {
-                            return false;
-                        };
-                        let version_specifiers = match version_or_url {
-                            VersionOrUrl::VersionSpecifier(version_specifiers) => {
-                                version_specifiers
-                            }
-                            VersionOrUrl::Url(_) => return false,
-                        };
-                        version_specifiers
-                            .iter()
-                            .any(pep440_rs::VersionSpecifier::any_prerelease)
-                    })
-                    .map(|requirement| requirement.name.clone())
-                    .collect(),
-            ),
-            PreReleaseMode::IfNecessaryOrExplicit => Self::IfNecessaryOrExplicit(
-                manifest
-                    .requirements(markers)
-                    .filter(|requirement| {
-                        let Some(version_or_url) = &requirement.version_or_url else {
-                            return false;
-                        };
-                        let version_specifiers = match version_or_url {
-                            VersionOrUrl::VersionSpecifier(version_specifiers) => {
-                                version_specifiers
-                            }
-                            VersionOrUrl::Url(_) => return false,
-                        };
-                        version_specifiers
-                            .iter()
-                            .any(pep440_rs::VersionSpecifier::any_prerelease)
-                    })
-                    .map(|requirement| requirement.name.clone())
-                    .collect(),
-            ),
+            PreReleaseMode::Explicit => Self::Explicit(explicit_prereleases(manifest, markers)),
+            PreReleaseMode::IfNecessaryOrExplicit => {
+                Self::IfNecessaryOrExplicit(explicit_prereleases(manifest, markers))
+            }
         }
     }
 
@@ -113,3 +81,27 @@ impl PreReleaseStrategy {
         }
     }
 }
+
+/// Return the names of all packages with an explicit pre-release marker in their version
+/// requirements (e.g., `torch>=2.2.0a1`).
+fn explicit_prereleases(
+    manifest: &Manifest,
+    markers: &MarkerEnvironment,
+) -> FxHashSet<PackageName> {
+    manifest
+        .requirements(markers)
+        .filter(|requirement| {
+            let Some(version_or_url) = &requirement.version_or_url else {
+                return false;
+            };
+            let version_specifiers = match version_or_url {
+                VersionOrUrl::VersionSpecifier(version_specifiers) => version_specifiers,
+                VersionOrUrl::Url(_) => return false,
+            };
+            version_specifiers
+                .iter()
+                .any(pep440_rs::VersionSpecifier::any_prerelease)
+        })
+        .map(|requirement| requirement.name.clone())
+        .collect()
+}
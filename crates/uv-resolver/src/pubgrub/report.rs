@@ -6,6 +6,7 @@ use std::ops::Bound;
 use derivative::Derivative;
 use distribution_types::IndexLocations;
 use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use pep440_rs::Version;
 use pubgrub::range::Range;
@@ -19,7 +20,7 @@ use crate::candidate_selector::CandidateSelector;
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{IncompletePackage, UnavailablePackage};
 
-use super::PubGrubPackage;
+use super::{PubGrubPackage, PubGrubPython};
 
 #[derive(Debug)]
 pub(crate) struct PubGrubReportFormatter<'a> {
@@ -343,6 +344,7 @@ impl PubGrubReportFormatter<'_> {
         index_locations: &Option<IndexLocations>,
         unavailable_packages: &FxHashMap<PackageName, UnavailablePackage>,
         incomplete_packages: &FxHashMap<PackageName, BTreeMap<Version, IncompletePackage>>,
+        newest_compatible_versions: &FxHashMap<PackageName, Version>,
     ) -> IndexSet<PubGrubHint> {
         /// Returns `true` if pre-releases were allowed for a package.
         fn allowed_prerelease(package: &PubGrubPackage, selector: &CandidateSelector) -> bool {
@@ -427,7 +429,25 @@ impl PubGrubReportFormatter<'_> {
                                         reason: reason.clone(),
                                     });
                                 }
-                                Some(UnavailablePackage::NotFound) => {}
+                                Some(UnavailablePackage::NotFound(suggestions)) => {
+                                    // Omit any packages that are already part of the resolution,
+                                    // as they're unlikely to be what the user meant.
+                                    let suggestions = suggestions
+                                        .iter()
+                                        .filter(|suggestion| {
+                                            !self.available_versions.keys().any(|package| {
+                                                matches!(package, PubGrubPackage::Package(name, ..) if name == *suggestion)
+                                            })
+                                        })
+                                        .cloned()
+                                        .collect::<Vec<_>>();
+                                    if !suggestions.is_empty() {
+                                        hints.insert(PubGrubHint::NotFound {
+                                            package: package.clone(),
+                                            suggestions,
+                                        });
+                                    }
+                                }
                                 None => {}
                             }
 
@@ -472,28 +492,99 @@ impl PubGrubReportFormatter<'_> {
                         }
                     }
                 }
+                External::FromDependencyOf(package, package_set, dependency, dependency_set) => {
+                    // Check for versions that were excluded due to their `requires-python`, where
+                    // an older version of the package is compatible.
+                    if let (
+                        PubGrubPackage::Package(name, ..),
+                        PubGrubPackage::Python(kind),
+                        Some(python),
+                    ) = (package, dependency, self.python_requirement)
+                    {
+                        let python_version = match kind {
+                            PubGrubPython::Installed => python.installed(),
+                            PubGrubPython::Target => python.target(),
+                        };
+                        if !dependency_set.contains(python_version) {
+                            if let Some(newest) = newest_compatible_versions.get(name) {
+                                let is_older = self
+                                    .available_versions
+                                    .get(package)
+                                    .into_iter()
+                                    .flatten()
+                                    .filter(|version| package_set.contains(version))
+                                    .all(|version| version > newest);
+                                if is_older && !package_set.contains(newest) {
+                                    self.insert_hint(
+                                        &mut hints,
+                                        PubGrubHint::RequiresPython {
+                                            package: package.clone(),
+                                            range: self
+                                                .simplify_set(package_set, package)
+                                                .into_owned(),
+                                            python: dependency.clone(),
+                                            requires_python: dependency_set.clone(),
+                                            python_version: python_version.to_string(),
+                                            // As in the report, only distinguish the requested
+                                            // Python version if it differs from the current one.
+                                            requested: matches!(kind, PubGrubPython::Target)
+                                                && python.target() != python.installed(),
+                                            compatible: newest.clone(),
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
                 External::NotRoot(..) => {}
-                External::FromDependencyOf(..) => {}
             },
             DerivationTree::Derived(derived) => {
-                hints.extend(self.hints(
-                    &derived.cause1,
-                    selector,
-                    index_locations,
-                    unavailable_packages,
-                    incomplete_packages,
-                ));
-                hints.extend(self.hints(
-                    &derived.cause2,
-                    selector,
-                    index_locations,
-                    unavailable_packages,
-                    incomplete_packages,
-                ));
+                for cause in [&derived.cause1, &derived.cause2] {
+                    for hint in self.hints(
+                        cause,
+                        selector,
+                        index_locations,
+                        unavailable_packages,
+                        incomplete_packages,
+                        newest_compatible_versions,
+                    ) {
+                        self.insert_hint(&mut hints, hint);
+                    }
+                }
             }
         }
         hints
     }
+
+    /// Insert a hint, merging any [`PubGrubHint::RequiresPython`] hints for the same package, such
+    /// that the excluded versions are reported together.
+    fn insert_hint(&self, hints: &mut IndexSet<PubGrubHint>, mut hint: PubGrubHint) {
+        if let Some(PubGrubHint::RequiresPython {
+            range: existing_range,
+            requires_python: existing_requires_python,
+            ..
+        }) = hints.get(&hint)
+        {
+            let existing_range = existing_range.clone();
+            let existing_requires_python = existing_requires_python.clone();
+            if let PubGrubHint::RequiresPython {
+                package,
+                range,
+                requires_python,
+                ..
+            } = &mut hint
+            {
+                *range = self
+                    .simplify_set(&range.union(&existing_range), package)
+                    .into_owned();
+                *requires_python = requires_python.union(&existing_requires_python);
+            }
+            hints.replace(hint);
+        } else {
+            hints.insert(hint);
+        }
+    }
 }
 
 #[derive(Derivative, Debug, Clone)]
@@ -519,6 +610,13 @@ pub(crate) enum PubGrubHint {
     NoIndex,
     /// A package was not found in the registry, but network access was disabled.
     Offline,
+    /// A package was not found in the registry, but similarly-named packages exist (e.g., the
+    /// requested name was misspelled).
+    NotFound {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        suggestions: Vec<PackageName>,
+    },
     /// Metadata for a package could not be parsed.
     InvalidPackageMetadata {
         package: PubGrubPackage,
@@ -548,6 +646,23 @@ pub(crate) enum PubGrubHint {
         #[derivative(PartialEq = "ignore", Hash = "ignore")]
         reason: String,
     },
+    /// Versions of a package were excluded because they require a different Python version, but
+    /// an older version of the package is compatible.
+    RequiresPython {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        range: Range<Version>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        python: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        requires_python: Range<Version>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        python_version: String,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        requested: bool,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        compatible: Version,
+    },
     /// The structure of a package version was invalid (e.g., multiple `.dist-info` directories).
     InvalidVersionStructure {
         package: PubGrubPackage,
@@ -597,6 +712,22 @@ impl std::fmt::Display for PubGrubHint {
                     ":".bold(),
                 )
             }
+            Self::NotFound {
+                package,
+                suggestions,
+            } => {
+                write!(
+                    f,
+                    "{}{} {} was not found in the package registry; did you mean {}?",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    suggestions
+                        .iter()
+                        .map(|suggestion| format!("`{suggestion}`").bold().to_string())
+                        .join(" or "),
+                )
+            }
             Self::InvalidPackageMetadata { package, reason } => {
                 write!(
                     f,
@@ -647,6 +778,28 @@ impl std::fmt::Display for PubGrubHint {
                     textwrap::indent(reason, "  ")
                 )
             }
+            Self::RequiresPython {
+                package,
+                range,
+                python,
+                requires_python,
+                python_version,
+                requested,
+                compatible,
+            } => {
+                let qualifier = if *requested { "requested" } else { "current" };
+                write!(
+                    f,
+                    "{}{} Newer versions of {} ({}) require {}, but the {qualifier} Python version is {}; the newest compatible version is {}",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    PackageRange::compatibility(package, range).bold(),
+                    PackageRange::compatibility(python, requires_python).bold(),
+                    python_version.bold(),
+                    format!("{package}=={compatible}").bold()
+                )
+            }
             PubGrubHint::InconsistentVersionMetadata {
                 package,
                 version,
@@ -10,6 +10,16 @@ pub(crate) fn apply_redirect(url: &VerbatimUrl, redirect: Url) -> VerbatimUrl {
     // The redirect should be the "same" URL, but with a specific commit hash added after the `@`.
     // We take advantage of this to preserve as much of the verbatim representation as possible.
     if let Some(given) = url.given() {
+        // Set aside the fragment (e.g., `#subdirectory=pkg_dir`), which follows the `@`.
+        let (given, fragment) = match given.split_once('#') {
+            Some((given, fragment)) => (given, Some(fragment)),
+            None => (given, None),
+        };
+        let with_fragment = |given: String| match fragment {
+            Some(fragment) => format!("{given}#{fragment}"),
+            None => given,
+        };
+
         if let Some(precise_suffix) = redirect
             .raw()
             .path()
@@ -22,13 +32,14 @@ pub(crate) fn apply_redirect(url: &VerbatimUrl, redirect: Url) -> VerbatimUrl {
                     // And the portion after the `@` is stable between the parsed and given representations...
                     if given_suffix == parsed_suffix {
                         // Preserve everything that precedes the `@` in the precise representation.
-                        return redirect.with_given(format!("{given_prefix}@{precise_suffix}"));
+                        return redirect
+                            .with_given(with_fragment(format!("{given_prefix}@{precise_suffix}")));
                     }
                 }
             } else {
                 // If there was no `@` in the original representation, we can just append the
                 // precise suffix to the given representation.
-                return redirect.with_given(format!("{given}@{precise_suffix}"));
+                return redirect.with_given(with_fragment(format!("{given}@{precise_suffix}")));
             }
         }
     }
@@ -80,6 +91,36 @@ mod tests {
         )?;
         assert_eq!(apply_redirect(&verbatim, redirect), expected);
 
+        // The fragment (e.g., a subdirectory) should be preserved after the precise suffix.
+        let verbatim = VerbatimUrl::parse_url("https://github.com/flask.git#subdirectory=src")?
+            .with_given("git+https://github.com/flask.git#subdirectory=src");
+        let redirect = Url::parse(
+            "https://github.com/flask.git@b90a4f1f4a370e92054b9cc9db0efcb864f87ebe#subdirectory=src",
+        )?;
+
+        let expected = VerbatimUrl::parse_url(
+            "https://github.com/flask.git@b90a4f1f4a370e92054b9cc9db0efcb864f87ebe#subdirectory=src",
+        )?
+        .with_given(
+            "git+https://github.com/flask.git@b90a4f1f4a370e92054b9cc9db0efcb864f87ebe#subdirectory=src",
+        );
+        assert_eq!(apply_redirect(&verbatim, redirect), expected);
+
+        let verbatim =
+            VerbatimUrl::parse_url("https://github.com/flask.git@main#subdirectory=src")?
+                .with_given("git+https://${DOMAIN}.com/flask.git@main#subdirectory=src");
+        let redirect = Url::parse(
+            "https://github.com/flask.git@b90a4f1f4a370e92054b9cc9db0efcb864f87ebe#subdirectory=src",
+        )?;
+
+        let expected = VerbatimUrl::parse_url(
+            "https://github.com/flask.git@b90a4f1f4a370e92054b9cc9db0efcb864f87ebe#subdirectory=src",
+        )?
+        .with_given(
+            "git+https://${DOMAIN}.com/flask.git@b90a4f1f4a370e92054b9cc9db0efcb864f87ebe#subdirectory=src",
+        );
+        assert_eq!(apply_redirect(&verbatim, redirect), expected);
+
         Ok(())
     }
 }
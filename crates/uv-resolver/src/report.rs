@@ -0,0 +1,107 @@
+//! A machine-readable report of a completed resolution.
+//!
+//! A [`ResolutionReport`] extends the pinned distributions of a [`Lock`] with the edges that
+//! explain why each distribution was included, such that tooling can consume the resolver's
+//! decisions without parsing `requirements.txt` annotations.
+
+use serde::Serialize;
+
+use distribution_types::Name;
+use pep508_rs::MarkerEnvironment;
+use uv_normalize::PackageName;
+
+use crate::{Lock, LockError, LockedDist, ResolutionGraph};
+
+/// A serializable report of a [`ResolutionGraph`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResolutionReport {
+    /// The marker environment against which the resolution was performed.
+    markers: MarkerEnvironment,
+    /// The pinned packages, sorted by name.
+    packages: Vec<ReportedPackage>,
+}
+
+/// A single pinned package within a [`ResolutionReport`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReportedPackage {
+    /// The pinned distribution, including its version, source, and hashes.
+    #[serde(flatten)]
+    dist: LockedDist,
+    /// Whether the package was required directly, rather than as a dependency of another package.
+    requested: bool,
+    /// The packages that depend on this package, along with the version range each requested.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    requested_by: Vec<RequestedBy>,
+}
+
+/// An incoming dependency edge for a [`ReportedPackage`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RequestedBy {
+    /// The name of the dependent package.
+    name: PackageName,
+    /// The version range that the dependent package requested.
+    specifier: String,
+}
+
+impl ResolutionReport {
+    /// Create a [`ResolutionReport`] from a [`ResolutionGraph`], resolved against the given
+    /// markers.
+    pub fn from_resolution_graph(
+        graph: &ResolutionGraph,
+        markers: &MarkerEnvironment,
+    ) -> Result<Self, LockError> {
+        let lock = Lock::from_resolution_graph(graph, markers)?;
+
+        let packages = lock
+            .distributions()
+            .iter()
+            .map(|dist| {
+                let mut requested_by = graph
+                    .dependents(dist.name())
+                    .map(|(dependent, range)| RequestedBy {
+                        name: dependent.name().clone(),
+                        specifier: range.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                requested_by.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+                requested_by.dedup();
+
+                ReportedPackage {
+                    dist: dist.clone(),
+                    requested: graph.is_root(dist.name()),
+                    requested_by,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            markers: markers.clone(),
+            packages,
+        })
+    }
+
+    /// Return the pinned packages in the report.
+    pub fn packages(&self) -> &[ReportedPackage] {
+        &self.packages
+    }
+}
+
+impl ReportedPackage {
+    /// Return the pinned distribution.
+    pub fn dist(&self) -> &LockedDist {
+        &self.dist
+    }
+
+    /// Returns `true` if the package was required directly.
+    pub fn requested(&self) -> bool {
+        self.requested
+    }
+
+    /// Return the packages that depend on this package.
+    pub fn requested_by(&self) -> &[RequestedBy] {
+        &self.requested_by
+    }
+}
@@ -1,9 +1,11 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::hash::BuildHasherDefault;
 
 use anyhow::Result;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use pubgrub::range::Range;
@@ -17,13 +19,16 @@ use distribution_types::{
 };
 use once_map::OnceMap;
 use pep440_rs::Version;
-use pep508_rs::MarkerEnvironment;
-use pypi_types::HashDigest;
+use pep508_rs::{MarkerEnvironment, Requirement};
+use pypi_types::{HashDigest, MetadataLicense};
+use uv_configuration::{Constraints, Overrides};
 use uv_distribution::to_precise;
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::dependency_provider::UvDependencyProvider;
 use crate::editables::Editables;
+use crate::license_policy::{LicensePolicy, LicenseViolation};
+use crate::metrics::ResolverMetrics;
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
 use crate::pubgrub::{PubGrubDistribution, PubGrubPackage};
@@ -51,12 +56,28 @@ pub struct ResolutionGraph {
     petgraph: petgraph::graph::Graph<ResolvedDist, Range<Version>, petgraph::Directed>,
     /// The metadata for every distribution in this resolution.
     hashes: FxHashMap<PackageName, Vec<HashDigest>>,
+    /// The resolved version of every distribution in this resolution, including those that were
+    /// requested by URL.
+    versions: FxHashMap<PackageName, Version>,
+    /// The packages that were required directly, rather than as a dependency of another package.
+    roots: FxHashSet<PackageName>,
     /// The enabled extras for every distribution in this resolution.
     extras: FxHashMap<PackageName, Vec<ExtraName>>,
+    /// The dependencies that were introduced by each enabled extra of every distribution in this
+    /// resolution (e.g., `colorama` for `black[colorama]`).
+    extra_dependencies: FxHashMap<PackageName, BTreeMap<ExtraName, Vec<PackageName>>>,
+    /// The declared license of every non-editable distribution in this resolution.
+    licenses: FxHashMap<PackageName, MetadataLicense>,
     /// The set of editable requirements in this resolution.
     editables: Editables,
+    /// The constraints that were applied to each distribution in this resolution.
+    constraints: FxHashMap<PackageName, Vec<Requirement>>,
+    /// The overrides that replaced the requirements on each distribution in this resolution.
+    overrides: FxHashMap<PackageName, Vec<Requirement>>,
     /// Any diagnostics that were encountered while building the graph.
     diagnostics: Vec<Diagnostic>,
+    /// The timings and request counts collected over the course of the resolution.
+    metrics: ResolverMetrics,
 }
 
 impl ResolutionGraph {
@@ -70,13 +91,19 @@ impl ResolutionGraph {
         state: &State<UvDependencyProvider>,
         preferences: &Preferences,
         editables: Editables,
+        constraints: &Constraints,
+        overrides: &Overrides,
+        markers: &MarkerEnvironment,
     ) -> Result<Self, ResolveError> {
         // TODO(charlie): petgraph is a really heavy and unnecessary dependency here. We should
         // write our own graph, given that our requirements are so simple.
         let mut petgraph = petgraph::graph::Graph::with_capacity(selection.len(), selection.len());
         let mut hashes =
             FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
+        let mut versions =
+            FxHashMap::with_capacity_and_hasher(selection.len(), BuildHasherDefault::default());
         let mut extras = FxHashMap::default();
+        let mut licenses = FxHashMap::default();
         let mut diagnostics = Vec::new();
 
         // Add every package to the graph.
@@ -113,7 +140,19 @@ impl ResolutionGraph {
                         }
                     }
 
+                    // Record its license, for enforcement of any license policy.
+                    if editables.get(package_name).is_none() {
+                        let dist = PubGrubDistribution::from_registry(package_name, version);
+                        if let Some(metadata_response) = distributions.get(&dist.version_id()) {
+                            if let MetadataResponse::Found(ref archive) = *metadata_response {
+                                licenses
+                                    .insert(package_name.clone(), archive.metadata.license.clone());
+                            }
+                        }
+                    }
+
                     // Add the distribution to the graph.
+                    versions.insert(package_name.clone(), version.clone());
                     let index = petgraph.add_node(pinned_package);
                     inverse.insert(package_name, index);
                 }
@@ -144,7 +183,20 @@ impl ResolutionGraph {
                         }
                     }
 
+                    // Record its license, for enforcement of any license policy.
+                    if editables.get(package_name).is_none() {
+                        if let Some(metadata_response) =
+                            distributions.get(&pinned_package.version_id())
+                        {
+                            if let MetadataResponse::Found(ref archive) = *metadata_response {
+                                licenses
+                                    .insert(package_name.clone(), archive.metadata.license.clone());
+                            }
+                        }
+                    }
+
                     // Add the distribution to the graph.
+                    versions.insert(package_name.clone(), version.clone());
                     let index = petgraph.add_node(pinned_package.into());
                     inverse.insert(package_name, index);
                 }
@@ -260,6 +312,9 @@ impl ResolutionGraph {
         }
 
         // Add every edge to the graph.
+        let mut roots = FxHashSet::default();
+        let mut extra_dependencies: FxHashMap<PackageName, BTreeMap<ExtraName, Vec<PackageName>>> =
+            FxHashMap::default();
         for (package, version) in selection {
             for id in &state.incompatibilities[package] {
                 if let Kind::FromDependencyOf(
@@ -277,7 +332,17 @@ impl ResolutionGraph {
                         continue;
                     }
 
-                    let PubGrubPackage::Package(self_package, _, _) = self_package else {
+                    // Track the packages that were required directly by the root.
+                    if let PubGrubPackage::Root(_) = self_package {
+                        if let PubGrubPackage::Package(dependency_package, _, _) =
+                            dependency_package
+                        {
+                            roots.insert(dependency_package.clone());
+                        }
+                        continue;
+                    }
+
+                    let PubGrubPackage::Package(self_package, self_extra, _) = self_package else {
                         continue;
                     };
                     let PubGrubPackage::Package(dependency_package, _, _) = dependency_package
@@ -298,20 +363,63 @@ impl ResolutionGraph {
                             *dependency_index,
                             dependency_range.clone(),
                         );
+
+                        // Attribute the dependency to the extra that introduced it, if any.
+                        if let Some(extra) = self_extra {
+                            extra_dependencies
+                                .entry(self_package.clone())
+                                .or_insert_with(BTreeMap::new)
+                                .entry(extra.clone())
+                                .or_insert_with(Vec::new)
+                                .push(dependency_package.clone());
+                        }
                     }
                 }
             }
         }
 
+        // A package may be requested with the same extra multiple times (e.g., by different
+        // dependents), and an extra may require the same dependency multiple times.
+        for extras in extras.values_mut() {
+            extras.sort_unstable();
+            extras.dedup();
+        }
+        for dependencies in extra_dependencies
+            .values_mut()
+            .flat_map(BTreeMap::values_mut)
+        {
+            dependencies.sort_unstable();
+            dependencies.dedup();
+        }
+
+        // Record the constraints and overrides that applied to each package in the resolution.
+        // Constraints and overrides on packages that weren't included in the resolution are
+        // omitted.
+        let constraints = applied_requirements(&petgraph, markers, |name| constraints.get(name));
+        let overrides = applied_requirements(&petgraph, markers, |name| overrides.get(name));
+
         Ok(Self {
             petgraph,
             hashes,
+            versions,
+            roots,
             extras,
+            extra_dependencies,
+            licenses,
             editables,
+            constraints,
+            overrides,
             diagnostics,
+            metrics: ResolverMetrics::default(),
         })
     }
 
+    /// Attach the [`ResolverMetrics`] collected over the course of the resolution.
+    #[must_use]
+    pub(crate) fn with_metrics(self, metrics: ResolverMetrics) -> Self {
+        Self { metrics, ..self }
+    }
+
     /// Return the number of packages in the graph.
     pub fn len(&self) -> usize {
         self.petgraph.node_count()
@@ -338,11 +446,177 @@ impl ResolutionGraph {
             .map(|node| node.weight)
     }
 
+    /// Return the resolved version of the given package, if it's in the graph.
+    pub(crate) fn version(&self, name: &PackageName) -> Option<&Version> {
+        self.versions.get(name)
+    }
+
+    /// Return the extras that were enabled for the given package, sorted by name.
+    pub fn extras_for(&self, name: &PackageName) -> &[ExtraName] {
+        self.extras.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the dependencies that were introduced by each enabled extra of the given package.
+    ///
+    /// Dependencies that are also required by the package itself (or by another of its enabled
+    /// extras) are included for every extra that requires them.
+    pub fn extra_dependencies_for(
+        &self,
+        name: &PackageName,
+    ) -> Option<&BTreeMap<ExtraName, Vec<PackageName>>> {
+        self.extra_dependencies.get(name)
+    }
+
+    /// Returns `true` if the given package was required directly, rather than as a dependency of
+    /// another package.
+    pub(crate) fn is_root(&self, name: &PackageName) -> bool {
+        self.roots.contains(name)
+    }
+
+    /// Return the packages in the resolution that violate the given [`LicensePolicy`], sorted by
+    /// name.
+    ///
+    /// Editable packages are exempt from the policy.
+    pub fn license_violations(&self, policy: &LicensePolicy) -> Vec<LicenseViolation> {
+        if policy.is_none() {
+            return Vec::new();
+        }
+        let mut violations = self
+            .licenses
+            .iter()
+            .filter_map(|(name, license)| {
+                let reason = policy.evaluate(license)?;
+                let version = self.versions.get(name)?.clone();
+                Some(LicenseViolation {
+                    name: name.clone(),
+                    version,
+                    reason,
+                })
+            })
+            .collect::<Vec<_>>();
+        violations.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        violations
+    }
+
+    /// Return the declared license of the given package, if it's known (i.e., unless the package
+    /// is editable).
+    pub fn license_for(&self, name: &PackageName) -> Option<&MetadataLicense> {
+        self.licenses.get(name)
+    }
+
+    /// Return the hashes for the given package, if any were recorded.
+    pub(crate) fn hashes_for(&self, name: &PackageName) -> &[HashDigest] {
+        self.hashes.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over the [`ResolvedDist`] entities in this resolution in topological order, such
+    /// that every distribution is yielded after all of its dependencies (e.g., to determine an
+    /// installation order).
+    ///
+    /// Distributions that participate in a dependency cycle are yielded consecutively, in an
+    /// arbitrary order relative to one another.
+    pub fn topological_order(&self) -> impl Iterator<Item = &ResolvedDist> {
+        topological_order(&self.petgraph)
+            .into_iter()
+            .map(|index| &self.petgraph[index])
+    }
+
+    /// Iterate over the direct dependencies of the given package, along with the version range
+    /// that was requested for each.
+    pub fn dependencies(
+        &self,
+        name: &PackageName,
+    ) -> impl Iterator<Item = (&ResolvedDist, &Range<Version>)> {
+        self.edges(name, Direction::Outgoing)
+    }
+
+    /// Iterate over the packages that directly depend on the given package, along with the
+    /// version range that each requested.
+    pub fn dependents(
+        &self,
+        name: &PackageName,
+    ) -> impl Iterator<Item = (&ResolvedDist, &Range<Version>)> {
+        self.edges(name, Direction::Incoming)
+    }
+
+    /// Return every path through the resolution from a directly-required package to the given
+    /// package, i.e., the answer to "why is this package here?".
+    ///
+    /// Each path starts with a directly-required package and ends with the given package. If the
+    /// given package was itself required directly, the path consisting of only that package is
+    /// included. Returns an empty list if the package is not in the resolution.
+    pub fn paths_to(&self, name: &PackageName) -> Vec<Vec<&ResolvedDist>> {
+        let Some(target) = self
+            .petgraph
+            .node_indices()
+            .find(|index| self.petgraph[*index].name() == name)
+        else {
+            return Vec::new();
+        };
+        paths_to(&self.petgraph, target, |index| {
+            self.roots.contains(self.petgraph[index].name())
+        })
+        .into_iter()
+        .map(|path| path.into_iter().map(|index| &self.petgraph[index]).collect())
+        .collect()
+    }
+
+    /// Iterate over the edges of the given package in the given direction.
+    fn edges(
+        &self,
+        name: &PackageName,
+        direction: Direction,
+    ) -> impl Iterator<Item = (&ResolvedDist, &Range<Version>)> {
+        self.petgraph
+            .node_indices()
+            .find(|index| self.petgraph[*index].name() == name)
+            .into_iter()
+            .flat_map(move |index| edges(&self.petgraph, index, direction))
+            .map(|(other, weight)| (&self.petgraph[other], weight))
+    }
+
+    /// Return the constraints that were applied to the given package, if any.
+    pub fn constraints_for(&self, name: &PackageName) -> &[Requirement] {
+        self.constraints.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over the constraints that were applied during resolution, along with the packages
+    /// they applied to.
+    ///
+    /// Like `pip`'s `-c`, constraints on packages that were never required have no effect, and so
+    /// are omitted.
+    pub fn constraints(&self) -> impl Iterator<Item = (&PackageName, &[Requirement])> {
+        self.constraints
+            .iter()
+            .map(|(name, constraints)| (name, constraints.as_slice()))
+    }
+
+    /// Return the overrides that replaced the declared requirements on the given package, if any.
+    pub fn overrides_for(&self, name: &PackageName) -> &[Requirement] {
+        self.overrides.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over the overrides that were applied during resolution, along with the packages
+    /// they applied to.
+    ///
+    /// Any requirement on an overridden package (whether direct or transitive) was replaced by
+    /// its overrides prior to resolution.
+    pub fn overrides(&self) -> impl Iterator<Item = (&PackageName, &[Requirement])> {
+        self.overrides
+            .iter()
+            .map(|(name, overrides)| (name, overrides.as_slice()))
+    }
+
     /// Return the [`Diagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[Diagnostic] {
         &self.diagnostics
     }
 
+    /// Return the [`ResolverMetrics`] collected over the course of the resolution.
+    pub fn metrics(&self) -> &ResolverMetrics {
+        &self.metrics
+    }
+
     /// Return the underlying graph.
     pub fn petgraph(
         &self,
@@ -495,6 +769,92 @@ impl ResolutionGraph {
     }
 }
 
+/// Return the nodes of the graph in topological order, such that every node comes after all of the
+/// nodes that it has edges to (i.e., dependencies before dependents).
+///
+/// Nodes that participate in a cycle are returned consecutively, in an arbitrary order relative to
+/// one another.
+fn topological_order<N, E>(
+    graph: &petgraph::graph::Graph<N, E, petgraph::Directed>,
+) -> Vec<NodeIndex> {
+    // Kosaraju's algorithm yields the strongly connected components in reverse topological
+    // order. Since edges point from dependents to dependencies, dependencies come first.
+    petgraph::algo::kosaraju_scc(graph)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Iterate over the nodes on the other end of the edges of `node` in the given direction (i.e.,
+/// its dependencies for [`Direction::Outgoing`], and its dependents for [`Direction::Incoming`]),
+/// along with the weight of each edge.
+fn edges<N, E>(
+    graph: &petgraph::graph::Graph<N, E, petgraph::Directed>,
+    node: NodeIndex,
+    direction: Direction,
+) -> impl Iterator<Item = (NodeIndex, &E)> {
+    graph.edges_directed(node, direction).map(move |edge| {
+        let other = match direction {
+            Direction::Outgoing => edge.target(),
+            Direction::Incoming => edge.source(),
+        };
+        (other, edge.weight())
+    })
+}
+
+/// Find every path through the graph from a root node to the `target` node, following edges from
+/// dependents to dependencies. Each path is returned in root-to-target order.
+///
+/// Cycles are broken by never visiting a node twice within the same path.
+fn paths_to<N, E>(
+    graph: &petgraph::graph::Graph<N, E, petgraph::Directed>,
+    target: NodeIndex,
+    is_root: impl Fn(NodeIndex) -> bool,
+) -> Vec<Vec<NodeIndex>> {
+    let mut paths = Vec::new();
+    let mut stack = vec![vec![target]];
+    while let Some(path) = stack.pop() {
+        let node = *path.last().expect("paths are non-empty");
+        if is_root(node) {
+            paths.push(path.iter().rev().copied().collect());
+        }
+        for dependent in graph.neighbors_directed(node, Direction::Incoming) {
+            if !path.contains(&dependent) {
+                let mut path = path.clone();
+                path.push(dependent);
+                stack.push(path);
+            }
+        }
+    }
+    paths.sort_unstable();
+    paths
+}
+
+/// Collect the requirements (e.g., constraints or overrides) that apply to each package in the
+/// graph, filtered to those whose markers match the given environment.
+fn applied_requirements<'a>(
+    petgraph: &petgraph::graph::Graph<ResolvedDist, Range<Version>, petgraph::Directed>,
+    markers: &MarkerEnvironment,
+    get: impl Fn(&PackageName) -> Option<&'a Vec<Requirement>>,
+) -> FxHashMap<PackageName, Vec<Requirement>> {
+    petgraph
+        .node_indices()
+        .filter_map(|index| {
+            let name = petgraph[index].name();
+            let applied = get(name)?
+                .iter()
+                .filter(|requirement| requirement.evaluate_markers(markers, &[]))
+                .cloned()
+                .collect::<Vec<_>>();
+            if applied.is_empty() {
+                None
+            } else {
+                Some((name.clone(), applied))
+            }
+        })
+        .collect()
+}
+
 /// A [`std::fmt::Display`] implementation for the resolution graph.
 #[derive(Debug)]
 #[allow(clippy::struct_excessive_bools)]
@@ -794,3 +1154,140 @@ impl Diagnostic {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{Graph, NodeIndex};
+    use petgraph::Direction;
+
+    use super::{edges, paths_to, topological_order};
+
+    /// Return the position of each of the given nodes in the topological order of the graph.
+    fn positions<N, E>(graph: &Graph<N, E>, nodes: &[NodeIndex]) -> Vec<usize> {
+        let order = topological_order(graph);
+        nodes
+            .iter()
+            .map(|node| order.iter().position(|index| index == node).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn topological_order_acyclic() {
+        // a -> b -> d, a -> c -> d.
+        let mut graph = Graph::<&str, ()>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b, ());
+        graph.add_edge(a, c, ());
+        graph.add_edge(b, d, ());
+        graph.add_edge(c, d, ());
+
+        assert_eq!(topological_order(&graph).len(), 4);
+        let [a, b, c, d] = positions(&graph, &[a, b, c, d])[..] else {
+            unreachable!()
+        };
+        assert!(d < b && d < c);
+        assert!(b < a && c < a);
+    }
+
+    #[test]
+    fn topological_order_cycle() {
+        // a -> b -> c -> b, c -> d.
+        let mut graph = Graph::<&str, ()>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(c, b, ());
+        graph.add_edge(c, d, ());
+
+        // Every node is yielded exactly once, and the members of the cycle are adjacent.
+        assert_eq!(topological_order(&graph).len(), 4);
+        let [a, b, c, d] = positions(&graph, &[a, b, c, d])[..] else {
+            unreachable!()
+        };
+        assert_eq!(b.abs_diff(c), 1);
+        assert!(d < b && d < c);
+        assert!(b < a && c < a);
+    }
+
+    #[test]
+    fn dependencies_and_dependents() {
+        // a -> b (">=1"), a -> c (">=2"), b -> c (">=3"), c -> a (">=4").
+        let mut graph = Graph::<&str, &str>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b, ">=1");
+        graph.add_edge(a, c, ">=2");
+        graph.add_edge(b, c, ">=3");
+        graph.add_edge(c, a, ">=4");
+
+        let collect = |node, direction| {
+            let mut others = edges(&graph, node, direction)
+                .map(|(other, weight)| (graph[other], *weight))
+                .collect::<Vec<_>>();
+            others.sort_unstable();
+            others
+        };
+
+        assert_eq!(
+            collect(a, Direction::Outgoing),
+            vec![("b", ">=1"), ("c", ">=2")]
+        );
+        assert_eq!(collect(a, Direction::Incoming), vec![("c", ">=4")]);
+        assert_eq!(collect(b, Direction::Outgoing), vec![("c", ">=3")]);
+        assert_eq!(collect(b, Direction::Incoming), vec![("a", ">=1")]);
+        assert_eq!(collect(c, Direction::Outgoing), vec![("a", ">=4")]);
+        assert_eq!(
+            collect(c, Direction::Incoming),
+            vec![("a", ">=2"), ("b", ">=3")]
+        );
+    }
+
+    #[test]
+    fn paths_to_target() {
+        // a -> b -> d, a -> c -> d, c -> b, with `a` and `c` required directly.
+        let mut graph = Graph::<&str, ()>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b, ());
+        graph.add_edge(a, c, ());
+        graph.add_edge(b, d, ());
+        graph.add_edge(c, d, ());
+        graph.add_edge(c, b, ());
+
+        let paths = paths_to(&graph, d, |index| index == a || index == c);
+        assert_eq!(
+            paths,
+            vec![
+                vec![a, b, d],
+                vec![a, c, b, d],
+                vec![a, c, d],
+                vec![c, b, d],
+                vec![c, d],
+            ]
+        );
+    }
+
+    #[test]
+    fn paths_to_cycle() {
+        // a -> b -> c -> b, with `a` required directly.
+        let mut graph = Graph::<&str, ()>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(c, b, ());
+
+        assert_eq!(paths_to(&graph, c, |index| index == a), vec![vec![a, b, c]]);
+        assert_eq!(paths_to(&graph, a, |index| index == a), vec![vec![a]]);
+    }
+}
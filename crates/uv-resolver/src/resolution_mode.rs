@@ -45,4 +45,13 @@ impl ResolutionStrategy {
             }
         }
     }
+
+    /// Returns `true` if the highest compatible version of the given package should be selected.
+    pub(crate) fn prefers_highest(&self, package_name: &PackageName) -> bool {
+        match self {
+            Self::Highest => true,
+            Self::Lowest => false,
+            Self::LowestDirect(direct_dependencies) => !direct_dependencies.contains(package_name),
+        }
+    }
 }
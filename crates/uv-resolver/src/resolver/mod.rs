@@ -2,8 +2,10 @@
 
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::Result;
 use dashmap::{DashMap, DashSet};
@@ -27,16 +29,18 @@ use platform_tags::Tags;
 use pypi_types::Metadata23;
 pub(crate) use urls::Urls;
 use uv_client::RegistryClient;
-use uv_configuration::{Constraints, Overrides};
+use uv_configuration::{Concurrency, Constraints, Overrides};
 use uv_distribution::{ArchiveMetadata, DistributionDatabase};
 use uv_interpreter::Interpreter;
 use uv_normalize::PackageName;
 use uv_types::{BuildContext, HashStrategy, InstalledPackagesProvider};
 
+use crate::candidate_filter::CandidateFilters;
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::editables::Editables;
 use crate::error::ResolveError;
 use crate::manifest::Manifest;
+use crate::metrics::MetricsRecorder;
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
 use crate::pubgrub::{
@@ -61,6 +65,7 @@ mod index;
 mod locals;
 mod provider;
 mod reporter;
+mod suggestions;
 mod urls;
 
 /// The package version is unavailable and cannot be used
@@ -78,8 +83,8 @@ pub(crate) enum UnavailablePackage {
     NoIndex,
     /// Network requests were disabled (i.e., `--offline`), and the package was not found in the cache.
     Offline,
-    /// The package was not found in the registry.
-    NotFound,
+    /// The package was not found in the registry, along with any similarly-named packages.
+    NotFound(Vec<PackageName>),
     /// The package metadata was found, but could not be parsed.
     InvalidMetadata(String),
     /// The package has an invalid structure.
@@ -133,6 +138,10 @@ pub struct Resolver<
     incomplete_packages: DashMap<PackageName, DashMap<Version, IncompletePackage>>,
     /// The set of all registry-based packages visited during resolution.
     visited: DashSet<PackageName>,
+    /// The timings and request counts collected over the course of the resolution.
+    metrics: MetricsRecorder,
+    /// The maximum number of metadata requests to have in flight at once.
+    concurrency: NonZeroUsize,
     reporter: Option<Arc<dyn Reporter>>,
     provider: Provider,
 }
@@ -166,13 +175,13 @@ impl<
             flat_index,
             tags,
             PythonRequirement::new(interpreter, markers),
-            AllowedYanks::from_manifest(&manifest, markers),
+            AllowedYanks::from_manifest(&manifest, markers, options.yanked_mode),
             hasher,
             options.exclude_newer,
             build_context.no_binary(),
             build_context.no_build(),
         );
-        Self::new_custom_io(
+        Ok(Self::new_custom_io(
             manifest,
             options,
             hasher,
@@ -181,7 +190,18 @@ impl<
             index,
             provider,
             installed_packages,
-        )
+        )?
+        .with_concurrency(client.concurrency().downloads))
+    }
+
+    /// Set the [`CandidateFilters`] to consult for each file listed by the index, to veto or
+    /// re-rank the files available for each package version.
+    #[must_use]
+    pub fn with_candidate_filters(self, candidate_filters: CandidateFilters) -> Self {
+        Self {
+            provider: self.provider.with_candidate_filters(candidate_filters),
+            ..self
+        }
     }
 }
 
@@ -203,12 +223,20 @@ impl<
         provider: Provider,
         installed_packages: &'a InstalledPackages,
     ) -> Result<Self, ResolveError> {
+        let selector = CandidateSelector::for_resolution(options, &manifest, markers);
+        let preferences = Preferences::from_iter(
+            manifest.preferences,
+            markers,
+            selector.resolution_strategy(),
+        );
         Ok(Self {
             index,
             unavailable_packages: DashMap::default(),
             incomplete_packages: DashMap::default(),
             visited: DashSet::default(),
-            selector: CandidateSelector::for_resolution(options, &manifest, markers),
+            metrics: MetricsRecorder::default(),
+            concurrency: Concurrency::DEFAULT_DOWNLOADS,
+            selector,
             dependency_mode: options.dependency_mode,
             urls: Urls::from_manifest(&manifest, markers)?,
             locals: Locals::from_manifest(&manifest, markers),
@@ -216,7 +244,7 @@ impl<
             requirements: manifest.requirements,
             constraints: manifest.constraints,
             overrides: manifest.overrides,
-            preferences: Preferences::from_iter(manifest.preferences, markers),
+            preferences,
             exclusions: manifest.exclusions,
             editables: Editables::from_requirements(manifest.editables),
             hasher,
@@ -239,8 +267,19 @@ impl<
         }
     }
 
+    /// Set the maximum number of metadata requests to have in flight at once.
+    #[must_use]
+    pub fn with_concurrency(self, concurrency: NonZeroUsize) -> Self {
+        Self {
+            concurrency,
+            ..self
+        }
+    }
+
     /// Resolve a set of requirements into a set of pinned versions.
     pub async fn resolve(self) -> Result<ResolutionGraph, ResolveError> {
+        let start = Instant::now();
+
         // A channel to fetch package metadata (e.g., given `flask`, fetch all versions) and version
         // metadata (e.g., given `flask==1.0.0`, fetch the metadata for that version).
         // Channel size is set large to accommodate batch prefetching.
@@ -256,7 +295,7 @@ impl<
         match tokio::try_join!(requests_fut, resolve_fut) {
             Ok(((), resolution)) => {
                 self.on_complete();
-                Ok(resolution)
+                Ok(resolution.with_metrics(self.metrics.finish(start.elapsed())))
             }
             Err(err) => {
                 // Add version information to improve unsat error messages.
@@ -332,6 +371,9 @@ impl<
                     &state,
                     &self.preferences,
                     self.editables.clone(),
+                    &self.constraints,
+                    &self.overrides,
+                    self.markers,
                 );
             };
             next = highest_priority_pkg;
@@ -373,7 +415,7 @@ impl<
                                         "was not found in the provided package locations"
                                     }
                                     UnavailablePackage::Offline => "was not found in the cache",
-                                    UnavailablePackage::NotFound => {
+                                    UnavailablePackage::NotFound(_) => {
                                         "was not found in the package registry"
                                     }
                                     UnavailablePackage::InvalidMetadata(_) => {
@@ -725,9 +767,11 @@ impl<
                             .insert(package_name.clone(), UnavailablePackage::Offline);
                         &[]
                     }
-                    VersionsResponse::NotFound => {
-                        self.unavailable_packages
-                            .insert(package_name.clone(), UnavailablePackage::NotFound);
+                    VersionsResponse::NotFound(ref suggestions) => {
+                        self.unavailable_packages.insert(
+                            package_name.clone(),
+                            UnavailablePackage::NotFound(suggestions.clone()),
+                        );
                         &[]
                     }
                 };
@@ -1048,12 +1092,15 @@ impl<
     ) -> Result<(), ResolveError> {
         let mut response_stream = ReceiverStream::new(request_stream)
             .map(|request| self.process_request(request).boxed())
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency.get());
 
         while let Some(response) = response_stream.next().await {
             match response? {
                 Some(Response::Package(package_name, version_map)) => {
                     trace!("Received package metadata for: {package_name}");
+                    if let Some(reporter) = self.reporter.as_ref() {
+                        reporter.on_fetch(&package_name);
+                    }
                     self.index.packages.done(package_name, version_map);
                 }
                 Some(Response::Installed { dist, metadata }) => {
@@ -1107,18 +1154,21 @@ impl<
         match request {
             // Fetch package metadata from the registry.
             Request::Package(package_name) => {
+                let start = Instant::now();
                 let package_versions = self
                     .provider
                     .get_package_versions(&package_name)
                     .boxed()
                     .await
                     .map_err(ResolveError::Client)?;
+                self.metrics.record_versions(&package_name, start.elapsed());
 
                 Ok(Some(Response::Package(package_name, package_versions)))
             }
 
             // Fetch distribution metadata from the distribution database.
             Request::Dist(dist) => {
+                let start = Instant::now();
                 let metadata = self
                     .provider
                     .get_or_build_wheel_metadata(&dist)
@@ -1136,6 +1186,7 @@ impl<
                             ResolveError::FetchAndBuild(Box::new(source_dist), err)
                         }
                     })?;
+                self.metrics.record_metadata(dist.name(), start.elapsed());
                 Ok(Some(Response::Dist { dist, metadata }))
             }
 
@@ -1171,9 +1222,11 @@ impl<
 
                         return Ok(None);
                     }
-                    VersionsResponse::NotFound => {
-                        self.unavailable_packages
-                            .insert(package_name.clone(), UnavailablePackage::NotFound);
+                    VersionsResponse::NotFound(ref suggestions) => {
+                        self.unavailable_packages.insert(
+                            package_name.clone(),
+                            UnavailablePackage::NotFound(suggestions.clone()),
+                        );
 
                         return Ok(None);
                     }
@@ -10,8 +10,10 @@ use uv_distribution::{ArchiveMetadata, DistributionDatabase};
 use uv_normalize::PackageName;
 use uv_types::{BuildContext, HashStrategy};
 
+use crate::candidate_filter::CandidateFilters;
 use crate::flat_index::FlatIndex;
 use crate::python_requirement::PythonRequirement;
+use crate::resolver::suggestions::suggest;
 use crate::version_map::VersionMap;
 use crate::yanks::AllowedYanks;
 use crate::ExcludeNewer;
@@ -24,8 +26,9 @@ pub type WheelMetadataResult = Result<MetadataResponse, uv_distribution::Error>;
 pub enum VersionsResponse {
     /// The package was found in the registry with the included versions
     Found(Vec<VersionMap>),
-    /// The package was not found in the registry
-    NotFound,
+    /// The package was not found in the registry, along with the names of any known packages
+    /// that are similar to the requested name
+    NotFound(Vec<PackageName>),
     /// The package was not found in the local registry
     NoIndex,
     /// The package was not found in the cache and the network is not available.
@@ -86,6 +89,7 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext + Send + Sync> {
     exclude_newer: Option<ExcludeNewer>,
     no_binary: NoBinary,
     no_build: NoBuild,
+    candidate_filters: CandidateFilters,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Context> {
@@ -114,6 +118,25 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
             exclude_newer,
             no_binary: no_binary.clone(),
             no_build: no_build.clone(),
+            candidate_filters: CandidateFilters::default(),
+        }
+    }
+
+    /// Set the [`CandidateFilters`] to consult for each file listed by the index or by the
+    /// `--find-links` locations.
+    #[must_use]
+    pub fn with_candidate_filters(self, candidate_filters: CandidateFilters) -> Self {
+        let flat_index = self.flat_index.with_candidate_filters(
+            &self.tags,
+            &self.hasher,
+            &self.no_build,
+            &self.no_binary,
+            &candidate_filters,
+        );
+        Self {
+            flat_index,
+            candidate_filters,
+            ..self
         }
     }
 }
@@ -143,6 +166,7 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                             self.flat_index.get(package_name).cloned(),
                             &self.no_binary,
                             &self.no_build,
+                            &self.candidate_filters,
                         )
                     })
                     .collect(),
@@ -152,7 +176,11 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                     if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
                         Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
                     } else {
-                        Ok(VersionsResponse::NotFound)
+                        let known = self.client.cached_package_names();
+                        Ok(VersionsResponse::NotFound(suggest(
+                            package_name,
+                            known.iter().chain(self.flat_index.package_names()),
+                        )))
                     }
                 }
                 uv_client::ErrorKind::NoIndex(_) => {
@@ -8,6 +8,9 @@ use uv_normalize::PackageName;
 pub type BuildId = usize;
 
 pub trait Reporter: Send + Sync {
+    /// Callback to invoke when the available versions of a package are fetched.
+    fn on_fetch(&self, name: &PackageName);
+
     /// Callback to invoke when a dependency is resolved.
     fn on_progress(&self, name: &PackageName, version: &VersionOrUrl);
 
@@ -0,0 +1,110 @@
+use uv_normalize::PackageName;
+
+/// The maximum number of suggestions to offer for a package that could not be found.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Return the known package names that are close enough to `package_name` to plausibly be what
+/// the user meant (e.g., `requests` for `reqests`), closest first.
+pub(crate) fn suggest<'a>(
+    package_name: &PackageName,
+    known: impl IntoIterator<Item = &'a PackageName>,
+) -> Vec<PackageName> {
+    let name = package_name.as_ref();
+
+    // Allow roughly one edit for every three characters, such that short names (e.g., `six`)
+    // don't match everything of a similar length.
+    let threshold = (name.chars().count() / 3).max(1);
+
+    let mut candidates = known
+        .into_iter()
+        .filter(|candidate| *candidate != package_name)
+        .filter_map(|candidate| {
+            let distance = distance(name, candidate.as_ref());
+            (distance <= threshold).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+/// Compute the optimal string alignment distance between two strings: the number of insertions,
+/// deletions, substitutions, and transpositions of adjacent characters needed to turn one into
+/// the other.
+fn distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // The distances for the previous two rows, and the current row, of the DP table.
+    let mut prev_prev = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use super::{distance, suggest};
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(distance("requests", "requests"), 0);
+        assert_eq!(distance("reqests", "requests"), 1);
+        assert_eq!(distance("reqeusts", "requests"), 1);
+        assert_eq!(distance("flask", "flasks"), 1);
+        assert_eq!(distance("", "six"), 3);
+        assert_eq!(distance("numpy", "scipy"), 3);
+    }
+
+    #[test]
+    fn suggestions() {
+        let known = [
+            "requests",
+            "requests-toolbelt",
+            "flask",
+            "six",
+            "sax",
+            "httpx",
+        ]
+        .into_iter()
+        .map(|name| PackageName::from_str(name).unwrap())
+        .collect::<Vec<_>>();
+        let suggest = |name: &str| {
+            suggest(&PackageName::from_str(name).unwrap(), &known)
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(suggest("reqeusts"), vec!["requests"]);
+        assert_eq!(suggest("Flsak"), vec!["flask"]);
+        assert_eq!(suggest("sx"), vec!["sax", "six"]);
+        assert_eq!(suggest("django"), Vec::<String>::new());
+        // A package is never suggested as an alternative to itself.
+        assert_eq!(suggest("requests"), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,408 @@
+//! Universal (multi-platform) resolution.
+//!
+//! The resolver evaluates markers against a single [`MarkerEnvironment`]. To produce a resolution
+//! that is valid across several platforms, we resolve once per target environment (a "fork"), and
+//! then merge the per-fork results: packages that are pinned identically in every fork are emitted
+//! unconditionally, while packages that differ across forks are emitted once per distinct pin,
+//! guarded by a marker expression that selects the forks in which that pin applies.
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+
+use distribution_types::{Name, Verbatim};
+use pep508_rs::{
+    MarkerEnvironment, MarkerExpression, MarkerOperator, MarkerTree, MarkerValue,
+    MarkerValueString, MarkerValueVersion,
+};
+use uv_normalize::PackageName;
+
+use crate::ResolutionGraph;
+
+/// A resolution for a single target environment within a [`UniversalResolution`].
+#[derive(Debug)]
+pub struct ResolutionFork {
+    /// The marker environment against which the fork was resolved.
+    markers: MarkerEnvironment,
+    /// The resolution for the fork.
+    graph: ResolutionGraph,
+}
+
+impl ResolutionFork {
+    /// Return the marker environment against which the fork was resolved.
+    pub fn markers(&self) -> &MarkerEnvironment {
+        &self.markers
+    }
+
+    /// Return the resolution for the fork.
+    pub fn graph(&self) -> &ResolutionGraph {
+        &self.graph
+    }
+}
+
+/// A resolution spanning multiple target environments.
+#[derive(Debug)]
+pub struct UniversalResolution {
+    forks: Vec<ResolutionFork>,
+    /// The marker expression that selects each fork, in the same order as `forks`.
+    fork_markers: Vec<MarkerTree>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UniversalError {
+    #[error("A universal resolution requires at least one target environment")]
+    NoForks,
+
+    #[error(
+        "Multiple target environments have identical markers (`sys_platform == '{sys_platform}'`, `platform_machine == '{platform_machine}'`, `python_full_version == '{python_full_version}'`), so their resolutions can't be distinguished"
+    )]
+    IndistinguishableForks {
+        sys_platform: String,
+        platform_machine: String,
+        python_full_version: String,
+    },
+}
+
+/// A pinned package in a [`UniversalResolution`], along with the markers under which it applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniversalPin {
+    /// The name of the package.
+    pub name: PackageName,
+    /// The pinned requirement, e.g., `anyio==4.3.0` or `anyio @ https://...`.
+    pub requirement: String,
+    /// The markers under which the pin applies, or `None` if it applies in every fork.
+    pub markers: Option<MarkerTree>,
+}
+
+impl std::fmt::Display for UniversalPin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(markers) = &self.markers {
+            write!(f, "{} ; {markers}", self.requirement)
+        } else {
+            write!(f, "{}", self.requirement)
+        }
+    }
+}
+
+impl UniversalResolution {
+    /// Create a [`UniversalResolution`] from the resolutions for each target environment.
+    ///
+    /// Returns an error if there are no target environments, or if any two of them can't be
+    /// distinguished by their markers.
+    pub fn new(
+        forks: impl IntoIterator<Item = (MarkerEnvironment, ResolutionGraph)>,
+    ) -> Result<Self, UniversalError> {
+        let forks = forks
+            .into_iter()
+            .map(|(markers, graph)| ResolutionFork { markers, graph })
+            .collect::<Vec<_>>();
+        let fork_markers =
+            fork_markers(&forks.iter().map(|fork| &fork.markers).collect::<Vec<_>>())?;
+        Ok(Self {
+            forks,
+            fork_markers,
+        })
+    }
+
+    /// Return the per-environment forks that make up this resolution.
+    pub fn forks(&self) -> &[ResolutionFork] {
+        &self.forks
+    }
+
+    /// Return the merged pins across all forks, sorted by package name.
+    pub fn pins(&self) -> Vec<UniversalPin> {
+        let forks = self
+            .forks
+            .iter()
+            .map(|fork| {
+                let petgraph = fork.graph.petgraph();
+                petgraph
+                    .node_indices()
+                    .map(|index| {
+                        let dist = &petgraph[index];
+                        let requirement = if dist.is_editable() {
+                            format!("-e {}", dist.verbatim())
+                        } else {
+                            dist.verbatim().to_string()
+                        };
+                        (dist.name().clone(), requirement)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        merge(&self.fork_markers, &forks)
+    }
+
+    /// Return the marker expression that selects the given forks (by index into
+    /// [`UniversalResolution::forks`]), or `None` if every fork is selected.
+    pub(crate) fn marker_for(&self, forks: &[usize]) -> Option<MarkerTree> {
+        select(&self.fork_markers, forks)
+    }
+}
+
+/// Write the resolution in the requirements.txt format, with markers on any pins that don't apply
+/// to every fork.
+impl std::fmt::Display for UniversalResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for pin in self.pins() {
+            writeln!(f, "{pin}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Merge the `(name, requirement)` pins of each fork, given the marker expression that selects
+/// each fork.
+fn merge(markers: &[MarkerTree], forks: &[Vec<(PackageName, String)>]) -> Vec<UniversalPin> {
+    // For each package, map each distinct pin to the set of forks in which it appears.
+    let mut packages: BTreeMap<&PackageName, BTreeMap<&str, Vec<usize>>> = BTreeMap::new();
+    for (fork, pins) in forks.iter().enumerate() {
+        for (name, requirement) in pins {
+            packages
+                .entry(name)
+                .or_default()
+                .entry(requirement.as_str())
+                .or_default()
+                .push(fork);
+        }
+    }
+
+    let mut pins = Vec::new();
+    for (name, requirements) in packages {
+        for (requirement, indices) in requirements {
+            pins.push(UniversalPin {
+                name: name.clone(),
+                requirement: requirement.to_string(),
+                markers: select(markers, &indices),
+            });
+        }
+    }
+    pins
+}
+
+/// Return the marker expression that selects the given forks, given the marker expression that
+/// selects each fork, or `None` if every fork is selected.
+fn select(markers: &[MarkerTree], indices: &[usize]) -> Option<MarkerTree> {
+    if indices.len() == markers.len() {
+        return None;
+    }
+    let mut trees = indices
+        .iter()
+        .map(|index| markers[*index].clone())
+        .collect::<Vec<_>>();
+    if trees.len() == 1 {
+        trees.pop()
+    } else {
+        Some(MarkerTree::Or(trees))
+    }
+}
+
+/// Compute a marker expression that distinguishes each environment from the others, using only
+/// the marker values that differ between environments.
+///
+/// For a single environment, no marker values differ, and the expression is the empty conjunction
+/// (which is always true). Returns an error if there are no environments, or if any two
+/// environments have identical marker values.
+fn fork_markers(environments: &[&MarkerEnvironment]) -> Result<Vec<MarkerTree>, UniversalError> {
+    let keys: [(MarkerValue, fn(&MarkerEnvironment) -> String); 11] = [
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::SysPlatform),
+            |env| env.sys_platform.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::PlatformSystem),
+            |env| env.platform_system.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::OsName),
+            |env| env.os_name.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::PlatformMachine),
+            |env| env.platform_machine.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::PlatformRelease),
+            |env| env.platform_release.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::PlatformVersion),
+            |env| env.platform_version.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::ImplementationName),
+            |env| env.implementation_name.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvString(MarkerValueString::PlatformPythonImplementation),
+            |env| env.platform_python_implementation.clone(),
+        ),
+        (
+            MarkerValue::MarkerEnvVersion(MarkerValueVersion::ImplementationVersion),
+            |env| env.implementation_version.to_string(),
+        ),
+        (
+            MarkerValue::MarkerEnvVersion(MarkerValueVersion::PythonVersion),
+            |env| env.python_version.to_string(),
+        ),
+        (
+            MarkerValue::MarkerEnvVersion(MarkerValueVersion::PythonFullVersion),
+            |env| env.python_full_version.to_string(),
+        ),
+    ];
+
+    if environments.is_empty() {
+        return Err(UniversalError::NoForks);
+    }
+
+    // Every environment must differ from every other in at least one marker value.
+    let values = environments
+        .iter()
+        .map(|env| keys.iter().map(|(_, value)| value(env)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    if let Some(env) = environments
+        .iter()
+        .zip(&values)
+        .duplicates_by(|(_, values)| *values)
+        .map(|(env, _)| env)
+        .next()
+    {
+        return Err(UniversalError::IndistinguishableForks {
+            sys_platform: env.sys_platform.clone(),
+            platform_machine: env.platform_machine.clone(),
+            python_full_version: env.python_full_version.to_string(),
+        });
+    }
+
+    // Only consider the marker values that vary across environments.
+    let keys = keys
+        .into_iter()
+        .filter(|(_, value)| environments.iter().map(|env| value(env)).unique().count() > 1)
+        .collect::<Vec<_>>();
+
+    Ok(environments
+        .iter()
+        .map(|env| {
+            let mut expressions = keys
+                .iter()
+                .map(|(key, value)| {
+                    MarkerTree::Expression(MarkerExpression {
+                        l_value: key.clone(),
+                        operator: MarkerOperator::Equal,
+                        r_value: MarkerValue::QuotedString(value(env)),
+                    })
+                })
+                .collect::<Vec<_>>();
+            if expressions.len() == 1 {
+                expressions.pop().unwrap()
+            } else {
+                MarkerTree::And(expressions)
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::{MarkerEnvironment, StringVersion};
+    use uv_normalize::PackageName;
+
+    use super::{fork_markers, merge, UniversalError};
+
+    fn environment(sys_platform: &str, platform_system: &str) -> MarkerEnvironment {
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: StringVersion::from_str("3.12.1").unwrap(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: String::new(),
+            platform_system: platform_system.to_string(),
+            platform_version: String::new(),
+            python_full_version: StringVersion::from_str("3.12.1").unwrap(),
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: sys_platform.to_string(),
+        }
+    }
+
+    fn pin(name: &str, version: &str) -> (PackageName, String) {
+        (
+            PackageName::from_str(name).unwrap(),
+            format!("{name}=={version}"),
+        )
+    }
+
+    #[test]
+    fn single_fork() {
+        let linux = environment("linux", "Linux");
+        let markers = fork_markers(&[&linux]).unwrap();
+
+        let pins = merge(&markers, &[vec![pin("anyio", "4.3.0")]]);
+        let pins = pins.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(pins, vec!["anyio==4.3.0"]);
+    }
+
+    #[test]
+    fn divergent_forks() {
+        let linux = environment("linux", "Linux");
+        let darwin = environment("darwin", "Darwin");
+        let windows = environment("win32", "Windows");
+        let markers = fork_markers(&[&linux, &darwin, &windows]).unwrap();
+
+        let pins = merge(
+            &markers,
+            &[
+                vec![pin("anyio", "4.3.0"), pin("uvloop", "0.19.0")],
+                vec![pin("anyio", "4.3.0"), pin("uvloop", "0.18.0")],
+                vec![pin("anyio", "4.3.0"), pin("colorama", "0.4.6")],
+            ],
+        );
+        let pins = pins.iter().map(ToString::to_string).collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(pins, @r###"
+        [
+            "anyio==4.3.0",
+            "colorama==0.4.6 ; sys_platform == 'win32' and platform_system == 'Windows'",
+            "uvloop==0.18.0 ; sys_platform == 'darwin' and platform_system == 'Darwin'",
+            "uvloop==0.19.0 ; sys_platform == 'linux' and platform_system == 'Linux'",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn release_forks() {
+        // Environments that only differ in fields outside the most common markers.
+        let old = environment("linux", "Linux");
+        let new = MarkerEnvironment {
+            platform_release: "6.5.0".to_string(),
+            ..environment("linux", "Linux")
+        };
+        let markers = fork_markers(&[&old, &new]).unwrap();
+
+        let pins = merge(
+            &markers,
+            &[vec![pin("anyio", "4.2.0")], vec![pin("anyio", "4.3.0")]],
+        );
+        let pins = pins.iter().map(ToString::to_string).collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(pins, @r###"
+        [
+            "anyio==4.2.0 ; platform_release == ''",
+            "anyio==4.3.0 ; platform_release == '6.5.0'",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn indistinguishable_forks() {
+        let linux = environment("linux", "Linux");
+        let err = fork_markers(&[&linux, &environment("darwin", "Darwin"), &linux]).unwrap_err();
+        assert!(
+            matches!(&err, UniversalError::IndistinguishableForks { sys_platform, .. } if sys_platform == "linux"),
+            "{err:?}"
+        );
+
+        let err = fork_markers(&[]).unwrap_err();
+        assert!(matches!(err, UniversalError::NoForks), "{err:?}");
+    }
+}
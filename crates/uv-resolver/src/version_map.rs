@@ -7,7 +7,7 @@ use tracing::instrument;
 
 use distribution_filename::{DistFilename, WheelFilename};
 use distribution_types::{
-    Dist, Hash, IncompatibleSource, IncompatibleWheel, IndexUrl, PrioritizedDist,
+    Dist, Hash, IncompatibleSource, IncompatibleWheel, IndexUrl, PrioritizedDist, Rank,
     SourceDistCompatibility, WheelCompatibility,
 };
 use pep440_rs::{Version, VersionSpecifiers};
@@ -19,6 +19,7 @@ use uv_normalize::PackageName;
 use uv_types::HashStrategy;
 use uv_warnings::warn_user_once;
 
+use crate::candidate_filter::{CandidateFile, CandidateFilters};
 use crate::flat_index::FlatDistributions;
 use crate::{python_requirement::PythonRequirement, yanks::AllowedYanks, ExcludeNewer};
 
@@ -52,6 +53,7 @@ impl VersionMap {
         flat_index: Option<FlatDistributions>,
         no_binary: &NoBinary,
         no_build: &NoBuild,
+        candidate_filters: &CandidateFilters,
     ) -> Self {
         let mut map = BTreeMap::new();
         // Create stubs for each entry in simple metadata. The full conversion
@@ -107,6 +109,7 @@ impl VersionMap {
             NoBuild::All => true,
             NoBuild::Packages(packages) => packages.contains(package_name),
         };
+        let allow_all_yanks = allowed_yanks.allows_all();
         let allowed_yanks = allowed_yanks
             .allowed_versions(package_name)
             .cloned()
@@ -122,8 +125,10 @@ impl VersionMap {
                 tags: tags.clone(),
                 python_requirement: python_requirement.clone(),
                 exclude_newer: exclude_newer.copied(),
+                allow_all_yanks,
                 allowed_yanks,
                 required_hashes,
+                candidate_filters: candidate_filters.clone(),
             }),
         }
     }
@@ -304,13 +309,22 @@ struct VersionMapLazy {
     python_requirement: PythonRequirement,
     /// Whether files newer than this timestamp should be excluded or not.
     exclude_newer: Option<ExcludeNewer>,
+    /// Whether all yanked versions are allowed.
+    allow_all_yanks: bool,
     /// Which yanked versions are allowed
     allowed_yanks: FxHashSet<Version>,
     /// The hashes of allowed distributions.
     required_hashes: Vec<HashDigest>,
+    /// The filters that may veto or re-rank each file.
+    candidate_filters: CandidateFilters,
 }
 
 impl VersionMapLazy {
+    /// Returns `true` if the given version may be selected even if it's been yanked.
+    fn allows_yanked(&self, version: &Version) -> bool {
+        self.allow_all_yanks || self.allowed_yanks.contains(version)
+    }
+
     /// Returns the distribution for the given version, if it exists.
     fn get(&self, version: &Version) -> Option<&PrioritizedDist> {
         self.get_with_version(version)
@@ -380,8 +394,17 @@ impl VersionMapLazy {
                     (false, None)
                 };
 
-                // Prioritize amongst all available files.
+                // Consult the candidate filters, if any.
                 let version = filename.version().clone();
+                let filtered = self.candidate_filters.evaluate(&CandidateFile {
+                    package_name: filename.name(),
+                    version: &version,
+                    filename: &filename,
+                    file: &file,
+                    index: &self.index,
+                });
+
+                // Prioritize amongst all available files.
                 let requires_python = file.requires_python.clone();
                 let yanked = file.yanked.clone();
                 let hashes = file.hashes.clone();
@@ -395,6 +418,7 @@ impl VersionMapLazy {
                             yanked,
                             excluded,
                             upload_time,
+                            filtered,
                         );
                         let dist = Dist::from_registry(
                             DistFilename::WheelFilename(filename),
@@ -411,6 +435,7 @@ impl VersionMapLazy {
                             yanked,
                             excluded,
                             upload_time,
+                            filtered,
                         );
                         let dist = Dist::from_registry(
                             DistFilename::SourceDistFilename(filename),
@@ -439,6 +464,7 @@ impl VersionMapLazy {
         yanked: Option<Yanked>,
         excluded: bool,
         upload_time: Option<i64>,
+        filtered: Result<Rank, String>,
     ) -> SourceDistCompatibility {
         // Check if builds are disabled
         if self.no_build {
@@ -454,11 +480,19 @@ impl VersionMapLazy {
 
         // Check if yanked
         if let Some(yanked) = yanked {
-            if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
+            if yanked.is_yanked() && !self.allows_yanked(version) {
                 return SourceDistCompatibility::Incompatible(IncompatibleSource::Yanked(yanked));
             }
         }
 
+        // Check if rejected by a candidate filter
+        let rank = match filtered {
+            Ok(rank) => rank,
+            Err(reason) => {
+                return SourceDistCompatibility::Incompatible(IncompatibleSource::Filtered(reason))
+            }
+        };
+
         // Check if Python version is supported
         // Source distributions must meet both the _target_ Python version and the
         // _installed_ Python version (to build successfully)
@@ -488,7 +522,7 @@ impl VersionMapLazy {
             }
         };
 
-        SourceDistCompatibility::Compatible(hash)
+        SourceDistCompatibility::Compatible(hash, rank)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -501,6 +535,7 @@ impl VersionMapLazy {
         yanked: Option<Yanked>,
         excluded: bool,
         upload_time: Option<i64>,
+        filtered: Result<Rank, String>,
     ) -> WheelCompatibility {
         // Check if binaries are disabled
         if self.no_binary {
@@ -514,11 +549,19 @@ impl VersionMapLazy {
 
         // Check if yanked
         if let Some(yanked) = yanked {
-            if yanked.is_yanked() && !self.allowed_yanks.contains(version) {
+            if yanked.is_yanked() && !self.allows_yanked(version) {
                 return WheelCompatibility::Incompatible(IncompatibleWheel::Yanked(yanked));
             }
         }
 
+        // Check if rejected by a candidate filter
+        let rank = match filtered {
+            Ok(rank) => rank,
+            Err(reason) => {
+                return WheelCompatibility::Incompatible(IncompatibleWheel::Filtered(reason))
+            }
+        };
+
         // Check for a Python version incompatibility`
         if let Some(requires_python) = requires_python {
             if !requires_python.contains(self.python_requirement.target()) {
@@ -552,7 +595,7 @@ impl VersionMapLazy {
             }
         };
 
-        WheelCompatibility::Compatible(hash, priority)
+        WheelCompatibility::Compatible(hash, rank, priority)
     }
 }
 
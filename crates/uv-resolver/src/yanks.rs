@@ -6,15 +6,46 @@ use uv_normalize::PackageName;
 
 use crate::{Manifest, Preference};
 
+/// The strategy to use when considering yanked versions (per PEP 592) as candidates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum YankedMode {
+    /// Allow yanked versions only if they're pinned exactly (e.g., `foo==1.0.0`) in the
+    /// requirements or preferences.
+    #[default]
+    AllowPinned,
+
+    /// Allow all yanked versions.
+    Allow,
+
+    /// Disallow all yanked versions, even if they're pinned exactly.
+    Disallow,
+}
+
 /// A set of package versions that are permitted, even if they're marked as yanked by the
 /// relevant index.
 #[derive(Debug, Default, Clone)]
-pub struct AllowedYanks(FxHashMap<PackageName, FxHashSet<Version>>);
+pub struct AllowedYanks {
+    mode: YankedMode,
+    versions: FxHashMap<PackageName, FxHashSet<Version>>,
+}
 
 impl AllowedYanks {
-    pub fn from_manifest(manifest: &Manifest, markers: &MarkerEnvironment) -> Self {
+    pub fn from_manifest(
+        manifest: &Manifest,
+        markers: &MarkerEnvironment,
+        mode: YankedMode,
+    ) -> Self {
         let mut allowed_yanks = FxHashMap::<PackageName, FxHashSet<Version>>::default();
 
+        // Under `allow` and `disallow`, the pins themselves are irrelevant.
+        if mode != YankedMode::AllowPinned {
+            return Self {
+                mode,
+                versions: allowed_yanks,
+            };
+        }
+
         for requirement in manifest
             .requirements(markers)
             .chain(manifest.preferences.iter().map(Preference::requirement))
@@ -36,12 +67,20 @@ impl AllowedYanks {
                     .insert(specifier.version().clone());
             }
         }
-        Self(allowed_yanks)
+        Self {
+            mode,
+            versions: allowed_yanks,
+        }
+    }
+
+    /// Returns `true` if all yanked versions are allowed, regardless of the requirements.
+    pub fn allows_all(&self) -> bool {
+        self.mode == YankedMode::Allow
     }
 
     /// Returns versions for the given package which are allowed even if marked as yanked by the
     /// relevant index.
     pub fn allowed_versions(&self, package_name: &PackageName) -> Option<&FxHashSet<Version>> {
-        self.0.get(package_name)
+        self.versions.get(package_name)
     }
 }
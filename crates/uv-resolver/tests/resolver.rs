@@ -5,21 +5,25 @@
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
+use url::Url;
 
-use distribution_types::{IndexLocations, Resolution, SourceDist};
+use distribution_types::{BuildableSource, IndexLocations, Resolution, SourceDist, VersionOrUrl};
 use pep508_rs::{MarkerEnvironment, Requirement, StringVersion};
 use platform_tags::{Arch, Os, Platform, Tags};
 use uv_cache::Cache;
 use uv_client::RegistryClientBuilder;
 use uv_configuration::{BuildKind, Constraints, NoBinary, NoBuild, Overrides, SetupPyStrategy};
 use uv_interpreter::{find_default_python, Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
 use uv_resolver::{
     DisplayResolutionGraph, ExcludeNewer, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options,
     OptionsBuilder, PreReleaseMode, Preference, ResolutionGraph, ResolutionMode, Resolver,
+    ResolverReporter, YankedMode,
 };
 use uv_types::{
     BuildContext, BuildIsolation, EmptyInstalledPackages, HashStrategy, SourceBuildTrait,
@@ -118,6 +122,16 @@ async fn resolve(
     options: Options,
     markers: &'static MarkerEnvironment,
     tags: &Tags,
+) -> Result<ResolutionGraph> {
+    resolve_with_reporter(manifest, options, markers, tags, None).await
+}
+
+async fn resolve_with_reporter(
+    manifest: Manifest,
+    options: Options,
+    markers: &'static MarkerEnvironment,
+    tags: &Tags,
+    reporter: Option<RecordingReporter>,
 ) -> Result<ResolutionGraph> {
     let client = RegistryClientBuilder::new(Cache::temp()?).build();
     let flat_index = FlatIndex::default();
@@ -142,9 +156,44 @@ async fn resolve(
         &build_context,
         &installed_packages,
     )?;
+    let resolver = match reporter {
+        Some(reporter) => resolver.with_reporter(reporter),
+        None => resolver,
+    };
     Ok(resolver.resolve().await?)
 }
 
+/// A [`ResolverReporter`] that records the packages that were fetched and resolved.
+#[derive(Default, Clone)]
+struct RecordingReporter {
+    fetched: Arc<Mutex<Vec<PackageName>>>,
+    resolved: Arc<Mutex<Vec<PackageName>>>,
+}
+
+impl ResolverReporter for RecordingReporter {
+    fn on_fetch(&self, name: &PackageName) {
+        self.fetched.lock().unwrap().push(name.clone());
+    }
+
+    fn on_progress(&self, name: &PackageName, _version: &VersionOrUrl) {
+        self.resolved.lock().unwrap().push(name.clone());
+    }
+
+    fn on_complete(&self) {}
+
+    fn on_build_start(&self, _source: &BuildableSource) -> usize {
+        0
+    }
+
+    fn on_build_complete(&self, _source: &BuildableSource, _id: usize) {}
+
+    fn on_checkout_start(&self, _url: &Url, _rev: &str) -> usize {
+        0
+    }
+
+    fn on_checkout_complete(&self, _url: &Url, _rev: &str, _index: usize) {}
+}
+
 macro_rules! assert_snapshot {
     ($value:expr, @$snapshot:literal) => {
         let snapshot = anstream::adapter::strip_str(&format!("{}", $value)).to_string();
@@ -178,6 +227,40 @@ async fn black() -> Result<()> {
     Ok(())
 }
 
+/// Report each package as its versions are fetched, and again as it's resolved.
+#[tokio::test]
+async fn black_reporter() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("black<=23.9.1").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let reporter = RecordingReporter::default();
+    let resolution = resolve_with_reporter(
+        manifest,
+        options,
+        &MARKERS_311,
+        &TAGS_311,
+        Some(reporter.clone()),
+    )
+    .await?;
+    assert_eq!(resolution.len(), 6);
+
+    // Every resolved package was fetched first, and each package is only fetched once.
+    let fetched = reporter.fetched.lock().unwrap().clone();
+    let resolved = reporter.resolved.lock().unwrap().clone();
+    assert!(!resolved.is_empty());
+    for name in &resolved {
+        assert!(fetched.contains(name), "{name} wasn't fetched: {fetched:?}");
+    }
+    let mut unique = fetched.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), fetched.len(), "{fetched:?}");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn black_colorama() -> Result<()> {
     let manifest = Manifest::simple(vec![
@@ -303,6 +386,11 @@ async fn black_mypy_extensions() -> Result<()> {
         # via black
     "###);
 
+    assert_eq!(
+        resolution.constraints_for(&PackageName::from_str("mypy-extensions")?),
+        &[Requirement::from_str("mypy-extensions<0.4.4")?]
+    );
+
     Ok(())
 }
 
@@ -379,6 +467,53 @@ async fn black_flake8() -> Result<()> {
         # via black
     "###);
 
+    assert_eq!(resolution.constraints().count(), 0);
+
+    Ok(())
+}
+
+/// Resolve `black` with an override on `mypy-extensions`, to ensure that overrides replace the
+/// declared requirement and are reported in the resolution.
+#[tokio::test]
+async fn black_override_mypy_extensions() -> Result<()> {
+    let manifest = Manifest::new(
+        vec![Requirement::from_str("black<=23.9.1").unwrap()],
+        Constraints::default(),
+        Overrides::from_requirements(vec![Requirement::from_str("mypy-extensions<1").unwrap()]),
+        vec![],
+        None,
+        vec![],
+        Exclusions::default(),
+        vec![],
+    );
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    black==23.9.1
+    click==8.1.7
+        # via black
+    mypy-extensions==0.4.4
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+    "###);
+
+    assert_eq!(
+        resolution.overrides_for(&PackageName::from_str("mypy-extensions")?),
+        &[Requirement::from_str("mypy-extensions<1")?]
+    );
+    assert!(resolution
+        .overrides_for(&PackageName::from_str("click")?)
+        .is_empty());
+
     Ok(())
 }
 
@@ -651,6 +786,118 @@ async fn pylint_allow_explicit_prerelease_with_marker() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a yanked version of `attrs` that's pinned exactly, which is allowed by default.
+#[tokio::test]
+async fn yanked_allow_pinned() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("attrs==21.1.0").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    attrs==21.1.0
+    "###);
+
+    Ok(())
+}
+
+/// Fail to resolve a yanked version of `attrs` that's only matched by a range, which is
+/// disallowed by default.
+#[tokio::test]
+async fn yanked_allow_pinned_range() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("attrs>20.3.0,<21.2.0").unwrap()]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let err = resolve(manifest, options, &MARKERS_311, &TAGS_311)
+        .await
+        .unwrap_err();
+
+    assert_snapshot!(err, @r###"
+    Because only the following versions of attrs are available:
+        attrs<=20.3.0
+        attrs==21.1.0
+        attrs>=21.2.0
+    and attrs==21.1.0 is unusable because it was yanked (reason: Installable but not importable on Python 3.4), we can conclude that attrs>20.3.0,<21.2.0 cannot be used.
+    And because you require attrs>20.3.0,<21.2.0, we can conclude that the requirements are unsatisfiable.
+    "###);
+
+    Ok(())
+}
+
+/// Resolve a yanked version of `attrs` that's only matched by a range, but pinned exactly by a
+/// preference (e.g., from an existing lockfile), which is allowed by default.
+#[tokio::test]
+async fn yanked_allow_pinned_preference() -> Result<()> {
+    let manifest = Manifest::new(
+        vec![Requirement::from_str("attrs>20.3.0,<21.2.0")?],
+        Constraints::default(),
+        Overrides::default(),
+        vec![Preference::from_requirement(Requirement::from_str(
+            "attrs==21.1.0",
+        )?)],
+        None,
+        vec![],
+        Exclusions::default(),
+        vec![],
+    );
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    attrs==21.1.0
+    "###);
+
+    Ok(())
+}
+
+/// Resolve a yanked version of `attrs` that's only matched by a range, with `--yanked allow`.
+#[tokio::test]
+async fn yanked_allow() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("attrs>20.3.0,<21.2.0").unwrap()]);
+    let options = OptionsBuilder::new()
+        .yanked_mode(YankedMode::Allow)
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    attrs==21.1.0
+    "###);
+
+    Ok(())
+}
+
+/// Fail to resolve a yanked version of `attrs`, even though it's pinned exactly, with
+/// `--yanked disallow`.
+#[tokio::test]
+async fn yanked_disallow() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from_str("attrs==21.1.0").unwrap()]);
+    let options = OptionsBuilder::new()
+        .yanked_mode(YankedMode::Disallow)
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let err = resolve(manifest, options, &MARKERS_311, &TAGS_311)
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("attrs==21.1.0 is unusable because it was yanked"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 /// Resolve `msgraph-sdk==1.0.0`, which depends on `msgraph-core>=1.0.0a2`. The resolver should
 /// fail with a pre-release-centric hint.
 #[tokio::test]
@@ -0,0 +1,219 @@
+//! Management of tools, i.e., applications installed into their own dedicated environments, with
+//! their executables exposed on a shared directory.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use configparser::ini::Ini;
+use directories::{BaseDirs, ProjectDirs};
+use tracing::debug;
+
+use uv_fs::{LockedFile, Simplified};
+use uv_normalize::PackageName;
+
+pub use crate::receipt::{Tool, ToolEntrypoint, ToolReceipt};
+
+mod receipt;
+
+/// The name of the receipt file within each tool's environment.
+const RECEIPT: &str = "uv-receipt.toml";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error("Failed to read tool receipt at: {0}")]
+    ReceiptRead(PathBuf, #[source] Box<toml::de::Error>),
+
+    #[error("Failed to serialize tool receipt")]
+    ReceiptWrite(#[from] toml::ser::Error),
+
+    #[error("Failed to parse `entry_points.txt` at: {0}: {1}")]
+    EntryPoints(PathBuf, String),
+
+    #[error("Could not determine a directory for tool executables; set `UV_TOOL_BIN_DIR`")]
+    NoExecutableDirectory,
+}
+
+/// The directory in which tools are installed, with one environment per tool.
+#[derive(Debug, Clone)]
+pub struct InstalledTools {
+    root: PathBuf,
+}
+
+impl InstalledTools {
+    /// Create a handle to the tools installed at the given root directory.
+    pub fn from_path(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Prefer, in order:
+    /// 1. The specific tool directory specified by the user via `UV_TOOL_DIR`.
+    /// 2. The system-appropriate data directory (e.g., `$XDG_DATA_HOME/uv/tools`).
+    /// 3. A `.uv/tools` directory in the current working directory.
+    pub fn from_settings() -> Self {
+        if let Some(root) = std::env::var_os("UV_TOOL_DIR") {
+            Self::from_path(root)
+        } else if let Some(project_dirs) = ProjectDirs::from("", "", "uv") {
+            Self::from_path(project_dirs.data_dir().join("tools"))
+        } else {
+            Self::from_path(Path::new(".uv").join("tools"))
+        }
+    }
+
+    /// Return the root directory of the installed tools.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return the directory of the environment for the given tool.
+    pub fn tool_dir(&self, name: &PackageName) -> PathBuf {
+        self.root.join(name.to_string())
+    }
+
+    /// Lock the tools directory, to prevent concurrent modification.
+    pub fn acquire_lock(&self) -> Result<LockedFile, Error> {
+        fs_err::create_dir_all(&self.root)?;
+        Ok(LockedFile::acquire(
+            self.root.join(".lock"),
+            self.root.user_display(),
+        )?)
+    }
+
+    /// Read the receipt for the given tool, if it's installed.
+    pub fn get(&self, name: &PackageName) -> Result<Option<Tool>, Error> {
+        let path = self.tool_dir(name).join(RECEIPT);
+        let contents = match fs_err::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let receipt: ToolReceipt =
+            toml::from_str(&contents).map_err(|err| Error::ReceiptRead(path, Box::new(err)))?;
+        Ok(Some(receipt.tool))
+    }
+
+    /// Return the installed tools, sorted by name.
+    ///
+    /// Directories without a receipt (e.g., partially installed tools) are skipped.
+    pub fn tools(&self) -> Result<Vec<(PackageName, Tool)>, Error> {
+        let mut tools = Vec::new();
+        for directory in uv_fs::directories(&self.root) {
+            let Some(name) = directory
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| PackageName::new(name.to_string()).ok())
+            else {
+                continue;
+            };
+            match self.get(&name)? {
+                Some(tool) => tools.push((name, tool)),
+                None => debug!("Skipping tool without a receipt: {}", directory.display()),
+            }
+        }
+        tools.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(tools)
+    }
+
+    /// Write the receipt for the given tool.
+    pub fn add(&self, name: &PackageName, tool: Tool) -> Result<(), Error> {
+        let contents = toml::to_string(&ToolReceipt { tool })?;
+        fs_err::write(self.tool_dir(name).join(RECEIPT), contents)?;
+        Ok(())
+    }
+
+    /// Remove the environment for the given tool, if it exists.
+    pub fn remove(&self, name: &PackageName) -> Result<(), Error> {
+        remove_dir_all(&self.tool_dir(name))
+    }
+
+    /// Move the environment for the given tool (if any) aside, such that it can be put back with
+    /// [`InstalledTools::restore`] if replacing it fails.
+    ///
+    /// The environment is moved, rather than a replacement being created elsewhere and moved into
+    /// place, since environments can't be relocated: their scripts refer to their interpreter by
+    /// its absolute path.
+    pub fn stash(&self, name: &PackageName) -> Result<(), Error> {
+        let stash = self.stash_dir(name);
+        remove_dir_all(&stash)?;
+        match fs_err::rename(self.tool_dir(name), &stash) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Replace the environment for the given tool with the one moved aside by
+    /// [`InstalledTools::stash`], if any.
+    pub fn restore(&self, name: &PackageName) -> Result<(), Error> {
+        self.remove(name)?;
+        match fs_err::rename(self.stash_dir(name), self.tool_dir(name)) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Remove the environment moved aside by [`InstalledTools::stash`], if any.
+    pub fn discard_stash(&self, name: &PackageName) -> Result<(), Error> {
+        remove_dir_all(&self.stash_dir(name))
+    }
+
+    /// Return the directory to which [`InstalledTools::stash`] moves a tool's environment.
+    ///
+    /// The leading dot ensures that the directory isn't mistaken for a tool.
+    fn stash_dir(&self, name: &PackageName) -> PathBuf {
+        self.root.join(format!(".{name}.stash"))
+    }
+}
+
+/// Remove a directory and its contents, if it exists.
+fn remove_dir_all(path: &Path) -> Result<(), Error> {
+    match fs_err::remove_dir_all(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Return the directory on which tool executables are exposed.
+///
+/// Prefer, in order:
+/// 1. The specific directory specified by the user via `UV_TOOL_BIN_DIR`.
+/// 2. `$XDG_BIN_HOME`.
+/// 3. `$HOME/.local/bin`.
+pub fn find_executable_directory() -> Result<PathBuf, Error> {
+    if let Some(dir) = std::env::var_os("UV_TOOL_BIN_DIR").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_BIN_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    BaseDirs::new()
+        .map(|dirs| dirs.home_dir().join(".local").join("bin"))
+        .ok_or(Error::NoExecutableDirectory)
+}
+
+/// Return the names of the executables declared in a distribution's `entry_points.txt`, i.e., its
+/// `console_scripts` and `gui_scripts`, sorted by name.
+pub fn entrypoint_names(dist_info: &Path) -> Result<Vec<String>, Error> {
+    let path = dist_info.join("entry_points.txt");
+    let contents = match fs_err::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let ini = Ini::new_cs()
+        .read(contents)
+        .map_err(|err| Error::EntryPoints(path, err))?;
+
+    let mut names = ["console_scripts", "gui_scripts"]
+        .iter()
+        .filter_map(|section| ini.get(*section))
+        .flat_map(|section| section.keys().cloned())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+    Ok(names)
+}
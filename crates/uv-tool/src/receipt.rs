@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A receipt for an installed tool, stored as `uv-receipt.toml` in the tool's environment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolReceipt {
+    pub tool: Tool,
+}
+
+/// The metadata recorded for an installed tool, used to list, upgrade, and uninstall it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Tool {
+    /// The requirement from which the tool was installed (e.g., `black>=24`).
+    pub requirement: String,
+    /// The Python interpreter requested when the tool was installed, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python: Option<String>,
+    /// The executables exposed by the tool.
+    #[serde(default)]
+    pub entrypoints: Vec<ToolEntrypoint>,
+}
+
+/// An executable exposed by an installed tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ToolEntrypoint {
+    /// The name of the executable (e.g., `black`).
+    pub name: String,
+    /// The path at which the executable was exposed (e.g., `~/.local/bin/black`).
+    pub install_path: PathBuf,
+}
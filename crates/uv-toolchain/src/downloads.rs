@@ -9,9 +9,11 @@ use uv_client::BetterReqwestError;
 
 use futures::TryStreamExt;
 
+use pypi_types::{HashAlgorithm, HashDigest};
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tracing::debug;
 use url::Url;
+use uv_extract::hash::Hasher;
 use uv_fs::Simplified;
 
 #[derive(Error, Debug)]
@@ -40,6 +42,14 @@ pub enum Error {
         #[source]
         err: io::Error,
     },
+    #[error("failed to read download: {0}")]
+    HashExhaustion(#[source] io::Error),
+    #[error("hash mismatch for `{installation}`: expected `{expected}`, computed `{actual}`")]
+    HashMismatch {
+        installation: String,
+        expected: String,
+        actual: String,
+    },
     #[error("failed to read toolchain directory: {0}", dir.user_display())]
     ReadError {
         dir: PathBuf,
@@ -213,7 +223,7 @@ impl PythonDownload {
         self.sha256
     }
 
-    /// Download and extract
+    /// Download and extract, verifying the archive against its SHA-256 checksum, if known.
     pub async fn fetch(
         &self,
         client: &uv_client::BaseClient,
@@ -245,8 +255,29 @@ impl PythonDownload {
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
             .into_async_read();
 
+        // Hash the archive as it's extracted.
+        let mut hashers = vec![Hasher::from(HashAlgorithm::Sha256)];
+        let mut hasher = uv_extract::hash::HashReader::new(reader.compat(), &mut hashers);
+
         debug!("Extracting {filename}");
-        uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
+        uv_extract::stream::archive(&mut hasher, filename, temp_dir.path()).await?;
+
+        // Verify the checksum, if one is known, before persisting the toolchain.
+        if let Some(expected) = self.sha256 {
+            hasher.finish().await.map_err(Error::HashExhaustion)?;
+            let actual = hashers
+                .into_iter()
+                .map(HashDigest::from)
+                .next()
+                .expect("a SHA-256 hasher was provided");
+            if *actual.digest != *expected {
+                return Err(Error::HashMismatch {
+                    installation: self.key.to_string(),
+                    expected: expected.to_string(),
+                    actual: actual.digest.to_string(),
+                });
+            }
+        }
 
         // Extract the top-level directory.
         let extracted = match uv_extract::strip_component(temp_dir.path()) {
@@ -436,3 +467,109 @@ impl Display for PythonDownload {
         f.write_str(self.key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use anyhow::Result;
+    use http_body_util::Full;
+    use hyper::body::Bytes;
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpListener;
+    use uv_client::BaseClientBuilder;
+
+    use super::{Arch, DownloadResult, Error, ImplementationName, Libc, Os, PythonDownload};
+
+    const KEY: &str = "cpython-3.12.0-linux-x86_64-gnu";
+
+    /// Create a zip archive containing a toolchain with a single file.
+    fn archive() -> Result<Vec<u8>> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("python/bin/python3", zip::write::FileOptions::default())?;
+        writer.write_all(b"#!/bin/sh\n")?;
+        Ok(writer.finish()?.into_inner())
+    }
+
+    /// Serve the given archive, returning its URL.
+    async fn serve(archive: Vec<u8>) -> Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let archive = Bytes::from(archive);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let archive = archive.clone();
+                tokio::spawn(async move {
+                    let svc = service_fn(move |_| {
+                        let archive = archive.clone();
+                        async move { Ok::<_, hyper::Error>(hyper::Response::new(Full::new(archive))) }
+                    });
+                    http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), svc)
+                        .await
+                        .unwrap();
+                });
+            }
+        });
+        Ok(format!("http://{addr}/{KEY}.zip"))
+    }
+
+    fn download(url: &str, sha256: &str) -> PythonDownload {
+        PythonDownload {
+            key: KEY,
+            implementation: ImplementationName::Cpython,
+            arch: Arch::X86_64,
+            os: Os::Linux,
+            libc: Libc::Gnu,
+            major: 3,
+            minor: 12,
+            patch: 0,
+            url: Box::leak(url.to_string().into_boxed_str()),
+            sha256: Some(Box::leak(sha256.to_string().into_boxed_str())),
+        }
+    }
+
+    /// A download that doesn't match its expected checksum should be rejected, without
+    /// persisting the toolchain.
+    #[tokio::test]
+    async fn fetch_hash_mismatch() -> Result<()> {
+        let url = serve(archive()?).await?;
+        let client = BaseClientBuilder::new().build();
+        let toolchains = tempfile::tempdir()?;
+
+        let expected = "0".repeat(64);
+        let result = download(&url, &expected)
+            .fetch(&client, toolchains.path())
+            .await;
+        let (installation, reported, actual) = match result {
+            Err(Error::HashMismatch {
+                installation,
+                expected,
+                actual,
+            }) => (installation, expected, actual),
+            Err(err) => panic!("expected a hash mismatch, got: {err}"),
+            Ok(_) => panic!("expected a hash mismatch"),
+        };
+        assert_eq!(installation, KEY);
+        assert_eq!(reported, expected);
+        assert_ne!(actual, expected);
+        assert!(!toolchains.path().join(KEY).exists());
+
+        // With the correct checksum, the toolchain is persisted.
+        let result = download(&url, &actual)
+            .fetch(&client, toolchains.path())
+            .await?;
+        assert!(matches!(result, DownloadResult::Fetched(_)));
+        assert!(toolchains
+            .path()
+            .join(KEY)
+            .join("bin")
+            .join("python3")
+            .is_file());
+
+        Ok(())
+    }
+}
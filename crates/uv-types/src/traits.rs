@@ -114,10 +114,11 @@ pub trait BuildContext: Sync {
 pub trait SourceBuildTrait {
     /// A wrapper for `uv_build::SourceBuild::get_metadata_without_build`.
     ///
-    /// For PEP 517 builds, this calls `prepare_metadata_for_build_wheel`
+    /// For PEP 517 builds, this calls `prepare_metadata_for_build_wheel` (or
+    /// `prepare_metadata_for_build_editable`, for PEP 660 editable builds)
     ///
     /// Returns the metadata directory if we're having a PEP 517 build and the
-    /// `prepare_metadata_for_build_wheel` hook exists
+    /// corresponding `prepare_metadata_for_build_*` hook exists
     fn metadata(&mut self) -> impl Future<Output = Result<Option<PathBuf>>> + Send;
 
     /// A wrapper for `uv_build::SourceBuild::build`.
@@ -42,11 +42,16 @@ fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
 }
 
 /// Write all the files that belong to a venv without any packages installed.
+///
+/// On Unix, the Python executables are symlinks to the base interpreter, unless `copies` is set,
+/// in which case the base interpreter is copied into the virtual environment instead. On Windows,
+/// the executables are always copied.
 pub fn create_bare_venv(
     location: &Path,
     interpreter: &Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    copies: bool,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<Virtualenv, Error> {
     // Determine the base Python executable; that is, the Python executable that should be
@@ -147,24 +152,34 @@ pub fn create_bare_venv(
     {
         use fs_err::os::unix::fs::symlink;
 
-        symlink(&base_python, &executable)?;
-        symlink(
-            "python",
+        let aliases = [
             scripts.join(format!("python{}", interpreter.python_major())),
-        )?;
-        symlink(
-            "python",
             scripts.join(format!(
                 "python{}.{}",
                 interpreter.python_major(),
                 interpreter.python_minor(),
             )),
-        )?;
+        ];
+        if copies {
+            // Mirror `python -m venv --copies`, which copies the interpreter under each name.
+            fs::copy(&base_python, &executable)?;
+            for alias in aliases {
+                fs::copy(&base_python, alias)?;
+            }
+        } else {
+            symlink(&base_python, &executable)?;
+            for alias in aliases {
+                symlink("python", alias)?;
+            }
+        }
     }
 
     // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
     #[cfg(windows)]
     {
+        // The executables are always copied on Windows, regardless of `copies`.
+        let _ = copies;
+
         // https://github.com/python/cpython/blob/d457345bbc6414db0443819290b04a9a4333313d/Lib/venv/__init__.py#L261-L267
         // https://github.com/pypa/virtualenv/blob/d9fdf48d69f0d0ca56140cf0381edbb5d6fe09f5/src/virtualenv/create/via_global_ref/builtin/cpython/cpython3.py#L78-L83
         // There's two kinds of applications on windows: Those that allocate a console (python.exe) and those that
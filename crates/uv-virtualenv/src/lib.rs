@@ -51,6 +51,7 @@ pub fn create_venv(
     interpreter: Interpreter,
     prompt: Prompt,
     system_site_packages: bool,
+    copies: bool,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<PythonEnvironment, Error> {
     // Create the virtualenv at the given location.
@@ -59,6 +60,7 @@ pub fn create_venv(
         &interpreter,
         prompt,
         system_site_packages,
+        copies,
         extra_cfg,
     )?;
 
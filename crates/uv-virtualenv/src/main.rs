@@ -24,6 +24,8 @@ struct Cli {
     prompt: Option<String>,
     #[clap(long)]
     system_site_packages: bool,
+    #[clap(long)]
+    copies: bool,
 }
 
 fn run() -> Result<(), uv_virtualenv::Error> {
@@ -46,6 +48,7 @@ fn run() -> Result<(), uv_virtualenv::Error> {
         &interpreter,
         Prompt::from_args(cli.prompt),
         cli.system_site_packages,
+        cli.copies,
         Vec::new(),
     )?;
     Ok(())
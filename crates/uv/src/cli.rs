@@ -1,20 +1,33 @@
+use std::ffi::OsString;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::SystemTime;
 
 use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 use clap::{Args, Parser, Subcommand};
+use url::Url;
 
-use distribution_types::{FlatIndexLocation, IndexUrl};
+use distribution_types::{FlatIndexLocation, IndexLocations, IndexPin, IndexUrl};
+use uv_attestation::{AttestationMode, PackageIdentity};
 use uv_auth::KeyringProvider;
 use uv_cache::CacheArgs;
-use uv_configuration::IndexStrategy;
-use uv_configuration::{ConfigSettingEntry, PackageNameSpecifier};
-use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{AnnotationStyle, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_client::Connectivity;
+use uv_configuration::{parse_bandwidth, Concurrency, IndexStrategy};
+use uv_configuration::{ConfigSettingEntry, ManylinuxVersion, PackageNameSpecifier, TargetTriple};
+use uv_normalize::{ExtraName, GroupName, PackageName};
+use uv_publish::TrustedPublishing;
+use uv_resolver::{
+    AnnotationStyle, ExcludeNewer, LicensePolicyMode, PreReleaseMode, ResolutionMode, YankedMode,
+};
 use uv_toolchain::PythonVersion;
 
-use crate::commands::{extra_name_with_clap_error, ListFormat, VersionFormat};
+use crate::commands::{
+    extra_name_with_clap_error, AuditFormat, AuditSeverity, BundleFormat, CompileFormat,
+    ListFormat, SbomFormat, VersionFormat,
+};
 use crate::compat;
 
 #[derive(Parser)]
@@ -70,6 +83,28 @@ pub(crate) struct GlobalArgs {
     /// included in your system's certificate store.
     #[arg(global = true, long, env = "UV_NATIVE_TLS")]
     pub(crate) native_tls: bool,
+
+    /// The maximum number of requests (e.g., for package versions, metadata, or distributions) to
+    /// have in flight at once.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_CONCURRENT_DOWNLOADS",
+        default_value_t = Concurrency::DEFAULT_DOWNLOADS
+    )]
+    pub(crate) concurrent_downloads: NonZeroUsize,
+
+    /// The maximum number of concurrent requests to any single host (e.g., an index or artifact
+    /// proxy).
+    ///
+    /// By default, requests to each host are unlimited, beyond `--concurrent-downloads`.
+    #[arg(global = true, long, env = "UV_MAX_CONNECTIONS_PER_HOST")]
+    pub(crate) max_connections_per_host: Option<NonZeroUsize>,
+
+    /// The maximum average download bandwidth, in bytes per second, with an optional `K`, `M`, or
+    /// `G` suffix (e.g., `10M`).
+    #[arg(global = true, long, env = "UV_MAX_BANDWIDTH", value_parser = parse_bandwidth)]
+    pub(crate) max_bandwidth: Option<u64>,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -102,6 +137,12 @@ pub(crate) enum Commands {
     /// Create a virtual environment.
     #[clap(alias = "virtualenv", alias = "v")]
     Venv(VenvArgs),
+    /// Run a Python script, installing the dependencies declared in its inline metadata.
+    Run(RunArgs),
+    /// Install and manage command-line tools provided by Python packages.
+    Tool(ToolNamespace),
+    /// Upload distributions to a package index.
+    Publish(PublishArgs),
     /// Manage the cache.
     Cache(CacheNamespace),
     /// Manage the `uv` executable.
@@ -146,9 +187,11 @@ pub(crate) enum CacheCommand {
     /// Clear the cache, removing all entries or those linked to specific packages.
     Clean(CleanArgs),
     /// Prune all unreachable objects from the cache.
-    Prune,
+    Prune(PruneArgs),
     /// Show the cache directory.
     Dir,
+    /// Show the disk usage of the cache, broken down by bucket.
+    Size,
 }
 
 #[derive(Args)]
@@ -158,6 +201,26 @@ pub(crate) struct CleanArgs {
     pub(crate) package: Vec<PackageName>,
 }
 
+#[derive(Args)]
+pub(crate) struct PruneArgs {
+    /// Also remove any cache entries that haven't been used since the given time.
+    ///
+    /// Accepts RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`), timestamps without a timezone
+    /// offset, which are interpreted as UTC (e.g., `2006-12-02T02:07:43`), and UTC dates in the
+    /// same format (e.g., `2006-12-02`), which are interpreted as the start of the day.
+    ///
+    /// The last use of an entry is approximated by the access and modification times of its
+    /// files.
+    #[arg(long, value_parser = parse_timestamp)]
+    pub(crate) unused_since: Option<SystemTime>,
+
+    /// Also remove the least recently used cache entries until the cache fits within the given
+    /// size, in bytes, with an optional (binary) unit suffix: `K`, `M`, or `G` (e.g., `500M` or
+    /// `10G`).
+    #[arg(long, value_parser = parse_size)]
+    pub(crate) max_size: Option<u64>,
+}
+
 #[derive(Args)]
 pub(crate) struct PipNamespace {
     #[clap(subcommand)]
@@ -182,6 +245,12 @@ pub(crate) enum PipCommand {
     Show(PipShowArgs),
     /// Verify installed packages have compatible dependencies.
     Check(PipCheckArgs),
+    /// Generate a software bill of materials (SBOM) for the installed packages.
+    Sbom(PipSbomArgs),
+    /// Audit the installed packages for known vulnerabilities.
+    Audit(PipAuditArgs),
+    /// Pack the installed packages into a self-contained, runnable bundle.
+    Bundle(PipBundleArgs),
 }
 
 /// A re-implementation of `Option`, used to avoid Clap's automatic `Option` flattening in
@@ -201,6 +270,32 @@ impl<T> Maybe<T> {
     }
 }
 
+/// Parse a point in time, as an RFC 3339 timestamp, a timestamp without a timezone offset, or a
+/// date, all interpreted as UTC.
+fn parse_timestamp(input: &str) -> Result<SystemTime, String> {
+    if let Ok(date) = NaiveDate::from_str(input) {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc().into());
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Ok(datetime.with_timezone(&Utc).into());
+    }
+    if let Ok(datetime) = NaiveDateTime::from_str(input) {
+        return Ok(datetime.and_utc().into());
+    }
+    Err(format!(
+        "`{input}` is not a valid date or timestamp (expected, e.g., `2006-12-02` or `2006-12-02T02:07:43Z`)"
+    ))
+}
+
+/// Parse a size, in bytes, with an optional (binary) unit suffix: `K`, `M`, or `G`.
+fn parse_size(input: &str) -> Result<u64, String> {
+    if input.trim() == "0" {
+        return Ok(0);
+    }
+    parse_bandwidth(input)
+        .map_err(|_| format!("`{input}` is not a valid size (expected, e.g., `500M` or `10G`)"))
+}
+
 /// Parse a string into an [`IndexUrl`], mapping the empty string to `None`.
 fn parse_index_url(input: &str) -> Result<Maybe<IndexUrl>, String> {
     if input.is_empty() {
@@ -252,6 +347,11 @@ pub(crate) struct PipCompileArgs {
     #[clap(long, conflicts_with = "extra")]
     pub(crate) all_extras: bool,
 
+    /// Include dependencies from the given dependency group (as defined in the
+    /// `[dependency-groups]` table of a `pyproject.toml`); may be provided more than once.
+    #[clap(long)]
+    pub(crate) group: Vec<GroupName>,
+
     /// Ignore package dependencies, instead only add those packages explicitly listed
     /// on the command line to the resulting the requirements file.
     #[clap(long)]
@@ -266,10 +366,24 @@ pub(crate) struct PipCompileArgs {
     #[clap(long, hide = true)]
     pub(crate) pre: bool,
 
+    /// The strategy to use when considering yanked versions.
+    ///
+    /// By default, `uv` only selects a yanked version if it's pinned exactly (e.g., via
+    /// `foo==1.0.0`).
+    #[clap(long, value_enum, default_value_t = YankedMode::default(), env = "UV_YANKED")]
+    pub(crate) yanked: YankedMode,
+
     /// Write the compiled requirements to the given `requirements.txt` file.
     #[clap(long, short)]
     pub(crate) output_file: Option<PathBuf>,
 
+    /// Prefer the versions pinned by an existing `poetry.lock` or `Pipfile.lock` file.
+    ///
+    /// Allows migrating from Poetry or Pipenv without changing every pinned version. Pins in the
+    /// output file (if any) take precedence over those in the imported lockfile.
+    #[clap(long)]
+    pub(crate) import_lock: Option<PathBuf>,
+
     /// Include extras in the output file.
     ///
     /// By default, `uv` strips extras, as any packages pulled in by the extras are already included
@@ -290,6 +404,15 @@ pub(crate) struct PipCompileArgs {
     #[clap(long, default_value_t=AnnotationStyle::Split, value_enum)]
     pub(crate) annotation_style: AnnotationStyle,
 
+    /// Select the output format between: `requirements-txt` (default), `json`, `cyclonedx`, or
+    /// `spdx`.
+    ///
+    /// The `json` format omits the header and index comments, and records the source, hashes,
+    /// and dependents of every resolved package. The `cyclonedx` and `spdx` formats write a
+    /// software bill of materials for the resolution, as with `uv pip sbom`.
+    #[clap(long, value_enum, default_value_t = CompileFormat::default())]
+    pub(crate) format: CompileFormat,
+
     /// Change header comment to reflect custom command wrapping `uv pip compile`.
     #[clap(long, env = "UV_CUSTOM_COMPILE_COMMAND")]
     pub(crate) custom_compile_command: Option<String>,
@@ -357,6 +480,13 @@ pub(crate) struct PipCompileArgs {
     #[clap(long, default_value_t, value_enum, env = "UV_INDEX_STRATEGY")]
     pub(crate) index_strategy: IndexStrategy,
 
+    /// Only fetch the given package from the given index, ignoring all other indexes and the
+    /// `--index-strategy`, as in `--index-pin torch=https://download.pytorch.org/whl/cpu`.
+    ///
+    /// May be provided multiple times.
+    #[clap(long, value_name = "PACKAGE=URL")]
+    pub(crate) index_pin: Vec<IndexPin>,
+
     /// Attempt to use `keyring` for authentication for index urls
     ///
     /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
@@ -367,7 +497,8 @@ pub(crate) struct PipCompileArgs {
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file containing a
+    /// flat list of links to package files.
     ///
     /// If a URL, the page must contain a flat list of links to package files.
     #[clap(long, short)]
@@ -404,9 +535,19 @@ pub(crate) struct PipCompileArgs {
     /// exit with an error.
     ///
     /// Alias for `--only-binary :all:`.
-    #[clap(long, conflicts_with = "only_binary")]
+    #[clap(long, conflicts_with = "no_binary", conflicts_with = "only_binary")]
     pub(crate) no_build: bool,
 
+    /// Don't use pre-built wheels.
+    ///
+    /// The given packages will be resolved from a source distribution, which will be built to
+    /// extract its metadata.
+    ///
+    /// Multiple packages may be provided. Disable binaries for all packages with `:all:`.
+    /// Clear previously specified packages with `:none:`.
+    #[clap(long, conflicts_with = "no_build")]
+    pub(crate) no_binary: Vec<PackageNameSpecifier>,
+
     /// Only use pre-built wheels; don't build source distributions.
     ///
     /// When enabled, resolving will not run code from the given packages. The cached wheels of already-built
@@ -418,6 +559,43 @@ pub(crate) struct PipCompileArgs {
     #[clap(long, conflicts_with = "no_build")]
     pub(crate) only_binary: Vec<PackageNameSpecifier>,
 
+    /// The most recent `manylinux` version to consider compatible (e.g., `manylinux2014` or
+    /// `manylinux_2_28`).
+    ///
+    /// By default, the `manylinux` version is inferred from the glibc version of the target
+    /// interpreter. Use this option to avoid wheels that require a newer glibc than is available
+    /// at runtime.
+    #[clap(long)]
+    pub(crate) max_manylinux: Option<ManylinuxVersion>,
+
+    /// Don't use platform-specific wheels.
+    ///
+    /// Only wheels tagged with the `any` platform will be considered compatible; packages without
+    /// such a wheel will be built from a source distribution.
+    #[clap(long)]
+    pub(crate) no_platform_wheels: bool,
+
+    /// Allow only packages with the given license (e.g., `MIT`).
+    ///
+    /// Licenses are read from the `License-Expression`, `License`, and `License ::` classifier
+    /// fields of each resolved package's metadata, and compared case-insensitively. May be
+    /// provided multiple times. Packages that don't declare a license are rejected. Editable
+    /// packages are exempt.
+    #[clap(long)]
+    pub(crate) allow_license: Vec<String>,
+
+    /// Reject packages with the given license (e.g., `GPL-3.0-only`).
+    ///
+    /// May be provided multiple times. Packages with multiple alternative licenses (e.g.,
+    /// `MIT OR GPL-3.0-only`) are accepted if any of them is acceptable.
+    #[clap(long)]
+    pub(crate) deny_license: Vec<String>,
+
+    /// Whether to fail (`error`) or warn (`warn`) when a resolved package violates the license
+    /// policy.
+    #[clap(long, value_enum, default_value_t = LicensePolicyMode::default())]
+    pub(crate) license_policy_mode: LicensePolicyMode,
+
     /// Settings to pass to the PEP 517 build backend, specified as `KEY=VALUE` pairs.
     #[clap(long, short = 'C', alias = "config-settings")]
     pub(crate) config_setting: Vec<ConfigSettingEntry>,
@@ -430,10 +608,25 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, short)]
     pub(crate) python_version: Option<PythonVersion>,
 
+    /// The platform for which requirements should be resolved.
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aarch64-apple-darwin`.
+    ///
+    /// Combined with `--python-version`, this allows resolving for a deployment target that differs
+    /// from the current machine.
+    ///
+    /// May be provided multiple times to resolve for several platforms at once, producing a single
+    /// set of pins in which any pins that differ by platform are guarded by environment markers.
+    #[arg(long)]
+    pub(crate) python_platform: Vec<TargetTriple>,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
-    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
-    /// format (e.g., `2006-12-02`).
+    /// Accepts RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`), timestamps without a timezone
+    /// offset, which are interpreted as UTC (e.g., `2006-12-02T02:07:43`), and UTC dates in the
+    /// same format (e.g., `2006-12-02`).
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
@@ -472,6 +665,9 @@ pub(crate) struct PipCompileArgs {
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipSyncArgs {
     /// Include all packages listed in the given `requirements.txt` files.
+    ///
+    /// Alternatively, install the distributions from a lockfile written by
+    /// `uv pip compile --format lock` that apply to the current environment.
     #[clap(required(true))]
     pub(crate) src_file: Vec<PathBuf>,
 
@@ -507,6 +703,31 @@ pub(crate) struct PipSyncArgs {
     #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
     pub(crate) link_mode: install_wheel_rs::linker::LinkMode,
 
+    /// How the scripts generated during installation should locate the Python interpreter.
+    ///
+    /// By default, scripts refer to the interpreter by its absolute path. Use `env` to run the
+    /// first `python` on the `PATH` (via `#!/usr/bin/env python`), `relative` to locate the
+    /// interpreter relative to the script (such that the environment can be moved), or provide an
+    /// absolute path to the interpreter (e.g., the path at which the environment will be deployed).
+    ///
+    /// Only applies on Unix.
+    #[clap(long)]
+    pub(crate) shebang: Option<install_wheel_rs::Shebang>,
+
+    /// Allow packages to overwrite files that were installed by other packages.
+    ///
+    /// By default, `uv` refuses to install a package that would overwrite a file belonging to
+    /// another package (unless the files are identical).
+    #[clap(long)]
+    pub(crate) allow_overwrite: bool,
+
+    /// The strategy to use when considering yanked versions.
+    ///
+    /// By default, `uv` only selects a yanked version if it's pinned exactly (e.g., via
+    /// `foo==1.0.0`).
+    #[clap(long, value_enum, default_value_t = YankedMode::default(), env = "UV_YANKED")]
+    pub(crate) yanked: YankedMode,
+
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
     /// The index given by this flag is given lower priority than all other
@@ -533,7 +754,8 @@ pub(crate) struct PipSyncArgs {
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file containing a
+    /// flat list of links to package files.
     ///
     /// If a URL, the page must contain a flat list of links to package files.
     #[clap(long, short)]
@@ -553,6 +775,13 @@ pub(crate) struct PipSyncArgs {
     #[clap(long, default_value_t, value_enum, env = "UV_INDEX_STRATEGY")]
     pub(crate) index_strategy: IndexStrategy,
 
+    /// Only fetch the given package from the given index, ignoring all other indexes and the
+    /// `--index-strategy`, as in `--index-pin torch=https://download.pytorch.org/whl/cpu`.
+    ///
+    /// May be provided multiple times.
+    #[clap(long, value_name = "PACKAGE=URL")]
+    pub(crate) index_pin: Vec<IndexPin>,
+
     /// Require a matching hash for each requirement.
     ///
     /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
@@ -567,6 +796,37 @@ pub(crate) struct PipSyncArgs {
     #[clap(long, hide = true)]
     pub(crate) require_hashes: bool,
 
+    /// Verify PEP 740 attestations for distributions from package indexes before installing them.
+    ///
+    /// With `verify`, the attestations of any distribution that has them are checked; with
+    /// `require`, every distribution from a package index must have valid attestations.
+    /// Attestations are verified against the trust root provided via `--attestation-trust-root`,
+    /// and must be signed by the identity pinned for the package via `--attestation-identity`.
+    /// The publisher listed by the index is not trusted.
+    #[clap(
+        long = "attestations",
+        value_enum,
+        default_value_t,
+        env = "UV_ATTESTATIONS"
+    )]
+    pub(crate) attestation_mode: AttestationMode,
+
+    /// A PEM file containing the Sigstore (Fulcio) root and intermediate certificates, and the
+    /// transparency log (Rekor) public keys, against which to verify attestations.
+    ///
+    /// Alternatively, Sigstore's trusted root (i.e., `trusted_root.json`) may be provided.
+    #[clap(long, env = "UV_ATTESTATION_TRUST_ROOT")]
+    pub(crate) attestation_trust_root: Option<PathBuf>,
+
+    /// The identity that a package's attestations must be signed by, as
+    /// `<package>=<source repository URI>` (e.g.,
+    /// `sampleproject=https://github.com/pypa/sampleproject`).
+    ///
+    /// Required for every package with attestations when verifying them. May be provided
+    /// multiple times.
+    #[clap(long)]
+    pub(crate) attestation_identity: Vec<PackageIdentity>,
+
     /// Attempt to use `keyring` for authentication for index urls
     ///
     /// Function's similar to `pip`'s `--keyring-provider subprocess` argument,
@@ -651,6 +911,22 @@ pub(crate) struct PipSyncArgs {
     #[clap(long, conflicts_with = "no_build")]
     pub(crate) only_binary: Vec<PackageNameSpecifier>,
 
+    /// The most recent `manylinux` version to consider compatible (e.g., `manylinux2014` or
+    /// `manylinux_2_28`).
+    ///
+    /// By default, the `manylinux` version is inferred from the glibc version of the target
+    /// interpreter. Use this option to avoid wheels that require a newer glibc than is available
+    /// at runtime.
+    #[clap(long)]
+    pub(crate) max_manylinux: Option<ManylinuxVersion>,
+
+    /// Don't use platform-specific wheels.
+    ///
+    /// Only wheels tagged with the `any` platform will be considered compatible; packages without
+    /// such a wheel will be built from a source distribution.
+    #[clap(long)]
+    pub(crate) no_platform_wheels: bool,
+
     /// Compile Python files to bytecode.
     ///
     /// By default, does not compile Python (`.py`) files to bytecode (`__pycache__/*.pyc`), instead
@@ -673,6 +949,9 @@ pub(crate) struct PipSyncArgs {
 
     /// Validate the virtual environment after completing the installation, to detect packages with
     /// missing dependencies or other issues.
+    ///
+    /// Also fails (and rolls back) the installation if a package has anomalies that would
+    /// otherwise only be warned about, such as `RECORD` entries without hashes.
     #[clap(long)]
     pub(crate) strict: bool,
 
@@ -726,6 +1005,11 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, conflicts_with = "extra")]
     pub(crate) all_extras: bool,
 
+    /// Include dependencies from the given dependency group (as defined in the
+    /// `[dependency-groups]` table of a `pyproject.toml`); may be provided more than once.
+    #[clap(long)]
+    pub(crate) group: Vec<GroupName>,
+
     /// Allow package upgrades.
     #[clap(long, short = 'U')]
     pub(crate) upgrade: bool,
@@ -771,6 +1055,24 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
     pub(crate) link_mode: install_wheel_rs::linker::LinkMode,
 
+    /// How the scripts generated during installation should locate the Python interpreter.
+    ///
+    /// By default, scripts refer to the interpreter by its absolute path. Use `env` to run the
+    /// first `python` on the `PATH` (via `#!/usr/bin/env python`), `relative` to locate the
+    /// interpreter relative to the script (such that the environment can be moved), or provide an
+    /// absolute path to the interpreter (e.g., the path at which the environment will be deployed).
+    ///
+    /// Only applies on Unix.
+    #[clap(long)]
+    pub(crate) shebang: Option<install_wheel_rs::Shebang>,
+
+    /// Allow packages to overwrite files that were installed by other packages.
+    ///
+    /// By default, `uv` refuses to install a package that would overwrite a file belonging to
+    /// another package (unless the files are identical).
+    #[clap(long)]
+    pub(crate) allow_overwrite: bool,
+
     #[clap(long, value_enum, default_value_t = ResolutionMode::default(), env = "UV_RESOLUTION")]
     pub(crate) resolution: ResolutionMode,
 
@@ -780,6 +1082,13 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, hide = true)]
     pub(crate) pre: bool,
 
+    /// The strategy to use when considering yanked versions.
+    ///
+    /// By default, `uv` only selects a yanked version if it's pinned exactly (e.g., via
+    /// `foo==1.0.0`).
+    #[clap(long, value_enum, default_value_t = YankedMode::default(), env = "UV_YANKED")]
+    pub(crate) yanked: YankedMode,
+
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
     /// The index given by this flag is given lower priority than all other
@@ -806,7 +1115,8 @@ pub(crate) struct PipInstallArgs {
     /// Locations to search for candidate distributions, beyond those found in the indexes.
     ///
     /// If a path, the target must be a directory that contains package as wheel files (`.whl`) or
-    /// source distributions (`.tar.gz` or `.zip`) at the top level.
+    /// source distributions (`.tar.gz` or `.zip`) at the top level, or an HTML file containing a
+    /// flat list of links to package files.
     ///
     /// If a URL, the page must contain a flat list of links to package files.
     #[clap(long, short)]
@@ -826,6 +1136,13 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, default_value_t, value_enum, env = "UV_INDEX_STRATEGY")]
     pub(crate) index_strategy: IndexStrategy,
 
+    /// Only fetch the given package from the given index, ignoring all other indexes and the
+    /// `--index-strategy`, as in `--index-pin torch=https://download.pytorch.org/whl/cpu`.
+    ///
+    /// May be provided multiple times.
+    #[clap(long, value_name = "PACKAGE=URL")]
+    pub(crate) index_pin: Vec<IndexPin>,
+
     /// Require a matching hash for each requirement.
     ///
     /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
@@ -840,6 +1157,37 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, hide = true)]
     pub(crate) require_hashes: bool,
 
+    /// Verify PEP 740 attestations for distributions from package indexes before installing them.
+    ///
+    /// With `verify`, the attestations of any distribution that has them are checked; with
+    /// `require`, every distribution from a package index must have valid attestations.
+    /// Attestations are verified against the trust root provided via `--attestation-trust-root`,
+    /// and must be signed by the identity pinned for the package via `--attestation-identity`.
+    /// The publisher listed by the index is not trusted.
+    #[clap(
+        long = "attestations",
+        value_enum,
+        default_value_t,
+        env = "UV_ATTESTATIONS"
+    )]
+    pub(crate) attestation_mode: AttestationMode,
+
+    /// A PEM file containing the Sigstore (Fulcio) root and intermediate certificates, and the
+    /// transparency log (Rekor) public keys, against which to verify attestations.
+    ///
+    /// Alternatively, Sigstore's trusted root (i.e., `trusted_root.json`) may be provided.
+    #[clap(long, env = "UV_ATTESTATION_TRUST_ROOT")]
+    pub(crate) attestation_trust_root: Option<PathBuf>,
+
+    /// The identity that a package's attestations must be signed by, as
+    /// `<package>=<source repository URI>` (e.g.,
+    /// `sampleproject=https://github.com/pypa/sampleproject`).
+    ///
+    /// Required for every package with attestations when verifying them. May be provided
+    /// multiple times.
+    #[clap(long)]
+    pub(crate) attestation_identity: Vec<PackageIdentity>,
+
     /// Attempt to use `keyring` for authentication for index urls
     ///
     /// Due to not having Python imports, only `--keyring-provider subprocess` argument is currently
@@ -882,6 +1230,25 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, env = "UV_BREAK_SYSTEM_PACKAGES", requires = "discovery")]
     pub(crate) break_system_packages: bool,
 
+    /// Install packages into the specified directory, rather than into the virtual or system
+    /// Python environment.
+    ///
+    /// Packages are installed at the top-level of the directory, with any scripts placed in a
+    /// `bin` subdirectory. Packages that are already present in the directory are treated as
+    /// installed, and are replaced when upgrading or reinstalling.
+    #[clap(long, conflicts_with = "user")]
+    pub(crate) target: Option<PathBuf>,
+
+    /// Install packages into the user site-packages directory, rather than into the virtual or
+    /// system Python environment.
+    ///
+    /// Like `pip install --user`, packages are installed into the user scheme of the system Python
+    /// (e.g., `~/.local/lib/python3.12/site-packages` on Linux), which can be changed by setting
+    /// `PYTHONUSERBASE`. The user site-packages directory isn't visible from within a virtual
+    /// environment, so a virtual environment can't be used with `--user`.
+    #[clap(long)]
+    pub(crate) user: bool,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[clap(long)]
@@ -924,6 +1291,43 @@ pub(crate) struct PipInstallArgs {
     #[clap(long, conflicts_with = "no_build")]
     pub(crate) only_binary: Vec<PackageNameSpecifier>,
 
+    /// The most recent `manylinux` version to consider compatible (e.g., `manylinux2014` or
+    /// `manylinux_2_28`).
+    ///
+    /// By default, the `manylinux` version is inferred from the glibc version of the target
+    /// interpreter. Use this option to avoid wheels that require a newer glibc than is available
+    /// at runtime.
+    #[clap(long)]
+    pub(crate) max_manylinux: Option<ManylinuxVersion>,
+
+    /// Don't use platform-specific wheels.
+    ///
+    /// Only wheels tagged with the `any` platform will be considered compatible; packages without
+    /// such a wheel will be built from a source distribution.
+    #[clap(long)]
+    pub(crate) no_platform_wheels: bool,
+
+    /// Allow only packages with the given license (e.g., `MIT`).
+    ///
+    /// Licenses are read from the `License-Expression`, `License`, and `License ::` classifier
+    /// fields of each resolved package's metadata, and compared case-insensitively. May be
+    /// provided multiple times. Packages that don't declare a license are rejected. Editable
+    /// packages are exempt.
+    #[clap(long)]
+    pub(crate) allow_license: Vec<String>,
+
+    /// Reject packages with the given license (e.g., `GPL-3.0-only`).
+    ///
+    /// May be provided multiple times. Packages with multiple alternative licenses (e.g.,
+    /// `MIT OR GPL-3.0-only`) are accepted if any of them is acceptable.
+    #[clap(long)]
+    pub(crate) deny_license: Vec<String>,
+
+    /// Whether to fail (`error`) or warn (`warn`) when a resolved package violates the license
+    /// policy.
+    #[clap(long, value_enum, default_value_t = LicensePolicyMode::default())]
+    pub(crate) license_policy_mode: LicensePolicyMode,
+
     /// Compile Python files to bytecode.
     ///
     /// By default, does not compile Python (`.py`) files to bytecode (`__pycache__/*.pyc`), instead
@@ -946,13 +1350,17 @@ pub(crate) struct PipInstallArgs {
 
     /// Validate the virtual environment after completing the installation, to detect packages with
     /// missing dependencies or other issues.
+    ///
+    /// Also fails (and rolls back) the installation if a package has anomalies that would
+    /// otherwise only be warned about, such as `RECORD` entries without hashes.
     #[clap(long)]
     pub(crate) strict: bool,
 
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
-    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
-    /// format (e.g., `2006-12-02`).
+    /// Accepts RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`), timestamps without a timezone
+    /// offset, which are interpreted as UTC (e.g., `2006-12-02T02:07:43`), and UTC dates in the
+    /// same format (e.g., `2006-12-02`).
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
@@ -1060,6 +1468,159 @@ pub(crate) struct PipFreezeArgs {
     pub(crate) system: bool,
 }
 
+#[derive(Args)]
+pub(crate) struct PipAuditArgs {
+    /// Read advisories from a local OSV database, rather than querying the OSV API.
+    ///
+    /// The database is a directory of OSV JSON files, such as an extracted
+    /// `https://osv-vulnerabilities.storage.googleapis.com/PyPI/all.zip` export.
+    #[clap(long)]
+    pub(crate) advisory_db: Option<PathBuf>,
+
+    /// Only fail if a vulnerability of at least the given severity is found.
+    ///
+    /// Vulnerabilities without a known severity are always considered to meet the threshold. By
+    /// default, any vulnerability causes a failure.
+    #[clap(long, value_enum)]
+    pub(crate) severity_threshold: Option<AuditSeverity>,
+
+    /// Select the output format between: `text` (default) or `json`.
+    #[clap(long, value_enum, default_value_t = AuditFormat::default())]
+    pub(crate) format: AuditFormat,
+
+    /// The Python interpreter for which packages should be audited.
+    ///
+    /// By default, `uv` audits packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, group = "discovery")]
+    pub(crate) python: Option<String>,
+
+    /// Audit packages for the system Python.
+    ///
+    /// By default, `uv` audits packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found. The
+    /// `--system` option instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, env = "UV_SYSTEM_PYTHON", group = "discovery")]
+    pub(crate) system: bool,
+
+    /// Run offline, i.e., without accessing the network.
+    ///
+    /// Requires `--advisory-db`.
+    #[arg(global = true, long)]
+    pub(crate) offline: bool,
+}
+
+#[derive(Args)]
+pub(crate) struct PipSbomArgs {
+    /// Select the output format between: `cyclonedx` (default) or `spdx`.
+    #[clap(long, value_enum, default_value_t = SbomFormat::default())]
+    pub(crate) format: SbomFormat,
+
+    /// The Python interpreter for which packages should be listed.
+    ///
+    /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, group = "discovery")]
+    pub(crate) python: Option<String>,
+
+    /// List packages for the system Python.
+    ///
+    /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found. The `--system` option
+    /// instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, env = "UV_SYSTEM_PYTHON", group = "discovery")]
+    pub(crate) system: bool,
+}
+
+#[derive(Args)]
+#[command(group = clap::ArgGroup::new("entrypoint").required(true))]
+pub(crate) struct PipBundleArgs {
+    /// The path to which the bundle should be written.
+    #[clap(long, short)]
+    pub(crate) output: PathBuf,
+
+    /// The entry point to run when the bundle is executed, as `module:function` (e.g.,
+    /// `app.handler:main`) or `module` (to run the module as `__main__`).
+    #[clap(long, group = "entrypoint")]
+    pub(crate) entry_point: Option<install_wheel_rs::EntryPoint>,
+
+    /// Run the console script with the given name, as declared by one of the installed packages.
+    #[clap(long, group = "entrypoint")]
+    pub(crate) script: Option<String>,
+
+    /// Select the bundle format between: `zipapp` (default), a single archive runnable with
+    /// `python app.pyz`; or `directory`, a relocatable directory runnable with `python app/`.
+    ///
+    /// Packages with native extension modules can't be imported from a zipapp, and require the
+    /// `directory` format.
+    #[clap(long, value_enum, default_value_t = BundleFormat::default())]
+    pub(crate) format: BundleFormat,
+
+    /// The interpreter to reference in the zipapp's shebang line.
+    #[clap(
+        long,
+        default_value = "/usr/bin/env python3",
+        conflicts_with = "no_shebang"
+    )]
+    pub(crate) shebang: String,
+
+    /// Omit the shebang line from the zipapp.
+    #[clap(long)]
+    pub(crate) no_shebang: bool,
+
+    /// Exclude the specified package(s) from the bundle.
+    #[clap(long)]
+    pub(crate) r#exclude: Vec<PackageName>,
+
+    /// The Python interpreter whose packages should be bundled.
+    ///
+    /// By default, `uv` bundles packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment, group = "discovery")]
+    pub(crate) python: Option<String>,
+
+    /// Bundle packages from the system Python.
+    ///
+    /// By default, `uv` bundles packages in the currently activated virtual environment, or a
+    /// virtual environment (`.venv`) located in the current working directory or any parent
+    /// directory, falling back to the system Python if no virtual environment is found. The
+    /// `--system` option instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[clap(long, env = "UV_SYSTEM_PYTHON", group = "discovery")]
+    pub(crate) system: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipListArgs {
@@ -1142,6 +1703,11 @@ pub(crate) struct PipCheckArgs {
     /// should be used with caution.
     #[clap(long, env = "UV_SYSTEM_PYTHON", group = "discovery")]
     pub(crate) system: bool,
+
+    /// Verify the installed files of each package against its `RECORD`, to detect files that are
+    /// missing, have been modified since installation, or were added without being recorded.
+    #[clap(long)]
+    pub(crate) verify: bool,
 }
 
 #[derive(Args)]
@@ -1239,6 +1805,12 @@ pub(crate) struct VenvArgs {
     #[clap(long)]
     pub(crate) system_site_packages: bool,
 
+    /// Copy the Python interpreter into the virtual environment, rather than symlinking it.
+    ///
+    /// Only affects Unix platforms; on Windows, the interpreter is always copied.
+    #[clap(long)]
+    pub(crate) copies: bool,
+
     /// The method to use when installing packages from the global cache.
     ///
     /// This option is only used for installing seed packages.
@@ -1298,8 +1870,9 @@ pub(crate) struct VenvArgs {
 
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
-    /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
-    /// format (e.g., `2006-12-02`).
+    /// Accepts RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`), timestamps without a timezone
+    /// offset, which are interpreted as UTC (e.g., `2006-12-02T02:07:43`), and UTC dates in the
+    /// same format (e.g., `2006-12-02`).
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
@@ -1307,6 +1880,242 @@ pub(crate) struct VenvArgs {
     pub(crate) compat_args: compat::VenvCompatArgs,
 }
 
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct RunArgs {
+    /// The path to the Python script to run.
+    ///
+    /// If the script contains a `# /// script` metadata block (PEP 723), its `dependencies` are
+    /// installed into a cached environment, and its `requires-python` is enforced.
+    pub(crate) script: PathBuf,
+
+    /// The arguments to pass to the script.
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub(crate) args: Vec<OsString>,
+
+    /// The Python interpreter to use to run the script.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[clap(long, short, verbatim_doc_comment)]
+    pub(crate) python: Option<String>,
+
+    /// Recreate the script's cached environment, and refresh all cached data.
+    ///
+    /// Without this flag, the environment is reused for as long as the script's dependencies,
+    /// the interpreter, the index URLs, and `--exclude-newer` are unchanged, even if newer
+    /// versions of the dependencies are available.
+    #[clap(long, conflicts_with = "offline")]
+    pub(crate) refresh: bool,
+
+    /// Recreate the script's cached environment if it depends on a specific package, and
+    /// refresh cached data for that package.
+    #[clap(long, conflicts_with = "offline")]
+    pub(crate) refresh_package: Vec<PackageName>,
+
+    #[command(flatten)]
+    pub(crate) environment: EnvironmentArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct ToolNamespace {
+    #[clap(subcommand)]
+    pub(crate) command: ToolCommand,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ToolCommand {
+    /// Install a tool into its own environment, exposing its executables.
+    Install(ToolInstallArgs),
+    /// List the installed tools and their executables.
+    List,
+    /// Upgrade installed tools to the latest compatible versions.
+    Upgrade(ToolUpgradeArgs),
+    /// Uninstall a tool, removing its environment and executables.
+    Uninstall(ToolUninstallArgs),
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct ToolInstallArgs {
+    /// The tool to install, as a requirement (e.g., `black`, or `black>=24`).
+    pub(crate) requirement: String,
+
+    /// The Python interpreter to use for the tool's environment.
+    #[clap(long, short)]
+    pub(crate) python: Option<String>,
+
+    /// Reinstall the tool if it's already installed, and overwrite any existing executables with
+    /// the same names.
+    #[clap(long)]
+    pub(crate) force: bool,
+
+    #[command(flatten)]
+    pub(crate) environment: EnvironmentArgs,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct ToolUpgradeArgs {
+    /// The names of the tools to upgrade.
+    #[clap(required_unless_present = "all", conflicts_with = "all")]
+    pub(crate) names: Vec<PackageName>,
+
+    /// Upgrade all installed tools.
+    #[clap(long)]
+    pub(crate) all: bool,
+
+    #[command(flatten)]
+    pub(crate) environment: EnvironmentArgs,
+}
+
+#[derive(Args)]
+pub(crate) struct ToolUninstallArgs {
+    /// The names of the tools to uninstall.
+    #[clap(required = true)]
+    pub(crate) names: Vec<PackageName>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct PublishArgs {
+    /// The wheels and source distributions to upload, as paths or glob patterns.
+    ///
+    /// Defaults to the contents of the `dist` directory. Files that aren't wheels or source
+    /// distributions are skipped.
+    #[clap(default_value = "dist/*")]
+    pub(crate) files: Vec<String>,
+
+    /// The URL of the upload endpoint (by default: <https://upload.pypi.org/legacy/>).
+    #[clap(
+        long,
+        env = "UV_PUBLISH_URL",
+        default_value = "https://upload.pypi.org/legacy/"
+    )]
+    pub(crate) publish_url: Url,
+
+    /// The username for the upload.
+    #[clap(long, short, env = "UV_PUBLISH_USERNAME", requires = "password")]
+    pub(crate) username: Option<String>,
+
+    /// The password for the upload.
+    #[clap(long, env = "UV_PUBLISH_PASSWORD", requires = "username")]
+    pub(crate) password: Option<String>,
+
+    /// An API token for the upload, sent as the password for the `__token__` user.
+    #[clap(
+        long,
+        short,
+        env = "UV_PUBLISH_TOKEN",
+        conflicts_with_all = ["username", "password"]
+    )]
+    pub(crate) token: Option<String>,
+
+    /// Skip files that already exist on the index, rather than failing.
+    ///
+    /// As there's no standard response for existing files, this relies on recognizing the
+    /// responses of known indexes (e.g., PyPI, pypiserver, Nexus, and Artifactory).
+    #[clap(long)]
+    pub(crate) skip_existing: bool,
+
+    /// The number of times to retry an upload after a transient failure, such as a connection
+    /// error or a server error.
+    #[clap(long, default_value_t = 3)]
+    pub(crate) retries: u32,
+
+    /// Attempt to use `keyring` for authentication for the upload URL.
+    #[clap(long, default_value_t, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: KeyringProvider,
+
+    /// Whether to exchange an OIDC token from the CI provider for a short-lived upload token.
+    ///
+    /// Trusted publishing is supported in GitHub Actions and GitLab CI. By default, it's used
+    /// when no other credentials are available and the CI job was granted an OIDC token.
+    /// Credentials given explicitly take precedence.
+    #[clap(long, default_value_t, value_enum, env = "UV_TRUSTED_PUBLISHING")]
+    pub(crate) trusted_publishing: TrustedPublishing,
+}
+
+/// Options for resolving and installing packages into an environment managed by `uv`, i.e., a
+/// script or tool environment.
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct EnvironmentArgs {
+    /// The method to use when installing packages from the global cache.
+    ///
+    /// Defaults to `clone` (also known as Copy-on-Write) on macOS, and `hardlink` on Linux and
+    /// Windows.
+    #[clap(long, value_enum, default_value_t = install_wheel_rs::linker::LinkMode::default())]
+    pub(crate) link_mode: install_wheel_rs::linker::LinkMode,
+
+    /// The URL of the Python package index (by default: <https://pypi.org/simple>).
+    ///
+    /// The index given by this flag is given lower priority than all other
+    /// indexes specified via the `--extra-index-url` flag.
+    #[clap(long, short, env = "UV_INDEX_URL", value_parser = parse_index_url)]
+    pub(crate) index_url: Option<Maybe<IndexUrl>>,
+
+    /// Extra URLs of package indexes to use, in addition to `--index-url`.
+    ///
+    /// All indexes given via this flag take priority over the index
+    /// in `--index-url` (which defaults to PyPI). And when multiple
+    /// `--extra-index-url` flags are given, earlier values take priority.
+    #[clap(long, env = "UV_EXTRA_INDEX_URL", value_delimiter = ' ', value_parser = parse_index_url)]
+    pub(crate) extra_index_url: Vec<Maybe<IndexUrl>>,
+
+    /// Ignore the registry index (e.g., PyPI), instead relying on direct URL dependencies.
+    #[clap(long)]
+    pub(crate) no_index: bool,
+
+    /// The strategy to use when resolving against multiple index URLs.
+    #[clap(long, default_value_t, value_enum, env = "UV_INDEX_STRATEGY")]
+    pub(crate) index_strategy: IndexStrategy,
+
+    /// Attempt to use `keyring` for authentication for index urls
+    #[clap(long, default_value_t, value_enum, env = "UV_KEYRING_PROVIDER")]
+    pub(crate) keyring_provider: KeyringProvider,
+
+    /// Run offline, i.e., without accessing the network.
+    #[arg(long)]
+    pub(crate) offline: bool,
+
+    /// Limit candidate packages to those that were uploaded prior to the given date.
+    ///
+    /// Accepts RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`), timestamps without a timezone
+    /// offset, which are interpreted as UTC (e.g., `2006-12-02T02:07:43`), and UTC dates in the
+    /// same format (e.g., `2006-12-02`).
+    #[arg(long)]
+    pub(crate) exclude_newer: Option<ExcludeNewer>,
+}
+
+impl EnvironmentArgs {
+    /// Return the index locations to resolve against.
+    pub(crate) fn index_locations(&self) -> IndexLocations {
+        IndexLocations::new(
+            self.index_url.clone().and_then(Maybe::into_option),
+            self.extra_index_url
+                .iter()
+                .cloned()
+                .filter_map(Maybe::into_option)
+                .collect(),
+            Vec::new(),
+            self.no_index,
+        )
+    }
+
+    /// Return whether the network may be accessed.
+    pub(crate) fn connectivity(&self) -> Connectivity {
+        if self.offline {
+            Connectivity::Offline
+        } else {
+            Connectivity::Online
+        }
+    }
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct AddArgs {
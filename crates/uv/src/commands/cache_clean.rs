@@ -25,6 +25,8 @@ pub(crate) fn cache_clean(
         return Ok(ExitStatus::Success);
     }
 
+    let _lock = cache.lock_exclusive()?;
+
     if packages.is_empty() {
         writeln!(
             printer.stderr(),
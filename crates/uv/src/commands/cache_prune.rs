@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
@@ -9,8 +10,14 @@ use uv_fs::Simplified;
 use crate::commands::{human_readable_bytes, ExitStatus};
 use crate::printer::Printer;
 
-/// Prune all unreachable objects from the cache.
-pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+/// Prune all unreachable objects from the cache, along with any entries that haven't been used
+/// since `unused_since`, and the least recently used entries in excess of `max_size`.
+pub(crate) fn cache_prune(
+    cache: &Cache,
+    unused_since: Option<SystemTime>,
+    max_size: Option<u64>,
+    printer: Printer,
+) -> Result<ExitStatus> {
     if !cache.root().exists() {
         writeln!(
             printer.stderr(),
@@ -26,9 +33,21 @@ pub(crate) fn cache_prune(cache: &Cache, printer: Printer) -> Result<ExitStatus>
         cache.root().user_display().cyan()
     )?;
 
-    let summary = cache
+    let _lock = cache.lock_exclusive()?;
+
+    let mut summary = cache
         .prune()
         .with_context(|| format!("Failed to prune cache at: {}", cache.root().user_display()))?;
+    if let Some(unused_since) = unused_since {
+        summary += cache.prune_unused_since(unused_since).with_context(|| {
+            format!("Failed to prune cache at: {}", cache.root().user_display())
+        })?;
+    }
+    if let Some(max_size) = max_size {
+        summary += cache.evict_to_size(max_size).with_context(|| {
+            format!("Failed to prune cache at: {}", cache.root().user_display())
+        })?;
+    }
 
     // Write a summary of the number of files and directories removed.
     match (summary.num_files, summary.num_dirs) {
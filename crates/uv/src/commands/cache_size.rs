@@ -0,0 +1,52 @@
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Simplified;
+
+use crate::commands::{human_readable_bytes, ExitStatus};
+use crate::printer::Printer;
+
+/// Show the disk usage of the cache, broken down by bucket.
+pub(crate) fn cache_size(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    if !cache.root().exists() {
+        writeln!(
+            printer.stderr(),
+            "No cache found at: {}",
+            cache.root().user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let sizes = cache.bucket_sizes().with_context(|| {
+        format!(
+            "Failed to compute the size of the cache at: {}",
+            cache.root().user_display()
+        )
+    })?;
+
+    for (bucket, size) in &sizes {
+        writeln!(printer.stdout(), "{bucket}: {}", format_bytes(*size))?;
+    }
+
+    let total = sizes.iter().map(|(_, size)| size).sum::<u64>();
+    writeln!(
+        printer.stdout(),
+        "{}",
+        format!("Total: {}", format_bytes(total)).bold()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Format a byte count for display.
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes}B")
+    } else {
+        let (bytes, unit) = human_readable_bytes(bytes);
+        format!("{bytes:.1}{unit}")
+    }
+}
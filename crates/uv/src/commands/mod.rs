@@ -1,28 +1,64 @@
+use std::borrow::Cow;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{fmt::Display, fmt::Write, process::ExitCode};
 
 use anyhow::Context;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
 pub(crate) use cache_prune::cache_prune;
-use distribution_types::InstalledMetadata;
+pub(crate) use cache_size::cache_size;
+use distribution_types::{
+    BuiltDist, CachedDist, Dist, DistributionMetadata, IndexLocations, InstalledDist,
+    InstalledMetadata, Name, Resolution, ResolvedDist, SourceDist,
+};
+use install_wheel_rs::linker::LinkMode;
+use pep508_rs::Requirement;
+pub(crate) use pip_audit::{pip_audit, AuditSeverity};
+pub(crate) use pip_bundle::pip_bundle;
 pub(crate) use pip_check::pip_check;
 pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile};
 pub(crate) use pip_freeze::pip_freeze;
 pub(crate) use pip_install::pip_install;
 pub(crate) use pip_list::pip_list;
+pub(crate) use pip_sbom::{pip_sbom, resolution_sbom};
 pub(crate) use pip_show::pip_show;
 pub(crate) use pip_sync::pip_sync;
 pub(crate) use pip_uninstall::pip_uninstall;
+use platform_tags::{Tags, TagsError};
+pub(crate) use publish::publish;
+use pypi_types::HashAlgorithm;
+pub(crate) use run::run;
+use rustc_hash::FxHashMap;
 #[cfg(feature = "self-update")]
 pub(crate) use self_update::self_update;
+pub(crate) use tool_install::tool_install;
+pub(crate) use tool_list::tool_list;
+pub(crate) use tool_uninstall::tool_uninstall;
+pub(crate) use tool_upgrade::tool_upgrade;
+use tracing::{debug, warn};
+use url::Url;
+use uv_attestation::{AttestationMode, AttestationPolicy, Provenance, PROVENANCE_MEDIA_TYPE};
+use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
+use uv_client::{Connectivity, RegistryClient, RegistryClientBuilder};
+use uv_configuration::{
+    Concurrency, ConfigSettings, IndexStrategy, NoBinary, NoBuild, SetupPyStrategy, TagPolicy,
+};
+use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::compile_tree;
-use uv_interpreter::PythonEnvironment;
+use uv_installer::{compile_tree, Snapshot};
+use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_normalize::PackageName;
+use uv_resolver::{
+    CandidateFilters, ExcludeNewer, FlatIndex, InMemoryIndex, LicensePolicy, LicensePolicyMode,
+    NoPlatformWheels, OptionsBuilder, ResolutionGraph,
+};
+use uv_types::{BuildContext, BuildIsolation, HashStrategy, InFlight};
+use uv_warnings::warn_user;
 pub(crate) use venv::venv;
 pub(crate) use version::version;
 
@@ -31,17 +67,27 @@ use crate::printer::Printer;
 mod cache_clean;
 mod cache_dir;
 mod cache_prune;
+mod cache_size;
+mod pip_audit;
+mod pip_bundle;
 mod pip_check;
 mod pip_compile;
 mod pip_freeze;
 mod pip_install;
 mod pip_list;
+mod pip_sbom;
 mod pip_show;
 mod pip_sync;
 mod pip_uninstall;
+mod publish;
 mod reporters;
+mod run;
 #[cfg(feature = "self-update")]
 mod self_update;
+mod tool_install;
+mod tool_list;
+mod tool_uninstall;
+mod tool_upgrade;
 mod venv;
 mod version;
 
@@ -58,6 +104,9 @@ pub(crate) enum ExitStatus {
     /// The command failed with an unexpected error.
     #[allow(unused)]
     Error,
+
+    /// The command's child process exited with the given exit code.
+    External(u8),
 }
 
 impl From<ExitStatus> for ExitCode {
@@ -66,6 +115,7 @@ impl From<ExitStatus> for ExitCode {
             ExitStatus::Success => Self::from(0),
             ExitStatus::Failure => Self::from(1),
             ExitStatus::Error => Self::from(2),
+            ExitStatus::External(code) => Self::from(code),
         }
     }
 }
@@ -125,6 +175,445 @@ pub(crate) enum ListFormat {
     Json,
 }
 
+#[derive(Debug, Default, Copy, Clone, clap::ValueEnum)]
+pub(crate) enum CompileFormat {
+    /// Write the resolution as a `requirements.txt` file.
+    #[default]
+    RequirementsTxt,
+    /// Write the resolution in a machine-readable JSON format, including the source, hashes, and
+    /// dependents of every package.
+    Json,
+    /// Write the resolution as a CycloneDX 1.5 software bill of materials.
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// Write the resolution as an SPDX 2.3 software bill of materials.
+    Spdx,
+    /// Write the resolution as a lockfile, to be installed with `uv pip sync`. When resolving for
+    /// multiple platforms, the entries that only apply to some platforms are guarded by markers.
+    Lock,
+}
+
+impl CompileFormat {
+    /// Return the software bill of materials format, if the resolution should be written as one.
+    pub(crate) fn sbom(self) -> Option<SbomFormat> {
+        match self {
+            Self::RequirementsTxt | Self::Json | Self::Lock => None,
+            Self::CycloneDx => Some(SbomFormat::CycloneDx),
+            Self::Spdx => Some(SbomFormat::Spdx),
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, clap::ValueEnum)]
+pub(crate) enum AuditFormat {
+    /// Display the vulnerabilities in a human-readable format.
+    #[default]
+    Text,
+    /// Display the vulnerabilities in a machine-readable JSON format.
+    Json,
+}
+
+#[derive(Debug, Default, Copy, Clone, clap::ValueEnum)]
+pub(crate) enum SbomFormat {
+    /// Emit a CycloneDX 1.5 JSON document.
+    #[default]
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// Emit an SPDX 2.3 JSON document.
+    Spdx,
+}
+
+#[derive(Debug, Default, Copy, Clone, clap::ValueEnum)]
+pub(crate) enum BundleFormat {
+    /// Write a single zipapp archive.
+    #[default]
+    Zipapp,
+    /// Write a relocatable directory.
+    Directory,
+}
+
+/// Determine the [`Tags`] to use when selecting wheels for the given interpreter, after applying
+/// the [`TagPolicy`]'s `manylinux` cap.
+pub(super) fn interpreter_tags(
+    interpreter: &Interpreter,
+    tag_policy: TagPolicy,
+) -> Result<Cow<'_, Tags>, TagsError> {
+    let platform = tag_policy.platform(interpreter.platform());
+    if platform == *interpreter.platform() {
+        return Ok(Cow::Borrowed(interpreter.tags()?));
+    }
+    Ok(Cow::Owned(Tags::from_env(
+        &platform,
+        interpreter.python_tuple(),
+        interpreter.implementation_name(),
+        interpreter.implementation_tuple(),
+        interpreter.gil_disabled(),
+    )?))
+}
+
+/// Determine the [`CandidateFilters`] to apply when selecting among the files listed by an index,
+/// given the [`TagPolicy`].
+pub(super) fn candidate_filters(tag_policy: TagPolicy) -> CandidateFilters {
+    let candidate_filters = CandidateFilters::default();
+    if tag_policy.no_platform_wheels() {
+        candidate_filters.with(Arc::new(NoPlatformWheels))
+    } else {
+        candidate_filters
+    }
+}
+
+/// Report any packages in the resolution that violate the [`LicensePolicy`].
+///
+/// Returns `false` if the resolution should be rejected, i.e., if there are violations and the
+/// policy is enforced as an error.
+pub(super) fn check_license_policy(
+    resolution: &ResolutionGraph,
+    license_policy: &LicensePolicy,
+    printer: Printer,
+) -> Result<bool, std::fmt::Error> {
+    let violations = resolution.license_violations(license_policy);
+    if violations.is_empty() {
+        return Ok(true);
+    }
+
+    let s = if violations.len() == 1 { "" } else { "s" };
+    let header = format!(
+        "Found {} package{s} that violate{} the license policy:",
+        violations.len(),
+        if violations.len() == 1 { "s" } else { "" }
+    );
+    match license_policy.mode() {
+        LicensePolicyMode::Error => {
+            writeln!(
+                printer.stderr(),
+                "{}{} {}",
+                "error".red().bold(),
+                ":".bold(),
+                header.bold()
+            )?;
+        }
+        LicensePolicyMode::Warn => {
+            writeln!(
+                printer.stderr(),
+                "{}{} {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                header.bold()
+            )?;
+        }
+    }
+    for violation in &violations {
+        writeln!(printer.stderr(), "  {}", violation.message())?;
+    }
+
+    Ok(license_policy.mode() == LicensePolicyMode::Warn)
+}
+
+/// The SHA-256 digests of the registry distributions whose attestations were verified, which
+/// the downloaded archives must match.
+#[derive(Debug, Default)]
+pub(super) struct AttestedDigests(FxHashMap<PackageName, String>);
+
+impl AttestedDigests {
+    /// Return the [`HashStrategy`] to use when downloading the distributions, such that a SHA-256
+    /// digest is computed from the bytes of every downloaded archive.
+    pub(super) fn hasher<'a>(&self, hasher: &'a HashStrategy) -> Cow<'a, HashStrategy> {
+        if !self.0.is_empty() && matches!(hasher, HashStrategy::None) {
+            Cow::Owned(HashStrategy::Generate)
+        } else {
+            Cow::Borrowed(hasher)
+        }
+    }
+
+    /// Verify that the downloaded archives match the digests in their attestations, as opposed to
+    /// the digests claimed by the index.
+    pub(super) fn check(&self, wheels: &[CachedDist]) -> anyhow::Result<()> {
+        for wheel in wheels {
+            let Some(expected) = self.0.get(wheel.name()) else {
+                continue;
+            };
+            let actual = match wheel {
+                CachedDist::Registry(wheel) => wheel
+                    .hashes
+                    .iter()
+                    .find(|digest| digest.algorithm == HashAlgorithm::Sha256),
+                CachedDist::Url(_) => None,
+            };
+            match actual {
+                Some(actual) if actual.digest.eq_ignore_ascii_case(expected) => {}
+                Some(actual) => anyhow::bail!(
+                    "Downloaded archive for `{wheel}` doesn't match its attestations: expected sha256 `{expected}`, but found `{}`",
+                    actual.digest
+                ),
+                None => anyhow::bail!(
+                    "Unable to verify that the downloaded archive for `{wheel}` matches its attestations, since its SHA-256 hash is unknown"
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Verify the PEP 740 attestations of any registry distributions against the
+/// [`AttestationPolicy`], prior to downloading them.
+///
+/// Returns the attested digests, which the downloaded archives must then be checked against via
+/// [`AttestedDigests::check`].
+///
+/// Distributions from other sources (e.g., direct URLs and local paths) are exempt.
+pub(super) async fn verify_attestations(
+    remote: &[Dist],
+    attestation_policy: &AttestationPolicy,
+    client: &RegistryClient,
+    printer: Printer,
+) -> anyhow::Result<AttestedDigests> {
+    let mut digests = AttestedDigests::default();
+    if attestation_policy.mode() == AttestationMode::Ignore {
+        return Ok(digests);
+    }
+
+    let start = std::time::Instant::now();
+    for dist in remote {
+        let file = match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => &wheel.file,
+            Dist::Source(SourceDist::Registry(sdist)) => &sdist.file,
+            _ => continue,
+        };
+
+        let Some(provenance_url) = file.provenance.as_deref() else {
+            if attestation_policy.mode() == AttestationMode::Require {
+                anyhow::bail!(
+                    "Attestations are required, but the index doesn't provide any for `{}`",
+                    file.filename
+                );
+            }
+            debug!("No attestations available for: {}", file.filename);
+            continue;
+        };
+        let Some(sha256) = file
+            .hashes
+            .iter()
+            .find(|digest| digest.algorithm == HashAlgorithm::Sha256)
+        else {
+            anyhow::bail!(
+                "Unable to verify attestations for `{}`, since the index doesn't provide a SHA-256 hash",
+                file.filename
+            );
+        };
+
+        let provenance = fetch_provenance(client, provenance_url)
+            .await
+            .with_context(|| format!("Failed to fetch attestations for `{}`", file.filename))?;
+        let identity = attestation_policy
+            .verify(dist.name(), &provenance, &file.filename, &sha256.digest)
+            .with_context(|| format!("Failed to verify attestations for `{}`", file.filename))?;
+        debug!(
+            "Verified attestations for {} from: {identity}",
+            file.filename
+        );
+        digests
+            .0
+            .insert(dist.name().clone(), sha256.digest.to_string());
+    }
+
+    let verified = digests.0.len();
+    if verified > 0 {
+        let s = if verified == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Verified attestations for {} in {}",
+                format!("{verified} package{s}").bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+    }
+
+    Ok(digests)
+}
+
+/// Fetch the PEP 740 provenance object at the given URL.
+async fn fetch_provenance(client: &RegistryClient, url: &str) -> anyhow::Result<Provenance> {
+    let url = Url::parse(url)?;
+    let provenance = client
+        .uncached_client()
+        .get(url)
+        .header("Accept", PROVENANCE_MEDIA_TYPE)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Provenance>()
+        .await?;
+    Ok(provenance)
+}
+
+/// Resolve the given requirements and install them into a freshly created environment, as for
+/// script and tool environments.
+///
+/// Assumes the default build settings, since neither scripts nor tools can provide them.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn install_requirements(
+    requirements: &[Requirement],
+    venv: &PythonEnvironment,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> anyhow::Result<Resolution> {
+    let start = std::time::Instant::now();
+    let interpreter = venv.interpreter();
+
+    // Add all authenticated sources to the store.
+    for url in index_locations.urls() {
+        GLOBAL_AUTH_STORE.save_from_url(url);
+    }
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .native_tls(native_tls)
+        .concurrency(concurrency)
+        .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
+        .keyring_provider(keyring_provider)
+        .connectivity(connectivity)
+        .markers(interpreter.markers())
+        .platform(interpreter.platform())
+        .build();
+
+    // Create a shared in-memory index.
+    let index = InMemoryIndex::default();
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let flat_index = FlatIndex::default();
+    let config_settings = ConfigSettings::default();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        cache,
+        interpreter,
+        index_locations,
+        &flat_index,
+        &index,
+        &in_flight,
+        SetupPyStrategy::default(),
+        &config_settings,
+        BuildIsolation::Isolated,
+        link_mode,
+        &NoBuild::None,
+        &NoBinary::None,
+    )
+    .with_options(
+        OptionsBuilder::new()
+            .index_strategy(index_strategy)
+            .exclude_newer(exclude_newer)
+            .build(),
+    );
+
+    // Resolve and install the requirements.
+    let resolution = build_dispatch
+        .resolve(requirements)
+        .await
+        .context("Failed to resolve requirements")?;
+    build_dispatch
+        .install(&resolution, venv)
+        .await
+        .context("Failed to install requirements")?;
+
+    let s = if resolution.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Installed {} in {}",
+            format!("{} package{}", resolution.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+    for distribution in resolution
+        .distributions()
+        .filter_map(|dist| match dist {
+            ResolvedDist::Installable(dist) => Some(dist),
+            ResolvedDist::Installed(_) => None,
+        })
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(&b.version())))
+    {
+        writeln!(
+            printer.stderr(),
+            " {} {}{}",
+            "+".green(),
+            distribution.name().as_ref().bold(),
+            distribution.version_or_url().dimmed()
+        )?;
+    }
+
+    Ok(resolution)
+}
+
+/// Uninstall the given distributions, to make way for their replacements.
+///
+/// If any of the distributions fails to uninstall, the [`Snapshot`] (if any) of the
+/// distributions is restored before returning the error, such that the environment isn't left
+/// with a partially applied plan.
+pub(super) async fn uninstall_dists<'a>(
+    dists: impl IntoIterator<Item = &'a InstalledDist>,
+    snapshot: &mut Option<Snapshot>,
+) -> anyhow::Result<()> {
+    for dist_info in dists {
+        if let Err(err) = uninstall_dist(dist_info).await {
+            if let Some(snapshot) = snapshot.take() {
+                if let Err(restore) = snapshot.restore() {
+                    warn!("Failed to restore snapshot: {restore}");
+                }
+            }
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Uninstall a single distribution, falling back to removing its top-level modules if its
+/// `RECORD` is missing or invalid.
+async fn uninstall_dist(dist_info: &InstalledDist) -> anyhow::Result<()> {
+    let summary = match uv_installer::uninstall(dist_info).await {
+        Ok(summary) => {
+            for warning in &summary.warnings {
+                warn_user!("{dist_info}: {warning}");
+            }
+            summary
+        }
+        Err(uv_installer::UninstallError::Uninstall(
+            install_wheel_rs::Error::MissingRecord(_) | install_wheel_rs::Error::RecordCsv(_),
+        )) => {
+            warn_user!(
+                "The RECORD file for the package at {} is missing or invalid; removing the package based on its top-level modules instead. Any scripts it installed may be left behind.",
+                dist_info.path().user_display().cyan(),
+            );
+            uv_installer::repair_uninstall(dist_info).await?
+        }
+        Err(err) => return Err(err.into()),
+    };
+    debug!(
+        "Uninstalled {} ({} file{}, {} director{})",
+        dist_info.name(),
+        summary.file_count,
+        if summary.file_count == 1 { "" } else { "s" },
+        summary.dir_count,
+        if summary.dir_count == 1 { "y" } else { "ies" },
+    );
+    Ok(())
+}
+
 /// Compile all Python source files in site-packages to bytecode, to speed up the
 /// initial run of any subsequent executions.
 ///
@@ -0,0 +1,550 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use distribution_types::Name;
+use pep440_rs::Version;
+use uv_cache::Cache;
+use uv_client::{BaseClientBuilder, Connectivity};
+use uv_configuration::Concurrency;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+
+use crate::commands::{AuditFormat, ExitStatus};
+use crate::printer::Printer;
+
+/// The base URL of the OSV API.
+const OSV_API: &str = "https://api.osv.dev/v1";
+
+/// The maximum number of queries in a single OSV `querybatch` request.
+const OSV_QUERYBATCH_LIMIT: usize = 1000;
+
+/// Audit the installed packages in the current environment for known vulnerabilities.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pip_audit(
+    advisory_db: Option<&Path>,
+    severity_threshold: Option<AuditSeverity>,
+    format: AuditFormat,
+    python: Option<&str>,
+    system: bool,
+    connectivity: Connectivity,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let packages = site_packages
+        .iter()
+        .map(|dist| (dist.name().clone(), dist.version().clone()))
+        .sorted_unstable()
+        .dedup()
+        .collect::<Vec<_>>();
+
+    // Collect the advisories that may affect the installed packages.
+    let advisories = if let Some(advisory_db) = advisory_db {
+        read_advisory_db(advisory_db)?
+    } else {
+        if matches!(connectivity, Connectivity::Offline) {
+            return Err(anyhow!(
+                "Auditing in offline mode requires an advisory database (`--advisory-db`)"
+            ));
+        }
+        query_osv(&packages, connectivity, native_tls, concurrency).await?
+    };
+
+    // Match the advisories against the installed packages.
+    let mut findings = Vec::new();
+    for (name, version) in &packages {
+        for advisory in &advisories {
+            if let Some(fixed_versions) = advisory.affects(name, version) {
+                findings.push(Finding {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    id: advisory.id.clone(),
+                    aliases: advisory.aliases.clone(),
+                    summary: advisory.summary.clone(),
+                    severity: advisory.severity(),
+                    fixed_versions: fixed_versions.iter().map(ToString::to_string).collect(),
+                });
+            }
+        }
+    }
+
+    // Determine whether any of the findings meet the severity threshold. Advisories without a
+    // known severity are always considered to meet it.
+    let failed = findings
+        .iter()
+        .any(|finding| match (finding.severity, severity_threshold) {
+            (Some(severity), Some(threshold)) => severity >= threshold,
+            _ => true,
+        });
+
+    match format {
+        AuditFormat::Text => {
+            if findings.is_empty() {
+                writeln!(
+                    printer.stderr(),
+                    "{}",
+                    format!(
+                        "No known vulnerabilities found in {} package{}",
+                        packages.len(),
+                        if packages.len() == 1 { "" } else { "s" }
+                    )
+                    .dimmed()
+                )?;
+            } else {
+                let affected = findings
+                    .iter()
+                    .map(|finding| &finding.name)
+                    .collect::<FxHashSet<_>>()
+                    .len();
+                writeln!(
+                    printer.stderr(),
+                    "Found {} known vulnerabilit{} in {} package{}",
+                    findings.len(),
+                    if findings.len() == 1 { "y" } else { "ies" },
+                    affected,
+                    if affected == 1 { "" } else { "s" }
+                )?;
+                for finding in &findings {
+                    let severity = finding
+                        .severity
+                        .map_or_else(|| "unknown".to_string(), |severity| severity.to_string());
+                    let fixed = if finding.fixed_versions.is_empty() {
+                        "no fix available".to_string()
+                    } else {
+                        format!("fixed in {}", finding.fixed_versions.join(", "))
+                    };
+                    writeln!(
+                        printer.stdout(),
+                        "{} {}: {} ({severity}, {fixed})",
+                        finding.name.bold(),
+                        finding.version,
+                        finding.id.bold(),
+                    )?;
+                }
+            }
+        }
+        AuditFormat::Json => {
+            writeln!(printer.stdout(), "{}", serde_json::to_string(&findings)?)?;
+        }
+    }
+
+    if failed {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Success)
+    }
+}
+
+/// Read the advisories from an offline OSV database, i.e., a directory of OSV JSON files (such
+/// as an extracted `PyPI/all.zip` export).
+fn read_advisory_db(path: &Path) -> Result<Vec<Advisory>> {
+    let mut advisories = Vec::new();
+    for entry in fs_err::read_dir(path)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+        let contents = fs_err::read(&path)?;
+        let advisory = serde_json::from_slice::<Advisory>(&contents)
+            .with_context(|| format!("Failed to parse advisory: {}", path.user_display()))?;
+        advisories.push(advisory);
+    }
+    Ok(advisories)
+}
+
+/// Query the OSV API for the advisories that affect the given packages.
+async fn query_osv(
+    packages: &[(PackageName, Version)],
+    connectivity: Connectivity,
+    native_tls: bool,
+    concurrency: Concurrency,
+) -> Result<Vec<Advisory>> {
+    let client = BaseClientBuilder::new()
+        .connectivity(connectivity)
+        .native_tls(native_tls)
+        .concurrency(concurrency)
+        .build();
+
+    // Determine the IDs of the advisories that affect any of the packages. The API accepts a
+    // limited number of queries per request, and paginates the results of any query with too
+    // many matches, in which case the query is repeated with the returned page token.
+    let mut ids = FxHashSet::default();
+    let mut queries = packages
+        .iter()
+        .map(|(name, version)| Query {
+            package: QueryPackage {
+                name: name.to_string(),
+                ecosystem: "PyPI",
+            },
+            version: version.to_string(),
+            page_token: None,
+        })
+        .collect::<Vec<_>>();
+    while !queries.is_empty() {
+        let mut next_page = Vec::new();
+        for chunk in queries.chunks(OSV_QUERYBATCH_LIMIT) {
+            let response = client
+                .client()
+                .post(format!("{OSV_API}/querybatch"))
+                .header("Content-Type", "application/json")
+                .body(serde_json::to_vec(&QueryBatch { queries: chunk })?)
+                .send()
+                .await?
+                .error_for_status()?;
+            let batch = serde_json::from_slice::<QueryBatchResponse>(&response.bytes().await?)?;
+            for (query, result) in chunk.iter().zip(batch.results) {
+                ids.extend(result.vulns.into_iter().map(|vuln| vuln.id));
+                if let Some(page_token) = result.next_page_token {
+                    next_page.push(Query {
+                        page_token: Some(page_token),
+                        ..query.clone()
+                    });
+                }
+            }
+        }
+        queries = next_page;
+    }
+
+    // Fetch the full advisory for each ID.
+    let mut advisories = Vec::with_capacity(ids.len());
+    for id in ids.into_iter().sorted() {
+        debug!("Fetching advisory: {id}");
+        let response = client
+            .client()
+            .get(format!("{OSV_API}/vulns/{id}"))
+            .send()
+            .await?
+            .error_for_status()?;
+        let advisory = serde_json::from_slice::<Advisory>(&response.bytes().await?)
+            .with_context(|| format!("Failed to parse advisory: {id}"))?;
+        advisories.push(advisory);
+    }
+
+    Ok(advisories)
+}
+
+/// The severity of an advisory, as reported by the GitHub Advisory Database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AuditSeverity {
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+impl FromStr for AuditSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "moderate" | "medium" => Ok(Self::Moderate),
+            "high" => Ok(Self::High),
+            "critical" => Ok(Self::Critical),
+            _ => Err(format!("Unknown severity: {s}")),
+        }
+    }
+}
+
+impl std::fmt::Display for AuditSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Moderate => write!(f, "moderate"),
+            Self::High => write!(f, "high"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+/// A known vulnerability affecting an installed package.
+#[derive(Debug, Serialize)]
+struct Finding {
+    name: String,
+    version: String,
+    id: String,
+    aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<AuditSeverity>,
+    fixed_versions: Vec<String>,
+}
+
+/// An OSV `querybatch` request.
+#[derive(Debug, Serialize)]
+struct QueryBatch<'a> {
+    queries: &'a [Query],
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Query {
+    package: QueryPackage,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct QueryPackage {
+    name: String,
+    ecosystem: &'static str,
+}
+
+/// The response to an OSV `querybatch` request.
+#[derive(Debug, Deserialize)]
+struct QueryBatchResponse {
+    results: Vec<QueryBatchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryBatchResult {
+    #[serde(default)]
+    vulns: Vec<QueryBatchVuln>,
+    /// The token with which to request the next page of results, if any.
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryBatchVuln {
+    id: String,
+}
+
+/// An advisory in the OSV format.
+///
+/// See: <https://ossf.github.io/osv-schema/>
+#[derive(Debug, Deserialize)]
+struct Advisory {
+    id: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+    #[serde(default)]
+    database_specific: Option<DatabaseSpecific>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Affected {
+    package: Option<AffectedPackage>,
+    #[serde(default)]
+    ranges: Vec<AffectedRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AffectedPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AffectedRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<RangeEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RangeEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatabaseSpecific {
+    severity: Option<String>,
+}
+
+impl Advisory {
+    /// If the advisory affects the given package version, return the versions in which it was
+    /// fixed.
+    fn affects(&self, name: &PackageName, version: &Version) -> Option<Vec<Version>> {
+        if self.withdrawn.is_some() {
+            return None;
+        }
+
+        let mut affected = false;
+        let mut fixed_versions = Vec::new();
+        for entry in &self.affected {
+            let Some(package) = entry.package.as_ref() else {
+                continue;
+            };
+            if package.ecosystem != "PyPI"
+                || PackageName::from_str(&package.name).ok().as_ref() != Some(name)
+            {
+                continue;
+            }
+
+            if entry
+                .versions
+                .iter()
+                .filter_map(|affected| Version::from_str(affected).ok())
+                .any(|affected| affected == *version)
+            {
+                affected = true;
+            }
+
+            for range in &entry.ranges {
+                if range.kind != "ECOSYSTEM" {
+                    continue;
+                }
+                if range.contains(version) {
+                    affected = true;
+                }
+                fixed_versions.extend(
+                    range
+                        .events
+                        .iter()
+                        .filter_map(|event| event.fixed.as_deref())
+                        .filter_map(|fixed| Version::from_str(fixed).ok()),
+                );
+            }
+        }
+
+        if affected {
+            fixed_versions.sort_unstable();
+            fixed_versions.dedup();
+            Some(fixed_versions)
+        } else {
+            None
+        }
+    }
+
+    /// Return the severity of the advisory, if known.
+    fn severity(&self) -> Option<AuditSeverity> {
+        self.database_specific
+            .as_ref()
+            .and_then(|database_specific| database_specific.severity.as_deref())
+            .and_then(|severity| AuditSeverity::from_str(severity).ok())
+    }
+}
+
+impl AffectedRange {
+    /// Returns `true` if the version falls within the range.
+    ///
+    /// As required by the OSV schema, the events are sorted by version before being evaluated,
+    /// since advisories don't necessarily list them in order. Events with an invalid version are
+    /// ignored.
+    fn contains(&self, version: &Version) -> bool {
+        let mut events = self
+            .events
+            .iter()
+            .filter_map(RangeEvent::parse)
+            .collect::<Vec<_>>();
+        events.sort_by(|a, b| a.version().cmp(&b.version()));
+
+        let mut affected = false;
+        for event in events {
+            match event {
+                Event::Introduced(introduced) => {
+                    if introduced.map_or(true, |introduced| *version >= introduced) {
+                        affected = true;
+                    }
+                }
+                Event::Fixed(fixed) => {
+                    if *version >= fixed {
+                        affected = false;
+                    }
+                }
+                Event::LastAffected(last_affected) => {
+                    if *version > last_affected {
+                        affected = false;
+                    }
+                }
+            }
+        }
+        affected
+    }
+}
+
+/// A parsed [`RangeEvent`].
+#[derive(Debug)]
+enum Event {
+    /// The version in which the vulnerability was introduced, or `None` for the special `0`
+    /// version, which precedes all others.
+    Introduced(Option<Version>),
+    /// The version in which the vulnerability was fixed.
+    Fixed(Version),
+    /// The last version affected by the vulnerability.
+    LastAffected(Version),
+}
+
+impl Event {
+    /// Return the version of the event, for sorting.
+    fn version(&self) -> Option<&Version> {
+        match self {
+            Self::Introduced(version) => version.as_ref(),
+            Self::Fixed(version) | Self::LastAffected(version) => Some(version),
+        }
+    }
+}
+
+impl RangeEvent {
+    /// Parse the event, if its version is valid.
+    fn parse(&self) -> Option<Event> {
+        if let Some(introduced) = self.introduced.as_deref() {
+            if introduced == "0" {
+                return Some(Event::Introduced(None));
+            }
+            return Version::from_str(introduced)
+                .ok()
+                .map(|introduced| Event::Introduced(Some(introduced)));
+        }
+        if let Some(fixed) = self.fixed.as_deref() {
+            return Version::from_str(fixed).ok().map(Event::Fixed);
+        }
+        if let Some(last_affected) = self.last_affected.as_deref() {
+            return Version::from_str(last_affected)
+                .ok()
+                .map(Event::LastAffected);
+        }
+        None
+    }
+}
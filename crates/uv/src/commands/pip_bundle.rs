@@ -0,0 +1,130 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::Name;
+use install_wheel_rs::{Bundle, EntryPoint};
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+
+use crate::commands::{BundleFormat, ExitStatus};
+use crate::printer::Printer;
+
+/// Pack the installed packages in the current environment into a self-contained, runnable bundle.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pip_bundle(
+    output: &Path,
+    entry_point: Option<EntryPoint>,
+    script: Option<&str>,
+    format: BundleFormat,
+    shebang: Option<&str>,
+    exclude: &[PackageName],
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let packages = site_packages
+        .iter()
+        .filter(|dist| !exclude.contains(dist.name()))
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
+        .collect::<Vec<_>>();
+
+    // Editable installs reference their source tree, so they can't be relocated into a bundle.
+    if let Some(dist) = packages.iter().find(|dist| dist.is_editable()) {
+        bail!(
+            "Editable package `{}` can't be bundled; install it in non-editable mode, or pass `--exclude {}`",
+            dist.name(),
+            dist.name()
+        );
+    }
+
+    // Resolve the entry point, either as given or from an installed console script.
+    let entry_point = match (entry_point, script) {
+        (Some(entry_point), _) => entry_point,
+        (None, Some(script)) => {
+            let mut found = None;
+            for dist in &packages {
+                if let Some(entry_point) = EntryPoint::from_script(dist.path(), script)? {
+                    found = Some(entry_point);
+                    break;
+                }
+            }
+            let Some(entry_point) = found else {
+                bail!("No installed package provides a console script named `{script}`");
+            };
+            entry_point
+        }
+        (None, None) => bail!("Either `--entry-point` or `--script` must be provided"),
+    };
+
+    let mut bundle = Bundle::new(entry_point);
+    let mut files = 0;
+    for dist in &packages {
+        files += bundle.add_dist(dist.path())?;
+    }
+
+    match format {
+        BundleFormat::Zipapp => {
+            if bundle.has_extension_modules() {
+                bail!(
+                    "The installed packages include native extension modules, which can't be imported from a zipapp; use `--format directory` instead"
+                );
+            }
+            bundle.write_zipapp(output, shebang)?;
+        }
+        BundleFormat::Directory => {
+            if output.exists() {
+                bail!(
+                    "Output directory already exists: {}",
+                    output.user_display().cyan()
+                );
+            }
+            bundle.write_directory(output)?;
+        }
+    }
+
+    let s = if packages.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Bundled {} ({files} files) into {}",
+            format!("{} package{s}", packages.len()).bold(),
+            output.user_display().cyan()
+        )
+        .dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
@@ -1,7 +1,7 @@
 use std::fmt::Write;
 
 use anyhow::Result;
-use distribution_types::InstalledDist;
+use distribution_types::{InstalledDist, Name};
 use owo_colors::OwoColorize;
 use std::time::Instant;
 use tracing::debug;
@@ -15,9 +15,13 @@ use crate::commands::{elapsed, ExitStatus};
 use crate::printer::Printer;
 
 /// Check for incompatibilties in installed packages.
+///
+/// If `verify` is set, the installed files of each package are also verified against its
+/// `RECORD`.
 pub(crate) fn pip_check(
     python: Option<&str>,
     system: bool,
+    verify: bool,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -62,14 +66,13 @@ pub(crate) fn pip_check(
 
     let diagnostics: Vec<Diagnostic> = site_packages.diagnostics()?.into_iter().collect();
 
-    if diagnostics.is_empty() {
+    let compatible = if diagnostics.is_empty() {
         writeln!(
             printer.stderr(),
             "{}",
             "All installed packages are compatible".to_string().dimmed()
         )?;
-
-        Ok(ExitStatus::Success)
+        true
     } else {
         let incompats = if diagnostics.len() == 1 {
             "incompatibility"
@@ -89,7 +92,91 @@ pub(crate) fn pip_check(
         for diagnostic in &diagnostics {
             writeln!(printer.stderr(), "{}", diagnostic.message().bold())?;
         }
+        false
+    };
 
+    // Verify the installed files against each package's `RECORD`.
+    let verified = !verify || verify_packages(&packages, printer)?;
+
+    if compatible && verified {
+        Ok(ExitStatus::Success)
+    } else {
         Ok(ExitStatus::Failure)
     }
 }
+
+/// Verify the installed files of each package against its `RECORD`, reporting any files that are
+/// missing, modified, or orphaned.
+///
+/// Returns `true` if every package matches its `RECORD`.
+fn verify_packages(packages: &[&InstalledDist], printer: Printer) -> Result<bool> {
+    let mut invalid = 0usize;
+    for dist in packages {
+        // Legacy editables don't have a `RECORD` to verify against.
+        if let InstalledDist::LegacyEditable(_) = dist {
+            continue;
+        }
+        let verification = match install_wheel_rs::verify_wheel(dist.path()) {
+            Ok(verification) => verification,
+            Err(err) => {
+                // Report the package (e.g., if its `RECORD` is missing), and keep checking the
+                // others.
+                invalid += 1;
+                writeln!(
+                    printer.stderr(),
+                    "{}",
+                    format!("The package `{}` could not be verified:", dist.name()).bold()
+                )?;
+                match err {
+                    install_wheel_rs::Error::MissingRecord(path) => {
+                        writeln!(printer.stderr(), "  missing: {}", path.user_display())?;
+                    }
+                    err => writeln!(printer.stderr(), "  {err}")?,
+                }
+                continue;
+            }
+        };
+        if verification.is_valid() {
+            continue;
+        }
+        invalid += 1;
+
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("The package `{}` does not match its RECORD:", dist.name()).bold()
+        )?;
+        for path in &verification.missing {
+            writeln!(printer.stderr(), "  missing: {}", path.user_display())?;
+        }
+        for path in &verification.modified {
+            writeln!(printer.stderr(), "  modified: {}", path.user_display())?;
+        }
+        for path in &verification.orphaned {
+            writeln!(printer.stderr(), "  orphaned: {}", path.user_display())?;
+        }
+    }
+
+    if invalid == 0 {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            "All installed packages match their RECORD"
+                .to_string()
+                .dimmed()
+        )?;
+    } else {
+        let s = if invalid == 1 { "" } else { "s" };
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Found {}",
+                format!("{invalid} package{s} with inconsistent files").bold()
+            )
+            .dimmed()
+        )?;
+    }
+
+    Ok(invalid == 0)
+}
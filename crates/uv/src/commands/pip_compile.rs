@@ -10,40 +10,45 @@ use anstream::{eprint, AutoStream, StripStream};
 use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
 use tempfile::tempdir_in;
 use tracing::debug;
 
 use distribution_types::{IndexLocations, LocalEditable, LocalEditables, Verbatim};
 use install_wheel_rs::linker::LinkMode;
+use pep508_rs::MarkerEnvironment;
 use platform_tags::Tags;
 use requirements_txt::EditableRequirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
 use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
 use uv_configuration::{
-    ConfigSettings, Constraints, IndexStrategy, NoBinary, NoBuild, Overrides, SetupPyStrategy,
-    Upgrade,
+    Concurrency, ConfigSettings, Constraints, IndexStrategy, NoBinary, NoBuild, Overrides,
+    SetupPyStrategy, TagPolicy, TargetTriple, Upgrade,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::Downloader;
 use uv_interpreter::{find_best_python, PythonEnvironment};
-use uv_normalize::{ExtraName, PackageName};
+use uv_normalize::{ExtraName, GroupName, PackageName};
 use uv_requirements::{
-    upgrade::read_lockfile, ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver,
-    RequirementsSource, RequirementsSpecification, SourceTreeResolver,
+    upgrade::{read_imported_lockfile, read_lockfile},
+    ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver, RequirementsSource,
+    RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
     AnnotationStyle, DependencyMode, DisplayResolutionGraph, ExcludeNewer, Exclusions, FlatIndex,
-    InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode, PythonRequirement, ResolutionMode,
-    Resolver,
+    InMemoryIndex, LicensePolicy, Lock, Manifest, OptionsBuilder, PreReleaseMode,
+    PythonRequirement, ResolutionMode, ResolutionReport, Resolver, UniversalResolution, YankedMode,
 };
 use uv_toolchain::PythonVersion;
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
 use uv_warnings::warn_user;
 
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{
+    candidate_filters, check_license_policy, elapsed, resolution_sbom, CompileFormat, ExitStatus,
+};
 use crate::printer::Printer;
 
 /// Resolve a set of requirements into a set of pinned versions.
@@ -53,9 +58,12 @@ pub(crate) async fn pip_compile(
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
     extras: ExtrasSpecification<'_>,
+    groups: &[GroupName],
     output_file: Option<&Path>,
+    import_lock: Option<&Path>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    yanked_mode: YankedMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     generate_hashes: bool,
@@ -76,10 +84,16 @@ pub(crate) async fn pip_compile(
     connectivity: Connectivity,
     no_build_isolation: bool,
     no_build: NoBuild,
+    no_binary: NoBinary,
+    tag_policy: TagPolicy,
+    license_policy: &LicensePolicy,
     python_version: Option<PythonVersion>,
+    python_platforms: Vec<TargetTriple>,
     exclude_newer: Option<ExcludeNewer>,
     annotation_style: AnnotationStyle,
+    format: CompileFormat,
     native_tls: bool,
+    concurrency: Concurrency,
     quiet: bool,
     link_mode: LinkMode,
     cache: Cache,
@@ -87,6 +101,27 @@ pub(crate) async fn pip_compile(
 ) -> Result<ExitStatus> {
     let start = std::time::Instant::now();
 
+    // When resolving for multiple platforms, the first is resolved as usual, and the others are
+    // resolved separately and merged into a universal resolution.
+    let python_platform = python_platforms.first().copied();
+    if python_platforms.len() > 1 {
+        if generate_hashes {
+            return Err(anyhow!(
+                "`--generate-hashes` is not supported when resolving for multiple platforms"
+            ));
+        }
+        if matches!(format, CompileFormat::Json) {
+            return Err(anyhow!(
+                "`--format json` is not supported when resolving for multiple platforms"
+            ));
+        }
+        if format.sbom().is_some() {
+            return Err(anyhow!(
+                "`--format cyclonedx` and `--format spdx` are not supported when resolving for multiple platforms"
+            ));
+        }
+    }
+
     // If the user requests `extras` but does not provide a valid source (e.g., a `pyproject.toml`),
     // return an error.
     if !extras.is_empty() && !requirements.iter().any(RequirementsSource::allows_extras) {
@@ -95,14 +130,23 @@ pub(crate) async fn pip_compile(
         ));
     }
 
+    // Similarly, dependency groups can only be read from a `pyproject.toml`.
+    if !groups.is_empty() && !requirements.iter().any(RequirementsSource::allows_groups) {
+        return Err(anyhow!(
+            "Requesting dependency groups requires a `pyproject.toml` file."
+        ));
+    }
+
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .keyring_provider(keyring_provider);
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
+        requires_python,
         requirements,
         constraints,
         overrides,
@@ -113,17 +157,26 @@ pub(crate) async fn pip_compile(
         extra_index_urls,
         no_index,
         find_links,
-        no_binary: _,
+        no_binary: specified_no_binary,
         no_build: specified_no_build,
+        pre,
     } = RequirementsSpecification::from_sources(
         requirements,
         constraints,
         overrides,
         &extras,
+        groups,
         &client_builder,
     )
     .await?;
 
+    // Allow pre-releases if any requirements file enables `--pre`.
+    let prerelease_mode = if pre {
+        PreReleaseMode::Allow
+    } else {
+        prerelease_mode
+    };
+
     // If all the metadata could be statically resolved, validate that every extra was used. If we
     // need to resolve metadata via PEP 517, we don't know which extras are used until much later.
     if source_trees.is_empty() {
@@ -175,20 +228,27 @@ pub(crate) async fn pip_compile(
     // Create a shared in-memory index.
     let source_index = InMemoryIndex::default();
 
-    // If we're resolving against a different Python version, use a separate index. Source
-    // distributions will be built against the installed version, and so the index may contain
-    // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
-        InMemoryIndexRef::Owned(InMemoryIndex::default())
-    } else {
-        InMemoryIndexRef::Borrowed(&source_index)
-    };
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed interpreter, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index =
+        if python_version.is_some() || python_platform.is_some() || !tag_policy.is_none() {
+            InMemoryIndexRef::Owned(InMemoryIndex::default())
+        } else {
+            InMemoryIndexRef::Borrowed(&source_index)
+        };
 
     // Determine the tags, markers, and interpreter to use for resolution.
-    let tags = if let Some(python_version) = python_version.as_ref() {
+    let tags = if python_platform.is_some() || python_version.is_some() || !tag_policy.is_none() {
+        let platform = python_platform.map(TargetTriple::platform);
+        let platform =
+            tag_policy.platform(platform.as_ref().unwrap_or_else(|| interpreter.platform()));
         Cow::Owned(Tags::from_env(
-            interpreter.platform(),
-            (python_version.major(), python_version.minor()),
+            &platform,
+            python_version.as_ref().map_or_else(
+                || interpreter.python_tuple(),
+                |python_version| (python_version.major(), python_version.minor()),
+            ),
             interpreter.implementation_name(),
             interpreter.implementation_tuple(),
             interpreter.gil_disabled(),
@@ -196,10 +256,64 @@ pub(crate) async fn pip_compile(
     } else {
         Cow::Borrowed(interpreter.tags()?)
     };
-    let markers = python_version.map_or_else(
-        || Cow::Borrowed(interpreter.markers()),
-        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
-    );
+    let markers = match (python_platform, python_version) {
+        (Some(python_platform), Some(python_version)) => {
+            Cow::Owned(python_version.markers(&python_platform.markers(interpreter.markers())))
+        }
+        (Some(python_platform), None) => Cow::Owned(python_platform.markers(interpreter.markers())),
+        (None, Some(python_version)) => Cow::Owned(python_version.markers(interpreter.markers())),
+        (None, None) => Cow::Borrowed(interpreter.markers()),
+    };
+
+    // Determine the tags and markers for any additional platforms.
+    let fork_targets = python_platforms
+        .iter()
+        .skip(1)
+        .map(|python_platform| -> Result<(MarkerEnvironment, Tags)> {
+            let platform = tag_policy.platform(&python_platform.platform());
+            let tags = Tags::from_env(
+                &platform,
+                python_version.as_ref().map_or_else(
+                    || interpreter.python_tuple(),
+                    |python_version| (python_version.major(), python_version.minor()),
+                ),
+                interpreter.implementation_name(),
+                interpreter.implementation_tuple(),
+                interpreter.gil_disabled(),
+            )?;
+            let markers = python_platform.markers(interpreter.markers());
+            let markers = match python_version.as_ref() {
+                Some(python_version) => python_version.markers(&markers),
+                None => markers,
+            };
+            Ok((markers, tags))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Each platform's pins are guarded by its markers, so the platforms must be distinguishable.
+    if let Some(duplicate) = std::iter::once(markers.as_ref())
+        .chain(fork_targets.iter().map(|(markers, _)| markers))
+        .duplicates()
+        .next()
+    {
+        return Err(anyhow!(
+            "Multiple `--python-platform` values target the same environment (`sys_platform == '{}'` and `platform_machine == '{}'`)",
+            duplicate.sys_platform,
+            duplicate.platform_machine
+        ));
+    }
+
+    // Validate that the project is compatible with the target Python version.
+    if let Some(requires_python) = requires_python.as_ref() {
+        let requirement = PythonRequirement::new(&interpreter, &markers);
+        if !requires_python.contains(requirement.target()) {
+            return Err(anyhow!(
+                "The project requires Python {}, but resolution targets Python {}",
+                requires_python,
+                requirement.target()
+            ));
+        }
+    }
 
     // Generate, but don't enforce hashes for the requirements.
     let hasher = if generate_hashes {
@@ -220,6 +334,7 @@ pub(crate) async fn pip_compile(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -229,14 +344,41 @@ pub(crate) async fn pip_compile(
         .build();
 
     // Read the lockfile, if present.
-    let preferences = read_lockfile(output_file, upgrade).await?;
+    let mut preferences = read_lockfile(output_file, &upgrade).await?;
+
+    // Read the pins from an imported lockfile, for any packages that aren't already pinned.
+    if let Some(import_lock) = import_lock {
+        let imported = read_imported_lockfile(import_lock, &markers, &upgrade)?;
+        let pinned = preferences
+            .iter()
+            .map(|preference| preference.name().clone())
+            .collect::<FxHashSet<_>>();
+        preferences.extend(
+            imported
+                .into_iter()
+                .filter(|preference| !pinned.contains(preference.name())),
+        );
+    }
+
+    // Determine the policy for selecting among the files listed by each index.
+    let candidate_filters = candidate_filters(tag_policy);
+
+    // Combine the `--no-binary` and `--no-build` flags.
+    let no_binary = no_binary.combine(specified_no_binary);
+    let no_build = no_build.combine(specified_no_build);
 
     // Resolve the flat indexes from `--find-links`.
-    let flat_index = {
+    let flat_index_entries = {
         let client = FlatIndexClient::new(&client, &cache);
-        let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &NoBinary::None)
+        client.fetch(index_locations.flat_index()).await?
     };
+    let flat_index = FlatIndex::from_entries(
+        flat_index_entries.clone(),
+        &tags,
+        &hasher,
+        &no_build,
+        &no_binary,
+    );
 
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
@@ -250,9 +392,6 @@ pub(crate) async fn pip_compile(
         BuildIsolation::Isolated
     };
 
-    // Combine the `--no-build` flags.
-    let no_build = no_build.combine(specified_no_build);
-
     let build_dispatch = BuildDispatch::new(
         &client,
         &cache,
@@ -266,9 +405,14 @@ pub(crate) async fn pip_compile(
         build_isolation,
         link_mode,
         &no_build,
-        &NoBinary::None,
+        &no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .index_strategy(index_strategy)
+            .exclude_newer(exclude_newer)
+            .build(),
+    );
 
     // Resolve the requirements from the provided sources.
     let requirements = {
@@ -388,6 +532,79 @@ pub(crate) async fn pip_compile(
     .resolve(&markers)
     .await?;
 
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .yanked_mode(yanked_mode)
+        .index_strategy(index_strategy)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .build();
+
+    // Resolve for each additional platform, with its own index, since the available distributions
+    // (and so the package priorities) differ by platform.
+    let mut fork_resolutions = Vec::with_capacity(fork_targets.len());
+    for (fork_markers, fork_tags) in fork_targets {
+        let fork_index = InMemoryIndex::default();
+        let fork_flat_index = FlatIndex::from_entries(
+            flat_index_entries.clone(),
+            &fork_tags,
+            &hasher,
+            &no_build,
+            &no_binary,
+        );
+        let lookaheads = LookaheadResolver::new(
+            &requirements,
+            &constraints,
+            &overrides,
+            &editables,
+            &hasher,
+            &build_dispatch,
+            &client,
+            &fork_index,
+        )
+        .with_reporter(ResolverReporter::from(printer))
+        .resolve(&fork_markers)
+        .await?;
+        let manifest = Manifest::new(
+            requirements.clone(),
+            constraints.clone(),
+            overrides.clone(),
+            preferences.clone(),
+            project.clone(),
+            editables.clone(),
+            Exclusions::All,
+            lookaheads,
+        );
+        let resolver = Resolver::new(
+            manifest,
+            options,
+            &fork_markers,
+            &interpreter,
+            &fork_tags,
+            &client,
+            &fork_flat_index,
+            &fork_index,
+            &hasher,
+            &build_dispatch,
+            &EmptyInstalledPackages,
+        )?
+        .with_candidate_filters(candidate_filters.clone())
+        .with_reporter(ResolverReporter::from(printer));
+        let resolution = match resolver.resolve().await {
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                let report = miette::Report::msg(format!("{err}")).context(format!(
+                    "No solution found when resolving dependencies for `sys_platform == '{}'`:",
+                    fork_markers.sys_platform
+                ));
+                eprint!("{report:?}");
+                return Ok(ExitStatus::Failure);
+            }
+            result => result,
+        }?;
+        fork_resolutions.push((fork_markers, resolution));
+    }
+
     // Create a manifest of the requirements.
     let manifest = Manifest::new(
         requirements,
@@ -401,13 +618,6 @@ pub(crate) async fn pip_compile(
         lookaheads,
     );
 
-    let options = OptionsBuilder::new()
-        .resolution_mode(resolution_mode)
-        .prerelease_mode(prerelease_mode)
-        .dependency_mode(dependency_mode)
-        .exclude_newer(exclude_newer)
-        .build();
-
     // Resolve the dependencies.
     let resolver = Resolver::new(
         manifest.clone(),
@@ -422,6 +632,7 @@ pub(crate) async fn pip_compile(
         &build_dispatch,
         &EmptyInstalledPackages,
     )?
+    .with_candidate_filters(candidate_filters)
     .with_reporter(ResolverReporter::from(printer));
 
     let resolution = match resolver.resolve().await {
@@ -434,6 +645,29 @@ pub(crate) async fn pip_compile(
         result => result,
     }?;
 
+    let metrics = resolution.metrics();
+    let requests = client.metrics();
+    debug!(
+        "Resolution took {:.3}s: {} index requests, {} metadata requests, {} HTTP requests ({} bytes), {} cache hits, {} cache misses",
+        metrics.wall_time.as_secs_f64(),
+        metrics.index_requests,
+        metrics.metadata_requests,
+        requests.requests,
+        requests.bytes_downloaded,
+        requests.cache_hits,
+        requests.cache_misses,
+    );
+    if let Some((package, slowest)) = metrics
+        .packages
+        .iter()
+        .max_by_key(|(_, package)| package.latency())
+    {
+        debug!(
+            "Slowest package to resolve: {package} ({:.3}s)",
+            slowest.latency().as_secs_f64()
+        );
+    }
+
     let s = if resolution.len() == 1 { "" } else { "s" };
     writeln!(
         printer.stderr(),
@@ -457,9 +691,58 @@ pub(crate) async fn pip_compile(
         )?;
     }
 
+    // Enforce the license policy before writing any output.
+    for resolution in std::iter::once(&resolution)
+        .chain(fork_resolutions.iter().map(|(_, resolution)| resolution))
+    {
+        if !check_license_policy(resolution, license_policy, printer)? {
+            return Ok(ExitStatus::Failure);
+        }
+    }
+
     // Write the resolved dependencies to the output channel.
     let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
 
+    if matches!(format, CompileFormat::Json) {
+        let report = ResolutionReport::from_resolution_graph(&resolution, &markers)?;
+        writeln!(writer, "{}", serde_json::to_string_pretty(&report)?)?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if matches!(format, CompileFormat::Lock) {
+        let lock = if fork_resolutions.is_empty() {
+            Lock::from_resolution_graph(&resolution, &markers)?.with_tags(&tags)
+        } else {
+            Lock::from_universal_resolution(&UniversalResolution::new(
+                std::iter::once((markers.into_owned(), resolution)).chain(fork_resolutions),
+            )?)?
+        };
+        write!(writer, "{}", lock.to_toml()?)?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if let Some(sbom_format) = format.sbom() {
+        // Name the document after the first requirements file, if any.
+        let name = requirements
+            .iter()
+            .find_map(|source| match source {
+                RequirementsSource::RequirementsTxt(path)
+                | RequirementsSource::PyprojectToml(path)
+                | RequirementsSource::SetupPy(path)
+                | RequirementsSource::SetupCfg(path) => path
+                    .file_name()
+                    .filter(|name| *name != "-")
+                    .map(|name| name.to_string_lossy().to_string()),
+                RequirementsSource::Package(_)
+                | RequirementsSource::Editable(_)
+                | RequirementsSource::Lockfile(_) => None,
+            })
+            .unwrap_or_else(|| "resolution".to_string());
+        let bom = resolution_sbom(sbom_format, &name, &resolution, &markers)?;
+        writeln!(writer, "{}", serde_json::to_string_pretty(&bom)?)?;
+        return Ok(ExitStatus::Success);
+    }
+
     if include_header {
         writeln!(
             writer,
@@ -481,7 +764,7 @@ pub(crate) async fn pip_compile(
         )?;
     }
 
-    if include_marker_expression {
+    if include_marker_expression && fork_resolutions.is_empty() {
         let relevant_markers = resolution.marker_tree(&manifest, &top_level_index, &markers);
         writeln!(
             writer,
@@ -519,6 +802,20 @@ pub(crate) async fn pip_compile(
         writeln!(writer)?;
     }
 
+    // If resolving for multiple platforms, write the merged pins, with markers on any pins that
+    // differ by platform.
+    if !fork_resolutions.is_empty() {
+        let universal = UniversalResolution::new(
+            std::iter::once((markers.into_owned(), resolution)).chain(fork_resolutions),
+        )?;
+        for pin in universal.pins() {
+            if !no_emit_packages.contains(&pin.name) {
+                writeln!(writer, "{pin}")?;
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
     write!(
         writer,
         "{}",
@@ -575,14 +872,17 @@ fn cmd(
 
             // Skip any index URLs, unless requested.
             if !include_index_url {
-                if arg.starts_with("--extra-index-url=") || arg.starts_with("--index-url=") {
+                if arg.starts_with("--extra-index-url=")
+                    || arg.starts_with("--index-url=")
+                    || arg.starts_with("--index-pin=")
+                {
                     // Reset state; skip this iteration.
                     *skip_next = None;
                     return Some(None);
                 }
 
                 // Mark the next item as (to be) skipped.
-                if arg == "--index-url" || arg == "--extra-index-url" {
+                if arg == "--index-url" || arg == "--extra-index-url" || arg == "--index-pin" {
                     *skip_next = Some(true);
                     return Some(None);
                 }
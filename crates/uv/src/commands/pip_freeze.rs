@@ -62,6 +62,9 @@ pub(crate) fn pip_freeze(
                     writeln!(printer.stdout(), "{} @ {}", dist.name().bold(), dist.url)?;
                 }
             }
+            InstalledDist::LegacyEditable(dist) => {
+                writeln!(printer.stdout(), "-e {}", dist.target_url)?;
+            }
         }
     }
 
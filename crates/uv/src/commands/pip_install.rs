@@ -1,12 +1,13 @@
 use std::fmt::Write;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
 
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
 use tempfile::tempdir_in;
 use tracing::debug;
 
@@ -15,37 +16,44 @@ use distribution_types::{
     LocalEditables, Name, Resolution,
 };
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::Shebang;
 use pep508_rs::{MarkerEnvironment, Requirement};
 use platform_tags::Tags;
 use pypi_types::{Metadata23, Yanked};
 use requirements_txt::EditableRequirement;
+use uv_attestation::AttestationPolicy;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
 use uv_client::{
     BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClient, RegistryClientBuilder,
 };
 use uv_configuration::{
-    ConfigSettings, Constraints, IndexStrategy, NoBinary, NoBuild, Overrides, Reinstall,
-    SetupPyStrategy, Upgrade,
+    Concurrency, ConfigSettings, Constraints, IndexStrategy, NoBinary, NoBuild, Overrides,
+    Reinstall, SetupPyStrategy, TagPolicy, Upgrade,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::{BuiltEditable, Downloader, Plan, Planner, ResolvedEditable, SitePackages};
-use uv_interpreter::{Interpreter, PythonEnvironment};
-use uv_normalize::PackageName;
+use uv_installer::{
+    BuiltEditable, Downloader, Plan, Planner, ResolvedEditable, SitePackages, Snapshot,
+};
+use uv_interpreter::{Interpreter, PythonEnvironment, Target};
+use uv_normalize::{GroupName, PackageName};
 use uv_requirements::{
     ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver, RequirementsSource,
     RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
-    DependencyMode, ExcludeNewer, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options,
-    OptionsBuilder, PreReleaseMode, Preference, ResolutionGraph, ResolutionMode, Resolver,
+    CandidateFilters, DependencyMode, ExcludeNewer, Exclusions, FlatIndex, InMemoryIndex,
+    LicensePolicy, Manifest, Options, OptionsBuilder, PreReleaseMode, Preference, ResolutionGraph,
+    ResolutionMode, Resolver, YankedMode,
 };
 use uv_types::{BuildIsolation, HashStrategy, InFlight};
-use uv_warnings::warn_user;
 
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    candidate_filters, check_license_policy, compile_bytecode, elapsed, interpreter_tags,
+    uninstall_dists, verify_attestations, ChangeEvent, ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
 
 use super::DryRunEvent;
@@ -57,8 +65,10 @@ pub(crate) async fn pip_install(
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
     extras: &ExtrasSpecification<'_>,
+    groups: &[GroupName],
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    yanked_mode: YankedMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     index_locations: IndexLocations,
@@ -66,6 +76,8 @@ pub(crate) async fn pip_install(
     keyring_provider: KeyringProvider,
     reinstall: Reinstall,
     link_mode: LinkMode,
+    shebang: Shebang,
+    allow_overwrite: bool,
     compile: bool,
     require_hashes: bool,
     setup_py: SetupPyStrategy,
@@ -74,12 +86,18 @@ pub(crate) async fn pip_install(
     no_build_isolation: bool,
     no_build: NoBuild,
     no_binary: NoBinary,
+    tag_policy: TagPolicy,
+    license_policy: &LicensePolicy,
+    attestation_policy: &AttestationPolicy,
     strict: bool,
     exclude_newer: Option<ExcludeNewer>,
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
+    target: Option<PathBuf>,
+    user: bool,
     native_tls: bool,
+    concurrency: Concurrency,
     cache: Cache,
     dry_run: bool,
     printer: Printer,
@@ -89,11 +107,13 @@ pub(crate) async fn pip_install(
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .keyring_provider(keyring_provider);
 
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
+        requires_python,
         requirements,
         constraints,
         overrides,
@@ -105,21 +125,42 @@ pub(crate) async fn pip_install(
         find_links,
         no_binary: specified_no_binary,
         no_build: specified_no_build,
+        pre,
         extras: _,
     } = read_requirements(
         requirements,
         constraints,
         overrides,
         extras,
+        groups,
         &client_builder,
     )
     .await?;
 
+    // Allow pre-releases if any requirements file enables `--pre`.
+    let prerelease_mode = if pre {
+        PreReleaseMode::Allow
+    } else {
+        prerelease_mode
+    };
+
     // Detect the current Python interpreter.
     let venv = if let Some(python) = python.as_ref() {
         PythonEnvironment::from_requested_python(python, &cache)?
     } else if system {
         PythonEnvironment::from_default_python(&cache)?
+    } else if user {
+        // The user site-packages directory belongs to the system Python.
+        PythonEnvironment::from_default_python(&cache)?
+    } else if target.is_some() {
+        // When installing into a `--target` directory, any interpreter will do; prefer the active
+        // virtual environment, if there is one.
+        match PythonEnvironment::from_virtualenv(&cache) {
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(&cache)?
+            }
+            result => result?,
+        }
     } else {
         PythonEnvironment::from_virtualenv(&cache)?
     };
@@ -129,8 +170,42 @@ pub(crate) async fn pip_install(
         venv.python_executable().user_display().cyan()
     );
 
-    // If the environment is externally managed, abort.
-    if let Some(externally_managed) = venv.interpreter().is_externally_managed() {
+    // Apply any `--target` directory.
+    let venv = if let Some(target) = target {
+        debug!(
+            "Installing into target directory: {}",
+            target.user_display()
+        );
+        let target = Target::from(target);
+        target.init()?;
+        venv.with_target(target)
+    } else if user {
+        // Like pip, refuse to install into the user site-packages from a virtual environment, in
+        // which it isn't visible.
+        if venv.interpreter().is_virtualenv() {
+            return Err(anyhow!(
+                "Can't install into the user site-packages directory from a virtual environment (`{}`), since it isn't visible there",
+                venv.root().user_display().cyan()
+            ));
+        }
+        let venv = venv.with_user_site();
+        debug!(
+            "Installing into user site-packages directory: {}",
+            venv.interpreter().purelib().user_display()
+        );
+        fs_err::create_dir_all(venv.interpreter().purelib())?;
+        venv
+    } else {
+        venv
+    };
+
+    // If the environment is externally managed, abort. Installing into a `--target` directory
+    // leaves the environment untouched.
+    if let Some(externally_managed) = venv
+        .interpreter()
+        .is_externally_managed()
+        .filter(|_| venv.interpreter().target().is_none())
+    {
         if break_system_packages {
             debug!("Ignoring externally managed environment due to `--break-system-packages`");
         } else {
@@ -183,9 +258,20 @@ pub(crate) async fn pip_install(
 
     // Determine the tags, markers, and interpreter to use for resolution.
     let interpreter = venv.interpreter().clone();
-    let tags = venv.interpreter().tags()?;
+    let tags = interpreter_tags(venv.interpreter(), tag_policy)?;
     let markers = venv.interpreter().markers();
 
+    // Validate that the project is compatible with the target Python version.
+    if let Some(requires_python) = requires_python.as_ref() {
+        if !requires_python.contains(interpreter.python_version()) {
+            return Err(anyhow!(
+                "The project requires Python {}, but {} is installed",
+                requires_python,
+                interpreter.python_version()
+            ));
+        }
+    }
+
     // Collect the set of required hashes.
     let hasher = if require_hashes {
         HashStrategy::from_requirements(
@@ -211,6 +297,7 @@ pub(crate) async fn pip_install(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -219,11 +306,18 @@ pub(crate) async fn pip_install(
         .platform(interpreter.platform())
         .build();
 
+    // Determine the policy for selecting among the files listed by each index.
+    let candidate_filters = candidate_filters(tag_policy);
+
+    // Combine the `--no-binary` and `--no-build` flags.
+    let no_binary = no_binary.combine(specified_no_binary);
+    let no_build = no_build.combine(specified_no_build);
+
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags, &hasher, &no_build, &no_binary)
+        FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &no_binary)
     };
 
     // Determine whether to enable build isolation.
@@ -233,10 +327,6 @@ pub(crate) async fn pip_install(
         BuildIsolation::Isolated
     };
 
-    // Combine the `--no-binary` and `--no-build` flags.
-    let no_binary = no_binary.combine(specified_no_binary);
-    let no_build = no_build.combine(specified_no_build);
-
     // Create a shared in-memory index.
     let index = InMemoryIndex::default();
 
@@ -259,7 +349,12 @@ pub(crate) async fn pip_install(
         &no_build,
         &no_binary,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .index_strategy(index_strategy)
+            .exclude_newer(exclude_newer)
+            .build(),
+    );
 
     // Resolve the requirements from the provided sources.
     let requirements = {
@@ -316,7 +411,7 @@ pub(crate) async fn pip_install(
             &hasher,
             &cache,
             &interpreter,
-            tags,
+            &tags,
             &client,
             &resolve_dispatch,
             printer,
@@ -327,10 +422,24 @@ pub(crate) async fn pip_install(
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
+        .yanked_mode(yanked_mode)
+        .index_strategy(index_strategy)
         .dependency_mode(dependency_mode)
         .exclude_newer(exclude_newer)
         .build();
 
+    // Track the packages that were requested directly, as opposed to being pulled in as
+    // dependencies, so that only they are marked as `REQUESTED` in the environment.
+    let requested = requirements
+        .iter()
+        .map(|requirement| requirement.name.clone())
+        .chain(
+            editables
+                .iter()
+                .map(|built_editable| built_editable.metadata.name.clone()),
+        )
+        .collect::<FxHashSet<_>>();
+
     // Resolve the requirements.
     let resolution = match resolve(
         requirements,
@@ -343,18 +452,24 @@ pub(crate) async fn pip_install(
         &reinstall,
         &upgrade,
         &interpreter,
-        tags,
+        &tags,
         markers,
         &client,
         &flat_index,
         &index,
         &resolve_dispatch,
         options,
+        &candidate_filters,
         printer,
     )
     .await
     {
-        Ok(resolution) => Resolution::from(resolution),
+        Ok(resolution) => {
+            if !check_license_policy(&resolution, license_policy, printer)? {
+                return Ok(ExitStatus::Failure);
+            }
+            Resolution::from(resolution)
+        }
         Err(Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
@@ -387,7 +502,12 @@ pub(crate) async fn pip_install(
             &no_build,
             &no_binary,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_options(
+            OptionsBuilder::new()
+                .index_strategy(index_strategy)
+                .exclude_newer(exclude_newer)
+                .build(),
+        )
     };
 
     // Sync the environment.
@@ -398,15 +518,20 @@ pub(crate) async fn pip_install(
         &reinstall,
         &no_binary,
         link_mode,
+        shebang,
+        allow_overwrite,
+        strict,
         compile,
         &index_locations,
         &hasher,
-        tags,
+        &tags,
         &client,
         &in_flight,
         &install_dispatch,
         &cache,
         &venv,
+        requested,
+        attestation_policy,
         dry_run,
         printer,
     )
@@ -426,6 +551,7 @@ async fn read_requirements(
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
     extras: &ExtrasSpecification<'_>,
+    groups: &[GroupName],
     client_builder: &BaseClientBuilder<'_>,
 ) -> Result<RequirementsSpecification, Error> {
     // If the user requests `extras` but does not provide a valid source (e.g., a `pyproject.toml`),
@@ -437,12 +563,20 @@ async fn read_requirements(
         .into());
     }
 
+    // Similarly, dependency groups can only be read from a `pyproject.toml`.
+    if !groups.is_empty() && !requirements.iter().any(RequirementsSource::allows_groups) {
+        return Err(
+            anyhow!("Requesting dependency groups requires a `pyproject.toml` file.").into(),
+        );
+    }
+
     // Read all requirements from the provided sources.
     let spec = RequirementsSpecification::from_sources(
         requirements,
         constraints,
         overrides,
         extras,
+        groups,
         client_builder,
     )
     .await?;
@@ -555,6 +689,7 @@ async fn resolve(
     index: &InMemoryIndex,
     build_dispatch: &BuildDispatch<'_>,
     options: Options,
+    candidate_filters: &CandidateFilters,
     printer: Printer,
 ) -> Result<ResolutionGraph, Error> {
     let start = std::time::Instant::now();
@@ -625,6 +760,7 @@ async fn resolve(
         build_dispatch,
         site_packages,
     )?
+    .with_candidate_filters(candidate_filters.clone())
     .with_reporter(ResolverReporter::from(printer));
     let resolution = resolver.resolve().await?;
 
@@ -663,6 +799,9 @@ async fn install(
     reinstall: &Reinstall,
     no_binary: &NoBinary,
     link_mode: LinkMode,
+    shebang: Shebang,
+    allow_overwrite: bool,
+    strict: bool,
     compile: bool,
     index_urls: &IndexLocations,
     hasher: &HashStrategy,
@@ -672,6 +811,8 @@ async fn install(
     build_dispatch: &BuildDispatch<'_>,
     cache: &Cache,
     venv: &PythonEnvironment,
+    requested: FxHashSet<PackageName>,
+    attestation_policy: &AttestationPolicy,
     dry_run: bool,
     printer: Printer,
 ) -> Result<(), Error> {
@@ -690,7 +831,7 @@ async fn install(
     let plan = Planner::with_requirements(&requirements)
         .with_editable_requirements(&editables)
         .build(
-            site_packages,
+            site_packages.clone(),
             reinstall,
             no_binary,
             hasher,
@@ -740,19 +881,24 @@ async fn install(
         })
         .collect::<Vec<_>>();
 
+    // Verify any attestations before downloading the distributions.
+    let attested = verify_attestations(&remote, attestation_policy, client, printer).await?;
+
     // Download, build, and unzip any missing distributions.
     let wheels = if remote.is_empty() {
         vec![]
     } else {
         let start = std::time::Instant::now();
 
-        let downloader = Downloader::new(cache, tags, hasher, client, build_dispatch)
+        let hasher = attested.hasher(hasher);
+        let downloader = Downloader::new(cache, tags, &hasher, client, build_dispatch)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
             .download(remote.clone(), in_flight)
             .await
             .context("Failed to download distributions")?;
+        attested.check(&wheels)?;
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -769,32 +915,13 @@ async fn install(
         wheels
     };
 
-    // Remove any existing installations.
-    if !reinstalls.is_empty() {
-        for dist_info in &reinstalls {
-            match uv_installer::uninstall(dist_info).await {
-                Ok(summary) => {
-                    debug!(
-                        "Uninstalled {} ({} file{}, {} director{})",
-                        dist_info.name(),
-                        summary.file_count,
-                        if summary.file_count == 1 { "" } else { "s" },
-                        summary.dir_count,
-                        if summary.dir_count == 1 { "y" } else { "ies" },
-                    );
-                }
-                Err(uv_installer::UninstallError::Uninstall(
-                    install_wheel_rs::Error::MissingRecord(_),
-                )) => {
-                    warn_user!(
-                        "Failed to uninstall package at {} due to missing RECORD file. Installation may result in an incomplete environment.",
-                        dist_info.path().user_display().cyan(),
-                    );
-                }
-                Err(err) => return Err(err.into()),
-            }
-        }
-    }
+    // Remove any existing installations, taking a snapshot to restore if the installation fails.
+    let mut snapshot = if reinstalls.is_empty() {
+        None
+    } else {
+        Some(Snapshot::take(&reinstalls, venv)?)
+    };
+    uninstall_dists(&reinstalls, &mut snapshot).await?;
 
     // Install the resolved distributions.
     let wheels = wheels.into_iter().chain(cached).collect::<Vec<_>>();
@@ -802,6 +929,11 @@ async fn install(
         let start = std::time::Instant::now();
         uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_shebang(shebang)
+            .with_conflict_check((!allow_overwrite).then_some(&site_packages))
+            .with_strict(strict)
+            .with_requested(requested)
+            .with_snapshot(snapshot)
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
@@ -0,0 +1,393 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde_json::{json, Value};
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{InstalledDist, Name};
+use pep508_rs::MarkerEnvironment;
+use pypi_types::{DirectUrl, MetadataLicense};
+use uv_cache::Cache;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::PythonEnvironment;
+use uv_normalize::PackageName;
+use uv_resolver::{Lock, LockedDist, LockedSource, ResolutionGraph};
+
+use crate::commands::{ExitStatus, SbomFormat};
+use crate::printer::Printer;
+
+/// Generate a software bill of materials (SBOM) for the installed packages in the current
+/// environment.
+pub(crate) fn pip_sbom(
+    format: SbomFormat,
+    python: Option<&str>,
+    system: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let venv = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else if system {
+        PythonEnvironment::from_default_python(cache)?
+    } else {
+        match PythonEnvironment::from_virtualenv(cache) {
+            Ok(venv) => venv,
+            Err(uv_interpreter::Error::VenvNotFound) => {
+                PythonEnvironment::from_default_python(cache)?
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let packages = site_packages
+        .iter()
+        .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
+        .map(Package::from_installed)
+        .collect::<Result<Vec<_>>>()?;
+
+    let name = venv.root().file_name().map_or_else(
+        || "environment".to_string(),
+        |name| name.to_string_lossy().to_string(),
+    );
+    let bom = render(format, &name, &packages);
+    writeln!(printer.stdout(), "{}", serde_json::to_string_pretty(&bom)?)?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Generate a software bill of materials (SBOM) for the packages in a resolution, as with
+/// `uv pip compile --format cyclonedx`.
+///
+/// Unlike an installed environment, the resolution records the exact artifact selected for each
+/// registry package, so every package carries its download URL and hashes.
+pub(crate) fn resolution_sbom(
+    format: SbomFormat,
+    name: &str,
+    resolution: &ResolutionGraph,
+    markers: &MarkerEnvironment,
+) -> Result<Value> {
+    let lock = Lock::from_resolution_graph(resolution, markers)?;
+    let packages = lock
+        .distributions()
+        .iter()
+        .map(|dist| Package::from_locked(dist, resolution.license_for(dist.name())))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(render(format, name, &packages))
+}
+
+/// Render the packages in the given format.
+fn render(format: SbomFormat, name: &str, packages: &[Package]) -> Value {
+    match format {
+        SbomFormat::CycloneDx => cyclonedx(packages),
+        SbomFormat::Spdx => spdx(name, packages),
+    }
+}
+
+/// A package to include in the bill of materials.
+#[derive(Debug)]
+struct Package {
+    name: PackageName,
+    version: String,
+    /// The URL from which the package was installed, if it wasn't installed from a registry.
+    url: Option<String>,
+    /// The hashes of the installed archive, as `(algorithm, digest)` pairs.
+    hashes: Vec<(String, String)>,
+    /// The license declared in the package's `METADATA`.
+    license: MetadataLicense,
+}
+
+impl Package {
+    /// Collect the bill of materials entry for an installed distribution.
+    ///
+    /// Hashes are only known for archives installed from a direct URL, as recorded in
+    /// `direct_url.json`.
+    fn from_installed(dist: &InstalledDist) -> Result<Self> {
+        let (url, hashes) = match InstalledDist::direct_url(dist.path())? {
+            Some(DirectUrl::ArchiveUrl {
+                url, archive_info, ..
+            }) => {
+                let mut hashes = archive_info
+                    .hashes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                if hashes.is_empty() {
+                    if let Some((algorithm, digest)) = archive_info
+                        .hash
+                        .as_deref()
+                        .and_then(|hash| hash.split_once('='))
+                    {
+                        hashes.push((algorithm.to_string(), digest.to_string()));
+                    }
+                }
+                hashes.sort_unstable();
+                (Some(url), hashes)
+            }
+            Some(DirectUrl::VcsUrl { url, vcs_info, .. }) => {
+                let url = match vcs_info.commit_id {
+                    Some(commit_id) => format!("{}+{url}@{commit_id}", vcs_info.vcs),
+                    None => format!("{}+{url}", vcs_info.vcs),
+                };
+                (Some(url), Vec::new())
+            }
+            Some(DirectUrl::LocalDirectory { url, .. }) => (Some(url), Vec::new()),
+            None => (None, Vec::new()),
+        };
+
+        let license = match dist.license() {
+            Ok(license) => license,
+            Err(err) => {
+                debug!("Failed to read license for {dist}: {err}");
+                MetadataLicense::default()
+            }
+        };
+
+        Ok(Self {
+            name: dist.name().clone(),
+            version: dist.version().to_string(),
+            url,
+            hashes,
+            license,
+        })
+    }
+
+    /// Collect the bill of materials entry for a distribution in a resolution.
+    ///
+    /// Editable distributions have no known license, as their metadata isn't retained.
+    fn from_locked(dist: &LockedDist, license: Option<&MetadataLicense>) -> Result<Self> {
+        let url = match dist.source() {
+            LockedSource::Registry(_) => dist.file().map(|file| file.url().to_string()),
+            LockedSource::DirectUrl(url) | LockedSource::Path(url) | LockedSource::Git(url) => {
+                Some(url.to_string())
+            }
+        };
+        let mut hashes = dist
+            .hashes()?
+            .into_iter()
+            .map(|hash| (hash.algorithm.to_string(), hash.digest.to_string()))
+            .collect::<Vec<_>>();
+        hashes.sort_unstable();
+
+        Ok(Self {
+            name: dist.name().clone(),
+            version: dist.version().to_string(),
+            url,
+            hashes,
+            license: license.cloned().unwrap_or_default(),
+        })
+    }
+
+    /// Return the package URL (purl) that identifies the package.
+    fn purl(&self) -> String {
+        format!("pkg:pypi/{}@{}", self.name, self.version)
+    }
+
+    /// Return the names of the declared licenses, if no SPDX expression is available.
+    ///
+    /// Prefers the first line of the free-text `License` field, falling back to the final
+    /// component of any `License ::` classifiers.
+    fn license_names(&self) -> Vec<String> {
+        if let Some(text) = self
+            .license
+            .text
+            .as_deref()
+            .and_then(|text| text.lines().next())
+        {
+            return vec![text.trim().to_string()];
+        }
+        self.license
+            .classifiers
+            .iter()
+            .map(|classifier| {
+                let name = classifier.rsplit("::").next().unwrap_or(classifier);
+                name.trim().to_string()
+            })
+            .collect()
+    }
+}
+
+/// Render the packages as a CycloneDX 1.5 JSON document.
+fn cyclonedx(packages: &[Package]) -> Value {
+    let components = packages
+        .iter()
+        .map(|package| {
+            let purl = package.purl();
+            let mut component = json!({
+                "type": "library",
+                "bom-ref": purl,
+                "name": package.name.to_string(),
+                "version": package.version,
+                "purl": purl,
+            });
+
+            let hashes = package
+                .hashes
+                .iter()
+                .filter_map(|(algorithm, digest)| {
+                    let algorithm = match algorithm.as_str() {
+                        "md5" => "MD5",
+                        "sha256" => "SHA-256",
+                        "sha384" => "SHA-384",
+                        "sha512" => "SHA-512",
+                        _ => return None,
+                    };
+                    Some(json!({ "alg": algorithm, "content": digest }))
+                })
+                .collect::<Vec<_>>();
+            if !hashes.is_empty() {
+                component["hashes"] = Value::Array(hashes);
+            }
+
+            let licenses = if let Some(expression) = package.license.expression.as_deref() {
+                vec![json!({ "expression": expression })]
+            } else {
+                package
+                    .license_names()
+                    .into_iter()
+                    .map(|name| json!({ "license": { "name": name } }))
+                    .collect()
+            };
+            if !licenses.is_empty() {
+                component["licenses"] = Value::Array(licenses);
+            }
+
+            if let Some(url) = package.url.as_deref() {
+                component["externalReferences"] = json!([{ "type": "distribution", "url": url }]);
+            }
+
+            component
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "tools": [{ "name": "uv", "version": env!("CARGO_PKG_VERSION") }],
+        },
+        "components": components,
+    })
+}
+
+/// Render the packages as an SPDX 2.3 JSON document.
+fn spdx(name: &str, packages: &[Package]) -> Value {
+    let now = Utc::now();
+
+    let spdx_packages = packages
+        .iter()
+        .map(|package| {
+            let mut spdx_package = json!({
+                "name": package.name.to_string(),
+                "SPDXID": spdx_id(package),
+                "versionInfo": package.version,
+                "downloadLocation": package.url.as_deref().unwrap_or("NOASSERTION"),
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": package.license.expression.as_deref().unwrap_or("NOASSERTION"),
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": package.purl(),
+                }],
+            });
+
+            let checksums = package
+                .hashes
+                .iter()
+                .filter_map(|(algorithm, digest)| {
+                    let algorithm = match algorithm.as_str() {
+                        "md5" => "MD5",
+                        "sha256" => "SHA256",
+                        "sha384" => "SHA384",
+                        "sha512" => "SHA512",
+                        _ => return None,
+                    };
+                    Some(json!({ "algorithm": algorithm, "checksumValue": digest }))
+                })
+                .collect::<Vec<_>>();
+            if !checksums.is_empty() {
+                spdx_package["checksums"] = Value::Array(checksums);
+            }
+
+            // Without an SPDX expression, record the declared license as a free-text comment.
+            if package.license.expression.is_none() {
+                let names = package.license_names();
+                if !names.is_empty() {
+                    spdx_package["licenseComments"] = json!(names.join(", "));
+                }
+            }
+
+            spdx_package
+        })
+        .collect::<Vec<_>>();
+
+    let relationships = packages
+        .iter()
+        .map(|package| {
+            json!({
+                "spdxElementId": "SPDXRef-DOCUMENT",
+                "relationshipType": "DESCRIBES",
+                "relatedSpdxElement": spdx_id(package),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": name,
+        "documentNamespace": namespace(name, now),
+        "creationInfo": {
+            "created": now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "creators": [format!("Tool: uv-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": spdx_packages,
+        "relationships": relationships,
+    })
+}
+
+/// Return a unique URI for an SPDX document with the given name, created at the given time.
+///
+/// The name is percent-encoded as a single path segment, since it's taken from user-controlled
+/// input (e.g., the name of the environment's directory).
+fn namespace(name: &str, created: DateTime<Utc>) -> String {
+    let mut url = Url::parse("https://spdx.org/spdxdocs").expect("valid URL");
+    url.path_segments_mut()
+        .expect("URL to be a base")
+        .push(&format!(
+            "{name}-{}",
+            created.timestamp_nanos_opt().unwrap_or_default()
+        ));
+    url.to_string()
+}
+
+/// Return the SPDX identifier for a package, which may only contain letters, numbers, `.`, and
+/// `-`.
+fn spdx_id(package: &Package) -> String {
+    format!("SPDXRef-Package-{}-{}", package.name, package.version)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
@@ -7,35 +7,47 @@ use owo_colors::OwoColorize;
 use tracing::debug;
 
 use distribution_types::{
-    IndexLocations, InstalledMetadata, LocalDist, LocalEditable, LocalEditables, Name, ResolvedDist,
+    Dist, IndexLocations, InstalledMetadata, LocalDist, LocalEditable, LocalEditables, Name,
+    Resolution, ResolvedDist, SourceDist,
 };
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::Shebang;
 
+use pep508_rs::{MarkerEnvironment, Requirement, RequirementsTxtRequirement};
 use platform_tags::Tags;
 use pypi_types::Yanked;
-use requirements_txt::EditableRequirement;
+use requirements_txt::{EditableRequirement, RequirementEntry};
+use uv_attestation::AttestationPolicy;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::{ArchiveTarget, ArchiveTimestamp, Cache};
 use uv_client::{
     BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClient, RegistryClientBuilder,
 };
 use uv_configuration::{
-    ConfigSettings, IndexStrategy, NoBinary, NoBuild, Reinstall, SetupPyStrategy,
+    Concurrency, ConfigSettings, IndexStrategy, NoBinary, NoBuild, Reinstall, SetupPyStrategy,
+    TagPolicy,
 };
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_installer::{is_dynamic, Downloader, Plan, Planner, ResolvedEditable, SitePackages};
+use uv_installer::{
+    is_dynamic, Downloader, Plan, Planner, ResolvedEditable, SitePackages, Snapshot,
+};
 use uv_interpreter::{Interpreter, PythonEnvironment};
 use uv_requirements::{
     ExtrasSpecification, NamedRequirementsResolver, RequirementsSource, RequirementsSpecification,
     SourceTreeResolver,
 };
-use uv_resolver::{DependencyMode, FlatIndex, InMemoryIndex, Manifest, OptionsBuilder, Resolver};
+use uv_resolver::{
+    DependencyMode, FlatIndex, InMemoryIndex, Lock, Manifest, OptionsBuilder, PreReleaseMode,
+    Resolver, YankedMode,
+};
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
-use uv_warnings::warn_user;
 
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    candidate_filters, compile_bytecode, elapsed, interpreter_tags, uninstall_dists,
+    verify_attestations, ChangeEvent, ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
 
 /// Install a set of locked requirements into the current Python environment.
@@ -44,8 +56,11 @@ pub(crate) async fn pip_sync(
     sources: &[RequirementsSource],
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    shebang: Shebang,
+    allow_overwrite: bool,
     compile: bool,
     require_hashes: bool,
+    yanked_mode: YankedMode,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProvider,
@@ -55,11 +70,14 @@ pub(crate) async fn pip_sync(
     no_build_isolation: bool,
     no_build: NoBuild,
     no_binary: NoBinary,
+    tag_policy: TagPolicy,
+    attestation_policy: &AttestationPolicy,
     strict: bool,
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
     native_tls: bool,
+    concurrency: Concurrency,
     cache: Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -68,15 +86,31 @@ pub(crate) async fn pip_sync(
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .keyring_provider(keyring_provider);
 
+    // A lockfile is installed in place of the requirements, once the target environment is known.
+    let lockfile = match sources {
+        [RequirementsSource::Lockfile(path)] => Some(path),
+        _ if sources
+            .iter()
+            .any(|source| matches!(source, RequirementsSource::Lockfile(_))) =>
+        {
+            return Err(anyhow!(
+                "A lockfile can't be combined with other requirements"
+            ));
+        }
+        _ => None,
+    };
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project: _,
-        requirements,
+        requires_python: _,
+        mut requirements,
         constraints: _,
         overrides: _,
-        editables,
+        mut editables,
         source_trees,
         extras: _,
         index_url,
@@ -85,11 +119,16 @@ pub(crate) async fn pip_sync(
         find_links,
         no_binary: specified_no_binary,
         no_build: specified_no_build,
-    } = RequirementsSpecification::from_simple_sources(sources, &client_builder).await?;
+        pre,
+    } = if lockfile.is_some() {
+        RequirementsSpecification::default()
+    } else {
+        RequirementsSpecification::from_simple_sources(sources, &client_builder).await?
+    };
 
     // Validate that the requirements are non-empty.
-    let num_requirements = requirements.len() + source_trees.len() + editables.len();
-    if num_requirements == 0 {
+    let mut num_requirements = requirements.len() + source_trees.len() + editables.len();
+    if num_requirements == 0 && lockfile.is_none() {
         writeln!(printer.stderr(), "No requirements found")?;
         return Ok(ExitStatus::Success);
     }
@@ -131,9 +170,25 @@ pub(crate) async fn pip_sync(
     let _lock = venv.lock()?;
 
     // Determine the current environment markers.
-    let tags = venv.interpreter().tags()?;
+    let tags = interpreter_tags(venv.interpreter(), tag_policy)?;
     let markers = venv.interpreter().markers();
 
+    // Select the locked distributions that apply to the current environment.
+    let resolution = if let Some(lockfile) = lockfile {
+        let lock = Lock::from_toml(&fs_err::read_to_string(lockfile)?)
+            .with_context(|| format!("Failed to read lockfile: {}", lockfile.user_display()))?;
+        let resolution = lock.to_resolution(markers, &tags)?;
+        (requirements, editables) = locked_requirements(&lock, &resolution, markers)?;
+        num_requirements = requirements.len() + editables.len();
+        if num_requirements == 0 {
+            writeln!(printer.stderr(), "No requirements found")?;
+            return Ok(ExitStatus::Success);
+        }
+        Some(resolution)
+    } else {
+        None
+    };
+
     // Collect the set of required hashes.
     let hasher = if require_hashes {
         HashStrategy::from_requirements(
@@ -158,6 +213,7 @@ pub(crate) async fn pip_sync(
     // Initialize the registry client.
     let client = RegistryClientBuilder::new(cache.clone())
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .connectivity(connectivity)
         .index_urls(index_locations.index_urls())
         .index_strategy(index_strategy)
@@ -166,11 +222,18 @@ pub(crate) async fn pip_sync(
         .platform(venv.interpreter().platform())
         .build();
 
+    // Determine the policy for selecting among the files listed by each index.
+    let candidate_filters = candidate_filters(tag_policy);
+
+    // Combine the `--no-binary` and `--no-build` flags.
+    let no_binary = no_binary.combine(specified_no_binary);
+    let no_build = no_build.combine(specified_no_build);
+
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags, &hasher, &no_build, &no_binary)
+        FlatIndex::from_entries(entries, &tags, &hasher, &no_build, &no_binary)
     };
 
     // Create a shared in-memory index.
@@ -186,10 +249,6 @@ pub(crate) async fn pip_sync(
         BuildIsolation::Isolated
     };
 
-    // Combine the `--no-binary` and `--no-build` flags.
-    let no_binary = no_binary.combine(specified_no_binary);
-    let no_build = no_build.combine(specified_no_build);
-
     // Determine the set of installed packages.
     let site_packages = SitePackages::from_executable(&venv)?;
 
@@ -246,7 +305,7 @@ pub(crate) async fn pip_sync(
         reinstall,
         &hasher,
         venv.interpreter(),
-        tags,
+        &tags,
         &cache,
         &client,
         &build_dispatch,
@@ -265,14 +324,14 @@ pub(crate) async fn pip_sync(
     } = Planner::with_requirements(&requirements)
         .with_editable_requirements(&resolved_editables.editables)
         .build(
-            site_packages,
+            site_packages.clone(),
             reinstall,
             &no_binary,
             &hasher,
             &index_locations,
             &cache,
             &venv,
-            tags,
+            &tags,
         )
         .context("Failed to determine installation plan")?;
 
@@ -296,17 +355,33 @@ pub(crate) async fn pip_sync(
     // Resolve any registry-based requirements.
     let remote = if remote.is_empty() {
         Vec::new()
+    } else if let Some(resolution) = resolution.as_ref() {
+        // Install the locked distributions, without re-resolving.
+        remote
+            .iter()
+            .map(|requirement| {
+                resolution
+                    .get_remote(&requirement.name)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("`{}` is not present in the lockfile", requirement.name))
+            })
+            .collect::<Result<Vec<_>>>()?
     } else {
         let start = std::time::Instant::now();
 
-        // Determine the tags, markers, and interpreter to use for resolution.
+        // Determine the markers and interpreter to use for resolution.
         let interpreter = venv.interpreter();
-        let tags = interpreter.tags()?;
         let markers = interpreter.markers();
 
-        // Resolve with `--no-deps`.
+        // Resolve with `--no-deps`, allowing pre-releases if any requirements file enables `--pre`.
         let options = OptionsBuilder::new()
             .dependency_mode(DependencyMode::Direct)
+            .prerelease_mode(if pre {
+                PreReleaseMode::Allow
+            } else {
+                PreReleaseMode::default()
+            })
+            .yanked_mode(yanked_mode)
             .build();
 
         // Create a bound on the progress bar, since we know the number of packages upfront.
@@ -318,7 +393,7 @@ pub(crate) async fn pip_sync(
             options,
             markers,
             interpreter,
-            tags,
+            &tags,
             &client,
             &flat_index,
             &index,
@@ -327,6 +402,7 @@ pub(crate) async fn pip_sync(
             // TODO(zanieb): We should consider support for installed packages in pip sync
             &EmptyInstalledPackages,
         )?
+        .with_candidate_filters(candidate_filters)
         .with_reporter(reporter);
 
         let resolution = match resolver.resolve().await {
@@ -360,19 +436,24 @@ pub(crate) async fn pip_sync(
             .collect::<Vec<_>>()
     };
 
+    // Verify any attestations before downloading the distributions.
+    let attested = verify_attestations(&remote, attestation_policy, &client, printer).await?;
+
     // Download, build, and unzip any missing distributions.
     let wheels = if remote.is_empty() {
         Vec::new()
     } else {
         let start = std::time::Instant::now();
 
-        let downloader = Downloader::new(&cache, tags, &hasher, &client, &build_dispatch)
+        let hasher = attested.hasher(&hasher);
+        let downloader = Downloader::new(&cache, &tags, &hasher, &client, &build_dispatch)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
             .download(remote.clone(), &in_flight)
             .await
             .context("Failed to download distributions")?;
+        attested.check(&wheels)?;
 
         let s = if wheels.len() == 1 { "" } else { "s" };
         writeln!(
@@ -389,33 +470,19 @@ pub(crate) async fn pip_sync(
         wheels
     };
 
-    // Remove any unnecessary packages.
+    // Remove any unnecessary packages, taking a snapshot to restore if the installation fails.
+    let mut snapshot = if extraneous.is_empty() && reinstalls.is_empty() {
+        None
+    } else {
+        Some(Snapshot::take(
+            extraneous.iter().chain(reinstalls.iter()),
+            &venv,
+        )?)
+    };
     if !extraneous.is_empty() || !reinstalls.is_empty() {
         let start = std::time::Instant::now();
 
-        for dist_info in extraneous.iter().chain(reinstalls.iter()) {
-            match uv_installer::uninstall(dist_info).await {
-                Ok(summary) => {
-                    debug!(
-                        "Uninstalled {} ({} file{}, {} director{})",
-                        dist_info.name(),
-                        summary.file_count,
-                        if summary.file_count == 1 { "" } else { "s" },
-                        summary.dir_count,
-                        if summary.dir_count == 1 { "y" } else { "ies" },
-                    );
-                }
-                Err(uv_installer::UninstallError::Uninstall(
-                    install_wheel_rs::Error::MissingRecord(_),
-                )) => {
-                    warn_user!(
-                        "Failed to uninstall package at {} due to missing RECORD file. Installation may result in an incomplete environment.",
-                        dist_info.path().user_display().cyan(),
-                    );
-                }
-                Err(err) => return Err(err.into()),
-            }
-        }
+        uninstall_dists(extraneous.iter().chain(reinstalls.iter()), &mut snapshot).await?;
 
         let s = if extraneous.len() + reinstalls.len() == 1 {
             ""
@@ -440,6 +507,10 @@ pub(crate) async fn pip_sync(
         let start = std::time::Instant::now();
         uv_installer::Installer::new(&venv)
             .with_link_mode(link_mode)
+            .with_shebang(shebang)
+            .with_conflict_check((!allow_overwrite).then_some(&site_packages))
+            .with_strict(strict)
+            .with_snapshot(snapshot)
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
@@ -546,6 +617,37 @@ pub(crate) async fn pip_sync(
     Ok(ExitStatus::Success)
 }
 
+/// Convert the locked distributions that apply to the given environment (as selected by
+/// [`Lock::to_resolution`]) into pinned requirements, with their locked hashes, and editables.
+fn locked_requirements(
+    lock: &Lock,
+    resolution: &Resolution,
+    markers: &MarkerEnvironment,
+) -> Result<(Vec<RequirementEntry>, Vec<EditableRequirement>)> {
+    let mut requirements = Vec::new();
+    let mut editables = Vec::new();
+    for locked in lock.applicable(markers)? {
+        let Some(ResolvedDist::Installable(dist)) = resolution.get(locked.name()) else {
+            continue;
+        };
+        if let Dist::Source(SourceDist::Path(sdist)) = dist {
+            if sdist.editable {
+                editables.push(EditableRequirement {
+                    url: sdist.url.clone(),
+                    extras: locked.extras().to_vec(),
+                    path: sdist.path.clone(),
+                });
+                continue;
+            }
+        }
+        requirements.push(RequirementEntry {
+            requirement: RequirementsTxtRequirement::Pep508(Requirement::from(dist.clone())),
+            hashes: locked.hashes()?.iter().map(ToString::to_string).collect(),
+        });
+    }
+    Ok((requirements, editables))
+}
+
 #[derive(Debug)]
 struct ResolvedEditables {
     /// The set of resolved editables, including both those that were already installed and those
@@ -10,8 +10,10 @@ use pep508_rs::{Requirement, RequirementsTxtRequirement, UnnamedRequirement};
 use uv_auth::KeyringProvider;
 use uv_cache::Cache;
 use uv_client::{BaseClientBuilder, Connectivity};
+use uv_configuration::Concurrency;
 use uv_fs::Simplified;
 use uv_interpreter::PythonEnvironment;
+use uv_warnings::warn_user;
 
 use crate::commands::{elapsed, ExitStatus};
 use crate::printer::Printer;
@@ -27,6 +29,7 @@ pub(crate) async fn pip_uninstall(
     cache: Cache,
     connectivity: Connectivity,
     native_tls: bool,
+    concurrency: Concurrency,
     keyring_provider: KeyringProvider,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -34,6 +37,7 @@ pub(crate) async fn pip_uninstall(
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
+        .concurrency(concurrency)
         .keyring_provider(keyring_provider);
 
     // Read all requirements from the provided sources.
@@ -173,6 +177,9 @@ pub(crate) async fn pip_uninstall(
             summary.dir_count,
             if summary.dir_count == 1 { "y" } else { "ies" },
         );
+        for warning in &summary.warnings {
+            warn_user!("{distribution}: {warning}");
+        }
     }
 
     writeln!(
@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+use url::Url;
+
+use uv_auth::{default_providers, redact_url, KeyringProvider};
+use uv_client::BaseClientBuilder;
+use uv_configuration::Concurrency;
+use uv_publish::{
+    files_for_publishing, get_trusted_publishing_token, upload, Credentials, PublishError,
+    TrustedPublishing, UploadOutcome,
+};
+
+use crate::commands::{human_readable_bytes, ExitStatus};
+use crate::printer::Printer;
+
+/// Upload wheels and source distributions to a package index.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn publish(
+    paths: Vec<String>,
+    publish_url: &Url,
+    credentials: Credentials,
+    skip_existing: bool,
+    retries: u32,
+    keyring_provider: KeyringProvider,
+    trusted_publishing: TrustedPublishing,
+    native_tls: bool,
+    concurrency: Concurrency,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let files = files_for_publishing(paths)?;
+
+    // Retries are handled per upload, rather than by the client middleware, so that each attempt
+    // is reported.
+    let client = BaseClientBuilder::new()
+        .keyring_provider(keyring_provider)
+        .native_tls(native_tls)
+        .concurrency(concurrency)
+        .retries(0)
+        .build();
+
+    // Fall back to the same credential providers as the index client (environment variables,
+    // netrc, and the keyring), and then to trusted publishing. In `always` mode, trusted
+    // publishing takes precedence over the providers, but not over explicit credentials.
+    let credentials = if trusted_publishing == TrustedPublishing::Always {
+        credentials
+    } else {
+        credentials
+            .or_from_providers(publish_url, &default_providers(keyring_provider))
+            .await
+    };
+    let credentials =
+        if matches!(credentials, Credentials::None) && publish_url.username().is_empty() {
+            match get_trusted_publishing_token(publish_url, trusted_publishing, &client)
+                .await
+                .map_err(PublishError::TrustedPublishing)?
+            {
+                Some(token) => {
+                    debug!("Using trusted publishing to authenticate the upload");
+                    Credentials::Token(token)
+                }
+                None => credentials,
+            }
+        } else {
+            credentials
+        };
+
+    let s = if files.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "Publishing {} file{s} to {}",
+        files.len(),
+        redact_url(publish_url).cyan()
+    )?;
+
+    for (file, filename) in files {
+        let size = fs_err::metadata(&file)?.len();
+        let (bytes, unit) = human_readable_bytes(size);
+        writeln!(
+            printer.stderr(),
+            "{} {filename} {}",
+            "Uploading".bold().green(),
+            format!("({bytes:.1}{unit})").dimmed()
+        )?;
+
+        match upload(
+            &file,
+            &filename,
+            publish_url,
+            &client,
+            retries,
+            &credentials,
+            skip_existing,
+        )
+        .await?
+        {
+            UploadOutcome::Uploaded => {}
+            UploadOutcome::AlreadyExists => {
+                writeln!(
+                    printer.stderr(),
+                    "{}",
+                    format!("File {filename} already exists, skipping").dimmed()
+                )?;
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
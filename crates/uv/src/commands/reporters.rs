@@ -9,6 +9,7 @@ use distribution_types::{
     BuildableSource, CachedDist, DistributionMetadata, LocalEditable, Name, SourceDist,
     VersionOrUrl,
 };
+use install_wheel_rs::linker::InstallSummary;
 use uv_normalize::PackageName;
 
 use crate::printer::Printer;
@@ -153,8 +154,11 @@ impl InstallReporter {
 }
 
 impl uv_installer::InstallReporter for InstallReporter {
-    fn on_install_progress(&self, wheel: &CachedDist) {
+    fn on_install_start(&self, wheel: &CachedDist) {
         self.progress.set_message(format!("{wheel}"));
+    }
+
+    fn on_install_progress(&self, _wheel: &CachedDist, _summary: &InstallSummary) {
         self.progress.inc(1);
     }
 
@@ -276,6 +280,10 @@ impl ResolverReporter {
 }
 
 impl uv_resolver::ResolverReporter for ResolverReporter {
+    fn on_fetch(&self, name: &PackageName) {
+        self.progress.set_message(format!("{name}"));
+    }
+
     fn on_progress(&self, name: &PackageName, version_or_url: &VersionOrUrl) {
         self.on_progress(name, version_or_url);
     }
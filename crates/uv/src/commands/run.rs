@@ -0,0 +1,228 @@
+use std::ffi::OsString;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use uv_auth::KeyringProvider;
+use uv_cache::{Cache, CacheBucket, CacheEntry};
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, IndexStrategy};
+use uv_fs::{LockedFile, Simplified};
+use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_requirements::Pep723Metadata;
+use uv_resolver::ExcludeNewer;
+
+use crate::commands::{install_requirements, ExitStatus};
+use crate::printer::Printer;
+
+/// Run a Python script, provisioning an environment for the dependencies declared in its inline
+/// metadata (PEP 723).
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run(
+    script: &Path,
+    args: &[OsString],
+    python: Option<&str>,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Read the inline metadata from the script.
+    let metadata = Pep723Metadata::read(script)
+        .with_context(|| format!("Failed to read script: {}", script.user_display()))?;
+
+    // Locate the Python interpreter.
+    let base = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else {
+        PythonEnvironment::from_default_python(cache)?
+    };
+    let interpreter = base.interpreter().clone();
+
+    // Validate that the interpreter is compatible with the script.
+    if let Some(requires_python) = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.requires_python.as_ref())
+    {
+        if !requires_python.contains(interpreter.python_version()) {
+            return Err(anyhow!(
+                "The script requires Python {}, but {} is installed",
+                requires_python,
+                interpreter.python_version()
+            ));
+        }
+    }
+
+    // If the script has dependencies, provision an environment for them.
+    let venv = match metadata {
+        Some(metadata) if !metadata.dependencies.is_empty() => Some(
+            script_environment(
+                &metadata,
+                interpreter,
+                link_mode,
+                index_locations,
+                index_strategy,
+                keyring_provider,
+                connectivity,
+                exclude_newer,
+                native_tls,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await?,
+        ),
+        _ => None,
+    };
+
+    let executable = venv.as_ref().map_or_else(
+        || base.python_executable(),
+        PythonEnvironment::python_executable,
+    );
+    debug!(
+        "Running {} with {}",
+        script.user_display(),
+        executable.user_display()
+    );
+
+    let mut command = std::process::Command::new(executable);
+    command.arg(script).args(args);
+
+    // Expose the environment's scripts to the child process.
+    if let Some(venv) = venv.as_ref() {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let path = std::env::join_paths(
+            std::iter::once(venv.scripts().to_path_buf()).chain(std::env::split_paths(&path)),
+        )?;
+        command.env("VIRTUAL_ENV", venv.root()).env("PATH", path);
+    }
+
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run: {}", executable.user_display()))?;
+
+    match status.code() {
+        Some(0) => Ok(ExitStatus::Success),
+        Some(code) => Ok(ExitStatus::External(u8::try_from(code).unwrap_or(1))),
+        // The child process was terminated by a signal.
+        None => Ok(ExitStatus::Failure),
+    }
+}
+
+/// Return a cached environment that satisfies the script's dependencies, creating it if
+/// necessary.
+#[allow(clippy::too_many_arguments)]
+async fn script_environment(
+    metadata: &Pep723Metadata,
+    interpreter: Interpreter,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<PythonEnvironment> {
+    // Key the environment by the interpreter, the (order-independent) requirements, and the
+    // options that affect their resolution.
+    let requirements = metadata
+        .dependencies
+        .iter()
+        .map(ToString::to_string)
+        .sorted()
+        .collect::<Vec<_>>();
+    let indexes = index_locations
+        .indexes()
+        .map(ToString::to_string)
+        .chain(index_locations.flat_index().map(ToString::to_string))
+        .collect::<Vec<_>>();
+    let key = cache_key::digest(&(
+        interpreter.sys_executable().to_path_buf(),
+        interpreter.python_version().to_string(),
+        requirements,
+        indexes,
+        exclude_newer.map(|exclude_newer| exclude_newer.timestamp_millis()),
+    ));
+
+    let bucket = cache.bucket(CacheBucket::Environments);
+    fs_err::create_dir_all(&bucket)?;
+    let root = bucket.join(&key);
+    let marker = root.join(".complete");
+
+    let _lock = LockedFile::acquire(bucket.join(format!("{key}.lock")), root.user_display())?;
+
+    // If the environment was fully provisioned by a previous run, reuse it, unless the user
+    // requested a refresh of any of its dependencies (e.g., via `--refresh`).
+    if marker.is_file() {
+        let entry = CacheEntry::from_path(&marker);
+        let mut stale = false;
+        for requirement in &metadata.dependencies {
+            if cache.freshness(&entry, Some(&requirement.name))?.is_stale() {
+                stale = true;
+                break;
+            }
+        }
+        if !stale {
+            debug!("Reusing script environment at: {}", root.user_display());
+            return Ok(PythonEnvironment::from_root(&root, cache)?);
+        }
+        debug!(
+            "Removing stale script environment at: {}",
+            root.user_display()
+        );
+        fs_err::remove_dir_all(&root)?;
+    } else if root.exists() {
+        // Otherwise, discard any partially provisioned environment and start over.
+        debug!(
+            "Removing incomplete script environment at: {}",
+            root.user_display()
+        );
+        fs_err::remove_dir_all(&root)?;
+    }
+
+    debug!("Creating script environment at: {}", root.user_display());
+
+    let venv = uv_virtualenv::create_venv(
+        &root,
+        interpreter,
+        uv_virtualenv::Prompt::None,
+        false,
+        false,
+        vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())],
+    )?;
+
+    // Resolve and install the script's dependencies.
+    install_requirements(
+        &metadata.dependencies,
+        &venv,
+        link_mode,
+        index_locations,
+        index_strategy,
+        keyring_provider,
+        connectivity,
+        exclude_newer,
+        native_tls,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await?;
+
+    // Mark the environment as complete, such that it can be reused.
+    fs_err::write(&marker, "")?;
+
+    Ok(venv)
+}
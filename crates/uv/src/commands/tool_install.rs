@@ -0,0 +1,385 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use pep440_rs::Version;
+use pep508_rs::Requirement;
+use uv_auth::KeyringProvider;
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, IndexStrategy};
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::{Interpreter, PythonEnvironment};
+use uv_normalize::PackageName;
+use uv_resolver::ExcludeNewer;
+use uv_tool::{find_executable_directory, InstalledTools, Tool, ToolEntrypoint};
+use uv_warnings::warn_user;
+
+use crate::commands::{install_requirements, ExitStatus};
+use crate::printer::Printer;
+
+/// Install a tool into its own environment, exposing its executables.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn tool_install(
+    requirement: &str,
+    python: Option<&str>,
+    force: bool,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let requirement = Requirement::from_str(requirement)
+        .with_context(|| format!("Failed to parse tool requirement: `{requirement}`"))?;
+
+    let installed_tools = InstalledTools::from_settings();
+    let _lock = installed_tools.acquire_lock()?;
+
+    if !force && installed_tools.get(&requirement.name)?.is_some() {
+        writeln!(
+            printer.stderr(),
+            "`{}` is already installed (use `--force` to reinstall)",
+            requirement.name.as_ref().bold()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    install_tool(
+        &installed_tools,
+        &requirement,
+        python,
+        force,
+        link_mode,
+        index_locations,
+        index_strategy,
+        keyring_provider,
+        connectivity,
+        exclude_newer,
+        native_tls,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Create a fresh environment for a tool, install it, and expose its executables, replacing any
+/// existing installation of the tool.
+///
+/// Unless `force` is set, refuses to overwrite executables that don't belong to the tool. If the
+/// installation fails, any existing installation is left in place.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(super) async fn install_tool(
+    installed_tools: &InstalledTools,
+    requirement: &Requirement,
+    python: Option<&str>,
+    force: bool,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<()> {
+    let name = &requirement.name;
+
+    // Locate the Python interpreter.
+    let interpreter = if let Some(python) = python {
+        PythonEnvironment::from_requested_python(python, cache)?
+    } else {
+        PythonEnvironment::from_default_python(cache)?
+    }
+    .interpreter()
+    .clone();
+
+    // Move any existing environment aside, such that it can be restored if the installation
+    // fails. Its executables are left in place until the replacement is ready.
+    let existing = installed_tools.get(name)?;
+    installed_tools.stash(name)?;
+
+    let result = create_tool(
+        installed_tools,
+        requirement,
+        interpreter,
+        python,
+        force,
+        existing.as_ref(),
+        link_mode,
+        index_locations,
+        index_strategy,
+        keyring_provider,
+        connectivity,
+        exclude_newer,
+        native_tls,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await;
+    let (entrypoints, bin_dir) = match result {
+        Ok(result) => result,
+        Err(err) => {
+            debug!("Restoring previous environment for `{name}`");
+            installed_tools.restore(name)?;
+            return Err(err);
+        }
+    };
+    installed_tools.discard_stash(name)?;
+
+    // Remove any executables exposed by a previous installation that are no longer provided.
+    if let Some(existing) = existing {
+        for entrypoint in existing.entrypoints {
+            if entrypoints
+                .iter()
+                .all(|(_, _, target)| *target != entrypoint.install_path)
+            {
+                debug!(
+                    "Removing stale executable: {}",
+                    entrypoint.install_path.user_display()
+                );
+                remove_executable(&entrypoint.install_path)?;
+            }
+        }
+    }
+
+    let s = if entrypoints.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "Installed {} executable{s}: {}",
+        entrypoints.len(),
+        entrypoints
+            .iter()
+            .map(|(name, _, _)| name.bold())
+            .join(", ")
+    )?;
+
+    // Warn if the executables won't be found on the `PATH`.
+    let on_path = std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir == bin_dir));
+    if !on_path {
+        warn_user!(
+            "`{}` is not on your PATH. To use the installed executables, add it to your PATH.",
+            bin_dir.user_display()
+        );
+    }
+
+    Ok(())
+}
+
+/// An executable exposed by a tool: its name, its path in the tool environment, and the path at
+/// which it's exposed.
+type Entrypoint = (String, PathBuf, PathBuf);
+
+/// Create the environment for a tool, install it, expose its executables, and write its receipt,
+/// returning the exposed executables and the directory that contains them.
+///
+/// On failure, any executables that were newly exposed are removed again; the caller is
+/// responsible for removing the environment itself.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+async fn create_tool(
+    installed_tools: &InstalledTools,
+    requirement: &Requirement,
+    interpreter: Interpreter,
+    python: Option<&str>,
+    force: bool,
+    existing: Option<&Tool>,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<(Vec<Entrypoint>, PathBuf)> {
+    let name = &requirement.name;
+
+    let tool_dir = installed_tools.tool_dir(name);
+    debug!("Creating tool environment at: {}", tool_dir.user_display());
+    let venv = uv_virtualenv::create_venv(
+        &tool_dir,
+        interpreter,
+        uv_virtualenv::Prompt::Static(name.to_string()),
+        false,
+        false,
+        vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())],
+    )?;
+
+    install_requirements(
+        std::slice::from_ref(requirement),
+        &venv,
+        link_mode,
+        index_locations,
+        index_strategy,
+        keyring_provider,
+        connectivity,
+        exclude_newer,
+        native_tls,
+        concurrency,
+        cache,
+        printer,
+    )
+    .await?;
+
+    // Determine the executables provided by the tool itself, as opposed to its dependencies.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let Some(dist) = site_packages.get_packages(name).into_iter().next() else {
+        bail!("Failed to find `{name}` in the tool environment");
+    };
+    let names = uv_tool::entrypoint_names(dist.path())?;
+    if names.is_empty() {
+        bail!("No executables are provided by `{name}`");
+    }
+
+    let bin_dir = find_executable_directory()?;
+    fs_err::create_dir_all(&bin_dir)?;
+
+    let entrypoints = names
+        .into_iter()
+        .map(|name| {
+            let filename = format!("{name}{}", std::env::consts::EXE_SUFFIX);
+            let source = venv.scripts().join(&filename);
+            let target = bin_dir.join(&filename);
+            (name, source, target)
+        })
+        .collect::<Vec<_>>();
+
+    // Avoid clobbering executables that belong to other tools, or weren't installed by uv.
+    // Executables that link into the tool's own environment (e.g., left behind by an interrupted
+    // installation) are considered to belong to the tool.
+    let owned = existing
+        .iter()
+        .flat_map(|tool| tool.entrypoints.iter())
+        .map(|entrypoint| entrypoint.install_path.as_path())
+        .collect::<Vec<_>>();
+    if !force {
+        if let Some((_, _, target)) = entrypoints.iter().find(|(_, _, target)| {
+            fs_err::symlink_metadata(target).is_ok()
+                && !owned.contains(&target.as_path())
+                && !fs_err::read_link(target).is_ok_and(|source| source.starts_with(&tool_dir))
+        }) {
+            bail!(
+                "Executable already exists: {} (use `--force` to overwrite)",
+                target.user_display()
+            );
+        }
+    }
+
+    let mut linked = Vec::new();
+    let result = entrypoints.iter().try_for_each(|(_, source, target)| {
+        debug!(
+            "Linking executable: {} -> {}",
+            target.user_display(),
+            source.user_display()
+        );
+        link_executable(source, target)?;
+        linked.push(target.as_path());
+        Ok::<_, anyhow::Error>(())
+    });
+    let result = result.and_then(|()| {
+        installed_tools.add(
+            name,
+            Tool {
+                requirement: requirement.to_string(),
+                python: python.map(ToString::to_string),
+                entrypoints: entrypoints
+                    .iter()
+                    .map(|(name, _, target)| ToolEntrypoint {
+                        name: name.clone(),
+                        install_path: target.clone(),
+                    })
+                    .collect(),
+            },
+        )?;
+        Ok(())
+    });
+    if let Err(err) = result {
+        // Remove any executables that didn't belong to the previous installation, as they'd
+        // otherwise dangle once it's restored.
+        for target in linked {
+            if !owned.contains(&target) {
+                remove_executable(target)?;
+            }
+        }
+        return Err(err);
+    }
+
+    Ok((entrypoints, bin_dir))
+}
+
+/// Return the installed version of a tool, if it can be determined.
+pub(super) fn tool_version(
+    installed_tools: &InstalledTools,
+    name: &PackageName,
+    cache: &Cache,
+) -> Option<Version> {
+    let venv = match PythonEnvironment::from_root(installed_tools.tool_dir(name), cache) {
+        Ok(venv) => venv,
+        Err(err) => {
+            debug!("Failed to read environment for `{name}`: {err}");
+            return None;
+        }
+    };
+    let site_packages = match SitePackages::from_executable(&venv) {
+        Ok(site_packages) => site_packages,
+        Err(err) => {
+            debug!("Failed to read packages for `{name}`: {err}");
+            return None;
+        }
+    };
+    let version = site_packages
+        .get_packages(name)
+        .first()
+        .map(|dist| dist.version().clone());
+    version
+}
+
+/// Expose an executable from a tool environment at the given path.
+///
+/// On Unix, the executable is symlinked. On Windows, the launcher is copied, as it refers to the
+/// environment's interpreter by its absolute path.
+#[cfg(unix)]
+fn link_executable(source: &Path, target: &Path) -> std::io::Result<()> {
+    uv_fs::replace_symlink(source, target)
+}
+
+/// Expose an executable from a tool environment at the given path.
+///
+/// On Unix, the executable is symlinked. On Windows, the launcher is copied, as it refers to the
+/// environment's interpreter by its absolute path.
+#[cfg(windows)]
+fn link_executable(source: &Path, target: &Path) -> std::io::Result<()> {
+    fs_err::copy(source, target)?;
+    Ok(())
+}
+
+/// Remove an executable exposed by a tool, if it exists.
+pub(super) fn remove_executable(path: &Path) -> std::io::Result<()> {
+    match fs_err::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_tool::InstalledTools;
+
+use crate::commands::tool_install::tool_version;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the installed tools and their executables.
+pub(crate) fn tool_list(cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+    let installed_tools = InstalledTools::from_settings();
+    let tools = installed_tools.tools()?;
+
+    if tools.is_empty() {
+        writeln!(printer.stderr(), "No tools installed")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for (name, tool) in tools {
+        match tool_version(&installed_tools, &name, cache) {
+            Some(version) => writeln!(printer.stdout(), "{} v{version}", name.as_ref().bold())?,
+            None => writeln!(printer.stdout(), "{}", name.as_ref().bold())?,
+        }
+        for entrypoint in tool.entrypoints {
+            writeln!(printer.stdout(), "- {}", entrypoint.name)?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
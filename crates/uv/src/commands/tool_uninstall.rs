@@ -0,0 +1,46 @@
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use uv_fs::Simplified;
+use uv_normalize::PackageName;
+use uv_tool::InstalledTools;
+
+use crate::commands::tool_install::remove_executable;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Uninstall tools, removing their environments and executables.
+pub(crate) fn tool_uninstall(names: &[PackageName], printer: Printer) -> Result<ExitStatus> {
+    let installed_tools = InstalledTools::from_settings();
+    let _lock = installed_tools.acquire_lock()?;
+
+    // Validate that every tool is installed before removing any of them.
+    let mut tools = Vec::with_capacity(names.len());
+    for name in names {
+        let Some(tool) = installed_tools.get(name)? else {
+            bail!("`{name}` is not installed");
+        };
+        tools.push((name, tool));
+    }
+
+    for (name, tool) in tools {
+        for entrypoint in &tool.entrypoints {
+            debug!(
+                "Removing executable: {}",
+                entrypoint.install_path.user_display()
+            );
+            remove_executable(&entrypoint.install_path)?;
+        }
+        installed_tools.remove(name)?;
+
+        writeln!(printer.stderr(), "Uninstalled {}", name.as_ref().bold())?;
+        for entrypoint in &tool.entrypoints {
+            writeln!(printer.stderr(), " {} {}", "-".red(), entrypoint.name)?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
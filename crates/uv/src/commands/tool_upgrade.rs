@@ -0,0 +1,110 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use pep508_rs::Requirement;
+use uv_auth::KeyringProvider;
+use uv_cache::{Cache, Refresh};
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, IndexStrategy};
+use uv_normalize::PackageName;
+use uv_resolver::ExcludeNewer;
+use uv_tool::InstalledTools;
+
+use crate::commands::tool_install::{install_tool, tool_version};
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Upgrade installed tools to the latest versions allowed by the requirements they were installed
+/// from.
+///
+/// Each tool is reinstalled into a fresh environment, such that its dependencies are upgraded too.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn tool_upgrade(
+    names: &[PackageName],
+    all: bool,
+    link_mode: LinkMode,
+    index_locations: &IndexLocations,
+    index_strategy: IndexStrategy,
+    keyring_provider: KeyringProvider,
+    connectivity: Connectivity,
+    exclude_newer: Option<ExcludeNewer>,
+    native_tls: bool,
+    concurrency: Concurrency,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let installed_tools = InstalledTools::from_settings();
+    let _lock = installed_tools.acquire_lock()?;
+
+    // Determine the tools to upgrade.
+    let tools = if all {
+        installed_tools.tools()?
+    } else {
+        let mut tools = Vec::with_capacity(names.len());
+        for name in names {
+            let Some(tool) = installed_tools.get(name)? else {
+                bail!("`{name}` is not installed");
+            };
+            tools.push((name.clone(), tool));
+        }
+        tools
+    };
+
+    if tools.is_empty() {
+        writeln!(printer.stderr(), "No tools installed")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Revalidate any cached index responses, to discover newly published versions.
+    let cache = cache.with_refresh(Refresh::from_args(true, Vec::new()));
+
+    for (name, tool) in tools {
+        let requirement = Requirement::from_str(&tool.requirement).with_context(|| {
+            format!(
+                "Failed to parse requirement for `{name}`: `{}`",
+                tool.requirement
+            )
+        })?;
+
+        let previous = tool_version(&installed_tools, &name, &cache);
+        install_tool(
+            &installed_tools,
+            &requirement,
+            tool.python.as_deref(),
+            false,
+            link_mode,
+            index_locations,
+            index_strategy,
+            keyring_provider,
+            connectivity,
+            exclude_newer,
+            native_tls,
+            concurrency,
+            &cache,
+            printer,
+        )
+        .await?;
+        let current = tool_version(&installed_tools, &name, &cache);
+
+        match (previous, current) {
+            (Some(previous), Some(current)) if previous != current => writeln!(
+                printer.stderr(),
+                "Upgraded {} from v{previous} to v{current}",
+                name.as_ref().bold()
+            )?,
+            (_, Some(current)) => writeln!(
+                printer.stderr(),
+                "{} is up to date (v{current})",
+                name.as_ref().bold()
+            )?,
+            (_, None) => writeln!(printer.stderr(), "Reinstalled {}", name.as_ref().bold())?,
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
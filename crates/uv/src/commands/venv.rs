@@ -17,7 +17,9 @@ use pep508_rs::Requirement;
 use uv_auth::{KeyringProvider, GLOBAL_AUTH_STORE};
 use uv_cache::Cache;
 use uv_client::{Connectivity, FlatIndexClient, RegistryClientBuilder};
-use uv_configuration::{ConfigSettings, IndexStrategy, NoBinary, NoBuild, SetupPyStrategy};
+use uv_configuration::{
+    Concurrency, ConfigSettings, IndexStrategy, NoBinary, NoBuild, SetupPyStrategy,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_interpreter::{find_default_python, find_requested_python, Error};
@@ -29,7 +31,11 @@ use crate::printer::Printer;
 use crate::shell::Shell;
 
 /// Create a virtual environment.
-#[allow(clippy::unnecessary_wraps, clippy::too_many_arguments)]
+#[allow(
+    clippy::unnecessary_wraps,
+    clippy::too_many_arguments,
+    clippy::fn_params_excessive_bools
+)]
 pub(crate) async fn venv(
     path: &Path,
     python_request: Option<&str>,
@@ -39,10 +45,12 @@ pub(crate) async fn venv(
     keyring_provider: KeyringProvider,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    copies: bool,
     connectivity: Connectivity,
     seed: bool,
     exclude_newer: Option<ExcludeNewer>,
     native_tls: bool,
+    concurrency: Concurrency,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -55,10 +63,12 @@ pub(crate) async fn venv(
         keyring_provider,
         prompt,
         system_site_packages,
+        copies,
         connectivity,
         seed,
         exclude_newer,
         native_tls,
+        concurrency,
         cache,
         printer,
     )
@@ -92,7 +102,7 @@ enum VenvError {
 }
 
 /// Create a virtual environment.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 async fn venv_impl(
     path: &Path,
     python_request: Option<&str>,
@@ -102,10 +112,12 @@ async fn venv_impl(
     keyring_provider: KeyringProvider,
     prompt: uv_virtualenv::Prompt,
     system_site_packages: bool,
+    copies: bool,
     connectivity: Connectivity,
     seed: bool,
     exclude_newer: Option<ExcludeNewer>,
     native_tls: bool,
+    concurrency: Concurrency,
     cache: &Cache,
     printer: Printer,
 ) -> miette::Result<ExitStatus> {
@@ -138,9 +150,15 @@ async fn venv_impl(
     let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
 
     // Create the virtual environment.
-    let venv =
-        uv_virtualenv::create_venv(path, interpreter, prompt, system_site_packages, extra_cfg)
-            .map_err(VenvError::Creation)?;
+    let venv = uv_virtualenv::create_venv(
+        path,
+        interpreter,
+        prompt,
+        system_site_packages,
+        copies,
+        extra_cfg,
+    )
+    .map_err(VenvError::Creation)?;
 
     // Install seed packages.
     if seed {
@@ -155,6 +173,7 @@ async fn venv_impl(
         // Instantiate a client.
         let client = RegistryClientBuilder::new(cache.clone())
             .native_tls(native_tls)
+            .concurrency(concurrency)
             .index_urls(index_locations.index_urls())
             .index_strategy(index_strategy)
             .keyring_provider(keyring_provider)
@@ -11,14 +11,19 @@ use owo_colors::OwoColorize;
 use tracing::instrument;
 
 use distribution_types::IndexLocations;
+use uv_attestation::AttestationPolicy;
 use uv_cache::{Cache, Refresh};
 use uv_client::Connectivity;
-use uv_configuration::NoBinary;
-use uv_configuration::{ConfigSettings, NoBuild, Reinstall, SetupPyStrategy, Upgrade};
+use uv_configuration::{Concurrency, NoBinary};
+use uv_configuration::{ConfigSettings, NoBuild, Reinstall, SetupPyStrategy, TagPolicy, Upgrade};
+use uv_publish::Credentials;
 use uv_requirements::{ExtrasSpecification, RequirementsSource};
-use uv_resolver::{DependencyMode, PreReleaseMode};
+use uv_resolver::{DependencyMode, LicensePolicy, PreReleaseMode};
 
-use crate::cli::{CacheCommand, CacheNamespace, Cli, Commands, Maybe, PipCommand, PipNamespace};
+use crate::cli::{
+    CacheCommand, CacheNamespace, Cli, Commands, Maybe, PipCommand, PipNamespace, ToolCommand,
+    ToolNamespace,
+};
 #[cfg(feature = "self-update")]
 use crate::cli::{SelfCommand, SelfNamespace};
 use crate::commands::ExitStatus;
@@ -107,6 +112,13 @@ async fn run() -> Result<ExitStatus> {
 
     let globals = cli.global_args;
 
+    // Limit the concurrent requests made by each client, and the bandwidth they consume.
+    let concurrency = Concurrency {
+        downloads: globals.concurrent_downloads,
+        per_host: globals.max_connections_per_host,
+        bandwidth: globals.max_bandwidth,
+    };
+
     // Configure the `tracing` crate, which controls internal logging.
     #[cfg(feature = "tracing-durations-export")]
     let (duration_layer, _duration_guard) = logging::setup_duration()?;
@@ -154,6 +166,14 @@ async fn run() -> Result<ExitStatus> {
 
     let cache = Cache::try_from(cli.cache_args)?;
 
+    // Hold a shared lock on the cache for the duration of the command, such that the cache isn't
+    // cleaned or pruned from under it. The `cache` commands acquire their own locks as needed.
+    let _cache_lock = if matches!(cli.command, Commands::Cache(_) | Commands::Clean(_)) {
+        None
+    } else {
+        Some(cache.lock_shared()?)
+    };
+
     match cli.command {
         Commands::Pip(PipNamespace {
             command: PipCommand::Compile(args),
@@ -184,7 +204,8 @@ async fn run() -> Result<ExitStatus> {
                     .collect(),
                 args.find_links,
                 args.no_index,
-            );
+            )
+            .with_pins(args.index_pin);
             let extras = if args.all_extras {
                 ExtrasSpecification::All
             } else if args.extra.is_empty() {
@@ -193,7 +214,14 @@ async fn run() -> Result<ExitStatus> {
                 ExtrasSpecification::Some(&args.extra)
             };
             let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
+            let no_binary = NoBinary::from_args(args.no_binary);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            let tag_policy = TagPolicy::from_args(args.max_manylinux, args.no_platform_wheels);
+            let license_policy = LicensePolicy::from_args(
+                args.allow_license,
+                args.deny_license,
+                args.license_policy_mode,
+            );
             let dependency_mode = if args.no_deps {
                 DependencyMode::Direct
             } else {
@@ -215,9 +243,12 @@ async fn run() -> Result<ExitStatus> {
                 &constraints,
                 &overrides,
                 extras,
+                &args.group,
                 args.output_file.as_deref(),
+                args.import_lock.as_deref(),
                 args.resolution,
                 prerelease,
+                args.yanked,
                 dependency_mode,
                 upgrade,
                 args.generate_hashes,
@@ -242,10 +273,16 @@ async fn run() -> Result<ExitStatus> {
                 },
                 args.no_build_isolation,
                 no_build,
+                no_binary,
+                tag_policy,
+                &license_policy,
                 args.python_version,
+                args.python_platform,
                 args.exclude_newer,
                 args.annotation_style,
+                args.format,
                 globals.native_tls,
+                concurrency,
                 globals.quiet,
                 args.link_mode,
                 cache,
@@ -267,7 +304,8 @@ async fn run() -> Result<ExitStatus> {
                     .collect(),
                 args.find_links,
                 args.no_index,
-            );
+            )
+            .with_pins(args.index_pin);
             let sources = args
                 .src_file
                 .into_iter()
@@ -276,6 +314,12 @@ async fn run() -> Result<ExitStatus> {
             let reinstall = Reinstall::from_args(args.reinstall, args.reinstall_package);
             let no_binary = NoBinary::from_args(args.no_binary);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            let tag_policy = TagPolicy::from_args(args.max_manylinux, args.no_platform_wheels);
+            let attestation_policy = AttestationPolicy::from_args(
+                args.attestation_mode,
+                args.attestation_trust_root.as_deref(),
+                args.attestation_identity,
+            )?;
             let setup_py = if args.legacy_setup_py {
                 SetupPyStrategy::Setuptools
             } else {
@@ -287,8 +331,11 @@ async fn run() -> Result<ExitStatus> {
                 &sources,
                 &reinstall,
                 args.link_mode,
+                args.shebang.unwrap_or_default(),
+                args.allow_overwrite,
                 args.compile,
                 args.require_hashes,
+                args.yanked,
                 index_urls,
                 args.index_strategy,
                 args.keyring_provider,
@@ -302,11 +349,14 @@ async fn run() -> Result<ExitStatus> {
                 args.no_build_isolation,
                 no_build,
                 no_binary,
+                tag_policy,
+                &attestation_policy,
                 args.strict,
                 args.python,
                 args.system,
                 args.break_system_packages,
                 globals.native_tls,
+                concurrency,
                 cache,
                 printer,
             )
@@ -345,7 +395,8 @@ async fn run() -> Result<ExitStatus> {
                     .collect(),
                 args.find_links,
                 args.no_index,
-            );
+            )
+            .with_pins(args.index_pin);
             let extras = if args.all_extras {
                 ExtrasSpecification::All
             } else if args.extra.is_empty() {
@@ -357,6 +408,17 @@ async fn run() -> Result<ExitStatus> {
             let upgrade = Upgrade::from_args(args.upgrade, args.upgrade_package);
             let no_binary = NoBinary::from_args(args.no_binary);
             let no_build = NoBuild::from_args(args.only_binary, args.no_build);
+            let tag_policy = TagPolicy::from_args(args.max_manylinux, args.no_platform_wheels);
+            let license_policy = LicensePolicy::from_args(
+                args.allow_license,
+                args.deny_license,
+                args.license_policy_mode,
+            );
+            let attestation_policy = AttestationPolicy::from_args(
+                args.attestation_mode,
+                args.attestation_trust_root.as_deref(),
+                args.attestation_identity,
+            )?;
             let dependency_mode = if args.no_deps {
                 DependencyMode::Direct
             } else {
@@ -379,8 +441,10 @@ async fn run() -> Result<ExitStatus> {
                 &constraints,
                 &overrides,
                 &extras,
+                &args.group,
                 args.resolution,
                 prerelease,
+                args.yanked,
                 dependency_mode,
                 upgrade,
                 index_urls,
@@ -388,6 +452,8 @@ async fn run() -> Result<ExitStatus> {
                 args.keyring_provider,
                 reinstall,
                 args.link_mode,
+                args.shebang.unwrap_or_default(),
+                args.allow_overwrite,
                 args.compile,
                 args.require_hashes,
                 setup_py,
@@ -400,12 +466,18 @@ async fn run() -> Result<ExitStatus> {
                 args.no_build_isolation,
                 no_build,
                 no_binary,
+                tag_policy,
+                &license_policy,
+                &attestation_policy,
                 args.strict,
                 args.exclude_newer,
                 args.python,
                 args.system,
                 args.break_system_packages,
+                args.target,
+                args.user,
                 globals.native_tls,
+                concurrency,
                 cache,
                 args.dry_run,
                 printer,
@@ -437,6 +509,7 @@ async fn run() -> Result<ExitStatus> {
                     Connectivity::Online
                 },
                 globals.native_tls,
+                concurrency,
                 args.keyring_provider,
                 printer,
             )
@@ -452,6 +525,50 @@ async fn run() -> Result<ExitStatus> {
             &cache,
             printer,
         ),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Audit(args),
+        }) => {
+            commands::pip_audit(
+                args.advisory_db.as_deref(),
+                args.severity_threshold,
+                args.format,
+                args.python.as_deref(),
+                args.system,
+                if args.offline {
+                    Connectivity::Offline
+                } else {
+                    Connectivity::Online
+                },
+                globals.native_tls,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Bundle(args),
+        }) => commands::pip_bundle(
+            &args.output,
+            args.entry_point,
+            args.script.as_deref(),
+            args.format,
+            (!args.no_shebang).then_some(args.shebang.as_str()),
+            &args.exclude,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Sbom(args),
+        }) => commands::pip_sbom(
+            args.format,
+            args.python.as_deref(),
+            args.system,
+            &cache,
+            printer,
+        ),
         Commands::Pip(PipNamespace {
             command: PipCommand::List(args),
         }) => {
@@ -481,20 +598,29 @@ async fn run() -> Result<ExitStatus> {
         ),
         Commands::Pip(PipNamespace {
             command: PipCommand::Check(args),
-        }) => commands::pip_check(args.python.as_deref(), args.system, &cache, printer),
+        }) => commands::pip_check(
+            args.python.as_deref(),
+            args.system,
+            args.verify,
+            &cache,
+            printer,
+        ),
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Clean(args),
         })
         | Commands::Clean(args) => commands::cache_clean(&args.package, &cache, printer),
         Commands::Cache(CacheNamespace {
-            command: CacheCommand::Prune,
-        }) => commands::cache_prune(&cache, printer),
+            command: CacheCommand::Prune(args),
+        }) => commands::cache_prune(&cache, args.unused_since, args.max_size, printer),
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Dir,
         }) => {
             commands::cache_dir(&cache);
             Ok(ExitStatus::Success)
         }
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Size,
+        }) => commands::cache_size(&cache, printer),
         Commands::Venv(args) => {
             args.compat_args.validate()?;
 
@@ -527,6 +653,7 @@ async fn run() -> Result<ExitStatus> {
                 args.keyring_provider,
                 uv_virtualenv::Prompt::from_args(prompt),
                 args.system_site_packages,
+                args.copies,
                 if args.offline {
                     Connectivity::Offline
                 } else {
@@ -535,11 +662,98 @@ async fn run() -> Result<ExitStatus> {
                 args.seed,
                 args.exclude_newer,
                 globals.native_tls,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Run(args) => {
+            let cache = cache.with_refresh(Refresh::from_args(args.refresh, args.refresh_package));
+            commands::run(
+                &args.script,
+                &args.args,
+                args.python.as_deref(),
+                args.environment.link_mode,
+                &args.environment.index_locations(),
+                args.environment.index_strategy,
+                args.environment.keyring_provider,
+                args.environment.connectivity(),
+                args.environment.exclude_newer,
+                globals.native_tls,
+                concurrency,
                 &cache,
                 printer,
             )
             .await
         }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Install(args),
+        }) => {
+            commands::tool_install(
+                &args.requirement,
+                args.python.as_deref(),
+                args.force,
+                args.environment.link_mode,
+                &args.environment.index_locations(),
+                args.environment.index_strategy,
+                args.environment.keyring_provider,
+                args.environment.connectivity(),
+                args.environment.exclude_newer,
+                globals.native_tls,
+                concurrency,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::List,
+        }) => commands::tool_list(&cache, printer),
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Upgrade(args),
+        }) => {
+            commands::tool_upgrade(
+                &args.names,
+                args.all,
+                args.environment.link_mode,
+                &args.environment.index_locations(),
+                args.environment.index_strategy,
+                args.environment.keyring_provider,
+                args.environment.connectivity(),
+                args.environment.exclude_newer,
+                globals.native_tls,
+                concurrency,
+                cache,
+                printer,
+            )
+            .await
+        }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Uninstall(args),
+        }) => commands::tool_uninstall(&args.names, printer),
+        Commands::Publish(args) => {
+            let credentials = if let Some(token) = args.token {
+                Credentials::Token(token)
+            } else if let (Some(username), Some(password)) = (args.username, args.password) {
+                Credentials::Basic { username, password }
+            } else {
+                Credentials::None
+            };
+            commands::publish(
+                args.files,
+                &args.publish_url,
+                credentials,
+                args.skip_existing,
+                args.retries,
+                args.keyring_provider,
+                args.trusted_publishing,
+                globals.native_tls,
+                concurrency,
+                printer,
+            )
+            .await
+        }
         #[cfg(feature = "self-update")]
         Commands::Self_(SelfNamespace {
             command: SelfCommand::Update,
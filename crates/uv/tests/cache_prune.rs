@@ -128,7 +128,7 @@ fn prune_stale_symlink() -> Result<()> {
         .success();
 
     // Remove the wheels directory, causing the symlink to become stale.
-    let wheels = context.cache_dir.child("wheels-v1");
+    let wheels = context.cache_dir.child("wheels-v2");
     fs_err::remove_dir_all(wheels)?;
 
     let filters: Vec<_> = context
@@ -156,3 +156,130 @@ fn prune_stale_symlink() -> Result<()> {
 
     Ok(())
 }
+
+/// `cache prune --unused-since` should retain any entries that were used since the given time,
+/// and remove those that weren't.
+#[test]
+fn prune_unused_since() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Every entry was used since 2000.
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--unused-since").arg("2000-01-01"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    No unused entries found
+    "###);
+
+    // No entry was used since the year 3000.
+    prune_command(&context)
+        .arg("--unused-since")
+        .arg("3000-01-01T00:00:00Z")
+        .assert()
+        .success();
+    assert_eq!(
+        fs_err::read_dir(context.cache_dir.child("archive-v0"))?.count(),
+        0
+    );
+
+    // The cache remains usable.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// `cache prune --max-size` should evict entries until the cache fits within the given size.
+#[test]
+fn prune_max_size() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio")?;
+
+    // Install a requirement, to populate the cache.
+    sync_command(&context)
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // The cache already fits within 10G.
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--max-size").arg("10G"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    No unused entries found
+    "###);
+
+    // Evicting down to zero bytes removes every entry, including the unzipped wheels, along with
+    // the symlinks that refer to them.
+    prune_command(&context)
+        .arg("--max-size")
+        .arg("0")
+        .assert()
+        .success();
+    assert_eq!(
+        fs_err::read_dir(context.cache_dir.child("archive-v0"))?.count(),
+        0
+    );
+    assert!(context.cache_dir.child("CACHEDIR.TAG").exists());
+
+    // No dangling symlinks are left behind for a subsequent prune to remove.
+    uv_snapshot!(context.filters(), prune_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    No unused entries found
+    "###);
+
+    Ok(())
+}
+
+/// `cache prune` should reject invalid timestamps and sizes.
+#[test]
+fn prune_invalid_arguments() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--unused-since").arg("yesterday"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'yesterday' for '--unused-since <UNUSED_SINCE>': `yesterday` is not a valid date or timestamp (expected, e.g., `2006-12-02` or `2006-12-02T02:07:43Z`)
+
+    For more information, try '--help'.
+    "###);
+
+    uv_snapshot!(context.filters(), prune_command(&context).arg("--max-size").arg("lots"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'lots' for '--max-size <MAX_SIZE>': `lots` is not a valid size (expected, e.g., `500M` or `10G`)
+
+    For more information, try '--help'.
+    "###);
+}
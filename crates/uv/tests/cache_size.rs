@@ -0,0 +1,47 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `cache size` command with options shared across scenarios.
+fn size_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("cache")
+        .arg("size")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// `cache size` should report the disk usage of each bucket.
+#[test]
+fn size() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install a requirement, to populate the cache.
+    context.install().arg("iniconfig==2.0.0").assert().success();
+
+    let output = size_command(&context).output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let buckets = stdout
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .collect::<Vec<_>>();
+    assert!(buckets
+        .iter()
+        .any(|(bucket, size)| *bucket == "archive-v0" && *size != "0B"));
+    assert!(buckets.iter().any(|(bucket, _)| *bucket == "Total"));
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip audit` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("audit")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `pip sync` command with options shared across scenarios.
+fn sync_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Audit an environment against an offline advisory database.
+#[test]
+fn audit_advisory_db() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    sync_command(&context)
+        .arg(requirements_txt.path())
+        .assert()
+        .success();
+
+    // Write an advisory that affects `tomli`, and one that affects an older `MarkupSafe`.
+    let advisory_db = context.temp_dir.child("advisories");
+    advisory_db
+        .child("GHSA-0000-0000-0001.json")
+        .write_str(indoc! {r#"
+        {
+            "id": "GHSA-0000-0000-0001",
+            "summary": "Example vulnerability in tomli",
+            "database_specific": { "severity": "HIGH" },
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": "tomli" },
+                "ranges": [{
+                    "type": "ECOSYSTEM",
+                    "events": [{ "introduced": "0" }, { "fixed": "2.0.2" }]
+                }]
+            }]
+        }
+    "#})?;
+    advisory_db
+        .child("GHSA-0000-0000-0002.json")
+        .write_str(indoc! {r#"
+        {
+            "id": "GHSA-0000-0000-0002",
+            "database_specific": { "severity": "CRITICAL" },
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": "markupsafe" },
+                "ranges": [{
+                    "type": "ECOSYSTEM",
+                    "events": [{ "introduced": "2.0.0" }, { "fixed": "2.1.0" }]
+                }]
+            }]
+        }
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("--advisory-db")
+        .arg(advisory_db.path())
+        .arg("--offline"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    tomli 2.0.1: GHSA-0000-0000-0001 (high, fixed in 2.0.2)
+
+    ----- stderr -----
+    Found 1 known vulnerability in 1 package
+    "###
+    );
+
+    // A vulnerability below the threshold is reported, but doesn't fail the audit.
+    uv_snapshot!(command(&context)
+        .arg("--advisory-db")
+        .arg(advisory_db.path())
+        .arg("--severity-threshold")
+        .arg("critical")
+        .arg("--offline"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    tomli 2.0.1: GHSA-0000-0000-0001 (high, fixed in 2.0.2)
+
+    ----- stderr -----
+    Found 1 known vulnerability in 1 package
+    "###
+    );
+
+    Ok(())
+}
+
+/// The events of an advisory's range should be evaluated in version order, regardless of the
+/// order in which they're listed.
+#[test]
+fn audit_unsorted_events() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3\ntomli==2.0.1")?;
+
+    sync_command(&context)
+        .arg(requirements_txt.path())
+        .assert()
+        .success();
+
+    // Write an advisory that affects `tomli` in `[0, 1.0.0)` and `[2.0.0, 2.0.2)`, listing the
+    // second interval first, and one that affects `MarkupSafe` in `[2.1.0, 2.1.2]`.
+    let advisory_db = context.temp_dir.child("advisories");
+    advisory_db
+        .child("GHSA-0000-0000-0001.json")
+        .write_str(indoc! {r#"
+        {
+            "id": "GHSA-0000-0000-0001",
+            "database_specific": { "severity": "HIGH" },
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": "tomli" },
+                "ranges": [{
+                    "type": "ECOSYSTEM",
+                    "events": [
+                        { "introduced": "2.0.0" },
+                        { "fixed": "2.0.2" },
+                        { "introduced": "0" },
+                        { "fixed": "1.0.0" }
+                    ]
+                }]
+            }]
+        }
+    "#})?;
+    advisory_db
+        .child("GHSA-0000-0000-0002.json")
+        .write_str(indoc! {r#"
+        {
+            "id": "GHSA-0000-0000-0002",
+            "database_specific": { "severity": "CRITICAL" },
+            "affected": [{
+                "package": { "ecosystem": "PyPI", "name": "markupsafe" },
+                "ranges": [{
+                    "type": "ECOSYSTEM",
+                    "events": [{ "last_affected": "2.1.2" }, { "introduced": "2.1.0" }]
+                }]
+            }]
+        }
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("--advisory-db")
+        .arg(advisory_db.path())
+        .arg("--offline"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+    tomli 2.0.1: GHSA-0000-0000-0001 (high, fixed in 1.0.0, 2.0.2)
+
+    ----- stderr -----
+    Found 1 known vulnerability in 1 package
+    "###
+    );
+
+    Ok(())
+}
+
+/// Auditing offline requires an advisory database.
+#[test]
+fn audit_offline_without_advisory_db() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("--offline"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Auditing in offline mode requires an advisory database (`--advisory-db`)
+    "###
+    );
+}
@@ -0,0 +1,146 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot, venv_to_interpreter, TestContext};
+
+mod common;
+
+/// Create a `pip bundle` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("bundle")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `pip install` command with options shared across scenarios.
+fn install_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Bundle an installed console script into a zipapp, and run it.
+#[test]
+fn bundle_zipapp() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    install_command(&context)
+        .arg("pygments==2.17.2")
+        .assert()
+        .success();
+
+    let filters = [(r"\(\d+ files\)", "([N] files)")]
+        .into_iter()
+        .chain(context.filters())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg("--script")
+        .arg("pygmentize")
+        .arg("--output")
+        .arg("app.pyz"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Bundled 1 package ([N] files) into app.pyz
+    "###
+    );
+
+    let output = Command::new(venv_to_interpreter(&context.venv))
+        .arg(context.temp_dir.child("app.pyz").path())
+        .arg("-V")
+        .current_dir(&context.temp_dir)
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.contains("Pygments version 2.17.2"));
+
+    Ok(())
+}
+
+/// Bundle a module entry point into a directory.
+#[test]
+fn bundle_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    install_command(&context)
+        .arg("pygments==2.17.2")
+        .assert()
+        .success();
+
+    command(&context)
+        .arg("--entry-point")
+        .arg("pygments")
+        .arg("--format")
+        .arg("directory")
+        .arg("--output")
+        .arg("app")
+        .assert()
+        .success();
+
+    let app = context.temp_dir.child("app");
+    app.child("__main__.py")
+        .assert(predicates::str::contains("runpy.run_module(\"pygments\""));
+    app.child("pygments")
+        .child("__init__.py")
+        .assert(predicates::path::is_file());
+    app.child("pygments-2.17.2.dist-info")
+        .child("METADATA")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// Request a console script that no installed package provides.
+#[test]
+fn bundle_missing_script() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    install_command(&context)
+        .arg("pygments==2.17.2")
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("--script")
+        .arg("black")
+        .arg("--output")
+        .arg("app.pyz"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No installed package provides a console script named `black`
+    "###
+    );
+
+    Ok(())
+}
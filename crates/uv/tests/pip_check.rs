@@ -1,6 +1,7 @@
 use std::process::Command;
 
 use anyhow::Result;
+use assert_cmd::prelude::*;
 use assert_fs::fixture::FileWriteStr;
 use assert_fs::fixture::PathChild;
 
@@ -223,3 +224,121 @@ fn check_multiple_incompatible_packages() -> Result<()> {
 
     Ok(())
 }
+
+/// Verify the installed files against each package's `RECORD`.
+#[test]
+fn check_verify() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    install_command(&context)
+        .arg("iniconfig==2.0.0")
+        .assert()
+        .success();
+
+    uv_snapshot!(check_command(&context).arg("--verify"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 1 package in [TIME]
+    All installed packages are compatible
+    All installed packages match their RECORD
+    "###
+    );
+
+    // Tamper with an installed file, and add an unrecorded one.
+    let package = context.site_packages().join("iniconfig");
+    fs_err::write(package.join("__init__.py"), "raise RuntimeError")?;
+    fs_err::write(package.join("extra.py"), "")?;
+
+    check_command(&context)
+        .arg("--verify")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The package `iniconfig` does not match its RECORD:",
+        ))
+        .stderr(predicates::str::contains("modified: "))
+        .stderr(predicates::str::contains("orphaned: "))
+        .stderr(predicates::str::contains(
+            "Found 1 package with inconsistent files",
+        ));
+
+    Ok(())
+}
+
+/// Verify the installed files when one of the packages is missing its `RECORD`, which should be
+/// reported without aborting the verification of the other packages.
+#[test]
+fn check_verify_missing_record() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    install_command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("tomli==2.0.1")
+        .assert()
+        .success();
+
+    // Remove the `RECORD` of one package, and tamper with a file of the other.
+    fs_err::remove_file(
+        context
+            .site_packages()
+            .join("iniconfig-2.0.0.dist-info")
+            .join("RECORD"),
+    )?;
+    fs_err::write(
+        context.site_packages().join("tomli").join("__init__.py"),
+        "raise RuntimeError",
+    )?;
+
+    check_command(&context)
+        .arg("--verify")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The package `iniconfig` could not be verified:",
+        ))
+        .stderr(predicates::str::contains("RECORD"))
+        .stderr(predicates::str::contains(
+            "The package `tomli` does not match its RECORD:",
+        ))
+        .stderr(predicates::str::contains(
+            "Found 2 packages with inconsistent files",
+        ));
+
+    Ok(())
+}
+
+/// Dependencies that are gated behind an extra should be validated if an installed package
+/// requests that extra.
+#[test]
+fn check_requested_extra() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = context.site_packages();
+    fs_err::create_dir_all(site_packages.join("a-1.0.0.dist-info"))?;
+    fs_err::write(
+        site_packages.join("a-1.0.0.dist-info").join("METADATA"),
+        "Metadata-Version: 2.1\nName: a\nVersion: 1.0.0\nRequires-Dist: b[x]\n",
+    )?;
+    fs_err::create_dir_all(site_packages.join("b-1.0.0.dist-info"))?;
+    fs_err::write(
+        site_packages.join("b-1.0.0.dist-info").join("METADATA"),
+        "Metadata-Version: 2.1\nName: b\nVersion: 1.0.0\nProvides-Extra: x\nRequires-Dist: c ; extra == \"x\"\n",
+    )?;
+
+    uv_snapshot!(check_command(&context), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 2 packages in [TIME]
+    Found 1 incompatibility
+    The package `b` requires `c ; extra == 'x'`, but it's not installed.
+    "###
+    );
+
+    Ok(())
+}
@@ -47,6 +47,135 @@ fn compile_requirements_in() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a specific version of `anyio` from a `requirements.in` file, writing the resolution
+/// as JSON.
+#[test]
+fn compile_requirements_in_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let output = context
+        .compile()
+        .arg("--format")
+        .arg("json")
+        .arg("--generate-hashes")
+        .arg("requirements.in")
+        .output()?;
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let packages = report["packages"]
+        .as_array()
+        .context("Missing `packages`")?;
+    let names = packages
+        .iter()
+        .map(|package| package["name"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["anyio", "idna", "sniffio"]);
+
+    let anyio = &packages[0];
+    assert_eq!(anyio["version"], "3.7.0");
+    assert_eq!(anyio["requested"], true);
+    assert!(anyio["source"]["registry"].is_string());
+    assert!(!anyio["hashes"]
+        .as_array()
+        .context("Missing `hashes`")?
+        .is_empty());
+    assert_eq!(
+        anyio["dependencies"],
+        serde_json::json!(["idna", "sniffio"])
+    );
+
+    let idna = &packages[1];
+    assert_eq!(idna["requested"], false);
+    assert_eq!(idna["requested-by"][0]["name"], "anyio");
+
+    Ok(())
+}
+
+/// Report the enabled extras of each package, along with the dependencies that each extra
+/// introduced, when writing the resolution as JSON.
+#[test]
+fn compile_requirements_in_json_extras() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask[dotenv]==3.0.2")?;
+
+    let output = context
+        .compile()
+        .arg("--format")
+        .arg("json")
+        .arg("requirements.in")
+        .output()?;
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let packages = report["packages"]
+        .as_array()
+        .context("Missing `packages`")?;
+
+    let flask = packages
+        .iter()
+        .find(|package| package["name"] == "flask")
+        .context("Missing `flask`")?;
+    assert_eq!(flask["extras"], serde_json::json!(["dotenv"]));
+    assert_eq!(
+        flask["optional-dependencies"],
+        serde_json::json!({ "dotenv": ["python-dotenv"] })
+    );
+    assert!(flask["dependencies"]
+        .as_array()
+        .context("Missing `dependencies`")?
+        .contains(&serde_json::json!("python-dotenv")));
+
+    // Packages without enabled extras omit both fields.
+    let jinja2 = packages
+        .iter()
+        .find(|package| package["name"] == "jinja2")
+        .context("Missing `jinja2`")?;
+    assert!(jinja2.get("extras").is_none());
+    assert!(jinja2.get("optional-dependencies").is_none());
+
+    Ok(())
+}
+
+/// Reject a resolution that includes a package with a denied license.
+#[test]
+fn compile_deny_license() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let output = context
+        .compile()
+        .arg("--deny-license")
+        .arg("mit")
+        .arg("requirements.in")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert_eq!(output.status.code(), Some(1), "{stderr}");
+    assert!(stderr.contains("the license policy:"), "{stderr}");
+    assert!(stderr.contains("anyio==3.7.0: `MIT` is denied"), "{stderr}");
+    assert!(output.stdout.is_empty());
+
+    // In `warn` mode, the resolution is still written.
+    let output = context
+        .compile()
+        .arg("--deny-license")
+        .arg("mit")
+        .arg("--license-policy-mode")
+        .arg("warn")
+        .arg("requirements.in")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("anyio==3.7.0: `MIT` is denied"), "{stderr}");
+    assert!(String::from_utf8(output.stdout)?.contains("anyio==3.7.0"));
+
+    Ok(())
+}
+
 /// Resolve a specific version of `anyio` from a `requirements.in` file with a `--annotation-style=line` flag.
 #[test]
 fn compile_requirements_in_annotation_line() -> Result<()> {
@@ -329,6 +458,233 @@ optional-dependencies.foo = [
     Ok(())
 }
 
+/// Resolve a package from a dependency group that includes another group in a `pyproject.toml` file.
+#[test]
+fn compile_pyproject_toml_dependency_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[dependency-groups]
+test = [
+    "anyio==3.7.0",
+    {include-group = "lint"},
+]
+lint = [
+    "sniffio==1.3.1",
+]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--group")
+            .arg("test"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z pyproject.toml --group test
+    anyio==3.7.0
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Request a dependency group that isn't defined in a `pyproject.toml` file.
+#[test]
+fn compile_pyproject_toml_missing_dependency_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[dependency-groups]
+test = [
+    "anyio==3.7.0",
+]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--group")
+            .arg("docs"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to read dependency groups from `pyproject.toml`
+      Caused by: Dependency group `docs` is not defined in `pyproject.toml`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write a workspace at the given directory, with members `alpha` and `beta`, where `alpha`
+/// depends on `beta`.
+fn workspace(root: &impl PathChild) -> Result<()> {
+    root.child("pyproject.toml").write_str(
+        r#"[tool.uv.workspace]
+members = ["packages/*"]
+"#,
+    )?;
+    root.child("packages")
+        .child("alpha")
+        .child("pyproject.toml")
+        .write_str(
+            r#"[build-system]
+requires = ["setuptools>=42"]
+build-backend = "setuptools.build_meta"
+
+[project]
+name = "alpha"
+version = "0.1.0"
+dependencies = ["beta", "anyio==3.7.0"]
+"#,
+        )?;
+    root.child("packages")
+        .child("beta")
+        .child("pyproject.toml")
+        .write_str(
+            r#"[build-system]
+requires = ["setuptools>=42"]
+build-backend = "setuptools.build_meta"
+
+[project]
+name = "beta"
+version = "0.1.0"
+dependencies = ["sniffio==1.3.1"]
+"#,
+        )?;
+    Ok(())
+}
+
+/// Resolve the members of a workspace together, such that dependencies between members are
+/// satisfied by the local source trees.
+#[test]
+fn compile_pyproject_toml_workspace() -> Result<()> {
+    let context = TestContext::new("3.12");
+    workspace(&context.temp_dir)?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z pyproject.toml
+    -e packages/alpha
+    -e packages/beta
+    anyio==3.7.0
+        # via alpha
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via
+        #   anyio
+        #   beta
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 5 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Discover the members of a workspace whose root contains glob metacharacters, which must be
+/// matched literally.
+#[test]
+fn compile_pyproject_toml_workspace_glob_root() -> Result<()> {
+    let context = TestContext::new("3.12");
+    workspace(&context.temp_dir.child("workspace[1]"))?;
+
+    uv_snapshot!(context.compile()
+            .arg("workspace[1]/pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z workspace[1]/pyproject.toml
+    -e workspace[1]/packages/alpha
+    -e workspace[1]/packages/beta
+    anyio==3.7.0
+        # via alpha
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via
+        #   anyio
+        #   beta
+
+    ----- stderr -----
+    Built 2 editables in [TIME]
+    Resolved 5 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Locking any member of a workspace locks the entire workspace, such that the members share a
+/// single lockfile.
+#[test]
+fn compile_pyproject_toml_workspace_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+    workspace(&context.temp_dir)?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml")
+            .arg("--format")
+            .arg("lock")
+            .arg("--output-file")
+            .arg("root.lock")
+            .arg("--quiet"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(context.compile()
+            .arg("packages/beta/pyproject.toml")
+            .arg("--format")
+            .arg("lock")
+            .arg("--output-file")
+            .arg("member.lock")
+            .arg("--quiet"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+
+    let lock = fs::read_to_string(context.temp_dir.child("root.lock"))?;
+    assert_eq!(lock.matches("editable = true").count(), 2, "{lock}");
+    for name in ["alpha", "beta", "anyio", "idna", "sniffio"] {
+        assert!(lock.contains(&format!("name = \"{name}\"")), "{lock}");
+    }
+    assert_eq!(
+        lock,
+        fs::read_to_string(context.temp_dir.child("member.lock"))?
+    );
+
+    Ok(())
+}
+
 /// Resolve a package from an extra with non-normalized names in a `pyproject.toml` file.
 #[test]
 fn compile_pyproject_toml_extra_name_normalization() -> Result<()> {
@@ -788,10 +1144,143 @@ fn compile_python_312() -> Result<()> {
         # via black
 
     ----- stderr -----
-    Resolved 6 packages in [TIME]
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve for a platform other than the current one, such that platform-specific dependencies
+/// are included (or omitted) according to the target.
+#[test]
+fn compile_python_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("click==8.1.7")?;
+
+    // `colorama` is only required on Windows.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --python-platform windows
+    click==8.1.7
+    colorama==0.4.6
+        # via click
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("aarch64-apple-darwin"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --python-platform aarch64-apple-darwin
+    click==8.1.7
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve for multiple platforms at once, such that pins that only apply on some platforms are
+/// guarded by markers.
+#[test]
+fn compile_python_platform_universal() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("click==8.1.7")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("linux")
+            .arg("--python-platform")
+            .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --python-platform linux --python-platform windows
+    click==8.1.7
+    colorama==0.4.6 ; sys_platform == 'win32' and platform_system == 'Windows' and os_name == 'nt' and platform_machine == 'AMD64'
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Platforms that can't be told apart by their markers are rejected.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("linux")
+            .arg("--python-platform")
+            .arg("x86_64-unknown-linux-gnu"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Multiple `--python-platform` values target the same environment (`sys_platform == 'linux'` and `platform_machine == 'x86_64'`)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve for multiple platforms at once, writing a lockfile in which the entries that only
+/// apply on some platforms are guarded by markers.
+#[test]
+fn compile_python_platform_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("click==8.1.7")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("linux")
+            .arg("--python-platform")
+            .arg("windows")
+            .arg("--format")
+            .arg("lock")
+            .arg("--output-file")
+            .arg("requirements.lock")
+            .arg("--quiet"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
     "###
     );
 
+    let lock = fs::read_to_string(context.temp_dir.child("requirements.lock"))?;
+    let linux = "sys_platform == 'linux' and platform_system == 'Linux' and os_name == 'posix' and platform_machine == 'x86_64'";
+    let windows = "sys_platform == 'win32' and platform_system == 'Windows' and os_name == 'nt' and platform_machine == 'AMD64'";
+
+    // `colorama` is only required on Windows, and `click` depends on it there.
+    assert_eq!(lock.matches("name = \"click\"").count(), 2, "{lock}");
+    assert_eq!(lock.matches("name = \"colorama\"").count(), 1, "{lock}");
+    assert!(lock.contains(&format!("marker = \"{linux}\"")), "{lock}");
+    assert!(lock.contains(&format!("marker = \"{windows}\"")), "{lock}");
+    assert!(lock.contains("dependencies = [\"colorama\"]"), "{lock}");
+
     Ok(())
 }
 
@@ -884,6 +1373,8 @@ fn compile_python_37() -> Result<()> {
       × No solution found when resolving dependencies:
       ╰─▶ Because the requested Python version (3.7) does not satisfy Python>=3.8 and black==23.10.1 depends on Python>=3.8, we can conclude that black==23.10.1 cannot be used.
           And because you require black==23.10.1, we can conclude that the requirements are unsatisfiable.
+
+          hint: Newer versions of black (black==23.10.1) require Python>=3.8, but the requested Python version is 3.7; the newest compatible version is black==23.3.0
     "###);
 
     Ok(())
@@ -3819,6 +4310,64 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
+/// Compile using `--find-links` with a local directory, with wheels disabled.
+#[test]
+fn find_links_no_binary() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-binary")
+            .arg(":all:")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-binary :all: --no-index
+    tqdm==999.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Compile using `--find-links` with a local directory, with wheels disabled by the requirements
+/// file itself.
+#[test]
+fn find_links_no_binary_requirements_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        --no-binary tqdm
+        tqdm
+    "})?;
+
+    uv_snapshot!(context.filters(), context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(context.workspace_root.join("scripts").join("links")), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index
+    tqdm==999.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
 /// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
 #[test]
 fn find_links_url() -> Result<()> {
@@ -4008,6 +4557,159 @@ fn upgrade_all() -> Result<()> {
     Ok(())
 }
 
+/// Import the pins for `black==23.10.1` from an existing `poetry.lock`, with older versions of
+/// `click` and `pathspec`. The pins should be preserved.
+#[test]
+fn import_poetry_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let poetry_lock = context.temp_dir.child("poetry.lock");
+    poetry_lock.write_str(indoc! {r#"
+        [[package]]
+        name = "black"
+        version = "23.10.1"
+        python-versions = ">=3.8"
+        files = []
+
+        [package.dependencies]
+        click = ">=8.0.0"
+        mypy-extensions = ">=0.4.3"
+        packaging = ">=22.0"
+        pathspec = ">=0.9.0"
+        platformdirs = ">=2"
+
+        [[package]]
+        name = "click"
+        version = "8.1.2"
+        python-versions = ">=3.7"
+        files = []
+
+        [package.dependencies]
+        colorama = {version = "*", markers = "platform_system == \"Windows\""}
+
+        [[package]]
+        name = "colorama"
+        version = "0.4.6"
+        python-versions = "*"
+        markers = "platform_system == \"Windows\""
+        files = []
+
+        [[package]]
+        name = "mypy-extensions"
+        version = "1.0.0"
+        python-versions = ">=3.5"
+        files = []
+
+        [[package]]
+        name = "packaging"
+        version = "23.2"
+        python-versions = ">=3.7"
+        files = []
+
+        [[package]]
+        name = "pathspec"
+        version = "0.11.0"
+        python-versions = ">=3.7"
+        files = []
+
+        [[package]]
+        name = "platformdirs"
+        version = "4.0.0"
+        python-versions = ">=3.8"
+        files = []
+
+        [metadata]
+        lock-version = "2.0"
+        python-versions = "^3.8"
+        content-hash = "0000"
+    "#})?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--import-lock")
+        .arg("poetry.lock"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --import-lock poetry.lock
+    black==23.10.1
+    click==8.1.2
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    // Upgrading a package ignores its imported pin.
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--import-lock")
+        .arg("poetry.lock")
+        .arg("--upgrade-package")
+        .arg("click"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --import-lock poetry.lock --upgrade-package click
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Only `poetry.lock` and `Pipfile.lock` files can be imported.
+#[test]
+fn import_unsupported_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let lockfile = context.temp_dir.child("pdm.lock");
+    lockfile.touch()?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--import-lock")
+        .arg("pdm.lock"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unsupported lockfile `pdm.lock`: expected a `poetry.lock` or `Pipfile.lock` file
+    "###
+    );
+
+    Ok(())
+}
+
 /// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
 /// Only `click` should be upgraded.
 #[test]
@@ -5705,6 +6407,45 @@ fn pre_alias() -> Result<()> {
     Ok(())
 }
 
+/// Allow pre-releases via `--pre` in a requirements file.
+#[test]
+fn pre_requirements_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        --pre
+        flask<2.0.0rc4
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    click==8.1.7
+        # via flask
+    flask==2.0.0rc2
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.3
+        # via flask
+    markupsafe==2.1.5
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Allow a pre-release for a version specifier in a constraint file.
 #[test]
 fn pre_release_constraint() -> Result<()> {
@@ -6011,6 +6752,37 @@ requires-python = "<=3.8"
     Ok(())
 }
 
+/// Raise an error when a `pyproject.toml`'s `requires-python` constraint is not met.
+#[test]
+fn requires_python_pyproject_toml() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+dependencies = [
+  "anyio==4.0.0"
+]
+requires-python = "<=3.8"
+"#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("pyproject.toml"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The project requires Python <=3.8, but resolution targets Python 3.12.[X]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Raise an error when an editable's `Requires-Python` constraint is not met.
 #[test]
 fn requires_python_editable_target_version() -> Result<()> {
@@ -7786,6 +8558,60 @@ fn emit_index_annotation_multiple_indexes() -> Result<()> {
     Ok(())
 }
 
+/// `--index-pin` fetches a package from the pinned index only, as shown by
+/// `--emit-index-annotation`.
+#[test]
+fn emit_index_annotation_index_pin() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("requests")?;
+
+    // By default, `requests` is found on the extra index first.
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--emit-index-annotation")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --emit-index-annotation --no-deps
+    requests==2.5.4.1
+        # from https://test.pypi.org/simple
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // When pinned to PyPI, the extra index is never consulted for `requests`.
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--index-pin")
+        .arg("requests=https://pypi.org/simple")
+        .arg("--emit-index-annotation")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --emit-index-annotation --no-deps
+    requests==2.31.0
+        # from https://pypi.org/simple
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Test error message when direct dependency is an empty set.
 #[test]
 fn no_version_for_direct_dependency() -> Result<()> {
@@ -7810,3 +8636,34 @@ fn no_version_for_direct_dependency() -> Result<()> {
 
     Ok(())
 }
+
+/// Resolve a misspelled package name, which should suggest a similarly-named package that was
+/// previously fetched from the index.
+#[test]
+fn not_found_suggestion() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Populate the cache with the correctly-spelled package.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0")?;
+    let output = context.compile().arg("requirements.in").output()?;
+    assert!(output.status.success());
+
+    requirements_in.write_str("inicnofig")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because inicnofig was not found in the package registry and you require inicnofig, we can conclude that the requirements are unsatisfiable.
+
+          hint: inicnofig was not found in the package registry; did you mean `iniconfig`?
+    "###
+    );
+
+    Ok(())
+}
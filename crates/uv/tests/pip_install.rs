@@ -12,7 +12,9 @@ use itertools::Itertools;
 use common::{uv_snapshot, TestContext};
 use uv_fs::Simplified;
 
-use crate::common::{get_bin, BUILD_VENDOR_LINKS_URL};
+use crate::common::{
+    get_bin, python_path_with_versions, venv_to_interpreter, BUILD_VENDOR_LINKS_URL,
+};
 
 mod common;
 
@@ -302,6 +304,172 @@ fn install_package() {
     context.assert_command("import flask").success();
 }
 
+/// Identical wheels fetched from different sources should share a single unzipped archive in
+/// the cache.
+#[test]
+fn install_content_addressed_archive() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.install().arg("iniconfig==2.0.0").assert().success();
+
+    context
+        .install()
+        .arg("iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl")
+        .arg("--reinstall")
+        .assert()
+        .success();
+
+    let archives = fs_err::read_dir(context.cache_dir.join("archive-v0"))?.count();
+    assert_eq!(archives, 1);
+
+    Ok(())
+}
+
+/// Only the packages that were requested directly should be marked as `REQUESTED`.
+#[test]
+fn install_marks_requested() {
+    let context = TestContext::new("3.12");
+
+    context.install().arg("anyio==4.3.0").assert().success();
+
+    let site_packages = context.site_packages();
+    assert!(site_packages
+        .join("anyio-4.3.0.dist-info")
+        .join("REQUESTED")
+        .exists());
+    assert!(site_packages
+        .join("anyio-4.3.0.dist-info")
+        .join("INSTALLER")
+        .exists());
+    assert!(!site_packages
+        .join("sniffio-1.3.1.dist-info")
+        .join("REQUESTED")
+        .exists());
+}
+
+/// Install a package into a `--target` directory, rather than the virtual environment.
+#[test]
+fn install_target() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--target")
+        .arg("target"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The package should be installed at the top-level of the target directory...
+    context
+        .temp_dir
+        .child("target")
+        .child("iniconfig")
+        .child("__init__.py")
+        .assert(predicates::path::exists());
+
+    // ...and not in the virtual environment.
+    context.assert_command("import iniconfig").failure();
+
+    // Installing again should be a no-op.
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--target")
+        .arg("target"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+}
+
+/// Install a package into the user site-packages directory of the system Python, with `--user`.
+#[test]
+#[cfg(unix)]
+fn install_user() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let python_path = python_path_with_versions(&context.temp_dir, &["3.12"])
+        .expect("Failed to create Python test path");
+    let user_base = context.temp_dir.child("user");
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--user")
+        .env("UV_TEST_PYTHON_PATH", &python_path)
+        .env("PYTHONUSERBASE", user_base.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The package should be installed into the user site-packages directory...
+    user_base
+        .child("lib")
+        .child("python3.12")
+        .child("site-packages")
+        .child("iniconfig")
+        .child("__init__.py")
+        .assert(predicates::path::exists());
+
+    // ...and not in the virtual environment.
+    context.assert_command("import iniconfig").failure();
+
+    // Installing again should be a no-op.
+    uv_snapshot!(context.filters(), context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--user")
+        .env("UV_TEST_PYTHON_PATH", &python_path)
+        .env("PYTHONUSERBASE", user_base.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Refuse to install into the user site-packages directory from a virtual environment.
+#[test]
+fn install_user_virtualenv() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--user")
+        .arg("--python")
+        .arg(venv_to_interpreter(&context.venv)), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Can't install into the user site-packages directory from a virtual environment (`[VENV]/`), since it isn't visible there
+    "###
+    );
+}
+
 /// Install a package from a `requirements.txt` into a virtual environment.
 #[test]
 fn install_requirements_txt() -> Result<()> {
@@ -597,7 +765,8 @@ fn reinstall_extras() -> Result<()> {
     Ok(())
 }
 
-/// Warn, but don't fail, when uninstalling incomplete packages.
+/// Warn, but don't fail, when uninstalling incomplete packages, removing them based on their
+/// top-level modules instead.
 #[test]
 fn reinstall_incomplete() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -640,13 +809,66 @@ fn reinstall_incomplete() -> Result<()> {
     ----- stderr -----
     Resolved 3 packages in [TIME]
     Downloaded 1 package in [TIME]
-    warning: Failed to uninstall package at [SITE_PACKAGES]/anyio-3.7.0.dist-info due to missing RECORD file. Installation may result in an incomplete environment.
+    warning: The RECORD file for the package at [SITE_PACKAGES]/anyio-3.7.0.dist-info is missing or invalid; removing the package based on its top-level modules instead. Any scripts it installed may be left behind.
     Installed 1 package in [TIME]
      - anyio==3.7.0
      + anyio==4.0.0
     "###
     );
 
+    // The old distribution should be removed entirely.
+    assert!(!context
+        .site_packages()
+        .join("anyio-3.7.0.dist-info")
+        .exists());
+
+    Ok(())
+}
+
+/// Repair a package with a damaged `RECORD` file by reinstalling it.
+#[test]
+fn reinstall_damaged_record() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Install anyio.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==3.7.0")?;
+
+    context
+        .install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Corrupt the `RECORD` file, and remove one of the package's modules.
+    fs_err::write(
+        context.site_packages().join("anyio-3.7.0.dist-info/RECORD"),
+        "anyio/__init__.py,sha256=abc,not-a-size\n",
+    )?;
+    fs_err::remove_file(context.site_packages().join("anyio/to_thread.py"))?;
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--reinstall-package")
+        .arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    warning: The RECORD file for the package at [SITE_PACKAGES]/anyio-3.7.0.dist-info is missing or invalid; removing the package based on its top-level modules instead. Any scripts it installed may be left behind.
+    Installed 1 package in [TIME]
+     - anyio==3.7.0
+     + anyio==3.7.0
+    "###
+    );
+
+    // The package should be intact again.
+    assert!(context.site_packages().join("anyio/to_thread.py").exists());
+
     Ok(())
 }
 
@@ -878,6 +1100,80 @@ fn install_editable_no_binary() {
     );
 }
 
+/// Install an editable package with a backend that prepares its metadata through
+/// `prepare_metadata_for_build_editable`, which should be passed back to `build_editable`.
+#[test]
+fn install_editable_prepare_metadata() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let package = context.temp_dir.child("editable");
+    package.child("pyproject.toml").write_str(indoc! {r#"
+        [build-system]
+        requires = []
+        build-backend = "backend"
+        backend-path = ["."]
+        "#
+    })?;
+    package.child("editable.py").write_str("VALUE = 1\n")?;
+    package.child("backend.py").write_str(indoc! {r#"
+        import os
+        import pathlib
+        import zipfile
+
+        DIST_INFO = "editable-0.1.0.dist-info"
+        METADATA = "Metadata-Version: 2.1\nName: editable\nVersion: 0.1.0\n"
+
+        def prepare_metadata_for_build_wheel(metadata_directory, config_settings=None):
+            raise RuntimeError("expected `prepare_metadata_for_build_editable` to be called")
+
+        def prepare_metadata_for_build_editable(metadata_directory, config_settings=None):
+            dist_info = pathlib.Path(metadata_directory, DIST_INFO)
+            dist_info.mkdir()
+            (dist_info / "METADATA").write_text(METADATA)
+            return DIST_INFO
+
+        def build_editable(wheel_directory, config_settings=None, metadata_directory=None):
+            if metadata_directory is None:
+                raise RuntimeError("expected the prepared metadata directory")
+            metadata = pathlib.Path(metadata_directory, "METADATA").read_text()
+            filename = "editable-0.1.0-py3-none-any.whl"
+            with zipfile.ZipFile(os.path.join(wheel_directory, filename), "w") as wheel:
+                wheel.writestr("editable.pth", os.path.abspath(os.path.dirname(__file__)))
+                wheel.writestr(f"{DIST_INFO}/METADATA", metadata)
+                wheel.writestr(
+                    f"{DIST_INFO}/WHEEL",
+                    "Wheel-Version: 1.0\nGenerator: backend\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                )
+                wheel.writestr(
+                    f"{DIST_INFO}/RECORD",
+                    f"editable.pth,,\n{DIST_INFO}/METADATA,,\n{DIST_INFO}/WHEEL,,\n{DIST_INFO}/RECORD,,\n",
+                )
+            return filename
+        "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg("-e")
+        .arg("./editable"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Built 1 editable in [TIME]
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + editable==0.1.0 (from file://[TEMP_DIR]/editable)
+    "###
+    );
+
+    context
+        .assert_command("from editable import VALUE; assert VALUE == 1")
+        .success();
+
+    Ok(())
+}
+
 /// Install a source distribution that uses the `flit` build system, along with `flit`
 /// at the top-level, along with `--reinstall` to force a re-download after resolution, to ensure
 /// that the `flit` install and the source distribution build don't conflict.
@@ -1861,6 +2157,43 @@ fn launcher() -> Result<()> {
     Ok(())
 }
 
+/// Install a package with an entrypoint that locates the interpreter relative to the script.
+#[test]
+#[cfg(unix)]
+fn launcher_relative_shebang() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("../.."))?;
+
+    context
+        .install()
+        .arg(format!(
+            "simple_launcher@{}",
+            project_root
+                .join("scripts/links/simple_launcher-0.1.0-py3-none-any.whl")
+                .display()
+        ))
+        .arg("--shebang")
+        .arg("relative")
+        .assert()
+        .success();
+
+    let launcher = context.venv.join("bin").join("simple_launcher");
+    let contents = fs_err::read_to_string(&launcher)?;
+    assert!(contents.starts_with("#!/bin/sh\n"));
+    assert!(!contents.contains(&*context.venv.to_string_lossy()));
+
+    uv_snapshot!(Command::new(launcher), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hi from the simple launcher!
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
+
 #[test]
 fn launcher_with_symlink() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -4098,3 +4431,372 @@ fn require_hashes_override() -> Result<()> {
 
     Ok(())
 }
+
+/// Attestation verification requires a trust root.
+#[test]
+fn attestations_without_trust_root() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.install()
+        .arg("anyio==4.0.0")
+        .arg("--attestations")
+        .arg("verify")
+        .env_remove("UV_ATTESTATION_TRUST_ROOT"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Attestation verification requires a trust root; provide one with `--attestation-trust-root`
+    "###
+    );
+}
+
+/// With `--attestations require`, distributions without attestations are rejected before they're
+/// downloaded.
+#[test]
+fn attestations_required() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // The trust root is never consulted, since the index doesn't provide any attestations.
+    let trust_root = context.temp_dir.child("trust-root.pem");
+    trust_root.write_str(indoc! {r"
+        -----BEGIN CERTIFICATE-----
+        MIIB9zCCAXygAwIBAgIUALZNAPFdxHPwjeDloDwyYChAO/4wCgYIKoZIzj0EAwMw
+        KjEVMBMGA1UEChMMc2lnc3RvcmUuZGV2MREwDwYDVQQDEwhzaWdzdG9yZTAeFw0y
+        MTEwMDcxMzU2NTlaFw0zMTEwMDUxMzU2NThaMCoxFTATBgNVBAoTDHNpZ3N0b3Jl
+        LmRldjERMA8GA1UEAxMIc2lnc3RvcmUwdjAQBgcqhkjOPQIBBgUrgQQAIgNiAAT7
+        XeFT4rb3PQGwS4IajtLk3/OlnpgangaBclYpsYBr5i+4ynB07ceb3LP0OIOZdxex
+        X69c5iVuyJRQ+Hz05yi+UF3uBWAlHpiS5sh0+H2GHE7SXrk1EC5m1Tr19L9gg92j
+        YzBhMA4GA1UdDwEB/wQEAwIBBjAPBgNVHRMBAf8EBTADAQH/MB0GA1UdDgQWBBRY
+        wB5fkUWlZql6zJChkyLQKsXF+jAfBgNVHSMEGDAWgBRYwB5fkUWlZql6zJChkyLQ
+        KsXF+jAKBggqhkjOPQQDAwNpADBmAjEAj1nHeXZp+13NWBNa+EDsDP8G1WWg1tCM
+        WP/WHPqpaVo0jhsweNFZgSs0eE7wYI4qAjEA2WB9ot98sIkoF3vZYdd3/VtWB5b9
+        TNMea7Ix/stJ5TfcLLeABLE4BNJOsQ4vnBHJ
+        -----END CERTIFICATE-----
+        -----BEGIN PUBLIC KEY-----
+        MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE2G2Y+2tabdTV5BcGiBIx0a9fAFwr
+        kBbmLSGtks4L3qX6yYY0zufBnhC8Ur/iy55GhWP/9A/bY2LhC30M9+RYtw==
+        -----END PUBLIC KEY-----
+    "})?;
+
+    uv_snapshot!(context.install()
+        .arg("iniconfig==2.0.0")
+        .arg("--attestations")
+        .arg("require")
+        .arg("--attestation-trust-root")
+        .arg(trust_root.path()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Attestations are required, but the index doesn't provide any for `iniconfig-2.0.0-py3-none-any.whl`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Verify the attestations published to PyPI for a distribution against Sigstore's trusted root.
+#[test]
+fn attestations_verify() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Download Sigstore's trusted root, as distributed via its TUF repository.
+    let trust_root = context.temp_dir.child("trusted_root.json");
+    let response = reqwest::blocking::get(
+        "https://raw.githubusercontent.com/sigstore/root-signing/main/targets/trusted_root.json",
+    )?;
+    trust_root.write_str(&response.text()?)?;
+
+    // Attestations signed by another repository are rejected.
+    uv_snapshot!(context.install()
+        .arg("sigstore==3.6.1")
+        .arg("--no-deps")
+        .arg("--attestations")
+        .arg("verify")
+        .arg("--attestation-trust-root")
+        .arg(trust_root.path())
+        .arg("--attestation-identity")
+        .arg("sigstore=https://github.com/attacker/sigstore-python"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to verify attestations for `sigstore-3.6.1-py3-none-any.whl`
+      Caused by: Attestation was signed by `https://github.com/sigstore/sigstore-python`, but expected `https://github.com/attacker/sigstore-python`
+    "###
+    );
+
+    // With the expected identity, the attestations verify.
+    uv_snapshot!(context.install()
+        .arg("sigstore==3.6.1")
+        .arg("--no-deps")
+        .arg("--attestations")
+        .arg("require")
+        .arg("--attestation-trust-root")
+        .arg(trust_root.path())
+        .arg("--attestation-identity")
+        .arg("sigstore=https://github.com/sigstore/sigstore-python"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Verified attestations for 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + sigstore==3.6.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write a local package with the given name, which installs a `shared` module with the given
+/// contents.
+fn shared_module_package(
+    context: &TestContext,
+    name: &str,
+    contents: &str,
+) -> Result<std::path::PathBuf> {
+    let package = context.temp_dir.child(name);
+    package.child("pyproject.toml").write_str(&format!(
+        indoc! {r#"
+            [build-system]
+            requires = ["hatchling"]
+            build-backend = "hatchling.build"
+
+            [project]
+            name = "{name}"
+            version = "0.1.0"
+
+            [tool.hatch.build.targets.wheel]
+            packages = ["shared"]
+        "#},
+        name = name
+    ))?;
+    package
+        .child("shared")
+        .child("__init__.py")
+        .write_str(contents)?;
+    Ok(package.to_path_buf())
+}
+
+/// Refuse to install a package that would overwrite a file installed by another package.
+#[test]
+fn install_file_conflict() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let first = shared_module_package(&context, "first", "VALUE = 1\n")?;
+    let second = shared_module_package(&context, "second", "VALUE = 2\n")?;
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg(&first)
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(BUILD_VENDOR_LINKS_URL), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + first==0.1.0 (from file://[TEMP_DIR]/first)
+    "###
+    );
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg(&second)
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(BUILD_VENDOR_LINKS_URL), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    error: Failed to install: second-0.1.0-py3-none-any.whl (second==0.1.0 (from file://[TEMP_DIR]/second))
+      Caused by: The file `shared/__init__.py` would overwrite a file installed by `first`
+    "###
+    );
+
+    // The existing file is left untouched.
+    context
+        .assert_command("from shared import VALUE; assert VALUE == 1")
+        .success();
+
+    Ok(())
+}
+
+/// Install a package that overwrites a file installed by another package, with
+/// `--allow-overwrite`.
+#[test]
+fn install_file_conflict_allow_overwrite() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let first = shared_module_package(&context, "first", "VALUE = 1\n")?;
+    let second = shared_module_package(&context, "second", "VALUE = 2\n")?;
+
+    context
+        .install()
+        .arg(&first)
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(BUILD_VENDOR_LINKS_URL)
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.install()
+        .arg(&second)
+        .arg("--allow-overwrite")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(BUILD_VENDOR_LINKS_URL), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + second==0.1.0 (from file://[TEMP_DIR]/second)
+    "###
+    );
+
+    context
+        .assert_command("from shared import VALUE; assert VALUE == 2")
+        .success();
+
+    Ok(())
+}
+
+/// Build two local packages with the same build requirements, which should share a single
+/// isolated build environment.
+#[test]
+fn install_reuse_build_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+    for name in ["alpha", "beta"] {
+        let package = context.temp_dir.child(name);
+        package.child("pyproject.toml").write_str(&format!(
+            indoc! {r#"
+                [build-system]
+                requires = ["hatchling"]
+                build-backend = "hatchling.build"
+
+                [project]
+                name = "{name}"
+                version = "0.1.0"
+            "#},
+            name = name
+        ))?;
+        package.child(name).child("__init__.py").write_str("")?;
+    }
+
+    let output = context
+        .install()
+        .arg("./alpha")
+        .arg("./beta")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(BUILD_VENDOR_LINKS_URL)
+        .arg("--verbose")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+
+    // The environment is created once, and reused by the second build.
+    assert_eq!(
+        stderr.matches("Creating build environment for").count(),
+        1,
+        "{stderr}"
+    );
+    assert!(stderr.contains("Reusing build environment for"), "{stderr}");
+
+    context.assert_command("import alpha, beta").success();
+
+    Ok(())
+}
+
+/// Write a wheel for `example==1.0.0` with the given platform tag, whose `example` module records
+/// the platform tag it was built for.
+fn platform_wheel(directory: &std::path::Path, platform_tag: &str) -> Result<()> {
+    use std::io::Write;
+
+    let dist_info = "example-1.0.0.dist-info";
+    let mut wheel = zip::ZipWriter::new(fs_err::File::create(
+        directory.join(format!("example-1.0.0-py3-none-{platform_tag}.whl")),
+    )?);
+    let options = zip::write::FileOptions::default();
+    for (path, contents) in [
+        (
+            "example.py".to_string(),
+            format!("PLATFORM = {platform_tag:?}\n"),
+        ),
+        (
+            format!("{dist_info}/METADATA"),
+            "Metadata-Version: 2.1\nName: example\nVersion: 1.0.0\n".to_string(),
+        ),
+        (
+            format!("{dist_info}/WHEEL"),
+            format!("Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-{platform_tag}\n"),
+        ),
+        (format!("{dist_info}/RECORD"), String::new()),
+    ] {
+        wheel.start_file(path, options)?;
+        wheel.write_all(contents.as_bytes())?;
+    }
+    wheel.finish()?;
+    Ok(())
+}
+
+/// Install from a `--find-links` directory containing both a `manylinux` and a universal wheel,
+/// with `--max-manylinux` and `--no-platform-wheels` restricting the platform wheels that are
+/// considered compatible.
+#[test]
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn install_tag_policy() -> Result<()> {
+    let manylinux = format!("manylinux_2_17_{}", std::env::consts::ARCH);
+
+    for (args, expected) in [
+        (&[][..], manylinux.as_str()),
+        (
+            &["--max-manylinux", "manylinux_2_17"][..],
+            manylinux.as_str(),
+        ),
+        (&["--max-manylinux", "manylinux2010"][..], "any"),
+        (&["--no-platform-wheels"][..], "any"),
+    ] {
+        let context = TestContext::new("3.12");
+        let wheels = context.temp_dir.child("wheels");
+        wheels.create_dir_all()?;
+        platform_wheel(wheels.path(), &manylinux)?;
+        platform_wheel(wheels.path(), "any")?;
+
+        context
+            .install()
+            .arg("example")
+            .arg("--no-index")
+            .arg("--find-links")
+            .arg(wheels.path())
+            .args(args)
+            .assert()
+            .success();
+
+        context
+            .assert_command(&format!(
+                "from example import PLATFORM; assert PLATFORM == {expected:?}, PLATFORM"
+            ))
+            .success();
+    }
+
+    Ok(())
+}
@@ -3980,6 +3980,8 @@ fn python_greater_than_current_excluded() {
           Because the current Python version (3.9.[X]) does not satisfy Python>=3.12 and package-a==4.0.0 depends on Python>=3.12, we can conclude that package-a==4.0.0 cannot be used.
           And because we know from (2) that package-a>=2.0.0,<4.0.0 cannot be used, we can conclude that package-a>=2.0.0 cannot be used.
           And because you require package-a>=2.0.0, we can conclude that the requirements are unsatisfiable.
+
+          hint: Newer versions of package-a (package-a>=2.0.0) require Python>=3.10, but the current Python version is 3.9.[X]; the newest compatible version is package-a==1.0.0
     "###);
 
     assert_not_installed(
@@ -0,0 +1,173 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `pip sbom` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("sbom")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Create a `pip sync` command with options shared across scenarios.
+fn sync_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Install a registry package and a direct URL wheel, then emit their CycloneDX and SPDX
+/// documents.
+#[test]
+fn sbom() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1\niniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl")?;
+
+    sync_command(&context)
+        .arg(requirements_txt.path())
+        .assert()
+        .success();
+
+    // Generate a CycloneDX document.
+    let output = command(&context).output()?;
+    assert!(output.status.success());
+    let bom: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+
+    let components = bom["components"]
+        .as_array()
+        .context("Missing `components`")?;
+    assert_eq!(components.len(), 2);
+
+    let iniconfig = &components[0];
+    assert_eq!(iniconfig["name"], "iniconfig");
+    assert_eq!(iniconfig["version"], "2.0.0");
+    assert_eq!(iniconfig["purl"], "pkg:pypi/iniconfig@2.0.0");
+    assert_eq!(
+        iniconfig["externalReferences"][0]["url"],
+        "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"
+    );
+
+    let tomli = &components[1];
+    assert_eq!(tomli["name"], "tomli");
+    assert_eq!(tomli["version"], "2.0.1");
+    assert!(tomli.get("externalReferences").is_none());
+    assert!(tomli.get("licenses").is_some());
+
+    // Generate an SPDX document.
+    let output = command(&context).arg("--format").arg("spdx").output()?;
+    assert!(output.status.success());
+    let bom: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(bom["spdxVersion"], "SPDX-2.3");
+
+    let packages = bom["packages"].as_array().context("Missing `packages`")?;
+    assert_eq!(packages.len(), 2);
+    assert_eq!(packages[0]["SPDXID"], "SPDXRef-Package-iniconfig-2.0.0");
+    assert_eq!(packages[1]["downloadLocation"], "NOASSERTION");
+    assert_eq!(
+        packages[1]["externalRefs"][0]["referenceLocator"],
+        "pkg:pypi/tomli@2.0.1"
+    );
+
+    Ok(())
+}
+
+/// Resolve a set of requirements, then emit their CycloneDX and SPDX documents via
+/// `pip compile --format`, without installing them.
+#[test]
+fn sbom_resolution() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("my requirements.in");
+    requirements_in.write_str("tomli==2.0.1")?;
+
+    // Generate a CycloneDX document.
+    let output = context
+        .compile()
+        .arg("my requirements.in")
+        .arg("--format")
+        .arg("cyclonedx")
+        .arg("--quiet")
+        .output()?;
+    assert!(output.status.success());
+    let bom: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+
+    let components = bom["components"]
+        .as_array()
+        .context("Missing `components`")?;
+    assert_eq!(components.len(), 1);
+
+    // Unlike an installed environment, the resolution records the selected artifact and its
+    // hashes.
+    let tomli = &components[0];
+    assert_eq!(tomli["name"], "tomli");
+    assert_eq!(tomli["version"], "2.0.1");
+    assert!(tomli["externalReferences"][0]["url"]
+        .as_str()
+        .is_some_and(|url| url.ends_with("/tomli-2.0.1-py3-none-any.whl")));
+    assert_eq!(tomli["hashes"][0]["alg"], "SHA-256");
+    assert!(tomli.get("licenses").is_some());
+
+    // Generate an SPDX document, named after the requirements file.
+    let output = context
+        .compile()
+        .arg("my requirements.in")
+        .arg("--format")
+        .arg("spdx")
+        .arg("--quiet")
+        .output()?;
+    assert!(output.status.success());
+    let bom: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(bom["spdxVersion"], "SPDX-2.3");
+    assert_eq!(bom["name"], "my requirements.in");
+
+    // The name is percent-encoded in the namespace.
+    let namespace = bom["documentNamespace"]
+        .as_str()
+        .context("Missing `documentNamespace`")?;
+    assert!(
+        namespace.starts_with("https://spdx.org/spdxdocs/my%20requirements.in-"),
+        "{namespace}"
+    );
+
+    let packages = bom["packages"].as_array().context("Missing `packages`")?;
+    assert_eq!(packages.len(), 1);
+    assert!(packages[0]["downloadLocation"]
+        .as_str()
+        .is_some_and(|url| url.ends_with("/tomli-2.0.1-py3-none-any.whl")));
+    assert_eq!(packages[0]["checksums"][0]["algorithm"], "SHA256");
+
+    Ok(())
+}
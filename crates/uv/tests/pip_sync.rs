@@ -910,6 +910,64 @@ fn warn_on_yanked_version() -> Result<()> {
     Ok(())
 }
 
+/// Refuse to install a yanked version with `--yanked disallow`, even though it's pinned exactly.
+#[test]
+fn yanked_disallow() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // This version is yanked.
+    let requirements_in = context.temp_dir.child("requirements.txt");
+    requirements_in.write_str("colorama==0.4.2")?;
+
+    uv_snapshot!(context.filters(), windows_filters=false, command(&context)
+        .arg("requirements.txt")
+        .arg("--yanked")
+        .arg("disallow"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because colorama==0.4.2 is unusable because it was yanked (reason: Bad build, missing files, will not install) and you require colorama==0.4.2, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    context.assert_command("import colorama").failure();
+
+    Ok(())
+}
+
+/// Allow pre-releases via `--pre` in a requirements file, and warn on options that have no effect.
+#[test]
+fn pre_requirements_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        --pre
+        --trusted-host pypi.org
+        flask<2.0.0rc4
+    "})?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Ignoring unsupported option in requirements file: `--trusted-host`
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + flask==2.0.0rc2
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a local wheel.
 #[test]
 fn install_local_wheel() -> Result<()> {
@@ -1051,6 +1109,76 @@ fn mismatched_version() -> Result<()> {
     Ok(())
 }
 
+/// Fail to install a wheel that replaces an installed distribution, which should restore the
+/// replaced distribution and remove the wheels that were installed alongside it.
+#[test]
+fn install_failure_restores_snapshot() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    // Replace `tomli` with a wheel whose version doesn't match its filename, which fails to
+    // install after the installed `tomli` is removed.
+    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/97/75/10a9ebee3fd790d20926a90a2547f0bf78f371b2f13aa822c759680ca7b9/tomli-2.0.1-py3-none-any.whl")?;
+    let archive = context.temp_dir.child("tomli-3.7.2-py3-none-any.whl");
+    let mut archive_file = fs_err::File::create(archive.path())?;
+    std::io::copy(&mut response.bytes()?.as_ref(), &mut archive_file)?;
+
+    requirements_txt.write_str(&format!(
+        "tomli @ {}\niniconfig==2.0.0",
+        Url::from_file_path(archive.path()).unwrap()
+    ))?;
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Uninstalled 1 package in [TIME]
+    error: Failed to install: tomli-3.7.2-py3-none-any.whl (tomli==3.7.2 (from file://[TEMP_DIR]/tomli-3.7.2-py3-none-any.whl))
+      Caused by: Wheel version does not match filename: 2.0.1 != 3.7.2
+    "###
+    );
+
+    // The replaced `tomli` is restored...
+    context.assert_command("import tomli").success();
+    assert!(context
+        .site_packages()
+        .join("tomli-2.0.1.dist-info")
+        .join("RECORD")
+        .is_file());
+
+    // ...and `iniconfig`, which was installed alongside it, is removed.
+    context.assert_command("import iniconfig").failure();
+    assert!(!context
+        .site_packages()
+        .join("iniconfig-2.0.0.dist-info")
+        .exists());
+
+    Ok(())
+}
+
 /// Install a wheel whose actual name doesn't match the name encoded in the filename.
 #[test]
 fn mismatched_name() -> Result<()> {
@@ -4693,3 +4821,83 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
 
     Ok(())
 }
+
+/// Install from a lockfile, skipping the entries whose markers don't apply to the environment.
+#[test]
+fn sync_lockfile() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let lockfile = context.temp_dir.child("requirements.lock");
+    lockfile.write_str(indoc! {r#"
+        version = 1
+
+        [markers]
+        implementation_name = "cpython"
+        implementation_version = "3.12.1"
+        os_name = "posix"
+        platform_machine = "x86_64"
+        platform_python_implementation = "CPython"
+        platform_release = "6.5.0"
+        platform_system = "Linux"
+        platform_version = "Linux"
+        python_full_version = "3.12.1"
+        python_version = "3.12"
+        sys_platform = "linux"
+
+        [[distribution]]
+        name = "anyio"
+        version = "4.0.0"
+        marker = "python_version < '3'"
+
+        [distribution.source]
+        registry = "https://pypi.org/simple"
+
+        [distribution.file]
+        filename = "anyio-4.0.0-py3-none-any.whl"
+        url = "https://files.pythonhosted.org/packages/anyio-4.0.0-py3-none-any.whl"
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        hashes = ["sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374"]
+
+        [distribution.source]
+        registry = "https://pypi.org/simple"
+
+        [distribution.file]
+        filename = "iniconfig-2.0.0-py3-none-any.whl"
+        url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"
+    "#})?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.lock")
+        .arg("--require-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // A lockfile can't be combined with other requirements.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.lock")
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: A lockfile can't be combined with other requirements
+    "###
+    );
+
+    Ok(())
+}
@@ -274,6 +274,56 @@ fn uninstall_editable_by_name() -> Result<()> {
     Ok(())
 }
 
+/// Uninstall a legacy editable, as installed by `setup.py develop`: the `.egg-link` file and its
+/// entry in `easy-install.pth` are removed, while the project itself is left in place.
+#[test]
+fn uninstall_legacy_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a project with an `.egg-info` directory.
+    let project = context.temp_dir.child("legacy");
+    project
+        .child("legacy.egg-info")
+        .child("PKG-INFO")
+        .write_str("Metadata-Version: 2.1\nName: legacy\nVersion: 0.1.0\n")?;
+    project.child("legacy.py").touch()?;
+
+    // Link the project into the environment.
+    let site_packages = context.site_packages();
+    let target = project.path().to_str().expect("Path is valid unicode");
+    fs_err::write(
+        site_packages.join("legacy.egg-link"),
+        format!("{target}\n."),
+    )?;
+    fs_err::write(
+        site_packages.join("easy-install.pth"),
+        format!("/path/to/other\n{target}\n"),
+    )?;
+
+    uv_snapshot!(context.filters(), uninstall_command(&context)
+        .arg("legacy"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - legacy==0.1.0 (from file://[TEMP_DIR]/legacy/)
+    "###
+    );
+
+    assert!(!site_packages.join("legacy.egg-link").exists());
+    assert_eq!(
+        fs_err::read_to_string(site_packages.join("easy-install.pth"))?,
+        "/path/to/other\n"
+    );
+    project
+        .child("legacy.py")
+        .assert(predicates::path::exists());
+
+    Ok(())
+}
+
 #[test]
 fn uninstall_by_path() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -0,0 +1,91 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot, INSTA_FILTERS};
+
+mod common;
+
+/// Create a `publish` command with options shared across scenarios.
+fn command(temp_dir: &assert_fs::TempDir) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("publish")
+        .arg("--publish-url")
+        .arg("http://127.0.0.1:1/legacy/")
+        .arg("--retries")
+        .arg("0")
+        .env_remove("UV_PUBLISH_TOKEN")
+        .env_remove("UV_TRUSTED_PUBLISHING")
+        .env("UV_NO_WRAP", "1")
+        .current_dir(temp_dir);
+    command
+}
+
+/// Fail when there's nothing to publish.
+#[test]
+fn publish_no_files() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir.child("dist").child(".gitignore").write_str("*")?;
+
+    uv_snapshot!(INSTA_FILTERS.to_vec(), command(&temp_dir), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Path patterns didn't match any wheels or source distributions
+    "###
+    );
+
+    Ok(())
+}
+
+/// Fail before uploading anything when a distribution's metadata can't be read.
+#[test]
+fn publish_invalid_wheel() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir
+        .child("dist")
+        .child("example-1.0.0-py3-none-any.whl")
+        .write_str("not a zip file")?;
+
+    let output = command(&temp_dir).output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(!output.status.success(), "{stderr}");
+    assert!(
+        stderr.contains("Failed to publish: `dist/example-1.0.0-py3-none-any.whl`")
+            || stderr.contains("Failed to publish: `dist\\example-1.0.0-py3-none-any.whl`"),
+        "{stderr}"
+    );
+
+    Ok(())
+}
+
+/// Fail when trusted publishing is required outside of a supported CI provider.
+#[test]
+fn publish_trusted_publishing_unsupported() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    temp_dir
+        .child("dist")
+        .child("example-1.0.0-py3-none-any.whl")
+        .touch()?;
+
+    uv_snapshot!(INSTA_FILTERS.to_vec(), command(&temp_dir)
+        .arg("--trusted-publishing")
+        .arg("always")
+        .env_remove("GITHUB_ACTIONS")
+        .env_remove("GITLAB_CI"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to obtain an upload token via trusted publishing
+      Caused by: Trusted publishing is only supported in GitHub Actions and GitLab CI
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, python_path_with_versions, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `run` command with options shared across scenarios.
+fn command(context: &TestContext) -> Result<Command> {
+    let python_path = python_path_with_versions(&context.temp_dir, &["3.12"])?;
+    let mut command = Command::new(get_bin());
+    command
+        .arg("run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("--python")
+        .arg("3.12")
+        .env("UV_TEST_PYTHON_PATH", python_path)
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    Ok(command)
+}
+
+/// Run a script with inline metadata, installing its dependencies into a cached environment.
+#[test]
+fn run_script_with_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let script = context.temp_dir.child("main.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig==2.0.0",
+        # ]
+        # ///
+        import sys
+
+        import iniconfig
+
+        print(sys.argv[1:])
+    "#})?;
+
+    let output = command(&context)?
+        .arg("main.py")
+        .arg("--flag")
+        .arg("value")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert_eq!(
+        String::from_utf8(output.stdout)?.trim(),
+        "['--flag', 'value']"
+    );
+    assert!(stderr.contains("+ iniconfig==2.0.0"), "{stderr}");
+
+    // The environment is reused on subsequent runs.
+    let output = command(&context)?.arg("main.py").output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(!stderr.contains("iniconfig"), "{stderr}");
+
+    Ok(())
+}
+
+/// Recreate the script's cached environment with `--refresh` and `--refresh-package`.
+///
+/// Options must precede the script, as any later arguments are passed to the script itself.
+#[test]
+fn run_script_refresh() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let script = context.temp_dir.child("main.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # dependencies = [
+        #   "iniconfig==2.0.0",
+        # ]
+        # ///
+        import iniconfig
+    "#})?;
+
+    let output = command(&context)?.arg("main.py").output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("+ iniconfig==2.0.0"), "{stderr}");
+
+    // The environment is recreated when refreshing all packages...
+    let output = command(&context)?
+        .arg("--refresh")
+        .arg("main.py")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("+ iniconfig==2.0.0"), "{stderr}");
+
+    // ...or one of the script's dependencies...
+    let output = command(&context)?
+        .arg("--refresh-package")
+        .arg("iniconfig")
+        .arg("main.py")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("+ iniconfig==2.0.0"), "{stderr}");
+
+    // ...but not when refreshing an unrelated package.
+    let output = command(&context)?
+        .arg("--refresh-package")
+        .arg("tomli")
+        .arg("main.py")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(!stderr.contains("iniconfig"), "{stderr}");
+
+    Ok(())
+}
+
+/// Propagate the script's exit code, and reject scripts that require a different Python.
+#[test]
+fn run_script_exit_code_and_requires_python() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let script = context.temp_dir.child("exit.py");
+    script.write_str("import sys; sys.exit(3)")?;
+    command(&context)?.arg("exit.py").assert().code(3);
+
+    let script = context.temp_dir.child("old.py");
+    script.write_str(indoc! {r#"
+        # /// script
+        # requires-python = "<3.8"
+        # ///
+        print("unreachable")
+    "#})?;
+    let output = command(&context)?.arg("old.py").output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert_eq!(output.status.code(), Some(2), "{stderr}");
+    assert!(
+        stderr.contains("The script requires Python <3.8, but 3.12"),
+        "{stderr}"
+    );
+    assert!(output.stdout.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, python_path_with_versions, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `tool` command with options shared across scenarios.
+fn command(context: &TestContext, subcommand: &str) -> Result<Command> {
+    let python_path = python_path_with_versions(&context.temp_dir, &["3.12"])?;
+    let mut command = Command::new(get_bin());
+    command
+        .arg("tool")
+        .arg(subcommand)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("UV_TOOL_DIR", context.temp_dir.child("tools").path())
+        .env("UV_TOOL_BIN_DIR", context.temp_dir.child("bin").path())
+        .env("UV_TEST_PYTHON_PATH", python_path)
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    Ok(command)
+}
+
+/// Install a tool, list it, and uninstall it.
+#[test]
+fn tool_install_list_uninstall() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let executable = context
+        .temp_dir
+        .child("bin")
+        .child(format!("pyflakes{}", std::env::consts::EXE_SUFFIX));
+
+    let output = command(&context, "install")?
+        .arg("pyflakes==3.2.0")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("+ pyflakes==3.2.0"), "{stderr}");
+    assert!(
+        stderr.contains("Installed 1 executable: pyflakes"),
+        "{stderr}"
+    );
+    executable.assert(predicates::path::exists());
+    context
+        .temp_dir
+        .child("tools")
+        .child("pyflakes")
+        .child("uv-receipt.toml")
+        .assert(predicates::path::is_file());
+
+    // The exposed executable runs the tool.
+    Command::new(executable.path())
+        .arg("--version")
+        .assert()
+        .success();
+
+    // Installing again is a no-op.
+    let output = command(&context, "install")?.arg("pyflakes").output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("is already installed"), "{stderr}");
+
+    let output = command(&context, "list")?.output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "pyflakes v3.2.0\n- pyflakes\n"
+    );
+
+    let output = command(&context, "uninstall")?.arg("pyflakes").output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(output.status.success(), "{stderr}");
+    executable.assert(predicates::path::missing());
+    context
+        .temp_dir
+        .child("tools")
+        .child("pyflakes")
+        .assert(predicates::path::missing());
+
+    // Uninstalling a tool that isn't installed fails.
+    command(&context, "uninstall")?
+        .arg("pyflakes")
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+/// Refuse to overwrite an existing executable that doesn't belong to the tool.
+#[test]
+fn tool_install_existing_executable() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let executable = context
+        .temp_dir
+        .child("bin")
+        .child(format!("pyflakes{}", std::env::consts::EXE_SUFFIX));
+    executable.write_str("not a tool")?;
+
+    let output = command(&context, "install")?
+        .arg("pyflakes==3.2.0")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(!output.status.success(), "{stderr}");
+    assert!(stderr.contains("Executable already exists"), "{stderr}");
+    executable.assert("not a tool");
+    context
+        .temp_dir
+        .child("tools")
+        .child("pyflakes")
+        .assert(predicates::path::missing());
+
+    // With `--force`, the executable is replaced.
+    command(&context, "install")?
+        .arg("pyflakes==3.2.0")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("--force")
+        .assert()
+        .success();
+    Command::new(executable.path())
+        .arg("--version")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// A failed reinstallation leaves the existing installation in place.
+#[test]
+fn tool_install_failure_preserves_existing() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let executable = context
+        .temp_dir
+        .child("bin")
+        .child(format!("pyflakes{}", std::env::consts::EXE_SUFFIX));
+
+    command(&context, "install")?
+        .arg("pyflakes==3.2.0")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .assert()
+        .success();
+
+    // Reinstalling with an unsatisfiable requirement fails...
+    let output = command(&context, "install")?
+        .arg("pyflakes==999.0.0")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .arg("--force")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(!output.status.success(), "{stderr}");
+
+    // ...but the existing installation, its receipt, and its executable are untouched.
+    let output = command(&context, "list")?.output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "pyflakes v3.2.0\n- pyflakes\n"
+    );
+    Command::new(executable.path())
+        .arg("--version")
+        .assert()
+        .success();
+
+    // Executables left dangling by a lost environment don't block a plain reinstall. (On
+    // Windows, executables are copied rather than linked, so their origin is unknown.)
+    #[cfg(unix)]
+    {
+        fs_err::remove_dir_all(context.temp_dir.child("tools").child("pyflakes"))?;
+        command(&context, "install")?
+            .arg("pyflakes==3.2.0")
+            .arg("--python")
+            .arg("3.12")
+            .arg("--exclude-newer")
+            .arg(EXCLUDE_NEWER)
+            .assert()
+            .success();
+    }
+
+    Ok(())
+}
@@ -163,6 +163,33 @@ fn create_venv_defaults_to_cwd() {
     context.venv.assert(predicates::path::is_dir());
 }
 
+#[test]
+#[cfg(unix)]
+fn create_venv_copies() {
+    let context = VenvTestContext::new(&["3.12"]);
+    uv_snapshot!(context.filters(), context.venv_command()
+        .arg(context.venv.as_os_str())
+        .arg("--copies")
+        .arg("--python")
+        .arg("3.12")
+        .env("UV_NO_WRAP", "1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.12.[X] interpreter at: [PATH]
+    Creating virtualenv at: .venv
+    Activate with: source .venv/bin/activate
+    "###
+    );
+
+    // The interpreter should be a copy, rather than a symlink.
+    let executable = context.venv.child("bin").child("python");
+    executable.assert(predicates::path::is_file());
+    assert!(!executable.path().is_symlink());
+}
+
 #[test]
 fn seed() {
     let context = VenvTestContext::new(&["3.12"]);